@@ -0,0 +1,220 @@
+//! 热点路径基准测试
+//! 
+//! 运行：`cargo bench --bench hot_paths`
+
+use chrono::{Duration, Utc};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use std::sync::Arc;
+
+use everscan::indicators;
+use everscan::models::{AggregatedMetric, DataSource, MetricBuilder};
+use everscan::storage::MetricStore;
+use everscan::web::cache::DataCache;
+
+/// 历史序列长度
+const HISTORY_POINTS: usize = 10_000;
+
+/// 生成确定性的模拟价格序列（正弦波 + 趋势）
+fn price_history(points: usize) -> Vec<f64> {
+    (0..points)
+        .map(|i| 100.0 + (i as f64 * 0.05).sin() * 10.0 + i as f64 * 0.001)
+        .collect()
+}
+
+/// 生成模拟币种数据
+fn coin_payload(coin_id: &str, price: f64) -> serde_json::Value {
+    serde_json::json!({
+        "name": coin_id,
+        "symbol": coin_id.to_uppercase(),
+        "current_price": price,
+        "total_volume": 1_000_000.0,
+        "price_change_percentage_24h": 1.5,
+        "market_cap": 50_000_000.0,
+    })
+}
+
+/// 生成模拟指标数据
+fn sample_metrics(count: usize) -> Vec<AggregatedMetric> {
+    (0..count)
+        .map(|i| {
+            MetricBuilder::new(DataSource::EVERSCAN, format!("metric_{}", i % 20))
+                .value(i as f64 * 1.5)
+                .metadata(serde_json::json!({ "classification": "Neutral", "coin": "hype" }))
+                .build()
+                .expect("有效指标")
+        })
+        .collect()
+}
+
+/// 缓存并发读写
+fn bench_cache(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(4)
+        .enable_all()
+        .build()
+        .unwrap();
+    
+    let cache = Arc::new(DataCache::new());
+    let coins: Vec<String> = (0..50).map(|i| format!("coin{}", i)).collect();
+    runtime.block_on(async {
+        for (i, coin) in coins.iter().enumerate() {
+            cache.set_coin_data(coin, coin_payload(coin, i as f64 + 1.0)).await;
+        }
+    });
+    
+    let mut group = c.benchmark_group("cache");
+    
+    group.bench_function("get_market_data", |b| {
+        b.to_async(&runtime).iter(|| async {
+            black_box(cache.get_market_data("coin7").await)
+        })
+    });
+    
+    group.bench_function("metric_value", |b| {
+        b.to_async(&runtime).iter(|| async {
+            black_box(cache.metric_value("price:coin7").await)
+        })
+    });
+    
+    // 多个读者与一个写者同时访问
+    for readers in [1usize, 4, 16] {
+        group.throughput(Throughput::Elements((readers * 100) as u64));
+        group.bench_with_input(BenchmarkId::new("concurrent_reads_with_writer", readers), &readers, |b, &readers| {
+            b.to_async(&runtime).iter(|| {
+                let cache = cache.clone();
+                let coins = coins.clone();
+                async move {
+                    let mut handles = Vec::with_capacity(readers + 1);
+                    for r in 0..readers {
+                        let cache = cache.clone();
+                        let coins = coins.clone();
+                        handles.push(tokio::spawn(async move {
+                            for i in 0..100 {
+                                black_box(cache.get_market_data(&coins[(r + i) % coins.len()]).await);
+                            }
+                        }));
+                    }
+                    let writer = cache.clone();
+                    handles.push(tokio::spawn(async move {
+                        for i in 0..10 {
+                            let coin = &coins[i % coins.len()];
+                            writer.set_coin_data(coin, coin_payload(coin, i as f64)).await;
+                        }
+                    }));
+                    for handle in handles {
+                        handle.await.unwrap();
+                    }
+                }
+            })
+        });
+    }
+    
+    group.finish();
+}
+
+/// 技术指标计算
+fn bench_indicators(c: &mut Criterion) {
+    let prices = price_history(HISTORY_POINTS);
+    let mut group = c.benchmark_group("indicators_10k");
+    group.throughput(Throughput::Elements(HISTORY_POINTS as u64));
+    
+    group.bench_function("rsi_14", |b| b.iter(|| indicators::rsi(black_box(&prices), 14)));
+    group.bench_function("ema_50", |b| b.iter(|| indicators::ema(black_box(&prices), 50)));
+    group.bench_function("bollinger_20", |b| b.iter(|| indicators::bollinger_bands(black_box(&prices), 20, 2.0)));
+    
+    // 滚动计算整条序列的RSI（用于绘制历史曲线）
+    group.bench_function("rolling_rsi_14_last_500", |b| {
+        b.iter(|| {
+            (HISTORY_POINTS - 500..HISTORY_POINTS)
+                .filter_map(|end| indicators::rsi(&prices[..end], 14))
+                .count()
+        })
+    });
+    
+    group.finish();
+}
+
+/// 一个采集周期内每个币种一个数据点
+fn cycle_metrics(coins: usize) -> Vec<AggregatedMetric> {
+    (0..coins)
+        .map(|i| {
+            MetricBuilder::new(DataSource::EVERSCAN, format!("indicator_price_coin{}", i))
+                .value(i as f64 + 1.0)
+                .build()
+                .expect("有效指标")
+        })
+        .collect()
+}
+
+/// 指标存储批量写入
+fn bench_metric_store(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    let path = std::env::temp_dir().join(format!("everscan-bench-metrics-{}.jsonl", std::process::id()));
+    let coins = 500;
+    let cycle = cycle_metrics(coins);
+    let open_empty = || {
+        std::fs::write(&path, "").unwrap();
+        runtime.block_on(MetricStore::open(&path, 30)).unwrap()
+    };
+
+    let mut group = c.benchmark_group("metric_store");
+    group.throughput(Throughput::Elements(coins as u64));
+
+    // 整个周期一次写入（一次加锁、一次追加文件）
+    group.bench_function("save_batched_500_coins", |b| {
+        b.iter_batched(open_empty, |store| runtime.block_on(store.save_metrics(&cycle)).unwrap(), BatchSize::PerIteration)
+    });
+
+    // 对照：每个数据点单独写入
+    group.bench_function("save_per_metric_500_coins", |b| {
+        b.iter_batched(open_empty, |store| runtime.block_on(async {
+            for metric in &cycle {
+                store.save_metrics(std::slice::from_ref(metric)).await.unwrap();
+            }
+        }), BatchSize::PerIteration)
+    });
+
+    // 向已有10k点的序列导入1000个更早的历史数据点
+    let now = Utc::now();
+    let point = |minutes: i64| MetricBuilder::new(DataSource::IMPORT, "indicator_price_bitcoin")
+        .value(minutes as f64)
+        .timestamp(now - Duration::minutes(minutes))
+        .build()
+        .expect("有效指标");
+    let existing: Vec<AggregatedMetric> = (0..HISTORY_POINTS as i64).map(point).collect();
+    let backfill: Vec<AggregatedMetric> = (0..1_000).map(|i| point(i * 7 + 3)).collect();
+    group.throughput(Throughput::Elements(backfill.len() as u64));
+    group.bench_function("backfill_1000_into_10k", |b| {
+        b.iter_batched(
+            || {
+                let store = MetricStore::new(30);
+                runtime.block_on(store.save_metrics(&existing)).unwrap();
+                store
+            },
+            |store| runtime.block_on(store.save_metrics(&backfill)).unwrap(),
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+    std::fs::remove_file(&path).ok();
+}
+
+/// 指标序列化
+fn bench_serialization(c: &mut Criterion) {
+    let metrics = sample_metrics(1_000);
+    let json = serde_json::to_vec(&metrics).unwrap();
+    
+    let mut group = c.benchmark_group("metric_serialization");
+    group.throughput(Throughput::Elements(metrics.len() as u64));
+    
+    group.bench_function("serialize_1000", |b| b.iter(|| serde_json::to_vec(black_box(&metrics)).unwrap()));
+    group.bench_function("deserialize_1000", |b| {
+        b.iter(|| serde_json::from_slice::<Vec<AggregatedMetric>>(black_box(&json)).unwrap())
+    });
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_cache, bench_indicators, bench_metric_store, bench_serialization);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+//! 构建脚本
+//!
+//! 记录版本信息（git提交、编译时间、启用的特性），供 `/api/version` 使用；
+//! 启用 grpc 特性时生成 `proto/everscan.proto` 对应的服务端代码。
+//! 消息类型在 `src/grpc/mod.rs` 中手工定义，因此这里只描述服务，不需要protoc
+
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    emit_build_info();
+    #[cfg(feature = "grpc")]
+    generate_grpc_service();
+}
+
+/// 以编译期环境变量输出构建信息
+///
+/// * `EVERSCAN_GIT_COMMIT` - 当前提交（不在git仓库中时为空）
+/// * `EVERSCAN_BUILD_TIMESTAMP` - 编译时间（Unix秒，设置了 `SOURCE_DATE_EPOCH` 时使用该值以便复现构建）
+/// * `EVERSCAN_FEATURES` - 启用的特性（逗号分隔）
+fn emit_build_info() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    // 提交变化时重新运行（HEAD 指向的分支引用可能是松散文件，也可能在 packed-refs 中）
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    if let Ok(head) = std::fs::read_to_string(".git/HEAD") {
+        if let Some(reference) = head.trim().strip_prefix("ref: ") {
+            println!("cargo:rerun-if-changed=.git/{}", reference);
+            println!("cargo:rerun-if-changed=.git/packed-refs");
+        }
+    }
+
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=EVERSCAN_GIT_COMMIT={}", commit);
+
+    let timestamp = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<u64>().ok())
+        .unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0));
+    println!("cargo:rustc-env=EVERSCAN_BUILD_TIMESTAMP={}", timestamp);
+
+    // Cargo以 CARGO_FEATURE_<大写名称，- 替换为 _> 传入启用的特性；本项目的特性名只使用 -
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(|name| name.to_lowercase().replace('_', "-")))
+        .filter(|name| name != "default")
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=EVERSCAN_FEATURES={}", features.join(","));
+}
+
+#[cfg(feature = "grpc")]
+fn generate_grpc_service() {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    println!("cargo:rerun-if-changed=proto/everscan.proto");
+    let method = |name: &str, route: &str, input: &str, output: &str| {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("crate::grpc::{}", input))
+            .output_type(format!("crate::grpc::{}", output))
+            .codec_path("tonic::codec::ProstCodec")
+    };
+    let service = Service::builder()
+        .name("EverScan")
+        .package("everscan.v1")
+        .method(method("get_market_data", "GetMarketData", "MarketDataRequest", "MarketDataResponse").build())
+        .method(method("query_metrics", "QueryMetrics", "MetricQueryRequest", "MetricQueryResponse").build())
+        .method(method("stream_updates", "StreamUpdates", "StreamUpdatesRequest", "UpdateEvent").server_streaming().build())
+        .build();
+    Builder::new().build_client(false).compile(&[service]);
+}
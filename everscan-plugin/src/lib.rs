@@ -0,0 +1,267 @@
+//! EverScan 采集插件接口
+//!
+//! 第三方采集器实现 [`Task`]（需要时配合 [`ApiClient`]），用 [`MetricBuilder`] 构建指标，
+//! 再通过 [`register_plugin!`] 在编译期登记到全局注册表。主程序启用 `plugins` 特性并链接插件crate后，
+//! 按配置中的 `[plugins.<名称>]` 创建任务，与内置任务一样调度、写入指标存储和记录运行状态。
+//!
+//! ```ignore
+//! use everscan_plugin::{async_trait, register_plugin, Metric, MetricBuilder, Task, TaskContext};
+//!
+//! struct GasTask;
+//!
+//! #[async_trait]
+//! impl Task for GasTask {
+//!     fn id(&self) -> &str { "gas" }
+//!     fn name(&self) -> &str { "Gas价格采集" }
+//!     fn interval_seconds(&self) -> u64 { 300 }
+//!     async fn collect(&self, ctx: &TaskContext) -> anyhow::Result<Vec<Metric>> {
+//!         Ok(vec![MetricBuilder::new("gas_price_gwei").value(12.5).unit("gwei").timestamp(ctx.now).build()?])
+//!     }
+//! }
+//!
+//! register_plugin! {
+//!     name: "gas",
+//!     display_name: "Gas Tracker",
+//!     version: env!("CARGO_PKG_VERSION"),
+//!     create: |_config| Ok(vec![Box::new(GasTask) as Box<dyn Task>]),
+//! }
+//! ```
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+pub use async_trait::async_trait;
+#[doc(hidden)]
+pub use inventory;
+
+/// 插件接口版本
+///
+/// 接口有不兼容的变化时递增；主程序跳过版本不一致的插件
+pub const PLUGIN_API_VERSION: u32 = 1;
+
+/// 插件产出的指标
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    /// 指标名称
+    pub name: String,
+    /// 数值
+    pub value: f64,
+    /// 单位（可选）
+    pub unit: Option<String>,
+    /// 数据时间戳（None表示采集时间）
+    pub timestamp: Option<DateTime<Utc>>,
+    /// 标签（链、资产、交易所等维度）
+    pub labels: BTreeMap<String, String>,
+    /// 扩展元数据（可选）
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// 指标构建器
+#[derive(Debug, Clone)]
+pub struct MetricBuilder {
+    /// 指标名称
+    name: String,
+    /// 数值
+    value: Option<f64>,
+    /// 单位
+    unit: Option<String>,
+    /// 数据时间戳
+    timestamp: Option<DateTime<Utc>>,
+    /// 标签
+    labels: BTreeMap<String, String>,
+    /// 扩展元数据
+    metadata: Option<serde_json::Value>,
+}
+
+impl MetricBuilder {
+    /// 创建指标构建器
+    ///
+    /// # 参数
+    /// * `name` - 指标名称
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: None,
+            unit: None,
+            timestamp: None,
+            labels: BTreeMap::new(),
+            metadata: None,
+        }
+    }
+
+    /// 设置数值
+    pub fn value(mut self, value: f64) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// 设置单位
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    /// 设置数据时间戳
+    pub fn timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// 添加标签（键和值统一转为小写）
+    pub fn label(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.labels.insert(key.as_ref().to_lowercase(), value.as_ref().to_lowercase());
+        self
+    }
+
+    /// 设置扩展元数据
+    pub fn metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// 构建指标
+    ///
+    /// # 返回
+    /// * `Result<Metric>` - 名称为空、未设置数值或数值不是有限数时返回错误
+    pub fn build(self) -> Result<Metric> {
+        if self.name.trim().is_empty() {
+            return Err(anyhow!("指标名称不能为空"));
+        }
+        let value = self.value.ok_or_else(|| anyhow!("指标 {} 未设置数值", self.name))?;
+        if !value.is_finite() {
+            return Err(anyhow!("指标 {} 的数值无效: {}", self.name, value));
+        }
+        Ok(Metric {
+            name: self.name,
+            value,
+            unit: self.unit,
+            timestamp: self.timestamp,
+            labels: self.labels,
+            metadata: self.metadata,
+        })
+    }
+}
+
+/// 任务执行上下文
+#[derive(Debug, Clone)]
+pub struct TaskContext {
+    /// 本次执行的开始时间
+    pub now: DateTime<Utc>,
+}
+
+/// 插件采集任务
+///
+/// 任务ID在插件内唯一即可，主程序以 `<插件名>.<任务ID>` 登记
+#[async_trait]
+pub trait Task: Send + Sync {
+    /// 任务ID
+    fn id(&self) -> &str;
+
+    /// 任务名称
+    fn name(&self) -> &str;
+
+    /// 任务描述
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// 执行间隔（秒）
+    fn interval_seconds(&self) -> u64;
+
+    /// 采集指标
+    ///
+    /// # 参数
+    /// * `ctx` - 执行上下文
+    ///
+    /// # 返回
+    /// * `Result<Vec<Metric>>` - 采集到的指标或错误（错误会计入任务失败并按队列策略重试）
+    async fn collect(&self, ctx: &TaskContext) -> Result<Vec<Metric>>;
+}
+
+/// 插件的上游API客户端
+///
+/// 可选：实现后主程序的启动自检会调用 [`ApiClient::health_check`]
+#[async_trait]
+pub trait ApiClient: Send + Sync {
+    /// 客户端名称
+    fn name(&self) -> &str;
+
+    /// 检查上游API是否可用
+    async fn health_check(&self) -> Result<bool>;
+}
+
+/// 创建插件任务的函数，参数为配置中 `[plugins.<名称>]` 表转换的JSON
+pub type CreateTasks = fn(&serde_json::Value) -> Result<Vec<Box<dyn Task>>>;
+
+/// 创建插件API客户端的函数，参数与 [`CreateTasks`] 相同
+pub type CreateClients = fn(&serde_json::Value) -> Result<Vec<Box<dyn ApiClient>>>;
+
+/// 插件登记信息
+///
+/// 通过 [`register_plugin!`] 创建，不要直接构造
+pub struct Plugin {
+    /// 插件名称（即指标的数据源标识和配置表名，小写）
+    pub name: &'static str,
+    /// 显示名称
+    pub display_name: &'static str,
+    /// 插件版本
+    pub version: &'static str,
+    /// 编译插件时的接口版本
+    pub api_version: u32,
+    /// 创建任务
+    pub create: CreateTasks,
+    /// 创建API客户端（可选）
+    pub clients: Option<CreateClients>,
+}
+
+inventory::collect!(Plugin);
+
+/// 所有已链接的插件（按名称排序）
+pub fn plugins() -> Vec<&'static Plugin> {
+    let mut plugins: Vec<&'static Plugin> = inventory::iter::<Plugin>.into_iter().collect();
+    plugins.sort_by_key(|p| p.name);
+    plugins
+}
+
+/// 登记插件
+///
+/// `clients` 可省略
+#[macro_export]
+macro_rules! register_plugin {
+    (
+        name: $name:expr,
+        display_name: $display_name:expr,
+        version: $version:expr,
+        create: $create:expr
+        $(, clients: $clients:expr)?
+        $(,)?
+    ) => {
+        $crate::inventory::submit! {
+            $crate::Plugin {
+                name: $name,
+                display_name: $display_name,
+                version: $version,
+                api_version: $crate::PLUGIN_API_VERSION,
+                create: $create,
+                clients: $crate::register_plugin!(@clients $($clients)?),
+            }
+        }
+    };
+    (@clients) => { None };
+    (@clients $clients:expr) => { Some($clients) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_builder_validation() {
+        let metric = MetricBuilder::new("gas_price_gwei").value(12.5).unit("gwei").label("Chain", "Arbitrum").build().unwrap();
+        assert_eq!(metric.labels.get("chain").map(String::as_str), Some("arbitrum"));
+        assert!(MetricBuilder::new("gas_price_gwei").build().is_err());
+        assert!(MetricBuilder::new("gas_price_gwei").value(f64::NAN).build().is_err());
+        assert!(MetricBuilder::new(" ").value(1.0).build().is_err());
+    }
+}
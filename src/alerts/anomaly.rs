@@ -0,0 +1,289 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::alerts::{Alert, AlertManager};
+use crate::analytics::{anomaly_score, AnomalyMethod};
+use crate::config::AnomalyRuleConfig;
+use crate::storage::{JsonFileStore, MetricQuery, MetricStore};
+
+/// 异常事件最大保留条数
+const MAX_ANOMALY_EVENTS: usize = 500;
+
+/// 异常事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyEvent {
+    /// 唯一标识符
+    pub id: Uuid,
+    /// 指标名称
+    pub metric_name: String,
+    /// 检测方法
+    pub method: AnomalyMethod,
+    /// 异常评分
+    pub score: f64,
+    /// 触发阈值
+    pub threshold: f64,
+    /// 异常值
+    pub value: f64,
+    /// 基准值（均值或中位数）
+    pub baseline: f64,
+    /// 异常值的数据时间
+    pub timestamp: DateTime<Utc>,
+    /// 检测时间
+    pub detected_at: DateTime<Utc>,
+}
+
+/// 异常事件记录
+///
+/// 保留最近的异常事件，配置状态文件时持久化到磁盘
+pub struct AnomalyLog {
+    /// 事件（最新的在末尾）
+    events: RwLock<VecDeque<AnomalyEvent>>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<VecDeque<AnomalyEvent>>>,
+}
+
+impl AnomalyLog {
+    /// 创建内存中的异常事件记录（不持久化）
+    pub fn new() -> Self {
+        Self {
+            events: RwLock::new(VecDeque::new()),
+            store: None,
+        }
+    }
+
+    /// 从状态文件加载异常事件记录
+    ///
+    /// # 参数
+    /// * `path` - 状态文件路径
+    ///
+    /// # 返回
+    /// * `Result<Self>` - 异常事件记录或错误
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let events = store.load().await?;
+        Ok(Self {
+            events: RwLock::new(events),
+            store: Some(store),
+        })
+    }
+
+    /// 记录异常事件
+    pub async fn record(&self, event: AnomalyEvent) {
+        let mut events = self.events.write().await;
+        events.push_back(event);
+        while events.len() > MAX_ANOMALY_EVENTS {
+            events.pop_front();
+        }
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save(&events).await {
+                warn!("⚠️ 保存异常事件失败: {}", e);
+            }
+        }
+    }
+
+    /// 获取最近的异常事件
+    ///
+    /// # 参数
+    /// * `metric_name` - 只返回该指标的事件（可选）
+    /// * `limit` - 最多返回条数
+    ///
+    /// # 返回
+    /// * `Vec<AnomalyEvent>` - 事件列表（最新的在前）
+    pub async fn recent(&self, metric_name: Option<&str>, limit: usize) -> Vec<AnomalyEvent> {
+        self.events.read().await
+            .iter()
+            .rev()
+            .filter(|e| metric_name.is_none_or(|name| e.metric_name == name))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for AnomalyLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 指标异常检测
+///
+/// 定期检查配置的指标，用滚动窗口内的历史数据为最新数据点评分（Z分数或MAD），
+/// 超过阈值时记录异常事件并触发 `anomaly:<指标名称>` 告警
+pub struct AnomalyDetector {
+    /// 指标存储
+    metrics: Arc<MetricStore>,
+    /// 异常事件记录
+    log: Arc<AnomalyLog>,
+    /// 告警管理器
+    alert_manager: Arc<AlertManager>,
+    /// 检测规则
+    rules: Vec<AnomalyRuleConfig>,
+    /// 检查间隔
+    check_interval: Duration,
+    /// 每条规则已检测过的最新数据时间
+    checked: HashMap<usize, DateTime<Utc>>,
+}
+
+impl AnomalyDetector {
+    /// 创建新的异常检测
+    ///
+    /// # 参数
+    /// * `metrics` - 指标存储
+    /// * `log` - 异常事件记录
+    /// * `alert_manager` - 告警管理器
+    /// * `rules` - 检测规则
+    /// * `check_interval` - 检查间隔
+    pub fn new(
+        metrics: Arc<MetricStore>,
+        log: Arc<AnomalyLog>,
+        alert_manager: Arc<AlertManager>,
+        rules: Vec<AnomalyRuleConfig>,
+        check_interval: Duration,
+    ) -> Self {
+        Self {
+            metrics,
+            log,
+            alert_manager,
+            rules,
+            check_interval,
+            checked: HashMap::new(),
+        }
+    }
+
+    /// 持续运行检测循环
+    pub async fn run(mut self) {
+        info!("🔍 启动指标异常检测，共 {} 条规则", self.rules.len());
+
+        let mut interval = tokio::time::interval(self.check_interval);
+        loop {
+            interval.tick().await;
+            for (event, alert) in self.check(Utc::now()).await {
+                self.log.record(event).await;
+                self.alert_manager.fire(alert).await;
+            }
+        }
+    }
+
+    /// 检测各规则指标的最新数据点
+    ///
+    /// 每个数据点只检测一次
+    ///
+    /// # 参数
+    /// * `now` - 当前时间
+    ///
+    /// # 返回
+    /// * `Vec<(AnomalyEvent, Alert)>` - 检测到的异常及对应告警
+    pub async fn check(&mut self, now: DateTime<Utc>) -> Vec<(AnomalyEvent, Alert)> {
+        let mut anomalies = Vec::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            let query = MetricQuery { limit: Some(rule.window + 1), ..MetricQuery::default() };
+            let points: Vec<(DateTime<Utc>, f64)> = self.metrics.query(&rule.metric, &query).await
+                .into_iter()
+                .filter_map(|m| Some((m.timestamp, m.value.as_f64()?)))
+                .collect();
+            let Some((&(timestamp, value), history)) = points.split_last() else {
+                continue;
+            };
+            if self.checked.get(&index).is_some_and(|checked| *checked >= timestamp) {
+                continue;
+            }
+            self.checked.insert(index, timestamp);
+            if history.len() < rule.min_points.max(2) {
+                debug!("🔍 指标 {} 历史数据不足（{} 个），跳过异常检测", rule.metric, history.len());
+                continue;
+            }
+
+            let history: Vec<f64> = history.iter().map(|(_, v)| *v).collect();
+            let Some(score) = anomaly_score(rule.method, &history, value) else {
+                continue;
+            };
+            if score.score.abs() < rule.threshold {
+                continue;
+            }
+
+            let event = AnomalyEvent {
+                id: Uuid::new_v4(),
+                metric_name: rule.metric.clone(),
+                method: rule.method,
+                score: score.score,
+                threshold: rule.threshold,
+                value,
+                baseline: score.baseline,
+                timestamp,
+                detected_at: now,
+            };
+            let direction = if score.score > 0.0 { "异常升高" } else { "异常降低" };
+            let alert = Alert::new(
+                format!("anomaly:{}", rule.metric),
+                rule.severity,
+                format!("指标 {} {}", rule.metric, direction),
+                format!(
+                    "最新值 {:.4}，基准 {:.4}，{}评分 {:.2}（阈值 {}，窗口 {} 个数据点）",
+                    value, score.baseline, rule.method, score.score, rule.threshold, history.len(),
+                ),
+            ).metadata(serde_json::json!({ "anomaly": event }));
+            anomalies.push((event, alert));
+        }
+        anomalies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::AlertSeverity;
+    use crate::models::{DataSource, MetricBuilder};
+    use chrono::Duration as ChronoDuration;
+
+    #[tokio::test]
+    async fn test_detects_spike_once() {
+        let metrics = Arc::new(MetricStore::new(30));
+        let now = Utc::now();
+        let volumes = [100.0, 104.0, 97.0, 101.0, 99.0, 103.0, 98.0, 100.0, 102.0, 101.0, 450.0];
+        let points: Vec<_> = volumes.iter().enumerate()
+            .map(|(i, v)| {
+                MetricBuilder::new(DataSource::EVERSCAN, "volume_24h_btc")
+                    .value(*v)
+                    .timestamp(now - ChronoDuration::hours((volumes.len() - i) as i64))
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        metrics.save_metrics(&points).await.unwrap();
+
+        let rule = AnomalyRuleConfig {
+            metric: "volume_24h_btc".to_string(),
+            method: AnomalyMethod::Mad,
+            threshold: 3.5,
+            window: 48,
+            min_points: 10,
+            severity: AlertSeverity::Warning,
+        };
+        let mut detector = AnomalyDetector::new(
+            metrics,
+            Arc::new(AnomalyLog::new()),
+            Arc::new(AlertManager::new()),
+            vec![rule],
+            Duration::from_secs(60),
+        );
+
+        let anomalies = detector.check(now).await;
+        assert_eq!(anomalies.len(), 1);
+        let (event, alert) = &anomalies[0];
+        assert_eq!(event.value, 450.0);
+        assert!(event.score > 3.5);
+        assert_eq!(alert.rule, "anomaly:volume_24h_btc");
+
+        // 同一数据点不会重复检测
+        assert!(detector.check(now).await.is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, debug};
+
+use crate::alerts::{Alert, AlertManager};
+use crate::calendar::{EconomicCalendar, EconomicEvent, EventImportance};
+use crate::config::CalendarReminderConfig;
+
+/// 宏观事件提醒
+/// 
+/// 定期检查经济日历，在事件发布前按配置的提前量触发告警（如"CPI发布前1小时提醒"）
+pub struct EventReminder {
+    /// 经济日历
+    calendar: Arc<EconomicCalendar>,
+    /// 告警管理器
+    alert_manager: Arc<AlertManager>,
+    /// 提醒配置
+    reminders: Vec<CalendarReminderConfig>,
+    /// 检查间隔
+    check_interval: Duration,
+    /// 已提醒的（事件ID，提醒序号）
+    reminded: HashSet<(String, usize)>,
+}
+
+impl EventReminder {
+    /// 创建新的事件提醒
+    /// 
+    /// # 参数
+    /// * `calendar` - 经济日历
+    /// * `alert_manager` - 告警管理器
+    /// * `reminders` - 提醒配置
+    /// * `check_interval` - 检查间隔
+    pub fn new(
+        calendar: Arc<EconomicCalendar>,
+        alert_manager: Arc<AlertManager>,
+        reminders: Vec<CalendarReminderConfig>,
+        check_interval: Duration,
+    ) -> Self {
+        Self {
+            calendar,
+            alert_manager,
+            reminders,
+            check_interval,
+            reminded: HashSet::new(),
+        }
+    }
+
+    /// 持续运行提醒循环
+    pub async fn run(mut self) {
+        info!("🗓️ 启动宏观事件提醒，共 {} 条提醒规则", self.reminders.len());
+        
+        let mut interval = tokio::time::interval(self.check_interval);
+        loop {
+            interval.tick().await;
+            for alert in self.check(Utc::now()).await {
+                self.alert_manager.fire(alert).await;
+            }
+        }
+    }
+
+    /// 在指定时间点检查需要发送的提醒
+    /// 
+    /// 事件进入 `发布时间 - 提前量` 到发布时间之间的窗口时提醒一次
+    /// 
+    /// # 参数
+    /// * `now` - 当前时间
+    /// 
+    /// # 返回
+    /// * `Vec<Alert>` - 需要发送的提醒
+    pub async fn check(&mut self, now: DateTime<Utc>) -> Vec<Alert> {
+        let Some(max_lead) = self.reminders.iter().map(|r| r.minutes_before).max() else {
+            return Vec::new();
+        };
+        let upcoming = self.calendar
+            .events_between(now, now + ChronoDuration::minutes(max_lead as i64), EventImportance::Low)
+            .await;
+        
+        let mut alerts = Vec::new();
+        for event in &upcoming {
+            for (index, reminder) in self.reminders.iter().enumerate() {
+                let lead = ChronoDuration::minutes(reminder.minutes_before as i64);
+                if !Self::matches(reminder, event) || event.time - lead > now {
+                    continue;
+                }
+                if !self.reminded.insert((event.id.clone(), index)) {
+                    continue;
+                }
+                
+                let minutes = (event.time - now).num_minutes();
+                debug!("🗓️ 事件 {} 将在 {} 分钟后发布", event.title, minutes);
+                alerts.push(Alert::new(
+                    format!("calendar:{}:{}", event.id, index),
+                    reminder.severity,
+                    format!("{} {} 将在 {} 分钟后发布", event.country, event.title, minutes),
+                    format!(
+                        "发布时间 {}，重要性 {}，预期 {}，前值 {}",
+                        event.time.to_rfc3339(),
+                        event.importance,
+                        event.forecast.as_deref().unwrap_or("-"),
+                        event.previous.as_deref().unwrap_or("-"),
+                    ),
+                ).metadata(serde_json::json!({
+                    "event": event,
+                    "minutes_before": reminder.minutes_before,
+                })));
+            }
+        }
+        
+        // 已发布的事件不会再进入窗口，清理对应记录
+        let upcoming_ids: HashSet<&str> = upcoming.iter().map(|e| e.id.as_str()).collect();
+        self.reminded.retain(|(id, _)| upcoming_ids.contains(id.as_str()));
+        
+        alerts
+    }
+
+    /// 事件是否符合提醒条件
+    fn matches(reminder: &CalendarReminderConfig, event: &EconomicEvent) -> bool {
+        event.importance >= reminder.min_importance
+            && reminder.keyword.as_ref()
+                .is_none_or(|keyword| event.title.to_lowercase().contains(&keyword.to_lowercase()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::AlertSeverity;
+
+    #[tokio::test]
+    async fn test_reminds_once_within_lead_time() {
+        let now = Utc::now();
+        let calendar = Arc::new(EconomicCalendar::new());
+        calendar.merge(vec![
+            EconomicEvent::new("CPI m/m", "USD", now + ChronoDuration::minutes(90), EventImportance::High),
+            EconomicEvent::new("Crude Oil Inventories", "USD", now + ChronoDuration::minutes(90), EventImportance::Medium),
+        ], now).await.unwrap();
+
+        let cpi = CalendarReminderConfig {
+            keyword: Some("cpi".to_string()),
+            min_importance: EventImportance::Medium,
+            minutes_before: 60,
+            severity: AlertSeverity::Critical,
+        };
+        let mut reminder = EventReminder::new(
+            calendar,
+            Arc::new(AlertManager::new()),
+            vec![cpi],
+            Duration::from_secs(60),
+        );
+
+        assert!(reminder.check(now).await.is_empty());
+        let alerts = reminder.check(now + ChronoDuration::minutes(31)).await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].severity, AlertSeverity::Critical);
+        assert!(reminder.check(now + ChronoDuration::minutes(40)).await.is_empty());
+    }
+}
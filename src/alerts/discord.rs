@@ -0,0 +1,65 @@
+use anyhow::{Result, Context};
+use async_trait::async_trait;
+use std::time::Duration;
+
+use crate::alerts::{Alert, AlertSeverity, Notifier};
+use crate::clients::HttpClientBuilder;
+use crate::config::DiscordConfig;
+
+/// Discord通知渠道
+/// 
+/// 通过Discord频道Webhook发送告警消息
+pub struct DiscordNotifier {
+    /// 渠道名称
+    name: String,
+    /// HTTP客户端
+    client: reqwest::Client,
+    /// Discord Webhook URL
+    webhook_url: String,
+}
+
+impl DiscordNotifier {
+    /// 根据配置创建Discord通知渠道
+    pub fn from_config(config: &DiscordConfig) -> Result<Self> {
+        let client = HttpClientBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .user_agent("EverScan-Discord/1.0")
+            .build()?;
+        
+        Ok(Self {
+            name: format!("discord:{}", config.name),
+            client,
+            webhook_url: config.webhook_url.expose().to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        let icon = match alert.severity {
+            AlertSeverity::Info => "ℹ️",
+            AlertSeverity::Warning => "⚠️",
+            AlertSeverity::Critical => "🚨",
+        };
+        let content = format!("{} **[{}] {}**\n{}", icon, alert.severity, alert.title, alert.message);
+        
+        let response = self.client
+            .post(&self.webhook_url)
+            .json(&serde_json::json!({ "content": content }))
+            .send()
+            .await
+            .context("发送Discord消息失败")?;
+        
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Discord Webhook请求失败: HTTP {} - {}", status, text));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+use crate::alerts::{Alert, AlertManager};
+use crate::config::InactiveCoinsConfig;
+use crate::web::cache::DataCache;
+
+/// 停止更新币种检测
+///
+/// 定期检查缓存中被采集任务标记为停止更新（见 `CachedMarketData::inactive`）的币种，
+/// 进入该状态时触发一次 `inactive_coin:<币种ID>` 告警，避免关注列表中的币种悄悄失效
+pub struct InactiveCoinMonitor {
+    /// 数据缓存
+    cache: Arc<DataCache>,
+    /// 告警管理器
+    alert_manager: Arc<AlertManager>,
+    /// 检测配置
+    config: InactiveCoinsConfig,
+    /// 已告警的停止更新币种
+    flagged: HashSet<String>,
+}
+
+impl InactiveCoinMonitor {
+    /// 创建新的停止更新币种检测
+    ///
+    /// # 参数
+    /// * `cache` - 数据缓存
+    /// * `alert_manager` - 告警管理器
+    /// * `config` - 检测配置
+    pub fn new(cache: Arc<DataCache>, alert_manager: Arc<AlertManager>, config: InactiveCoinsConfig) -> Self {
+        Self {
+            cache,
+            alert_manager,
+            config,
+            flagged: HashSet::new(),
+        }
+    }
+
+    /// 持续运行检测循环
+    pub async fn run(mut self) {
+        info!("🪦 启动停止更新币种检测，报价超过 {} 小时未更新视为停止更新", self.config.max_quote_age_hours);
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.check_interval_seconds.max(1)));
+        loop {
+            interval.tick().await;
+            for alert in self.check().await {
+                self.alert_manager.fire(alert).await;
+            }
+        }
+    }
+
+    /// 检查各币种的停止更新状态
+    ///
+    /// 每次进入停止更新状态只告警一次，恢复更新后再次停止会重新告警
+    ///
+    /// # 返回
+    /// * `Vec<Alert>` - 新停止更新的币种告警
+    pub async fn check(&mut self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        let mut inactive = HashSet::new();
+        for data in self.cache.get_all_market_data().await {
+            let Some(status) = data.inactive else {
+                continue;
+            };
+            inactive.insert(data.coin_id.clone());
+            if !self.flagged.insert(data.coin_id.clone()) {
+                continue;
+            }
+            alerts.push(Alert::new(
+                format!("inactive_coin:{}", data.coin_id),
+                self.config.severity,
+                format!("币种 {} 已停止更新", data.symbol),
+                format!(
+                    "{}（{}）自 {} 起停止更新: {}，已从市场广度计算中排除，请检查关注列表",
+                    data.name, data.symbol, status.since.format("%Y-%m-%d %H:%M UTC"), status.reason,
+                ),
+            ).metadata(serde_json::json!({ "coin_id": data.coin_id, "inactive": status })));
+        }
+        self.flagged.retain(|coin_id| inactive.contains(coin_id));
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_alerts_once_per_inactive_period() {
+        let cache = Arc::new(DataCache::new());
+        cache.set_coin_data("luna", serde_json::json!({
+            "current_price": 0.0001,
+            "symbol": "LUNA",
+            "name": "Terra",
+            "data_source": "CoinMarketCap"
+        })).await;
+        let mut monitor = InactiveCoinMonitor::new(cache.clone(), Arc::new(AlertManager::new()), InactiveCoinsConfig::default());
+        assert!(monitor.check().await.is_empty());
+
+        cache.set_coin_inactive("luna", Some("CoinMarketCap未收录 LUNA 币种".to_string())).await;
+        let alerts = monitor.check().await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule, "inactive_coin:luna");
+
+        // 持续停止更新时不重复告警，刷新数据不会清除标记
+        cache.set_coin_data("luna", serde_json::json!({
+            "current_price": 0.0001,
+            "symbol": "LUNA",
+            "name": "Terra",
+            "data_source": "CoinMarketCap"
+        })).await;
+        assert!(cache.get_market_data("luna").await.unwrap().inactive.is_some());
+        assert!(monitor.check().await.is_empty());
+
+        // 恢复后再次停止更新会重新告警
+        cache.set_coin_inactive("luna", None).await;
+        assert!(monitor.check().await.is_empty());
+        cache.set_coin_inactive("luna", Some("报价已 72 小时未更新".to_string())).await;
+        assert_eq!(monitor.check().await.len(), 1);
+    }
+}
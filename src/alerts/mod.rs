@@ -0,0 +1,297 @@
+pub mod anomaly;
+pub mod calendar;
+pub mod discord;
+pub mod inactive;
+pub mod policy;
+pub mod price_divergence;
+pub mod rules;
+pub mod script;
+pub mod staleness;
+pub mod telegram;
+pub mod watches;
+pub mod webhook;
+
+pub use anomaly::*;
+pub use calendar::*;
+pub use discord::*;
+pub use inactive::*;
+pub use policy::*;
+pub use price_divergence::*;
+pub use rules::*;
+pub use script::*;
+pub use staleness::*;
+pub use telegram::*;
+pub use watches::*;
+pub use webhook::*;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn, error, debug};
+use uuid::Uuid;
+
+use crate::storage::JsonFileStore;
+
+/// 告警历史最大保留条数
+const MAX_ALERT_HISTORY: usize = 500;
+
+/// 告警级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    /// 提示
+    Info,
+    /// 警告
+    Warning,
+    /// 严重
+    Critical,
+}
+
+impl AlertSeverity {
+    /// 升级一个级别（严重级别保持不变）
+    pub fn escalate(self) -> Self {
+        match self {
+            AlertSeverity::Info => AlertSeverity::Warning,
+            AlertSeverity::Warning | AlertSeverity::Critical => AlertSeverity::Critical,
+        }
+    }
+}
+
+impl std::fmt::Display for AlertSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlertSeverity::Info => write!(f, "提示"),
+            AlertSeverity::Warning => write!(f, "警告"),
+            AlertSeverity::Critical => write!(f, "严重"),
+        }
+    }
+}
+
+/// 告警事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    /// 唯一标识符
+    pub id: Uuid,
+    /// 触发告警的规则标识（如"staleness:fear_greed"）
+    pub rule: String,
+    /// 告警级别
+    pub severity: AlertSeverity,
+    /// 告警标题
+    pub title: String,
+    /// 告警详情
+    pub message: String,
+    /// 触发时间
+    pub created_at: DateTime<Utc>,
+    /// 扩展元数据（可选）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+impl Alert {
+    /// 创建新的告警
+    pub fn new(
+        rule: impl Into<String>,
+        severity: AlertSeverity,
+        title: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            rule: rule.into(),
+            severity,
+            title: title.into(),
+            message: message.into(),
+            created_at: Utc::now(),
+            metadata: None,
+        }
+    }
+
+    /// 设置元数据
+    pub fn metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+}
+
+/// 通知渠道特征
+/// 
+/// 所有告警通知渠道（日志、Webhook等）都需要实现这个特征
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// 获取渠道名称
+    fn name(&self) -> &str;
+
+    /// 发送告警通知
+    /// 
+    /// # 参数
+    /// * `alert` - 告警事件
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 成功或错误
+    async fn notify(&self, alert: &Alert) -> Result<()>;
+}
+
+/// 日志通知渠道
+/// 
+/// 将告警写入日志，作为默认渠道始终启用
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    fn name(&self) -> &str {
+        "log"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        match alert.severity {
+            AlertSeverity::Info => info!("🔔 [{}] {}: {}", alert.severity, alert.title, alert.message),
+            AlertSeverity::Warning => warn!("🔔 [{}] {}: {}", alert.severity, alert.title, alert.message),
+            AlertSeverity::Critical => error!("🚨 [{}] {}: {}", alert.severity, alert.title, alert.message),
+        }
+        Ok(())
+    }
+}
+
+/// 通知路由
+/// 
+/// 只有级别不低于 `min_severity` 的告警才会发送到该渠道
+struct NotifierRoute {
+    /// 通知渠道
+    notifier: Arc<dyn Notifier>,
+    /// 最低告警级别
+    min_severity: AlertSeverity,
+}
+
+/// 告警管理器
+/// 
+/// 负责告警去重、冷却和升级，按级别路由到通知渠道并保留最近的告警历史
+pub struct AlertManager {
+    /// 通知路由列表
+    routes: Vec<NotifierRoute>,
+    /// 告警策略
+    policy: AlertPolicy,
+    /// 告警去重状态
+    state: RwLock<AlertState>,
+    /// 告警状态持久化存储（可选）
+    state_store: Option<JsonFileStore<AlertState>>,
+    /// 告警历史（最新的在末尾）
+    history: RwLock<VecDeque<Alert>>,
+}
+
+impl AlertManager {
+    /// 创建新的告警管理器（默认包含日志渠道，使用默认策略，不持久化状态）
+    pub fn new() -> Self {
+        Self {
+            routes: vec![NotifierRoute {
+                notifier: Arc::new(LogNotifier),
+                min_severity: AlertSeverity::Info,
+            }],
+            policy: AlertPolicy::default(),
+            state: RwLock::new(AlertState::default()),
+            state_store: None,
+            history: RwLock::new(VecDeque::new()),
+        }
+    }
+
+    /// 设置告警策略
+    pub fn with_policy(mut self, policy: AlertPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// 启用状态持久化，并加载已有状态
+    /// 
+    /// # 参数
+    /// * `path` - 状态文件路径
+    /// 
+    /// # 返回
+    /// * `Result<Self>` - 告警管理器或错误
+    pub async fn with_state_file(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        self.state = RwLock::new(store.load().await?);
+        self.state_store = Some(store);
+        Ok(self)
+    }
+
+    /// 添加通知渠道
+    /// 
+    /// # 参数
+    /// * `notifier` - 通知渠道
+    /// * `min_severity` - 该渠道接收的最低告警级别
+    pub fn add_notifier(&mut self, notifier: Arc<dyn Notifier>, min_severity: AlertSeverity) {
+        info!("📣 已注册告警通知渠道: {}（最低级别: {}）", notifier.name(), min_severity);
+        self.routes.push(NotifierRoute { notifier, min_severity });
+    }
+
+    /// 触发告警
+    /// 
+    /// 经过去重/冷却/升级策略后，按级别同时发送到对应渠道；
+    /// 单个渠道发送失败或响应缓慢不会影响其他渠道
+    /// 
+    /// # 参数
+    /// * `alert` - 告警事件
+    /// 
+    /// # 返回
+    /// * `bool` - 告警是否被发送（false表示在冷却期内被抑制）
+    pub async fn fire(&self, alert: Alert) -> bool {
+        let decision = {
+            let mut state = self.state.write().await;
+            let decision = self.policy.evaluate(&mut state, alert.clone(), Utc::now());
+            self.persist_state(&state).await;
+            decision
+        };
+        
+        let Some(alert) = decision else {
+            debug!("🔕 告警 {} 处于冷却期，已抑制", alert.rule);
+            return false;
+        };
+        
+        let routes: Vec<_> = self.routes.iter().filter(|r| alert.severity >= r.min_severity).collect();
+        let results = join_all(routes.iter().map(|route| route.notifier.notify(&alert))).await;
+        for (route, result) in routes.iter().zip(results) {
+            if let Err(e) = result {
+                error!("❌ 告警通知渠道 {} 发送失败: {}", route.notifier.name(), e);
+            }
+        }
+
+        let mut history = self.history.write().await;
+        history.push_back(alert);
+        while history.len() > MAX_ALERT_HISTORY {
+            history.pop_front();
+        }
+        true
+    }
+
+    /// 保存告警状态
+    async fn persist_state(&self, state: &AlertState) {
+        if let Some(store) = &self.state_store {
+            if let Err(e) = store.save(state).await {
+                warn!("⚠️ 保存告警状态失败: {}", e);
+            }
+        }
+    }
+
+    /// 获取最近的告警历史
+    /// 
+    /// # 参数
+    /// * `limit` - 最多返回条数
+    /// 
+    /// # 返回
+    /// * `Vec<Alert>` - 告警列表（最新的在前）
+    pub async fn recent_alerts(&self, limit: usize) -> Vec<Alert> {
+        let history = self.history.read().await;
+        history.iter().rev().take(limit).cloned().collect()
+    }
+}
+
+impl Default for AlertManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
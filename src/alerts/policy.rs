@@ -0,0 +1,158 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::alerts::{Alert, AlertSeverity};
+use crate::config::AlertsConfig;
+
+/// 告警策略
+/// 
+/// 定义去重冷却窗口和升级规则：
+/// 同一规则、同一级别的告警在冷却窗口内只发送一次，
+/// 窗口内被抑制的次数达到阈值时升级一个级别再发送一次
+#[derive(Debug, Clone)]
+pub struct AlertPolicy {
+    /// 默认冷却时间（秒）
+    pub default_cooldown_seconds: u64,
+    /// 按规则前缀覆盖的冷却时间（秒），最长前缀优先
+    pub rule_cooldowns: HashMap<String, u64>,
+    /// 冷却窗口内重复触发多少次后升级（0表示不升级）
+    pub escalation_threshold: u32,
+}
+
+/// 单个告警键的状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertRuleState {
+    /// 最后一次发送时间
+    pub last_fired_at: Option<DateTime<Utc>>,
+    /// 当前冷却窗口内被抑制的次数
+    pub suppressed_count: u32,
+    /// 当前冷却窗口内是否已升级
+    pub escalated: bool,
+    /// 累计触发次数
+    pub total_triggers: u64,
+}
+
+/// 告警状态（持久化到磁盘，重启后不会重复发送冷却中的告警）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertState {
+    /// key: `{rule}#{severity}`, value: 状态
+    pub rules: HashMap<String, AlertRuleState>,
+}
+
+impl AlertPolicy {
+    /// 从配置创建告警策略
+    pub fn from_config(config: &AlertsConfig) -> Self {
+        Self {
+            default_cooldown_seconds: config.default_cooldown_seconds,
+            rule_cooldowns: config.rule_cooldowns.clone(),
+            escalation_threshold: config.escalation_threshold,
+        }
+    }
+
+    /// 获取规则的冷却时间
+    /// 
+    /// # 参数
+    /// * `rule` - 规则标识
+    /// 
+    /// # 返回
+    /// * `ChronoDuration` - 冷却时间
+    pub fn cooldown_for(&self, rule: &str) -> ChronoDuration {
+        let seconds = self.rule_cooldowns.iter()
+            .filter(|(prefix, _)| rule.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, seconds)| *seconds)
+            .unwrap_or(self.default_cooldown_seconds);
+        ChronoDuration::seconds(seconds as i64)
+    }
+
+    /// 评估告警是否需要发送
+    /// 
+    /// # 参数
+    /// * `state` - 告警状态
+    /// * `alert` - 待发送的告警
+    /// * `now` - 当前时间
+    /// 
+    /// # 返回
+    /// * `Option<Alert>` - 需要发送的告警（可能已升级），None表示被抑制
+    pub fn evaluate(&self, state: &mut AlertState, alert: Alert, now: DateTime<Utc>) -> Option<Alert> {
+        let key = format!("{}#{:?}", alert.rule, alert.severity);
+        let cooldown = self.cooldown_for(&alert.rule);
+        let entry = state.rules.entry(key).or_default();
+        entry.total_triggers += 1;
+        
+        let in_cooldown = entry.last_fired_at
+            .map(|last| now - last < cooldown)
+            .unwrap_or(false);
+        
+        if !in_cooldown {
+            entry.last_fired_at = Some(now);
+            entry.suppressed_count = 0;
+            entry.escalated = false;
+            return Some(alert);
+        }
+        
+        entry.suppressed_count += 1;
+        
+        let should_escalate = self.escalation_threshold > 0
+            && entry.suppressed_count >= self.escalation_threshold
+            && !entry.escalated
+            && alert.severity < AlertSeverity::Critical;
+        
+        if !should_escalate {
+            return None;
+        }
+        
+        entry.escalated = true;
+        let suppressed = entry.suppressed_count;
+        let mut escalated = alert;
+        escalated.severity = escalated.severity.escalate();
+        escalated.title = format!("[升级] {}", escalated.title);
+        escalated.message = format!("{}（冷却窗口内已重复触发 {} 次）", escalated.message, suppressed);
+        Some(escalated)
+    }
+}
+
+impl Default for AlertPolicy {
+    fn default() -> Self {
+        Self::from_config(&AlertsConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> AlertPolicy {
+        AlertPolicy {
+            default_cooldown_seconds: 600,
+            rule_cooldowns: HashMap::from([("staleness".to_string(), 60)]),
+            escalation_threshold: 2,
+        }
+    }
+
+    #[test]
+    fn test_cooldown_prefix_match() {
+        let policy = policy();
+        assert_eq!(policy.cooldown_for("staleness:fear_greed").num_seconds(), 60);
+        assert_eq!(policy.cooldown_for("price:btc").num_seconds(), 600);
+    }
+
+    #[test]
+    fn test_dedup_and_escalation() {
+        let policy = policy();
+        let mut state = AlertState::default();
+        let now = Utc::now();
+        let alert = || Alert::new("price:btc", AlertSeverity::Warning, "BTC", "crossed");
+
+        assert!(policy.evaluate(&mut state, alert(), now).is_some());
+        assert!(policy.evaluate(&mut state, alert(), now + ChronoDuration::seconds(10)).is_none());
+
+        let escalated = policy.evaluate(&mut state, alert(), now + ChronoDuration::seconds(20)).unwrap();
+        assert_eq!(escalated.severity, AlertSeverity::Critical);
+        assert!(policy.evaluate(&mut state, alert(), now + ChronoDuration::seconds(30)).is_none());
+
+        let after_cooldown = policy.evaluate(&mut state, alert(), now + ChronoDuration::seconds(700)).unwrap();
+        assert_eq!(after_cooldown.severity, AlertSeverity::Warning);
+    }
+}
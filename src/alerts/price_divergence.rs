@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::alerts::{Alert, AlertManager};
+use crate::clients::{CoinGeckoClient, ExchangeTickerClient};
+use crate::config::PriceDivergenceConfig;
+use crate::web::cache::DataCache;
+
+/// 多数据源价格偏离检测
+///
+/// 定期从CoinGecko和交易所获取参考价格写入缓存，与主数据源价格比较；
+/// 偏离超过阈值时在API中标记该币种（见 `CachedMarketData::price_divergence`），
+/// 并在进入偏离状态时触发 `price_divergence:<币种ID>` 告警
+pub struct PriceDivergenceMonitor {
+    /// 数据缓存
+    cache: Arc<DataCache>,
+    /// CoinGecko客户端
+    coingecko: CoinGeckoClient,
+    /// 交易所行情客户端
+    exchange: ExchangeTickerClient,
+    /// 告警管理器
+    alert_manager: Arc<AlertManager>,
+    /// 检测配置
+    config: PriceDivergenceConfig,
+    /// 当前处于偏离状态的币种
+    flagged: HashSet<String>,
+}
+
+impl PriceDivergenceMonitor {
+    /// 创建新的价格偏离检测
+    ///
+    /// # 参数
+    /// * `cache` - 数据缓存
+    /// * `coingecko` - CoinGecko客户端
+    /// * `exchange` - 交易所行情客户端
+    /// * `alert_manager` - 告警管理器
+    /// * `config` - 检测配置
+    pub fn new(
+        cache: Arc<DataCache>,
+        coingecko: CoinGeckoClient,
+        exchange: ExchangeTickerClient,
+        alert_manager: Arc<AlertManager>,
+        config: PriceDivergenceConfig,
+    ) -> Self {
+        Self {
+            cache,
+            coingecko,
+            exchange,
+            alert_manager,
+            config,
+            flagged: HashSet::new(),
+        }
+    }
+
+    /// 持续运行检测循环
+    pub async fn run(mut self) {
+        info!("⚖️ 启动多数据源价格偏离检测，共 {} 个币种", self.config.coins.len());
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.check_interval_seconds.max(1)));
+        loop {
+            interval.tick().await;
+            self.refresh_quotes().await;
+            for alert in self.check().await {
+                self.alert_manager.fire(alert).await;
+            }
+        }
+    }
+
+    /// 获取各币种的参考价格并写入缓存
+    ///
+    /// 单个数据源失败时记录警告，不影响其他数据源
+    async fn refresh_quotes(&self) {
+        for coin in &self.config.coins {
+            if let Some(id) = &coin.coingecko_id {
+                match self.coingecko.get_usd_price(id).await {
+                    Ok(price) => {
+                        self.cache.record_price_quote(&coin.coin_id, CoinGeckoClient::SOURCE.display_name, price).await;
+                    }
+                    Err(e) => warn!("⚠️ 获取 {} 的CoinGecko参考价格失败: {}", coin.coin_id, e),
+                }
+            }
+            if let Some(symbol) = &coin.exchange_symbol {
+                match self.exchange.get_price(symbol).await {
+                    Ok(price) => {
+                        self.cache.record_price_quote(&coin.coin_id, ExchangeTickerClient::SOURCE.display_name, price).await;
+                    }
+                    Err(e) => warn!("⚠️ 获取 {} 的交易所参考价格失败: {}", coin.coin_id, e),
+                }
+            }
+        }
+    }
+
+    /// 检查各币种的价格偏离
+    ///
+    /// 只在币种由正常变为偏离时告警，恢复后再次偏离会重新告警
+    ///
+    /// # 返回
+    /// * `Vec<Alert>` - 新进入偏离状态的币种告警
+    pub async fn check(&mut self) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for coin in &self.config.coins {
+            let divergence = self.cache.get_market_data(&coin.coin_id).await
+                .and_then(|data| data.price_divergence);
+            let Some(divergence) = divergence.filter(|d| d.flagged) else {
+                if self.flagged.remove(&coin.coin_id) {
+                    info!("✅ 币种 {} 各数据源价格已恢复一致", coin.coin_id);
+                }
+                continue;
+            };
+            if !self.flagged.insert(coin.coin_id.clone()) {
+                debug!("⚖️ 币种 {} 仍处于价格偏离状态", coin.coin_id);
+                continue;
+            }
+
+            alerts.push(Alert::new(
+                format!("price_divergence:{}", coin.coin_id),
+                self.config.severity,
+                format!("币种 {} 多数据源价格偏离", coin.coin_id),
+                format!(
+                    "{} 报价 {:.6}，{} 报价 {:.6}，偏离 {:.2}%（阈值 {}%），可能存在过期上市信息或流动性不足导致的定价错误",
+                    divergence.lowest.source, divergence.lowest.price,
+                    divergence.highest.source, divergence.highest.price,
+                    divergence.divergence_pct, divergence.threshold_pct,
+                ),
+            ).metadata(serde_json::json!({ "coin_id": coin.coin_id, "divergence": divergence })));
+        }
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PriceReferenceConfig;
+
+    #[tokio::test]
+    async fn test_alerts_once_per_divergence() {
+        let cache = Arc::new(DataCache::new().with_divergence_threshold(3.0));
+        cache.set_coin_data("hype", serde_json::json!({
+            "current_price": 40.0,
+            "symbol": "HYPE",
+            "name": "Hyperliquid",
+            "data_source": "CoinMarketCap"
+        })).await;
+        let config = PriceDivergenceConfig {
+            coins: vec![PriceReferenceConfig {
+                coin_id: "hype".to_string(),
+                coingecko_id: None,
+                exchange_symbol: None,
+            }],
+            ..PriceDivergenceConfig::default()
+        };
+        let mut monitor = PriceDivergenceMonitor::new(
+            cache.clone(),
+            CoinGeckoClient::new(Duration::from_secs(5)).unwrap(),
+            ExchangeTickerClient::new(Duration::from_secs(5)).unwrap(),
+            Arc::new(AlertManager::new()),
+            config,
+        );
+
+        // 只有一个数据源时无法比较
+        assert!(monitor.check().await.is_empty());
+
+        cache.record_price_quote("hype", "CoinGecko", 40.4).await;
+        assert!(monitor.check().await.is_empty());
+
+        cache.record_price_quote("hype", "Binance", 44.0).await;
+        let alerts = monitor.check().await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule, "price_divergence:hype");
+        let divergence = cache.get_market_data("hype").await.unwrap().price_divergence.unwrap();
+        assert!(divergence.flagged);
+        assert_eq!(divergence.highest.source, "Binance");
+
+        // 持续偏离时不重复告警，恢复后再次偏离会重新告警
+        assert!(monitor.check().await.is_empty());
+        cache.record_price_quote("hype", "Binance", 40.1).await;
+        assert!(monitor.check().await.is_empty());
+        cache.record_price_quote("hype", "Binance", 36.0).await;
+        assert_eq!(monitor.check().await.len(), 1);
+    }
+}
@@ -0,0 +1,547 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn, debug};
+use uuid::Uuid;
+
+use crate::alerts::{Alert, AlertManager, AlertSeverity, ScriptCondition, ScriptContext};
+use crate::storage::JsonFileStore;
+use crate::web::cache::DataCache;
+
+/// 每条规则保留的最大触发记录数
+const MAX_TRIGGERS_PER_RULE: usize = 100;
+
+/// 规则触发条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleCondition {
+    /// 高于阈值
+    Above,
+    /// 低于阈值
+    Below,
+    /// Rhai脚本条件（见 [`ScriptCondition`]），不使用指标键和阈值
+    Script,
+}
+
+impl RuleCondition {
+    /// 判断数值是否满足条件（脚本条件不按数值判断，始终为false）
+    pub fn matches(self, value: f64, threshold: f64) -> bool {
+        match self {
+            RuleCondition::Above => value > threshold,
+            RuleCondition::Below => value < threshold,
+            RuleCondition::Script => false,
+        }
+    }
+}
+
+impl std::fmt::Display for RuleCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleCondition::Above => write!(f, "高于"),
+            RuleCondition::Below => write!(f, "低于"),
+            RuleCondition::Script => write!(f, "脚本"),
+        }
+    }
+}
+
+/// 告警规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// 唯一标识符
+    pub id: Uuid,
+    /// 规则名称
+    pub name: String,
+    /// 指标键（参见 `DataCache::metric_value`）
+    pub metric: String,
+    /// 触发条件
+    pub condition: RuleCondition,
+    /// 阈值
+    pub threshold: f64,
+    /// 脚本条件（条件为 `script` 时使用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+    /// 告警级别
+    pub severity: AlertSeverity,
+    /// 是否启用（false表示已暂停）
+    pub enabled: bool,
+    /// 所属用户（None表示全局规则）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Uuid>,
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+    /// 更新时间
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AlertRule {
+    /// 规则对指定用户是否可见（全局规则对所有人可见）
+    pub fn is_visible_to(&self, user: Option<Uuid>) -> bool {
+        self.owner.is_none() || self.owner == user
+    }
+
+    /// 条件描述（如 `fear_greed_index 低于 20`，脚本条件为脚本本身）
+    pub fn describe_condition(&self) -> String {
+        match (&self.condition, &self.script) {
+            (RuleCondition::Script, Some(script)) => script.clone(),
+            _ => format!("{} {} {}", self.metric, self.condition, self.threshold),
+        }
+    }
+}
+
+/// 告警规则输入（创建/编辑）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRuleInput {
+    /// 规则名称
+    pub name: String,
+    /// 指标键（脚本条件不需要）
+    #[serde(default)]
+    pub metric: String,
+    /// 触发条件
+    pub condition: RuleCondition,
+    /// 阈值（脚本条件不需要）
+    #[serde(default)]
+    pub threshold: f64,
+    /// 脚本条件（条件为 `script` 时必填）
+    #[serde(default)]
+    pub script: Option<String>,
+    /// 告警级别（默认警告）
+    #[serde(default)]
+    pub severity: Option<AlertSeverity>,
+    /// 是否启用（默认启用）
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+impl AlertRuleInput {
+    /// 校验输入
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(anyhow!("规则名称不能为空"));
+        }
+        if self.condition == RuleCondition::Script {
+            ScriptCondition::compile(self.script.as_deref().unwrap_or_default())?;
+            return Ok(());
+        }
+        if !DataCache::is_known_metric_key(&self.metric) {
+            return Err(anyhow!("不支持的指标: {}", self.metric));
+        }
+        if !self.threshold.is_finite() {
+            return Err(anyhow!("阈值必须是有限数值"));
+        }
+        Ok(())
+    }
+}
+
+/// 规则触发记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleTrigger {
+    /// 规则ID
+    pub rule_id: Uuid,
+    /// 触发时的指标值
+    pub value: f64,
+    /// 触发时的阈值
+    pub threshold: f64,
+    /// 是否实际发送（false表示处于冷却期被抑制）
+    pub delivered: bool,
+    /// 触发时间
+    pub triggered_at: DateTime<Utc>,
+}
+
+/// 规则存储状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleStoreState {
+    /// 规则列表
+    pub rules: Vec<AlertRule>,
+    /// 触发记录
+    /// key: 规则ID, value: 触发记录（最新的在末尾）
+    pub triggers: HashMap<Uuid, VecDeque<RuleTrigger>>,
+}
+
+/// 告警规则存储
+/// 
+/// 支持运行时增删改查，变更后持久化到磁盘
+pub struct AlertRuleStore {
+    /// 规则状态
+    state: RwLock<RuleStoreState>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<RuleStoreState>>,
+}
+
+impl AlertRuleStore {
+    /// 创建内存中的规则存储（不持久化）
+    pub fn new() -> Self {
+        Self {
+            state: RwLock::new(RuleStoreState::default()),
+            store: None,
+        }
+    }
+
+    /// 从状态文件加载规则存储
+    /// 
+    /// # 参数
+    /// * `path` - 状态文件路径
+    /// 
+    /// # 返回
+    /// * `Result<Self>` - 规则存储或错误
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let state: RuleStoreState = store.load().await?;
+        info!("📏 已加载 {} 条告警规则", state.rules.len());
+        Ok(Self {
+            state: RwLock::new(state),
+            store: Some(store),
+        })
+    }
+
+    /// 导入配置文件中的静态规则（同名规则已存在时跳过）
+    /// 
+    /// # 参数
+    /// * `inputs` - 配置中的规则
+    pub async fn seed(&self, inputs: &[AlertRuleInput]) -> Result<()> {
+        for input in inputs {
+            let exists = self.state.read().await.rules.iter().any(|r| r.name == input.name);
+            if !exists {
+                self.create(None, input.clone()).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 获取所有规则
+    pub async fn list(&self) -> Vec<AlertRule> {
+        self.state.read().await.rules.clone()
+    }
+
+    /// 获取单条规则
+    pub async fn get(&self, id: Uuid) -> Option<AlertRule> {
+        self.state.read().await.rules.iter().find(|r| r.id == id).cloned()
+    }
+
+    /// 创建规则
+    /// 
+    /// # 参数
+    /// * `owner` - 所属用户（None表示全局规则）
+    /// * `input` - 规则输入
+    pub async fn create(&self, owner: Option<Uuid>, input: AlertRuleInput) -> Result<AlertRule> {
+        input.validate()?;
+        let now = Utc::now();
+        let rule = AlertRule {
+            id: Uuid::new_v4(),
+            name: input.name,
+            metric: input.metric,
+            condition: input.condition,
+            threshold: input.threshold,
+            script: input.script.filter(|_| input.condition == RuleCondition::Script),
+            severity: input.severity.unwrap_or(AlertSeverity::Warning),
+            enabled: input.enabled.unwrap_or(true),
+            owner,
+            created_at: now,
+            updated_at: now,
+        };
+        
+        let mut state = self.state.write().await;
+        state.rules.push(rule.clone());
+        self.persist(&state).await?;
+        info!("📏 已创建告警规则: {} ({})", rule.name, rule.id);
+        Ok(rule)
+    }
+
+    /// 编辑规则
+    pub async fn update(&self, id: Uuid, input: AlertRuleInput) -> Result<AlertRule> {
+        input.validate()?;
+        let mut state = self.state.write().await;
+        let rule = state.rules.iter_mut()
+            .find(|r| r.id == id)
+            .ok_or_else(|| anyhow!("规则 {} 不存在", id))?;
+        
+        rule.name = input.name;
+        rule.metric = input.metric;
+        rule.condition = input.condition;
+        rule.threshold = input.threshold;
+        rule.script = input.script.filter(|_| input.condition == RuleCondition::Script);
+        if let Some(severity) = input.severity {
+            rule.severity = severity;
+        }
+        if let Some(enabled) = input.enabled {
+            rule.enabled = enabled;
+        }
+        rule.updated_at = Utc::now();
+        let rule = rule.clone();
+        
+        self.persist(&state).await?;
+        info!("📏 已更新告警规则: {} ({})", rule.name, rule.id);
+        Ok(rule)
+    }
+
+    /// 暂停或恢复规则
+    pub async fn set_enabled(&self, id: Uuid, enabled: bool) -> Result<AlertRule> {
+        let mut state = self.state.write().await;
+        let rule = state.rules.iter_mut()
+            .find(|r| r.id == id)
+            .ok_or_else(|| anyhow!("规则 {} 不存在", id))?;
+        rule.enabled = enabled;
+        rule.updated_at = Utc::now();
+        let rule = rule.clone();
+        
+        self.persist(&state).await?;
+        info!("📏 告警规则 {} 已{}", rule.name, if enabled { "恢复" } else { "暂停" });
+        Ok(rule)
+    }
+
+    /// 删除规则
+    pub async fn delete(&self, id: Uuid) -> Result<AlertRule> {
+        let mut state = self.state.write().await;
+        let index = state.rules.iter()
+            .position(|r| r.id == id)
+            .ok_or_else(|| anyhow!("规则 {} 不存在", id))?;
+        let rule = state.rules.remove(index);
+        state.triggers.remove(&id);
+        
+        self.persist(&state).await?;
+        info!("📏 已删除告警规则: {} ({})", rule.name, rule.id);
+        Ok(rule)
+    }
+
+    /// 记录规则触发
+    pub async fn record_trigger(&self, trigger: RuleTrigger) {
+        let mut state = self.state.write().await;
+        let triggers = state.triggers.entry(trigger.rule_id).or_default();
+        triggers.push_back(trigger);
+        while triggers.len() > MAX_TRIGGERS_PER_RULE {
+            triggers.pop_front();
+        }
+        if let Err(e) = self.persist(&state).await {
+            warn!("⚠️ 保存规则触发记录失败: {}", e);
+        }
+    }
+
+    /// 获取规则的触发记录
+    /// 
+    /// # 返回
+    /// * `Vec<RuleTrigger>` - 触发记录（最新的在前）
+    pub async fn triggers(&self, id: Uuid, limit: usize) -> Vec<RuleTrigger> {
+        self.state.read().await.triggers
+            .get(&id)
+            .map(|t| t.iter().rev().take(limit).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// 持久化状态
+    async fn persist(&self, state: &RuleStoreState) -> Result<()> {
+        if let Some(store) = &self.store {
+            store.save(state).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for AlertRuleStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 判断规则在给定数据下是否满足条件（不触发告警，供情景模拟使用）
+///
+/// # 参数
+/// * `rule` - 规则
+/// * `cache` - 数据缓存（可以是模拟情景的缓存）
+/// * `context` - 脚本条件的求值上下文（应由同一缓存构建）
+///
+/// # 返回
+/// * `Option<f64>` - 满足条件时为触发值（脚本条件为1），不满足或暂无数据时为None
+pub async fn check_rule(rule: &AlertRule, cache: &DataCache, context: &ScriptContext) -> Option<f64> {
+    if rule.condition == RuleCondition::Script {
+        let condition = ScriptCondition::compile(rule.script.as_deref().unwrap_or_default()).ok()?;
+        return condition.evaluate(context).ok()?.then_some(1.0);
+    }
+    let value = cache.metric_value(&rule.metric).await?;
+    rule.condition.matches(value, rule.threshold).then_some(value)
+}
+
+/// 已编译的脚本条件
+/// key: 规则ID, value: (规则更新时间, 编译结果)
+type CompiledScripts = HashMap<Uuid, (DateTime<Utc>, Option<ScriptCondition>)>;
+
+/// 告警规则评估器
+/// 
+/// 定期读取缓存中的指标值，对所有启用的规则求值并触发告警
+pub struct RuleEvaluator {
+    /// 数据缓存
+    cache: Arc<DataCache>,
+    /// 规则存储
+    rules: Arc<AlertRuleStore>,
+    /// 告警管理器
+    alert_manager: Arc<AlertManager>,
+    /// 评估间隔
+    interval: Duration,
+    /// 已编译的脚本条件
+    scripts: Mutex<CompiledScripts>,
+}
+
+impl RuleEvaluator {
+    /// 创建新的规则评估器
+    /// 
+    /// 评估间隔至少为1秒
+    pub fn new(
+        cache: Arc<DataCache>,
+        rules: Arc<AlertRuleStore>,
+        alert_manager: Arc<AlertManager>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            cache,
+            rules,
+            alert_manager,
+            interval: interval.max(Duration::from_secs(1)),
+            scripts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 持续运行评估循环
+    pub async fn run(self) {
+        info!("📏 启动告警规则评估，间隔 {}s", self.interval.as_secs());
+        
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            self.evaluate_once().await;
+        }
+    }
+
+    /// 对所有启用的规则求值一次
+    /// 
+    /// # 返回
+    /// * `usize` - 满足条件的规则数量
+    pub async fn evaluate_once(&self) -> usize {
+        let mut matched = 0;
+        let mut script_context = None;
+        
+        for rule in self.rules.list().await.into_iter().filter(|r| r.enabled) {
+            if rule.condition == RuleCondition::Script {
+                let context = match &script_context {
+                    Some(context) => context,
+                    None => script_context.insert(ScriptContext::from_cache(&self.cache).await),
+                };
+                if self.evaluate_script(&rule, context).await {
+                    matched += 1;
+                    self.fire(&rule, 1.0, format!("脚本条件满足: {}", rule.describe_condition())).await;
+                }
+                continue;
+            }
+
+            let Some(value) = self.cache.metric_value(&rule.metric).await else {
+                debug!("📏 规则 {} 的指标 {} 暂无数据", rule.name, rule.metric);
+                continue;
+            };
+            
+            if !rule.condition.matches(value, rule.threshold) {
+                continue;
+            }
+            matched += 1;
+            let message = format!("{} 当前值 {} {} 阈值 {}", rule.metric, value, rule.condition, rule.threshold);
+            self.fire(&rule, value, message).await;
+        }
+        
+        matched
+    }
+
+    /// 对脚本条件求值（首次求值或规则更新后重新编译）
+    ///
+    /// # 返回
+    /// * `bool` - 条件是否满足，编译或求值失败时为false
+    async fn evaluate_script(&self, rule: &AlertRule, context: &ScriptContext) -> bool {
+        let mut scripts = self.scripts.lock().await;
+        let compiled = match scripts.get(&rule.id) {
+            Some((updated_at, compiled)) if *updated_at == rule.updated_at => compiled,
+            _ => {
+                let compiled = match ScriptCondition::compile(rule.script.as_deref().unwrap_or_default()) {
+                    Ok(condition) => Some(condition),
+                    Err(e) => {
+                        warn!("⚠️ 规则 {} 的脚本条件无法编译: {}", rule.name, e);
+                        None
+                    }
+                };
+                &scripts.entry(rule.id).insert_entry((rule.updated_at, compiled)).into_mut().1
+            }
+        };
+        let Some(condition) = compiled else {
+            return false;
+        };
+        match condition.evaluate(context) {
+            Ok(matched) => matched,
+            Err(e) => {
+                debug!("📏 规则 {} 的脚本条件暂无法求值: {}", rule.name, e);
+                false
+            }
+        }
+    }
+
+    /// 触发规则告警并记录
+    ///
+    /// # 参数
+    /// * `rule` - 规则
+    /// * `value` - 触发时的指标值（脚本条件为1）
+    /// * `message` - 告警内容
+    async fn fire(&self, rule: &AlertRule, value: f64, message: String) {
+        let alert = Alert::new(
+            format!("rule:{}", rule.id),
+            rule.severity,
+            rule.name.clone(),
+            message,
+        ).metadata(match &rule.script {
+            Some(script) => serde_json::json!({
+                "rule_id": rule.id,
+                "script": script,
+            }),
+            None => serde_json::json!({
+                "rule_id": rule.id,
+                "metric": rule.metric,
+                "value": value,
+                "threshold": rule.threshold,
+            }),
+        });
+        
+        let delivered = self.alert_manager.fire(alert).await;
+        self.rules.record_trigger(RuleTrigger {
+            rule_id: rule.id,
+            value,
+            threshold: rule.threshold,
+            delivered,
+            triggered_at: Utc::now(),
+        }).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_script_rule_evaluation() {
+        let cache = Arc::new(DataCache::new());
+        let rules = Arc::new(AlertRuleStore::new());
+        let input = AlertRuleInput {
+            name: "极度恐惧".to_string(),
+            metric: String::new(),
+            condition: RuleCondition::Script,
+            threshold: 0.0,
+            script: Some("fear_greed < 25".to_string()),
+            severity: None,
+            enabled: None,
+        };
+        assert!(rules.create(None, AlertRuleInput { script: Some("fear_greed <".to_string()), ..input.clone() }).await.is_err());
+        let rule = rules.create(None, input).await.unwrap();
+
+        let evaluator = RuleEvaluator::new(cache.clone(), rules.clone(), Arc::new(AlertManager::new()), Duration::from_secs(60));
+        assert_eq!(evaluator.evaluate_once().await, 0);
+        cache.set_fear_greed_index(crate::clients::FearGreedSnapshot::from_value(20, Utc::now(), "test")).await;
+        assert_eq!(evaluator.evaluate_once().await, 1);
+        assert_eq!(rules.triggers(rule.id, 10).await.len(), 1);
+    }
+}
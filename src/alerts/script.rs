@@ -0,0 +1,132 @@
+use anyhow::{anyhow, Result};
+use rhai::{Dynamic, Engine, Map, Scope, AST};
+use std::sync::OnceLock;
+
+use crate::web::cache::{DataCache, ALTCOIN_SEASON_KEY, FEAR_GREED_KEY};
+
+/// 单次求值允许的最大操作数（防止耗时过长的表达式拖慢评估循环）
+const MAX_OPERATIONS: u64 = 10_000;
+
+/// 脚本引擎（全局共享，只编译表达式，不支持语句和循环）
+fn engine() -> &'static Engine {
+    static ENGINE: OnceLock<Engine> = OnceLock::new();
+    ENGINE.get_or_init(|| {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.set_max_expr_depths(32, 32);
+        engine.set_max_string_size(1024);
+        engine.set_max_array_size(1024);
+        engine.set_max_map_size(1024);
+        engine.on_print(|_| {});
+        engine.on_debug(|_, _, _| {});
+        engine
+    })
+}
+
+/// 编译后的告警脚本条件
+///
+/// 条件是一条Rhai表达式，结果必须是布尔值，如
+/// `fear_greed < 25 && btc.rsi < 30 && btc.price_change_24h < -5.0`
+#[derive(Debug, Clone)]
+pub struct ScriptCondition {
+    /// 编译后的表达式
+    ast: AST,
+}
+
+impl ScriptCondition {
+    /// 编译脚本条件
+    ///
+    /// # 参数
+    /// * `script` - Rhai表达式
+    ///
+    /// # 返回
+    /// * `Result<Self>` - 编译结果，语法错误时返回错误
+    pub fn compile(script: &str) -> Result<Self> {
+        if script.trim().is_empty() {
+            return Err(anyhow!("脚本条件不能为空"));
+        }
+        let ast = engine().compile_expression(script).map_err(|e| anyhow!("脚本条件语法错误: {}", e))?;
+        Ok(Self { ast })
+    }
+
+    /// 对上下文求值
+    ///
+    /// # 参数
+    /// * `context` - 当前指标上下文
+    ///
+    /// # 返回
+    /// * `Result<bool>` - 条件是否满足，引用了缺失的指标或结果不是布尔值时返回错误
+    pub fn evaluate(&self, context: &ScriptContext) -> Result<bool> {
+        let mut scope = context.scope.clone();
+        engine().eval_ast_with_scope::<bool>(&mut scope, &self.ast).map_err(|e| anyhow!("{}", e))
+    }
+}
+
+/// 脚本条件的求值上下文
+///
+/// 提供以下变量（尚无数据的变量不存在，引用时求值失败）：
+/// * `fear_greed` - 贪婪恐惧指数
+/// * `altcoin_season` - 山寨币季节指数
+/// * `<币种ID>` / `<币种符号小写>` - 行情对象，字段为 `price`、`price_change_24h`、`market_cap`、`volume_24h`、`rsi`
+#[derive(Debug, Clone, Default)]
+pub struct ScriptContext {
+    /// 变量
+    scope: Scope<'static>,
+}
+
+impl ScriptContext {
+    /// 从数据缓存构建上下文
+    pub async fn from_cache(cache: &DataCache) -> Self {
+        let mut context = Self::default();
+        if let Some(value) = cache.metric_value(FEAR_GREED_KEY).await {
+            context.set("fear_greed", value);
+        }
+        if let Some(value) = cache.metric_value(ALTCOIN_SEASON_KEY).await {
+            context.set("altcoin_season", value);
+        }
+        for data in cache.get_all_market_data().await {
+            let mut coin = Map::new();
+            coin.insert("price".into(), Dynamic::from(data.current_price));
+            let optional = [
+                ("price_change_24h", data.price_change_24h),
+                ("market_cap", data.market_cap),
+                ("volume_24h", data.volume_24h),
+            ];
+            for (field, value) in optional {
+                coin.insert(field.into(), value.map_or(Dynamic::UNIT, Dynamic::from));
+            }
+            coin.insert("rsi".into(), Dynamic::from(data.technical_indicators.rsi.value));
+            let symbol = data.symbol.to_lowercase();
+            if !symbol.is_empty() && symbol != data.coin_id {
+                context.scope.push_constant(symbol, coin.clone());
+            }
+            context.scope.push_constant(data.coin_id.clone(), coin);
+        }
+        context
+    }
+
+    /// 设置数值变量
+    pub fn set(&mut self, name: &str, value: f64) {
+        self.scope.push_constant(name.to_string(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_condition() {
+        assert!(ScriptCondition::compile("fear_greed <").is_err());
+        assert!(ScriptCondition::compile("").is_err());
+
+        let condition = ScriptCondition::compile("fear_greed < 25 && altcoin_season >= 75.0").unwrap();
+        let mut context = ScriptContext::default();
+        context.set("fear_greed", 20.0);
+        assert!(condition.evaluate(&context).is_err());
+        context.set("altcoin_season", 80.0);
+        assert!(condition.evaluate(&context).unwrap());
+
+        assert!(ScriptCondition::compile("fear_greed + 1").unwrap().evaluate(&context).is_err());
+    }
+}
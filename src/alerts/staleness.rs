@@ -0,0 +1,181 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, debug};
+
+use crate::alerts::{Alert, AlertManager, AlertSeverity};
+use crate::tasks::{TaskHealth, TaskManager};
+
+/// 任务存活监控（Dead man's switch）
+/// 
+/// 定期检查每个任务最后一次成功执行的时间，
+/// 超过 `执行间隔 × 倍数` 仍未成功时触发告警，恢复后发送恢复通知
+pub struct StalenessMonitor {
+    /// 任务管理器
+    task_manager: TaskManager,
+    /// 告警管理器
+    alert_manager: Arc<AlertManager>,
+    /// 允许的最大间隔倍数
+    multiplier: f64,
+    /// 检查间隔
+    check_interval: Duration,
+    /// 监控启动时间（从未成功过的任务以此为基准）
+    started_at: DateTime<Utc>,
+    /// 当前处于过期告警状态的任务ID
+    alerted: HashSet<String>,
+}
+
+impl StalenessMonitor {
+    /// 创建新的存活监控
+    /// 
+    /// # 参数
+    /// * `task_manager` - 任务管理器
+    /// * `alert_manager` - 告警管理器
+    /// * `multiplier` - 允许的最大间隔倍数
+    /// * `check_interval` - 检查间隔（至少1秒）
+    pub fn new(
+        task_manager: TaskManager,
+        alert_manager: Arc<AlertManager>,
+        multiplier: f64,
+        check_interval: Duration,
+    ) -> Self {
+        Self {
+            task_manager,
+            alert_manager,
+            multiplier,
+            check_interval: check_interval.max(Duration::from_secs(1)),
+            started_at: Utc::now(),
+            alerted: HashSet::new(),
+        }
+    }
+
+    /// 持续运行监控循环
+    pub async fn run(mut self) {
+        info!("⏱️ 启动任务存活监控，阈值为执行间隔的 {} 倍", self.multiplier);
+        
+        let mut interval = tokio::time::interval(self.check_interval);
+        loop {
+            interval.tick().await;
+            for alert in self.check(Utc::now()).await {
+                self.alert_manager.fire(alert).await;
+            }
+        }
+    }
+
+    /// 在指定时间点检查所有任务
+    /// 
+    /// 每个过期周期只告警一次，任务恢复后生成一条恢复通知
+    /// 
+    /// # 参数
+    /// * `now` - 当前时间
+    /// 
+    /// # 返回
+    /// * `Vec<Alert>` - 需要发送的告警
+    pub async fn check(&mut self, now: DateTime<Utc>) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        
+        for health in self.task_manager.get_task_health().await {
+            let deadline = self.deadline(&health);
+            let reference = health.last_success.unwrap_or(self.started_at);
+            let overdue = now - reference > deadline;
+            
+            debug!("⏱️ 任务 {} 最后成功: {:?}, 过期: {}", health.task_id, health.last_success, overdue);
+            
+            if overdue && self.alerted.insert(health.task_id.clone()) {
+                let since = health.last_success
+                    .map(|t| t.to_rfc3339())
+                    .unwrap_or_else(|| "从未成功".to_string());
+                alerts.push(Alert::new(
+                    format!("staleness:{}", health.task_id),
+                    AlertSeverity::Warning,
+                    format!("任务 {} 数据已过期", health.task_name),
+                    format!(
+                        "任务超过 {} 秒未成功执行（最后成功: {}，最后错误: {}）",
+                        deadline.num_seconds(),
+                        since,
+                        health.last_error.as_deref().unwrap_or("无"),
+                    ),
+                ).metadata(serde_json::json!({
+                    "task_id": health.task_id,
+                    "interval_seconds": health.interval_seconds,
+                    "last_success": health.last_success,
+                    "last_error": health.last_error,
+                })));
+            } else if !overdue && self.alerted.remove(&health.task_id) {
+                alerts.push(Alert::new(
+                    format!("staleness:{}", health.task_id),
+                    AlertSeverity::Info,
+                    format!("任务 {} 已恢复", health.task_name),
+                    "任务已重新成功执行，数据恢复更新",
+                ));
+            }
+        }
+        
+        alerts
+    }
+
+    /// 计算任务允许的最长未成功时长
+    fn deadline(&self, health: &TaskHealth) -> ChronoDuration {
+        let seconds = (health.interval_seconds as f64 * self.multiplier).ceil() as i64;
+        ChronoDuration::seconds(seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use crate::models::AggregatedMetric;
+    use crate::tasks::Task;
+    use crate::web::cache::DataCache;
+
+    struct FailingTask;
+
+    #[async_trait]
+    impl Task for FailingTask {
+        fn name(&self) -> &str { "failing" }
+        fn description(&self) -> &str { "always fails" }
+        fn id(&self) -> &str { "failing" }
+        fn interval_seconds(&self) -> u64 { 60 }
+        async fn execute(&self, _cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+            Err(anyhow::anyhow!("upstream down"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_overdue_task_alerts_once() {
+        let mut task_manager = TaskManager::new();
+        task_manager.register_task(Box::new(FailingTask)).await.unwrap();
+        task_manager.execute_all(&DataCache::new()).await.unwrap();
+
+        let mut monitor = StalenessMonitor::new(
+            task_manager,
+            Arc::new(AlertManager::new()),
+            3.0,
+            Duration::from_secs(60),
+        );
+        let started_at = monitor.started_at;
+
+        assert!(monitor.check(started_at + ChronoDuration::seconds(120)).await.is_empty());
+
+        let alerts = monitor.check(started_at + ChronoDuration::seconds(200)).await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule, "staleness:failing");
+        assert_eq!(alerts[0].severity, AlertSeverity::Warning);
+
+        assert!(monitor.check(started_at + ChronoDuration::seconds(400)).await.is_empty());
+    }
+
+    #[test]
+    fn test_zero_check_interval_is_clamped() {
+        let monitor = StalenessMonitor::new(
+            TaskManager::new(),
+            Arc::new(AlertManager::new()),
+            3.0,
+            Duration::ZERO,
+        );
+        assert_eq!(monitor.check_interval, Duration::from_secs(1));
+    }
+}
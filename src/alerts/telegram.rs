@@ -0,0 +1,68 @@
+use anyhow::{Result, Context};
+use async_trait::async_trait;
+use std::time::Duration;
+
+use crate::alerts::{Alert, Notifier};
+use crate::clients::HttpClientBuilder;
+use crate::config::TelegramConfig;
+
+/// Telegram通知渠道
+/// 
+/// 通过Telegram Bot API向指定会话发送告警消息
+pub struct TelegramNotifier {
+    /// 渠道名称
+    name: String,
+    /// HTTP客户端
+    client: reqwest::Client,
+    /// Bot令牌
+    bot_token: String,
+    /// 会话ID
+    chat_id: String,
+}
+
+impl TelegramNotifier {
+    /// 根据配置创建Telegram通知渠道
+    pub fn from_config(config: &TelegramConfig) -> Result<Self> {
+        let client = HttpClientBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .user_agent("EverScan-Telegram/1.0")
+            .build()?;
+        
+        Ok(Self {
+            name: format!("telegram:{}", config.name),
+            client,
+            bot_token: config.bot_token.expose().to_string(),
+            chat_id: config.chat_id.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        let text = format!("[{}] {}\n{}", alert.severity, alert.title, alert.message);
+        
+        let response = self.client
+            .post(&url)
+            .json(&serde_json::json!({
+                "chat_id": self.chat_id,
+                "text": text,
+                "disable_web_page_preview": true,
+            }))
+            .send()
+            .await
+            .context("发送Telegram消息失败")?;
+        
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Telegram API请求失败: HTTP {} - {}", status, text));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,394 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{info, warn, debug};
+use uuid::Uuid;
+
+use crate::alerts::{Alert, AlertManager, AlertSeverity};
+use crate::storage::JsonFileStore;
+use crate::web::cache::{market_data_key, DataCache};
+
+/// 价格穿越方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchDirection {
+    /// 向上突破
+    Above,
+    /// 向下跌破
+    Below,
+}
+
+impl WatchDirection {
+    /// 判断价格是否位于目标一侧
+    fn is_beyond(self, price: f64, threshold: f64) -> bool {
+        match self {
+            WatchDirection::Above => price >= threshold,
+            WatchDirection::Below => price <= threshold,
+        }
+    }
+}
+
+/// 监控模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchMode {
+    /// 触发一次后自动停用
+    #[default]
+    OneShot,
+    /// 每次穿越都触发
+    Repeating,
+}
+
+/// 价格目标监控
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceWatch {
+    /// 唯一标识符
+    pub id: Uuid,
+    /// 币种ID
+    pub coin_id: String,
+    /// 穿越方向
+    pub direction: WatchDirection,
+    /// 目标价格
+    pub threshold: f64,
+    /// 监控模式
+    pub mode: WatchMode,
+    /// 备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// 所属用户（None表示全局监控）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Uuid>,
+    /// 是否仍在监控
+    pub active: bool,
+    /// 最近一次观察到的价格
+    pub last_price: Option<f64>,
+    /// 累计触发次数
+    pub triggered_count: u32,
+    /// 最后一次触发时间
+    pub last_triggered_at: Option<DateTime<Utc>>,
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+}
+
+impl PriceWatch {
+    /// 监控对指定用户是否可见（全局监控对所有人可见）
+    pub fn is_visible_to(&self, user: Option<Uuid>) -> bool {
+        self.owner.is_none() || self.owner == user
+    }
+
+    /// 观察新价格
+    /// 
+    /// 只有上一次价格在目标另一侧、本次价格越过目标时才算穿越，
+    /// 因此创建时价格已在目标一侧的监控需要先回到另一侧才会触发
+    /// 
+    /// # 参数
+    /// * `price` - 最新价格
+    /// 
+    /// # 返回
+    /// * `bool` - 是否发生穿越
+    pub fn observe(&mut self, price: f64) -> bool {
+        let previous = self.last_price.replace(price);
+        if !self.active {
+            return false;
+        }
+        
+        let crossed = previous
+            .map(|prev| !self.direction.is_beyond(prev, self.threshold) && self.direction.is_beyond(price, self.threshold))
+            .unwrap_or(false);
+        
+        if crossed {
+            self.triggered_count += 1;
+            self.last_triggered_at = Some(Utc::now());
+            if self.mode == WatchMode::OneShot {
+                self.active = false;
+            }
+        }
+        crossed
+    }
+}
+
+/// 价格目标监控输入
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceWatchInput {
+    /// 币种ID
+    pub coin_id: String,
+    /// 穿越方向
+    pub direction: WatchDirection,
+    /// 目标价格
+    pub threshold: f64,
+    /// 监控模式（默认一次性）
+    #[serde(default)]
+    pub mode: WatchMode,
+    /// 备注
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// 价格目标监控存储
+pub struct WatchStore {
+    /// 监控列表
+    watches: RwLock<Vec<PriceWatch>>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<Vec<PriceWatch>>>,
+}
+
+impl WatchStore {
+    /// 创建内存中的监控存储（不持久化）
+    pub fn new() -> Self {
+        Self {
+            watches: RwLock::new(Vec::new()),
+            store: None,
+        }
+    }
+
+    /// 从状态文件加载监控存储
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let watches: Vec<PriceWatch> = store.load().await?;
+        info!("🎯 已加载 {} 个价格目标监控", watches.len());
+        Ok(Self {
+            watches: RwLock::new(watches),
+            store: Some(store),
+        })
+    }
+
+    /// 获取所有监控
+    pub async fn list(&self) -> Vec<PriceWatch> {
+        self.watches.read().await.clone()
+    }
+
+    /// 获取单个监控
+    pub async fn get(&self, id: Uuid) -> Option<PriceWatch> {
+        self.watches.read().await.iter().find(|w| w.id == id).cloned()
+    }
+
+    /// 创建监控
+    /// 
+    /// # 参数
+    /// * `owner` - 所属用户（None表示全局监控）
+    /// * `input` - 监控输入
+    pub async fn create(&self, owner: Option<Uuid>, input: PriceWatchInput) -> Result<PriceWatch> {
+        if input.coin_id.trim().is_empty() {
+            return Err(anyhow!("币种ID不能为空"));
+        }
+        if !input.threshold.is_finite() || input.threshold <= 0.0 {
+            return Err(anyhow!("目标价格必须是正数"));
+        }
+        
+        let watch = PriceWatch {
+            id: Uuid::new_v4(),
+            coin_id: input.coin_id.to_lowercase(),
+            direction: input.direction,
+            threshold: input.threshold,
+            mode: input.mode,
+            note: input.note,
+            owner,
+            active: true,
+            last_price: None,
+            triggered_count: 0,
+            last_triggered_at: None,
+            created_at: Utc::now(),
+        };
+        
+        let mut watches = self.watches.write().await;
+        watches.push(watch.clone());
+        self.persist(&watches).await?;
+        info!("🎯 已创建价格目标监控: {} {:?} {}", watch.coin_id, watch.direction, watch.threshold);
+        Ok(watch)
+    }
+
+    /// 删除监控
+    pub async fn delete(&self, id: Uuid) -> Result<PriceWatch> {
+        let mut watches = self.watches.write().await;
+        let index = watches.iter()
+            .position(|w| w.id == id)
+            .ok_or_else(|| anyhow!("监控 {} 不存在", id))?;
+        let watch = watches.remove(index);
+        self.persist(&watches).await?;
+        Ok(watch)
+    }
+
+    /// 用最新价格更新某币种的所有监控
+    /// 
+    /// 只在监控首次记录价格或价格移到目标另一侧时写入状态文件，
+    /// 价格停留在目标同一侧时不影响穿越判断，不重复写入
+    /// 
+    /// # 返回
+    /// * `Vec<PriceWatch>` - 本次发生穿越的监控
+    pub async fn observe(&self, coin_id: &str, price: f64) -> Vec<PriceWatch> {
+        let mut watches = self.watches.write().await;
+        let mut crossed = Vec::new();
+        let mut changed = false;
+        for watch in watches.iter_mut().filter(|w| w.coin_id == coin_id) {
+            changed |= watch.last_price.is_none_or(|previous| {
+                watch.direction.is_beyond(previous, watch.threshold) != watch.direction.is_beyond(price, watch.threshold)
+            });
+            if watch.observe(price) {
+                crossed.push(watch.clone());
+            }
+        }
+        if changed {
+            if let Err(e) = self.persist(&watches).await {
+                warn!("⚠️ 保存价格目标监控失败: {}", e);
+            }
+        }
+        crossed
+    }
+
+    /// 持久化
+    async fn persist(&self, watches: &Vec<PriceWatch>) -> Result<()> {
+        if let Some(store) = &self.store {
+            store.save(watches).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for WatchStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 价格目标监控器
+/// 
+/// 订阅缓存更新事件，币种数据更新时检查对应的价格目标
+pub struct PriceWatcher {
+    /// 数据缓存
+    cache: Arc<DataCache>,
+    /// 监控存储
+    watches: Arc<WatchStore>,
+    /// 告警管理器
+    alert_manager: Arc<AlertManager>,
+}
+
+impl PriceWatcher {
+    /// 创建新的价格目标监控器
+    pub fn new(cache: Arc<DataCache>, watches: Arc<WatchStore>, alert_manager: Arc<AlertManager>) -> Self {
+        Self {
+            cache,
+            watches,
+            alert_manager,
+        }
+    }
+
+    /// 持续处理缓存更新事件
+    pub async fn run(self) {
+        info!("🎯 启动价格目标监控");
+        let mut updates = self.cache.subscribe();
+        
+        loop {
+            match updates.recv().await {
+                Ok(update) => {
+                    for coin_id in update.coin_ids() {
+                        self.check_coin(coin_id).await;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("⚠️ 价格目标监控落后，跳过 {} 条更新事件", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// 检查单个币种的价格目标
+    async fn check_coin(&self, coin_id: &str) {
+        let Some(price) = self.cache.metric_value(&format!("price:{}", coin_id)).await else {
+            return;
+        };
+        debug!("🎯 检查 {} 的价格目标，当前价格 {}", coin_id, price);
+        
+        for watch in self.watches.observe(coin_id, price).await {
+            let verb = match watch.direction {
+                WatchDirection::Above => "突破",
+                WatchDirection::Below => "跌破",
+            };
+            let alert = Alert::new(
+                format!("watch:{}", watch.id),
+                AlertSeverity::Warning,
+                format!("{} 价格{} {}", watch.coin_id.to_uppercase(), verb, watch.threshold),
+                format!("当前价格 {}{}", price, watch.note.as_deref().map(|n| format!("（{}）", n)).unwrap_or_default()),
+            ).metadata(serde_json::json!({
+                "watch_id": watch.id,
+                "coin_id": watch.coin_id,
+                "price": price,
+                "threshold": watch.threshold,
+                "source_key": market_data_key(&watch.coin_id),
+            }));
+            self.alert_manager.fire(alert).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watch(mode: WatchMode) -> PriceWatch {
+        PriceWatch {
+            id: Uuid::new_v4(),
+            coin_id: "bitcoin".to_string(),
+            direction: WatchDirection::Above,
+            threshold: 100.0,
+            mode,
+            note: None,
+            owner: None,
+            active: true,
+            last_price: None,
+            triggered_count: 0,
+            last_triggered_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_one_shot_fires_once_on_crossing() {
+        let mut w = watch(WatchMode::OneShot);
+        assert!(!w.observe(105.0), "首次观察只记录价格");
+        assert!(!w.observe(95.0));
+        assert!(w.observe(101.0));
+        assert!(!w.active);
+        assert!(!w.observe(90.0));
+        assert!(!w.observe(110.0));
+        assert_eq!(w.triggered_count, 1);
+    }
+
+    #[test]
+    fn test_repeating_fires_on_each_crossing() {
+        let mut w = watch(WatchMode::Repeating);
+        w.observe(90.0);
+        assert!(w.observe(100.0));
+        assert!(!w.observe(120.0));
+        assert!(!w.observe(80.0));
+        assert!(w.observe(130.0));
+        assert_eq!(w.triggered_count, 2);
+        assert!(w.active);
+    }
+
+    #[tokio::test]
+    async fn test_observe_persists_only_side_changes() {
+        let path = std::env::temp_dir().join(format!("everscan-watches-{}.json", Uuid::new_v4()));
+        let store = WatchStore::open(&path).await.unwrap();
+        let input = PriceWatchInput {
+            coin_id: "bitcoin".to_string(),
+            direction: WatchDirection::Above,
+            threshold: 100.0,
+            mode: WatchMode::Repeating,
+            note: None,
+        };
+        store.create(None, input).await.unwrap();
+        let saved_price = || async { WatchStore::open(&path).await.unwrap().list().await[0].last_price };
+
+        store.observe("bitcoin", 90.0).await;
+        assert_eq!(saved_price().await, Some(90.0));
+        store.observe("bitcoin", 95.0).await;
+        assert_eq!(saved_price().await, Some(90.0));
+        assert_eq!(store.observe("bitcoin", 101.0).await.len(), 1);
+        assert_eq!(saved_price().await, Some(101.0));
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,175 @@
+use anyhow::{Result, Context};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use reqwest::StatusCode;
+use sha2::Sha256;
+use std::time::Duration;
+use tracing::{info, warn, debug};
+
+use crate::alerts::{Alert, Notifier};
+use crate::clients::HttpClientBuilder;
+use crate::config::WebhookConfig;
+
+/// 签名请求头
+pub const SIGNATURE_HEADER: &str = "X-Everscan-Signature";
+
+/// 时间戳请求头
+pub const TIMESTAMP_HEADER: &str = "X-Everscan-Timestamp";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 计算Webhook签名
+/// 
+/// 签名内容为 `{timestamp}.{body}`，接收方应使用同样的方式校验并拒绝过旧的时间戳
+/// 
+/// # 参数
+/// * `secret` - 共享密钥
+/// * `timestamp` - Unix时间戳（秒）
+/// * `body` - 请求体
+/// 
+/// # 返回
+/// * `String` - 形如 `sha256=<hex>` 的签名
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .expect("HMAC可以接受任意长度的密钥");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Webhook通知渠道
+/// 
+/// 将告警/事件以JSON形式POST到配置的URL，
+/// 配置了密钥时附带HMAC-SHA256签名，发送失败时按指数退避重试
+pub struct WebhookNotifier {
+    /// 渠道名称
+    name: String,
+    /// HTTP客户端
+    client: reqwest::Client,
+    /// 目标URL
+    url: String,
+    /// 签名密钥（可选）
+    secret: Option<String>,
+    /// 最大重试次数
+    max_retries: u32,
+    /// 首次重试等待时间
+    retry_backoff: Duration,
+}
+
+impl WebhookNotifier {
+    /// 根据配置创建Webhook通知渠道
+    /// 
+    /// # 参数
+    /// * `config` - Webhook配置
+    /// 
+    /// # 返回
+    /// * `Result<Self>` - 通知渠道或错误
+    pub fn from_config(config: &WebhookConfig) -> Result<Self> {
+        let client = HttpClientBuilder::new()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .user_agent("EverScan-Webhook/1.0")
+            .build()?;
+        
+        Ok(Self {
+            name: format!("webhook:{}", config.name),
+            client,
+            url: config.url.clone(),
+            secret: config.secret.as_ref().map(|s| s.expose().to_string()).filter(|s| !s.is_empty()),
+            max_retries: config.max_retries,
+            retry_backoff: Duration::from_millis(config.retry_backoff_ms),
+        })
+    }
+
+    /// 发送任意JSON事件
+    /// 
+    /// # 参数
+    /// * `payload` - 事件内容
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 成功或最后一次失败的错误
+    pub async fn send_json(&self, payload: &serde_json::Value) -> Result<()> {
+        let body = serde_json::to_vec(payload).context("序列化Webhook负载失败")?;
+        let mut backoff = self.retry_backoff;
+        let mut attempt = 0;
+        
+        loop {
+            match self.post_once(&body).await {
+                Ok(()) => {
+                    debug!("📤 Webhook {} 发送成功", self.name);
+                    return Ok(());
+                }
+                Err((e, retryable)) => {
+                    if !retryable || attempt >= self.max_retries {
+                        return Err(e.context(format!("Webhook {} 发送失败（已尝试 {} 次）", self.name, attempt + 1)));
+                    }
+                    attempt += 1;
+                    warn!("⚠️ Webhook {} 发送失败，{}ms 后第 {} 次重试: {}", self.name, backoff.as_millis(), attempt, e);
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+
+    /// 发送一次请求
+    /// 
+    /// # 返回
+    /// * `Result<(), (anyhow::Error, bool)>` - 失败时附带是否可重试
+    async fn post_once(&self, body: &[u8]) -> std::result::Result<(), (anyhow::Error, bool)> {
+        let timestamp = chrono::Utc::now().timestamp();
+        let mut request = self.client
+            .post(&self.url)
+            .header("Content-Type", "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .body(body.to_vec());
+        
+        if let Some(secret) = &self.secret {
+            request = request.header(SIGNATURE_HEADER, sign_payload(secret, timestamp, body));
+        }
+        
+        let response = request.send().await
+            .map_err(|e| (anyhow::Error::new(e), true))?;
+        
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        
+        // 仅对服务端错误和限流进行重试
+        let retryable = status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS;
+        let text = response.text().await.unwrap_or_default();
+        Err((anyhow::anyhow!("HTTP {} - {}", status, text), retryable))
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        self.send_json(&serde_json::json!({
+            "type": "alert",
+            "alert": alert,
+        })).await?;
+        info!("📤 告警已通过 {} 发送: {}", self.name, alert.title);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload() {
+        let signature = sign_payload("secret", 1_700_000_000, br#"{"type":"alert"}"#);
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert_eq!(signature, sign_payload("secret", 1_700_000_000, br#"{"type":"alert"}"#));
+        assert_ne!(signature, sign_payload("other", 1_700_000_000, br#"{"type":"alert"}"#));
+        assert_ne!(signature, sign_payload("secret", 1_700_000_001, br#"{"type":"alert"}"#));
+    }
+}
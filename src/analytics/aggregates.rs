@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::clients::CmcListing;
+
+/// 汇总市值（Total/Total2/Total3）
+///
+/// 由CMC上市信息（市值前100）计算，Total2剔除BTC，Total3剔除BTC和ETH
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketAggregates {
+    /// 计算时间
+    pub timestamp: DateTime<Utc>,
+    /// 参与统计的币种数
+    pub coins: usize,
+    /// 总市值
+    pub total: AggregateValue,
+    /// 剔除BTC的总市值
+    pub total2: AggregateValue,
+    /// 剔除BTC和ETH的总市值
+    pub total3: AggregateValue,
+    /// BTC市值占比（百分比）
+    pub btc_dominance: Option<f64>,
+    /// ETH市值占比（百分比）
+    pub eth_dominance: Option<f64>,
+    /// 24小时总交易量
+    pub volume_24h: f64,
+}
+
+/// 单项汇总市值
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AggregateValue {
+    /// 市值（美元）
+    pub market_cap: f64,
+    /// 24小时变化百分比（由各币种市值和涨跌幅反推）
+    pub change_24h_pct: Option<f64>,
+}
+
+impl AggregateValue {
+    /// 汇总一组币种的市值和24小时变化
+    fn sum<'a>(listings: impl Iterator<Item = &'a CmcListing>) -> Self {
+        let mut market_cap = 0.0;
+        let mut previous = 0.0;
+        for listing in listings {
+            let Some(cap) = listing.market_cap.filter(|c| *c > 0.0) else {
+                continue;
+            };
+            market_cap += cap;
+            previous += match listing.percent_change_24h {
+                Some(change) if change > -100.0 => cap / (1.0 + change / 100.0),
+                _ => cap,
+            };
+        }
+        Self {
+            market_cap,
+            change_24h_pct: (previous > 0.0).then(|| (market_cap - previous) / previous * 100.0),
+        }
+    }
+}
+
+/// 计算汇总市值
+///
+/// # 参数
+/// * `listings` - CMC上市信息
+/// * `timestamp` - 计算时间
+///
+/// # 返回
+/// * `Option<MarketAggregates>` - 上市信息中没有市值数据时为None
+pub fn compute_aggregates(listings: &[CmcListing], timestamp: DateTime<Utc>) -> Option<MarketAggregates> {
+    let is = |listing: &CmcListing, symbol: &str| listing.symbol.eq_ignore_ascii_case(symbol);
+    let total = AggregateValue::sum(listings.iter());
+    if total.market_cap <= 0.0 {
+        return None;
+    }
+    let dominance = |symbol: &str| {
+        let cap: f64 = listings.iter().filter(|l| is(l, symbol)).filter_map(|l| l.market_cap).sum();
+        (cap > 0.0).then(|| cap / total.market_cap * 100.0)
+    };
+
+    Some(MarketAggregates {
+        timestamp,
+        coins: listings.iter().filter(|l| l.market_cap.is_some_and(|c| c > 0.0)).count(),
+        total2: AggregateValue::sum(listings.iter().filter(|l| !is(l, "BTC"))),
+        total3: AggregateValue::sum(listings.iter().filter(|l| !is(l, "BTC") && !is(l, "ETH"))),
+        btc_dominance: dominance("BTC"),
+        eth_dominance: dominance("ETH"),
+        volume_24h: listings.iter().filter_map(|l| l.volume_24h).sum(),
+        total,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_aggregates() {
+        let listing = |symbol: &str, market_cap: f64, change: f64| CmcListing {
+            id: 0,
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            cmc_rank: None,
+            circulating_supply: None,
+            tags: Vec::new(),
+            market_cap: Some(market_cap),
+            volume_24h: Some(1.0),
+            percent_change_24h: Some(change),
+        };
+        let listings = vec![
+            listing("BTC", 600.0, 20.0),
+            listing("ETH", 200.0, 0.0),
+            listing("SOL", 150.0, 50.0),
+            listing("DOGE", 50.0, 0.0),
+        ];
+
+        let aggregates = compute_aggregates(&listings, Utc::now()).unwrap();
+        assert_eq!(aggregates.coins, 4);
+        assert_eq!(aggregates.total.market_cap, 1000.0);
+        assert_eq!(aggregates.total2.market_cap, 400.0);
+        assert_eq!(aggregates.total3.market_cap, 200.0);
+        assert_eq!(aggregates.btc_dominance, Some(60.0));
+        // SOL 100 -> 150，DOGE 50 -> 50：150 -> 200
+        assert_eq!(aggregates.total3.change_24h_pct.map(|c| (c * 100.0).round() / 100.0), Some(33.33));
+        assert_eq!(aggregates.volume_24h, 4.0);
+
+        assert!(compute_aggregates(&[], Utc::now()).is_none());
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+/// 将MAD换算为正态分布标准差的系数
+const MAD_SCALE: f64 = 0.6745;
+
+/// 异常检测方法
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnomalyMethod {
+    /// 滚动Z分数：偏离均值的标准差倍数
+    ZScore,
+    /// 中位数绝对偏差（修正Z分数），不易被窗口内的极端值带偏
+    #[default]
+    Mad,
+}
+
+impl std::fmt::Display for AnomalyMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnomalyMethod::ZScore => write!(f, "Z分数"),
+            AnomalyMethod::Mad => write!(f, "MAD"),
+        }
+    }
+}
+
+/// 异常评分结果
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AnomalyScore {
+    /// 评分（正数表示高于基准，负数表示低于基准）
+    pub score: f64,
+    /// 基准值（Z分数为均值，MAD为中位数）
+    pub baseline: f64,
+}
+
+/// 计算新值相对于历史窗口的异常评分
+///
+/// # 参数
+/// * `method` - 检测方法
+/// * `history` - 历史窗口内的数值（不含新值）
+/// * `value` - 新值
+///
+/// # 返回
+/// * `Option<AnomalyScore>` - 历史少于两个点或窗口内数值没有波动时返回None
+pub fn anomaly_score(method: AnomalyMethod, history: &[f64], value: f64) -> Option<AnomalyScore> {
+    if history.len() < 2 {
+        return None;
+    }
+    match method {
+        AnomalyMethod::ZScore => {
+            let n = history.len() as f64;
+            let mean = history.iter().sum::<f64>() / n;
+            let variance = history.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            let std_dev = variance.sqrt();
+            (std_dev > 0.0).then(|| AnomalyScore { score: (value - mean) / std_dev, baseline: mean })
+        }
+        AnomalyMethod::Mad => {
+            let center = median(history.to_vec());
+            let mad = median(history.iter().map(|v| (v - center).abs()).collect());
+            (mad > 0.0).then(|| AnomalyScore { score: MAD_SCALE * (value - center) / mad, baseline: center })
+        }
+    }
+}
+
+/// 中位数
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anomaly_score() {
+        let history = [100.0, 102.0, 98.0, 101.0, 99.0, 100.0, 250.0];
+
+        // 窗口内的极端值会拉大标准差，MAD不受影响
+        let z = anomaly_score(AnomalyMethod::ZScore, &history, 200.0).unwrap();
+        let mad = anomaly_score(AnomalyMethod::Mad, &history, 200.0).unwrap();
+        assert!(z.score < 3.0);
+        assert!(mad.score > 30.0);
+        assert_eq!(mad.baseline, 100.0);
+
+        assert!(anomaly_score(AnomalyMethod::Mad, &history, 50.0).unwrap().score < 0.0);
+        assert!(anomaly_score(AnomalyMethod::ZScore, &[5.0, 5.0, 5.0], 9.0).is_none());
+        assert!(anomaly_score(AnomalyMethod::Mad, &[5.0], 9.0).is_none());
+    }
+}
@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::indicators::sma;
+
+/// 新高新低的回看天数
+pub const HIGH_LOW_LOOKBACK_DAYS: usize = 30;
+
+/// 单个币种的广度计算输入
+#[derive(Debug, Clone)]
+pub struct BreadthInput {
+    /// 币种ID
+    pub coin_id: String,
+    /// 当前价格
+    pub price: f64,
+    /// 24小时价格变化百分比
+    pub change_24h: Option<f64>,
+    /// 今天之前的每日收盘价（按日期升序）
+    pub closes: Vec<f64>,
+}
+
+/// 满足条件的币种占比
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BreadthShare {
+    /// 满足条件的币种数
+    pub count: usize,
+    /// 有足够历史参与统计的币种数
+    pub eligible: usize,
+    /// 百分比（没有可统计币种时为空）
+    pub percent: Option<f64>,
+}
+
+impl BreadthShare {
+    fn new(count: usize, eligible: usize) -> Self {
+        Self {
+            count,
+            eligible,
+            percent: (eligible > 0).then(|| count as f64 / eligible as f64 * 100.0),
+        }
+    }
+}
+
+/// 市场广度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketBreadth {
+    /// 计算时间
+    pub timestamp: DateTime<Utc>,
+    /// 参与统计的币种数
+    pub universe: usize,
+    /// 24小时上涨币种数
+    pub advancers: usize,
+    /// 24小时下跌币种数
+    pub decliners: usize,
+    /// 24小时持平或缺少涨跌数据的币种数
+    pub unchanged: usize,
+    /// 涨跌比（没有下跌币种时为空）
+    pub advance_decline_ratio: Option<f64>,
+    /// 价格高于50日均线
+    pub above_sma50: BreadthShare,
+    /// 价格高于200日均线
+    pub above_sma200: BreadthShare,
+    /// 创30日新高
+    pub new_highs_30d: BreadthShare,
+    /// 创30日新低
+    pub new_lows_30d: BreadthShare,
+    /// 日线历史不足、未参与均线统计的币种
+    pub insufficient_history: Vec<String>,
+}
+
+/// 计算价格是否高于N日均线
+/// 
+/// # 返回
+/// * `Option<bool>` - 历史不足N天时为None
+fn above_sma(price: f64, closes: &[f64], period: usize) -> Option<bool> {
+    sma(closes, period).map(|average| price > average)
+}
+
+/// 最近N日的收盘价（历史不足时为None）
+fn recent_closes(closes: &[f64], days: usize) -> Option<&[f64]> {
+    (closes.len() >= days).then(|| &closes[closes.len() - days..])
+}
+
+/// 计算市场广度
+/// 
+/// # 参数
+/// * `inputs` - 各币种的当前价格与日线历史
+/// * `timestamp` - 计算时间
+/// 
+/// # 返回
+/// * `MarketBreadth` - 市场广度统计
+pub fn compute_breadth(inputs: &[BreadthInput], timestamp: DateTime<Utc>) -> MarketBreadth {
+    let advancers = inputs.iter().filter(|c| c.change_24h.is_some_and(|v| v > 0.0)).count();
+    let decliners = inputs.iter().filter(|c| c.change_24h.is_some_and(|v| v < 0.0)).count();
+    
+    let share = |check: &dyn Fn(&BreadthInput) -> Option<bool>| {
+        let results: Vec<bool> = inputs.iter().filter_map(check).collect();
+        BreadthShare::new(results.iter().filter(|r| **r).count(), results.len())
+    };
+    
+    MarketBreadth {
+        timestamp,
+        universe: inputs.len(),
+        advancers,
+        decliners,
+        unchanged: inputs.len() - advancers - decliners,
+        advance_decline_ratio: (decliners > 0).then(|| advancers as f64 / decliners as f64),
+        above_sma50: share(&|c| above_sma(c.price, &c.closes, 50)),
+        above_sma200: share(&|c| above_sma(c.price, &c.closes, 200)),
+        new_highs_30d: share(&|c| recent_closes(&c.closes, HIGH_LOW_LOOKBACK_DAYS).map(|window| window.iter().all(|close| c.price > *close))),
+        new_lows_30d: share(&|c| recent_closes(&c.closes, HIGH_LOW_LOOKBACK_DAYS).map(|window| window.iter().all(|close| c.price < *close))),
+        insufficient_history: inputs.iter()
+            .filter(|c| c.closes.len() < 50)
+            .map(|c| c.coin_id.clone())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_breadth() {
+        let rising: Vec<f64> = (1..=60).map(|i| i as f64).collect();
+        let inputs = vec![
+            BreadthInput { coin_id: "up".to_string(), price: 100.0, change_24h: Some(5.0), closes: rising.clone() },
+            BreadthInput { coin_id: "down".to_string(), price: 0.5, change_24h: Some(-3.0), closes: rising },
+            BreadthInput { coin_id: "new".to_string(), price: 1.0, change_24h: None, closes: Vec::new() },
+        ];
+        
+        let breadth = compute_breadth(&inputs, Utc::now());
+        assert_eq!((breadth.advancers, breadth.decliners, breadth.unchanged), (1, 1, 1));
+        assert_eq!(breadth.above_sma50.count, 1);
+        assert_eq!(breadth.above_sma50.eligible, 2);
+        assert_eq!(breadth.above_sma50.percent, Some(50.0));
+        assert_eq!(breadth.above_sma200.eligible, 0);
+        assert_eq!(breadth.new_highs_30d.count, 1);
+        assert_eq!(breadth.new_lows_30d.count, 1);
+        assert_eq!(breadth.insufficient_history, vec!["new".to_string()]);
+    }
+}
@@ -0,0 +1,187 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 每次减半间隔的区块数
+pub const HALVING_INTERVAL_BLOCKS: u64 = 210_000;
+
+/// 目标出块时间（秒）
+pub const TARGET_BLOCK_SECONDS: f64 = 600.0;
+
+/// 比特币减半事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Halving {
+    /// 第几次减半
+    pub number: u32,
+    /// 区块高度
+    pub height: u64,
+    /// 日期（UTC）
+    pub date: NaiveDate,
+    /// 当日收盘价（美元，约数）
+    pub price_usd: f64,
+}
+
+/// 历次周期表现
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PastCycle {
+    /// 周期起点的减半序号
+    pub halving: u32,
+    /// 周期高点日期
+    pub peak_date: NaiveDate,
+    /// 减半到高点的天数
+    pub days_to_peak: i64,
+    /// 减半到高点的涨幅（百分比）
+    pub peak_return_pct: f64,
+}
+
+/// 已发生的减半（价格为当日收盘价约数）
+fn halvings() -> Vec<Halving> {
+    let halving = |number: u32, (y, m, d): (i32, u32, u32), price_usd: f64| Halving {
+        number,
+        height: number as u64 * HALVING_INTERVAL_BLOCKS,
+        date: NaiveDate::from_ymd_opt(y, m, d).expect("有效日期"),
+        price_usd,
+    };
+    vec![
+        halving(1, (2012, 11, 28), 12.35),
+        halving(2, (2016, 7, 9), 650.63),
+        halving(3, (2020, 5, 11), 8_601.80),
+        halving(4, (2024, 4, 20), 64_940.00),
+    ]
+}
+
+/// 历次周期高点（日期，收盘价约数），按减半序号对应
+fn cycle_peaks() -> Vec<(u32, NaiveDate, f64)> {
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).expect("有效日期");
+    vec![
+        (1, date(2013, 12, 4), 1_151.17),
+        (2, date(2017, 12, 17), 19_497.40),
+        (3, date(2021, 11, 8), 67_566.83),
+    ]
+}
+
+/// 比特币周期位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CyclePosition {
+    /// 计算时间
+    pub timestamp: DateTime<Utc>,
+    /// 当前区块高度
+    pub block_height: u64,
+    /// 区块高度是否为按时间推算的估计值（链上API不可用时）
+    pub height_estimated: bool,
+    /// 最近一次减半
+    pub last_halving: Halving,
+    /// 距最近一次减半的天数
+    pub days_since_halving: i64,
+    /// 下次减半的区块高度
+    pub next_halving_height: u64,
+    /// 距下次减半的区块数
+    pub blocks_until_halving: u64,
+    /// 用于估算的平均出块时间（秒）
+    pub average_block_seconds: f64,
+    /// 下次减半的预计时间
+    pub estimated_next_halving: DateTime<Utc>,
+    /// 距下次减半的预计天数
+    pub days_until_halving: i64,
+    /// 当前周期进度（百分比，按区块计算）
+    pub cycle_progress_pct: f64,
+    /// 当前BTC价格
+    pub btc_price: Option<f64>,
+    /// 自最近一次减半以来的涨幅（百分比）
+    pub return_since_halving_pct: Option<f64>,
+    /// 历次周期表现
+    pub past_cycles: Vec<PastCycle>,
+    /// 历次周期减半到高点的平均天数
+    pub average_days_to_peak: f64,
+}
+
+/// 计算比特币周期位置
+/// 
+/// # 参数
+/// * `now` - 当前时间
+/// * `block_height` - 当前区块高度（None时按最近一次减半以来的时间和目标出块时间推算）
+/// * `average_block_seconds` - 平均出块时间（秒），用于预计下次减半时间
+/// * `btc_price` - 当前BTC价格
+/// 
+/// # 返回
+/// * `CyclePosition` - 周期位置与历次周期对比
+pub fn compute_cycle(
+    now: DateTime<Utc>,
+    block_height: Option<u64>,
+    average_block_seconds: f64,
+    btc_price: Option<f64>,
+) -> CyclePosition {
+    let halvings = halvings();
+    let today = now.date_naive();
+    
+    let (height, height_estimated) = match block_height {
+        Some(height) => (height, false),
+        None => {
+            let latest = halvings.iter().rev().find(|h| h.date <= today).unwrap_or(&halvings[0]);
+            let elapsed = (now - latest.date.and_hms_opt(0, 0, 0).expect("有效时间").and_utc()).num_seconds().max(0);
+            (latest.height + (elapsed as f64 / TARGET_BLOCK_SECONDS) as u64, true)
+        }
+    };
+    
+    let last_halving = halvings.iter()
+        .rev()
+        .find(|h| h.height <= height)
+        .unwrap_or(&halvings[0])
+        .clone();
+    let next_halving_height = (height / HALVING_INTERVAL_BLOCKS + 1) * HALVING_INTERVAL_BLOCKS;
+    let blocks_until_halving = next_halving_height - height;
+    let estimated_next_halving = now + Duration::seconds((blocks_until_halving as f64 * average_block_seconds) as i64);
+    
+    let past_cycles: Vec<PastCycle> = cycle_peaks().into_iter()
+        .filter_map(|(number, peak_date, peak_price)| {
+            let halving = halvings.iter().find(|h| h.number == number)?;
+            Some(PastCycle {
+                halving: number,
+                peak_date,
+                days_to_peak: (peak_date - halving.date).num_days(),
+                peak_return_pct: (peak_price / halving.price_usd - 1.0) * 100.0,
+            })
+        })
+        .collect();
+    let average_days_to_peak = past_cycles.iter().map(|c| c.days_to_peak as f64).sum::<f64>() / past_cycles.len().max(1) as f64;
+    
+    CyclePosition {
+        timestamp: now,
+        block_height: height,
+        height_estimated,
+        days_since_halving: (today - last_halving.date).num_days(),
+        return_since_halving_pct: btc_price.map(|price| (price / last_halving.price_usd - 1.0) * 100.0),
+        last_halving,
+        next_halving_height,
+        blocks_until_halving,
+        average_block_seconds,
+        days_until_halving: (estimated_next_halving - now).num_days(),
+        estimated_next_halving,
+        cycle_progress_pct: (HALVING_INTERVAL_BLOCKS - blocks_until_halving) as f64 / HALVING_INTERVAL_BLOCKS as f64 * 100.0,
+        btc_price,
+        past_cycles,
+        average_days_to_peak,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_cycle_from_height() {
+        let now = NaiveDate::from_ymd_opt(2025, 4, 20).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let cycle = compute_cycle(now, Some(892_500), TARGET_BLOCK_SECONDS, Some(129_880.0));
+        
+        assert_eq!(cycle.last_halving.number, 4);
+        assert_eq!(cycle.days_since_halving, 365);
+        assert_eq!(cycle.next_halving_height, 1_050_000);
+        assert_eq!(cycle.blocks_until_halving, 157_500);
+        assert_eq!(cycle.cycle_progress_pct, 25.0);
+        assert!((cycle.return_since_halving_pct.unwrap() - 100.0).abs() < 0.01);
+        assert_eq!(cycle.past_cycles.len(), 3);
+        
+        let estimated = compute_cycle(now, None, TARGET_BLOCK_SECONDS, None);
+        assert!(estimated.height_estimated);
+        assert_eq!(estimated.last_halving.number, 4);
+    }
+}
@@ -0,0 +1,251 @@
+use anyhow::{anyhow, bail, Result};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 二元运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpressionOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// 派生指标表达式
+///
+/// 支持 `+ - * /`、括号、一元负号、数字常量和指标引用；
+/// 指标引用可以是存储中的指标名称（如 `fear_greed_index`）或缓存指标键（如 `price:ethereum`），
+/// 名称中含 `-` 等字符时用双引号包裹（如 `"price:usd-coin"`）
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricExpression {
+    /// 数字常量
+    Number(f64),
+    /// 指标引用
+    Metric(String),
+    /// 取负
+    Neg(Box<MetricExpression>),
+    /// 二元运算
+    Binary(ExpressionOp, Box<MetricExpression>, Box<MetricExpression>),
+}
+
+impl MetricExpression {
+    /// 解析表达式
+    ///
+    /// # 参数
+    /// * `input` - 表达式文本（如 `price:ethereum / price:bitcoin`）
+    ///
+    /// # 返回
+    /// * `Result<MetricExpression>` - 语法错误时返回错误
+    pub fn parse(input: &str) -> Result<Self> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expression = parser.expression()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            bail!("表达式在 {:?} 处有多余内容", token);
+        }
+        Ok(expression)
+    }
+
+    /// 表达式引用的指标
+    pub fn inputs(&self) -> BTreeSet<String> {
+        let mut inputs = BTreeSet::new();
+        self.collect_inputs(&mut inputs);
+        inputs
+    }
+
+    fn collect_inputs(&self, inputs: &mut BTreeSet<String>) {
+        match self {
+            MetricExpression::Number(_) => {}
+            MetricExpression::Metric(name) => {
+                inputs.insert(name.clone());
+            }
+            MetricExpression::Neg(inner) => inner.collect_inputs(inputs),
+            MetricExpression::Binary(_, left, right) => {
+                left.collect_inputs(inputs);
+                right.collect_inputs(inputs);
+            }
+        }
+    }
+
+    /// 计算表达式
+    ///
+    /// # 参数
+    /// * `values` - 指标引用 -> 当前值
+    ///
+    /// # 返回
+    /// * `Result<f64>` - 缺少输入、除数为零或结果不是有限数时返回错误
+    pub fn evaluate(&self, values: &BTreeMap<String, f64>) -> Result<f64> {
+        let result = match self {
+            MetricExpression::Number(value) => *value,
+            MetricExpression::Metric(name) => *values.get(name).ok_or_else(|| anyhow!("缺少指标 {} 的值", name))?,
+            MetricExpression::Neg(inner) => -inner.evaluate(values)?,
+            MetricExpression::Binary(op, left, right) => {
+                let (left, right) = (left.evaluate(values)?, right.evaluate(values)?);
+                match op {
+                    ExpressionOp::Add => left + right,
+                    ExpressionOp::Sub => left - right,
+                    ExpressionOp::Mul => left * right,
+                    ExpressionOp::Div if right == 0.0 => bail!("除数为零"),
+                    ExpressionOp::Div => left / right,
+                }
+            }
+        };
+        if !result.is_finite() {
+            bail!("计算结果无效: {}", result);
+        }
+        Ok(result)
+    }
+}
+
+/// 词法单元
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Metric(String),
+    Op(char),
+    LParen,
+    RParen,
+}
+
+/// 拆分词法单元
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '+' | '-' | '*' | '/' => {
+                tokens.push(Token::Op(c));
+                chars.next();
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                chars.next();
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                chars.next();
+            }
+            '"' => {
+                chars.next();
+                let name: String = chars.by_ref().take_while(|&c| c != '"').collect();
+                if name.trim().is_empty() {
+                    bail!("引号中的指标名称为空或引号未闭合");
+                }
+                tokens.push(Token::Metric(name));
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut number = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit() || **c == '.') {
+                    number.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Number(number.parse().map_err(|_| anyhow!("无效的数字: {}", number))?));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | ':' | '.')) {
+                    name.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Metric(name));
+            }
+            other => bail!("表达式中有无法识别的字符: {}", other),
+        }
+    }
+    if tokens.is_empty() {
+        bail!("表达式为空");
+    }
+    Ok(tokens)
+}
+
+/// 递归下降解析器
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// 下一个词法单元是给定运算符之一时消费并返回
+    fn operator(&mut self, ops: &[char]) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) if ops.contains(op) => {
+                self.pos += 1;
+                Some(*op)
+            }
+            _ => None,
+        }
+    }
+
+    fn expression(&mut self) -> Result<MetricExpression> {
+        let mut left = self.term()?;
+        while let Some(op) = self.operator(&['+', '-']) {
+            let op = if op == '+' { ExpressionOp::Add } else { ExpressionOp::Sub };
+            left = MetricExpression::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<MetricExpression> {
+        let mut left = self.factor()?;
+        while let Some(op) = self.operator(&['*', '/']) {
+            let op = if op == '*' { ExpressionOp::Mul } else { ExpressionOp::Div };
+            left = MetricExpression::Binary(op, Box::new(left), Box::new(self.factor()?));
+        }
+        Ok(left)
+    }
+
+    fn factor(&mut self) -> Result<MetricExpression> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(MetricExpression::Number(value)),
+            Some(Token::Metric(name)) => Ok(MetricExpression::Metric(name)),
+            Some(Token::Op('-')) => Ok(MetricExpression::Neg(Box::new(self.factor()?))),
+            Some(Token::LParen) => {
+                let inner = self.expression()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => bail!("缺少右括号"),
+                }
+            }
+            Some(token) => bail!("表达式在 {:?} 处语法错误", token),
+            None => bail!("表达式不完整"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_evaluate() {
+        let expression = MetricExpression::parse("price:ethereum / price:bitcoin").unwrap();
+        assert_eq!(
+            expression.inputs().into_iter().collect::<Vec<_>>(),
+            vec!["price:bitcoin".to_string(), "price:ethereum".to_string()]
+        );
+        let values = BTreeMap::from([
+            ("price:ethereum".to_string(), 3000.0),
+            ("price:bitcoin".to_string(), 60000.0),
+            ("price:usd-coin".to_string(), 1.0),
+        ]);
+        assert_eq!(expression.evaluate(&values).unwrap(), 0.05);
+
+        // 优先级、括号、负号和带引号的名称
+        let expression = MetricExpression::parse("-(1 + 2) * 3 + \"price:usd-coin\" / 0.5").unwrap();
+        assert_eq!(expression.evaluate(&values).unwrap(), -7.0);
+
+        assert!(MetricExpression::parse("price:bitcoin /").is_err());
+        assert!(MetricExpression::parse("(1 + 2").is_err());
+        assert!(MetricExpression::parse("1 2").is_err());
+        assert!(MetricExpression::parse("1 / (price:bitcoin - price:bitcoin)").unwrap().evaluate(&values).is_err());
+        assert!(MetricExpression::parse("fear_greed_index").unwrap().evaluate(&values).is_err());
+    }
+}
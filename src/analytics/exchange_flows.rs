@@ -0,0 +1,150 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 交易所余额在一段时间内的变化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowTrend {
+    /// 统计天数
+    pub days: i64,
+    /// 期间净流入（正数为流入交易所，负数为流出）
+    pub net_flow: f64,
+    /// 相对期初余额的变化（百分比），期初数据不足时为None
+    pub change_pct: Option<f64>,
+}
+
+/// 单日交易所余额与净流量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyFlow {
+    /// 日期（UTC）
+    pub date: NaiveDate,
+    /// 日末交易所余额
+    pub balance: f64,
+    /// 当日净流入（与前一日余额之差）
+    pub net_flow: Option<f64>,
+}
+
+/// 单个资产的交易所资金流向
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetExchangeFlows {
+    /// 资产符号
+    pub asset: String,
+    /// 最新数据日期
+    pub as_of: NaiveDate,
+    /// 最新交易所余额
+    pub balance: f64,
+    /// 最近一日净流入
+    pub net_flow_24h: Option<f64>,
+    /// 7日趋势
+    pub trend_7d: FlowTrend,
+    /// 30日趋势
+    pub trend_30d: FlowTrend,
+    /// 每日余额与净流量（按日期升序）
+    pub daily: Vec<DailyFlow>,
+}
+
+impl AssetExchangeFlows {
+    /// 资金流向情绪分（0-100）
+    ///
+    /// 币从交易所流出通常意味着囤币、抛压减轻，视为偏乐观；
+    /// 7日余额每减少1%加10分，以50为中性并截断到0-100
+    pub fn sentiment_score(&self) -> Option<f64> {
+        self.trend_7d.change_pct.map(|pct| (50.0 - pct * 10.0).clamp(0.0, 100.0))
+    }
+}
+
+/// 交易所资金流向汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeFlows {
+    /// 计算时间
+    pub timestamp: DateTime<Utc>,
+    /// 各资产资金流向
+    pub assets: Vec<AssetExchangeFlows>,
+}
+
+impl ExchangeFlows {
+    /// 各资产情绪分的平均值
+    pub fn sentiment_score(&self) -> Option<f64> {
+        let scores: Vec<f64> = self.assets.iter().filter_map(AssetExchangeFlows::sentiment_score).collect();
+        if scores.is_empty() {
+            return None;
+        }
+        Some(scores.iter().sum::<f64>() / scores.len() as f64)
+    }
+}
+
+/// 根据每日交易所余额计算资金流向
+///
+/// 净流量按相邻两日余额之差计算，同一天有多个数据点时取最后一个
+///
+/// # 参数
+/// * `asset` - 资产符号
+/// * `balances` - 交易所余额时间序列
+///
+/// # 返回
+/// * `Option<AssetExchangeFlows>` - 没有数据时为None
+pub fn summarize_flows(asset: &str, balances: &[(DateTime<Utc>, f64)]) -> Option<AssetExchangeFlows> {
+    let by_day: BTreeMap<NaiveDate, f64> = balances.iter()
+        .map(|(timestamp, balance)| (timestamp.date_naive(), *balance))
+        .collect();
+    let (&as_of, &balance) = by_day.last_key_value()?;
+
+    let mut previous: Option<(NaiveDate, f64)> = None;
+    let daily: Vec<DailyFlow> = by_day.iter()
+        .map(|(&date, &balance)| {
+            let net_flow = previous
+                .filter(|(prev_date, _)| date - *prev_date == Duration::days(1))
+                .map(|(_, prev_balance)| balance - prev_balance);
+            previous = Some((date, balance));
+            DailyFlow { date, balance, net_flow }
+        })
+        .collect();
+
+    let trend = |days: i64| {
+        let start = as_of - Duration::days(days);
+        let net_flow = daily.iter()
+            .filter(|d| d.date > start)
+            .filter_map(|d| d.net_flow)
+            .sum();
+        let change_pct = by_day.range(..=start)
+            .next_back()
+            .filter(|(_, &opening)| opening > 0.0)
+            .map(|(_, &opening)| (balance - opening) / opening * 100.0);
+        FlowTrend { days, net_flow, change_pct }
+    };
+
+    Some(AssetExchangeFlows {
+        asset: asset.to_uppercase(),
+        as_of,
+        balance,
+        net_flow_24h: daily.last().and_then(|d| d.net_flow),
+        trend_7d: trend(7),
+        trend_30d: trend(30),
+        daily,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_flows_trends() {
+        let start = Utc::now() - Duration::days(40);
+        // 余额每天减少10个，第40天为1600
+        let balances: Vec<(DateTime<Utc>, f64)> = (0..=40)
+            .map(|i| (start + Duration::days(i), 2000.0 - i as f64 * 10.0))
+            .collect();
+
+        let flows = summarize_flows("btc", &balances).unwrap();
+        assert_eq!(flows.asset, "BTC");
+        assert_eq!(flows.balance, 1600.0);
+        assert_eq!(flows.net_flow_24h, Some(-10.0));
+        assert_eq!(flows.trend_7d.net_flow, -70.0);
+        assert_eq!(flows.trend_30d.net_flow, -300.0);
+        // 7日前余额1670，减少约4.19%
+        let pct = flows.trend_7d.change_pct.unwrap();
+        assert!((pct + 70.0 / 1670.0 * 100.0).abs() < 1e-9);
+        assert!(flows.sentiment_score().unwrap() > 90.0);
+    }
+}
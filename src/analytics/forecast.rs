@@ -0,0 +1,198 @@
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// 95%置信区间对应的标准正态分位数
+const Z_95: f64 = 1.96;
+
+/// 预测模型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForecastModel {
+    /// 指数加权移动平均（简单指数平滑），预测值为水平线
+    #[default]
+    Ewma,
+    /// Holt-Winters（加法季节性），未指定季节长度时退化为Holt线性趋势
+    HoltWinters,
+}
+
+/// 平滑参数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ForecastParams {
+    /// 水平平滑系数（0-1）
+    pub alpha: f64,
+    /// 趋势平滑系数（0-1，仅Holt-Winters）
+    pub beta: f64,
+    /// 季节平滑系数（0-1，仅Holt-Winters）
+    pub gamma: f64,
+    /// 季节长度（数据点数，仅Holt-Winters，可选）
+    pub season_length: Option<usize>,
+}
+
+impl Default for ForecastParams {
+    fn default() -> Self {
+        Self {
+            alpha: 0.3,
+            beta: 0.1,
+            gamma: 0.1,
+            season_length: None,
+        }
+    }
+}
+
+/// 预测点
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ForecastPoint {
+    /// 向前的步数（从1开始）
+    pub step: usize,
+    /// 预测值
+    pub value: f64,
+    /// 95%置信区间下界
+    pub lower: f64,
+    /// 95%置信区间上界
+    pub upper: f64,
+}
+
+/// 对等间隔序列做短期外推
+///
+/// 置信区间按样本内一步预测残差的标准差估算，随步数按平方根展宽，仅作近似
+///
+/// # 参数
+/// * `model` - 预测模型
+/// * `values` - 按时间升序的等间隔数值
+/// * `horizon` - 预测步数
+/// * `params` - 平滑参数
+///
+/// # 返回
+/// * `Result<Vec<ForecastPoint>>` - 参数无效或数据点不足时返回错误
+pub fn forecast(model: ForecastModel, values: &[f64], horizon: usize, params: &ForecastParams) -> Result<Vec<ForecastPoint>> {
+    for (name, value) in [("alpha", params.alpha), ("beta", params.beta), ("gamma", params.gamma)] {
+        if !(0.0..=1.0).contains(&value) {
+            bail!("平滑系数 {} 须在0到1之间: {}", name, value);
+        }
+    }
+    if horizon == 0 {
+        bail!("预测步数须大于0");
+    }
+
+    let (residuals, projection): (Vec<f64>, Box<dyn Fn(usize) -> f64>) = match model {
+        ForecastModel::Ewma => {
+            if values.len() < 2 {
+                bail!("至少需要2个数据点");
+            }
+            let (residuals, level) = ewma(values, params.alpha);
+            (residuals, Box::new(move |_| level))
+        }
+        ForecastModel::HoltWinters => match params.season_length {
+            Some(season) if season >= 2 => {
+                let required = season.checked_mul(2).ok_or_else(|| anyhow!("季节长度过大: {}", season))?;
+                if values.len() < required {
+                    bail!("季节长度为 {} 时至少需要 {} 个数据点", season, required);
+                }
+                let (residuals, level, trend, seasonals) = holt_winters(values, season, params);
+                (residuals, Box::new(move |h| level + h as f64 * trend + seasonals[(h - 1) % season]))
+            }
+            Some(season) => bail!("季节长度须至少为2: {}", season),
+            None => {
+                if values.len() < 3 {
+                    bail!("至少需要3个数据点");
+                }
+                let (residuals, level, trend) = holt(values, params);
+                (residuals, Box::new(move |h| level + h as f64 * trend))
+            }
+        },
+    };
+
+    let sigma = if residuals.is_empty() {
+        0.0
+    } else {
+        (residuals.iter().map(|r| r * r).sum::<f64>() / residuals.len() as f64).sqrt()
+    };
+    Ok((1..=horizon)
+        .map(|step| {
+            let value = projection(step);
+            let band = Z_95 * sigma * (step as f64).sqrt();
+            ForecastPoint { step, value, lower: value - band, upper: value + band }
+        })
+        .collect())
+}
+
+/// 简单指数平滑，返回（一步预测残差，最终水平）
+fn ewma(values: &[f64], alpha: f64) -> (Vec<f64>, f64) {
+    let mut level = values[0];
+    let mut residuals = Vec::with_capacity(values.len() - 1);
+    for &y in &values[1..] {
+        residuals.push(y - level);
+        level = alpha * y + (1.0 - alpha) * level;
+    }
+    (residuals, level)
+}
+
+/// Holt线性趋势，返回（一步预测残差，最终水平，最终趋势）
+fn holt(values: &[f64], params: &ForecastParams) -> (Vec<f64>, f64, f64) {
+    let mut level = values[0];
+    let mut trend = values[1] - values[0];
+    let mut residuals = Vec::with_capacity(values.len() - 1);
+    for &y in &values[1..] {
+        residuals.push(y - (level + trend));
+        let previous = level;
+        level = params.alpha * y + (1.0 - params.alpha) * (level + trend);
+        trend = params.beta * (level - previous) + (1.0 - params.beta) * trend;
+    }
+    (residuals, level, trend)
+}
+
+/// 加法季节性Holt-Winters，返回（一步预测残差，最终水平，最终趋势，后续一个季节的季节项）
+///
+/// 初始水平取第一个季节的均值，初始趋势取前两个季节均值之差除以季节长度
+fn holt_winters(values: &[f64], season: usize, params: &ForecastParams) -> (Vec<f64>, f64, f64, Vec<f64>) {
+    let first_mean = values[..season].iter().sum::<f64>() / season as f64;
+    let second_mean = values[season..season * 2].iter().sum::<f64>() / season as f64;
+    let mut level = first_mean;
+    let mut trend = (second_mean - first_mean) / season as f64;
+    let mut seasonals: Vec<f64> = values[..season].iter().map(|y| y - first_mean).collect();
+
+    let mut residuals = Vec::with_capacity(values.len() - season);
+    for (t, &y) in values.iter().enumerate().skip(season) {
+        let seasonal = seasonals[t - season];
+        residuals.push(y - (level + trend + seasonal));
+        let previous = level;
+        level = params.alpha * (y - seasonal) + (1.0 - params.alpha) * (level + trend);
+        trend = params.beta * (level - previous) + (1.0 - params.beta) * trend;
+        seasonals.push(params.gamma * (y - level) + (1.0 - params.gamma) * seasonal);
+    }
+    let next_season = seasonals[seasonals.len() - season..].to_vec();
+    (residuals, level, trend, next_season)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forecast_models() {
+        let params = ForecastParams::default();
+
+        // 常数序列：预测值不变，置信区间为零宽
+        let flat = forecast(ForecastModel::Ewma, &[5.0; 10], 3, &params).unwrap();
+        assert!(flat.iter().all(|p| p.value == 5.0 && p.lower == 5.0 && p.upper == 5.0));
+
+        // 线性趋势：Holt延续趋势
+        let linear: Vec<f64> = (0..20).map(|i| i as f64 * 2.0).collect();
+        let trend = forecast(ForecastModel::HoltWinters, &linear, 2, &params).unwrap();
+        assert!((trend[0].value - 40.0).abs() < 1e-6);
+        assert!((trend[1].value - 42.0).abs() < 1e-6);
+
+        // 季节性：按季节位置外推，区间随步数展宽
+        let seasonal: Vec<f64> = (0..24).map(|i| [10.0, 20.0, 30.0, 20.0][i % 4] + i as f64 * 0.1).collect();
+        let season_params = ForecastParams { season_length: Some(4), ..params };
+        let points = forecast(ForecastModel::HoltWinters, &seasonal, 4, &season_params).unwrap();
+        assert!((points[0].value - 12.4).abs() < 0.5);
+        assert!((points[2].value - 32.6).abs() < 0.5);
+        assert!(points[3].upper - points[3].lower >= points[0].upper - points[0].lower);
+
+        assert!(forecast(ForecastModel::HoltWinters, &seasonal[..6], 4, &season_params).is_err());
+        let huge_season = ForecastParams { season_length: Some(usize::MAX), ..params };
+        assert!(forecast(ForecastModel::HoltWinters, &seasonal, 4, &huge_season).is_err());
+        assert!(forecast(ForecastModel::Ewma, &[1.0, 2.0], 3, &ForecastParams { alpha: 1.5, ..params }).is_err());
+    }
+}
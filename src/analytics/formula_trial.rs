@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::clients::CoinMarketCapClient;
+use crate::models::AggregatedMetric;
+
+/// 版本标签（`baseline` / `candidate`）
+pub const LABEL_VARIANT: &str = "variant";
+
+/// 公式标签（如 `change_24h`）
+pub const LABEL_FORMULA: &str = "formula";
+
+/// 并行计算公式的指数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrialIndex {
+    /// 山寨币季节指数
+    AltcoinSeason,
+    /// 综合情绪分
+    CompositeSentiment,
+}
+
+impl TrialIndex {
+    /// 按名称解析（`altcoin_season` / `composite_sentiment`）
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "altcoin_season" => Some(Self::AltcoinSeason),
+            "composite_sentiment" => Some(Self::CompositeSentiment),
+            _ => None,
+        }
+    }
+
+    /// 两个版本写入的指标名称
+    pub fn metric_name(&self) -> &'static str {
+        match self {
+            Self::AltcoinSeason => "formula_trial_altcoin_season",
+            Self::CompositeSentiment => "formula_trial_composite_sentiment",
+        }
+    }
+
+    /// 指数值对应的分类（用于统计两个版本的分类是否一致）
+    pub fn classify(&self, value: f64) -> &'static str {
+        let value = value.round().clamp(0.0, 100.0) as u8;
+        match self {
+            Self::AltcoinSeason => CoinMarketCapClient::get_altcoin_season_classification_zh(value),
+            Self::CompositeSentiment => CoinMarketCapClient::get_sentiment_description(value),
+        }
+    }
+}
+
+/// 公式版本
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrialVariant {
+    /// 现行公式
+    Baseline,
+    /// 候选公式
+    Candidate,
+}
+
+impl TrialVariant {
+    /// 转换为字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Baseline => "baseline",
+            Self::Candidate => "candidate",
+        }
+    }
+}
+
+/// 同一时间两个版本的指数值
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparisonPoint {
+    /// 计算时间
+    pub timestamp: DateTime<Utc>,
+    /// 现行公式的值
+    pub baseline: f64,
+    /// 候选公式的值
+    pub candidate: f64,
+    /// 候选值减现行值
+    pub difference: f64,
+    /// 两个版本的分类是否不同
+    pub classification_changed: bool,
+}
+
+/// 两个版本的差异统计
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ComparisonSummary {
+    /// 成对的样本数
+    pub samples: usize,
+    /// 平均差值（候选减现行）
+    pub mean_difference: Option<f64>,
+    /// 平均绝对差值
+    pub mean_abs_difference: Option<f64>,
+    /// 最大绝对差值
+    pub max_abs_difference: Option<f64>,
+    /// 分类一致的样本占比（%）
+    pub classification_agreement_pct: Option<f64>,
+}
+
+/// 公式比较结果
+#[derive(Debug, Clone, Serialize)]
+pub struct FormulaComparison {
+    /// 指数
+    pub index: TrialIndex,
+    /// 现行公式（取最新样本的标签）
+    pub baseline_formula: Option<String>,
+    /// 候选公式（取最新样本的标签）
+    pub candidate_formula: Option<String>,
+    /// 差异统计
+    pub summary: ComparisonSummary,
+    /// 按时间排序的成对样本
+    pub points: Vec<ComparisonPoint>,
+}
+
+/// 按时间戳配对两个版本的数据点并统计差异
+///
+/// 只有两个版本都有数值的时间点参与比较
+///
+/// # 参数
+/// * `index` - 指数
+/// * `baseline` - 现行公式的数据点
+/// * `candidate` - 候选公式的数据点
+pub fn compare_variants(index: TrialIndex, baseline: &[AggregatedMetric], candidate: &[AggregatedMetric]) -> FormulaComparison {
+    let candidates: BTreeMap<DateTime<Utc>, f64> = candidate.iter()
+        .filter_map(|m| Some((m.timestamp, m.value.as_f64()?)))
+        .collect();
+    let mut points: Vec<ComparisonPoint> = baseline.iter()
+        .filter_map(|m| {
+            let baseline = m.value.as_f64()?;
+            let candidate = *candidates.get(&m.timestamp)?;
+            Some(ComparisonPoint {
+                timestamp: m.timestamp,
+                baseline,
+                candidate,
+                difference: candidate - baseline,
+                classification_changed: index.classify(baseline) != index.classify(candidate),
+            })
+        })
+        .collect();
+    points.sort_by_key(|p| p.timestamp);
+
+    let samples = points.len();
+    let summary = if samples == 0 {
+        ComparisonSummary::default()
+    } else {
+        let n = samples as f64;
+        ComparisonSummary {
+            samples,
+            mean_difference: Some(points.iter().map(|p| p.difference).sum::<f64>() / n),
+            mean_abs_difference: Some(points.iter().map(|p| p.difference.abs()).sum::<f64>() / n),
+            max_abs_difference: points.iter().map(|p| p.difference.abs()).reduce(f64::max),
+            classification_agreement_pct: Some(points.iter().filter(|p| !p.classification_changed).count() as f64 / n * 100.0),
+        }
+    };
+    let formula = |points: &[AggregatedMetric]| points.iter()
+        .max_by_key(|m| m.timestamp)
+        .and_then(|m| m.labels.get(LABEL_FORMULA).cloned());
+
+    FormulaComparison {
+        index,
+        baseline_formula: formula(baseline),
+        candidate_formula: formula(candidate),
+        summary,
+        points,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataSource, MetricBuilder, MetricValue};
+
+    fn point(value: f64, minutes: i64, formula: &str) -> AggregatedMetric {
+        MetricBuilder::new(DataSource::EVERSCAN, TrialIndex::AltcoinSeason.metric_name())
+            .value(MetricValue::scalar(value, "index"))
+            .timestamp(DateTime::<Utc>::UNIX_EPOCH + chrono::Duration::minutes(minutes))
+            .label(LABEL_FORMULA, formula)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_compare_variants_pairs_by_timestamp() {
+        let baseline = vec![point(70.0, 0, "change_24h"), point(80.0, 60, "change_24h"), point(50.0, 120, "change_24h")];
+        let candidate = vec![point(76.0, 0, "change_90d"), point(78.0, 60, "change_90d")];
+        let comparison = compare_variants(TrialIndex::AltcoinSeason, &baseline, &candidate);
+
+        assert_eq!(comparison.summary.samples, 2);
+        assert_eq!(comparison.summary.mean_difference, Some(2.0));
+        assert_eq!(comparison.summary.max_abs_difference, Some(6.0));
+        // 70 → 76 跨过山寨币季节阈值，80 → 78 分类不变
+        assert_eq!(comparison.summary.classification_agreement_pct, Some(50.0));
+        assert!(comparison.points[0].classification_changed);
+        assert_eq!(comparison.candidate_formula.as_deref(), Some("change_90d"));
+
+        let empty = compare_variants(TrialIndex::CompositeSentiment, &baseline, &[]);
+        assert_eq!(empty.summary.samples, 0);
+        assert!(empty.summary.mean_difference.is_none());
+    }
+}
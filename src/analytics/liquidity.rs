@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::clients::OrderBook;
+
+/// 交易对的流动性快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquiditySnapshot {
+    /// 交易对（如 BTCUSDT）
+    pub symbol: String,
+    /// 快照时间
+    pub timestamp: DateTime<Utc>,
+    /// 最优买价
+    pub best_bid: f64,
+    /// 最优卖价
+    pub best_ask: f64,
+    /// 中间价
+    pub mid_price: f64,
+    /// 买卖价差（基点）
+    pub spread_bps: f64,
+    /// 统计深度的价格范围（距中间价的百分比）
+    pub depth_pct: f64,
+    /// 范围内的买单金额（计价货币）
+    pub bid_depth: f64,
+    /// 范围内的卖单金额（计价货币）
+    pub ask_depth: f64,
+    /// 买卖盘不平衡度（-1到1，正数为买盘更厚）
+    pub imbalance: Option<f64>,
+    /// 返回的价位未覆盖整个范围（深度可能被低估）
+    pub truncated: bool,
+}
+
+/// 范围内的挂单金额，以及价位是否覆盖到范围边界
+fn depth_within<'a>(levels: impl Iterator<Item = &'a (f64, f64)>, in_range: impl Fn(f64) -> bool) -> (f64, bool) {
+    let mut depth = 0.0;
+    for (price, quantity) in levels {
+        if !in_range(*price) {
+            return (depth, true);
+        }
+        depth += price * quantity;
+    }
+    (depth, false)
+}
+
+/// 由订单簿计算流动性快照
+///
+/// # 参数
+/// * `symbol` - 交易对
+/// * `book` - 订单簿（买单价格从高到低，卖单价格从低到高）
+/// * `depth_pct` - 统计深度的价格范围（距中间价的百分比，如2表示±2%）
+/// * `timestamp` - 快照时间
+///
+/// # 返回
+/// * `Option<LiquiditySnapshot>` - 买卖任一侧为空或价格无效时为None
+pub fn summarize_order_book(symbol: &str, book: &OrderBook, depth_pct: f64, timestamp: DateTime<Utc>) -> Option<LiquiditySnapshot> {
+    let best_bid = book.bids.first()?.0;
+    let best_ask = book.asks.first()?.0;
+    if best_bid <= 0.0 || best_ask < best_bid {
+        return None;
+    }
+    let mid_price = (best_bid + best_ask) / 2.0;
+    let band = mid_price * depth_pct / 100.0;
+
+    let (bid_depth, bid_covered) = depth_within(book.bids.iter(), |price| price >= mid_price - band);
+    let (ask_depth, ask_covered) = depth_within(book.asks.iter(), |price| price <= mid_price + band);
+    let total = bid_depth + ask_depth;
+
+    Some(LiquiditySnapshot {
+        symbol: symbol.to_uppercase(),
+        timestamp,
+        best_bid,
+        best_ask,
+        mid_price,
+        spread_bps: (best_ask - best_bid) / mid_price * 10_000.0,
+        depth_pct,
+        bid_depth,
+        ask_depth,
+        imbalance: (total > 0.0).then(|| (bid_depth - ask_depth) / total),
+        truncated: !(bid_covered && ask_covered),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_order_book() {
+        let book = OrderBook {
+            bids: vec![(99.9, 10.0), (99.0, 5.0), (97.0, 100.0)],
+            asks: vec![(100.1, 2.0), (101.0, 3.0), (103.0, 100.0)],
+        };
+
+        let snapshot = summarize_order_book("btcusdt", &book, 2.0, Utc::now()).unwrap();
+        assert_eq!(snapshot.symbol, "BTCUSDT");
+        assert_eq!(snapshot.mid_price, 100.0);
+        assert!((snapshot.spread_bps - 20.0).abs() < 1e-6);
+        // ±2%：买单到98，卖单到102
+        assert!((snapshot.bid_depth - (999.0 + 495.0)).abs() < 1e-6);
+        assert!((snapshot.ask_depth - (200.2 + 303.0)).abs() < 1e-6);
+        assert!(snapshot.imbalance.unwrap() > 0.0);
+        assert!(!snapshot.truncated);
+
+        // 返回的价位没有覆盖整个范围
+        let thin = OrderBook { bids: vec![(99.9, 1.0)], asks: vec![(100.1, 1.0)] };
+        assert!(summarize_order_book("ETHUSDT", &thin, 2.0, Utc::now()).unwrap().truncated);
+        assert!(summarize_order_book("ETHUSDT", &OrderBook::default(), 2.0, Utc::now()).is_none());
+    }
+}
@@ -0,0 +1,35 @@
+pub mod aggregates;
+pub mod anomaly;
+pub mod breadth;
+pub mod cycle;
+pub mod derived;
+pub mod exchange_flows;
+#[cfg(feature = "forecast")]
+pub mod forecast;
+pub mod formula_trial;
+pub mod liquidity;
+pub mod nodes;
+pub mod options;
+pub mod ratios;
+pub mod scenario;
+pub mod sectors;
+pub mod sentiment;
+pub mod social;
+
+pub use aggregates::*;
+pub use anomaly::*;
+pub use breadth::*;
+pub use cycle::*;
+pub use derived::*;
+pub use exchange_flows::*;
+#[cfg(feature = "forecast")]
+pub use forecast::*;
+pub use formula_trial::*;
+pub use liquidity::*;
+pub use nodes::*;
+pub use options::*;
+pub use ratios::*;
+pub use scenario::*;
+pub use sectors::*;
+pub use sentiment::*;
+pub use social::*;
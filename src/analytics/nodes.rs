@@ -0,0 +1,144 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::{Alert, AlertSeverity};
+use crate::clients::{NodeChain, NodeProbe};
+use crate::config::NodeEndpoint;
+
+/// 自建节点的健康状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeStatus {
+    /// 节点名称
+    pub name: String,
+    /// 节点所属的链
+    pub chain: NodeChain,
+    /// 探测时间
+    pub timestamp: DateTime<Utc>,
+    /// 节点是否可访问
+    pub reachable: bool,
+    /// 节点的最新高度
+    pub height: Option<u64>,
+    /// 参考高度（同链节点和参考节点中的最高值）
+    pub reference_height: Option<u64>,
+    /// 落后参考高度的区块数
+    pub lag: Option<u64>,
+    /// 允许落后的区块数
+    pub max_lag: u64,
+    /// 连接的对等节点数
+    pub peers: Option<u64>,
+    /// 节点是否报告正在同步
+    pub syncing: Option<bool>,
+    /// 响应延迟（毫秒）
+    pub latency_ms: Option<f64>,
+    /// 探测失败的原因
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 是否健康（可访问且落后不超过允许值）
+    pub healthy: bool,
+    /// 是否本次新变为不健康
+    pub newly_unhealthy: bool,
+}
+
+impl NodeStatus {
+    /// 由探测结果计算节点状态
+    ///
+    /// # 参数
+    /// * `endpoint` - 节点配置
+    /// * `probe` - 探测结果
+    /// * `reference_height` - 参考高度（同链节点和参考节点中的最高值）
+    /// * `previous` - 上一次的状态（用于判断是否新变为不健康）
+    /// * `now` - 探测时间
+    ///
+    /// # 返回
+    /// * `NodeStatus` - 节点状态
+    pub fn evaluate(
+        endpoint: &NodeEndpoint,
+        probe: &Result<NodeProbe>,
+        reference_height: Option<u64>,
+        previous: Option<&NodeStatus>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let max_lag = endpoint.max_lag.unwrap_or_else(|| endpoint.chain.default_max_lag());
+        let probe_ok = probe.as_ref().ok();
+        let height = probe_ok.map(|p| p.height);
+        let lag = height.zip(reference_height).map(|(height, reference)| reference.saturating_sub(height));
+        let healthy = probe_ok.is_some() && lag.is_none_or(|lag| lag <= max_lag);
+
+        Self {
+            name: endpoint.name.clone(),
+            chain: endpoint.chain,
+            timestamp: now,
+            reachable: probe_ok.is_some(),
+            height,
+            reference_height,
+            lag,
+            max_lag,
+            peers: probe_ok.and_then(|p| p.peers),
+            syncing: probe_ok.and_then(|p| p.syncing),
+            latency_ms: probe_ok.map(|p| p.latency_ms),
+            error: probe.as_ref().err().map(|e| format!("{:#}", e)),
+            healthy,
+            newly_unhealthy: !healthy && previous.is_none_or(|p| p.healthy),
+        }
+    }
+
+    /// 节点不健康时的告警
+    ///
+    /// # 参数
+    /// * `severity` - 告警级别
+    pub fn unhealthy_alert(&self, severity: AlertSeverity) -> Option<Alert> {
+        if self.healthy {
+            return None;
+        }
+        let (rule, title, message) = match (&self.error, self.lag) {
+            (Some(error), _) => (
+                format!("nodes:down:{}", self.name),
+                format!("节点 {} 无法访问", self.name),
+                error.clone(),
+            ),
+            (None, lag) => (
+                format!("nodes:lag:{}", self.name),
+                format!("节点 {} 落后 {} 个区块", self.name, lag.unwrap_or_default()),
+                format!(
+                    "节点高度 {}，参考高度 {}，允许落后 {} 个区块",
+                    self.height.unwrap_or_default(), self.reference_height.unwrap_or_default(), self.max_lag,
+                ),
+            ),
+        };
+        Some(Alert::new(rule, severity, title, message).metadata(serde_json::json!({ "node": self })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_node_status() {
+        let endpoint = NodeEndpoint {
+            name: "geth".to_string(),
+            chain: NodeChain::Ethereum,
+            url: "http://localhost:8545".to_string(),
+            reference_url: None,
+            max_lag: None,
+        };
+        let probe = |height| Ok(NodeProbe { height, peers: Some(50), syncing: Some(false), latency_ms: 12.0 });
+        let now = Utc::now();
+
+        let synced = NodeStatus::evaluate(&endpoint, &probe(100), Some(102), None, now);
+        assert_eq!(synced.lag, Some(2));
+        assert!(synced.healthy);
+        assert!(synced.unhealthy_alert(AlertSeverity::Warning).is_none());
+
+        let behind = NodeStatus::evaluate(&endpoint, &probe(90), Some(102), Some(&synced), now);
+        assert!(!behind.healthy && behind.newly_unhealthy);
+        assert_eq!(behind.unhealthy_alert(AlertSeverity::Warning).unwrap().rule, "nodes:lag:geth");
+
+        // 持续不健康时不再标记为新发生
+        let down = NodeStatus::evaluate(&endpoint, &Err(anyhow!("connection refused")), Some(102), Some(&behind), now);
+        assert!(!down.reachable && !down.newly_unhealthy);
+        assert_eq!(down.unhealthy_alert(AlertSeverity::Warning).unwrap().rule, "nodes:down:geth");
+    }
+}
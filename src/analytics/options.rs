@@ -0,0 +1,186 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::clients::OptionSummary;
+
+/// ATM隐含波动率的目标期限（天）
+pub const ATM_TARGET_DAYS: i64 = 30;
+
+/// 期权类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionKind {
+    /// 看涨
+    Call,
+    /// 看跌
+    Put,
+}
+
+/// 解析后的期权合约
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionInstrument {
+    /// 到期日
+    pub expiry: NaiveDate,
+    /// 行权价
+    pub strike: f64,
+    /// 期权类型
+    pub kind: OptionKind,
+}
+
+impl OptionInstrument {
+    /// 解析Deribit合约名称（如 `BTC-27DEC24-60000-C`）
+    pub fn parse(name: &str) -> Option<Self> {
+        let mut parts = name.split('-');
+        let _currency = parts.next()?;
+        let expiry = NaiveDate::parse_from_str(parts.next()?, "%d%b%y").ok()?;
+        let strike = parts.next()?.replace('d', ".").parse().ok()?;
+        let kind = match parts.next()? {
+            "C" => OptionKind::Call,
+            "P" => OptionKind::Put,
+            _ => return None,
+        };
+        Some(Self { expiry, strike, kind })
+    }
+}
+
+/// 单个币种的期权市场概况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionsSnapshot {
+    /// 币种
+    pub currency: String,
+    /// DVOL波动率指数（获取失败时为None）
+    pub dvol: Option<f64>,
+    /// 平值隐含波动率（百分比）
+    pub atm_iv: Option<f64>,
+    /// 计算平值隐含波动率所用的到期日
+    pub atm_expiry: Option<NaiveDate>,
+    /// 按未平仓量计算的看跌/看涨比
+    pub put_call_oi_ratio: Option<f64>,
+    /// 按24小时成交量计算的看跌/看涨比
+    pub put_call_volume_ratio: Option<f64>,
+    /// 总未平仓量（以标的币计）
+    pub total_open_interest: f64,
+    /// 标的价格
+    pub underlying_price: Option<f64>,
+}
+
+/// 期权市场数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionsData {
+    /// 采集时间
+    pub timestamp: DateTime<Utc>,
+    /// 各币种期权概况
+    pub currencies: Vec<OptionsSnapshot>,
+}
+
+/// 看跌/看涨比，看涨为0时为None
+fn ratio(puts: f64, calls: f64) -> Option<f64> {
+    (calls > 0.0).then(|| puts / calls)
+}
+
+/// 汇总期权合约行情
+///
+/// 平值隐含波动率取到期日最接近30天的一组合约中，行权价最接近标的价格的看涨、看跌标记IV均值
+///
+/// # 参数
+/// * `currency` - 币种
+/// * `dvol` - DVOL波动率指数
+/// * `summaries` - 期权合约行情摘要
+/// * `today` - 当前日期（UTC）
+pub fn summarize_options(
+    currency: &str,
+    dvol: Option<f64>,
+    summaries: &[OptionSummary],
+    today: NaiveDate,
+) -> OptionsSnapshot {
+    let mut oi = (0.0, 0.0);
+    let mut volume = (0.0, 0.0);
+    let mut by_expiry: BTreeMap<NaiveDate, Vec<(OptionInstrument, &OptionSummary)>> = BTreeMap::new();
+
+    for summary in summaries {
+        let Some(instrument) = OptionInstrument::parse(&summary.instrument_name) else {
+            continue;
+        };
+        match instrument.kind {
+            OptionKind::Put => {
+                oi.0 += summary.open_interest;
+                volume.0 += summary.volume;
+            }
+            OptionKind::Call => {
+                oi.1 += summary.open_interest;
+                volume.1 += summary.volume;
+            }
+        }
+        if instrument.expiry > today {
+            by_expiry.entry(instrument.expiry).or_default().push((instrument, summary));
+        }
+    }
+
+    let underlying_price = summaries.iter().filter_map(|s| s.underlying_price).next_back();
+    let atm = by_expiry.iter()
+        .min_by_key(|(expiry, _)| ((**expiry - today).num_days() - ATM_TARGET_DAYS).abs())
+        .and_then(|(expiry, options)| {
+            let spot = options.iter().find_map(|(_, s)| s.underlying_price)?;
+            let atm_strike = options.iter()
+                .map(|(instrument, _)| instrument.strike)
+                .min_by(|a, b| (a - spot).abs().total_cmp(&(b - spot).abs()))?;
+            let ivs: Vec<f64> = options.iter()
+                .filter(|(instrument, _)| instrument.strike == atm_strike)
+                .filter_map(|(_, s)| s.mark_iv)
+                .collect();
+            (!ivs.is_empty()).then(|| (*expiry, ivs.iter().sum::<f64>() / ivs.len() as f64))
+        });
+
+    OptionsSnapshot {
+        currency: currency.to_uppercase(),
+        dvol,
+        atm_iv: atm.map(|(_, iv)| iv),
+        atm_expiry: atm.map(|(expiry, _)| expiry),
+        put_call_oi_ratio: ratio(oi.0, oi.1),
+        put_call_volume_ratio: ratio(volume.0, volume.1),
+        total_open_interest: oi.0 + oi.1,
+        underlying_price,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(name: &str, open_interest: f64, mark_iv: f64) -> OptionSummary {
+        OptionSummary {
+            instrument_name: name.to_string(),
+            open_interest,
+            volume: open_interest / 10.0,
+            mark_iv: Some(mark_iv),
+            underlying_price: Some(61_000.0),
+        }
+    }
+
+    #[test]
+    fn test_summarize_options() {
+        assert_eq!(
+            OptionInstrument::parse("ETH-5JAN25-3500-P"),
+            Some(OptionInstrument {
+                expiry: NaiveDate::from_ymd_opt(2025, 1, 5).unwrap(),
+                strike: 3500.0,
+                kind: OptionKind::Put,
+            })
+        );
+
+        let today = NaiveDate::from_ymd_opt(2024, 11, 25).unwrap();
+        let summaries = vec![
+            option("BTC-29NOV24-60000-C", 100.0, 40.0),
+            option("BTC-27DEC24-60000-C", 300.0, 52.0),
+            option("BTC-27DEC24-60000-P", 150.0, 54.0),
+            option("BTC-27DEC24-70000-C", 100.0, 50.0),
+            option("BTC-PERPETUAL", 1000.0, 0.0),
+        ];
+
+        let snapshot = summarize_options("btc", Some(55.0), &summaries, today);
+        assert_eq!(snapshot.atm_expiry, NaiveDate::from_ymd_opt(2024, 12, 27));
+        assert_eq!(snapshot.atm_iv, Some(53.0));
+        assert_eq!(snapshot.put_call_oi_ratio, Some(0.3));
+        assert_eq!(snapshot.total_open_interest, 650.0);
+    }
+}
@@ -0,0 +1,160 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::{Alert, AlertSeverity};
+use crate::config::RatioConfig;
+
+/// 比率突破方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RatioBreakout {
+    /// 高于回看区间最高值
+    Up,
+    /// 低于回看区间最低值
+    Down,
+}
+
+/// 比率的最新读数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatioReading {
+    /// 比率名称
+    pub name: String,
+    /// 说明
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 分子
+    pub numerator: String,
+    /// 分母
+    pub denominator: String,
+    /// 比率值
+    pub value: f64,
+    /// 相对24小时前的变化百分比
+    pub change_24h_pct: Option<f64>,
+    /// 相对7天前的变化百分比
+    pub change_7d_pct: Option<f64>,
+    /// 回看区间最高值（不含本次）
+    pub high: Option<f64>,
+    /// 回看区间最低值（不含本次）
+    pub low: Option<f64>,
+    /// 当前突破方向
+    pub breakout: Option<RatioBreakout>,
+    /// 是否本次新发生的突破
+    pub new_breakout: bool,
+    /// 计算时间
+    pub timestamp: DateTime<Utc>,
+}
+
+impl RatioReading {
+    /// 由历史读数计算最新读数
+    ///
+    /// 回看区间内少于两个历史读数时不判断突破
+    ///
+    /// # 参数
+    /// * `config` - 比率定义
+    /// * `value` - 当前比率值
+    /// * `history` - 回看区间内的历史读数（按时间升序，不含本次）
+    /// * `previous` - 上一次的读数（用于判断是否新发生突破）
+    /// * `now` - 当前时间
+    ///
+    /// # 返回
+    /// * `RatioReading` - 比率读数
+    pub fn assess(
+        config: &RatioConfig,
+        value: f64,
+        history: &[(DateTime<Utc>, f64)],
+        previous: Option<&RatioReading>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let change_since = |ago: Duration| {
+            history.iter()
+                .rev()
+                .find(|(at, _)| *at <= now - ago)
+                .filter(|(_, past)| *past > 0.0)
+                .map(|(_, past)| (value - past) / past * 100.0)
+        };
+        let high = history.iter().map(|(_, v)| *v).reduce(f64::max);
+        let low = history.iter().map(|(_, v)| *v).reduce(f64::min);
+        let breakout = match (high, low) {
+            _ if history.len() < 2 => None,
+            (Some(high), _) if value > high => Some(RatioBreakout::Up),
+            (_, Some(low)) if value < low => Some(RatioBreakout::Down),
+            _ => None,
+        };
+
+        Self {
+            name: config.name.clone(),
+            description: config.description.clone(),
+            numerator: config.numerator.clone(),
+            denominator: config.denominator.clone(),
+            value,
+            change_24h_pct: change_since(Duration::hours(24)),
+            change_7d_pct: change_since(Duration::days(7)),
+            high,
+            low,
+            breakout,
+            new_breakout: breakout.is_some() && previous.and_then(|p| p.breakout) != breakout,
+            timestamp: now,
+        }
+    }
+
+    /// 生成突破告警（规则 `ratio_breakout:<名称>`）
+    ///
+    /// # 参数
+    /// * `severity` - 告警级别
+    /// * `lookback_days` - 回看天数
+    ///
+    /// # 返回
+    /// * `Option<Alert>` - 未突破时为None
+    pub fn breakout_alert(&self, severity: AlertSeverity, lookback_days: u32) -> Option<Alert> {
+        let (direction, bound) = match self.breakout? {
+            RatioBreakout::Up => ("向上突破", self.high?),
+            RatioBreakout::Down => ("向下跌破", self.low?),
+        };
+        Some(Alert::new(
+            format!("ratio_breakout:{}", self.name),
+            severity,
+            format!("比率 {} {}{}日区间", self.name, direction, lookback_days),
+            format!(
+                "{} / {} = {:.6}，{}{}日{} {:.6}",
+                self.numerator, self.denominator, self.value, direction, lookback_days,
+                if self.breakout == Some(RatioBreakout::Up) { "最高值" } else { "最低值" }, bound,
+            ),
+        ).metadata(serde_json::json!({ "ratio": self })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio_breakout() {
+        let config = RatioConfig {
+            name: "eth_btc".to_string(),
+            numerator: "price:eth".to_string(),
+            denominator: "price:btc".to_string(),
+            description: None,
+        };
+        let now = Utc::now();
+        let history = vec![
+            (now - Duration::days(8), 0.050),
+            (now - Duration::days(2), 0.055),
+            (now - Duration::hours(1), 0.052),
+        ];
+
+        let inside = RatioReading::assess(&config, 0.053, &history, None, now);
+        assert_eq!(inside.breakout, None);
+        assert_eq!(inside.change_7d_pct.map(|c| c.round()), Some(6.0));
+        assert!(inside.breakout_alert(AlertSeverity::Info, 30).is_none());
+
+        let up = RatioReading::assess(&config, 0.056, &history, Some(&inside), now);
+        assert_eq!(up.breakout, Some(RatioBreakout::Up));
+        assert!(up.new_breakout);
+        assert_eq!(up.breakout_alert(AlertSeverity::Info, 30).unwrap().rule, "ratio_breakout:eth_btc");
+
+        // 持续突破时不是新突破，历史不足时不判断
+        let still_up = RatioReading::assess(&config, 0.057, &history, Some(&up), now);
+        assert!(!still_up.new_breakout);
+        assert_eq!(RatioReading::assess(&config, 1.0, &history[..1], None, now).breakout, None);
+    }
+}
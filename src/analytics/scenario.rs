@@ -0,0 +1,236 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::clients::FearGreedSnapshot;
+use crate::storage::MetricStore;
+use crate::web::cache::{CacheSnapshot, CachedMarketData, DataCache, ALTCOIN_SEASON_KEY, FEAR_GREED_KEY};
+
+/// 单次模拟最多的假设变化数
+pub const MAX_SCENARIO_OVERRIDES: usize = 50;
+
+/// 模拟中新建的贪婪恐惧指数快照的数据源名称
+const SCENARIO_SOURCE: &str = "scenario";
+
+/// 假设的指标变化
+///
+/// `value` 和 `change_pct` 二选一
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScenarioOverride {
+    /// 指标键：缓存指标键（如 `price:bitcoin`，币种部分也可以是符号，如 `price:btc`），
+    /// 或指标存储中的指标名称（派生指标的输入）
+    pub metric: String,
+    /// 设为该值
+    #[serde(default)]
+    pub value: Option<f64>,
+    /// 按百分比变化（如 -20 表示下跌20%）
+    #[serde(default)]
+    pub change_pct: Option<f64>,
+}
+
+impl ScenarioOverride {
+    /// 在当前值的基础上计算假设值
+    fn apply(&self, baseline: Option<f64>) -> Result<f64> {
+        let value = match (self.value, self.change_pct) {
+            (Some(value), None) => value,
+            (None, Some(pct)) => {
+                let baseline = baseline.ok_or_else(|| anyhow!("指标 {} 暂无数据，不能按百分比变化", self.metric))?;
+                baseline * (1.0 + pct / 100.0)
+            }
+            _ => bail!("指标 {} 需要指定 value 或 change_pct 之一", self.metric),
+        };
+        if !value.is_finite() {
+            bail!("指标 {} 的假设值无效", self.metric);
+        }
+        Ok(value)
+    }
+}
+
+/// 已应用的假设变化
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedOverride {
+    /// 解析后的指标键（币种符号已替换为币种ID）
+    pub metric: String,
+    /// 当前值
+    pub baseline: Option<f64>,
+    /// 假设值
+    pub value: f64,
+}
+
+/// 模拟情景
+///
+/// 在当前缓存快照上应用假设变化，得到一份独立的缓存，不影响实际数据
+pub struct Scenario {
+    /// 应用假设变化后的缓存
+    pub cache: DataCache,
+    /// 指标存储中指标的假设值（指标名 -> 值）
+    pub stored: BTreeMap<String, f64>,
+    /// 已应用的假设变化
+    pub applied: Vec<AppliedOverride>,
+}
+
+impl Scenario {
+    /// 构建模拟情景
+    ///
+    /// # 参数
+    /// * `cache` - 当前数据缓存
+    /// * `store` - 指标存储（读取非缓存指标的当前值）
+    /// * `overrides` - 假设变化（按顺序应用，同一指标的后续变化以前面的结果为基础）
+    ///
+    /// # 返回
+    /// * `Result<Self>` - 变化数超出上限、指标不存在或取值无效时返回错误
+    pub async fn build(cache: &DataCache, store: &MetricStore, overrides: &[ScenarioOverride]) -> Result<Self> {
+        if overrides.is_empty() {
+            bail!("至少需要一个假设变化");
+        }
+        if overrides.len() > MAX_SCENARIO_OVERRIDES {
+            bail!("假设变化最多 {} 个", MAX_SCENARIO_OVERRIDES);
+        }
+
+        let mut snapshot = cache.snapshot().await;
+        let mut stored = BTreeMap::new();
+        let mut applied = Vec::new();
+        for item in overrides {
+            let applied_override = match item.metric.as_str() {
+                FEAR_GREED_KEY => apply_fear_greed(&mut snapshot, item)?,
+                ALTCOIN_SEASON_KEY => apply_altcoin_season(&mut snapshot, item)?,
+                key => match key.split_once(':') {
+                    Some((field, coin)) => apply_coin_field(&mut snapshot, field, coin, item)?,
+                    None => {
+                        let baseline = match stored.get(key) {
+                            Some(value) => Some(*value),
+                            None => store.latest(key).await.and_then(|m| m.value.as_f64()),
+                        };
+                        let value = item.apply(baseline)?;
+                        stored.insert(key.to_string(), value);
+                        AppliedOverride { metric: key.to_string(), baseline, value }
+                    }
+                },
+            };
+            applied.push(applied_override);
+        }
+
+        let scenario_cache = DataCache::new();
+        scenario_cache.restore(snapshot).await;
+        Ok(Self {
+            cache: scenario_cache,
+            stored,
+            applied,
+        })
+    }
+}
+
+/// 修改贪婪恐惧指数（限制在0-100并取整）
+fn apply_fear_greed(snapshot: &mut CacheSnapshot, item: &ScenarioOverride) -> Result<AppliedOverride> {
+    let baseline = snapshot.fear_greed_index.as_ref().map(|data| data.value as f64);
+    let value = item.apply(baseline)?.clamp(0.0, 100.0).round();
+    snapshot.fear_greed_index = Some(match &snapshot.fear_greed_index {
+        Some(data) => data.with_value(value as u8),
+        None => FearGreedSnapshot::from_value(value as u8, Utc::now(), SCENARIO_SOURCE),
+    });
+    Ok(AppliedOverride { metric: FEAR_GREED_KEY.to_string(), baseline, value })
+}
+
+/// 修改山寨币季节指数（限制在0-100并取整）
+fn apply_altcoin_season(snapshot: &mut CacheSnapshot, item: &ScenarioOverride) -> Result<AppliedOverride> {
+    let index = snapshot.altcoin_season_index.as_mut()
+        .ok_or_else(|| anyhow!("山寨币季节指数暂无数据"))?;
+    let baseline = Some(index.value as f64);
+    let value = item.apply(baseline)?.clamp(0.0, 100.0).round();
+    index.value = value as u8;
+    Ok(AppliedOverride { metric: ALTCOIN_SEASON_KEY.to_string(), baseline, value })
+}
+
+/// 修改币种行情字段
+///
+/// 按百分比修改价格时，24小时涨跌幅和市值随之变化
+fn apply_coin_field(snapshot: &mut CacheSnapshot, field: &str, coin: &str, item: &ScenarioOverride) -> Result<AppliedOverride> {
+    let coin = coin.to_lowercase();
+    let data: &mut CachedMarketData = if snapshot.market_data.contains_key(&coin) {
+        snapshot.market_data.get_mut(&coin)
+    } else {
+        snapshot.market_data.values_mut().find(|d| d.symbol.to_lowercase() == coin)
+    }.ok_or_else(|| anyhow!("币种 {} 暂无行情数据", coin))?;
+    let metric = format!("{}:{}", field, data.coin_id);
+
+    let (baseline, value) = match field {
+        "price" => {
+            let baseline = data.current_price;
+            if baseline <= 0.0 || !baseline.is_finite() {
+                bail!("指标 {} 的当前价格无效（{}），无法按比例推算", metric, baseline);
+            }
+            let value = item.apply(Some(baseline))?;
+            if value <= 0.0 {
+                bail!("指标 {} 的假设值必须为正数", metric);
+            }
+            let ratio = value / baseline;
+            data.current_price = value;
+            data.market_cap = data.market_cap.map(|cap| cap * ratio);
+            data.price_change_24h = data.price_change_24h.map(|pct| ((1.0 + pct / 100.0) * ratio - 1.0) * 100.0);
+            (Some(baseline), value)
+        }
+        "price_change_24h" => {
+            let value = item.apply(data.price_change_24h)?;
+            (data.price_change_24h.replace(value), value)
+        }
+        "market_cap" => {
+            let value = item.apply(data.market_cap)?;
+            (data.market_cap.replace(value), value)
+        }
+        "volume_24h" => {
+            let value = item.apply(data.volume_24h)?;
+            (data.volume_24h.replace(value), value)
+        }
+        "rsi" => {
+            let rsi = &mut data.technical_indicators.rsi;
+            let baseline = rsi.value;
+            rsi.value = item.apply(Some(baseline))?.clamp(0.0, 100.0);
+            (Some(baseline), rsi.value)
+        }
+        _ => bail!("不支持的指标: {}", item.metric),
+    };
+    Ok(AppliedOverride { metric, baseline, value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scenario_overrides() {
+        let cache = DataCache::new();
+        cache.set_coin_data("bitcoin", serde_json::json!({
+            "current_price": 100000.0,
+            "symbol": "BTC",
+            "name": "Bitcoin",
+            "market_cap": 2.0e12,
+            "price_change_percentage_24h": 0.0,
+        })).await;
+        cache.set_fear_greed_index(FearGreedSnapshot::from_value(50, Utc::now(), "test")).await;
+        let store = MetricStore::new(30);
+
+        let scenario = Scenario::build(&cache, &store, &[
+            ScenarioOverride { metric: "price:btc".to_string(), value: None, change_pct: Some(-20.0) },
+            ScenarioOverride { metric: FEAR_GREED_KEY.to_string(), value: Some(150.0), change_pct: None },
+            ScenarioOverride { metric: "tvl_usd".to_string(), value: Some(1.0e9), change_pct: None },
+        ]).await.unwrap();
+
+        assert_eq!(scenario.applied[0].metric, "price:bitcoin");
+        assert_eq!(scenario.cache.metric_value("price:bitcoin").await, Some(80000.0));
+        assert_eq!(scenario.cache.metric_value("market_cap:bitcoin").await, Some(1.6e12));
+        assert!((scenario.cache.metric_value("price_change_24h:bitcoin").await.unwrap() + 20.0).abs() < 1e-9);
+        assert_eq!(scenario.cache.metric_value(FEAR_GREED_KEY).await, Some(100.0));
+        assert_eq!(scenario.stored.get("tvl_usd"), Some(&1.0e9));
+        // 实际缓存不受影响
+        assert_eq!(cache.metric_value("price:bitcoin").await, Some(100000.0));
+
+        let missing = ScenarioOverride { metric: "tvl_usd".to_string(), value: None, change_pct: Some(10.0) };
+        assert!(Scenario::build(&cache, &store, &[missing]).await.is_err());
+
+        // 当前价格为0时无法按比例推算市值和涨跌幅
+        cache.set_coin_data("dead", serde_json::json!({ "current_price": 0.0, "symbol": "DEAD", "name": "Dead" })).await;
+        let zero = ScenarioOverride { metric: "price:dead".to_string(), value: Some(1.0), change_pct: None };
+        assert!(Scenario::build(&cache, &store, &[zero]).await.is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::SectorConfig;
+
+/// 单个币种的板块统计输入
+#[derive(Debug, Clone)]
+pub struct SectorInput {
+    /// 币种ID
+    pub coin_id: String,
+    /// 分类标签
+    pub tags: Vec<String>,
+    /// 市值
+    pub market_cap: Option<f64>,
+    /// 24小时交易量
+    pub volume_24h: Option<f64>,
+    /// 24小时价格变化百分比
+    pub change_24h: Option<f64>,
+}
+
+/// 板块统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectorStats {
+    /// 板块名称
+    pub name: String,
+    /// 板块内的币种ID
+    pub coins: Vec<String>,
+    /// 总市值
+    pub market_cap: f64,
+    /// 占全部监控币种总市值的百分比
+    pub market_cap_share: Option<f64>,
+    /// 24小时市值变化（美元，正数为资金流入）
+    pub market_cap_change_24h: f64,
+    /// 24小时总交易量
+    pub volume_24h: f64,
+    /// 24小时涨跌幅的简单平均
+    pub avg_change_24h: Option<f64>,
+    /// 24小时涨跌幅的市值加权平均
+    pub weighted_change_24h: Option<f64>,
+}
+
+/// 板块统计结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectorSnapshot {
+    /// 计算时间
+    pub timestamp: DateTime<Utc>,
+    /// 各板块统计（按市值加权涨跌幅降序，便于观察板块轮动）
+    pub sectors: Vec<SectorStats>,
+    /// 未归入任何板块的币种
+    pub unclassified: Vec<String>,
+}
+
+/// 判断币种是否属于板块
+fn in_sector(input: &SectorInput, sector: &SectorConfig) -> bool {
+    sector.coins.iter().any(|c| c.eq_ignore_ascii_case(&input.coin_id))
+        || input.tags.iter().any(|tag| sector.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+}
+
+/// 计算板块统计
+///
+/// 一个币种可以同时属于多个板块；24小时市值变化由当前市值和涨跌幅反推
+///
+/// # 参数
+/// * `inputs` - 各币种的分类标签和行情
+/// * `sectors` - 板块定义
+/// * `timestamp` - 计算时间
+///
+/// # 返回
+/// * `SectorSnapshot` - 板块统计结果
+pub fn compute_sectors(inputs: &[SectorInput], sectors: &[SectorConfig], timestamp: DateTime<Utc>) -> SectorSnapshot {
+    let total_market_cap: f64 = inputs.iter().filter_map(|c| c.market_cap).sum();
+
+    let mut stats: Vec<SectorStats> = sectors.iter()
+        .map(|sector| {
+            let members: Vec<&SectorInput> = inputs.iter().filter(|c| in_sector(c, sector)).collect();
+            let market_cap: f64 = members.iter().filter_map(|c| c.market_cap).sum();
+            let market_cap_change_24h = members.iter()
+                .filter_map(|c| {
+                    // 跌幅达到100%时无法反推24小时前的市值
+                    let (market_cap, change) = (c.market_cap?, c.change_24h.filter(|change| *change > -100.0)?);
+                    Some(market_cap - market_cap / (1.0 + change / 100.0))
+                })
+                .sum();
+            let changes: Vec<f64> = members.iter().filter_map(|c| c.change_24h).collect();
+            let weighted: Vec<(f64, f64)> = members.iter()
+                .filter_map(|c| Some((c.market_cap.filter(|m| *m > 0.0)?, c.change_24h?)))
+                .collect();
+            let weight: f64 = weighted.iter().map(|(m, _)| m).sum();
+            SectorStats {
+                name: sector.name.clone(),
+                coins: members.iter().map(|c| c.coin_id.clone()).collect(),
+                market_cap,
+                market_cap_share: (total_market_cap > 0.0).then(|| market_cap / total_market_cap * 100.0),
+                market_cap_change_24h,
+                volume_24h: members.iter().filter_map(|c| c.volume_24h).sum(),
+                avg_change_24h: (!changes.is_empty()).then(|| changes.iter().sum::<f64>() / changes.len() as f64),
+                weighted_change_24h: (weight > 0.0).then(|| weighted.iter().map(|(m, c)| m * c).sum::<f64>() / weight),
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| {
+        let key = |s: &SectorStats| s.weighted_change_24h.unwrap_or(f64::NEG_INFINITY);
+        key(b).total_cmp(&key(a))
+    });
+
+    SectorSnapshot {
+        timestamp,
+        sectors: stats,
+        unclassified: inputs.iter()
+            .filter(|c| !sectors.iter().any(|s| in_sector(c, s)))
+            .map(|c| c.coin_id.clone())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_sectors() {
+        let coin = |id: &str, tags: &[&str], market_cap: f64, change: f64| SectorInput {
+            coin_id: id.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            market_cap: Some(market_cap),
+            volume_24h: Some(10.0),
+            change_24h: Some(change),
+        };
+        let inputs = vec![
+            coin("eth", &["layer-1", "defi"], 300.0, 50.0),
+            coin("uni", &["DeFi"], 100.0, -50.0),
+            coin("doge", &["memes"], 100.0, 0.0),
+            coin("xyz", &[], 500.0, 1.0),
+        ];
+        let sectors = vec![
+            SectorConfig { name: "defi".to_string(), tags: vec!["defi".to_string()], coins: Vec::new() },
+            SectorConfig { name: "l1".to_string(), tags: vec!["layer-1".to_string()], coins: Vec::new() },
+            SectorConfig { name: "meme".to_string(), tags: Vec::new(), coins: vec!["DOGE".to_string()] },
+        ];
+
+        let snapshot = compute_sectors(&inputs, &sectors, Utc::now());
+        let names: Vec<&str> = snapshot.sectors.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["l1", "defi", "meme"]);
+
+        let defi = &snapshot.sectors[1];
+        assert_eq!(defi.coins, vec!["eth".to_string(), "uni".to_string()]);
+        assert_eq!(defi.market_cap, 400.0);
+        assert_eq!(defi.market_cap_share, Some(40.0));
+        // ETH 200 -> 300（+100），UNI 200 -> 100（-100）
+        assert_eq!(defi.market_cap_change_24h, 0.0);
+        assert_eq!(defi.avg_change_24h, Some(0.0));
+        assert_eq!(defi.weighted_change_24h, Some(25.0));
+        assert_eq!(snapshot.unclassified, vec!["xyz".to_string()]);
+
+        // 跌幅100%的币种不参与资金流向计算
+        let wiped = compute_sectors(&[coin("luna", &["defi"], 100.0, -100.0)], &sectors[..1], Utc::now());
+        assert_eq!(wiped.sectors[0].market_cap_change_24h, 0.0);
+    }
+}
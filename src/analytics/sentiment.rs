@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::analytics::{ExchangeFlows, SocialSentiment};
+use crate::clients::CoinMarketCapClient;
+use crate::config::SentimentConfig;
+use crate::web::cache::{DataCache, EXCHANGE_FLOWS_KEY, FEAR_GREED_KEY, SOCIAL_KEY};
+
+/// 综合情绪的组成部分
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentimentComponent {
+    /// 组成部分名称
+    pub name: String,
+    /// 情绪分（0-100，越高越贪婪）
+    pub score: f64,
+    /// 权重
+    pub weight: f64,
+}
+
+impl SentimentComponent {
+    /// 创建组成部分
+    pub fn new(name: impl Into<String>, score: f64, weight: f64) -> Self {
+        Self {
+            name: name.into(),
+            score: score.clamp(0.0, 100.0),
+            weight,
+        }
+    }
+}
+
+/// 综合情绪分
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositeSentiment {
+    /// 计算时间
+    pub timestamp: DateTime<Utc>,
+    /// 加权情绪分（0-100）
+    pub score: f64,
+    /// 情绪描述
+    pub classification: String,
+    /// 参与计算的组成部分
+    pub components: Vec<SentimentComponent>,
+}
+
+/// 按权重合成综合情绪分
+///
+/// 只对可用的组成部分做加权平均，权重不为正的部分被忽略
+///
+/// # 参数
+/// * `components` - 组成部分
+/// * `timestamp` - 计算时间
+///
+/// # 返回
+/// * `Option<CompositeSentiment>` - 没有可用组成部分时为None
+pub fn composite_sentiment(components: Vec<SentimentComponent>, timestamp: DateTime<Utc>) -> Option<CompositeSentiment> {
+    let components: Vec<SentimentComponent> = components.into_iter()
+        .filter(|c| c.weight > 0.0 && c.score.is_finite())
+        .collect();
+    let total_weight: f64 = components.iter().map(|c| c.weight).sum();
+    if total_weight <= 0.0 {
+        return None;
+    }
+
+    let score = components.iter().map(|c| c.score * c.weight).sum::<f64>() / total_weight;
+    Some(CompositeSentiment {
+        timestamp,
+        score,
+        classification: CoinMarketCapClient::get_sentiment_description(score.round() as u8).to_string(),
+        components,
+    })
+}
+
+/// 从缓存中的最新数据计算综合情绪
+///
+/// # 参数
+/// * `cache` - 数据缓存
+/// * `weights` - 各组成部分的权重
+pub async fn current_sentiment(cache: &DataCache, weights: &SentimentConfig) -> Option<CompositeSentiment> {
+    let mut components = Vec::new();
+    if let Some(value) = cache.metric_value(FEAR_GREED_KEY).await {
+        components.push(SentimentComponent::new("fear_greed", value, weights.fear_greed_weight));
+    }
+    if let Some(score) = cache.get_dataset::<ExchangeFlows>(EXCHANGE_FLOWS_KEY).await
+        .and_then(|flows| flows.sentiment_score())
+    {
+        components.push(SentimentComponent::new("exchange_flows", score, weights.exchange_flow_weight));
+    }
+    if let Some(score) = cache.get_dataset::<SocialSentiment>(SOCIAL_KEY).await
+        .and_then(|social| social.sentiment_score())
+    {
+        components.push(SentimentComponent::new("social", score, weights.social_weight));
+    }
+    composite_sentiment(components, Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_composite_sentiment_weights() {
+        let sentiment = composite_sentiment(vec![
+            SentimentComponent::new("fear_greed", 30.0, 1.0),
+            SentimentComponent::new("exchange_flows", 90.0, 0.5),
+            SentimentComponent::new("disabled", 0.0, 0.0),
+        ], Utc::now()).unwrap();
+
+        assert!((sentiment.score - 50.0).abs() < 1e-9);
+        assert_eq!(sentiment.components.len(), 2);
+        assert!(composite_sentiment(Vec::new(), Utc::now()).is_none());
+    }
+}
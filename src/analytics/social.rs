@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::clients::SocialMetrics;
+
+/// 单个币种的社交数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoinSocial {
+    /// 币种ID
+    pub coin_id: String,
+    /// 币种符号
+    pub symbol: String,
+    /// 社交指标
+    #[serde(flatten)]
+    pub metrics: SocialMetrics,
+}
+
+/// 监控币种的社交数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SocialSentiment {
+    /// 采集时间
+    pub timestamp: DateTime<Utc>,
+    /// 各币种社交数据
+    pub coins: Vec<CoinSocial>,
+}
+
+impl SocialSentiment {
+    /// 社交情绪分（0-100）
+    ///
+    /// 各币种正面情绪占比按24小时互动数加权平均，缺少互动数时按等权处理
+    pub fn sentiment_score(&self) -> Option<f64> {
+        let scored: Vec<(f64, f64)> = self.coins.iter()
+            .filter_map(|coin| {
+                let sentiment = coin.metrics.sentiment.filter(|s| s.is_finite())?;
+                let weight = coin.metrics.interactions_24h.filter(|w| *w > 0.0).unwrap_or(1.0);
+                Some((sentiment, weight))
+            })
+            .collect();
+        let total_weight: f64 = scored.iter().map(|(_, w)| w).sum();
+        if total_weight <= 0.0 {
+            return None;
+        }
+        Some(scored.iter().map(|(s, w)| s * w).sum::<f64>() / total_weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(symbol: &str, sentiment: f64, interactions_24h: Option<f64>) -> CoinSocial {
+        CoinSocial {
+            coin_id: symbol.to_lowercase(),
+            symbol: symbol.to_string(),
+            metrics: SocialMetrics {
+                sentiment: Some(sentiment),
+                interactions_24h,
+                ..SocialMetrics::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_interaction_weighted_score() {
+        let social = SocialSentiment {
+            timestamp: Utc::now(),
+            coins: vec![coin("BTC", 80.0, Some(3000.0)), coin("HYPE", 40.0, Some(1000.0))],
+        };
+        assert_eq!(social.sentiment_score(), Some(70.0));
+
+        let empty = SocialSentiment { timestamp: Utc::now(), coins: Vec::new() };
+        assert_eq!(empty.sentiment_score(), None);
+    }
+}
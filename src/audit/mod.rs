@@ -0,0 +1,215 @@
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// 内存中保留的最大审计记录数（文件中保留全部记录）
+const MAX_IN_MEMORY_ENTRIES: usize = 10_000;
+
+/// 审计记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// 唯一标识符
+    pub id: Uuid,
+    /// 操作者（用户名，未登录时为 `anonymous`）
+    pub actor: String,
+    /// 操作者用户ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<Uuid>,
+    /// 操作类型（如 `alert_rule.update`、`cache.clear`）
+    pub action: String,
+    /// 操作对象（如规则ID、任务ID）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// 操作内容
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+    /// 操作时间
+    pub timestamp: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    /// 创建审计记录
+    /// 
+    /// # 参数
+    /// * `actor` - 操作者用户名
+    /// * `actor_id` - 操作者用户ID
+    /// * `action` - 操作类型
+    pub fn new(actor: impl Into<String>, actor_id: Option<Uuid>, action: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            actor: actor.into(),
+            actor_id,
+            action: action.into(),
+            target: None,
+            payload: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// 设置操作对象
+    pub fn target(mut self, target: impl ToString) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    /// 设置操作内容
+    pub fn payload(mut self, payload: impl Serialize) -> Self {
+        self.payload = serde_json::to_value(payload).ok();
+        self
+    }
+}
+
+/// 审计记录查询条件
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AuditQuery {
+    /// 操作者
+    pub actor: Option<String>,
+    /// 操作类型前缀（如 `alert_rule`）
+    pub action: Option<String>,
+    /// 起始时间
+    pub since: Option<DateTime<Utc>>,
+    /// 最多返回条数（默认100）
+    pub limit: Option<usize>,
+}
+
+/// 审计日志
+/// 
+/// 以 JSON Lines 格式追加写入文件，记录只增不改
+pub struct AuditLog {
+    /// 最近的审计记录（最新的在末尾）
+    entries: RwLock<VecDeque<AuditEntry>>,
+    /// 日志文件路径（可选）
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    /// 创建内存中的审计日志（不持久化）
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(VecDeque::new()),
+            path: None,
+        }
+    }
+
+    /// 打开审计日志文件
+    /// 
+    /// # 参数
+    /// * `path` - 日志文件路径（不存在时在首次写入时创建）
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let mut entries = VecDeque::new();
+        
+        match tokio::fs::read_to_string(&path).await {
+            Ok(content) => {
+                for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                    match serde_json::from_str::<AuditEntry>(line) {
+                        Ok(entry) => {
+                            entries.push_back(entry);
+                            if entries.len() > MAX_IN_MEMORY_ENTRIES {
+                                entries.pop_front();
+                            }
+                        }
+                        Err(e) => warn!("⚠️ 跳过无法解析的审计记录: {}", e),
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e).with_context(|| format!("无法读取审计日志: {}", path.display())),
+        }
+        
+        info!("📝 已加载 {} 条审计记录", entries.len());
+        Ok(Self {
+            entries: RwLock::new(entries),
+            path: Some(path),
+        })
+    }
+
+    /// 记录审计事件
+    /// 
+    /// 写入文件失败只记录警告，不影响业务操作
+    pub async fn record(&self, entry: AuditEntry) {
+        info!("📝 审计: {} {} {}", entry.actor, entry.action, entry.target.as_deref().unwrap_or(""));
+        
+        let mut entries = self.entries.write().await;
+        if let Some(path) = &self.path {
+            if let Err(e) = Self::append(path, &entry).await {
+                warn!("⚠️ 写入审计日志失败: {}", e);
+            }
+        }
+        entries.push_back(entry);
+        if entries.len() > MAX_IN_MEMORY_ENTRIES {
+            entries.pop_front();
+        }
+    }
+
+    /// 查询审计记录
+    /// 
+    /// # 返回
+    /// * `Vec<AuditEntry>` - 符合条件的记录（最新的在前）
+    pub async fn query(&self, query: &AuditQuery) -> Vec<AuditEntry> {
+        self.entries.read().await
+            .iter()
+            .rev()
+            .filter(|e| query.actor.as_ref().is_none_or(|actor| &e.actor == actor))
+            .filter(|e| query.action.as_ref().is_none_or(|action| e.action.starts_with(action.as_str())))
+            .filter(|e| query.since.is_none_or(|since| e.timestamp >= since))
+            .take(query.limit.unwrap_or(100))
+            .cloned()
+            .collect()
+    }
+
+    /// 追加一行记录到文件
+    async fn append(path: &PathBuf, entry: &AuditEntry) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_records_survive_reopen() {
+        let path = std::env::temp_dir().join(format!("everscan-audit-{}.jsonl", Uuid::new_v4()));
+        
+        let log = AuditLog::open(&path).await.unwrap();
+        log.record(AuditEntry::new("alice", None, "alert_rule.create").target("r1")).await;
+        log.record(AuditEntry::new("bob", None, "cache.clear")).await;
+        
+        let reopened = AuditLog::open(&path).await.unwrap();
+        let all = reopened.query(&AuditQuery::default()).await;
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].action, "cache.clear", "最新的记录在前");
+        
+        let filtered = reopened.query(&AuditQuery { action: Some("alert_rule".into()), ..Default::default() }).await;
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].actor, "alice");
+        
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,29 @@
+use everscan::secrets::{SecretBox, ENCRYPTED_VALUE_PREFIX, MASTER_KEY_ENV};
+use std::io::Read;
+
+/// 加密配置中的敏感值
+/// 
+/// 用法：`EVERSCAN_MASTER_KEY=... cargo run --bin seal_secret -- <明文>`，
+/// 不传参数时从标准输入读取（避免明文留在 shell 历史中）。
+/// 输出的 `enc:v1:...` 可直接写入 config.toml 或环境变量
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv::dotenv().ok();
+    
+    let secret_box = SecretBox::from_env()
+        .ok_or_else(|| format!("环境变量{}未设置", MASTER_KEY_ENV))?;
+    
+    let plaintext = match std::env::args().nth(1) {
+        Some(value) => value,
+        None => {
+            let mut value = String::new();
+            std::io::stdin().read_to_string(&mut value)?;
+            value.trim_end_matches(['\r', '\n']).to_string()
+        }
+    };
+    if plaintext.is_empty() {
+        return Err("待加密的值不能为空".into());
+    }
+    
+    println!("{}{}", ENCRYPTED_VALUE_PREFIX, secret_box.seal(&plaintext)?);
+    Ok(())
+}
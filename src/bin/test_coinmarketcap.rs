@@ -2,7 +2,6 @@ use anyhow::Result;
 use reqwest::Client;
 use serde_json::Value;
 use std::env;
-use tokio;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -11,7 +10,7 @@ async fn main() -> Result<()> {
 
     let api_key = env::var("COINMARKETCAP_API_KEY").expect("COINMARKETCAP_API_KEY 环境变量未设置");
 
-    println!("🔑 使用API密钥: {}...", &api_key[..8]);
+    println!("🔑 使用API密钥: 已加载");
 
     let client = Client::new();
 
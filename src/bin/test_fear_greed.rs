@@ -4,6 +4,7 @@ use std::time::Duration;
 use std::env;
 
 #[derive(Debug, Serialize, Deserialize)]
+#[allow(dead_code)]
 struct TestResponse {
     data: serde_json::Value,
     status: serde_json::Value,
@@ -16,7 +17,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let api_key = env::var("COINMARKETCAP_API_KEY")
         .map_err(|_| "环境变量COINMARKETCAP_API_KEY未设置")?;
     
-    println!("🔑 API Key: {}...", &api_key[..8]);
+    println!("🔑 API Key: 已加载");
     
     let client = ClientBuilder::new()
         .timeout(Duration::from_secs(30))
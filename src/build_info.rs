@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// 编译信息
+///
+/// 由构建脚本在编译期记录，用于识别已部署实例的具体构建，问题报告中应附上这些信息
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildInfo {
+    /// 版本号
+    pub version: &'static str,
+    /// git提交（不在git仓库中编译时为空）
+    pub git_commit: Option<&'static str>,
+    /// 编译时间
+    pub built_at: Option<DateTime<Utc>>,
+    /// 编译配置（debug / release）
+    pub profile: &'static str,
+    /// 目标平台（操作系统-架构）
+    pub target: String,
+    /// 启用的编译特性
+    pub features: Vec<&'static str>,
+}
+
+impl BuildInfo {
+    /// 当前程序的编译信息
+    pub fn current() -> Self {
+        let git_commit = env!("EVERSCAN_GIT_COMMIT");
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            git_commit: (!git_commit.is_empty()).then_some(git_commit),
+            built_at: env!("EVERSCAN_BUILD_TIMESTAMP").parse()
+                .ok()
+                .filter(|&seconds| seconds > 0)
+                .and_then(|seconds| DateTime::from_timestamp(seconds, 0)),
+            profile: if cfg!(debug_assertions) { "debug" } else { "release" },
+            target: format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+            features: env!("EVERSCAN_FEATURES").split(',').filter(|name| !name.is_empty()).collect(),
+        }
+    }
+
+    /// 简短的版本描述（如 `0.1.0 (3f2a9c1d04be)`），用于日志
+    pub fn describe(&self) -> String {
+        match self.git_commit {
+            Some(commit) => format!("{} ({})", self.version, commit),
+            None => self.version.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info_from_build_script() {
+        let info = BuildInfo::current();
+        assert_eq!(info.features.contains(&"forecast"), cfg!(feature = "forecast"));
+        assert_eq!(info.features.contains(&"embedded-assets"), cfg!(feature = "embedded-assets"));
+        assert!(!info.features.contains(&"default"));
+        assert!(info.built_at.is_some());
+        assert!(info.describe().starts_with(env!("CARGO_PKG_VERSION")));
+    }
+}
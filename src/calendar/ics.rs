@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+
+use super::EconomicEvent;
+
+/// 日历产品标识
+const PRODID: &str = "-//EverScan//Event Calendar//ZH";
+
+/// 单个事件在日历中显示的时长（分钟）
+const EVENT_DURATION_MINUTES: u32 = 30;
+
+/// 建议日历客户端的刷新间隔
+const REFRESH_INTERVAL: &str = "PT6H";
+
+/// 内容行最大长度（字节，不含换行），超出时折行
+const MAX_LINE_OCTETS: usize = 75;
+
+/// 生成 iCalendar（RFC 5545）格式的事件订阅
+///
+/// 事件标识保持稳定，Google Calendar 等客户端重新拉取时会更新同一事件而不是重复添加
+///
+/// # 参数
+/// * `name` - 日历名称
+/// * `events` - 事件列表
+/// * `now` - 生成时间
+///
+/// # 返回
+/// * `String` - 以CRLF分隔的日历内容
+pub fn render_ics(name: &str, events: &[EconomicEvent], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+        format!("REFRESH-INTERVAL;VALUE=DURATION:{}", REFRESH_INTERVAL),
+        format!("X-PUBLISHED-TTL:{}", REFRESH_INTERVAL),
+    ];
+
+    for event in events {
+        let mut description = format!("重要性：{}", event.importance);
+        if let Some(forecast) = &event.forecast {
+            description.push_str(&format!("\n预期：{}", forecast));
+        }
+        if let Some(previous) = &event.previous {
+            description.push_str(&format!("\n前值：{}", previous));
+        }
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@everscan", event.id));
+        lines.push(format!("DTSTAMP:{}", format_utc(now)));
+        lines.push(format!("DTSTART:{}", format_utc(event.time)));
+        lines.push(format!("DURATION:PT{}M", EVENT_DURATION_MINUTES));
+        lines.push(format!("SUMMARY:{}", escape_text(&format!("[{}] {}", event.country, event.title))));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&description)));
+        lines.push(format!("CATEGORIES:{}", escape_text(&format!("{:?}", event.importance).to_uppercase())));
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut output = String::new();
+    for line in lines {
+        output.push_str(&fold_line(&line));
+        output.push_str("\r\n");
+    }
+    output
+}
+
+/// 格式化为UTC时间（如 `20240612T180000Z`）
+fn format_utc(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// 转义文本属性值中的特殊字符
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 按75字节折行，续行以空格开头，不拆分多字节字符
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / MAX_LINE_OCTETS * 3);
+    let mut line_octets = 0;
+    for c in line.chars() {
+        if line_octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // 续行开头的空格也计入长度
+            line_octets = 1;
+        }
+        folded.push(c);
+        line_octets += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::EventImportance;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_ics() {
+        let time = Utc.with_ymd_and_hms(2024, 6, 12, 18, 0, 0).unwrap();
+        let mut event = EconomicEvent::new("FOMC Statement, Rate; Decision", "usd", time, EventImportance::High);
+        event.forecast = Some("5.50%".to_string());
+
+        let ics = render_ics("EverScan", &[event.clone()], time);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains(&format!("UID:{}@everscan\r\n", event.id)));
+        assert!(ics.contains("DTSTART:20240612T180000Z\r\n"));
+        assert!(ics.contains("SUMMARY:[USD] FOMC Statement\\, Rate\\; Decision\r\n"));
+        assert!(ics.contains("\\n预期：5.50%"));
+
+        // 折行后每行不超过75字节，且不拆分中文字符
+        let long = fold_line(&format!("DESCRIPTION:{}", "宏观事件".repeat(20)));
+        assert!(long.split("\r\n").all(|l| l.len() <= MAX_LINE_OCTETS));
+        assert_eq!(long.replace("\r\n ", ""), format!("DESCRIPTION:{}", "宏观事件".repeat(20)));
+    }
+}
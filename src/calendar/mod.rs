@@ -0,0 +1,221 @@
+pub mod ics;
+
+pub use ics::*;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tracing::{info, debug};
+
+use crate::storage::{JsonFileStore, Reloadable};
+
+/// 已过去的事件保留天数
+const PAST_EVENT_RETENTION_DAYS: i64 = 30;
+
+/// 宏观事件重要性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventImportance {
+    /// 低
+    Low,
+    /// 中
+    Medium,
+    /// 高（FOMC利率决议、CPI、非农等）
+    High,
+}
+
+impl EventImportance {
+    /// 解析数据源中的重要性标记（如 `High`、`Medium`、`Low`）
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "high" => Some(EventImportance::High),
+            "medium" => Some(EventImportance::Medium),
+            "low" => Some(EventImportance::Low),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for EventImportance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventImportance::Low => write!(f, "低"),
+            EventImportance::Medium => write!(f, "中"),
+            EventImportance::High => write!(f, "高"),
+        }
+    }
+}
+
+/// 宏观经济事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EconomicEvent {
+    /// 事件标识（由国家、时间和标题生成，同一事件多次获取保持不变）
+    pub id: String,
+    /// 事件名称（如 `CPI m/m`、`FOMC Statement`）
+    pub title: String,
+    /// 国家/货币代码（如 USD）
+    pub country: String,
+    /// 发布时间
+    pub time: DateTime<Utc>,
+    /// 重要性
+    pub importance: EventImportance,
+    /// 预期值
+    pub forecast: Option<String>,
+    /// 前值
+    pub previous: Option<String>,
+}
+
+impl EconomicEvent {
+    /// 创建事件并生成标识
+    pub fn new(
+        title: impl Into<String>,
+        country: impl Into<String>,
+        time: DateTime<Utc>,
+        importance: EventImportance,
+    ) -> Self {
+        let title = title.into();
+        let country = country.into().to_uppercase();
+        let digest = Sha256::digest(format!("{}|{}|{}", country, time.timestamp(), title));
+        Self {
+            id: hex::encode(&digest[..6]),
+            title,
+            country,
+            time,
+            importance,
+            forecast: None,
+            previous: None,
+        }
+    }
+}
+
+/// 经济日历
+///
+/// 保存即将发生和最近发生的宏观事件，供日历接口和事件提醒使用
+pub struct EconomicCalendar {
+    /// 按时间排序的事件
+    events: RwLock<Vec<EconomicEvent>>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<Vec<EconomicEvent>>>,
+}
+
+impl EconomicCalendar {
+    /// 创建内存中的经济日历（不持久化）
+    pub fn new() -> Self {
+        Self {
+            events: RwLock::new(Vec::new()),
+            store: None,
+        }
+    }
+
+    /// 从状态文件加载经济日历
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let events: Vec<EconomicEvent> = store.load().await?;
+        info!("🗓️ 已加载 {} 个宏观事件", events.len());
+        Ok(Self {
+            events: RwLock::new(events),
+            store: Some(store),
+        })
+    }
+
+    /// 合并新获取的事件
+    ///
+    /// 同一标识的事件以新数据为准，超过保留期的历史事件被清理
+    ///
+    /// # 参数
+    /// * `incoming` - 新获取的事件
+    /// * `now` - 当前时间
+    ///
+    /// # 返回
+    /// * `Result<usize>` - 新增的事件数
+    pub async fn merge(&self, incoming: Vec<EconomicEvent>, now: DateTime<Utc>) -> Result<usize> {
+        let mut events = self.events.write().await;
+        let mut added = 0;
+        for event in incoming {
+            match events.iter_mut().find(|e| e.id == event.id) {
+                Some(existing) => *existing = event,
+                None => {
+                    events.push(event);
+                    added += 1;
+                }
+            }
+        }
+        let cutoff = now - Duration::days(PAST_EVENT_RETENTION_DAYS);
+        events.retain(|e| e.time >= cutoff);
+        events.sort_by_key(|e| e.time);
+
+        if let Some(store) = &self.store {
+            store.save(&events).await?;
+        }
+        debug!("🗓️ 合并宏观事件，新增 {} 个", added);
+        Ok(added)
+    }
+
+    /// 查询时间范围内的事件
+    ///
+    /// # 参数
+    /// * `from` - 起始时间（包含）
+    /// * `until` - 结束时间（不包含）
+    /// * `min_importance` - 最低重要性
+    pub async fn events_between(
+        &self,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+        min_importance: EventImportance,
+    ) -> Vec<EconomicEvent> {
+        self.events.read().await
+            .iter()
+            .filter(|e| e.time >= from && e.time < until && e.importance >= min_importance)
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for EconomicCalendar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Reloadable for EconomicCalendar {
+    fn name(&self) -> &str {
+        "economic_calendar"
+    }
+
+    async fn reload(&self) -> Result<()> {
+        if let Some(store) = &self.store {
+            *self.events.write().await = store.load().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_merge_updates_and_prunes() {
+        let calendar = EconomicCalendar::new();
+        let now = Utc::now();
+        let cpi = EconomicEvent::new("CPI m/m", "usd", now + Duration::hours(2), EventImportance::High);
+        let old = EconomicEvent::new("Retail Sales m/m", "USD", now - Duration::days(40), EventImportance::Medium);
+
+        assert_eq!(calendar.merge(vec![cpi.clone(), old], now).await.unwrap(), 2);
+
+        // 同一事件再次获取时更新预期值而不是新增
+        let mut revised = EconomicEvent::new("CPI m/m", "USD", cpi.time, EventImportance::High);
+        revised.forecast = Some("0.3%".to_string());
+        assert_eq!(revised.id, cpi.id);
+        assert_eq!(calendar.merge(vec![revised], now).await.unwrap(), 0);
+
+        let events = calendar.events_between(now - Duration::days(60), now + Duration::days(1), EventImportance::Low).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].forecast.as_deref(), Some("0.3%"));
+    }
+}
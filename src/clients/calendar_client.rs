@@ -0,0 +1,96 @@
+use anyhow::{Result, Context, anyhow};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use super::HttpClientBuilder;
+use crate::calendar::{EconomicEvent, EventImportance};
+use crate::models::{parse_timestamp, register_source, DataSource, SourceCapability, SourceKind};
+
+/// 经济日历数据源的事件格式（ForexFactory 周历JSON）
+#[derive(Debug, Deserialize)]
+struct RawCalendarEvent {
+    /// 事件名称
+    title: String,
+    /// 货币代码
+    country: String,
+    /// 发布时间（带时区偏移的RFC 3339）
+    date: String,
+    /// 重要性（High/Medium/Low/Holiday）
+    impact: String,
+    /// 预期值
+    #[serde(default)]
+    forecast: Option<String>,
+    /// 前值
+    #[serde(default)]
+    previous: Option<String>,
+}
+
+/// 经济日历客户端
+/// 
+/// 默认使用 ForexFactory 公开的本周日历JSON，不需要API密钥
+#[derive(Clone)]
+pub struct CalendarClient {
+    /// HTTP客户端
+    client: Client,
+    /// 数据源URL
+    url: String,
+}
+
+impl CalendarClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "economic_calendar",
+        display_name: "经济日历",
+        kind: SourceKind::Feed,
+        capabilities: &[SourceCapability::Macro],
+    };
+
+    /// 创建新的经济日历客户端
+    /// 
+    /// # 参数
+    /// * `url` - 数据源URL
+    /// * `timeout` - 请求超时时间
+    pub fn new(url: impl Into<String>, timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new().timeout(timeout).build()?,
+            url: url.into(),
+        })
+    }
+
+    /// 获取日历中的宏观事件
+    /// 
+    /// 假日等没有重要性分级的条目以及时间无法解析的条目会被跳过
+    pub async fn fetch(&self) -> Result<Vec<EconomicEvent>> {
+        debug!("🌐 请求经济日历URL: {}", self.url);
+        
+        let response = self.client.get(&self.url).send().await
+            .context("发送经济日历请求失败")?;
+        if !response.status().is_success() {
+            return Err(anyhow!("经济日历请求失败: HTTP {}", response.status()));
+        }
+        
+        let raw: Vec<RawCalendarEvent> = response.json().await
+            .context("解析经济日历数据失败")?;
+        let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+        
+        Ok(raw.into_iter()
+            .filter_map(|item| {
+                let importance = EventImportance::parse(&item.impact)?;
+                let time = match parse_timestamp(&item.date) {
+                    Ok(time) => time,
+                    Err(e) => {
+                        warn!("⚠️ 跳过时间无法解析的事件 {}: {}", item.title, e);
+                        return None;
+                    }
+                };
+                let mut event = EconomicEvent::new(item.title, item.country, time, importance);
+                event.forecast = non_empty(item.forecast);
+                event.previous = non_empty(item.previous);
+                Some(event)
+            })
+            .collect())
+    }
+}
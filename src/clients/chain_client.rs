@@ -0,0 +1,161 @@
+use anyhow::{Result, Context, anyhow};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::debug;
+
+use super::HttpClientBuilder;
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// 比特币链上数据客户端
+/// 
+/// 使用 mempool.space 的公开API，不需要API密钥
+#[derive(Clone)]
+pub struct ChainClient {
+    /// HTTP客户端
+    client: Client,
+    /// 基础URL
+    base_url: String,
+}
+
+/// 难度调整信息（只取需要的字段）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DifficultyAdjustment {
+    /// 最近区块的平均出块时间（毫秒）
+    time_avg: f64,
+    /// 当前调整周期的进度（百分比）
+    #[serde(default)]
+    progress_percent: Option<f64>,
+    /// 预计下次难度调整幅度（百分比）
+    #[serde(default)]
+    difficulty_change: Option<f64>,
+    /// 距下次调整的区块数
+    #[serde(default)]
+    remaining_blocks: Option<u64>,
+    /// 预计下次调整时间（毫秒时间戳）
+    #[serde(default)]
+    estimated_retarget_date: Option<i64>,
+}
+
+/// 全网算力响应（只取需要的字段）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HashrateResponse {
+    /// 当前全网算力（H/s）
+    current_hashrate: f64,
+    /// 当前难度
+    current_difficulty: f64,
+}
+
+/// 比特币挖矿概况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MiningStats {
+    /// 全网算力（H/s）
+    pub hashrate: f64,
+    /// 当前难度
+    pub difficulty: f64,
+    /// 预计下次难度调整幅度（百分比）
+    pub next_difficulty_change_pct: Option<f64>,
+    /// 当前调整周期的进度（百分比）
+    pub retarget_progress_pct: Option<f64>,
+    /// 距下次调整的区块数
+    pub retarget_remaining_blocks: Option<u64>,
+    /// 预计下次调整时间
+    pub estimated_retarget: Option<DateTime<Utc>>,
+}
+
+impl ChainClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "mempool",
+        display_name: "mempool.space",
+        kind: SourceKind::Api,
+        capabilities: &[SourceCapability::OnChain],
+    };
+
+    /// 创建新的链上数据客户端
+    /// 
+    /// # 参数
+    /// * `timeout` - 请求超时时间
+    pub fn new(timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new().timeout(timeout).build()?,
+            base_url: "https://mempool.space/api".to_string(),
+        })
+    }
+
+    /// 设置基础URL（用于自建 mempool 实例）
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// 获取当前区块高度
+    pub async fn tip_height(&self) -> Result<u64> {
+        let url = format!("{}/blocks/tip/height", self.base_url);
+        debug!("🌐 请求区块高度URL: {}", url);
+        
+        let response = self.client.get(&url).send().await
+            .context("发送区块高度请求失败")?;
+        if !response.status().is_success() {
+            return Err(anyhow!("区块高度API请求失败: HTTP {}", response.status()));
+        }
+        
+        let text = response.text().await.context("读取区块高度响应失败")?;
+        text.trim().parse().with_context(|| format!("无法解析区块高度: {}", text.trim()))
+    }
+
+    /// 获取最近区块的平均出块时间
+    pub async fn average_block_time(&self) -> Result<Duration> {
+        let url = format!("{}/v1/difficulty-adjustment", self.base_url);
+        debug!("🌐 请求难度调整信息URL: {}", url);
+        
+        let response = self.client.get(&url).send().await
+            .context("发送难度调整信息请求失败")?;
+        if !response.status().is_success() {
+            return Err(anyhow!("难度调整API请求失败: HTTP {}", response.status()));
+        }
+        
+        let adjustment: DifficultyAdjustment = response.json().await
+            .context("解析难度调整信息失败")?;
+        if !adjustment.time_avg.is_finite() || adjustment.time_avg <= 0.0 {
+            return Err(anyhow!("平均出块时间无效: {}", adjustment.time_avg));
+        }
+        Ok(Duration::from_millis(adjustment.time_avg as u64))
+    }
+
+    /// 获取全网算力、难度和下次难度调整预估
+    pub async fn mining_stats(&self) -> Result<MiningStats> {
+        let url = format!("{}/v1/mining/hashrate/3d", self.base_url);
+        debug!("🌐 请求全网算力URL: {}", url);
+
+        let response = self.client.get(&url).send().await
+            .context("发送全网算力请求失败")?;
+        if !response.status().is_success() {
+            return Err(anyhow!("全网算力API请求失败: HTTP {}", response.status()));
+        }
+        let hashrate: HashrateResponse = response.json().await
+            .context("解析全网算力失败")?;
+
+        let url = format!("{}/v1/difficulty-adjustment", self.base_url);
+        let response = self.client.get(&url).send().await
+            .context("发送难度调整信息请求失败")?;
+        if !response.status().is_success() {
+            return Err(anyhow!("难度调整API请求失败: HTTP {}", response.status()));
+        }
+        let adjustment: DifficultyAdjustment = response.json().await
+            .context("解析难度调整信息失败")?;
+
+        Ok(MiningStats {
+            hashrate: hashrate.current_hashrate,
+            difficulty: hashrate.current_difficulty,
+            next_difficulty_change_pct: adjustment.difficulty_change,
+            retarget_progress_pct: adjustment.progress_percent,
+            retarget_remaining_blocks: adjustment.remaining_blocks,
+            estimated_retarget: adjustment.estimated_retarget_date.and_then(DateTime::from_timestamp_millis),
+        })
+    }
+}
@@ -0,0 +1,202 @@
+use anyhow::{Result, Context, anyhow};
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+use tracing::debug;
+
+use super::{HttpClientBuilder, ListedCoin};
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// 历史行情响应
+#[derive(Debug, Deserialize)]
+struct MarketChart {
+    /// 价格: [时间戳(毫秒), 价格]
+    prices: Vec<[f64; 2]>,
+}
+
+/// 币种列表条目
+#[derive(Debug, Deserialize)]
+struct CoinListEntry {
+    id: String,
+    symbol: String,
+    name: String,
+}
+
+/// CoinGecko返回非成功状态码
+#[derive(Debug, Clone)]
+pub struct CoinGeckoHttpError {
+    /// 请求的币种
+    pub coin_id: String,
+    /// HTTP状态码
+    pub status: StatusCode,
+}
+
+impl CoinGeckoHttpError {
+    /// 是否为重试也不会成功的错误（除限流外的4xx，如币种ID不存在或已下架）
+    pub fn is_permanent(&self) -> bool {
+        self.status.is_client_error() && self.status != StatusCode::TOO_MANY_REQUESTS
+    }
+}
+
+impl fmt::Display for CoinGeckoHttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CoinGecko API请求失败: {} HTTP {}", self.coin_id, self.status)
+    }
+}
+
+impl std::error::Error for CoinGeckoHttpError {}
+
+/// 每次批量查询价格的最大币种数
+const SIMPLE_PRICE_BATCH: usize = 50;
+
+/// CoinGecko行情客户端
+/// 
+/// 使用免费的 `simple/price`、`market_chart` 和 `coins/list` 接口获取参考价格、历史价格和币种列表，不需要API密钥
+#[derive(Clone)]
+pub struct CoinGeckoClient {
+    /// HTTP客户端
+    client: Client,
+    /// 基础URL
+    base_url: String,
+}
+
+impl CoinGeckoClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "coingecko",
+        display_name: "CoinGecko",
+        kind: SourceKind::Api,
+        capabilities: &[SourceCapability::MarketData],
+    };
+
+    /// 创建新的CoinGecko客户端
+    /// 
+    /// # 参数
+    /// * `timeout` - 请求超时时间
+    pub fn new(timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new().timeout(timeout).build()?,
+            base_url: "https://api.coingecko.com/api/v3".to_string(),
+        })
+    }
+
+    /// 获取币种的美元价格
+    /// 
+    /// # 参数
+    /// * `coin_id` - CoinGecko币种ID（如 `hyperliquid`）
+    /// 
+    /// # 返回
+    /// * `Result<f64>` - 美元价格
+    pub async fn get_usd_price(&self, coin_id: &str) -> Result<f64> {
+        let url = format!("{}/simple/price", self.base_url);
+        debug!("🌐 请求CoinGecko价格: {}", coin_id);
+
+        let response = self.client.get(&url)
+            .query(&[("ids", coin_id), ("vs_currencies", "usd")])
+            .send().await
+            .with_context(|| format!("发送CoinGecko请求失败: {}", coin_id))?;
+        if !response.status().is_success() {
+            return Err(anyhow!("CoinGecko API请求失败: {} HTTP {}", coin_id, response.status()));
+        }
+
+        let body: HashMap<String, HashMap<String, f64>> = response.json().await
+            .with_context(|| format!("解析CoinGecko响应失败: {}", coin_id))?;
+        body.get(coin_id)
+            .and_then(|prices| prices.get("usd"))
+            .copied()
+            .ok_or_else(|| anyhow!("CoinGecko响应中没有 {} 的美元价格", coin_id))
+    }
+
+    /// 获取币种的每日历史价格
+    /// 
+    /// # 参数
+    /// * `coin_id` - CoinGecko币种ID（如 `hyperliquid`）
+    /// * `days` - 向前的天数（免费接口最多365天）
+    /// 
+    /// # 返回
+    /// * `Result<Vec<(DateTime<Utc>, f64)>>` - 按时间升序的（时间，美元价格），最后一个点为当前价格
+    pub async fn get_coin_history(&self, coin_id: &str, days: u32) -> Result<Vec<(DateTime<Utc>, f64)>> {
+        let url = format!("{}/coins/{}/market_chart", self.base_url, coin_id);
+        debug!("🌐 请求CoinGecko历史价格: {}（{} 天）", coin_id, days);
+
+        let response = self.client.get(&url)
+            .query(&[("vs_currency", "usd"), ("days", &days.to_string()), ("interval", "daily")])
+            .send().await
+            .with_context(|| format!("发送CoinGecko请求失败: {}", coin_id))?;
+        if !response.status().is_success() {
+            return Err(CoinGeckoHttpError { coin_id: coin_id.to_string(), status: response.status() }.into());
+        }
+
+        let chart: MarketChart = response.json().await
+            .with_context(|| format!("解析CoinGecko历史价格失败: {}", coin_id))?;
+        let mut prices: Vec<(DateTime<Utc>, f64)> = chart.prices.iter()
+            .filter_map(|[timestamp, price]| Some((Utc.timestamp_millis_opt(*timestamp as i64).single()?, *price)))
+            .collect();
+        prices.sort_by_key(|(timestamp, _)| *timestamp);
+        Ok(prices)
+    }
+
+    /// 获取CoinGecko收录的全部币种（不含价格）
+    /// 
+    /// # 返回
+    /// * `Result<Vec<ListedCoin>>` - 币种列表
+    pub async fn get_coin_list(&self) -> Result<Vec<ListedCoin>> {
+        let url = format!("{}/coins/list", self.base_url);
+        debug!("🌐 请求CoinGecko币种列表");
+
+        let response = self.client.get(&url).send().await
+            .context("发送CoinGecko币种列表请求失败")?;
+        if !response.status().is_success() {
+            return Err(anyhow!("CoinGecko币种列表请求失败: HTTP {}", response.status()));
+        }
+
+        let entries: Vec<CoinListEntry> = response.json().await
+            .context("解析CoinGecko币种列表失败")?;
+        Ok(entries.into_iter()
+            .map(|entry| ListedCoin {
+                id: entry.id,
+                symbol: entry.symbol.to_uppercase(),
+                name: entry.name,
+                price: None,
+                market_cap: None,
+            })
+            .collect())
+    }
+
+    /// 批量获取币种的美元价格和市值
+    /// 
+    /// # 参数
+    /// * `coin_ids` - CoinGecko币种ID
+    /// 
+    /// # 返回
+    /// * `Result<HashMap<String, (f64, Option<f64>)>>` - 币种ID -> （美元价格，市值），没有报价的币种不包含在内
+    pub async fn get_usd_quotes(&self, coin_ids: &[String]) -> Result<HashMap<String, (f64, Option<f64>)>> {
+        let url = format!("{}/simple/price", self.base_url);
+        let mut quotes = HashMap::new();
+        for batch in coin_ids.chunks(SIMPLE_PRICE_BATCH) {
+            let ids = batch.join(",");
+            debug!("🌐 请求CoinGecko价格: {} 个币种", batch.len());
+            let response = self.client.get(&url)
+                .query(&[("ids", ids.as_str()), ("vs_currencies", "usd"), ("include_market_cap", "true")])
+                .send().await
+                .context("发送CoinGecko批量价格请求失败")?;
+            if !response.status().is_success() {
+                return Err(anyhow!("CoinGecko批量价格请求失败: HTTP {}", response.status()));
+            }
+
+            let body: HashMap<String, HashMap<String, f64>> = response.json().await
+                .context("解析CoinGecko批量价格响应失败")?;
+            for (coin_id, fields) in body {
+                if let Some(price) = fields.get("usd") {
+                    let market_cap = fields.get("usd_market_cap").copied().filter(|m| *m > 0.0);
+                    quotes.insert(coin_id, (*price, market_cap));
+                }
+            }
+        }
+        Ok(quotes)
+    }
+}
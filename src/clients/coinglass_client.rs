@@ -0,0 +1,145 @@
+use anyhow::{Result, Context, anyhow};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::debug;
+
+use super::HttpClientBuilder;
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// 单个币种一小时的爆仓金额（各交易所合计）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationRecord {
+    /// 币种符号（如 BTC）
+    pub symbol: String,
+    /// 小时起始时间
+    pub hour: DateTime<Utc>,
+    /// 多单爆仓金额（美元）
+    pub long_usd: f64,
+    /// 空单爆仓金额（美元）
+    pub short_usd: f64,
+}
+
+/// Coinglass响应外层结构
+#[derive(Debug, Deserialize)]
+struct CoinglassResponse {
+    /// 状态码（"0"为成功）
+    code: String,
+    /// 错误信息
+    #[serde(default)]
+    msg: Option<String>,
+    /// 数据
+    #[serde(default)]
+    data: Option<Vec<RawLiquidation>>,
+}
+
+/// Coinglass聚合爆仓数据点
+#[derive(Debug, Deserialize)]
+struct RawLiquidation {
+    /// 时间（毫秒，旧版接口为秒）
+    #[serde(alias = "t")]
+    time: i64,
+    /// 多单爆仓金额
+    #[serde(alias = "longLiquidationUsd")]
+    aggregated_long_liquidation_usd: f64,
+    /// 空单爆仓金额
+    #[serde(alias = "shortLiquidationUsd")]
+    aggregated_short_liquidation_usd: f64,
+}
+
+/// Coinglass衍生品数据客户端
+///
+/// 使用聚合爆仓历史接口获取各交易所合约的每小时多/空爆仓金额
+#[derive(Clone)]
+pub struct CoinglassClient {
+    /// HTTP客户端
+    client: Client,
+    /// API密钥
+    api_key: String,
+    /// 基础URL
+    base_url: String,
+}
+
+impl CoinglassClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "coinglass",
+        display_name: "Coinglass",
+        kind: SourceKind::Api,
+        capabilities: &[SourceCapability::Derivatives],
+    };
+
+    /// 创建新的Coinglass客户端
+    ///
+    /// # 参数
+    /// * `api_key` - Coinglass API密钥
+    /// * `timeout` - 请求超时时间
+    pub fn new(api_key: impl Into<String>, timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new()
+                .timeout(timeout)
+                .user_agent("EverScan-CoinglassClient/1.0")
+                .build()?,
+            api_key: api_key.into(),
+            base_url: "https://open-api-v4.coinglass.com/api".to_string(),
+        })
+    }
+
+    /// 获取币种最近若干小时的聚合爆仓金额
+    ///
+    /// # 参数
+    /// * `symbol` - 币种符号（如 BTC）
+    /// * `exchanges` - 参与聚合的交易所（如 Binance、OKX）
+    /// * `hours` - 获取的小时数
+    ///
+    /// # 返回
+    /// * `Result<Vec<LiquidationRecord>>` - 按时间升序的每小时爆仓金额，最后一条通常是尚未结束的当前小时
+    pub async fn get_liquidation_history(&self, symbol: &str, exchanges: &[String], hours: usize) -> Result<Vec<LiquidationRecord>> {
+        let url = format!("{}/futures/liquidation/aggregated-history", self.base_url);
+        let symbol = symbol.to_uppercase();
+        debug!("🌐 请求Coinglass聚合爆仓数据: {}", symbol);
+
+        let limit = hours.clamp(1, 1000).to_string();
+        let exchange_list = exchanges.join(",");
+        let response = self.client.get(&url)
+            .header("CG-API-KEY", &self.api_key)
+            .query(&[
+                ("symbol", symbol.as_str()),
+                ("interval", "1h"),
+                ("limit", limit.as_str()),
+                ("exchange_list", exchange_list.as_str()),
+            ])
+            .send()
+            .await
+            .context("发送Coinglass请求失败")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Coinglass API请求失败: {} - {}", status, text));
+        }
+
+        let body: CoinglassResponse = response.json().await
+            .context("解析Coinglass响应失败")?;
+        if body.code != "0" {
+            return Err(anyhow!("Coinglass API返回错误: {} - {}", body.code, body.msg.unwrap_or_default()));
+        }
+
+        let mut records: Vec<LiquidationRecord> = body.data.unwrap_or_default()
+            .into_iter()
+            .filter_map(|raw| {
+                // 旧版接口的时间为秒
+                let millis = if raw.time < 10_000_000_000 { raw.time * 1000 } else { raw.time };
+                Some(LiquidationRecord {
+                    symbol: symbol.clone(),
+                    hour: DateTime::from_timestamp_millis(millis)?,
+                    long_usd: raw.aggregated_long_liquidation_usd,
+                    short_usd: raw.aggregated_short_liquidation_usd,
+                })
+            })
+            .collect();
+        records.sort_by_key(|r| r.hour);
+        Ok(records)
+    }
+}
@@ -1,11 +1,16 @@
 use anyhow::{Result, Context};
-use reqwest::{Client, ClientBuilder};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, ClientBuilder, StatusCode};
 use reqwest::header::{HeaderMap, HeaderValue};
 use serde::{Deserialize, Serialize, Deserializer};
 use std::time::Duration;
 use tracing::{info, debug, warn};
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use super::ListedCoin;
+use crate::models::{deserialize_timestamp, record_source_usage, register_source, DataSource, SourceCapability, SourceKind};
 
 /// CoinMarketCap API客户端
 /// 
@@ -18,19 +23,98 @@ pub struct CoinMarketCapClient {
     api_key: Option<String>,
     /// 基础URL
     base_url: String,
+    /// 贪婪恐惧指数数据源
+    fear_greed_provider: FearGreedProvider,
+    /// CoinMarketCap贪婪恐惧指数端点最近一次返回403/404的时间（自动模式下冷却期内直接使用Alternative.me）
+    cmc_fear_greed_unavailable_at: Arc<Mutex<Option<DateTime<Utc>>>>,
 }
 
-/// 贪婪恐惧指数数据
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FearGreedIndex {
+/// 贪婪恐惧指数数据源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FearGreedProvider {
+    /// 配置了API密钥时使用CoinMarketCap，端点返回403/404时自动改用Alternative.me
+    #[default]
+    Auto,
+    /// 只使用CoinMarketCap（需要API密钥）
+    Coinmarketcap,
+    /// 只使用Alternative.me的免费API
+    AlternativeMe,
+}
+
+/// Alternative.me数据源名称
+pub const ALTERNATIVE_ME_SOURCE: &str = "alternative.me";
+
+/// CoinMarketCap贪婪恐惧指数端点返回403/404后，自动模式下重新尝试前的冷却时间（秒）
+const CMC_FEAR_GREED_RETRY_SECONDS: i64 = 3600;
+
+/// 贪婪恐惧指数快照
+///
+/// 客户端、缓存、缓存快照和API共用的贪婪恐惧指数模型，本地化文案在创建时由指数值生成
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FearGreedSnapshot {
     /// 指数值 (0-100)
     pub value: u8,
     /// 指数分类 (如: "Extreme Fear", "Fear", "Neutral", "Greed", "Extreme Greed")
     pub value_classification: String,
-    /// 时间戳
-    pub timestamp: String,
-    /// 更新时间（Unix时间戳）
+    /// 中文分类
+    pub value_classification_zh: String,
+    /// 情绪描述
+    pub sentiment_description: String,
+    /// 投资建议
+    pub investment_advice: String,
+    /// 指数对应的时间（UTC，兼容旧数据中的Unix时间戳字符串）
+    #[serde(deserialize_with = "deserialize_timestamp")]
+    pub timestamp: DateTime<Utc>,
+    /// 距下次更新的秒数（数据源提供时）
     pub time_until_update: Option<u64>,
+    /// 采集时间
+    pub fetched_at: DateTime<Utc>,
+    /// 数据源（如 `alternative.me`）
+    pub source: String,
+}
+
+impl FearGreedSnapshot {
+    /// 由数据源返回的指数值和分类创建快照
+    ///
+    /// # 参数
+    /// * `value` - 指数值 (0-100)
+    /// * `value_classification` - 数据源返回的英文分类
+    /// * `timestamp` - 指数对应的时间
+    /// * `source` - 数据源名称
+    pub fn new(value: u8, value_classification: impl Into<String>, timestamp: DateTime<Utc>, source: impl Into<String>) -> Self {
+        let value_classification = value_classification.into();
+        Self {
+            value,
+            value_classification_zh: CoinMarketCapClient::get_chinese_classification(&value_classification).to_string(),
+            sentiment_description: CoinMarketCapClient::get_sentiment_description(value).to_string(),
+            investment_advice: CoinMarketCapClient::get_investment_advice(value).to_string(),
+            value_classification,
+            timestamp,
+            time_until_update: None,
+            fetched_at: Utc::now(),
+            source: source.into(),
+        }
+    }
+
+    /// 只有指数值时创建快照（分类按指数值推算）
+    ///
+    /// # 参数
+    /// * `value` - 指数值 (0-100)
+    /// * `timestamp` - 指数对应的时间
+    /// * `source` - 数据源名称
+    pub fn from_value(value: u8, timestamp: DateTime<Utc>, source: impl Into<String>) -> Self {
+        Self::new(value, CoinMarketCapClient::get_fear_greed_classification(value), timestamp, source)
+    }
+
+    /// 替换指数值，分类和本地化文案随之更新（用于情景模拟）
+    pub fn with_value(&self, value: u8) -> Self {
+        Self {
+            time_until_update: self.time_until_update,
+            fetched_at: self.fetched_at,
+            ..Self::from_value(value, self.timestamp, self.source.clone())
+        }
+    }
 }
 
 /// 山寨币季节指数
@@ -42,8 +126,9 @@ pub struct AltcoinSeasonIndex {
     pub classification: String,
     /// 中文分类
     pub classification_zh: String,
-    /// 获取时间戳
-    pub timestamp: String,
+    /// 计算时间（UTC）
+    #[serde(deserialize_with = "deserialize_timestamp")]
+    pub timestamp: DateTime<Utc>,
     /// 表现优于比特币的币种数量
     pub outperforming_count: u8,
     /// 总计币种数量（通常是100）
@@ -54,6 +139,128 @@ pub struct AltcoinSeasonIndex {
     pub market_advice: String,
 }
 
+/// 山寨币季节指数的成分币种
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AltcoinSeasonConstituent {
+    /// 符号
+    pub symbol: String,
+    /// 名称
+    pub name: String,
+    /// CMC排名
+    pub cmc_rank: Option<u64>,
+    /// 24小时涨跌幅（%）
+    pub change_24h: f64,
+    /// 90天涨跌幅（%）
+    pub change_90d: Option<f64>,
+    /// 24小时表现是否优于比特币
+    pub outperforming: bool,
+}
+
+/// 计算山寨币季节指数时排除的币种
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExcludedToken {
+    /// 符号
+    pub symbol: String,
+    /// 排除原因
+    pub reason: String,
+}
+
+/// 山寨币季节指数的计算公式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AltcoinSeasonFormula {
+    /// 24小时涨跌幅跑赢比特币的币种占比
+    #[default]
+    #[serde(rename = "change_24h")]
+    Change24h,
+    /// 90天涨跌幅跑赢比特币的币种占比（缺少90天涨跌幅的币种不参与比较）
+    #[serde(rename = "change_90d")]
+    Change90d,
+}
+
+impl AltcoinSeasonFormula {
+    /// 转换为字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Change24h => "change_24h",
+            Self::Change90d => "change_90d",
+        }
+    }
+}
+
+/// 山寨币季节指数的计算明细
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AltcoinSeasonConstituents {
+    /// 比特币24小时涨跌幅（%，比较基准）
+    pub btc_change_24h: f64,
+    /// 比特币90天涨跌幅（%）
+    pub btc_change_90d: Option<f64>,
+    /// 参与计算的币种
+    pub constituents: Vec<AltcoinSeasonConstituent>,
+    /// 排除的币种
+    pub excluded: Vec<ExcludedToken>,
+}
+
+impl AltcoinSeasonConstituents {
+    /// 按成分币种重新计算指数值（24小时跑赢比特币的占比，四舍五入到整数）
+    pub fn index_value(&self) -> u8 {
+        self.index_value_with(AltcoinSeasonFormula::Change24h)
+    }
+
+    /// 按指定公式计算指数值（跑赢比特币的占比，四舍五入到整数）
+    pub fn index_value_with(&self, formula: AltcoinSeasonFormula) -> u8 {
+        let (outperforming, compared) = self.outperformance(formula);
+        if compared == 0 {
+            return 0;
+        }
+        (outperforming as f32 / compared as f32 * 100.0).round() as u8
+    }
+
+    /// 按指定公式统计跑赢比特币的币种数和参与比较的币种数
+    pub fn outperformance(&self, formula: AltcoinSeasonFormula) -> (usize, usize) {
+        match formula {
+            AltcoinSeasonFormula::Change24h => (
+                self.constituents.iter().filter(|c| c.outperforming).count(),
+                self.constituents.len(),
+            ),
+            AltcoinSeasonFormula::Change90d => {
+                let Some(btc_change_90d) = self.btc_change_90d else {
+                    return (0, 0);
+                };
+                let changes: Vec<f64> = self.constituents.iter().filter_map(|c| c.change_90d).collect();
+                (changes.iter().filter(|&&change| change > btc_change_90d).count(), changes.len())
+            }
+        }
+    }
+}
+
+/// CMC上市信息
+/// 
+/// 排名、流通量和标签用于检测上市信息变化，市值和涨跌幅用于计算Total2/Total3等汇总市值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CmcListing {
+    /// CMC币种ID
+    pub id: u64,
+    /// 符号
+    pub symbol: String,
+    /// 名称
+    pub name: String,
+    /// CMC排名
+    pub cmc_rank: Option<u64>,
+    /// 流通量
+    pub circulating_supply: Option<f64>,
+    /// 标签
+    pub tags: Vec<String>,
+    /// 市值（美元）
+    #[serde(default)]
+    pub market_cap: Option<f64>,
+    /// 24小时交易量（美元）
+    #[serde(default)]
+    pub volume_24h: Option<f64>,
+    /// 24小时价格变化百分比
+    #[serde(default)]
+    pub percent_change_24h: Option<f64>,
+}
+
 /// 加密货币数据
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CryptocurrencyData {
@@ -75,10 +282,57 @@ pub struct CryptocurrencyData {
     pub percent_change_7d: Option<f64>,
     /// 市值排名
     pub cmc_rank: Option<u64>,
-    /// 最后更新时间
-    pub last_updated: String,
+    /// 是否仍在活跃交易（CoinMarketCap的 `is_active` 标志，未返回时视为活跃）
+    #[serde(default = "default_is_active")]
+    pub is_active: bool,
+    /// 流通量
+    #[serde(default)]
+    pub circulating_supply: Option<f64>,
+    /// 总供应量
+    #[serde(default)]
+    pub total_supply: Option<f64>,
+    /// 最大供应量（无上限时为None）
+    #[serde(default)]
+    pub max_supply: Option<f64>,
+    /// 完全稀释市值
+    #[serde(default)]
+    pub fully_diluted_market_cap: Option<f64>,
+    /// 分类标签（如 `layer-1`、`defi`、`memes`）
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 报价最后更新时间（UTC）
+    #[serde(deserialize_with = "deserialize_timestamp")]
+    pub last_updated: DateTime<Utc>,
 }
 
+fn default_is_active() -> bool {
+    true
+}
+
+/// CoinMarketCap未收录该币种（已下架或符号失效）
+#[derive(Debug, Clone)]
+pub struct CoinNotListed(pub String);
+
+impl fmt::Display for CoinNotListed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CoinMarketCap未收录 {} 币种", self.0)
+    }
+}
+
+impl std::error::Error for CoinNotListed {}
+
+/// CoinMarketCap端点对当前API密钥不可用（HTTP 403/404，通常是套餐不包含该端点）
+#[derive(Debug, Clone)]
+pub struct CmcEndpointUnavailable(pub StatusCode);
+
+impl fmt::Display for CmcEndpointUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CoinMarketCap端点不可用: HTTP {}", self.0)
+    }
+}
+
+impl std::error::Error for CmcEndpointUnavailable {}
+
 /// CoinMarketCap Fear & Greed API响应结构（最新数据）
 #[derive(Debug, Deserialize)]
 struct CmcFearGreedResponse {
@@ -96,27 +350,8 @@ struct CmcFearGreedData {
     /// 指数分类
     value_classification: String,
     /// 更新时间
-    update_time: String,
-}
-
-/// CoinMarketCap Fear & Greed API响应结构（历史数据）
-#[derive(Debug, Deserialize)]
-struct CmcFearGreedHistoryResponse {
-    /// 响应数据 - 数组格式
-    data: Vec<CmcFearGreedHistoryData>,
-    /// API状态
-    status: ApiStatus,
-}
-
-/// CoinMarketCap Fear & Greed 数据结构（历史数据）
-#[derive(Debug, Deserialize)]
-struct CmcFearGreedHistoryData {
-    /// 指数值 (0-100)
-    value: u64,
-    /// 指数分类
-    value_classification: String,
-    /// 时间戳
-    timestamp: String,
+    #[serde(deserialize_with = "deserialize_timestamp")]
+    update_time: DateTime<Utc>,
 }
 
 /// Legacy API响应结构（Alternative.me格式，已废弃）
@@ -133,8 +368,9 @@ struct FearGreedData {
     value: String,
     /// 指数分类
     value_classification: String,
-    /// 时间戳
-    timestamp: String,
+    /// 时间戳（Unix秒，字符串形式）
+    #[serde(deserialize_with = "deserialize_timestamp")]
+    timestamp: DateTime<Utc>,
     /// 更新时间
     time_until_update: Option<String>,
 }
@@ -154,7 +390,32 @@ struct CmcIndexData {
     symbol: String,
     quote: HashMap<String, Quote>,
     cmc_rank: Option<u64>,
-    last_updated: String,
+    #[serde(default)]
+    circulating_supply: Option<f64>,
+    /// 标签（字符串，或带 `slug` 字段的对象）
+    #[serde(default)]
+    tags: Vec<serde_json::Value>,
+}
+
+impl CmcIndexData {
+    /// 转换为上市信息
+    fn listing(&self) -> CmcListing {
+        let quote = self.quote.get("USD");
+        CmcListing {
+            id: self.id,
+            symbol: self.symbol.clone(),
+            name: self.name.clone(),
+            cmc_rank: self.cmc_rank,
+            circulating_supply: self.circulating_supply,
+            tags: self.tags.iter()
+                .filter_map(|tag| tag.as_str().or_else(|| tag.get("slug")?.as_str()))
+                .map(str::to_string)
+                .collect(),
+            market_cap: quote.map(|q| q.market_cap),
+            volume_24h: quote.map(|q| q.volume_24h),
+            percent_change_24h: quote.map(|q| q.percent_change_24h),
+        }
+    }
 }
 
 /// 报价数据
@@ -165,28 +426,20 @@ struct Quote {
     volume_24h: f64,
     percent_change_24h: f64,
     percent_change_7d: Option<f64>,
-    last_updated: String,
+    #[serde(deserialize_with = "deserialize_timestamp")]
+    last_updated: DateTime<Utc>,
     // 新增字段
     fully_diluted_market_cap: Option<f64>,
-    market_cap_dominance: Option<f64>,
-    percent_change_1h: Option<f64>,
-    percent_change_30d: Option<f64>,
-    percent_change_60d: Option<f64>,
     percent_change_90d: Option<f64>,
-    tvl: Option<f64>,
-    volume_change_24h: Option<f64>,
 }
 
 /// API状态
 #[derive(Debug, Deserialize)]
 struct ApiStatus {
-    timestamp: String,
     #[serde(deserialize_with = "deserialize_string_or_number")]
     error_code: u64,
     error_message: Option<String>,
-    elapsed: u64,
     credit_count: u64,
-    notice: Option<String>,
 }
 
 /// 加密货币响应
@@ -202,27 +455,25 @@ struct CryptocurrencyInfo {
     id: u64,
     name: String,
     symbol: String,
-    slug: String,
-    num_market_pairs: Option<u64>,
-    date_added: Option<String>,
     tags: Option<Vec<String>>,
     max_supply: Option<f64>,
     circulating_supply: Option<f64>,
     total_supply: Option<f64>,
-    platform: Option<serde_json::Value>,
     quote: HashMap<String, Quote>,
     cmc_rank: Option<u64>,
-    last_updated: String,
-    // 新增字段
     infinite_supply: Option<bool>,
     is_active: Option<u64>,
-    is_fiat: Option<u64>,
-    self_reported_circulating_supply: Option<f64>,
-    self_reported_market_cap: Option<f64>,
-    tvl_ratio: Option<f64>,
 }
 
 impl CoinMarketCapClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "coinmarketcap",
+        display_name: "CoinMarketCap",
+        kind: SourceKind::Api,
+        capabilities: &[SourceCapability::MarketData, SourceCapability::Sentiment],
+    };
+
     /// 创建新的CoinMarketCap客户端
     /// 
     /// # 参数
@@ -232,6 +483,7 @@ impl CoinMarketCapClient {
     /// # 返回
     /// * `Result<Self>` - 客户端实例或错误
     pub fn new(api_key: Option<String>, timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
         let mut headers = HeaderMap::new();
         headers.insert("User-Agent", HeaderValue::from_static(
             "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36"
@@ -253,16 +505,130 @@ impl CoinMarketCapClient {
             client,
             api_key,
             base_url: "https://pro-api.coinmarketcap.com".to_string(),
+            fear_greed_provider: FearGreedProvider::default(),
+            cmc_fear_greed_unavailable_at: Arc::new(Mutex::new(None)),
         })
     }
 
+    /// 设置贪婪恐惧指数数据源
+    ///
+    /// # 参数
+    /// * `provider` - 数据源
+    pub fn with_fear_greed_provider(mut self, provider: FearGreedProvider) -> Self {
+        self.fear_greed_provider = provider;
+        self
+    }
+
     /// 获取贪婪恐惧指数
     /// 
+    /// 按配置的数据源获取：自动模式下配置了API密钥时先请求CoinMarketCap的
+    /// `/v3/fear-and-greed/latest`，该端点对密钥不可用（HTTP 403/404）时改用Alternative.me，
+    /// 冷却一小时后再尝试CoinMarketCap，升级API套餐后无需重启即可生效
+    /// 
+    /// # 返回
+    /// * `Result<FearGreedSnapshot>` - 贪婪恐惧指数快照或错误
+    pub async fn get_fear_greed_index(&self) -> Result<FearGreedSnapshot> {
+        match self.fear_greed_provider {
+            FearGreedProvider::AlternativeMe => self.get_alternative_me_fear_greed().await,
+            FearGreedProvider::Coinmarketcap => self.get_cmc_fear_greed().await,
+            FearGreedProvider::Auto if self.api_key.is_none() || self.cmc_fear_greed_cooling_down(Utc::now()) => {
+                self.get_alternative_me_fear_greed().await
+            }
+            FearGreedProvider::Auto => match self.get_cmc_fear_greed().await {
+                Err(e) if e.is::<CmcEndpointUnavailable>() => {
+                    warn!("⚠️ {}，贪婪恐惧指数改用Alternative.me，{} 秒后重新尝试", e, CMC_FEAR_GREED_RETRY_SECONDS);
+                    if let Ok(mut unavailable_at) = self.cmc_fear_greed_unavailable_at.lock() {
+                        *unavailable_at = Some(Utc::now());
+                    }
+                    self.get_alternative_me_fear_greed().await
+                }
+                result => result,
+            },
+        }
+    }
+
+    /// CoinMarketCap贪婪恐惧指数端点是否仍在不可用后的冷却期内
+    ///
+    /// # 参数
+    /// * `now` - 当前时间
+    fn cmc_fear_greed_cooling_down(&self, now: DateTime<Utc>) -> bool {
+        self.cmc_fear_greed_unavailable_at.lock().ok()
+            .and_then(|unavailable_at| *unavailable_at)
+            .is_some_and(|at| (now - at).num_seconds() < CMC_FEAR_GREED_RETRY_SECONDS)
+    }
+
+    /// 从CoinMarketCap获取贪婪恐惧指数（需要API密钥）
+    /// 
+    /// # 返回
+    /// * `Result<FearGreedSnapshot>` - 贪婪恐惧指数快照或错误，端点不可用时为 `CmcEndpointUnavailable`
+    async fn get_cmc_fear_greed(&self) -> Result<FearGreedSnapshot> {
+        info!("📊 开始获取贪婪恐惧指数（使用CoinMarketCap API）");
+
+        let api_key = self.api_key.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("需要API密钥来获取CoinMarketCap贪婪恐惧指数"))?;
+        let url = format!("{}/v3/fear-and-greed/latest", self.base_url);
+
+        debug!("🌐 请求URL: {}", url);
+
+        let response = self.client
+            .get(&url)
+            .header("X-CMC_PRO_API_KEY", api_key)
+            .header("Accept", "application/json")
+            .header("Accept-Encoding", "identity")
+            .send()
+            .await
+            .context("发送CMC贪婪恐惧指数请求失败")?;
+
+        let status = response.status();
+        if status == StatusCode::FORBIDDEN || status == StatusCode::NOT_FOUND {
+            return Err(CmcEndpointUnavailable(status).into());
+        }
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_else(|_| "无法读取错误响应".to_string());
+            return Err(anyhow::anyhow!(
+                "CMC贪婪恐惧指数API请求失败: HTTP {} - {}",
+                status,
+                error_text
+            ));
+        }
+
+        let response_text = response.text().await
+            .context("读取CMC响应内容失败")?;
+
+        debug!("📄 CMC贪婪恐惧指数原始响应: {}", response_text);
+
+        let cmc_response: CmcFearGreedResponse = serde_json::from_str(&response_text)
+            .with_context(|| format!("解析CMC贪婪恐惧指数响应失败，原始响应: {}", response_text))?;
+        record_source_usage(Self::SOURCE.name, cmc_response.status.credit_count);
+
+        if cmc_response.status.error_code != 0 {
+            return Err(anyhow::anyhow!(
+                "CMC API错误: {} - {}",
+                cmc_response.status.error_code,
+                cmc_response.status.error_message.unwrap_or_else(|| "未知错误".to_string())
+            ));
+        }
+
+        let data = cmc_response.data;
+        let value = u8::try_from(data.value).ok()
+            .filter(|value| *value <= 100)
+            .ok_or_else(|| anyhow::anyhow!("CMC贪婪恐惧指数值超出范围: {}", data.value))?;
+        let fear_greed_index = FearGreedSnapshot::new(value, data.value_classification, data.update_time, Self::SOURCE.name);
+
+        info!("✅ 贪婪恐惧指数获取成功: {} - {}",
+              fear_greed_index.value,
+              fear_greed_index.value_classification);
+
+        Ok(fear_greed_index)
+    }
+
+    /// 从Alternative.me获取贪婪恐惧指数
+    /// 
     /// 使用Alternative.me的免费API，不需要CoinMarketCap API密钥
     /// 
     /// # 返回
-    /// * `Result<FearGreedIndex>` - 贪婪恐惧指数数据或错误
-    pub async fn get_fear_greed_index(&self) -> Result<FearGreedIndex> {
+    /// * `Result<FearGreedSnapshot>` - 贪婪恐惧指数快照或错误
+    async fn get_alternative_me_fear_greed(&self) -> Result<FearGreedSnapshot> {
         info!("📊 开始获取贪婪恐惧指数（使用Alternative.me API）");
         
         // 使用Alternative.me的免费API
@@ -306,11 +672,9 @@ impl CoinMarketCapClient {
         let time_until_update = data.time_until_update.as_ref()
             .and_then(|s| s.parse::<u64>().ok());
         
-        let fear_greed_index = FearGreedIndex {
-            value,
-            value_classification: data.value_classification.clone(),
-            timestamp: data.timestamp.clone(),
+        let fear_greed_index = FearGreedSnapshot {
             time_until_update,
+            ..FearGreedSnapshot::new(value, data.value_classification.clone(), data.timestamp, ALTERNATIVE_ME_SOURCE)
         };
 
         info!("✅ 贪婪恐惧指数获取成功: {} - {}", 
@@ -327,19 +691,39 @@ impl CoinMarketCapClient {
     /// # 返回
     /// * `Result<AltcoinSeasonIndex>` - 山寨币季节指数数据或错误
     pub async fn get_altcoin_season_index(&self) -> Result<AltcoinSeasonIndex> {
+        Ok(self.get_altcoin_season_with_constituents().await?.0)
+    }
+
+    /// 获取山寨币季节指数及其计算明细
+    /// 
+    /// # 返回
+    /// * `Result<(AltcoinSeasonIndex, AltcoinSeasonConstituents)>` - 指数和成分币种，或错误
+    pub async fn get_altcoin_season_with_constituents(&self) -> Result<(AltcoinSeasonIndex, AltcoinSeasonConstituents)> {
+        let (altcoin_index, constituents, _) = self.get_altcoin_season_with_listings().await?;
+        Ok((altcoin_index, constituents))
+    }
+
+    /// 获取山寨币季节指数、计算明细和市值前100币种的上市信息
+    /// 
+    /// 上市信息（排名、流通量、标签）来自同一次请求，用于检测排名和供应量变化
+    /// 
+    /// # 返回
+    /// * `Result<(AltcoinSeasonIndex, AltcoinSeasonConstituents, Vec<CmcListing>)>` - 指数、成分币种和上市信息，或错误
+    pub async fn get_altcoin_season_with_listings(&self) -> Result<(AltcoinSeasonIndex, AltcoinSeasonConstituents, Vec<CmcListing>)> {
         info!("🪙 开始获取山寨币季节指数（基于CMC 100指数）");
         
         // 获取CMC 100指数数据
         let cmc_data = self.get_cmc_100_index().await?;
         
         // 计算山寨币季节指数
-        let altcoin_index = self.calculate_altcoin_season_from_cmc(&cmc_data).await?;
+        let (altcoin_index, constituents) = Self::calculate_altcoin_season_from_cmc(&cmc_data)?;
         
         info!("✅ 山寨币季节指数计算成功: {} - {}", 
               altcoin_index.value, 
               altcoin_index.classification_zh);
 
-        Ok(altcoin_index)
+        let listings = cmc_data.iter().map(CmcIndexData::listing).collect();
+        Ok((altcoin_index, constituents, listings))
     }
 
     /// 获取CMC 100指数数据
@@ -347,30 +731,72 @@ impl CoinMarketCapClient {
     /// # 返回
     /// * `Result<Vec<CmcIndexData>>` - CMC 100指数数据或错误
     async fn get_cmc_100_index(&self) -> Result<Vec<CmcIndexData>> {
+        let data = self.get_listings(&[
+            ("start", "1"),
+            ("limit", "100"),
+            ("convert", "USD"),
+            ("sort", "market_cap"),
+            ("sort_dir", "desc"),
+            ("cryptocurrency_type", "all"),
+            ("tag", "all"),
+        ]).await?;
+        info!("✅ CMC 100指数数据获取成功，共 {} 个币种", data.len());
+        Ok(data)
+    }
+
+    /// 获取最近上线的币种
+    /// 
+    /// 按CMC收录时间倒序获取，附带当前美元价格和市值
+    /// 
+    /// # 参数
+    /// * `limit` - 获取数量
+    /// 
+    /// # 返回
+    /// * `Result<Vec<ListedCoin>>` - 最近上线的币种（ID为CMC数字ID）或错误
+    pub async fn get_recently_added(&self, limit: usize) -> Result<Vec<ListedCoin>> {
+        let limit = limit.clamp(1, 5000).to_string();
+        let data = self.get_listings(&[
+            ("start", "1"),
+            ("limit", limit.as_str()),
+            ("convert", "USD"),
+            ("sort", "date_added"),
+            ("sort_dir", "desc"),
+        ]).await?;
+        Ok(data.into_iter()
+            .map(|coin| {
+                let quote = coin.quote.get("USD");
+                ListedCoin {
+                    id: coin.id.to_string(),
+                    symbol: coin.symbol,
+                    name: coin.name,
+                    price: quote.map(|q| q.price),
+                    market_cap: quote.map(|q| q.market_cap).filter(|m| *m > 0.0),
+                }
+            })
+            .collect())
+    }
+
+    /// 请求CMC上市列表接口
+    /// 
+    /// # 参数
+    /// * `query` - 查询参数（排序、数量等）
+    async fn get_listings(&self, query: &[(&str, &str)]) -> Result<Vec<CmcIndexData>> {
         let api_key = self.api_key.as_ref()
-            .ok_or_else(|| anyhow::anyhow!("需要API密钥来访问CMC 100指数"))?;
+            .ok_or_else(|| anyhow::anyhow!("需要API密钥来访问CMC上市列表"))?;
         
         let url = format!("{}/v1/cryptocurrency/listings/latest", self.base_url);
         
-        debug!("🌐 请求CMC 100指数URL: {}", url);
+        debug!("🌐 请求CMC上市列表URL: {}", url);
         
         let response = self.client
             .get(&url)
             .header("X-CMC_PRO_API_KEY", api_key)
             .header("Accept", "application/json")
             .header("Accept-Encoding", "identity")
-            .query(&[
-                ("start", "1"),
-                ("limit", "100"),
-                ("convert", "USD"),
-                ("sort", "market_cap"),
-                ("sort_dir", "desc"),
-                ("cryptocurrency_type", "all"),
-                ("tag", "all"),
-            ])
+            .query(query)
             .send()
             .await
-            .context("发送CMC 100指数请求失败")?;
+            .context("发送CMC上市列表请求失败")?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -389,7 +815,8 @@ impl CoinMarketCapClient {
         debug!("📄 CMC API原始响应前500字符: {}", &response_text[..response_text.len().min(500)]);
 
         let cmc_response: Cmc100Response = serde_json::from_str(&response_text)
-            .with_context(|| format!("解析CMC 100指数响应失败，响应前500字符: {}", &response_text[..response_text.len().min(500)]))?;
+            .with_context(|| format!("解析CMC上市列表响应失败，响应前500字符: {}", &response_text[..response_text.len().min(500)]))?;
+        record_source_usage(Self::SOURCE.name, cmc_response.status.credit_count);
 
         if cmc_response.status.error_code != 0 {
             return Err(anyhow::anyhow!(
@@ -399,7 +826,6 @@ impl CoinMarketCapClient {
             ));
         }
 
-        info!("✅ CMC 100指数数据获取成功，共 {} 个币种", cmc_response.data.len());
         Ok(cmc_response.data)
     }
 
@@ -409,63 +835,74 @@ impl CoinMarketCapClient {
     /// * `cmc_data` - CMC 100指数数据
     /// 
     /// # 返回
-    /// * `Result<AltcoinSeasonIndex>` - 山寨币季节指数
-    async fn calculate_altcoin_season_from_cmc(&self, cmc_data: &[CmcIndexData]) -> Result<AltcoinSeasonIndex> {
+    /// * `Result<(AltcoinSeasonIndex, AltcoinSeasonConstituents)>` - 山寨币季节指数和计算明细
+    fn calculate_altcoin_season_from_cmc(cmc_data: &[CmcIndexData]) -> Result<(AltcoinSeasonIndex, AltcoinSeasonConstituents)> {
         info!("🧮 开始计算山寨币季节指数");
         
         // 找到比特币数据
         let bitcoin = cmc_data.iter()
             .find(|coin| coin.symbol == "BTC")
             .ok_or_else(|| anyhow::anyhow!("未找到比特币数据"))?;
-        
-        let btc_change_24h = bitcoin.quote.get("USD")
-            .map(|q| q.percent_change_24h)
-            .unwrap_or(0.0);
+        let btc_quote = bitcoin.quote.get("USD");
+        let btc_change_24h = btc_quote.map(|q| q.percent_change_24h).unwrap_or(0.0);
         
         info!("📊 比特币24小时变化: {:.2}%", btc_change_24h);
         
-        // 计算表现优于比特币的币种数量（排除比特币本身）
-        let mut outperforming_count = 0;
-        let mut total_count = 0;
-        
+        // 逐个比较表现是否优于比特币（排除比特币本身和缺少美元报价的币种）
+        let mut breakdown = AltcoinSeasonConstituents {
+            btc_change_24h,
+            btc_change_90d: btc_quote.and_then(|q| q.percent_change_90d),
+            ..AltcoinSeasonConstituents::default()
+        };
         for coin in cmc_data.iter() {
             if coin.symbol == "BTC" {
-                continue; // 跳过比特币本身
-            }
-            
-            if let Some(usd_quote) = coin.quote.get("USD") {
-                total_count += 1;
-                if usd_quote.percent_change_24h > btc_change_24h {
-                    outperforming_count += 1;
-                }
+                breakdown.excluded.push(ExcludedToken { symbol: coin.symbol.clone(), reason: "比较基准".to_string() });
+                continue;
             }
+            let Some(usd_quote) = coin.quote.get("USD") else {
+                breakdown.excluded.push(ExcludedToken { symbol: coin.symbol.clone(), reason: "缺少美元报价".to_string() });
+                continue;
+            };
+            breakdown.constituents.push(AltcoinSeasonConstituent {
+                symbol: coin.symbol.clone(),
+                name: coin.name.clone(),
+                cmc_rank: coin.cmc_rank,
+                change_24h: usd_quote.percent_change_24h,
+                change_90d: usd_quote.percent_change_90d,
+                outperforming: usd_quote.percent_change_24h > btc_change_24h,
+            });
         }
         
-        // 计算百分比
+        let altcoin_index = Self::altcoin_season_index(&breakdown, AltcoinSeasonFormula::Change24h);
+        info!("📈 山寨币表现统计: {}/{} 币种表现优于比特币 ({:.1}%)", 
+              altcoin_index.outperforming_count, altcoin_index.total_count, altcoin_index.outperforming_percentage);
+
+        Ok((altcoin_index, breakdown))
+    }
+
+    /// 按指定公式由计算明细生成山寨币季节指数
+    ///
+    /// # 参数
+    /// * `breakdown` - 计算明细
+    /// * `formula` - 计算公式
+    pub fn altcoin_season_index(breakdown: &AltcoinSeasonConstituents, formula: AltcoinSeasonFormula) -> AltcoinSeasonIndex {
+        let (outperforming_count, total_count) = breakdown.outperformance(formula);
         let outperforming_percentage = if total_count > 0 {
             (outperforming_count as f32 / total_count as f32) * 100.0
         } else {
             0.0
         };
-        
-        // 计算指数值（0-100）
-        let index_value = outperforming_percentage.round() as u8;
-        
-        info!("📈 山寨币表现统计: {}/{} 币种表现优于比特币 ({:.1}%)", 
-              outperforming_count, total_count, outperforming_percentage);
-        
-        let altcoin_index = AltcoinSeasonIndex {
+        let index_value = breakdown.index_value_with(formula);
+        AltcoinSeasonIndex {
             value: index_value,
             classification: Self::get_altcoin_season_classification(index_value).to_string(),
             classification_zh: Self::get_altcoin_season_classification_zh(index_value).to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
+            timestamp: Utc::now(),
             outperforming_count: outperforming_count as u8,
             total_count: total_count as u8,
             outperforming_percentage,
             market_advice: Self::get_altcoin_season_advice(index_value).to_string(),
-        };
-
-        Ok(altcoin_index)
+        }
     }
 
     /// 获取单个加密货币数据
@@ -501,6 +938,10 @@ impl CoinMarketCapClient {
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_else(|_| "无法读取错误响应".to_string());
+            // 符号无效时返回400 `Invalid value for "symbol"`，说明币种已不再收录
+            if status == reqwest::StatusCode::BAD_REQUEST && error_text.contains("Invalid value for") && error_text.contains("symbol") {
+                return Err(CoinNotListed(symbol.to_string()).into());
+            }
             return Err(anyhow::anyhow!(
                 "币种数据API请求失败: HTTP {} - {}", 
                 status, 
@@ -515,6 +956,7 @@ impl CoinMarketCapClient {
 
         let crypto_response: CryptocurrencyResponse = serde_json::from_str(&response_text)
             .context("解析币种数据响应失败")?;
+        record_source_usage(Self::SOURCE.name, crypto_response.status.credit_count);
 
         if crypto_response.status.error_code != 0 {
             return Err(anyhow::anyhow!(
@@ -527,7 +969,7 @@ impl CoinMarketCapClient {
         // 获取币种数据
         let crypto_info = crypto_response.data
             .get(symbol)
-            .ok_or_else(|| anyhow::anyhow!("未找到 {} 币种数据", symbol))?;
+            .ok_or_else(|| CoinNotListed(symbol.to_string()))?;
 
         let usd_quote = crypto_info.quote
             .get("USD")
@@ -543,13 +985,55 @@ impl CoinMarketCapClient {
             percent_change_24h: usd_quote.percent_change_24h,
             percent_change_7d: usd_quote.percent_change_7d,
             cmc_rank: crypto_info.cmc_rank,
-            last_updated: crypto_info.last_updated.clone(),
+            is_active: crypto_info.is_active != Some(0),
+            circulating_supply: crypto_info.circulating_supply,
+            total_supply: crypto_info.total_supply,
+            // 无限供应的币种即使返回了最大供应量也没有意义
+            max_supply: crypto_info.max_supply.filter(|_| crypto_info.infinite_supply != Some(true)),
+            fully_diluted_market_cap: usd_quote.fully_diluted_market_cap,
+            tags: crypto_info.tags.clone().unwrap_or_default(),
+            last_updated: usd_quote.last_updated,
         };
 
         info!("✅ {} 币种数据获取成功: ${:.4}", symbol, crypto_data.price);
         Ok(crypto_data)
     }
 
+    /// 查询API密钥信息
+    /// 
+    /// 调用 `/v1/key/info` 验证密钥有效性，该端点不消耗积分
+    /// 
+    /// # 返回
+    /// * `Result<serde_json::Value>` - 密钥套餐与用量信息（响应中的 `data` 字段）或错误
+    pub async fn get_key_info(&self) -> Result<serde_json::Value> {
+        let api_key = self.api_key.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("未配置CoinMarketCap API密钥"))?;
+        
+        let url = format!("{}/v1/key/info", self.base_url);
+        
+        debug!("🌐 请求API密钥信息URL: {}", url);
+        
+        let response = self.client
+            .get(&url)
+            .header("X-CMC_PRO_API_KEY", api_key)
+            .header("Accept", "application/json")
+            .header("Accept-Encoding", "identity")
+            .send()
+            .await
+            .context("发送API密钥信息请求失败")?;
+
+        let status = response.status();
+        let body: serde_json::Value = response.json().await
+            .context("解析API密钥信息响应失败")?;
+        
+        if !status.is_success() {
+            let message = body["status"]["error_message"].as_str().unwrap_or("未知错误");
+            return Err(anyhow::anyhow!("CMC API密钥验证失败: HTTP {} - {}", status, message));
+        }
+        
+        Ok(body["data"].clone())
+    }
+
     /// 健康检查
     /// 
     /// # 返回
@@ -588,6 +1072,23 @@ impl CoinMarketCapClient {
         }
     }
 
+    /// 获取指数值对应的贪婪恐惧指数分类（英文，区间与情绪描述一致）
+    /// 
+    /// # 参数
+    /// * `value` - 指数值 (0-100)
+    /// 
+    /// # 返回
+    /// * `&str` - 英文分类
+    pub fn get_fear_greed_classification(value: u8) -> &'static str {
+        match value {
+            0..=24 => "Extreme Fear",
+            25..=44 => "Fear",
+            45..=55 => "Neutral",
+            56..=75 => "Greed",
+            _ => "Extreme Greed",
+        }
+    }
+
     /// 获取山寨币季节指数分类（英文）
     /// 
     /// # 参数
@@ -674,9 +1175,55 @@ impl CoinMarketCapClient {
     }
 } 
 
+/// 自定义反序列化函数，处理字符串或数字类型的error_code
+fn deserialize_string_or_number<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::de::{self, Visitor};
+
+    struct StringOrNumberVisitor;
+
+    impl<'de> Visitor<'de> for StringOrNumberVisitor {
+        type Value = u64;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a string or number")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            value.parse::<u64>().map_err(de::Error::custom)
+        }
+
+        fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(value)
+        }
+
+        fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            if value >= 0 {
+                Ok(value as u64)
+            } else {
+                Err(de::Error::custom("negative number not allowed"))
+            }
+        }
+    }
+
+    deserializer.deserialize_any(StringOrNumberVisitor)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
 
     #[test]
     fn test_altcoin_season_classification() {
@@ -701,6 +1248,44 @@ mod tests {
         assert_eq!(CoinMarketCapClient::get_altcoin_season_advice(85), "山寨币季节，山寨币表现优异，注意风险管理");
     }
 
+    #[test]
+    fn test_fear_greed_snapshot_localization() {
+        let snapshot = FearGreedSnapshot::new(20, "Extreme Fear", Utc::now(), ALTERNATIVE_ME_SOURCE);
+        assert_eq!(snapshot.value_classification_zh, "极度恐惧");
+        assert_eq!(snapshot.investment_advice, "市场极度恐惧，可能是买入机会");
+
+        let greedy = snapshot.with_value(80);
+        assert_eq!(greedy.value_classification, "Extreme Greed");
+        assert_eq!(greedy.sentiment_description, "极度贪婪");
+        assert_eq!((greedy.timestamp, greedy.source.as_str()), (snapshot.timestamp, ALTERNATIVE_ME_SOURCE));
+
+        let json = serde_json::to_value(&greedy).unwrap();
+        assert_eq!(serde_json::from_value::<FearGreedSnapshot>(json).unwrap(), greedy);
+    }
+
+    #[test]
+    fn test_parse_cmc_fear_greed_latest() {
+        let body = r#"{
+            "data": {"value": 56, "update_time": "2024-09-19T02:54:56.017Z", "value_classification": "Neutral"},
+            "status": {"timestamp": "2024-09-19T02:55:00.000Z", "error_code": "0", "error_message": "", "elapsed": "0", "credit_count": 1, "notice": ""}
+        }"#;
+        let response: CmcFearGreedResponse = serde_json::from_str(body).unwrap();
+        assert_eq!((response.data.value, response.status.error_code), (56, 0));
+        assert_eq!(response.data.update_time, Utc.with_ymd_and_hms(2024, 9, 19, 2, 54, 56).unwrap() + chrono::Duration::milliseconds(17));
+        assert_eq!(serde_json::from_str::<FearGreedProvider>(r#""alternative_me""#).unwrap(), FearGreedProvider::AlternativeMe);
+    }
+
+    #[test]
+    fn test_cmc_fear_greed_retry_after_cooldown() {
+        let client = CoinMarketCapClient::new(Some("key".to_string()), Duration::from_secs(5)).unwrap();
+        let now = Utc::now();
+        assert!(!client.cmc_fear_greed_cooling_down(now));
+
+        *client.cmc_fear_greed_unavailable_at.lock().unwrap() = Some(now);
+        assert!(client.cmc_fear_greed_cooling_down(now + chrono::Duration::minutes(30)));
+        assert!(!client.cmc_fear_greed_cooling_down(now + chrono::Duration::seconds(CMC_FEAR_GREED_RETRY_SECONDS)));
+    }
+
     #[tokio::test]
     async fn test_altcoin_season_index_structure() {
         // 测试AltcoinSeasonIndex结构体的创建
@@ -708,7 +1293,7 @@ mod tests {
             value: 48,
             classification: "Balanced Market".to_string(),
             classification_zh: "平衡市场".to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
+            timestamp: Utc::now(),
             outperforming_count: 48,
             total_count: 100,
             outperforming_percentage: 48.0,
@@ -720,48 +1305,3 @@ mod tests {
         assert_eq!(index.classification_zh, "平衡市场");
     }
 }
-
-/// 自定义反序列化函数，处理字符串或数字类型的error_code
-fn deserialize_string_or_number<'de, D>(deserializer: D) -> Result<u64, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    use serde::de::{self, Visitor};
-
-    struct StringOrNumberVisitor;
-
-    impl<'de> Visitor<'de> for StringOrNumberVisitor {
-        type Value = u64;
-
-        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            formatter.write_str("a string or number")
-        }
-
-        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
-        where
-            E: de::Error,
-        {
-            value.parse::<u64>().map_err(de::Error::custom)
-        }
-
-        fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
-        where
-            E: de::Error,
-        {
-            Ok(value)
-        }
-
-        fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
-        where
-            E: de::Error,
-        {
-            if value >= 0 {
-                Ok(value as u64)
-            } else {
-                Err(de::Error::custom("negative number not allowed"))
-            }
-        }
-    }
-
-    deserializer.deserialize_any(StringOrNumberVisitor)
-}
\ No newline at end of file
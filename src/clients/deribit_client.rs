@@ -0,0 +1,133 @@
+use anyhow::{Result, Context, anyhow};
+use chrono::{Duration as ChronoDuration, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tracing::debug;
+
+use super::HttpClientBuilder;
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// Deribit期权数据客户端
+/// 
+/// 使用 Deribit 的公开行情API，不需要API密钥
+#[derive(Clone)]
+pub struct DeribitClient {
+    /// HTTP客户端
+    client: Client,
+    /// 基础URL
+    base_url: String,
+}
+
+/// Deribit响应外层结构
+#[derive(Debug, Deserialize)]
+struct DeribitResponse<T> {
+    /// 结果（出错时为None）
+    result: Option<T>,
+    /// 错误信息
+    error: Option<serde_json::Value>,
+}
+
+/// 波动率指数数据
+#[derive(Debug, Deserialize)]
+struct VolatilityIndexData {
+    /// K线数据: [时间戳(毫秒), 开, 高, 低, 收]
+    data: Vec<[f64; 5]>,
+}
+
+/// 期权合约行情摘要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionSummary {
+    /// 合约名称（如 `BTC-27DEC24-60000-C`）
+    pub instrument_name: String,
+    /// 未平仓量（以标的币计）
+    #[serde(default)]
+    pub open_interest: f64,
+    /// 24小时成交量（以标的币计）
+    #[serde(default)]
+    pub volume: f64,
+    /// 标记价格隐含波动率（百分比）
+    pub mark_iv: Option<f64>,
+    /// 标的价格
+    pub underlying_price: Option<f64>,
+}
+
+impl DeribitClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "deribit",
+        display_name: "Deribit",
+        kind: SourceKind::Api,
+        capabilities: &[SourceCapability::Derivatives],
+    };
+
+    /// 创建新的Deribit客户端
+    /// 
+    /// # 参数
+    /// * `timeout` - 请求超时时间
+    pub fn new(timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new().timeout(timeout).build()?,
+            base_url: "https://www.deribit.com/api/v2".to_string(),
+        })
+    }
+
+    /// 设置基础URL（用于测试网）
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// 获取最新的DVOL波动率指数
+    /// 
+    /// # 参数
+    /// * `currency` - 币种（BTC或ETH）
+    pub async fn get_dvol(&self, currency: &str) -> Result<f64> {
+        let end = Utc::now();
+        let start = end - ChronoDuration::hours(2);
+        let data: VolatilityIndexData = self.public("get_volatility_index_data", &[
+            ("currency", currency.to_string()),
+            ("start_timestamp", start.timestamp_millis().to_string()),
+            ("end_timestamp", end.timestamp_millis().to_string()),
+            ("resolution", "3600".to_string()),
+        ]).await?;
+        
+        data.data.iter()
+            .max_by(|a, b| a[0].total_cmp(&b[0]))
+            .map(|candle| candle[4])
+            .ok_or_else(|| anyhow!("{} DVOL数据为空", currency))
+    }
+
+    /// 获取全部期权合约的行情摘要
+    /// 
+    /// # 参数
+    /// * `currency` - 币种（BTC或ETH）
+    pub async fn get_option_summaries(&self, currency: &str) -> Result<Vec<OptionSummary>> {
+        self.public("get_book_summary_by_currency", &[
+            ("currency", currency.to_string()),
+            ("kind", "option".to_string()),
+        ]).await
+    }
+
+    /// 请求公开API
+    async fn public<T: DeserializeOwned>(&self, method: &str, params: &[(&str, String)]) -> Result<T> {
+        let url = format!("{}/public/{}", self.base_url, method);
+        debug!("🌐 请求Deribit接口: {}", method);
+        
+        let response = self.client.get(&url).query(params).send().await
+            .with_context(|| format!("发送Deribit请求失败: {}", method))?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Deribit API请求失败: {} HTTP {}", method, response.status()));
+        }
+        
+        let body: DeribitResponse<T> = response.json().await
+            .with_context(|| format!("解析Deribit响应失败: {}", method))?;
+        match (body.result, body.error) {
+            (Some(result), _) => Ok(result),
+            (None, Some(error)) => Err(anyhow!("Deribit API返回错误: {}", error)),
+            (None, None) => Err(anyhow!("Deribit响应缺少结果: {}", method)),
+        }
+    }
+}
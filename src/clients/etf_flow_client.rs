@@ -0,0 +1,69 @@
+use anyhow::{Result, Context, anyhow};
+use chrono::NaiveDate;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::debug;
+
+use super::HttpClientBuilder;
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// 单只ETF单日净流量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EtfFlowRecord {
+    /// 交易日
+    pub date: NaiveDate,
+    /// 基金代码（如 IBIT）
+    pub fund: String,
+    /// 标的资产（如 BTC）
+    pub asset: String,
+    /// 净流入金额（美元，负数为流出）
+    pub net_flow_usd: f64,
+}
+
+/// ETF资金流向数据源客户端
+/// 
+/// 轮询配置的URL，要求返回 [`EtfFlowRecord`] 组成的JSON数组
+#[derive(Clone)]
+pub struct EtfFlowClient {
+    /// HTTP客户端
+    client: Client,
+    /// 数据源URL
+    url: String,
+}
+
+impl EtfFlowClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "etf_flow_feed",
+        display_name: "ETF资金流向",
+        kind: SourceKind::Feed,
+        capabilities: &[SourceCapability::EtfFlows],
+    };
+
+    /// 创建新的ETF资金流向客户端
+    /// 
+    /// # 参数
+    /// * `url` - 数据源URL
+    /// * `timeout` - 请求超时时间
+    pub fn new(url: impl Into<String>, timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new().timeout(timeout).build()?,
+            url: url.into(),
+        })
+    }
+
+    /// 获取每日各基金净流量
+    pub async fn fetch(&self) -> Result<Vec<EtfFlowRecord>> {
+        debug!("🌐 请求ETF资金流向URL: {}", self.url);
+        
+        let response = self.client.get(&self.url).send().await
+            .context("发送ETF资金流向请求失败")?;
+        if !response.status().is_success() {
+            return Err(anyhow!("ETF资金流向请求失败: HTTP {}", response.status()));
+        }
+        
+        response.json().await.context("解析ETF资金流向数据失败")
+    }
+}
@@ -0,0 +1,256 @@
+use anyhow::{Result, Context, anyhow};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::debug;
+
+use super::HttpClientBuilder;
+
+/// 交易所余额
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeBalance {
+    /// 资产代码（大写，如 BTC）
+    pub asset: String,
+    /// 总数量（可用 + 冻结）
+    pub amount: f64,
+}
+
+/// 支持的交易所
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExchangeKind {
+    /// Binance
+    Binance,
+    /// Kraken
+    Kraken,
+}
+
+impl std::fmt::Display for ExchangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExchangeKind::Binance => write!(f, "binance"),
+            ExchangeKind::Kraken => write!(f, "kraken"),
+        }
+    }
+}
+
+/// 交易所只读连接器
+/// 
+/// 只调用查询余额的接口，建议为其创建仅有读取权限的API密钥
+#[async_trait]
+pub trait ExchangeConnector: Send + Sync {
+    /// 交易所类型
+    fn exchange(&self) -> ExchangeKind;
+    
+    /// 获取账户余额（已过滤数量为0的资产）
+    async fn fetch_balances(&self) -> Result<Vec<ExchangeBalance>>;
+}
+
+/// 根据交易所类型创建连接器
+/// 
+/// # 参数
+/// * `kind` - 交易所类型
+/// * `api_key` - API密钥
+/// * `api_secret` - API私钥
+pub fn connector_for(kind: ExchangeKind, api_key: String, api_secret: String) -> Result<Box<dyn ExchangeConnector>> {
+    let client = HttpClientBuilder::new()
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    Ok(match kind {
+        ExchangeKind::Binance => Box::new(BinanceConnector::new(client, api_key, api_secret)),
+        ExchangeKind::Kraken => Box::new(KrakenConnector::new(client, api_key, api_secret)),
+    })
+}
+
+/// Binance 现货账户连接器
+pub struct BinanceConnector {
+    client: Client,
+    api_key: String,
+    api_secret: String,
+    base_url: String,
+}
+
+/// Binance 账户信息响应
+#[derive(Debug, Deserialize)]
+struct BinanceAccount {
+    balances: Vec<BinanceBalance>,
+}
+
+/// Binance 单个资产余额
+#[derive(Debug, Deserialize)]
+struct BinanceBalance {
+    asset: String,
+    free: String,
+    locked: String,
+}
+
+impl BinanceConnector {
+    /// 创建 Binance 连接器
+    pub fn new(client: Client, api_key: String, api_secret: String) -> Self {
+        Self {
+            client,
+            api_key,
+            api_secret,
+            base_url: "https://api.binance.com".to_string(),
+        }
+    }
+
+    /// 计算请求签名（HMAC-SHA256 查询字符串，十六进制）
+    fn sign(&self, query: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.api_secret.as_bytes())
+            .expect("HMAC接受任意长度的密钥");
+        mac.update(query.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+#[async_trait]
+impl ExchangeConnector for BinanceConnector {
+    fn exchange(&self) -> ExchangeKind {
+        ExchangeKind::Binance
+    }
+
+    async fn fetch_balances(&self) -> Result<Vec<ExchangeBalance>> {
+        let query = format!("timestamp={}&recvWindow=10000", chrono::Utc::now().timestamp_millis());
+        let url = format!("{}/api/v3/account?{}&signature={}", self.base_url, query, self.sign(&query));
+        debug!("🔗 请求 Binance 账户余额");
+        
+        let response = self.client
+            .get(&url)
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await
+            .context("请求 Binance 账户信息失败")?;
+        
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Binance 返回错误 {}: {}", status, body));
+        }
+        
+        let account: BinanceAccount = response.json().await.context("解析 Binance 账户信息失败")?;
+        Ok(account.balances.into_iter()
+            .filter_map(|b| {
+                let amount = b.free.parse::<f64>().unwrap_or(0.0) + b.locked.parse::<f64>().unwrap_or(0.0);
+                (amount > 0.0).then_some(ExchangeBalance { asset: b.asset, amount })
+            })
+            .collect())
+    }
+}
+
+/// Kraken 账户连接器
+pub struct KrakenConnector {
+    client: Client,
+    api_key: String,
+    api_secret: String,
+    base_url: String,
+}
+
+/// Kraken 响应
+#[derive(Debug, Deserialize)]
+struct KrakenResponse {
+    error: Vec<String>,
+    result: Option<HashMap<String, String>>,
+}
+
+impl KrakenConnector {
+    /// 创建 Kraken 连接器
+    pub fn new(client: Client, api_key: String, api_secret: String) -> Self {
+        Self {
+            client,
+            api_key,
+            api_secret,
+            base_url: "https://api.kraken.com".to_string(),
+        }
+    }
+
+    /// 计算请求签名
+    /// 
+    /// `base64(HMAC-SHA512(path + SHA256(nonce + postdata), base64decode(secret)))`
+    fn sign(&self, path: &str, nonce: &str, post_data: &str) -> Result<String> {
+        let secret = BASE64.decode(&self.api_secret).context("Kraken 私钥不是有效的base64")?;
+        let digest = Sha256::digest(format!("{}{}", nonce, post_data).as_bytes());
+        
+        let mut mac = Hmac::<Sha512>::new_from_slice(&secret).expect("HMAC接受任意长度的密钥");
+        mac.update(path.as_bytes());
+        mac.update(&digest);
+        Ok(BASE64.encode(mac.finalize().into_bytes()))
+    }
+
+    /// 将 Kraken 资产代码转换为通用代码（XXBT → BTC, ZUSD → USD）
+    fn normalize_asset(asset: &str) -> String {
+        let asset = asset.split('.').next().unwrap_or(asset);
+        let asset = if asset.len() == 4 && (asset.starts_with('X') || asset.starts_with('Z')) {
+            &asset[1..]
+        } else {
+            asset
+        };
+        match asset {
+            "XBT" => "BTC".to_string(),
+            "XDG" => "DOGE".to_string(),
+            other => other.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ExchangeConnector for KrakenConnector {
+    fn exchange(&self) -> ExchangeKind {
+        ExchangeKind::Kraken
+    }
+
+    async fn fetch_balances(&self) -> Result<Vec<ExchangeBalance>> {
+        let path = "/0/private/Balance";
+        let nonce = chrono::Utc::now().timestamp_millis().to_string();
+        let post_data = format!("nonce={}", nonce);
+        let signature = self.sign(path, &nonce, &post_data)?;
+        debug!("🔗 请求 Kraken 账户余额");
+        
+        let response: KrakenResponse = self.client
+            .post(format!("{}{}", self.base_url, path))
+            .header("API-Key", &self.api_key)
+            .header("API-Sign", signature)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(post_data)
+            .send()
+            .await
+            .context("请求 Kraken 账户余额失败")?
+            .json()
+            .await
+            .context("解析 Kraken 账户余额失败")?;
+        
+        if !response.error.is_empty() {
+            return Err(anyhow!("Kraken 返回错误: {}", response.error.join(", ")));
+        }
+        
+        // 同一资产可能同时出现现货和质押余额（如 ETH 与 ETH.F），合并统计
+        let mut totals: HashMap<String, f64> = HashMap::new();
+        for (asset, amount) in response.result.unwrap_or_default() {
+            let amount = amount.parse::<f64>().unwrap_or(0.0);
+            *totals.entry(Self::normalize_asset(&asset)).or_default() += amount;
+        }
+        Ok(totals.into_iter()
+            .filter(|(_, amount)| *amount > 0.0)
+            .map(|(asset, amount)| ExchangeBalance { asset, amount })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kraken_asset_normalization() {
+        assert_eq!(KrakenConnector::normalize_asset("XXBT"), "BTC");
+        assert_eq!(KrakenConnector::normalize_asset("ZUSD"), "USD");
+        assert_eq!(KrakenConnector::normalize_asset("XETH"), "ETH");
+        assert_eq!(KrakenConnector::normalize_asset("ETH.F"), "ETH");
+        assert_eq!(KrakenConnector::normalize_asset("DOT"), "DOT");
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::{Result, Context, anyhow};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::debug;
+
+use super::HttpClientBuilder;
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// Binance最新成交价响应
+#[derive(Debug, Deserialize)]
+struct TickerPrice {
+    /// 价格（字符串形式的小数）
+    price: String,
+}
+
+/// Binance订单簿响应
+#[derive(Debug, Deserialize)]
+struct DepthResponse {
+    /// 买单（价格、数量，字符串形式的小数）
+    bids: Vec<(String, String)>,
+    /// 卖单
+    asks: Vec<(String, String)>,
+}
+
+/// 订单簿快照
+#[derive(Debug, Clone, Default)]
+pub struct OrderBook {
+    /// 买单（价格、数量），价格从高到低
+    pub bids: Vec<(f64, f64)>,
+    /// 卖单（价格、数量），价格从低到高
+    pub asks: Vec<(f64, f64)>,
+}
+
+/// 解析订单簿价位
+fn parse_levels(levels: Vec<(String, String)>) -> Result<Vec<(f64, f64)>> {
+    levels.into_iter()
+        .map(|(price, quantity)| {
+            Ok((
+                price.parse().with_context(|| format!("Binance返回的价格无效: {}", price))?,
+                quantity.parse().with_context(|| format!("Binance返回的数量无效: {}", quantity))?,
+            ))
+        })
+        .collect()
+}
+
+/// 交易所行情客户端
+/// 
+/// 使用 Binance 公开的现货最新成交价和订单簿接口，不需要API密钥
+#[derive(Clone)]
+pub struct ExchangeTickerClient {
+    /// HTTP客户端
+    client: Client,
+    /// 基础URL
+    base_url: String,
+}
+
+impl ExchangeTickerClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "binance",
+        display_name: "Binance",
+        kind: SourceKind::Api,
+        capabilities: &[SourceCapability::MarketData],
+    };
+
+    /// 创建新的交易所行情客户端
+    /// 
+    /// # 参数
+    /// * `timeout` - 请求超时时间
+    pub fn new(timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new().timeout(timeout).build()?,
+            base_url: "https://api.binance.com/api/v3".to_string(),
+        })
+    }
+
+    /// 获取交易对最新成交价
+    /// 
+    /// # 参数
+    /// * `symbol` - 交易对（如 `BTCUSDT`）
+    /// 
+    /// # 返回
+    /// * `Result<f64>` - 最新成交价
+    pub async fn get_price(&self, symbol: &str) -> Result<f64> {
+        let url = format!("{}/ticker/price", self.base_url);
+        debug!("🌐 请求Binance最新成交价: {}", symbol);
+
+        let response = self.client.get(&url)
+            .query(&[("symbol", symbol)])
+            .send().await
+            .with_context(|| format!("发送Binance请求失败: {}", symbol))?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Binance API请求失败: {} HTTP {}", symbol, response.status()));
+        }
+
+        let ticker: TickerPrice = response.json().await
+            .with_context(|| format!("解析Binance响应失败: {}", symbol))?;
+        ticker.price.parse()
+            .with_context(|| format!("Binance返回的价格无效: {}", ticker.price))
+    }
+
+    /// 获取交易对订单簿
+    /// 
+    /// # 参数
+    /// * `symbol` - 交易对（如 `BTCUSDT`）
+    /// * `limit` - 每侧价位数（Binance支持 5/10/20/50/100/500/1000/5000）
+    /// 
+    /// # 返回
+    /// * `Result<OrderBook>` - 订单簿快照
+    pub async fn get_order_book(&self, symbol: &str, limit: usize) -> Result<OrderBook> {
+        let url = format!("{}/depth", self.base_url);
+        debug!("🌐 请求Binance订单簿: {}", symbol);
+
+        let limit = limit.to_string();
+        let response = self.client.get(&url)
+            .query(&[("symbol", symbol), ("limit", limit.as_str())])
+            .send().await
+            .with_context(|| format!("发送Binance请求失败: {}", symbol))?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Binance API请求失败: {} HTTP {}", symbol, response.status()));
+        }
+
+        let depth: DepthResponse = response.json().await
+            .with_context(|| format!("解析Binance订单簿失败: {}", symbol))?;
+        Ok(OrderBook {
+            bids: parse_levels(depth.bids)?,
+            asks: parse_levels(depth.asks)?,
+        })
+    }
+}
@@ -1,10 +1,21 @@
 use anyhow::{Result, Context, anyhow};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tracing::{info, debug, error};
 use std::time::Duration;
 
 use super::{ApiClient, HttpClientBuilder};
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// Glassnode时间序列数据点
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GlassnodePoint {
+    /// 时间
+    pub timestamp: DateTime<Utc>,
+    /// 数值
+    pub value: f64,
+}
 
 /// Glassnode API客户端
 /// 
@@ -22,6 +33,14 @@ pub struct GlassnodeClient {
 }
 
 impl GlassnodeClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "glassnode",
+        display_name: "Glassnode",
+        kind: SourceKind::Api,
+        capabilities: &[SourceCapability::OnChain],
+    };
+
     /// 创建新的Glassnode客户端
     /// 
     /// # 参数
@@ -31,6 +50,7 @@ impl GlassnodeClient {
     /// # 返回
     /// * `Result<Self>` - 创建的客户端或错误
     pub fn new(api_key: impl Into<String>, timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
         let client = HttpClientBuilder::new()
             .timeout(timeout)
             .user_agent("EverScan-GlassnodeClient/1.0")
@@ -93,12 +113,37 @@ impl GlassnodeClient {
         
         Ok(result)
     }
+    
+    /// 获取日线时间序列
+    /// 
+    /// # 参数
+    /// * `metric` - 指标名称（如 `distribution/balance_exchanges`）
+    /// * `asset` - 资产符号
+    /// * `since` - 开始时间戳（秒）
+    /// 
+    /// # 返回
+    /// * `Result<Vec<GlassnodePoint>>` - 按时间升序排列的数据点，空值会被跳过
+    pub async fn get_daily_series(&self, metric: &str, asset: &str, since: i64) -> Result<Vec<GlassnodePoint>> {
+        let raw = self.get_metric(metric, asset, Some(since), None).await?;
+        let items = raw.as_array()
+            .ok_or_else(|| anyhow!("Glassnode响应格式错误: 期望数组"))?;
+        
+        let mut points: Vec<GlassnodePoint> = items.iter()
+            .filter_map(|item| {
+                let timestamp = DateTime::from_timestamp(item.get("t")?.as_i64()?, 0)?;
+                let value = item.get("v")?.as_f64()?;
+                Some(GlassnodePoint { timestamp, value })
+            })
+            .collect();
+        points.sort_by_key(|p| p.timestamp);
+        Ok(points)
+    }
 }
 
 #[async_trait::async_trait]
 impl ApiClient for GlassnodeClient {
     fn source_name(&self) -> &str {
-        "glassnode"
+        Self::SOURCE.name
     }
     
     async fn check_api_key(&self) -> Result<bool> {
@@ -0,0 +1,131 @@
+use anyhow::{Result, Context, anyhow};
+use chrono::{DateTime, NaiveDate};
+use reqwest::Client;
+use reqwest::header::{COOKIE, SET_COOKIE};
+use serde_json::Value;
+use std::time::Duration;
+use tracing::debug;
+
+use super::HttpClientBuilder;
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// Google Trends 非官方接口客户端
+/// 
+/// 模拟网页端的两步请求：先通过 explore 接口获取时间序列组件的令牌，
+/// 再请求 widgetdata 接口获取数据。接口未公开且有频率限制，请保持较低的采集频率
+#[derive(Clone)]
+pub struct GoogleTrendsClient {
+    /// HTTP客户端
+    client: Client,
+    /// 基础URL
+    base_url: String,
+    /// 地区代码（空字符串表示全球）
+    geo: String,
+}
+
+/// 去掉接口响应前的防JSON劫持前缀（如 `)]}'`）
+fn strip_prefix(body: &str) -> &str {
+    body.find(['{', '[']).map_or(body, |start| &body[start..])
+}
+
+impl GoogleTrendsClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "google_trends",
+        display_name: "Google Trends",
+        kind: SourceKind::Feed,
+        capabilities: &[SourceCapability::SearchTrends],
+    };
+
+    /// 创建新的Google Trends客户端
+    /// 
+    /// # 参数
+    /// * `geo` - 地区代码（如 US，空字符串表示全球）
+    /// * `timeout` - 请求超时时间
+    pub fn new(geo: impl Into<String>, timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new()
+                .timeout(timeout)
+                .user_agent("Mozilla/5.0 (compatible; EverScan/1.0)")
+                .build()?,
+            base_url: "https://trends.google.com".to_string(),
+            geo: geo.into(),
+        })
+    }
+
+    /// 获取搜索词近5年的每周热度
+    /// 
+    /// # 参数
+    /// * `term` - 搜索词
+    /// 
+    /// # 返回
+    /// * `Result<Vec<(NaiveDate, f64)>>` - 每周起始日期和热度（0-100，以该序列峰值为100）
+    pub async fn weekly_interest(&self, term: &str) -> Result<Vec<(NaiveDate, f64)>> {
+        let cookies = self.session_cookies().await?;
+        
+        let explore_req = serde_json::json!({
+            "comparisonItem": [{ "keyword": term, "geo": self.geo, "time": "today 5-y" }],
+            "category": 0,
+            "property": "",
+        });
+        let explore = self.get_json("/trends/api/explore", &explore_req.to_string(), None, &cookies).await
+            .with_context(|| format!("获取 {} 的Trends组件失败", term))?;
+        let widget = explore["widgets"].as_array()
+            .and_then(|widgets| widgets.iter().find(|w| w["id"] == "TIMESERIES"))
+            .ok_or_else(|| anyhow!("Trends响应中没有时间序列组件"))?;
+        let token = widget["token"].as_str().ok_or_else(|| anyhow!("Trends组件缺少令牌"))?;
+        
+        let data = self.get_json(
+            "/trends/api/widgetdata/multiline",
+            &widget["request"].to_string(),
+            Some(token),
+            &cookies,
+        ).await.with_context(|| format!("获取 {} 的Trends数据失败", term))?;
+        
+        let timeline = data["default"]["timelineData"].as_array()
+            .ok_or_else(|| anyhow!("Trends响应缺少timelineData"))?;
+        Ok(timeline.iter()
+            .filter_map(|point| {
+                let seconds: i64 = point["time"].as_str()?.parse().ok()?;
+                let value = point["value"].as_array()?.first()?.as_f64()?;
+                Some((DateTime::from_timestamp(seconds, 0)?.date_naive(), value))
+            })
+            .collect())
+    }
+
+    /// 访问首页获取会话Cookie（接口在没有Cookie时经常返回429）
+    async fn session_cookies(&self) -> Result<String> {
+        let response = self.client.get(format!("{}/?geo={}", self.base_url, self.geo)).send().await
+            .context("访问Google Trends首页失败")?;
+        Ok(response.headers()
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok()?.split(';').next())
+            .collect::<Vec<_>>()
+            .join("; "))
+    }
+
+    /// 请求接口并解析去掉前缀后的JSON
+    async fn get_json(&self, path: &str, req: &str, token: Option<&str>, cookies: &str) -> Result<Value> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("🌐 请求Google Trends接口: {}", path);
+        
+        let mut query = vec![("hl", "en-US"), ("tz", "0"), ("req", req)];
+        if let Some(token) = token {
+            query.push(("token", token));
+        }
+        let response = self.client.get(&url)
+            .query(&query)
+            .header(COOKIE, cookies)
+            .send()
+            .await
+            .context("发送Google Trends请求失败")?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Google Trends请求失败: HTTP {}", response.status()));
+        }
+        
+        let body = response.text().await.context("读取Google Trends响应失败")?;
+        serde_json::from_str(strip_prefix(&body)).context("解析Google Trends响应失败")
+    }
+}
@@ -0,0 +1,92 @@
+use anyhow::{Result, Context, anyhow};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::debug;
+
+use super::HttpClientBuilder;
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// 币种社交指标
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SocialMetrics {
+    /// 24小时社交帖子数
+    pub social_volume_24h: Option<f64>,
+    /// 24小时互动数（点赞、评论、转发等）
+    pub interactions_24h: Option<f64>,
+    /// 正面情绪占比（0-100）
+    pub sentiment: Option<f64>,
+    /// Galaxy Score（综合社交与行情表现，0-100）
+    pub galaxy_score: Option<f64>,
+    /// 社交占有率（百分比）
+    pub social_dominance: Option<f64>,
+}
+
+/// LunarCrush响应外层结构
+#[derive(Debug, Deserialize)]
+struct LunarCrushResponse {
+    /// 数据
+    data: SocialMetrics,
+}
+
+/// LunarCrush社交数据客户端
+#[derive(Clone)]
+pub struct LunarCrushClient {
+    /// HTTP客户端
+    client: Client,
+    /// API密钥
+    api_key: String,
+    /// 基础URL
+    base_url: String,
+}
+
+impl LunarCrushClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "lunarcrush",
+        display_name: "LunarCrush",
+        kind: SourceKind::Api,
+        capabilities: &[SourceCapability::Social],
+    };
+
+    /// 创建新的LunarCrush客户端
+    /// 
+    /// # 参数
+    /// * `api_key` - LunarCrush API密钥
+    /// * `timeout` - 请求超时时间
+    pub fn new(api_key: impl Into<String>, timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new()
+                .timeout(timeout)
+                .user_agent("EverScan-LunarCrushClient/1.0")
+                .build()?,
+            api_key: api_key.into(),
+            base_url: "https://lunarcrush.com/api4/public".to_string(),
+        })
+    }
+
+    /// 获取币种的社交指标
+    /// 
+    /// # 参数
+    /// * `symbol` - 币种符号（如 BTC）
+    pub async fn get_coin_metrics(&self, symbol: &str) -> Result<SocialMetrics> {
+        let url = format!("{}/coins/{}/v1", self.base_url, symbol.to_lowercase());
+        debug!("🌐 请求LunarCrush社交指标: {}", symbol);
+        
+        let response = self.client.get(&url)
+            .bearer_auth(&self.api_key)
+            .send()
+            .await
+            .context("发送LunarCrush请求失败")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("LunarCrush API请求失败: {} - {}", status, text));
+        }
+        
+        let body: LunarCrushResponse = response.json().await
+            .context("解析LunarCrush响应失败")?;
+        Ok(body.data)
+    }
+}
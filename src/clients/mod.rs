@@ -1,14 +1,40 @@
 // pub mod bitget_client; // 已移除Bitget客户端
 // pub mod dune_client;
-// pub mod glassnode_client;
 // pub mod debank_client;
 pub mod coinmarketcap_client; // CoinMarketCap客户端
+pub mod coingecko_client; // CoinGecko参考价格客户端
+pub mod exchange; // 交易所只读连接器
+pub mod exchange_ticker_client; // 交易所公开行情客户端
+pub mod chain_client; // 比特币链上数据客户端
+pub mod glassnode_client; // Glassnode链上数据客户端
+pub mod deribit_client; // Deribit期权数据客户端
+pub mod coinglass_client; // Coinglass爆仓数据客户端
+pub mod etf_flow_client; // ETF资金流向数据源
+pub mod calendar_client; // 经济日历数据源
+pub mod lunarcrush_client; // LunarCrush社交数据客户端
+pub mod google_trends_client; // Google Trends非官方客户端
+pub mod opensea_client; // OpenSea NFT数据客户端
+pub mod staking_client; // 质押收益率客户端
+pub mod node_rpc_client; // 自建节点JSON-RPC客户端
 
 // pub use bitget_client::*; // 已移除
 // pub use dune_client::*;
-// pub use glassnode_client::*;
 // pub use debank_client::*;
 pub use coinmarketcap_client::*; // 导出CoinMarketCap客户端
+pub use coingecko_client::*;
+pub use exchange::*;
+pub use exchange_ticker_client::*;
+pub use chain_client::*;
+pub use glassnode_client::*;
+pub use deribit_client::*;
+pub use coinglass_client::*;
+pub use etf_flow_client::*;
+pub use calendar_client::*;
+pub use lunarcrush_client::*;
+pub use google_trends_client::*;
+pub use opensea_client::*;
+pub use staking_client::*;
+pub use node_rpc_client::*;
 
 
 use anyhow::Result;
@@ -33,6 +59,21 @@ pub trait ApiClient {
     fn set_timeout(&mut self, timeout: Duration);
 }
 
+/// 数据源收录的币种（用于检测新上线币种）
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ListedCoin {
+    /// 数据源中的币种ID
+    pub id: String,
+    /// 符号
+    pub symbol: String,
+    /// 名称
+    pub name: String,
+    /// 美元价格（数据源未提供时为None）
+    pub price: Option<f64>,
+    /// 市值（美元）
+    pub market_cap: Option<f64>,
+}
+
 /// HTTP客户端构建器
 /// 
 /// 用于创建配置好的HTTP客户端
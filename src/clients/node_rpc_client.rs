@@ -0,0 +1,149 @@
+use anyhow::{Result, Context, anyhow};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use super::HttpClientBuilder;
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// 节点所属的链
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeChain {
+    /// 以太坊（及兼容EVM的链），高度为区块号
+    Ethereum,
+    /// Solana，高度为slot
+    Solana,
+}
+
+impl NodeChain {
+    /// 默认允许落后的高度（以太坊3个区块约36秒，Solana 150个slot约1分钟）
+    pub fn default_max_lag(&self) -> u64 {
+        match self {
+            NodeChain::Ethereum => 3,
+            NodeChain::Solana => 150,
+        }
+    }
+}
+
+/// 单次节点探测结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeProbe {
+    /// 最新区块高度（Solana为slot）
+    pub height: u64,
+    /// 连接的对等节点数（节点未开放该接口时为None）
+    pub peers: Option<u64>,
+    /// 节点是否报告正在同步（节点未开放该接口时为None）
+    pub syncing: Option<bool>,
+    /// 高度请求的响应延迟（毫秒）
+    pub latency_ms: f64,
+}
+
+/// JSON-RPC 响应
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    result: Option<Value>,
+    error: Option<Value>,
+}
+
+/// 解析 `0x` 开头的十六进制数量
+fn parse_quantity(value: &Value) -> Option<u64> {
+    u64::from_str_radix(value.as_str()?.strip_prefix("0x")?, 16).ok()
+}
+
+/// 自建节点 JSON-RPC 客户端
+///
+/// 探测节点的区块高度、对等节点数和响应延迟，支持以太坊和 Solana 的标准 JSON-RPC 接口
+#[derive(Clone)]
+pub struct NodeRpcClient {
+    /// HTTP客户端
+    client: Client,
+}
+
+impl NodeRpcClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "node_rpc",
+        display_name: "Node JSON-RPC",
+        kind: SourceKind::Feed,
+        capabilities: &[SourceCapability::OnChain],
+    };
+
+    /// 创建新的节点 JSON-RPC 客户端
+    ///
+    /// # 参数
+    /// * `timeout` - 请求超时时间
+    pub fn new(timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new()
+                .timeout(timeout)
+                .user_agent("EverScan-NodeMonitor/1.0")
+                .build()?,
+        })
+    }
+
+    /// 探测节点状态
+    ///
+    /// 高度请求失败时返回错误；对等节点数和同步状态为可选接口，失败时为None
+    ///
+    /// # 参数
+    /// * `chain` - 节点所属的链
+    /// * `url` - 节点 JSON-RPC 地址
+    pub async fn probe(&self, chain: NodeChain, url: &str) -> Result<NodeProbe> {
+        let (height_method, peers_method, syncing_method) = match chain {
+            NodeChain::Ethereum => ("eth_blockNumber", "net_peerCount", "eth_syncing"),
+            NodeChain::Solana => ("getSlot", "getClusterNodes", "getHealth"),
+        };
+
+        let started = Instant::now();
+        let height = self.call(url, height_method).await?;
+        let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+        let height = match chain {
+            NodeChain::Ethereum => parse_quantity(&height),
+            NodeChain::Solana => height.as_u64(),
+        }
+        .ok_or_else(|| anyhow!("无法解析 {} 返回的高度: {}", height_method, height))?;
+
+        let peers = match (chain, self.call(url, peers_method).await) {
+            (NodeChain::Ethereum, Ok(peers)) => parse_quantity(&peers),
+            (NodeChain::Solana, Ok(nodes)) => nodes.as_array().map(|nodes| nodes.len() as u64),
+            (_, Err(e)) => {
+                debug!("节点 {} 不支持 {}: {}", url, peers_method, e);
+                None
+            }
+        };
+        let syncing = match (chain, self.call(url, syncing_method).await) {
+            // eth_syncing 未同步时返回false，同步中返回进度对象
+            (NodeChain::Ethereum, Ok(status)) => Some(status != Value::Bool(false)),
+            (NodeChain::Solana, Ok(status)) => Some(status.as_str() != Some("ok")),
+            // getHealth 在节点落后时返回错误
+            (NodeChain::Solana, Err(_)) => Some(true),
+            (NodeChain::Ethereum, Err(_)) => None,
+        };
+
+        Ok(NodeProbe { height, peers, syncing, latency_ms })
+    }
+
+    /// 调用无参数的 JSON-RPC 方法
+    async fn call(&self, url: &str, method: &str) -> Result<Value> {
+        let response = self.client.post(url)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": [] }))
+            .send()
+            .await
+            .with_context(|| format!("发送 {} 请求失败", method))?;
+        if !response.status().is_success() {
+            return Err(anyhow!("{} 请求失败: HTTP {}", method, response.status()));
+        }
+
+        let body: RpcResponse = response.json().await
+            .with_context(|| format!("解析 {} 响应失败", method))?;
+        match (body.result, body.error) {
+            (Some(result), _) => Ok(result),
+            (None, Some(error)) => Err(anyhow!("{} 返回错误: {}", method, error)),
+            (None, None) => Err(anyhow!("{} 未返回结果", method)),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use anyhow::{Result, Context, anyhow};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::debug;
+
+use super::HttpClientBuilder;
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// NFT合集统计（金额以合集的计价币种表示，如ETH）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NftCollectionStats {
+    /// 地板价
+    pub floor_price: Option<f64>,
+    /// 地板价的计价币种（如 ETH）
+    pub floor_price_symbol: Option<String>,
+    /// 24小时成交额
+    pub volume_24h: Option<f64>,
+    /// 24小时成交额相对前一日的变化百分比
+    pub volume_change_24h: Option<f64>,
+    /// 24小时成交笔数
+    pub sales_24h: Option<f64>,
+    /// 7天成交额
+    pub volume_7d: Option<f64>,
+    /// 历史总成交额
+    pub total_volume: Option<f64>,
+    /// 持有人数
+    pub num_owners: Option<u64>,
+    /// 市值（地板价 × 供应量）
+    pub market_cap: Option<f64>,
+}
+
+/// OpenSea合集统计响应
+#[derive(Debug, Deserialize)]
+struct StatsResponse {
+    /// 全部历史统计
+    total: TotalStats,
+    /// 按时间区间的统计
+    #[serde(default)]
+    intervals: Vec<IntervalStats>,
+}
+
+/// 全部历史统计
+#[derive(Debug, Deserialize)]
+struct TotalStats {
+    /// 总成交额
+    volume: Option<f64>,
+    /// 持有人数
+    num_owners: Option<u64>,
+    /// 市值
+    market_cap: Option<f64>,
+    /// 地板价
+    floor_price: Option<f64>,
+    /// 地板价计价币种
+    floor_price_symbol: Option<String>,
+}
+
+/// 时间区间统计
+#[derive(Debug, Deserialize)]
+struct IntervalStats {
+    /// 区间（one_day、seven_day、thirty_day）
+    interval: String,
+    /// 成交额
+    volume: Option<f64>,
+    /// 成交额变化比例（0.25表示增长25%）
+    volume_change: Option<f64>,
+    /// 成交笔数
+    sales: Option<f64>,
+}
+
+/// OpenSea NFT数据客户端
+#[derive(Clone)]
+pub struct OpenSeaClient {
+    /// HTTP客户端
+    client: Client,
+    /// API密钥
+    api_key: String,
+    /// 基础URL
+    base_url: String,
+}
+
+impl OpenSeaClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "opensea",
+        display_name: "OpenSea",
+        kind: SourceKind::Api,
+        capabilities: &[SourceCapability::Nft],
+    };
+
+    /// 创建新的OpenSea客户端
+    ///
+    /// # 参数
+    /// * `api_key` - OpenSea API密钥
+    /// * `timeout` - 请求超时时间
+    pub fn new(api_key: impl Into<String>, timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new()
+                .timeout(timeout)
+                .user_agent("EverScan-OpenSeaClient/1.0")
+                .build()?,
+            api_key: api_key.into(),
+            base_url: "https://api.opensea.io/api/v2".to_string(),
+        })
+    }
+
+    /// 获取合集的地板价和成交统计
+    ///
+    /// # 参数
+    /// * `slug` - 合集标识（如 `boredapeyachtclub`）
+    pub async fn get_collection_stats(&self, slug: &str) -> Result<NftCollectionStats> {
+        let url = format!("{}/collections/{}/stats", self.base_url, slug);
+        debug!("🌐 请求OpenSea合集统计: {}", slug);
+
+        let response = self.client.get(&url)
+            .header("x-api-key", &self.api_key)
+            .send()
+            .await
+            .context("发送OpenSea请求失败")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("OpenSea API请求失败: {} - {}", status, text));
+        }
+
+        let body: StatsResponse = response.json().await
+            .context("解析OpenSea响应失败")?;
+        let interval = |name: &str| body.intervals.iter().find(|i| i.interval == name);
+        let one_day = interval("one_day");
+        Ok(NftCollectionStats {
+            floor_price: body.total.floor_price.filter(|p| *p > 0.0),
+            floor_price_symbol: body.total.floor_price_symbol.filter(|s| !s.is_empty()),
+            volume_24h: one_day.and_then(|i| i.volume),
+            volume_change_24h: one_day.and_then(|i| i.volume_change).map(|c| c * 100.0),
+            sales_24h: one_day.and_then(|i| i.sales),
+            volume_7d: interval("seven_day").and_then(|i| i.volume),
+            total_volume: body.total.volume,
+            num_owners: body.total.num_owners,
+            market_cap: body.total.market_cap,
+        })
+    }
+}
@@ -0,0 +1,210 @@
+use anyhow::{Result, Context, anyhow};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::debug;
+
+use super::HttpClientBuilder;
+use crate::models::{register_source, DataSource, SourceCapability, SourceKind};
+
+/// 质押收益率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakingYield {
+    /// 年化收益率（百分比）
+    pub apr_pct: f64,
+    /// 质押量占总供应量的比例（百分比，数据源未提供时为None）
+    pub staked_ratio_pct: Option<f64>,
+    /// 数据来源说明
+    pub method: String,
+}
+
+/// Lido stETH 收益率响应
+#[derive(Debug, Deserialize)]
+struct LidoAprResponse {
+    data: LidoAprData,
+}
+
+/// Lido stETH 收益率数据
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LidoAprData {
+    /// 7日简单移动平均年化收益率（百分比）
+    sma_apr: f64,
+}
+
+/// Solana RPC 响应
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<Value>,
+}
+
+/// Solana 通胀率（年化比例，0.05表示5%）
+#[derive(Debug, Deserialize)]
+struct InflationRate {
+    validator: f64,
+}
+
+/// Solana 供应量（单位lamports）
+#[derive(Debug, Deserialize)]
+struct SupplyResult {
+    value: SupplyValue,
+}
+
+/// Solana 供应量数值
+#[derive(Debug, Deserialize)]
+struct SupplyValue {
+    total: u64,
+}
+
+/// Solana 投票账户列表
+#[derive(Debug, Deserialize)]
+struct VoteAccounts {
+    current: Vec<VoteAccount>,
+    delinquent: Vec<VoteAccount>,
+}
+
+/// Solana 投票账户（只取需要的字段）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VoteAccount {
+    /// 委托给该验证者的质押量（lamports）
+    activated_stake: u64,
+}
+
+/// 由验证者通胀率和质押比例估算Solana质押收益率
+///
+/// 通胀奖励全部分配给质押者，因此收益率约为验证者通胀率除以质押比例（未扣除验证者佣金，也不含MEV收入）
+///
+/// # 参数
+/// * `validator_inflation` - 验证者通胀率（年化比例）
+/// * `total_supply` - 总供应量
+/// * `activated_stake` - 已激活的质押量（与总供应量同单位）
+///
+/// # 返回
+/// * `Option<StakingYield>` - 供应量或质押量为0时为None
+pub fn solana_staking_yield(validator_inflation: f64, total_supply: u64, activated_stake: u64) -> Option<StakingYield> {
+    if total_supply == 0 || activated_stake == 0 || !validator_inflation.is_finite() {
+        return None;
+    }
+    let staked_ratio = activated_stake as f64 / total_supply as f64;
+    Some(StakingYield {
+        apr_pct: validator_inflation / staked_ratio * 100.0,
+        staked_ratio_pct: Some(staked_ratio * 100.0),
+        method: "validator_inflation / staked_ratio".to_string(),
+    })
+}
+
+/// 质押收益率客户端
+///
+/// ETH 使用 Lido 公开API的 stETH 收益率，SOL 由 Solana RPC 的通胀率和质押量估算，均不需要API密钥
+#[derive(Clone)]
+pub struct StakingClient {
+    /// HTTP客户端
+    client: Client,
+    /// Lido API基础URL
+    lido_url: String,
+    /// Solana RPC地址
+    solana_rpc_url: String,
+}
+
+impl StakingClient {
+    /// 数据源描述
+    pub const SOURCE: DataSource = DataSource {
+        name: "staking",
+        display_name: "Lido / Solana RPC",
+        kind: SourceKind::Api,
+        capabilities: &[SourceCapability::OnChain],
+    };
+
+    /// 创建新的质押收益率客户端
+    ///
+    /// # 参数
+    /// * `solana_rpc_url` - Solana RPC地址
+    /// * `timeout` - 请求超时时间
+    pub fn new(solana_rpc_url: impl Into<String>, timeout: Duration) -> Result<Self> {
+        register_source(Self::SOURCE);
+        Ok(Self {
+            client: HttpClientBuilder::new()
+                .timeout(timeout)
+                .user_agent("EverScan-StakingClient/1.0")
+                .build()?,
+            lido_url: "https://eth-api.lido.fi/v1".to_string(),
+            solana_rpc_url: solana_rpc_url.into(),
+        })
+    }
+
+    /// 获取ETH质押收益率（Lido stETH 7日平均年化收益率）
+    pub async fn get_eth_staking_yield(&self) -> Result<StakingYield> {
+        let url = format!("{}/protocol/steth/apr/sma", self.lido_url);
+        debug!("🌐 请求Lido收益率URL: {}", url);
+
+        let response = self.client.get(&url).send().await
+            .context("发送Lido收益率请求失败")?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Lido API请求失败: HTTP {}", response.status()));
+        }
+        let body: LidoAprResponse = response.json().await
+            .context("解析Lido收益率失败")?;
+        if !body.data.sma_apr.is_finite() {
+            return Err(anyhow!("Lido收益率无效: {}", body.data.sma_apr));
+        }
+
+        Ok(StakingYield {
+            apr_pct: body.data.sma_apr,
+            staked_ratio_pct: None,
+            method: "lido_steth_sma_apr".to_string(),
+        })
+    }
+
+    /// 获取SOL质押收益率（由通胀率和质押比例估算）
+    pub async fn get_sol_staking_yield(&self) -> Result<StakingYield> {
+        let inflation: InflationRate = self.rpc("getInflationRate", json!([])).await?;
+        let supply: SupplyResult = self.rpc("getSupply", json!([{ "excludeNonCirculatingAccountsList": true }])).await?;
+        let accounts: VoteAccounts = self.rpc("getVoteAccounts", json!([])).await?;
+        let activated_stake = accounts.current.iter()
+            .chain(&accounts.delinquent)
+            .map(|a| a.activated_stake)
+            .sum();
+
+        solana_staking_yield(inflation.validator, supply.value.total, activated_stake)
+            .ok_or_else(|| anyhow!("Solana供应量或质押量为0"))
+    }
+
+    /// 调用Solana JSON-RPC方法
+    async fn rpc<T: serde::de::DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        debug!("🌐 请求Solana RPC: {}", method);
+        let response = self.client.post(&self.solana_rpc_url)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .await
+            .with_context(|| format!("发送Solana RPC请求 {} 失败", method))?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Solana RPC请求 {} 失败: HTTP {}", method, response.status()));
+        }
+
+        let body: RpcResponse<T> = response.json().await
+            .with_context(|| format!("解析Solana RPC响应 {} 失败", method))?;
+        match (body.result, body.error) {
+            (Some(result), _) => Ok(result),
+            (None, Some(error)) => Err(anyhow!("Solana RPC {} 返回错误: {}", method, error)),
+            (None, None) => Err(anyhow!("Solana RPC {} 未返回结果", method)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solana_staking_yield() {
+        // 4.6%通胀率，总供应量中65%已质押
+        let staking = solana_staking_yield(0.046, 1_000, 650).unwrap();
+        assert!((staking.apr_pct - 7.0769).abs() < 1e-3);
+        assert!((staking.staked_ratio_pct.unwrap() - 65.0).abs() < 1e-9);
+        assert!(solana_staking_yield(0.046, 0, 650).is_none());
+        assert!(solana_staking_yield(0.046, 1_000, 0).is_none());
+    }
+}
@@ -1,9 +1,16 @@
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fs;
 use tracing::info;
 
+use crate::alerts::{AlertRuleInput, AlertSeverity};
+use crate::analytics::AnomalyMethod;
+use crate::calendar::EventImportance;
+use crate::clients::{AltcoinSeasonFormula, FearGreedProvider, NodeChain};
+use crate::secrets::SecretString;
+
 /// 应用程序配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -13,6 +20,1424 @@ pub struct AppConfig {
     pub data_sources: DataSourcesConfig,
     /// 监控币种配置
     pub monitoring: MonitoringConfig,
+    /// 日线价格回填配置
+    #[serde(default)]
+    pub backfill: BackfillConfig,
+    /// 币种数据质量评分配置
+    #[serde(default)]
+    pub data_quality: DataQualityConfig,
+    /// 多数据源价格偏离检测配置
+    #[serde(default)]
+    pub price_divergence: PriceDivergenceConfig,
+    /// 停止更新（下架/不活跃）币种检测配置
+    #[serde(default)]
+    pub inactive_coins: InactiveCoinsConfig,
+    /// 山寨币季节判定配置
+    #[serde(default)]
+    pub altcoin_season: AltcoinSeasonConfig,
+    /// 贪婪恐惧指数变化跟踪配置
+    #[serde(default)]
+    pub fear_greed: FearGreedConfig,
+    /// 现货ETF资金流向配置
+    #[serde(default)]
+    pub etf_flows: EtfFlowsConfig,
+    /// 合约爆仓数据配置
+    #[serde(default)]
+    pub liquidations: LiquidationsConfig,
+    /// 订单簿流动性快照配置
+    #[serde(default)]
+    pub liquidity: LiquidityConfig,
+    /// NFT合集跟踪配置
+    #[serde(default)]
+    pub nft: NftConfig,
+    /// 网络健康（算力、质押收益率）配置
+    #[serde(default)]
+    pub network: NetworkConfig,
+    /// 自建节点健康监控配置
+    #[serde(default)]
+    pub nodes: NodesConfig,
+    /// 数据集变化检测配置
+    #[serde(default)]
+    pub changes: ChangeDetectionConfig,
+    /// 经济日历配置
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+    /// 综合情绪权重配置
+    #[serde(default)]
+    pub sentiment: SentimentConfig,
+    /// 指数公式并行比较配置
+    #[serde(default)]
+    pub formula_trial: FormulaTrialConfig,
+    /// Google Trends搜索热度配置
+    #[serde(default)]
+    pub trends: TrendsConfig,
+    /// 板块统计配置
+    #[serde(default)]
+    pub sectors: SectorsConfig,
+    /// 价格比率跟踪配置
+    #[serde(default)]
+    pub ratios: RatiosConfig,
+    /// 派生指标（由已有指标计算）
+    #[serde(default)]
+    pub derived_metrics: Vec<DerivedMetricConfig>,
+    /// 功能开关（开关名称 -> 是否开启，未配置的使用默认值；可通过管理接口临时覆盖）
+    #[serde(default)]
+    pub feature_flags: BTreeMap<String, bool>,
+    /// 告警配置
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    /// 本地存储配置
+    #[serde(default)]
+    pub storage: StorageConfig,
+    /// 内存数据缓存容量配置
+    #[serde(default)]
+    pub cache: CacheConfig,
+    /// CSV历史数据导入的默认列映射
+    #[serde(default)]
+    pub import: ImportConfig,
+    /// 数据目录定期备份配置
+    #[serde(default)]
+    pub backup: BackupConfig,
+    /// 冷指标归档配置
+    #[serde(default)]
+    pub archive: ArchiveConfig,
+    /// 用户认证配置
+    #[serde(default)]
+    pub auth: AuthConfig,
+    /// 租户（API密钥映射到租户，各自拥有关注列表、告警规则和限流）
+    #[serde(default)]
+    pub tenants: Vec<TenantConfig>,
+    /// 插件配置（`[plugins.<插件名>]`，只加载配置了的插件，表内容原样传给插件）
+    #[serde(default)]
+    pub plugins: BTreeMap<String, serde_json::Value>,
+    /// 自定义HTTP数据源（响应由WASM转换模块解析为指标，需启用 wasm 特性）
+    #[serde(default)]
+    pub custom_sources: Vec<CustomSourceConfig>,
+    /// 指标转发（把新保存的数据点同步写入InfluxDB或Graphite）
+    #[serde(default)]
+    pub forwarders: Vec<ForwarderConfig>,
+    /// 事件总线（把任务结果、告警和缓存更新以JSON事件发布到Kafka或NATS）
+    #[serde(default)]
+    pub event_bus: EventBusConfig,
+    /// 推送数据源（外部系统通过 `POST /api/ingest/:source` 推送指标）
+    #[serde(default)]
+    pub ingest: Vec<IngestSourceConfig>,
+    /// 按需刷新接口配置
+    #[serde(default)]
+    pub refresh: RefreshConfig,
+    /// WebSocket（`/ws`）访问控制配置
+    #[serde(default)]
+    pub websocket: WebSocketConfig,
+    /// 任务队列配置
+    #[serde(default)]
+    pub jobs: JobsConfig,
+    /// 采集实例与只读API实例之间的数据共享配置
+    #[serde(default)]
+    pub replica: ReplicaConfig,
+    /// gRPC接口配置（需启用 grpc 特性）
+    #[serde(default)]
+    pub grpc: GrpcConfig,
+    /// MQTT发布配置（需启用 mqtt 特性）
+    #[serde(default)]
+    pub mqtt: MqttConfig,
+    /// 日志配置
+    #[serde(default)]
+    pub logging: LoggingConfig,
+}
+
+/// 日志输出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// 人类可读文本
+    #[default]
+    Text,
+    /// 每行一个JSON对象（适合Loki/ELK采集）
+    Json,
+}
+
+/// 日志文件轮转周期
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    /// 每分钟
+    Minutely,
+    /// 每小时
+    Hourly,
+    /// 每天
+    #[default]
+    Daily,
+    /// 不轮转
+    Never,
+}
+
+/// 日志文件输出配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogFileConfig {
+    /// 日志目录
+    pub directory: String,
+    /// 文件名前缀
+    pub prefix: String,
+    /// 轮转周期
+    pub rotation: LogRotation,
+    /// 保留的最大文件数（0表示不限制）
+    pub max_files: usize,
+    /// 文件日志格式（默认与控制台相同）
+    pub format: Option<LogFormat>,
+}
+
+impl Default for LogFileConfig {
+    fn default() -> Self {
+        Self {
+            directory: "logs".to_string(),
+            prefix: "everscan.log".to_string(),
+            rotation: LogRotation::Daily,
+            max_files: 14,
+            format: None,
+        }
+    }
+}
+
+/// 日志配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// 默认日志级别（设置了 RUST_LOG 环境变量时以环境变量为准）
+    pub level: String,
+    /// 控制台输出格式
+    pub format: LogFormat,
+    /// 按模块覆盖日志级别，如 `"everscan::tasks" = "debug"`
+    pub modules: HashMap<String, String>,
+    /// 文件输出（可选）
+    pub file: Option<LogFileConfig>,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            format: LogFormat::Text,
+            modules: HashMap::new(),
+            file: None,
+        }
+    }
+}
+
+/// 用户认证配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// JWT签名密钥（未配置时启动时随机生成，重启后已签发的令牌失效）
+    pub jwt_secret: Option<SecretString>,
+    /// 令牌有效期（小时）
+    pub token_ttl_hours: u64,
+    /// 是否允许注册新用户
+    pub allow_registration: bool,
+    /// 管理员用户名列表
+    pub admin_users: Vec<String>,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            jwt_secret: None,
+            token_ttl_hours: 24 * 7,
+            allow_registration: true,
+            admin_users: Vec::new(),
+        }
+    }
+}
+
+/// 租户配置
+///
+/// 请求携带 `X-Api-Key` 时按密钥识别租户；采集任务只按全部租户关注币种的并集采集一次
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantConfig {
+    /// 租户ID（唯一）
+    pub id: String,
+    /// 显示名称
+    #[serde(default)]
+    pub name: String,
+    /// API密钥（支持 `enc:` 加密值）
+    pub api_keys: Vec<SecretString>,
+    /// 初始关注币种（CoinGecko ID，之后可通过 `/api/tenant/watchlist` 修改）
+    #[serde(default)]
+    pub coins: Vec<String>,
+    /// 每分钟最多请求数（0表示不限制）
+    #[serde(default = "default_tenant_requests_per_minute")]
+    pub requests_per_minute: u32,
+    /// 最近一小时最多请求数（滚动窗口，0表示不限制）
+    #[serde(default)]
+    pub requests_per_hour: u32,
+}
+
+fn default_tenant_requests_per_minute() -> u32 {
+    120
+}
+
+/// 按需刷新接口配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RefreshConfig {
+    /// 调用令牌（通过 `X-Refresh-Token` 请求头传入，未配置时不校验，支持 `enc:` 加密值）
+    pub token: Option<SecretString>,
+    /// 同一数据集两次刷新的最小间隔（秒）
+    pub min_interval_seconds: u64,
+}
+
+impl Default for RefreshConfig {
+    fn default() -> Self {
+        Self {
+            token: None,
+            min_interval_seconds: 60,
+        }
+    }
+}
+
+/// WebSocket（`/ws`）访问控制配置
+///
+/// 默认不校验，公开部署时可要求令牌并限制来源页面，避免第三方网页直接订阅数据推送
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebSocketConfig {
+    /// 是否要求认证（接受 `tokens` 中的令牌、登录会话令牌或租户API密钥）
+    pub require_auth: bool,
+    /// 专用于WebSocket的访问令牌（支持 `enc:` 加密值）
+    pub tokens: Vec<SecretString>,
+    /// 允许的来源页面（如 `https://dash.example.com`，为空时不限制；同源页面总是允许）
+    pub allowed_origins: Vec<String>,
+    /// 每个连接待发送消息的队列长度
+    pub send_queue_capacity: usize,
+    /// 客户端接收过慢导致队列已满时的处理方式
+    pub overflow_policy: OverflowPolicy,
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        Self {
+            require_auth: false,
+            tokens: Vec::new(),
+            allowed_origins: Vec::new(),
+            send_queue_capacity: 8,
+            overflow_policy: OverflowPolicy::DropOldest,
+        }
+    }
+}
+
+/// WebSocket发送队列溢出策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// 丢弃最早的待发送消息（推送的是完整快照，客户端只会错过中间状态）
+    DropOldest,
+    /// 断开连接，由客户端重新连接
+    Disconnect,
+}
+
+/// 任务队列配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JobsConfig {
+    /// 并发执行任务的工作者数量
+    pub workers: usize,
+    /// 每个优先级队列的容量，队列满时手动触发和刷新请求被拒绝
+    pub queue_capacity: usize,
+    /// 定时任务失败后的最大重试次数
+    pub max_retries: u32,
+    /// 重试前的等待时间（秒），每次重试翻倍
+    pub retry_backoff_seconds: u64,
+    /// 调度器检查到期任务的间隔（秒）
+    pub scheduler_tick_seconds: u64,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            workers: 4,
+            queue_capacity: 64,
+            max_retries: 2,
+            retry_backoff_seconds: 30,
+            scheduler_tick_seconds: 60,
+        }
+    }
+}
+
+/// 采集实例与只读API实例之间的数据共享配置
+/// 
+/// 两类实例共享 `[storage] data_dir`：采集实例写入缓存快照和各状态文件，
+/// 以 `--api-only` 启动的实例只读取这些文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplicaConfig {
+    /// 采集实例写入缓存快照的最小间隔（秒）
+    pub snapshot_interval_seconds: u64,
+    /// 只读实例重新加载数据的间隔（秒）
+    pub sync_interval_seconds: u64,
+}
+
+impl Default for ReplicaConfig {
+    fn default() -> Self {
+        Self {
+            snapshot_interval_seconds: 5,
+            sync_interval_seconds: 15,
+        }
+    }
+}
+
+/// gRPC接口配置
+/// 
+/// gRPC接口不做用户认证，默认只监听本机，供内网服务调用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GrpcConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 监听地址
+    pub host: String,
+    /// 监听端口
+    pub port: u16,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".to_string(),
+            port: 50051,
+        }
+    }
+}
+
+/// MQTT发布配置
+/// 
+/// 缓存更新时把指数和币价以纯文本数值发布到MQTT代理（保留消息），供物联网显示屏订阅
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MqttConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 代理地址
+    pub host: String,
+    /// 代理端口
+    pub port: u16,
+    /// 客户端ID
+    pub client_id: String,
+    /// 用户名（可选）
+    pub username: Option<String>,
+    /// 密码（可选，可加密）
+    pub password: Option<SecretString>,
+    /// 主题前缀，指标默认发布到 `<前缀>/<指标键>`（`:` 替换为 `/`，如 `everscan/price/bitcoin`）
+    pub topic_prefix: String,
+    /// 发布的缓存指标键（与告警规则相同，如 `fear_greed_index`、`price:bitcoin`）
+    pub metrics: Vec<String>,
+    /// 单独指定主题的指标（指标键 -> 主题），同样会被发布
+    pub topics: BTreeMap<String, String>,
+    /// 服务质量等级（0-2）
+    pub qos: u8,
+    /// 是否作为保留消息发布（新订阅者立即收到最新值）
+    pub retain: bool,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "localhost".to_string(),
+            port: 1883,
+            client_id: "everscan".to_string(),
+            username: None,
+            password: None,
+            topic_prefix: "everscan".to_string(),
+            metrics: vec![
+                "fear_greed_index".to_string(),
+                "altcoin_season_index".to_string(),
+                "price:bitcoin".to_string(),
+                "price:ethereum".to_string(),
+            ],
+            topics: BTreeMap::new(),
+            qos: 0,
+            retain: true,
+        }
+    }
+}
+
+/// 山寨币季节判定配置
+/// 
+/// 进入阈值高于退出阈值，指数在两者之间波动时保持当前状态，避免频繁切换
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AltcoinSeasonConfig {
+    /// 指数不低于该值时进入山寨币季节
+    pub enter_threshold: u8,
+    /// 指数不高于该值时退出山寨币季节
+    pub exit_threshold: u8,
+    /// 历史记录最大保留条数
+    pub max_history: usize,
+    /// 成分币种快照最大保留条数
+    pub max_constituent_snapshots: usize,
+    /// 指数计算公式（change_24h / change_90d）
+    pub formula: AltcoinSeasonFormula,
+}
+
+impl Default for AltcoinSeasonConfig {
+    fn default() -> Self {
+        Self {
+            enter_threshold: 75,
+            exit_threshold: 65,
+            max_history: 8760, // 按小时采集约一年
+            max_constituent_snapshots: 720, // 按小时采集约30天
+            formula: AltcoinSeasonFormula::default(),
+        }
+    }
+}
+
+/// 指数公式并行比较配置
+///
+/// 修改山寨币季节指数公式或综合情绪权重前，在截止时间之前同时计算现行和候选两个版本并写入指标存储，
+/// 通过 `/api/formulas/{index}/comparison` 验证差异后再切换默认值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormulaTrialConfig {
+    /// 山寨币季节指数候选公式的比较截止时间（不设置表示不比较）
+    pub altcoin_season_until: Option<chrono::DateTime<chrono::Utc>>,
+    /// 山寨币季节指数的候选公式
+    pub altcoin_season_candidate: AltcoinSeasonFormula,
+    /// 综合情绪候选权重的比较截止时间（不设置表示不比较）
+    pub sentiment_until: Option<chrono::DateTime<chrono::Utc>>,
+    /// 综合情绪的候选权重
+    pub sentiment_candidate: SentimentConfig,
+    /// 记录间隔（秒）
+    pub interval_seconds: u64,
+}
+
+impl Default for FormulaTrialConfig {
+    fn default() -> Self {
+        Self {
+            altcoin_season_until: None,
+            altcoin_season_candidate: AltcoinSeasonFormula::Change90d,
+            sentiment_until: None,
+            sentiment_candidate: SentimentConfig::default(),
+            interval_seconds: 3600,
+        }
+    }
+}
+
+/// 数据集变化检测配置
+///
+/// 比较相邻两次采集的CMC上市信息，排名、流通量或标签变化达到阈值时记录变化事件；
+/// 定期获取各数据源的币种列表，出现新的币种ID时记录新上线事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChangeDetectionConfig {
+    /// 排名变化达到该名次时记录事件
+    pub min_rank_change: u64,
+    /// 流通量变化达到该百分比时记录事件
+    pub min_supply_change_pct: f64,
+    /// 是否为变化事件触发告警
+    pub alerts: bool,
+    /// 变化事件告警级别
+    pub alert_severity: AlertSeverity,
+    /// 最多保留的变化事件数
+    pub max_events: usize,
+    /// 检测新上线币种的数据源（`coingecko`、`coinmarketcap`，为空表示不检测）
+    pub new_listing_sources: Vec<String>,
+    /// 新上线币种检测间隔（秒）
+    pub new_listing_interval_seconds: u64,
+    /// 每次从CoinMarketCap获取的最近上线币种数
+    pub cmc_recent_limit: usize,
+    /// 每次最多为多少个CoinGecko新币种查询首次价格
+    pub max_new_listing_quotes: usize,
+}
+
+impl Default for ChangeDetectionConfig {
+    fn default() -> Self {
+        Self {
+            min_rank_change: 5,
+            min_supply_change_pct: 1.0,
+            alerts: true,
+            alert_severity: AlertSeverity::Info,
+            max_events: 5000,
+            new_listing_sources: vec!["coingecko".to_string(), "coinmarketcap".to_string()],
+            new_listing_interval_seconds: 3600,
+            cmc_recent_limit: 200,
+            max_new_listing_quotes: 50,
+        }
+    }
+}
+
+/// 日线价格回填配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackfillConfig {
+    /// 是否在启动时回填
+    pub enabled: bool,
+    /// 回填天数（CoinGecko免费接口最多365天）
+    pub days: u32,
+    /// CoinGecko ID到缓存币种ID的映射，未列出的币种直接使用CoinGecko ID
+    pub coin_ids: BTreeMap<String, String>,
+}
+
+impl BackfillConfig {
+    /// 获取监控币种在缓存和价格历史中的ID
+    pub fn coin_id<'a>(&'a self, coingecko_id: &'a str) -> &'a str {
+        self.coin_ids.get(coingecko_id).map_or(coingecko_id, String::as_str)
+    }
+}
+
+impl Default for BackfillConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            days: 365,
+            coin_ids: BTreeMap::from([("hyperliquid".to_string(), "hype".to_string())]),
+        }
+    }
+}
+
+/// 币种数据质量评分配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataQualityConfig {
+    /// 各数据源价格与主数据源相差不超过该百分比时视为一致
+    pub tolerance_pct: f64,
+    /// 数据年龄不超过该秒数时视为新鲜，超过后评分逐步降低，达到3倍时新鲜度得分为零
+    pub stale_after_seconds: u64,
+    /// 统计采集失败次数的时间窗口（小时）
+    pub failure_window_hours: u64,
+}
+
+impl Default for DataQualityConfig {
+    fn default() -> Self {
+        Self {
+            tolerance_pct: 1.0,
+            stale_after_seconds: 28800, // 两个默认采集周期
+            failure_window_hours: 24,
+        }
+    }
+}
+
+/// 多数据源价格偏离检测配置
+/// 
+/// 定期从CoinGecko和交易所获取参考价格，与主数据源（CoinMarketCap）的价格比较
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PriceDivergenceConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 最高价与最低价之差占中位价的百分比超过该值时标记并告警
+    pub threshold_pct: f64,
+    /// 检查间隔（秒）
+    pub check_interval_seconds: u64,
+    /// 告警级别
+    pub severity: AlertSeverity,
+    /// 需要交叉校验的币种
+    pub coins: Vec<PriceReferenceConfig>,
+}
+
+impl Default for PriceDivergenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_pct: 3.0,
+            check_interval_seconds: 900,
+            severity: AlertSeverity::Warning,
+            coins: vec![PriceReferenceConfig {
+                coin_id: "hype".to_string(),
+                coingecko_id: Some("hyperliquid".to_string()),
+                exchange_symbol: None,
+            }],
+        }
+    }
+}
+
+/// 币种参考价格来源配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceReferenceConfig {
+    /// 缓存中的币种ID（如 `hype`）
+    pub coin_id: String,
+    /// CoinGecko币种ID（如 `hyperliquid`，可选）
+    #[serde(default)]
+    pub coingecko_id: Option<String>,
+    /// Binance现货交易对（如 `BTCUSDT`，可选）
+    #[serde(default)]
+    pub exchange_symbol: Option<String>,
+}
+
+/// 停止更新（下架/不活跃）币种检测配置
+///
+/// CoinMarketCap标记为不活跃、不再收录或报价长时间未更新的币种会在缓存中标记，
+/// 不参与市场广度计算，并在进入该状态时告警一次
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InactiveCoinsConfig {
+    /// 报价超过该小时数未更新时视为停止更新
+    pub max_quote_age_hours: u64,
+    /// 检查间隔（秒）
+    pub check_interval_seconds: u64,
+    /// 告警级别
+    pub severity: AlertSeverity,
+}
+
+impl Default for InactiveCoinsConfig {
+    fn default() -> Self {
+        Self {
+            max_quote_age_hours: 48,
+            check_interval_seconds: 300,
+            severity: AlertSeverity::Warning,
+        }
+    }
+}
+
+/// 贪婪恐惧指数变化跟踪配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FearGreedConfig {
+    /// 数据来源（`auto` / `coinmarketcap` / `alternative_me`）
+    pub provider: FearGreedProvider,
+    /// 24小时变化达到该点数时告警
+    pub swing_threshold: u8,
+    /// 历史记录最大保留条数
+    pub max_history: usize,
+}
+
+impl Default for FearGreedConfig {
+    fn default() -> Self {
+        Self {
+            provider: FearGreedProvider::default(),
+            swing_threshold: 15,
+            max_history: 8760,
+        }
+    }
+}
+
+/// 现货ETF资金流向配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EtfFlowsConfig {
+    /// 资金流向数据源URL（返回JSON数组，留空则不启用）
+    pub source_url: Option<String>,
+    /// 轮询间隔（秒）
+    pub interval_seconds: u64,
+    /// 单日某资产所有基金合计净流出达到该金额（美元）时告警
+    pub large_outflow_usd: f64,
+    /// 每个资产最多保留的天数
+    pub max_days: usize,
+}
+
+impl Default for EtfFlowsConfig {
+    fn default() -> Self {
+        Self {
+            source_url: None,
+            interval_seconds: 3600,
+            large_outflow_usd: 200_000_000.0,
+            max_days: 1095, // 约三年
+        }
+    }
+}
+
+/// 合约爆仓数据配置
+///
+/// 需要配置 `[data_sources.coinglass] api_key` 才会启用采集任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LiquidationsConfig {
+    /// 采集的币种符号
+    pub symbols: Vec<String>,
+    /// 参与聚合的交易所
+    pub exchanges: Vec<String>,
+    /// 轮询间隔（秒）
+    pub interval_seconds: u64,
+    /// 单个币种一小时多空爆仓合计达到该金额（美元）时触发连环爆仓告警
+    pub cascade_threshold_usd: f64,
+    /// 连环爆仓告警级别
+    pub cascade_severity: AlertSeverity,
+    /// 每个币种最多保留的小时数
+    pub max_hours: usize,
+}
+
+impl Default for LiquidationsConfig {
+    fn default() -> Self {
+        Self {
+            symbols: vec!["BTC".to_string(), "ETH".to_string()],
+            exchanges: vec!["Binance".to_string(), "OKX".to_string(), "Bybit".to_string()],
+            interval_seconds: 900,
+            cascade_threshold_usd: 100_000_000.0,
+            cascade_severity: AlertSeverity::Warning,
+            max_hours: 2160, // 90天
+        }
+    }
+}
+
+/// 订单簿流动性快照配置
+///
+/// 定期从Binance现货订单簿计算买卖价差和中间价附近的挂单深度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LiquidityConfig {
+    /// 交易对（如 BTCUSDT，为空则不启用）
+    pub symbols: Vec<String>,
+    /// 统计深度的价格范围（距中间价的百分比）
+    pub depth_pct: f64,
+    /// 快照间隔（秒）
+    pub interval_seconds: u64,
+}
+
+impl Default for LiquidityConfig {
+    fn default() -> Self {
+        Self {
+            symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+            depth_pct: 2.0,
+            interval_seconds: 300,
+        }
+    }
+}
+
+/// NFT合集跟踪配置
+///
+/// 需要配置 `[data_sources.opensea] api_key` 才会启用采集任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NftConfig {
+    /// OpenSea合集标识（如 `boredapeyachtclub`，为空则不启用）
+    pub collections: Vec<String>,
+    /// 轮询间隔（秒）
+    pub interval_seconds: u64,
+}
+
+impl Default for NftConfig {
+    fn default() -> Self {
+        Self {
+            collections: Vec::new(),
+            interval_seconds: 3600,
+        }
+    }
+}
+
+/// 网络健康配置
+///
+/// 比特币算力和难度来自mempool.space，ETH质押收益率来自Lido，SOL质押收益率由Solana RPC估算
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// 是否启用采集
+    pub enabled: bool,
+    /// Solana RPC地址（为空则不采集SOL质押收益率）
+    pub solana_rpc_url: String,
+    /// 轮询间隔（秒）
+    pub interval_seconds: u64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            solana_rpc_url: "https://api.mainnet-beta.solana.com".to_string(),
+            interval_seconds: 3600,
+        }
+    }
+}
+
+/// 自建节点健康监控配置
+///
+/// 定期探测配置节点的区块高度、对等节点数和响应延迟，落后参考高度过多或无法访问时告警
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodesConfig {
+    /// 节点列表（为空则不启用）
+    pub nodes: Vec<NodeEndpoint>,
+    /// 探测间隔（秒）
+    pub interval_seconds: u64,
+    /// 节点变为不健康时是否告警
+    pub alerts: bool,
+    /// 告警级别
+    pub alert_severity: AlertSeverity,
+}
+
+impl Default for NodesConfig {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            interval_seconds: 60,
+            alerts: true,
+            alert_severity: AlertSeverity::Warning,
+        }
+    }
+}
+
+/// 单个自建节点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeEndpoint {
+    /// 节点名称（用于指标名和告警规则）
+    pub name: String,
+    /// 节点所属的链（`ethereum` 或 `solana`）
+    pub chain: NodeChain,
+    /// JSON-RPC 地址
+    pub url: String,
+    /// 参考节点的 JSON-RPC 地址（可选，用于判断落后；未配置时只与同链的其他节点比较）
+    #[serde(default)]
+    pub reference_url: Option<String>,
+    /// 允许落后的区块数（默认以太坊3个，Solana 150个slot）
+    #[serde(default)]
+    pub max_lag: Option<u64>,
+}
+
+/// 综合情绪权重配置
+///
+/// 综合情绪分为各组成部分的加权平均，缺少数据的部分不参与计算，权重为0表示不参与
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SentimentConfig {
+    /// 贪婪恐惧指数权重
+    pub fear_greed_weight: f64,
+    /// 交易所资金流向权重
+    pub exchange_flow_weight: f64,
+    /// 社交情绪权重
+    pub social_weight: f64,
+}
+
+impl Default for SentimentConfig {
+    fn default() -> Self {
+        Self {
+            fear_greed_weight: 1.0,
+            exchange_flow_weight: 0.5,
+            social_weight: 0.5,
+        }
+    }
+}
+
+/// Google Trends搜索热度配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrendsConfig {
+    /// 搜索词（为空则不启用）
+    pub terms: Vec<String>,
+    /// 地区代码（如 US，空字符串表示全球）
+    pub geo: String,
+    /// 获取间隔（秒）
+    pub interval_seconds: u64,
+}
+
+impl Default for TrendsConfig {
+    fn default() -> Self {
+        Self {
+            terms: vec!["bitcoin".to_string(), "crypto crash".to_string()],
+            geo: String::new(),
+            interval_seconds: 86400, // 每天
+        }
+    }
+}
+
+/// 板块统计配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SectorsConfig {
+    /// 统计间隔（秒）
+    pub interval_seconds: u64,
+    /// 板块定义（为空则不启用）
+    pub sectors: Vec<SectorConfig>,
+}
+
+impl Default for SectorsConfig {
+    fn default() -> Self {
+        let sector = |name: &str, tags: &[&str]| SectorConfig {
+            name: name.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            coins: Vec::new(),
+        };
+        Self {
+            interval_seconds: 3600,
+            sectors: vec![
+                sector("l1", &["layer-1"]),
+                sector("defi", &["defi"]),
+                sector("ai", &["ai-big-data", "ai-agents"]),
+                sector("meme", &["memes"]),
+            ],
+        }
+    }
+}
+
+/// 板块定义
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectorConfig {
+    /// 板块名称（如 `defi`）
+    pub name: String,
+    /// 币种带有其中任一分类标签时归入该板块
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 额外归入该板块的币种ID（用于缺少标签的币种）
+    #[serde(default)]
+    pub coins: Vec<String>,
+}
+
+/// 价格比率跟踪配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RatiosConfig {
+    /// 计算间隔（秒）
+    pub interval_seconds: u64,
+    /// 判断突破时回看的天数
+    pub lookback_days: u32,
+    /// 突破回看区间最高/最低值时是否告警
+    pub alerts: bool,
+    /// 告警级别
+    pub alert_severity: AlertSeverity,
+    /// 比率定义（为空则不启用）
+    pub ratios: Vec<RatioConfig>,
+}
+
+impl Default for RatiosConfig {
+    fn default() -> Self {
+        let ratio = |name: &str, numerator: &str, denominator: &str| RatioConfig {
+            name: name.to_string(),
+            numerator: numerator.to_string(),
+            denominator: denominator.to_string(),
+            description: None,
+        };
+        Self {
+            interval_seconds: 900,
+            lookback_days: 30,
+            alerts: true,
+            alert_severity: AlertSeverity::Info,
+            ratios: vec![
+                ratio("eth_btc", "price:eth", "price:btc"),
+                ratio("sol_eth", "price:sol", "price:eth"),
+            ],
+        }
+    }
+}
+
+/// 比率定义
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatioConfig {
+    /// 比率名称（小写，写入指标存储时为 `ratio_<名称>`）
+    pub name: String,
+    /// 分子（缓存指标键如 `price:eth`，或指标存储中的指标名称）
+    pub numerator: String,
+    /// 分母
+    pub denominator: String,
+    /// 说明（可选）
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// 经济日历配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalendarConfig {
+    /// 日历数据源URL（留空则不启用）
+    pub source_url: String,
+    /// 只保留这些国家/货币的事件（为空表示全部）
+    pub countries: Vec<String>,
+    /// 只保留不低于该重要性的事件
+    pub min_importance: EventImportance,
+    /// 获取间隔（秒）
+    pub interval_seconds: u64,
+    /// 事件提醒
+    pub reminders: Vec<CalendarReminderConfig>,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            source_url: "https://nfs.faireconomy.media/ff_calendar_thisweek.json".to_string(),
+            countries: vec!["USD".to_string()],
+            min_importance: EventImportance::Medium,
+            interval_seconds: 21600, // 6小时
+            reminders: vec![CalendarReminderConfig::default()],
+        }
+    }
+}
+
+/// 宏观事件提醒配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalendarReminderConfig {
+    /// 只提醒名称包含该关键字的事件（不区分大小写，为空表示全部）
+    pub keyword: Option<String>,
+    /// 只提醒不低于该重要性的事件
+    pub min_importance: EventImportance,
+    /// 提前多少分钟提醒
+    pub minutes_before: u32,
+    /// 告警级别
+    pub severity: AlertSeverity,
+}
+
+impl Default for CalendarReminderConfig {
+    fn default() -> Self {
+        Self {
+            keyword: None,
+            min_importance: EventImportance::High,
+            minutes_before: 60,
+            severity: AlertSeverity::Warning,
+        }
+    }
+}
+
+/// 派生指标配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedMetricConfig {
+    /// 指标名称
+    pub name: String,
+    /// 计算表达式（如 `price:ethereum / price:bitcoin`）
+    pub expression: String,
+    /// 单位（可选）
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// 说明（可选）
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// 自定义HTTP数据源配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomSourceConfig {
+    /// 数据源标识（写入指标记录，小写）
+    pub name: String,
+    /// 请求URL（GET）
+    pub url: String,
+    /// 请求头（值可加密，如 `Authorization`）
+    #[serde(default)]
+    pub headers: BTreeMap<String, SecretString>,
+    /// WASM转换模块路径（`.wasm`，也支持 `.wat` 文本格式）
+    pub transform: String,
+    /// 获取间隔（秒）
+    #[serde(default = "default_custom_source_interval")]
+    pub interval_seconds: u64,
+    /// 请求超时（秒）
+    #[serde(default = "default_custom_source_timeout")]
+    pub timeout_seconds: u64,
+}
+
+/// 自定义数据源默认获取间隔（秒）
+fn default_custom_source_interval() -> u64 {
+    300
+}
+
+/// 自定义数据源默认请求超时（秒）
+fn default_custom_source_timeout() -> u64 {
+    30
+}
+
+/// 指标转发目标类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForwarderKind {
+    /// InfluxDB（HTTP写入接口，行协议）
+    Influxdb,
+    /// Graphite（TCP明文协议）
+    Graphite,
+}
+
+/// 指标转发配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForwarderConfig {
+    /// 转发目标名称（用于日志）
+    pub name: String,
+    /// 目标类型
+    pub kind: ForwarderKind,
+    /// InfluxDB写入URL（如 `http://localhost:8086/api/v2/write?org=home&bucket=everscan`），
+    /// 或Graphite地址（如 `localhost:2003`）
+    pub url: String,
+    /// InfluxDB令牌（可选，可加密，以 `Authorization: Token` 发送）
+    #[serde(default)]
+    pub token: Option<SecretString>,
+    /// 只转发名称以这些前缀开头的指标（为空时转发全部）
+    #[serde(default)]
+    pub prefixes: Vec<String>,
+    /// Graphite指标路径前缀
+    #[serde(default = "default_forwarder_path_prefix")]
+    pub path_prefix: String,
+    /// 累积到该行数时立即发送
+    #[serde(default = "default_forwarder_batch_size")]
+    pub batch_size: usize,
+    /// 定期发送间隔（秒）
+    #[serde(default = "default_forwarder_flush_interval")]
+    pub flush_interval_seconds: u64,
+}
+
+/// Graphite默认指标路径前缀
+fn default_forwarder_path_prefix() -> String {
+    "everscan".to_string()
+}
+
+/// 指标转发默认批量行数
+fn default_forwarder_batch_size() -> usize {
+    500
+}
+
+/// 指标转发默认发送间隔（秒）
+fn default_forwarder_flush_interval() -> u64 {
+    10
+}
+
+/// 事件总线类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventBusKind {
+    /// Kafka（需启用 kafka 特性）
+    #[default]
+    Kafka,
+    /// NATS（需启用 nats 特性）
+    Nats,
+}
+
+/// 事件总线配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventBusConfig {
+    /// 是否启用
+    pub enabled: bool,
+    /// 总线类型
+    pub kind: EventBusKind,
+    /// Kafka broker列表（逗号分隔，如 `localhost:9092`）或NATS地址（如 `nats://localhost:4222`）
+    pub url: String,
+    /// Kafka主题；NATS为主题前缀，事件发布到 `<前缀>.<事件类型>`
+    pub topic: String,
+    /// 是否发布任务结果（每个新保存的数据点一条事件）
+    pub task_results: bool,
+    /// 是否发布告警
+    pub alerts: bool,
+    /// 发布告警的最低级别
+    pub alert_min_severity: AlertSeverity,
+    /// 是否发布缓存更新
+    pub cache_updates: bool,
+}
+
+impl Default for EventBusConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: EventBusKind::Kafka,
+            url: "localhost:9092".to_string(),
+            topic: "everscan.events".to_string(),
+            task_results: true,
+            alerts: true,
+            alert_min_severity: AlertSeverity::Info,
+            cache_updates: true,
+        }
+    }
+}
+
+/// 推送数据源配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestSourceConfig {
+    /// 数据源标识（URL路径和指标记录中的数据源，小写）
+    pub name: String,
+    /// 推送令牌（可加密，配置了签名校验时可省略）
+    #[serde(default)]
+    pub token: Option<SecretString>,
+    /// 请求签名校验（可选）
+    #[serde(default)]
+    pub signature: Option<SignatureConfig>,
+}
+
+/// 签名算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureScheme {
+    /// HMAC-SHA256（共享密钥）
+    HmacSha256,
+    /// Ed25519（发送方公钥）
+    Ed25519,
+}
+
+/// 入站请求签名校验配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureConfig {
+    /// 签名算法
+    pub scheme: SignatureScheme,
+    /// HMAC共享密钥，或Ed25519公钥（hex或base64，可加密）
+    pub key: SecretString,
+    /// 签名请求头（值为hex或base64，可带 `sha256=` 前缀）
+    #[serde(default = "default_signature_header")]
+    pub header: String,
+    /// 时间戳请求头（配置后签名内容为 `{时间戳}.{请求体}`，并拒绝过旧的请求）
+    #[serde(default)]
+    pub timestamp_header: Option<String>,
+    /// 时间戳允许的最大偏差（秒）
+    #[serde(default = "default_signature_max_age")]
+    pub max_age_seconds: u64,
+}
+
+/// 默认签名请求头
+fn default_signature_header() -> String {
+    "x-signature".to_string()
+}
+
+/// 默认时间戳最大偏差（秒）
+fn default_signature_max_age() -> u64 {
+    300
+}
+
+/// 本地存储配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// 状态文件目录
+    pub data_dir: String,
+    /// 任务指标保留天数
+    pub metrics_retention_days: u32,
+    /// 指标慢查询阈值（毫秒，超过时记录查询条件，0表示不记录）
+    pub slow_query_ms: u64,
+    /// 分桶查询缓存条目数（0表示不缓存）
+    pub query_cache_entries: usize,
+    /// API单次查询最多返回的数据点数（分桶查询按时间桶数计，0表示不限制）
+    pub max_query_points: usize,
+    /// API原始数据点查询的最大时间范围（天，0表示不限制）
+    pub max_query_range_days: u32,
+    /// API查询超时（毫秒，0表示不限制）
+    pub query_timeout_ms: u64,
+}
+
+/// 内存数据缓存容量配置
+/// 
+/// 币种行情和分析类数据集按估算的序列化大小计入容量，超出上限时淘汰最久未访问的条目
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// 估算内存占用上限（字节，0表示不限制）
+    pub max_bytes: usize,
+    /// 条目数上限（0表示不限制）
+    pub max_entries: usize,
+}
+
+/// 数据目录定期备份配置
+/// 
+/// 把数据目录（指标、历史、告警状态等）打包为 `.tar.gz` 归档保存到本地目录，可选上传到S3
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// 是否启用定期备份
+    pub enabled: bool,
+    /// 备份间隔（小时）
+    pub interval_hours: u64,
+    /// 本地归档目录
+    pub directory: String,
+    /// 本地保留的归档数量
+    pub keep: usize,
+    /// S3（或兼容存储）上传配置（可选）
+    pub s3: Option<S3Config>,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: 24,
+            directory: "backups".to_string(),
+            keep: 7,
+            s3: None,
+        }
+    }
+}
+
+/// 冷指标归档配置
+/// 
+/// 把早于热数据窗口的指标导出为Parquet文件（保存在 `data_dir/archive`，可选上传到S3）并从指标存储中删除，
+/// 查询起始时间早于归档边界时自动从归档读取
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchiveConfig {
+    /// 是否启用归档
+    pub enabled: bool,
+    /// 热数据窗口（天），须小于 `storage.metrics_retention_days`
+    pub hot_days: u32,
+    /// 归档间隔（小时）
+    pub interval_hours: u64,
+    /// 上传到S3后是否保留本地Parquet文件（不保留时查询从S3下载）
+    pub keep_local: bool,
+    /// S3（或兼容存储）上传配置（可选）
+    pub s3: Option<S3Config>,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hot_days: 90,
+            interval_hours: 24,
+            keep_local: true,
+            s3: None,
+        }
+    }
+}
+
+/// S3（或兼容存储）配置，用于备份上传和指标归档
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3Config {
+    /// 存储桶
+    pub bucket: String,
+    /// 区域
+    #[serde(default = "default_s3_region")]
+    pub region: String,
+    /// 自定义端点（S3兼容存储，如 `https://minio.internal:9000`），留空使用AWS
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// 对象键前缀
+    #[serde(default = "default_s3_prefix")]
+    pub prefix: String,
+    /// 访问密钥ID（也可通过 `AWS_ACCESS_KEY_ID` 设置）
+    #[serde(default)]
+    pub access_key_id: Option<SecretString>,
+    /// 访问密钥（也可通过 `AWS_SECRET_ACCESS_KEY` 设置，支持 `enc:` 加密值）
+    #[serde(default)]
+    pub secret_access_key: Option<SecretString>,
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_s3_prefix() -> String {
+    "everscan/".to_string()
+}
+
+/// CSV历史数据导入配置
+/// 
+/// 列可以用表头名称或从0开始的列序号指定，命令行参数优先
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImportConfig {
+    /// 时间列
+    pub timestamp_column: String,
+    /// 数值列
+    pub value_column: String,
+    /// 时间格式（chrono格式串，如 `%Y-%m-%d`；留空时自动识别RFC 3339、Unix秒/毫秒和常见日期格式）
+    pub timestamp_format: Option<String>,
+    /// 字段分隔符
+    pub delimiter: char,
+    /// 第一行是否为表头
+    pub has_header: bool,
+    /// 数值单位（可选，如 `usd`）
+    pub unit: Option<String>,
+}
+
+impl Default for ImportConfig {
+    fn default() -> Self {
+        Self {
+            timestamp_column: "timestamp".to_string(),
+            value_column: "value".to_string(),
+            timestamp_format: None,
+            delimiter: ',',
+            has_header: true,
+            unit: None,
+        }
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            data_dir: "data".to_string(),
+            metrics_retention_days: 365,
+            slow_query_ms: 200,
+            query_cache_entries: 256,
+            max_query_points: 50_000,
+            max_query_range_days: 400,
+            query_timeout_ms: 5_000,
+        }
+    }
 }
 
 /// Web服务器配置
@@ -22,6 +1447,39 @@ pub struct ServerConfig {
     pub host: String,
     /// 服务器监听端口
     pub port: u16,
+    /// 前端静态文件目录（可选，设置后覆盖编译进二进制的前端资源）
+    #[serde(default)]
+    pub static_dir: Option<String>,
+    /// 监听的Unix套接字路径（设置后不再监听 `host:port`，供同机反向代理连接）
+    #[serde(default)]
+    pub unix_socket: Option<String>,
+    /// Unix套接字文件的权限
+    #[serde(default = "default_unix_socket_mode")]
+    pub unix_socket_mode: u32,
+    /// HTTPS配置（需启用 tls 特性）
+    #[serde(default)]
+    pub tls: TlsConfig,
+}
+
+/// Unix套接字文件的默认权限（所有者和同组用户可读写，便于反向代理加入同组后连接）
+fn default_unix_socket_mode() -> u32 {
+    0o660
+}
+
+/// HTTPS配置
+///
+/// 启用后 `server.port` 直接提供HTTPS，小型部署无需为TLS单独配置反向代理
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TlsConfig {
+    /// 是否启用HTTPS
+    pub enabled: bool,
+    /// PEM格式的证书链路径
+    pub cert_path: String,
+    /// PEM格式的私钥路径（PKCS#8、PKCS#1 或 SEC1）
+    pub key_path: String,
+    /// 把HTTP请求重定向到HTTPS的监听端口（如80，不配置时不监听HTTP）
+    pub redirect_http_port: Option<u16>,
 }
 
 /// 数据源配置
@@ -29,19 +1487,37 @@ pub struct ServerConfig {
 pub struct DataSourcesConfig {
     /// CoinMarketCap配置
     pub coinmarketcap: ApiConfig,
-    /// Glassnode配置（预留）
+    /// Glassnode配置（可选，用于交易所资金流向）
     pub glassnode: ApiConfig,
     /// DeBankAPI配置（预留）
     pub debank: ApiConfig,
     /// DuneAPI配置（预留）
     pub dune: ApiConfig,
+    /// LunarCrush配置（可选，用于社交情绪）
+    #[serde(default = "default_optional_api")]
+    pub lunarcrush: ApiConfig,
+    /// Coinglass配置（可选，用于合约爆仓数据）
+    #[serde(default = "default_optional_api")]
+    pub coinglass: ApiConfig,
+    /// OpenSea配置（可选，用于NFT合集地板价）
+    #[serde(default = "default_optional_api")]
+    pub opensea: ApiConfig,
+}
+
+/// 可选数据源的默认配置（无密钥）
+fn default_optional_api() -> ApiConfig {
+    ApiConfig {
+        api_key: None,
+        request_interval_ms: 1000,
+        timeout_seconds: 30,
+    }
 }
 
 /// API配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiConfig {
-    /// API密钥
-    pub api_key: Option<String>,
+    /// API密钥（支持 `enc:` 加密值）
+    pub api_key: Option<SecretString>,
     /// 请求间隔（毫秒）
     pub request_interval_ms: u64,
     /// 请求超时时间（秒）
@@ -57,6 +1533,159 @@ pub struct MonitoringConfig {
     pub update_interval_seconds: u64,
 }
 
+/// 告警配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertsConfig {
+    /// 任务超过 执行间隔 × 该倍数 仍未成功时触发过期告警
+    pub staleness_multiplier: f64,
+    /// 存活检查间隔（秒）
+    pub staleness_check_interval_seconds: u64,
+    /// 默认冷却时间（秒），同一规则同一级别的告警在窗口内只发送一次
+    pub default_cooldown_seconds: u64,
+    /// 按规则前缀覆盖冷却时间（秒），如 `staleness = 7200`
+    pub rule_cooldowns: HashMap<String, u64>,
+    /// 冷却窗口内重复触发多少次后升级告警级别（0表示不升级）
+    pub escalation_threshold: u32,
+    /// Webhook通知渠道
+    pub webhooks: Vec<WebhookConfig>,
+    /// Discord通知渠道
+    pub discord: Vec<DiscordConfig>,
+    /// Telegram通知渠道
+    pub telegram: Vec<TelegramConfig>,
+    /// 告警规则评估间隔（秒）
+    pub rule_check_interval_seconds: u64,
+    /// 静态告警规则（启动时导入，之后可通过 /api/alerts 管理）
+    pub rules: Vec<AlertRuleInput>,
+    /// 异常检测间隔（秒）
+    pub anomaly_check_interval_seconds: u64,
+    /// 异常检测规则
+    pub anomalies: Vec<AnomalyRuleConfig>,
+}
+
+/// 异常检测规则配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyRuleConfig {
+    /// 指标名称（指标存储中的标量指标）
+    pub metric: String,
+    /// 检测方法：zscore / mad
+    #[serde(default)]
+    pub method: AnomalyMethod,
+    /// 评分绝对值达到该阈值时视为异常
+    #[serde(default = "default_anomaly_threshold")]
+    pub threshold: f64,
+    /// 滚动窗口内的历史数据点数
+    #[serde(default = "default_anomaly_window")]
+    pub window: usize,
+    /// 历史数据点少于该数量时不检测
+    #[serde(default = "default_anomaly_min_points")]
+    pub min_points: usize,
+    /// 告警级别
+    #[serde(default = "default_anomaly_severity")]
+    pub severity: AlertSeverity,
+}
+
+fn default_anomaly_threshold() -> f64 {
+    3.5
+}
+
+fn default_anomaly_window() -> usize {
+    48
+}
+
+fn default_anomaly_min_points() -> usize {
+    10
+}
+
+fn default_anomaly_severity() -> AlertSeverity {
+    AlertSeverity::Warning
+}
+
+/// Discord通知渠道配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordConfig {
+    /// 渠道名称
+    pub name: String,
+    /// Discord频道Webhook URL（包含令牌，支持 `enc:` 加密值）
+    pub webhook_url: SecretString,
+    /// 接收的最低告警级别
+    #[serde(default = "default_min_severity")]
+    pub min_severity: AlertSeverity,
+}
+
+/// Telegram通知渠道配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelegramConfig {
+    /// 渠道名称
+    pub name: String,
+    /// Bot令牌（支持 `enc:` 加密值）
+    pub bot_token: SecretString,
+    /// 会话ID
+    pub chat_id: String,
+    /// 接收的最低告警级别
+    #[serde(default = "default_min_severity")]
+    pub min_severity: AlertSeverity,
+}
+
+fn default_min_severity() -> AlertSeverity {
+    AlertSeverity::Info
+}
+
+/// Webhook通知渠道配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// 渠道名称
+    pub name: String,
+    /// 目标URL
+    pub url: String,
+    /// HMAC-SHA256签名密钥（可选，支持 `enc:` 加密值）
+    #[serde(default)]
+    pub secret: Option<SecretString>,
+    /// 最大重试次数
+    #[serde(default = "default_webhook_max_retries")]
+    pub max_retries: u32,
+    /// 首次重试等待时间（毫秒），之后每次翻倍
+    #[serde(default = "default_webhook_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// 请求超时时间（秒）
+    #[serde(default = "default_webhook_timeout_seconds")]
+    pub timeout_seconds: u64,
+    /// 接收的最低告警级别
+    #[serde(default = "default_min_severity")]
+    pub min_severity: AlertSeverity,
+}
+
+fn default_webhook_max_retries() -> u32 {
+    3
+}
+
+fn default_webhook_retry_backoff_ms() -> u64 {
+    1000
+}
+
+fn default_webhook_timeout_seconds() -> u64 {
+    10
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            staleness_multiplier: 3.0,
+            staleness_check_interval_seconds: 60,
+            default_cooldown_seconds: 3600,
+            rule_cooldowns: HashMap::new(),
+            escalation_threshold: 3,
+            webhooks: Vec::new(),
+            discord: Vec::new(),
+            telegram: Vec::new(),
+            rule_check_interval_seconds: 60,
+            rules: Vec::new(),
+            anomaly_check_interval_seconds: 300,
+            anomalies: Vec::new(),
+        }
+    }
+}
+
 impl AppConfig {
     /// 从配置文件加载配置
     /// 
@@ -93,32 +1722,60 @@ impl AppConfig {
                 .context("解析SERVER_PORT失败")?;
         }
         
-        // API密钥配置        
+        // API密钥配置（环境变量同样支持 `enc:` 加密值）
         if let Ok(api_key) = env::var("COINMARKETCAP_API_KEY") {
-            self.data_sources.coinmarketcap.api_key = Some(api_key);
+            self.data_sources.coinmarketcap.api_key = Some(SecretString::resolve(&api_key)?);
         }
         
         if let Ok(api_key) = env::var("GLASSNODE_API_KEY") {
-            self.data_sources.glassnode.api_key = Some(api_key);
+            self.data_sources.glassnode.api_key = Some(SecretString::resolve(&api_key)?);
         }
         
         if let Ok(api_key) = env::var("DEBANK_API_KEY") {
-            self.data_sources.debank.api_key = Some(api_key);
+            self.data_sources.debank.api_key = Some(SecretString::resolve(&api_key)?);
         }
         
         if let Ok(api_key) = env::var("DUNE_API_KEY") {
-            self.data_sources.dune.api_key = Some(api_key);
+            self.data_sources.dune.api_key = Some(SecretString::resolve(&api_key)?);
+        }
+        
+        if let Ok(api_key) = env::var("LUNARCRUSH_API_KEY") {
+            self.data_sources.lunarcrush.api_key = Some(SecretString::resolve(&api_key)?);
+        }
+        
+        if let Ok(api_key) = env::var("COINGLASS_API_KEY") {
+            self.data_sources.coinglass.api_key = Some(SecretString::resolve(&api_key)?);
+        }
+        
+        if let Ok(api_key) = env::var("OPENSEA_API_KEY") {
+            self.data_sources.opensea.api_key = Some(SecretString::resolve(&api_key)?);
+        }
+        
+        // 备份和归档的S3凭证
+        for s3 in [self.backup.s3.as_mut(), self.archive.s3.as_mut()].into_iter().flatten() {
+            if let Ok(key_id) = env::var("AWS_ACCESS_KEY_ID") {
+                s3.access_key_id = Some(SecretString::resolve(&key_id)?);
+            }
+            if let Ok(secret) = env::var("AWS_SECRET_ACCESS_KEY") {
+                s3.secret_access_key = Some(SecretString::resolve(&secret)?);
+            }
         }
         
         Ok(())
     }
-    
+}
+
+impl Default for AppConfig {
     /// 创建默认配置
-    pub fn default() -> Self {
+    fn default() -> Self {
         Self {
             server: ServerConfig {
                 host: "0.0.0.0".to_string(),
                 port: 3000,
+                static_dir: None,
+                unix_socket: None,
+                unix_socket_mode: default_unix_socket_mode(),
+                tls: TlsConfig::default(),
             },
             data_sources: DataSourcesConfig {
                 coinmarketcap: ApiConfig {
@@ -141,11 +1798,55 @@ impl AppConfig {
                     request_interval_ms: 1000,
                     timeout_seconds: 30,
                 },
+                lunarcrush: default_optional_api(),
+                coinglass: default_optional_api(),
+                opensea: default_optional_api(),
             },
             monitoring: MonitoringConfig {
                 coins: vec!["hyperliquid".to_string()],
                 update_interval_seconds: 14400, // 4小时
             },
+            backfill: BackfillConfig::default(),
+            data_quality: DataQualityConfig::default(),
+            price_divergence: PriceDivergenceConfig::default(),
+            inactive_coins: InactiveCoinsConfig::default(),
+            altcoin_season: AltcoinSeasonConfig::default(),
+            fear_greed: FearGreedConfig::default(),
+            etf_flows: EtfFlowsConfig::default(),
+            liquidations: LiquidationsConfig::default(),
+            liquidity: LiquidityConfig::default(),
+            nft: NftConfig::default(),
+            network: NetworkConfig::default(),
+            nodes: NodesConfig::default(),
+            changes: ChangeDetectionConfig::default(),
+            calendar: CalendarConfig::default(),
+            sentiment: SentimentConfig::default(),
+            formula_trial: FormulaTrialConfig::default(),
+            trends: TrendsConfig::default(),
+            sectors: SectorsConfig::default(),
+            ratios: RatiosConfig::default(),
+            derived_metrics: Vec::new(),
+            feature_flags: BTreeMap::new(),
+            alerts: AlertsConfig::default(),
+            storage: StorageConfig::default(),
+            cache: CacheConfig::default(),
+            import: ImportConfig::default(),
+            backup: BackupConfig::default(),
+            archive: ArchiveConfig::default(),
+            auth: AuthConfig::default(),
+            tenants: Vec::new(),
+            plugins: BTreeMap::new(),
+            custom_sources: Vec::new(),
+            forwarders: Vec::new(),
+            event_bus: EventBusConfig::default(),
+            ingest: Vec::new(),
+            refresh: RefreshConfig::default(),
+            websocket: WebSocketConfig::default(),
+            jobs: JobsConfig::default(),
+            replica: ReplicaConfig::default(),
+            grpc: GrpcConfig::default(),
+            mqtt: MqttConfig::default(),
+            logging: LoggingConfig::default(),
         }
     }
 } 
\ No newline at end of file
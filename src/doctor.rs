@@ -0,0 +1,698 @@
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::clients::{CoinglassClient, CoinMarketCapClient, GlassnodeClient, LunarCrushClient, NodeRpcClient, OpenSeaClient};
+use crate::config::AppConfig;
+use crate::secrets::SecretBox;
+
+/// 检查结果状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// 通过
+    Pass,
+    /// 可以运行，但存在需要注意的问题
+    Warn,
+    /// 失败，服务无法正常工作
+    Fail,
+    /// 未执行
+    Skip,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skip => "SKIP",
+        };
+        f.write_str(label)
+    }
+}
+
+/// 单项检查结果
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    /// 检查分类（配置/存储/数据源/插件）
+    pub category: &'static str,
+    /// 检查项名称
+    pub name: String,
+    /// 检查状态
+    pub status: CheckStatus,
+    /// 详细说明
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(category: &'static str, name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            category,
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// 自检选项
+#[derive(Debug, Clone)]
+pub struct DoctorOptions {
+    /// 配置文件路径
+    pub config_path: String,
+    /// 跳过需要访问网络的检查
+    pub offline: bool,
+    /// 警告也视为失败
+    pub strict: bool,
+}
+
+impl Default for DoctorOptions {
+    fn default() -> Self {
+        Self {
+            config_path: "config.toml".to_string(),
+            offline: false,
+            strict: false,
+        }
+    }
+}
+
+impl DoctorOptions {
+    /// 从命令行参数解析选项（不含子命令本身）
+    ///
+    /// 支持 `--config <path>`、`--offline` 和 `--strict`
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--config" => {
+                    options.config_path = args.next()
+                        .ok_or_else(|| anyhow::anyhow!("--config 需要指定配置文件路径"))?;
+                }
+                "--offline" => options.offline = true,
+                "--strict" => options.strict = true,
+                other => return Err(anyhow::anyhow!("未知参数: {}", other)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// 自检报告
+#[derive(Debug, Clone, Default)]
+pub struct DoctorReport {
+    /// 全部检查结果
+    pub checks: Vec<CheckResult>,
+}
+
+impl DoctorReport {
+    /// 统计指定状态的检查项数量
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    /// 进程退出码
+    ///
+    /// * `0` - 全部通过（非严格模式下允许警告）
+    /// * `1` - 存在失败项
+    /// * `2` - 严格模式下存在警告项
+    pub fn exit_code(&self, strict: bool) -> i32 {
+        if self.count(CheckStatus::Fail) > 0 {
+            1
+        } else if strict && self.count(CheckStatus::Warn) > 0 {
+            2
+        } else {
+            0
+        }
+    }
+
+    /// 渲染为文本表格
+    pub fn render(&self) -> String {
+        let headers = ["分类", "检查项", "状态", "详情"];
+        let rows: Vec<[String; 4]> = self.checks.iter()
+            .map(|c| [c.category.to_string(), c.name.clone(), c.status.to_string(), c.detail.clone()])
+            .collect();
+
+        let mut widths = headers.map(display_width);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(display_width(cell));
+            }
+        }
+
+        let separator = widths.iter()
+            .map(|w| "-".repeat(w + 2))
+            .collect::<Vec<_>>()
+            .join("+");
+        let format_row = |cells: [&str; 4]| {
+            cells.iter().zip(&widths)
+                .map(|(cell, width)| format!(" {}{} ", cell, " ".repeat(width - display_width(cell))))
+                .collect::<Vec<_>>()
+                .join("|")
+        };
+
+        let mut out = String::new();
+        out.push_str(&format_row(headers));
+        out.push('\n');
+        out.push_str(&separator);
+        out.push('\n');
+        for row in &rows {
+            out.push_str(&format_row([&row[0], &row[1], &row[2], &row[3]]));
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "\n共 {} 项：{} 通过，{} 警告，{} 失败，{} 跳过\n",
+            self.checks.len(),
+            self.count(CheckStatus::Pass),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail),
+            self.count(CheckStatus::Skip),
+        ));
+        out
+    }
+}
+
+/// 终端显示宽度（中日韩字符和表情按两列计算）
+fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if (c as u32) >= 0x1100 { 2 } else { 1 }).sum()
+}
+
+/// 运行全部自检
+///
+/// # 参数
+/// * `options` - 自检选项
+///
+/// # 返回
+/// * `DoctorReport` - 自检报告；配置无法加载时只包含配置检查结果
+pub async fn run(options: &DoctorOptions) -> DoctorReport {
+    let mut report = DoctorReport::default();
+
+    let config = match AppConfig::from_file(&options.config_path) {
+        Ok(config) => {
+            report.checks.push(CheckResult::new("配置", "配置文件", CheckStatus::Pass, options.config_path.clone()));
+            config
+        }
+        Err(e) => {
+            report.checks.push(CheckResult::new("配置", "配置文件", CheckStatus::Fail, format!("{:#}", e)));
+            return report;
+        }
+    };
+
+    report.checks.extend(check_config(&config));
+    report.checks.extend(check_storage(&config.storage.data_dir).await);
+    report.checks.extend(check_data_sources(&config, options.offline).await);
+    #[cfg(feature = "plugins")]
+    report.checks.extend(check_plugins(&config, options.offline).await);
+    report
+}
+
+/// 检查配置合理性
+///
+/// 不访问网络和磁盘，只检查配置值本身
+pub fn check_config(config: &AppConfig) -> Vec<CheckResult> {
+    let mut checks = Vec::new();
+    let mut push = |name: &str, result: Result<String, (CheckStatus, String)>| {
+        let (status, detail) = match result {
+            Ok(detail) => (CheckStatus::Pass, detail),
+            Err((status, detail)) => (status, detail),
+        };
+        checks.push(CheckResult::new("配置", name, status, detail));
+    };
+    let fail = |detail: String| Err((CheckStatus::Fail, detail));
+    let warn = |detail: String| Err((CheckStatus::Warn, detail));
+
+    push("服务器地址", if config.server.port == 0 {
+        fail("端口不能为0".to_string())
+    } else if config.server.host.trim().is_empty() {
+        fail("监听地址不能为空".to_string())
+    } else {
+        Ok(format!("{}:{}", config.server.host, config.server.port))
+    });
+
+    let tls = &config.server.tls;
+    push("HTTPS", if !tls.enabled {
+        Ok("未启用".to_string())
+    } else if !cfg!(feature = "tls") {
+        fail("程序未启用 tls 特性，请使用 --features tls 重新编译".to_string())
+    } else if let Some(path) = [&tls.cert_path, &tls.key_path].into_iter().find(|p| !std::path::Path::new(p.as_str()).is_file()) {
+        fail(format!("证书或私钥文件不存在: {}", path))
+    } else if tls.redirect_http_port == Some(config.server.port) {
+        fail("redirect_http_port 不能与 server.port 相同".to_string())
+    } else {
+        Ok(match tls.redirect_http_port {
+            Some(port) => format!("证书 {}，HTTP端口 {} 重定向到HTTPS", tls.cert_path, port),
+            None => format!("证书 {}", tls.cert_path),
+        })
+    });
+
+    push("监控配置", if config.monitoring.update_interval_seconds == 0 {
+        fail("update_interval_seconds 必须大于0".to_string())
+    } else if config.monitoring.coins.is_empty() {
+        warn("未配置监控币种".to_string())
+    } else {
+        Ok(format!("{} 个币种，每 {} 秒更新", config.monitoring.coins.len(), config.monitoring.update_interval_seconds))
+    });
+
+    let season = &config.altcoin_season;
+    push("山寨币季节阈值", if season.exit_threshold >= season.enter_threshold || season.enter_threshold > 100 {
+        fail(format!("需要 exit_threshold < enter_threshold <= 100，当前 {} / {}", season.exit_threshold, season.enter_threshold))
+    } else {
+        Ok(format!("进入 {}，退出 {}", season.enter_threshold, season.exit_threshold))
+    });
+
+    let etf = &config.etf_flows;
+    push("ETF资金流向", match etf.source_url.as_deref().filter(|url| !url.trim().is_empty()) {
+        None => Ok("未配置数据源，已禁用".to_string()),
+        Some(url) if !is_http_url(url) => fail(format!("source_url 无效: {}", url)),
+        Some(_) if etf.interval_seconds == 0 => fail("interval_seconds 必须大于0".to_string()),
+        Some(_) => Ok(format!("每 {} 秒轮询，单日净流出 {:.0} 美元告警", etf.interval_seconds, etf.large_outflow_usd)),
+    });
+
+    let liquidations = &config.liquidations;
+    push("合约爆仓", if config.data_sources.coinglass.api_key.as_ref().is_none_or(|k| k.is_empty()) {
+        Ok("未配置Coinglass密钥，已禁用".to_string())
+    } else if liquidations.symbols.is_empty() {
+        fail("symbols 不能为空".to_string())
+    } else if liquidations.interval_seconds == 0 {
+        fail("interval_seconds 必须大于0".to_string())
+    } else {
+        Ok(format!(
+            "每 {} 秒轮询 {} 个币种，一小时爆仓 {:.0} 美元告警",
+            liquidations.interval_seconds, liquidations.symbols.len(), liquidations.cascade_threshold_usd
+        ))
+    });
+
+    let nodes = &config.nodes;
+    let mut node_names = std::collections::HashSet::new();
+    push("自建节点", if nodes.nodes.is_empty() {
+        Ok("未配置节点，已禁用".to_string())
+    } else if nodes.interval_seconds == 0 {
+        fail("interval_seconds 必须大于0".to_string())
+    } else if let Some(node) = nodes.nodes.iter().find(|n| !node_names.insert(n.name.as_str())) {
+        fail(format!("节点名称重复: {}", node.name))
+    } else if let Some(node) = nodes.nodes.iter().find(|n| !is_http_url(&n.url) || n.reference_url.as_deref().is_some_and(|url| !url.is_empty() && !is_http_url(url))) {
+        fail(format!("节点 {} 的URL无效", node.name))
+    } else {
+        Ok(format!("每 {} 秒探测 {} 个节点", nodes.interval_seconds, nodes.nodes.len()))
+    });
+
+    let calendar = &config.calendar;
+    push("经济日历", if calendar.source_url.trim().is_empty() {
+        Ok("未配置数据源，已禁用".to_string())
+    } else if !is_http_url(&calendar.source_url) {
+        fail(format!("source_url 无效: {}", calendar.source_url))
+    } else if calendar.interval_seconds == 0 {
+        fail("interval_seconds 必须大于0".to_string())
+    } else {
+        Ok(format!("{} 条事件提醒", calendar.reminders.len()))
+    });
+
+    let alerts = &config.alerts;
+    push("告警策略", if alerts.staleness_multiplier < 1.0 {
+        fail(format!("staleness_multiplier 不能小于1: {}", alerts.staleness_multiplier))
+    } else if alerts.staleness_check_interval_seconds == 0
+        || alerts.rule_check_interval_seconds == 0
+        || alerts.anomaly_check_interval_seconds == 0
+        || config.price_divergence.check_interval_seconds == 0
+    {
+        fail("告警检查间隔必须大于0".to_string())
+    } else {
+        Ok(format!("{} 个通知渠道", alerts.webhooks.len() + alerts.discord.len() + alerts.telegram.len()))
+    });
+
+    let mut channel_errors = Vec::new();
+    for webhook in &alerts.webhooks {
+        if !is_http_url(&webhook.url) {
+            channel_errors.push(format!("Webhook[{}] URL无效", webhook.name));
+        }
+    }
+    for discord in &alerts.discord {
+        if !is_http_url(discord.webhook_url.expose()) {
+            channel_errors.push(format!("Discord[{}] URL无效", discord.name));
+        }
+    }
+    for telegram in &alerts.telegram {
+        if telegram.bot_token.is_empty() || telegram.chat_id.trim().is_empty() {
+            channel_errors.push(format!("Telegram[{}] 缺少bot_token或chat_id", telegram.name));
+        }
+    }
+    push("通知渠道", if channel_errors.is_empty() {
+        Ok("配置有效".to_string())
+    } else {
+        fail(channel_errors.join("；"))
+    });
+
+    let invalid_rules: Vec<String> = alerts.rules.iter()
+        .filter_map(|rule| rule.validate().err().map(|e| format!("{}: {}", rule.name, e)))
+        .collect();
+    push("告警规则", if invalid_rules.is_empty() {
+        Ok(format!("{} 条预置规则", alerts.rules.len()))
+    } else {
+        fail(invalid_rules.join("；"))
+    });
+
+    push("用户认证", if config.auth.token_ttl_hours == 0 {
+        fail("token_ttl_hours 必须大于0".to_string())
+    } else if config.auth.jwt_secret.as_ref().is_none_or(|s| s.is_empty()) {
+        warn("未设置 jwt_secret，重启后所有会话失效".to_string())
+    } else {
+        Ok(format!("会话有效期 {} 小时", config.auth.token_ttl_hours))
+    });
+
+    let archive = &config.archive;
+    push("指标归档", if !archive.enabled {
+        Ok("未启用".to_string())
+    } else if archive.hot_days == 0 || archive.hot_days >= config.storage.metrics_retention_days {
+        fail(format!(
+            "hot_days 须大于0且小于 metrics_retention_days，当前 {} / {}",
+            archive.hot_days, config.storage.metrics_retention_days,
+        ))
+    } else if archive.s3.as_ref().is_some_and(|s3| s3.access_key_id.is_none() || s3.secret_access_key.is_none()) {
+        fail("已配置S3但缺少访问密钥".to_string())
+    } else {
+        Ok(format!("保留最近 {} 天的热数据，每 {} 小时归档", archive.hot_days, archive.interval_hours))
+    });
+
+    push("日志配置", match crate::logging::env_filter(&config.logging) {
+        Ok(filter) => Ok(filter.to_string()),
+        Err(e) => fail(format!("{:#}", e)),
+    });
+
+    let unknown_flags: Vec<&str> = config.feature_flags.keys()
+        .map(String::as_str)
+        .filter(|name| crate::feature_flags::definition(name).is_none())
+        .collect();
+    push("功能开关", if !unknown_flags.is_empty() {
+        warn(format!("未知的功能开关将被忽略: {}", unknown_flags.join(", ")))
+    } else {
+        let enabled = crate::feature_flags::FLAGS.iter()
+            .filter(|flag| config.feature_flags.get(flag.name).copied().unwrap_or(flag.default))
+            .count();
+        Ok(format!("{}/{} 个开启", enabled, crate::feature_flags::FLAGS.len()))
+    });
+
+    push("主密钥", if SecretBox::from_env().is_some() {
+        Ok("已设置".to_string())
+    } else {
+        warn("未设置 EVERSCAN_MASTER_KEY，无法添加交易所账户".to_string())
+    });
+
+    checks
+}
+
+/// 错误描述：最外层上下文加根本原因，省略中间重复的网络错误链
+fn error_detail(e: &anyhow::Error) -> String {
+    if e.chain().count() > 1 {
+        format!("{}: {}", e, e.root_cause())
+    } else {
+        e.to_string()
+    }
+}
+
+/// 是否为 http(s) URL
+fn is_http_url(url: &str) -> bool {
+    reqwest::Url::parse(url)
+        .map(|u| matches!(u.scheme(), "http" | "https"))
+        .unwrap_or(false)
+}
+
+/// 本地状态文件（文件名，是否为JSONL）
+const STATE_FILES: &[(&str, bool)] = &[
+    ("alert_rules.json", false),
+    ("alert_state.json", false),
+    ("watches.json", false),
+    ("users.json", false),
+    ("portfolio.json", false),
+    ("exchange_accounts.json", false),
+    ("altcoin_season_history.json", false),
+    ("fear_greed_history.json", false),
+    ("price_history.json", false),
+    ("rank_history.json", false),
+    ("etf_flows.json", false),
+    ("liquidations.json", false),
+    ("dataset_changes.json", false),
+    ("economic_calendar.json", false),
+    ("google_trends.json", false),
+    ("feature_flags.json", false),
+    ("metrics.jsonl", true),
+    ("audit_log.jsonl", true),
+];
+
+/// 检查本地存储
+///
+/// 验证数据目录可写，以及已有状态文件能被解析
+pub async fn check_storage(data_dir: &str) -> Vec<CheckResult> {
+    let mut checks = Vec::new();
+    let dir = Path::new(data_dir);
+
+    let probe = dir.join(".doctor_probe");
+    let writable = async {
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(&probe, b"ok").await?;
+        tokio::fs::remove_file(&probe).await
+    }.await;
+    checks.push(match writable {
+        Ok(()) => CheckResult::new("存储", "数据目录", CheckStatus::Pass, format!("{} 可写", data_dir)),
+        Err(e) => CheckResult::new("存储", "数据目录", CheckStatus::Fail, format!("{} 不可写: {}", data_dir, e)),
+    });
+
+    for (name, jsonl) in STATE_FILES {
+        let path = dir.join(name);
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                checks.push(CheckResult::new("存储", *name, CheckStatus::Fail, format!("读取失败: {}", e)));
+                continue;
+            }
+        };
+
+        let result = if *jsonl {
+            let bad = content.lines()
+                .filter(|line| !line.trim().is_empty())
+                .filter(|line| serde_json::from_str::<serde_json::Value>(line).is_err())
+                .count();
+            if bad == 0 {
+                CheckResult::new("存储", *name, CheckStatus::Pass, format!("{} 条记录", content.lines().count()))
+            } else {
+                CheckResult::new("存储", *name, CheckStatus::Warn, format!("{} 行无法解析，将被忽略", bad))
+            }
+        } else {
+            match serde_json::from_str::<serde_json::Value>(&content) {
+                Ok(value) if *name == "exchange_accounts.json"
+                    && value.as_array().is_some_and(|a| !a.is_empty())
+                    && SecretBox::from_env().is_none() =>
+                {
+                    CheckResult::new("存储", *name, CheckStatus::Fail, "存在交易所账户但未设置主密钥，无法解密凭据")
+                }
+                Ok(_) => CheckResult::new("存储", *name, CheckStatus::Pass, "格式有效"),
+                Err(e) => CheckResult::new("存储", *name, CheckStatus::Fail, format!("JSON解析失败: {}", e)),
+            }
+        };
+        checks.push(result);
+    }
+
+    checks
+}
+
+/// 检查数据源连通性和密钥有效性
+pub async fn check_data_sources(config: &AppConfig, offline: bool) -> Vec<CheckResult> {
+    let mut checks = Vec::new();
+    let cmc = &config.data_sources.coinmarketcap;
+
+    if offline {
+        for name in ["CoinMarketCap", "贪婪恐惧指数"] {
+            checks.push(CheckResult::new("数据源", name, CheckStatus::Skip, "离线模式"));
+        }
+    } else {
+        let api_key = cmc.api_key.as_ref().map(|k| k.expose().to_string());
+        match CoinMarketCapClient::new(api_key.clone(), Duration::from_secs(cmc.timeout_seconds)) {
+            Ok(client) => {
+                let client = client.with_fear_greed_provider(config.fear_greed.provider);
+                checks.push(match api_key {
+                    None => CheckResult::new("数据源", "CoinMarketCap", CheckStatus::Warn, "未配置API密钥，山寨币季节指数不可用"),
+                    Some(_) => match client.get_key_info().await {
+                        Ok(info) => {
+                            let credits = &info["usage"]["current_month"]["credits_left"];
+                            CheckResult::new("数据源", "CoinMarketCap", CheckStatus::Pass, format!("密钥有效，本月剩余积分 {}", credits))
+                        }
+                        Err(e) => CheckResult::new("数据源", "CoinMarketCap", CheckStatus::Fail, error_detail(&e)),
+                    },
+                });
+                checks.push(match client.get_fear_greed_index().await {
+                    Ok(index) => CheckResult::new("数据源", "贪婪恐惧指数", CheckStatus::Pass, format!("{}（来源 {}）", index.value, index.source)),
+                    Err(e) => CheckResult::new("数据源", "贪婪恐惧指数", CheckStatus::Fail, error_detail(&e)),
+                });
+            }
+            Err(e) => checks.push(CheckResult::new("数据源", "CoinMarketCap", CheckStatus::Fail, format!("{:#}", e))),
+        }
+    }
+
+    // Glassnode为可选数据源，只在配置了密钥时检查
+    let glassnode = &config.data_sources.glassnode;
+    if let Some(api_key) = glassnode.api_key.as_ref().filter(|k| !k.is_empty()) {
+        if offline {
+            checks.push(CheckResult::new("数据源", "Glassnode", CheckStatus::Skip, "离线模式"));
+        } else {
+            let since = (chrono::Utc::now() - chrono::Duration::days(2)).timestamp();
+            let result = match GlassnodeClient::new(api_key.expose(), Duration::from_secs(glassnode.timeout_seconds)) {
+                Ok(client) => client.get_daily_series("distribution/balance_exchanges", "BTC", since).await,
+                Err(e) => Err(e),
+            };
+            checks.push(match result {
+                Ok(points) => CheckResult::new("数据源", "Glassnode", CheckStatus::Pass, format!("交易所余额 {} 个数据点", points.len())),
+                Err(e) => CheckResult::new("数据源", "Glassnode", CheckStatus::Fail, error_detail(&e)),
+            });
+        }
+    }
+
+    // LunarCrush为可选数据源，只在配置了密钥时检查
+    let lunarcrush = &config.data_sources.lunarcrush;
+    if let Some(api_key) = lunarcrush.api_key.as_ref().filter(|k| !k.is_empty()) {
+        if offline {
+            checks.push(CheckResult::new("数据源", "LunarCrush", CheckStatus::Skip, "离线模式"));
+        } else {
+            let result = match LunarCrushClient::new(api_key.expose(), Duration::from_secs(lunarcrush.timeout_seconds)) {
+                Ok(client) => client.get_coin_metrics("BTC").await,
+                Err(e) => Err(e),
+            };
+            checks.push(match result {
+                Ok(metrics) => CheckResult::new("数据源", "LunarCrush", CheckStatus::Pass, format!("BTC 正面情绪占比 {:?}", metrics.sentiment)),
+                Err(e) => CheckResult::new("数据源", "LunarCrush", CheckStatus::Fail, error_detail(&e)),
+            });
+        }
+    }
+
+    // Coinglass为可选数据源，只在配置了密钥时检查
+    let coinglass = &config.data_sources.coinglass;
+    if let Some(api_key) = coinglass.api_key.as_ref().filter(|k| !k.is_empty()) {
+        if offline {
+            checks.push(CheckResult::new("数据源", "Coinglass", CheckStatus::Skip, "离线模式"));
+        } else {
+            let result = match CoinglassClient::new(api_key.expose(), Duration::from_secs(coinglass.timeout_seconds)) {
+                Ok(client) => client.get_liquidation_history("BTC", &config.liquidations.exchanges, 1).await,
+                Err(e) => Err(e),
+            };
+            checks.push(match result {
+                Ok(hours) => CheckResult::new("数据源", "Coinglass", CheckStatus::Pass, format!("BTC 爆仓数据 {} 个小时", hours.len())),
+                Err(e) => CheckResult::new("数据源", "Coinglass", CheckStatus::Fail, error_detail(&e)),
+            });
+        }
+    }
+
+    // OpenSea为可选数据源，只在配置了密钥和合集时检查第一个合集
+    let opensea = &config.data_sources.opensea;
+    if let (Some(api_key), Some(slug)) = (opensea.api_key.as_ref().filter(|k| !k.is_empty()), config.nft.collections.first()) {
+        if offline {
+            checks.push(CheckResult::new("数据源", "OpenSea", CheckStatus::Skip, "离线模式"));
+        } else {
+            let result = match OpenSeaClient::new(api_key.expose(), Duration::from_secs(opensea.timeout_seconds)) {
+                Ok(client) => client.get_collection_stats(slug).await,
+                Err(e) => Err(e),
+            };
+            checks.push(match result {
+                Ok(stats) => CheckResult::new("数据源", "OpenSea", CheckStatus::Pass, format!("{} 地板价 {:?}", slug, stats.floor_price)),
+                Err(e) => CheckResult::new("数据源", "OpenSea", CheckStatus::Fail, error_detail(&e)),
+            });
+        }
+    }
+
+    // 自建节点只在配置后检查，探测每个节点的高度
+    if !config.nodes.nodes.is_empty() {
+        match NodeRpcClient::new(Duration::from_secs(10)) {
+            Ok(client) => {
+                for node in &config.nodes.nodes {
+                    let name = format!("节点 {}", node.name);
+                    if offline {
+                        checks.push(CheckResult::new("数据源", &name, CheckStatus::Skip, "离线模式"));
+                        continue;
+                    }
+                    checks.push(match client.probe(node.chain, &node.url).await {
+                        Ok(probe) => CheckResult::new(
+                            "数据源", &name, CheckStatus::Pass,
+                            format!("高度 {}，{:?} 个对等节点，延迟 {:.0} 毫秒", probe.height, probe.peers, probe.latency_ms),
+                        ),
+                        Err(e) => CheckResult::new("数据源", &name, CheckStatus::Fail, error_detail(&e)),
+                    });
+                }
+            }
+            Err(e) => checks.push(CheckResult::new("数据源", "自建节点", CheckStatus::Fail, error_detail(&e))),
+        }
+    }
+
+    // 以下数据源的客户端尚未启用，配置了密钥也不会被使用
+    let unused = [
+        ("Dune", &config.data_sources.dune),
+    ];
+    for (name, source) in unused {
+        if source.api_key.as_ref().is_some_and(|k| !k.is_empty()) {
+            checks.push(CheckResult::new("数据源", name, CheckStatus::Warn, "已配置API密钥，但该数据源尚未启用"));
+        }
+    }
+
+    checks
+}
+
+/// 检查插件：列出已链接的插件，并对配置了的插件运行API客户端健康检查
+#[cfg(feature = "plugins")]
+pub async fn check_plugins(config: &AppConfig, offline: bool) -> Vec<CheckResult> {
+    let mut checks = Vec::new();
+    for plugin in everscan_plugin::plugins() {
+        let (status, detail) = if plugin.api_version != everscan_plugin::PLUGIN_API_VERSION {
+            (CheckStatus::Fail, format!("接口版本 {} 与程序的 {} 不一致", plugin.api_version, everscan_plugin::PLUGIN_API_VERSION))
+        } else if config.plugins.contains_key(plugin.name) {
+            (CheckStatus::Pass, format!("v{} 已启用", plugin.version))
+        } else {
+            (CheckStatus::Skip, format!("v{} 已链接，未配置 [plugins.{}]", plugin.version, plugin.name))
+        };
+        checks.push(CheckResult::new("插件", plugin.display_name, status, detail));
+    }
+    for name in config.plugins.keys() {
+        if !everscan_plugin::plugins().iter().any(|p| p.name == name) {
+            checks.push(CheckResult::new("插件", name.clone(), CheckStatus::Fail, "已配置，但程序未链接该插件"));
+        }
+    }
+
+    for (plugin, clients) in crate::plugins::load_plugin_clients(&config.plugins) {
+        let clients = match clients {
+            Ok(clients) => clients,
+            Err(e) => {
+                checks.push(CheckResult::new("插件", plugin.display_name, CheckStatus::Fail, format!("{:#}", e)));
+                continue;
+            }
+        };
+        for client in clients {
+            let name = format!("{}/{}", plugin.display_name, client.name());
+            checks.push(if offline {
+                CheckResult::new("插件", name, CheckStatus::Skip, "离线模式")
+            } else {
+                match client.health_check().await {
+                    Ok(true) => CheckResult::new("插件", name, CheckStatus::Pass, "上游API可用"),
+                    Ok(false) => CheckResult::new("插件", name, CheckStatus::Fail, "上游API不可用"),
+                    Err(e) => CheckResult::new("插件", name, CheckStatus::Fail, error_detail(&e)),
+                }
+            });
+        }
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_config_flags_invalid_values() {
+        let mut config = AppConfig::default();
+        config.auth.jwt_secret = Some(crate::secrets::SecretString::new("secret"));
+        let report = DoctorReport { checks: check_config(&config) };
+        assert_eq!(report.count(CheckStatus::Fail), 0);
+
+        config.server.port = 0;
+        config.monitoring.update_interval_seconds = 0;
+        let report = DoctorReport { checks: check_config(&config) };
+        assert_eq!(report.count(CheckStatus::Fail), 2);
+        assert_eq!(report.exit_code(false), 1);
+        assert!(report.render().contains("FAIL"));
+    }
+}
@@ -0,0 +1,54 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use rdkafka::config::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use std::time::Duration;
+
+use super::{BusEvent, EventSink};
+
+/// 单条消息的投递超时
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Kafka事件发布端
+///
+/// 所有事件写入同一主题，以 [`BusEvent::key`] 作为消息键
+pub struct KafkaSink {
+    /// 生产者
+    producer: FutureProducer,
+    /// 主题
+    topic: String,
+}
+
+impl KafkaSink {
+    /// 创建Kafka发布端（连接在首次发送时建立）
+    ///
+    /// # 参数
+    /// * `brokers` - broker列表（逗号分隔）
+    /// * `topic` - 主题
+    pub fn new(brokers: &str, topic: &str) -> Result<Self> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("message.timeout.ms", DELIVERY_TIMEOUT.as_millis().to_string())
+            .create()
+            .context("创建Kafka生产者失败")?;
+        Ok(Self {
+            producer,
+            topic: topic.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaSink {
+    fn name(&self) -> &str {
+        "kafka"
+    }
+
+    async fn publish(&self, event: &BusEvent) -> Result<()> {
+        let payload = serde_json::to_vec(event)?;
+        let record = FutureRecord::to(&self.topic).key(event.key()).payload(&payload);
+        self.producer.send(record, DELIVERY_TIMEOUT).await
+            .map_err(|(e, _)| anyhow!("写入Kafka主题 {} 失败: {}", self.topic, e))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "nats")]
+pub mod nats;
+
+#[cfg(feature = "kafka")]
+pub use kafka::*;
+#[cfg(feature = "nats")]
+pub use nats::*;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::alerts::{Alert, Notifier};
+use crate::config::{EventBusConfig, EventBusKind};
+use crate::models::AggregatedMetric;
+use crate::storage::MetricStore;
+use crate::web::cache::DataCache;
+
+/// 事件总线上的事件
+///
+/// 序列化为带 `type` 字段的JSON对象，其余字段与HTTP接口中的对应对象相同
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BusEvent {
+    /// 任务产出的数据点
+    TaskResult(AggregatedMetric),
+    /// 已发送的告警（经过冷却策略）
+    Alert(Alert),
+    /// 缓存更新
+    CacheUpdate {
+        /// 数据集键（如 `market_data:bitcoin`、`fear_greed_index`）
+        key: String,
+        /// 更新时间
+        updated_at: DateTime<Utc>,
+    },
+}
+
+impl BusEvent {
+    /// 事件类型（NATS主题后缀）
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BusEvent::TaskResult(_) => "task_result",
+            BusEvent::Alert(_) => "alert",
+            BusEvent::CacheUpdate { .. } => "cache_update",
+        }
+    }
+
+    /// 消息键（Kafka按键分区，同一指标、规则或数据集的事件保持顺序）
+    pub fn key(&self) -> &str {
+        match self {
+            BusEvent::TaskResult(metric) => &metric.metric_name,
+            BusEvent::Alert(alert) => &alert.rule,
+            BusEvent::CacheUpdate { key, .. } => key,
+        }
+    }
+}
+
+/// 事件发布端
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// 发布端名称（用于日志）
+    fn name(&self) -> &str;
+
+    /// 发布一条事件
+    async fn publish(&self, event: &BusEvent) -> Result<()>;
+}
+
+/// 检查程序是否支持配置的事件总线类型
+///
+/// 在启动阶段调用，缺少编译特性属于配置错误，不应进入后台重试
+pub fn ensure_supported(config: &EventBusConfig) -> Result<()> {
+    match config.kind {
+        #[cfg(feature = "kafka")]
+        EventBusKind::Kafka => Ok(()),
+        #[cfg(not(feature = "kafka"))]
+        EventBusKind::Kafka => anyhow::bail!("事件总线配置为Kafka，但程序未启用 kafka 特性"),
+        #[cfg(feature = "nats")]
+        EventBusKind::Nats => Ok(()),
+        #[cfg(not(feature = "nats"))]
+        EventBusKind::Nats => anyhow::bail!("事件总线配置为NATS，但程序未启用 nats 特性"),
+    }
+}
+
+/// 按配置连接事件总线
+///
+/// # 参数
+/// * `config` - 事件总线配置
+///
+/// # 返回
+/// * `Result<Arc<dyn EventSink>>` - 发布端，连接失败或程序未启用对应特性时返回错误
+pub async fn connect(config: &EventBusConfig) -> Result<Arc<dyn EventSink>> {
+    match config.kind {
+        #[cfg(feature = "kafka")]
+        EventBusKind::Kafka => Ok(Arc::new(KafkaSink::new(&config.url, &config.topic)?)),
+        #[cfg(not(feature = "kafka"))]
+        EventBusKind::Kafka => anyhow::bail!("事件总线配置为Kafka，但程序未启用 kafka 特性"),
+        #[cfg(feature = "nats")]
+        EventBusKind::Nats => Ok(Arc::new(NatsSink::connect(&config.url, &config.topic).await?)),
+        #[cfg(not(feature = "nats"))]
+        EventBusKind::Nats => anyhow::bail!("事件总线配置为NATS，但程序未启用 nats 特性"),
+    }
+}
+
+/// 延迟连接的发布端
+///
+/// 启动时事件总线不可用不阻塞启动：连接成功前发布返回错误（由调用方记录警告），
+/// 连接后转发到实际的发布端
+#[derive(Default)]
+pub struct DeferredSink {
+    /// 实际的发布端
+    inner: tokio::sync::OnceCell<Arc<dyn EventSink>>,
+}
+
+impl DeferredSink {
+    /// 创建尚未连接的发布端
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 连接成功后设置实际的发布端（只有第一次设置生效）
+    pub fn connected(&self, sink: Arc<dyn EventSink>) {
+        let _ = self.inner.set(sink);
+    }
+}
+
+#[async_trait]
+impl EventSink for DeferredSink {
+    fn name(&self) -> &str {
+        self.inner.get().map_or("未连接", |sink| sink.name())
+    }
+
+    async fn publish(&self, event: &BusEvent) -> Result<()> {
+        match self.inner.get() {
+            Some(sink) => sink.publish(event).await,
+            None => anyhow::bail!("事件总线尚未连接"),
+        }
+    }
+}
+
+/// 事件总线发布器
+///
+/// 订阅缓存更新和指标存储新保存的数据点并发布到事件总线；发布失败只记录警告
+pub struct EventBus {
+    /// 发布端
+    sink: Arc<dyn EventSink>,
+    /// 是否发布任务结果
+    task_results: bool,
+    /// 是否发布缓存更新
+    cache_updates: bool,
+}
+
+impl EventBus {
+    /// 创建事件总线发布器
+    ///
+    /// # 参数
+    /// * `sink` - 发布端
+    /// * `config` - 事件总线配置
+    pub fn new(sink: Arc<dyn EventSink>, config: &EventBusConfig) -> Self {
+        Self {
+            sink,
+            task_results: config.task_results,
+            cache_updates: config.cache_updates,
+        }
+    }
+
+    /// 持续发布事件
+    ///
+    /// # 参数
+    /// * `cache` - 数据缓存
+    /// * `store` - 指标存储
+    pub async fn run(self, cache: Arc<DataCache>, store: Arc<MetricStore>) {
+        if !self.task_results && !self.cache_updates {
+            return;
+        }
+        info!("📨 启动事件总线发布: {}", self.sink.name());
+        let mut updates = cache.subscribe();
+        let mut saved = store.subscribe();
+        loop {
+            tokio::select! {
+                update = updates.recv(), if self.cache_updates => match update {
+                    Ok(update) => {
+                        // 对外仍按数据集逐条发布，保持消息格式不变
+                        for key in update.keys {
+                            self.publish(BusEvent::CacheUpdate { key, updated_at: update.updated_at }).await;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => warn!("⚠️ 事件总线落后，跳过 {} 条缓存更新", skipped),
+                    Err(RecvError::Closed) => break,
+                },
+                batch = saved.recv(), if self.task_results => match batch {
+                    Ok(batch) => {
+                        for metric in batch.iter() {
+                            self.publish(BusEvent::TaskResult(metric.clone())).await;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => warn!("⚠️ 事件总线落后，跳过 {} 批数据点", skipped),
+                    Err(RecvError::Closed) => break,
+                },
+            }
+        }
+    }
+
+    /// 发布一条事件，失败时记录警告
+    async fn publish(&self, event: BusEvent) {
+        if let Err(e) = self.sink.publish(&event).await {
+            warn!("⚠️ 事件总线 {} 发布 {} 事件失败: {}", self.sink.name(), event.kind(), e);
+        }
+    }
+}
+
+/// 把告警发布到事件总线的通知渠道
+pub struct EventBusNotifier {
+    /// 发布端
+    sink: Arc<dyn EventSink>,
+}
+
+impl EventBusNotifier {
+    /// 创建告警发布渠道
+    pub fn new(sink: Arc<dyn EventSink>) -> Self {
+        Self { sink }
+    }
+}
+
+#[async_trait]
+impl Notifier for EventBusNotifier {
+    fn name(&self) -> &str {
+        "event_bus"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        self.sink.publish(&BusEvent::Alert(alert.clone())).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::AlertSeverity;
+
+    #[test]
+    fn test_event_json() {
+        let alert = Alert::new("fear_greed:drop", AlertSeverity::Warning, "恐惧", "指数下跌");
+        let event = BusEvent::Alert(alert);
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "alert");
+        assert_eq!(json["rule"], "fear_greed:drop");
+        assert_eq!(event.key(), "fear_greed:drop");
+
+        let event = BusEvent::CacheUpdate { key: "market_data:bitcoin".to_string(), updated_at: Utc::now() };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "cache_update");
+        assert_eq!(json["key"], "market_data:bitcoin");
+        assert_eq!(event.kind(), "cache_update");
+    }
+}
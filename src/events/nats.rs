@@ -0,0 +1,43 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+use super::{BusEvent, EventSink};
+
+/// NATS事件发布端
+///
+/// 事件发布到 `<前缀>.<事件类型>`，如 `everscan.alert`，订阅方可用 `everscan.>` 接收全部事件
+pub struct NatsSink {
+    /// 客户端
+    client: async_nats::Client,
+    /// 主题前缀
+    prefix: String,
+}
+
+impl NatsSink {
+    /// 连接NATS服务器
+    ///
+    /// # 参数
+    /// * `url` - 服务器地址
+    /// * `prefix` - 主题前缀
+    pub async fn connect(url: &str, prefix: &str) -> Result<Self> {
+        let client = async_nats::connect(url).await
+            .with_context(|| format!("连接NATS服务器 {} 失败", url))?;
+        Ok(Self {
+            client,
+            prefix: prefix.trim_end_matches('.').to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn publish(&self, event: &BusEvent) -> Result<()> {
+        let payload = serde_json::to_vec(event)?;
+        self.client.publish(format!("{}.{}", self.prefix, event.kind()), payload.into()).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tracing::info;
+
+use crate::storage::JsonFileStore;
+
+/// 指标预测接口（`/api/metrics/{name}/forecast`，还需启用 forecast 编译特性）
+pub const FORECAST: &str = "forecast";
+
+/// LunarCrush社交情绪采集
+pub const SOCIAL_SENTIMENT: &str = "social_sentiment";
+
+/// 综合情绪分接口（`/api/sentiment`）
+pub const COMPOSITE_SENTIMENT: &str = "composite_sentiment";
+
+/// 功能开关定义
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FlagDefinition {
+    /// 开关名称
+    pub name: &'static str,
+    /// 说明
+    pub description: &'static str,
+    /// 未配置时是否开启
+    pub default: bool,
+}
+
+/// 所有功能开关
+pub const FLAGS: &[FlagDefinition] = &[
+    FlagDefinition { name: FORECAST, description: "指标短期统计外推接口", default: true },
+    FlagDefinition { name: SOCIAL_SENTIMENT, description: "LunarCrush社交情绪采集（还需配置API密钥）", default: true },
+    FlagDefinition { name: COMPOSITE_SENTIMENT, description: "综合情绪分接口", default: true },
+];
+
+/// 查找功能开关定义
+pub fn definition(name: &str) -> Option<&'static FlagDefinition> {
+    FLAGS.iter().find(|flag| flag.name == name)
+}
+
+/// 管理员设置的覆盖值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlagOverride {
+    /// 是否开启
+    pub enabled: bool,
+    /// 设置的管理员
+    pub set_by: String,
+    /// 设置时间
+    pub set_at: DateTime<Utc>,
+}
+
+/// 功能开关状态
+#[derive(Debug, Clone, Serialize)]
+pub struct FlagStatus {
+    /// 开关名称
+    pub name: &'static str,
+    /// 说明
+    pub description: &'static str,
+    /// 默认值
+    pub default: bool,
+    /// 配置文件中的值
+    pub configured: Option<bool>,
+    /// 管理员覆盖值
+    #[serde(rename = "override")]
+    pub override_value: Option<FlagOverride>,
+    /// 当前是否开启
+    pub enabled: bool,
+}
+
+/// 功能开关
+///
+/// 实验性子系统按开关启用：优先级为管理员覆盖值、`[feature_flags]` 配置、默认值。
+/// 覆盖值保存在数据目录中，重启后仍然生效，清除后恢复为配置值
+pub struct FeatureFlags {
+    /// 配置文件中的值
+    configured: BTreeMap<String, bool>,
+    /// 管理员覆盖值
+    overrides: RwLock<BTreeMap<String, FlagOverride>>,
+    /// 覆盖值的持久化存储（可选）
+    store: Option<JsonFileStore<BTreeMap<String, FlagOverride>>>,
+}
+
+impl FeatureFlags {
+    /// 创建功能开关（覆盖值不持久化）
+    ///
+    /// # 参数
+    /// * `configured` - 配置文件中的值
+    pub fn new(configured: BTreeMap<String, bool>) -> Self {
+        Self {
+            configured,
+            overrides: RwLock::new(BTreeMap::new()),
+            store: None,
+        }
+    }
+
+    /// 创建功能开关并加载覆盖值
+    ///
+    /// # 参数
+    /// * `configured` - 配置文件中的值
+    /// * `path` - 覆盖值文件路径
+    pub async fn open(configured: BTreeMap<String, bool>, path: impl Into<PathBuf>) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let overrides: BTreeMap<String, FlagOverride> = store.load().await?;
+        if !overrides.is_empty() {
+            info!("🚩 已加载 {} 个功能开关覆盖值", overrides.len());
+        }
+        Ok(Self {
+            configured,
+            overrides: RwLock::new(overrides),
+            store: Some(store),
+        })
+    }
+
+    /// 功能是否开启（未知的开关视为关闭）
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.status(name).is_some_and(|status| status.enabled)
+    }
+
+    /// 单个开关的状态
+    pub fn status(&self, name: &str) -> Option<FlagStatus> {
+        let flag = definition(name)?;
+        let configured = self.configured.get(name).copied();
+        let override_value = self.overrides.read().ok()?.get(name).cloned();
+        Some(FlagStatus {
+            name: flag.name,
+            description: flag.description,
+            default: flag.default,
+            configured,
+            enabled: override_value.as_ref().map(|o| o.enabled).or(configured).unwrap_or(flag.default),
+            override_value,
+        })
+    }
+
+    /// 所有开关的状态
+    pub fn list(&self) -> Vec<FlagStatus> {
+        FLAGS.iter().filter_map(|flag| self.status(flag.name)).collect()
+    }
+
+    /// 设置覆盖值
+    ///
+    /// # 参数
+    /// * `name` - 开关名称
+    /// * `enabled` - 是否开启
+    /// * `operator` - 操作的管理员
+    pub async fn set_override(&self, name: &str, enabled: bool, operator: &str) -> Result<FlagStatus> {
+        definition(name).ok_or_else(|| anyhow!("未知的功能开关: {}", name))?;
+        let overrides = {
+            let mut overrides = self.overrides.write().map_err(|_| anyhow!("功能开关状态不可用"))?;
+            overrides.insert(name.to_string(), FlagOverride {
+                enabled,
+                set_by: operator.to_string(),
+                set_at: Utc::now(),
+            });
+            overrides.clone()
+        };
+        self.persist(&overrides).await?;
+        info!("🚩 {} 将功能开关 {} 设置为 {}", operator, name, if enabled { "开启" } else { "关闭" });
+        self.status(name).ok_or_else(|| anyhow!("未知的功能开关: {}", name))
+    }
+
+    /// 清除覆盖值，恢复为配置值或默认值
+    ///
+    /// # 参数
+    /// * `name` - 开关名称
+    pub async fn clear_override(&self, name: &str) -> Result<FlagStatus> {
+        definition(name).ok_or_else(|| anyhow!("未知的功能开关: {}", name))?;
+        let overrides = {
+            let mut overrides = self.overrides.write().map_err(|_| anyhow!("功能开关状态不可用"))?;
+            overrides.remove(name);
+            overrides.clone()
+        };
+        self.persist(&overrides).await?;
+        self.status(name).ok_or_else(|| anyhow!("未知的功能开关: {}", name))
+    }
+
+    /// 保存覆盖值
+    async fn persist(&self, overrides: &BTreeMap<String, FlagOverride>) -> Result<()> {
+        match &self.store {
+            Some(store) => store.save(overrides).await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_override_takes_precedence_and_persists() {
+        let path = std::env::temp_dir().join(format!("everscan-flags-{}.json", uuid::Uuid::new_v4()));
+        let configured = BTreeMap::from([(FORECAST.to_string(), false)]);
+        let flags = FeatureFlags::open(configured.clone(), &path).await.unwrap();
+        assert!(!flags.is_enabled(FORECAST));
+        assert!(flags.is_enabled(SOCIAL_SENTIMENT));
+        assert!(!flags.is_enabled("unknown"));
+
+        flags.set_override(FORECAST, true, "admin").await.unwrap();
+        assert!(flags.is_enabled(FORECAST));
+        assert!(flags.set_override("unknown", true, "admin").await.is_err());
+
+        // 覆盖值在重启后仍然生效
+        let reopened = FeatureFlags::open(configured, &path).await.unwrap();
+        let status = reopened.status(FORECAST).unwrap();
+        assert!(status.enabled);
+        assert_eq!(status.override_value.unwrap().set_by, "admin");
+
+        let status = reopened.clear_override(FORECAST).await.unwrap();
+        assert!(!status.enabled && status.override_value.is_none());
+        let _ = std::fs::remove_file(&path);
+    }
+}
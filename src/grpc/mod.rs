@@ -0,0 +1,338 @@
+//! gRPC接口
+//!
+//! 服务定义见 `proto/everscan.proto`。消息类型在此手工定义（与proto文件保持一致），
+//! 服务端代码由 build.rs 生成，因此构建时不需要protoc
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+use tracing::{debug, info};
+
+use crate::storage::{parse_bucket, MetricQuery, MetricStore, QueryLimitError};
+use crate::web::cache::{CachedMarketData, DataCache};
+
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/everscan.v1.EverScan.rs"));
+}
+
+pub use generated::ever_scan_server::{EverScan, EverScanServer};
+
+/// 市场数据快照的默认推送间隔（与WebSocket相同）
+const DEFAULT_STREAM_INTERVAL_SECONDS: u64 = 30;
+
+/// 单个推送流的缓冲事件数（客户端读取过慢时丢弃更新事件）
+const STREAM_BUFFER: usize = 16;
+
+/// 币种市场数据
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MarketData {
+    #[prost(string, tag = "1")]
+    pub coin_id: String,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(string, tag = "3")]
+    pub symbol: String,
+    #[prost(double, tag = "4")]
+    pub current_price: f64,
+    #[prost(double, optional, tag = "5")]
+    pub volume_24h: Option<f64>,
+    #[prost(double, optional, tag = "6")]
+    pub price_change_24h: Option<f64>,
+    #[prost(double, optional, tag = "7")]
+    pub market_cap: Option<f64>,
+    #[prost(double, tag = "8")]
+    pub rsi: f64,
+    #[prost(int64, tag = "9")]
+    pub updated_at_ms: i64,
+    #[prost(string, tag = "10")]
+    pub source: String,
+}
+
+impl From<CachedMarketData> for MarketData {
+    fn from(data: CachedMarketData) -> Self {
+        Self {
+            coin_id: data.coin_id,
+            name: data.name,
+            symbol: data.symbol,
+            current_price: data.current_price,
+            volume_24h: data.volume_24h,
+            price_change_24h: data.price_change_24h,
+            market_cap: data.market_cap,
+            rsi: data.technical_indicators.rsi.value,
+            updated_at_ms: data.updated_at.timestamp_millis(),
+            source: data.source,
+        }
+    }
+}
+
+/// 市场数据请求
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MarketDataRequest {
+    /// 币种ID（为空时返回全部）
+    #[prost(string, repeated, tag = "1")]
+    pub coin_ids: Vec<String>,
+}
+
+/// 市场数据响应
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MarketDataResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub items: Vec<MarketData>,
+}
+
+/// 指标查询请求
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MetricQueryRequest {
+    #[prost(string, tag = "1")]
+    pub metric_name: String,
+    #[prost(string, optional, tag = "2")]
+    pub source: Option<String>,
+    #[prost(int64, optional, tag = "3")]
+    pub since_ms: Option<i64>,
+    #[prost(int64, optional, tag = "4")]
+    pub until_ms: Option<i64>,
+    /// 时间桶（如 `5m`、`1h`、`1d`）
+    #[prost(string, optional, tag = "5")]
+    pub bucket: Option<String>,
+    #[prost(map = "string, string", tag = "6")]
+    pub labels: HashMap<String, String>,
+    #[prost(uint32, optional, tag = "7")]
+    pub limit: Option<u32>,
+    #[prost(bool, tag = "8")]
+    pub include_invalid: bool,
+}
+
+/// 指标数据点
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MetricPoint {
+    #[prost(int64, tag = "1")]
+    pub timestamp_ms: i64,
+    #[prost(double, tag = "2")]
+    pub value: f64,
+    #[prost(string, optional, tag = "3")]
+    pub unit: Option<String>,
+    #[prost(string, tag = "4")]
+    pub source: String,
+    #[prost(map = "string, string", tag = "5")]
+    pub labels: HashMap<String, String>,
+}
+
+/// 指标查询响应
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MetricQueryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub points: Vec<MetricPoint>,
+}
+
+/// 更新推送请求
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StreamUpdatesRequest {
+    /// 市场数据快照的推送间隔（秒，0表示默认）
+    #[prost(uint32, tag = "1")]
+    pub interval_seconds: u32,
+}
+
+/// 更新事件
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct UpdateEvent {
+    /// 数据集键，定期快照为 `market_data`
+    #[prost(string, tag = "1")]
+    pub key: String,
+    #[prost(int64, tag = "2")]
+    pub updated_at_ms: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub market_data: Vec<MarketData>,
+}
+
+/// gRPC服务实现
+#[derive(Clone)]
+pub struct EverScanService {
+    /// 数据缓存
+    cache: Arc<DataCache>,
+    /// 指标存储
+    metrics: Arc<MetricStore>,
+}
+
+impl EverScanService {
+    /// 创建gRPC服务
+    ///
+    /// # 参数
+    /// * `cache` - 数据缓存
+    /// * `metrics` - 指标存储（查询受API查询限制约束）
+    pub fn new(cache: Arc<DataCache>, metrics: Arc<MetricStore>) -> Self {
+        Self { cache, metrics }
+    }
+
+    /// 启动gRPC服务器（持续运行）
+    ///
+    /// # 参数
+    /// * `addr` - 监听地址
+    pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        info!("📡 启动gRPC服务: {}", addr);
+        tonic::transport::Server::builder()
+            .add_service(EverScanServer::new(self))
+            .serve(addr)
+            .await?;
+        Ok(())
+    }
+
+    /// 当前全部市场数据
+    async fn market_snapshot(&self) -> Vec<MarketData> {
+        self.cache.get_all_market_data().await.into_iter().map(MarketData::from).collect()
+    }
+}
+
+/// 毫秒时间戳转换为时间
+fn from_millis(ms: Option<i64>) -> Result<Option<DateTime<Utc>>, String> {
+    ms.map(|ms| DateTime::from_timestamp_millis(ms).ok_or_else(|| format!("无效的时间戳: {}", ms)))
+        .transpose()
+}
+
+/// 查询限制错误转换为gRPC状态
+fn limit_status(err: QueryLimitError) -> Status {
+    match err {
+        QueryLimitError::Timeout { .. } => Status::deadline_exceeded(err.to_string()),
+        _ => Status::out_of_range(err.to_string()),
+    }
+}
+
+#[tonic::async_trait]
+impl EverScan for EverScanService {
+    async fn get_market_data(&self, request: Request<MarketDataRequest>) -> Result<Response<MarketDataResponse>, Status> {
+        let coin_ids = request.into_inner().coin_ids;
+        let items = if coin_ids.is_empty() {
+            self.market_snapshot().await
+        } else {
+            let mut items = Vec::new();
+            for coin_id in &coin_ids {
+                items.extend(self.cache.get_market_data(coin_id).await.map(MarketData::from));
+            }
+            items
+        };
+        Ok(Response::new(MarketDataResponse { items }))
+    }
+
+    async fn query_metrics(&self, request: Request<MetricQueryRequest>) -> Result<Response<MetricQueryResponse>, Status> {
+        let request = request.into_inner();
+        if request.metric_name.trim().is_empty() {
+            return Err(Status::invalid_argument("指标名称不能为空"));
+        }
+        let query = MetricQuery {
+            since: from_millis(request.since_ms).map_err(Status::invalid_argument)?,
+            until: from_millis(request.until_ms).map_err(Status::invalid_argument)?,
+            source: request.source,
+            limit: request.limit.map(|l| l as usize),
+            labels: request.labels.into_iter().collect(),
+            include_invalid: request.include_invalid,
+        };
+
+        let points = match request.bucket.as_deref() {
+            Some(raw) => {
+                let bucket = parse_bucket(raw).ok_or_else(|| Status::invalid_argument(format!("无效的时间桶: {}", raw)))?;
+                self.metrics.query_bucketed_checked(&request.metric_name, &query, bucket).await
+                    .map_err(limit_status)?
+                    .iter()
+                    .map(|(timestamp, value)| MetricPoint {
+                        timestamp_ms: timestamp.timestamp_millis(),
+                        value: *value,
+                        ..MetricPoint::default()
+                    })
+                    .collect()
+            }
+            None => self.metrics.query_checked(&request.metric_name, &query).await
+                .map_err(limit_status)?
+                .into_iter()
+                .filter_map(|metric| Some(MetricPoint {
+                    timestamp_ms: metric.timestamp.timestamp_millis(),
+                    value: metric.value.as_f64()?,
+                    unit: metric.value.unit().map(str::to_string),
+                    source: metric.source,
+                    labels: metric.labels.into_iter().collect(),
+                }))
+                .collect(),
+        };
+        Ok(Response::new(MetricQueryResponse { points }))
+    }
+
+    type StreamUpdatesStream = ReceiverStream<Result<UpdateEvent, Status>>;
+
+    async fn stream_updates(&self, request: Request<StreamUpdatesRequest>) -> Result<Response<Self::StreamUpdatesStream>, Status> {
+        let seconds = match request.into_inner().interval_seconds {
+            0 => DEFAULT_STREAM_INTERVAL_SECONDS,
+            seconds => seconds as u64,
+        };
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let service = self.clone();
+        tokio::spawn(async move {
+            info!("📡 新的gRPC更新订阅（快照间隔 {}s）", seconds);
+            let mut updates = service.cache.subscribe();
+            let mut interval = tokio::time::interval(Duration::from_secs(seconds));
+            loop {
+                let event = tokio::select! {
+                    _ = interval.tick() => UpdateEvent {
+                        key: "market_data".to_string(),
+                        updated_at_ms: Utc::now().timestamp_millis(),
+                        market_data: service.market_snapshot().await,
+                    },
+                    update = updates.recv() => match update {
+                        Ok(update) => {
+                            let mut market_data = Vec::new();
+                            for coin_id in update.coin_ids() {
+                                market_data.extend(service.cache.get_market_data(coin_id).await.map(MarketData::from));
+                            }
+                            // 合并的批量更新以数据集前缀作为键
+                            let key = match update.keys.as_slice() {
+                                [key] => key.clone(),
+                                keys if keys.iter().all(|key| key.starts_with("market_data:")) => "market_data".to_string(),
+                                keys => keys.join(","),
+                            };
+                            UpdateEvent {
+                                key,
+                                updated_at_ms: update.updated_at.timestamp_millis(),
+                                market_data,
+                            }
+                        }
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                if tx.send(Ok(event)).await.is_err() {
+                    break;
+                }
+            }
+            debug!("📡 gRPC更新订阅已断开");
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_query_metrics_and_market_data() {
+        let cache = Arc::new(DataCache::new());
+        cache.set_coin_data("bitcoin", serde_json::json!({
+            "current_price": 100000.0,
+            "symbol": "BTC",
+            "name": "Bitcoin",
+        })).await;
+        let service = EverScanService::new(cache, Arc::new(MetricStore::new(30)));
+
+        let response = service.get_market_data(Request::new(MarketDataRequest { coin_ids: vec!["bitcoin".to_string(), "unknown".to_string()] })).await.unwrap();
+        assert_eq!(response.into_inner().items.len(), 1);
+
+        let empty = MetricQueryRequest::default();
+        assert_eq!(service.query_metrics(Request::new(empty)).await.unwrap_err().code(), tonic::Code::InvalidArgument);
+        let bad_bucket = MetricQueryRequest { metric_name: "x".to_string(), bucket: Some("1y".to_string()), ..MetricQueryRequest::default() };
+        assert_eq!(service.query_metrics(Request::new(bad_bucket)).await.unwrap_err().code(), tonic::Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,331 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tracing::{info, debug};
+
+use crate::alerts::{Alert, AlertSeverity};
+use crate::clients::{AltcoinSeasonConstituents, AltcoinSeasonIndex};
+use crate::config::AltcoinSeasonConfig;
+use crate::storage::{JsonFileStore, Reloadable};
+use crate::web::cache::ALTCOIN_SEASON_KEY;
+
+/// 市场阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeasonRegime {
+    /// 山寨币季节
+    AltcoinSeason,
+    /// 非山寨币季节（比特币主导或平衡市场）
+    BitcoinSeason,
+}
+
+/// 阶段变化趋势
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeasonTrend {
+    /// 本次采集进入山寨币季节
+    Entering,
+    /// 本次采集退出山寨币季节
+    Exiting,
+    /// 阶段未变化
+    Steady,
+}
+
+/// 山寨币季节指数历史数据点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonPoint {
+    /// 采集时间
+    pub timestamp: DateTime<Utc>,
+    /// 指数值 (0-100)
+    pub value: u8,
+    /// 当前所处阶段
+    pub regime: SeasonRegime,
+    /// 相对上一个数据点的阶段变化
+    pub trend: SeasonTrend,
+}
+
+/// 山寨币季节指数成分币种快照
+///
+/// 与同一时间的指数值一起保存，用于审计和按成分币种重新计算历史读数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstituentSnapshot {
+    /// 采集时间
+    pub timestamp: DateTime<Utc>,
+    /// 当时发布的指数值
+    pub value: u8,
+    /// 计算明细（比特币基准、成分币种和排除的币种）
+    #[serde(flatten)]
+    pub breakdown: AltcoinSeasonConstituents,
+}
+
+/// 当前阶段状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonState {
+    /// 当前所处阶段
+    pub regime: SeasonRegime,
+    /// 最近一次采集的阶段变化
+    pub trend: SeasonTrend,
+    /// 进入当前阶段的时间
+    pub regime_since: DateTime<Utc>,
+}
+
+/// 按滞回阈值计算下一个阶段
+/// 
+/// 已处于山寨币季节时需跌至退出阈值才退出，否则需升至进入阈值才进入
+fn next_regime(previous: Option<SeasonRegime>, value: u8, config: &AltcoinSeasonConfig) -> SeasonRegime {
+    match previous {
+        Some(SeasonRegime::AltcoinSeason) if value > config.exit_threshold => SeasonRegime::AltcoinSeason,
+        Some(SeasonRegime::BitcoinSeason) | None if value >= config.enter_threshold => SeasonRegime::AltcoinSeason,
+        _ => SeasonRegime::BitcoinSeason,
+    }
+}
+
+/// 山寨币季节指数历史存储
+pub struct AltcoinSeasonHistory {
+    /// 按时间排序的数据点
+    points: RwLock<Vec<SeasonPoint>>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<Vec<SeasonPoint>>>,
+    /// 成分币种快照（按时间排序）
+    snapshots: RwLock<Vec<ConstituentSnapshot>>,
+    /// 成分币种快照持久化存储（可选）
+    snapshot_store: Option<JsonFileStore<Vec<ConstituentSnapshot>>>,
+    /// 阶段判定配置
+    config: AltcoinSeasonConfig,
+}
+
+impl AltcoinSeasonHistory {
+    /// 创建内存中的历史存储（不持久化）
+    pub fn new(config: AltcoinSeasonConfig) -> Self {
+        Self {
+            points: RwLock::new(Vec::new()),
+            store: None,
+            snapshots: RwLock::new(Vec::new()),
+            snapshot_store: None,
+            config,
+        }
+    }
+
+    /// 从状态文件加载历史存储
+    pub async fn open(path: impl Into<PathBuf>, config: AltcoinSeasonConfig) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let points: Vec<SeasonPoint> = store.load().await?;
+        info!("🌊 已加载 {} 条山寨币季节指数历史", points.len());
+        Ok(Self {
+            points: RwLock::new(points),
+            store: Some(store),
+            snapshots: RwLock::new(Vec::new()),
+            snapshot_store: None,
+            config,
+        })
+    }
+
+    /// 启用成分币种快照持久化，并加载已有快照
+    /// 
+    /// # 参数
+    /// * `path` - 快照文件路径
+    pub async fn with_constituents_file(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let snapshots: Vec<ConstituentSnapshot> = store.load().await?;
+        info!("🌊 已加载 {} 条山寨币季节成分币种快照", snapshots.len());
+        self.snapshots = RwLock::new(snapshots);
+        self.snapshot_store = Some(store);
+        Ok(self)
+    }
+
+    /// 记录一次采集结果
+    /// 
+    /// # 参数
+    /// * `value` - 指数值
+    /// * `timestamp` - 采集时间
+    /// 
+    /// # 返回
+    /// * `Result<SeasonPoint>` - 带阶段判定的数据点
+    pub async fn record(&self, value: u8, timestamp: DateTime<Utc>) -> Result<SeasonPoint> {
+        let mut points = self.points.write().await;
+        let previous = points.last().map(|p| p.regime);
+        let regime = next_regime(previous, value, &self.config);
+        let trend = match (previous, regime) {
+            (Some(SeasonRegime::BitcoinSeason), SeasonRegime::AltcoinSeason) => SeasonTrend::Entering,
+            (Some(SeasonRegime::AltcoinSeason), SeasonRegime::BitcoinSeason) => SeasonTrend::Exiting,
+            _ => SeasonTrend::Steady,
+        };
+        
+        let point = SeasonPoint { timestamp, value, regime, trend };
+        points.push(point.clone());
+        let excess = points.len().saturating_sub(self.config.max_history);
+        points.drain(..excess);
+        
+        if let Some(store) = &self.store {
+            store.save(&points).await?;
+        }
+        debug!("🌊 记录山寨币季节指数: {} ({:?}, {:?})", value, regime, trend);
+        Ok(point)
+    }
+
+    /// 查询历史数据点
+    /// 
+    /// # 参数
+    /// * `since` - 起始时间（可选）
+    /// * `limit` - 最多返回最近的条数
+    pub async fn points(&self, since: Option<DateTime<Utc>>, limit: usize) -> Vec<SeasonPoint> {
+        let points = self.points.read().await;
+        let matched: Vec<&SeasonPoint> = points.iter()
+            .filter(|p| since.is_none_or(|since| p.timestamp >= since))
+            .collect();
+        matched.iter().skip(matched.len().saturating_sub(limit)).map(|p| (*p).clone()).collect()
+    }
+
+    /// 记录成分币种快照
+    /// 
+    /// # 参数
+    /// * `value` - 同一次采集的指数值
+    /// * `breakdown` - 计算明细
+    /// * `timestamp` - 采集时间
+    pub async fn record_constituents(&self, value: u8, breakdown: AltcoinSeasonConstituents, timestamp: DateTime<Utc>) -> Result<()> {
+        let mut snapshots = self.snapshots.write().await;
+        snapshots.push(ConstituentSnapshot { timestamp, value, breakdown });
+        let excess = snapshots.len().saturating_sub(self.config.max_constituent_snapshots);
+        snapshots.drain(..excess);
+        
+        if let Some(store) = &self.snapshot_store {
+            store.save(&snapshots).await?;
+        }
+        Ok(())
+    }
+
+    /// 查询成分币种快照
+    /// 
+    /// # 参数
+    /// * `at` - 返回该时间及之前最近的一条（不指定时返回最新一条）
+    pub async fn constituents(&self, at: Option<DateTime<Utc>>) -> Option<ConstituentSnapshot> {
+        let snapshots = self.snapshots.read().await;
+        snapshots.iter()
+            .rev()
+            .find(|s| at.is_none_or(|at| s.timestamp <= at))
+            .cloned()
+    }
+
+    /// 当前阶段状态
+    pub async fn state(&self) -> Option<SeasonState> {
+        let points = self.points.read().await;
+        let latest = points.last()?;
+        let regime_since = points.iter()
+            .rev()
+            .take_while(|p| p.regime == latest.regime)
+            .last()
+            .map(|p| p.timestamp)
+            .unwrap_or(latest.timestamp);
+        Some(SeasonState {
+            regime: latest.regime,
+            trend: latest.trend,
+            regime_since,
+        })
+    }
+}
+
+impl SeasonPoint {
+    /// 阶段切换告警
+    /// 
+    /// # 参数
+    /// * `index` - 本次采集的指数数据
+    /// 
+    /// # 返回
+    /// * `Option<Alert>` - 阶段未变化时为None
+    pub fn regime_alert(&self, index: &AltcoinSeasonIndex) -> Option<Alert> {
+        let title = match self.trend {
+            SeasonTrend::Entering => "进入山寨币季节",
+            SeasonTrend::Exiting => "退出山寨币季节",
+            SeasonTrend::Steady => return None,
+        };
+        Some(Alert::new(
+            "altcoin_season:regime",
+            AlertSeverity::Warning,
+            title,
+            format!("山寨币季节指数 {}（{}）", index.value, index.classification_zh),
+        ).metadata(serde_json::json!({
+            "value": index.value,
+            "regime": self.regime,
+            "trend": self.trend,
+            "source_key": ALTCOIN_SEASON_KEY,
+        })))
+    }
+}
+
+#[async_trait]
+impl Reloadable for AltcoinSeasonHistory {
+    fn name(&self) -> &str {
+        "altcoin_season_history"
+    }
+
+    async fn reload(&self) -> Result<()> {
+        if let Some(store) = &self.store {
+            *self.points.write().await = store.load().await?;
+        }
+        if let Some(store) = &self.snapshot_store {
+            *self.snapshots.write().await = store.load().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_regime_uses_hysteresis() {
+        let history = AltcoinSeasonHistory::new(AltcoinSeasonConfig::default());
+        let now = Utc::now();
+        
+        let mut trends = Vec::new();
+        for value in [50, 74, 75, 70, 66, 65, 70, 80] {
+            trends.push(history.record(value, now).await.unwrap().trend);
+        }
+        assert_eq!(trends, vec![
+            SeasonTrend::Steady,
+            SeasonTrend::Steady,
+            SeasonTrend::Entering,
+            SeasonTrend::Steady,
+            SeasonTrend::Steady,
+            SeasonTrend::Exiting,
+            SeasonTrend::Steady,
+            SeasonTrend::Entering,
+        ]);
+        assert_eq!(history.state().await.unwrap().regime, SeasonRegime::AltcoinSeason);
+        assert_eq!(history.points(None, 3).await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_constituent_snapshots() {
+        use crate::clients::AltcoinSeasonConstituent;
+
+        let history = AltcoinSeasonHistory::new(AltcoinSeasonConfig::default());
+        let now = Utc::now();
+        let constituent = |symbol: &str, change_24h: f64| AltcoinSeasonConstituent {
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            cmc_rank: None,
+            change_24h,
+            change_90d: None,
+            outperforming: change_24h > 1.0,
+        };
+        let breakdown = AltcoinSeasonConstituents {
+            btc_change_24h: 1.0,
+            btc_change_90d: None,
+            constituents: vec![constituent("ETH", 3.0), constituent("SOL", -2.0), constituent("XRP", 1.5)],
+            excluded: Vec::new(),
+        };
+        history.record_constituents(67, breakdown.clone(), now - chrono::Duration::hours(1)).await.unwrap();
+        history.record_constituents(33, AltcoinSeasonConstituents::default(), now).await.unwrap();
+
+        let earlier = history.constituents(Some(now - chrono::Duration::minutes(30))).await.unwrap();
+        assert_eq!(earlier.value, 67);
+        assert_eq!(earlier.breakdown.index_value(), earlier.value);
+        assert_eq!(history.constituents(None).await.unwrap().value, 33);
+        assert!(history.constituents(Some(now - chrono::Duration::hours(2))).await.is_none());
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::clients::{CoinGeckoClient, CoinGeckoHttpError};
+use crate::config::BackfillConfig;
+use crate::history::PriceHistory;
+
+/// 日线价格回填
+///
+/// 首次启动时价格历史为空，RSI、布林带和均线要逐日积累才准确；
+/// 该例程从CoinGecko获取监控币种过去N天的每日价格写入价格历史，已有足够天数的币种跳过
+pub struct PriceBackfill {
+    /// CoinGecko客户端
+    client: CoinGeckoClient,
+    /// 币种日线价格历史
+    prices: Arc<PriceHistory>,
+    /// 回填配置
+    config: BackfillConfig,
+    /// CoinGecko返回4xx（ID不存在或已下架）的币种，之后不再请求
+    rejected: Mutex<BTreeSet<String>>,
+}
+
+/// 一次回填的结果
+#[derive(Debug, Clone, Default)]
+pub struct BackfillReport {
+    /// 新增的日线总数
+    pub added: usize,
+    /// 因CoinGecko拒绝请求而跳过的币种（CoinGecko ID）
+    pub rejected: Vec<String>,
+}
+
+impl PriceBackfill {
+    /// 创建日线价格回填
+    ///
+    /// # 参数
+    /// * `client` - CoinGecko客户端
+    /// * `prices` - 币种日线价格历史
+    /// * `config` - 回填配置
+    pub fn new(client: CoinGeckoClient, prices: Arc<PriceHistory>, config: BackfillConfig) -> Self {
+        Self { client, prices, config, rejected: Mutex::new(BTreeSet::new()) }
+    }
+
+    /// 回填监控币种的日线价格
+    ///
+    /// 单个币种失败时记录警告并继续；已回填的币种再次执行时跳过，可以重复调用以重试失败的币种。
+    /// CoinGecko返回4xx（限流除外）的币种重试也不会成功，记录后跳过，不影响其他币种就绪
+    ///
+    /// # 参数
+    /// * `coingecko_ids` - 监控币种的CoinGecko ID
+    ///
+    /// # 返回
+    /// * `Result<BackfillReport>` - 回填结果，有币种因网络、限流或服务端错误失败时返回错误
+    pub async fn run(&self, coingecko_ids: &[String]) -> Result<BackfillReport> {
+        let mut total = 0;
+        let mut failed = 0;
+        for coingecko_id in coingecko_ids {
+            let coin_id = self.config.coin_id(coingecko_id);
+            if self.is_rejected(coingecko_id) || self.prices.day_count(coin_id).await >= self.config.days as usize {
+                continue;
+            }
+
+            let history = match self.client.get_coin_history(coingecko_id, self.config.days).await {
+                Ok(history) => history,
+                Err(e) if e.downcast_ref::<CoinGeckoHttpError>().is_some_and(CoinGeckoHttpError::is_permanent) => {
+                    warn!("⚠️ CoinGecko拒绝回填 {} 的日线价格，之后不再重试: {}", coin_id, e);
+                    if let Ok(mut rejected) = self.rejected.lock() {
+                        rejected.insert(coingecko_id.clone());
+                    }
+                    continue;
+                }
+                Err(e) => {
+                    warn!("⚠️ 回填 {} 日线价格失败: {}", coin_id, e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            // 当天尚未收盘，由实时采集记录
+            let today = Utc::now().date_naive();
+            let closes = history.into_iter()
+                .map(|(timestamp, price)| (timestamp.date_naive(), price))
+                .filter(|(date, _)| *date < today);
+            match self.prices.backfill(coin_id, closes).await {
+                Ok(added) => {
+                    info!("📅 已回填 {} 的 {} 天日线价格", coin_id, added);
+                    total += added;
+                }
+                Err(e) => {
+                    warn!("⚠️ 保存 {} 的回填价格失败: {}", coin_id, e);
+                    failed += 1;
+                }
+            }
+        }
+        if failed > 0 {
+            anyhow::bail!("{} 个币种回填失败（本次新增 {} 天日线）", failed, total);
+        }
+        let rejected: Vec<String> = self.rejected.lock().map(|r| r.iter().cloned().collect()).unwrap_or_default();
+        if !rejected.is_empty() {
+            warn!("⚠️ 以下币种未能回填日线价格，请检查CoinGecko ID: {}", rejected.join(", "));
+        }
+        Ok(BackfillReport { added: total, rejected })
+    }
+
+    /// 币种是否已被CoinGecko拒绝
+    fn is_rejected(&self, coingecko_id: &str) -> bool {
+        self.rejected.lock().is_ok_and(|rejected| rejected.contains(coingecko_id))
+    }
+}
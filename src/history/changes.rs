@@ -0,0 +1,458 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tracing::{info, debug};
+use uuid::Uuid;
+
+use crate::alerts::{Alert, AlertSeverity};
+use crate::clients::{CmcListing, ListedCoin};
+use crate::config::ChangeDetectionConfig;
+use crate::storage::{JsonFileStore, Reloadable};
+use crate::web::cache::CMC_LISTINGS_KEY;
+
+/// 变化内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// 排名变化
+    RankChanged {
+        /// 原排名
+        from: u64,
+        /// 新排名
+        to: u64,
+    },
+    /// 流通量变化
+    SupplyChanged {
+        /// 原流通量
+        from: f64,
+        /// 新流通量
+        to: f64,
+        /// 变化百分比
+        change_pct: f64,
+    },
+    /// 标签变化
+    TagsChanged {
+        /// 新增的标签
+        added: Vec<String>,
+        /// 移除的标签
+        removed: Vec<String>,
+    },
+    /// 进入列表（如进入市值前100）
+    Entered {
+        /// 排名
+        rank: Option<u64>,
+    },
+    /// 移出列表
+    Exited {
+        /// 移出前的排名
+        rank: Option<u64>,
+    },
+    /// 数据源新收录的币种
+    NewListing {
+        /// 数据源（如 `coingecko`）
+        source: String,
+        /// 数据源中的币种ID
+        coin_id: String,
+        /// 首次发现时的美元价格
+        price: Option<f64>,
+        /// 首次发现时的市值（美元）
+        market_cap: Option<f64>,
+    },
+}
+
+impl ChangeKind {
+    /// 变化类型名称（与序列化的 `type` 字段一致）
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChangeKind::RankChanged { .. } => "rank_changed",
+            ChangeKind::SupplyChanged { .. } => "supply_changed",
+            ChangeKind::TagsChanged { .. } => "tags_changed",
+            ChangeKind::Entered { .. } => "entered",
+            ChangeKind::Exited { .. } => "exited",
+            ChangeKind::NewListing { .. } => "new_listing",
+        }
+    }
+}
+
+/// 数据集变化事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    /// 唯一标识符
+    pub id: Uuid,
+    /// 数据集键
+    pub dataset: String,
+    /// 币种符号
+    pub symbol: String,
+    /// 币种名称
+    pub name: String,
+    /// 变化内容
+    #[serde(flatten)]
+    pub change: ChangeKind,
+    /// 检测时间
+    pub detected_at: DateTime<Utc>,
+}
+
+impl ChangeEvent {
+    /// 生成变化事件的告警
+    ///
+    /// # 参数
+    /// * `severity` - 告警级别
+    pub fn to_alert(&self, severity: AlertSeverity) -> Alert {
+        let (title, message) = match &self.change {
+            ChangeKind::RankChanged { from, to } => (
+                format!("{} CMC排名 {} → {}", self.symbol, from, to),
+                format!("{}（{}）的CMC排名从第 {} 名{}到第 {} 名", self.name, self.symbol, from, if to < from { "上升" } else { "下降" }, to),
+            ),
+            ChangeKind::SupplyChanged { from, to, change_pct } => (
+                format!("{} 流通量变化 {:+.2}%", self.symbol, change_pct),
+                format!("{}（{}）的流通量从 {:.0} 变为 {:.0}", self.name, self.symbol, from, to),
+            ),
+            ChangeKind::TagsChanged { added, removed } => (
+                format!("{} 标签变化", self.symbol),
+                format!("{}（{}）新增标签: {}；移除标签: {}", self.name, self.symbol,
+                    if added.is_empty() { "-".to_string() } else { added.join(", ") },
+                    if removed.is_empty() { "-".to_string() } else { removed.join(", ") }),
+            ),
+            ChangeKind::Entered { rank } => (
+                format!("{} 进入CMC市值前100", self.symbol),
+                format!("{}（{}）进入列表，排名 {}", self.name, self.symbol, rank.map_or("-".to_string(), |r| r.to_string())),
+            ),
+            ChangeKind::Exited { rank } => (
+                format!("{} 跌出CMC市值前100", self.symbol),
+                format!("{}（{}）移出列表，此前排名 {}", self.name, self.symbol, rank.map_or("-".to_string(), |r| r.to_string())),
+            ),
+            ChangeKind::NewListing { source, coin_id, price, market_cap } => (
+                format!("{} 新收录 {}", source, self.symbol),
+                format!("{}（{}，ID {}）首次出现，价格 {}，市值 {}", self.name, self.symbol, coin_id,
+                    price.map_or("-".to_string(), |p| format!("${}", p)),
+                    market_cap.map_or("-".to_string(), |m| format!("${:.0}", m))),
+            ),
+        };
+        Alert::new(
+            format!("changes:{}:{}", self.change.name(), self.symbol.to_lowercase()),
+            severity,
+            title,
+            message,
+        ).metadata(serde_json::json!({ "event": self }))
+    }
+}
+
+/// 变化事件查询条件
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChangeQuery {
+    /// 币种符号
+    pub symbol: Option<String>,
+    /// 变化类型（如 `rank_changed`）
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    /// 起始时间
+    pub since: Option<DateTime<Utc>>,
+    /// 最多返回条数（默认100）
+    pub limit: Option<usize>,
+}
+
+/// 持久化的变化检测状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ChangeState {
+    /// 上一次采集的上市信息（CMC币种ID -> 上市信息）
+    listings: BTreeMap<u64, CmcListing>,
+    /// 变化事件（最新的在末尾）
+    events: VecDeque<ChangeEvent>,
+    /// 各数据源已知的币种ID（数据源 -> 币种ID）
+    #[serde(default)]
+    known_ids: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// 数据集变化历史
+///
+/// 比较相邻两次采集的CMC上市信息，记录排名、流通量、标签变化以及进入/移出列表的事件；
+/// 跟踪各数据源已知的币种ID，记录新收录的币种。首次采集只作为比较基准，不产生事件
+pub struct ChangeHistory {
+    /// 变化检测状态
+    state: RwLock<ChangeState>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<ChangeState>>,
+    /// 配置
+    config: ChangeDetectionConfig,
+}
+
+impl ChangeHistory {
+    /// 创建内存中的变化历史（不持久化）
+    pub fn new(config: ChangeDetectionConfig) -> Self {
+        Self {
+            state: RwLock::new(ChangeState::default()),
+            store: None,
+            config,
+        }
+    }
+
+    /// 从状态文件加载变化历史
+    pub async fn open(path: impl Into<PathBuf>, config: ChangeDetectionConfig) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let state: ChangeState = store.load().await?;
+        info!("🔀 已加载 {} 条数据集变化事件", state.events.len());
+        Ok(Self {
+            state: RwLock::new(state),
+            store: Some(store),
+            config,
+        })
+    }
+
+    /// 变化检测配置
+    pub fn config(&self) -> &ChangeDetectionConfig {
+        &self.config
+    }
+
+    /// 记录一次采集的上市信息，与上一次比较后生成变化事件
+    ///
+    /// # 参数
+    /// * `listings` - 本次采集的上市信息
+    /// * `now` - 采集时间
+    ///
+    /// # 返回
+    /// * `Result<Vec<ChangeEvent>>` - 本次检测到的变化事件
+    pub async fn record_listings(&self, listings: &[CmcListing], now: DateTime<Utc>) -> Result<Vec<ChangeEvent>> {
+        let current: BTreeMap<u64, CmcListing> = listings.iter().map(|l| (l.id, l.clone())).collect();
+        let mut state = self.state.write().await;
+
+        let events: Vec<ChangeEvent> = if state.listings.is_empty() {
+            Vec::new()
+        } else {
+            diff_listings(&state.listings, &current, &self.config).into_iter()
+                .map(|(listing, change)| ChangeEvent {
+                    id: Uuid::new_v4(),
+                    dataset: CMC_LISTINGS_KEY.to_string(),
+                    symbol: listing.symbol.clone(),
+                    name: listing.name.clone(),
+                    change,
+                    detected_at: now,
+                })
+                .collect()
+        };
+
+        state.listings = current;
+        state.events.extend(events.iter().cloned());
+        while state.events.len() > self.config.max_events {
+            state.events.pop_front();
+        }
+        if let Some(store) = &self.store {
+            store.save(&state).await?;
+        }
+        debug!("🔀 比较 {} 个币种的上市信息，检测到 {} 个变化", listings.len(), events.len());
+        Ok(events)
+    }
+
+    /// 找出数据源中尚未见过的币种ID
+    ///
+    /// # 参数
+    /// * `source` - 数据源
+    /// * `ids` - 本次获取的币种ID
+    ///
+    /// # 返回
+    /// * `Option<Vec<String>>` - 未见过的币种ID，该数据源还没有比较基准时为None
+    pub async fn unknown_ids<'a>(&self, source: &str, ids: impl IntoIterator<Item = &'a String>) -> Option<Vec<String>> {
+        let state = self.state.read().await;
+        let known = state.known_ids.get(source)?;
+        Some(ids.into_iter().filter(|id| !known.contains(*id)).cloned().collect())
+    }
+
+    /// 记录数据源收录的币种，对未见过的币种生成 `new_listing` 事件
+    ///
+    /// 已知ID只增不减，币种从数据源中暂时消失后再出现不会重复记录
+    ///
+    /// # 参数
+    /// * `source` - 数据源
+    /// * `coins` - 本次获取的币种（新币种应附带首次价格和市值）
+    /// * `now` - 获取时间
+    ///
+    /// # 返回
+    /// * `Result<Vec<ChangeEvent>>` - 新收录币种的事件，首次记录该数据源时为空
+    pub async fn record_coin_ids(&self, source: &str, coins: &[ListedCoin], now: DateTime<Utc>) -> Result<Vec<ChangeEvent>> {
+        let mut state = self.state.write().await;
+        let baseline = !state.known_ids.contains_key(source);
+        let known = state.known_ids.entry(source.to_string()).or_default();
+
+        let mut events = Vec::new();
+        for coin in coins {
+            if !known.insert(coin.id.clone()) || baseline {
+                continue;
+            }
+            events.push(ChangeEvent {
+                id: Uuid::new_v4(),
+                dataset: format!("listings:{}", source),
+                symbol: coin.symbol.clone(),
+                name: coin.name.clone(),
+                change: ChangeKind::NewListing {
+                    source: source.to_string(),
+                    coin_id: coin.id.clone(),
+                    price: coin.price,
+                    market_cap: coin.market_cap,
+                },
+                detected_at: now,
+            });
+        }
+        let known_count = known.len();
+
+        state.events.extend(events.iter().cloned());
+        while state.events.len() > self.config.max_events {
+            state.events.pop_front();
+        }
+        if let Some(store) = &self.store {
+            store.save(&state).await?;
+        }
+        debug!("🆕 {} 已知 {} 个币种，新收录 {} 个", source, known_count, events.len());
+        Ok(events)
+    }
+
+    /// 查询变化事件
+    ///
+    /// # 返回
+    /// * `Vec<ChangeEvent>` - 符合条件的事件（最新的在前）
+    pub async fn query(&self, query: &ChangeQuery) -> Vec<ChangeEvent> {
+        self.state.read().await
+            .events
+            .iter()
+            .rev()
+            .filter(|e| query.symbol.as_ref().is_none_or(|symbol| e.symbol.eq_ignore_ascii_case(symbol)))
+            .filter(|e| query.kind.as_ref().is_none_or(|kind| e.change.name() == kind))
+            .filter(|e| query.since.is_none_or(|since| e.detected_at >= since))
+            .take(query.limit.unwrap_or(100))
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Reloadable for ChangeHistory {
+    fn name(&self) -> &str {
+        "changes"
+    }
+
+    async fn reload(&self) -> Result<()> {
+        if let Some(store) = &self.store {
+            *self.state.write().await = store.load().await?;
+        }
+        Ok(())
+    }
+}
+
+/// 比较两次采集的上市信息
+///
+/// # 返回
+/// * `Vec<(&CmcListing, ChangeKind)>` - 变化的币种（移出列表时为上一次的信息）及变化内容
+fn diff_listings<'a>(
+    previous: &'a BTreeMap<u64, CmcListing>,
+    current: &'a BTreeMap<u64, CmcListing>,
+    config: &ChangeDetectionConfig,
+) -> Vec<(&'a CmcListing, ChangeKind)> {
+    let mut changes = Vec::new();
+    for (id, now) in current {
+        let Some(before) = previous.get(id) else {
+            changes.push((now, ChangeKind::Entered { rank: now.cmc_rank }));
+            continue;
+        };
+        if let (Some(from), Some(to)) = (before.cmc_rank, now.cmc_rank) {
+            if from.abs_diff(to) >= config.min_rank_change.max(1) {
+                changes.push((now, ChangeKind::RankChanged { from, to }));
+            }
+        }
+        if let (Some(from), Some(to)) = (before.circulating_supply, now.circulating_supply) {
+            let change_pct = if from > 0.0 { (to - from) / from * 100.0 } else { 0.0 };
+            if change_pct.is_finite() && change_pct != 0.0 && change_pct.abs() >= config.min_supply_change_pct {
+                changes.push((now, ChangeKind::SupplyChanged { from, to, change_pct }));
+            }
+        }
+        let before_tags: BTreeSet<&String> = before.tags.iter().collect();
+        let now_tags: BTreeSet<&String> = now.tags.iter().collect();
+        if before_tags != now_tags {
+            changes.push((now, ChangeKind::TagsChanged {
+                added: now_tags.difference(&before_tags).map(|t| t.to_string()).collect(),
+                removed: before_tags.difference(&now_tags).map(|t| t.to_string()).collect(),
+            }));
+        }
+    }
+    for (id, before) in previous {
+        if !current.contains_key(id) {
+            changes.push((before, ChangeKind::Exited { rank: before.cmc_rank }));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(id: u64, symbol: &str, rank: u64, supply: f64, tags: &[&str]) -> CmcListing {
+        CmcListing {
+            id,
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            cmc_rank: Some(rank),
+            circulating_supply: Some(supply),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            market_cap: None,
+            volume_24h: None,
+            percent_change_24h: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_listing_changes() {
+        let history = ChangeHistory::new(ChangeDetectionConfig::default());
+        let now = Utc::now();
+
+        // 首次采集只作为基准
+        let first = vec![
+            listing(1, "BTC", 1, 19_700_000.0, &["mineable"]),
+            listing(2, "AAA", 20, 1_000_000.0, &[]),
+            listing(3, "BBB", 95, 5_000.0, &[]),
+        ];
+        assert!(history.record_listings(&first, now).await.unwrap().is_empty());
+
+        let second = vec![
+            listing(1, "BTC", 1, 19_700_100.0, &["mineable", "pow"]),
+            listing(2, "AAA", 12, 1_100_000.0, &[]),
+            listing(4, "CCC", 98, 1.0, &[]),
+        ];
+        let events = history.record_listings(&second, now).await.unwrap();
+        let kinds: Vec<(&str, &str)> = events.iter().map(|e| (e.symbol.as_str(), e.change.name())).collect();
+        assert_eq!(kinds, vec![
+            ("BTC", "tags_changed"),
+            ("AAA", "rank_changed"),
+            ("AAA", "supply_changed"),
+            ("CCC", "entered"),
+            ("BBB", "exited"),
+        ]);
+        assert_eq!(events[1].change, ChangeKind::RankChanged { from: 20, to: 12 });
+
+        let json = serde_json::to_value(&events[1]).unwrap();
+        assert_eq!(json["type"], "rank_changed");
+        assert_eq!(serde_json::from_value::<ChangeEvent>(json).unwrap(), events[1]);
+
+        let query = ChangeQuery { symbol: Some("aaa".to_string()), ..Default::default() };
+        assert_eq!(history.query(&query).await.len(), 2);
+        assert_eq!(events[1].to_alert(AlertSeverity::Info).rule, "changes:rank_changed:aaa");
+
+        let coin = |id: &str, price: Option<f64>| ListedCoin {
+            id: id.to_string(),
+            symbol: id.to_uppercase(),
+            name: id.to_string(),
+            price,
+            market_cap: None,
+        };
+        let ids = ["bitcoin".to_string(), "newcoin".to_string()];
+        assert_eq!(history.unknown_ids("coingecko", &ids).await, None);
+        assert!(history.record_coin_ids("coingecko", &[coin("bitcoin", None)], now).await.unwrap().is_empty());
+        assert_eq!(history.unknown_ids("coingecko", &ids).await, Some(vec!["newcoin".to_string()]));
+        let listed = history.record_coin_ids("coingecko", &[coin("bitcoin", None), coin("newcoin", Some(0.5))], now).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].change.name(), "new_listing");
+        let query = ChangeQuery { kind: Some("new_listing".to_string()), ..Default::default() };
+        assert_eq!(history.query(&query).await[0].symbol, "NEWCOIN");
+    }
+}
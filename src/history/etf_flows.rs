@@ -0,0 +1,245 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tracing::{info, debug};
+
+use crate::alerts::{Alert, AlertSeverity};
+use crate::clients::EtfFlowRecord;
+use crate::config::EtfFlowsConfig;
+use crate::storage::{JsonFileStore, Reloadable};
+use crate::web::cache::ETF_FLOWS_KEY;
+
+/// 每日各基金净流量：资产 -> 日期 -> 基金代码 -> 净流入（美元）
+type DailyFundFlows = HashMap<String, BTreeMap<NaiveDate, BTreeMap<String, f64>>>;
+
+/// 单个资产单日的ETF资金流向
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EtfFlowDay {
+    /// 交易日
+    pub date: NaiveDate,
+    /// 所有基金合计净流入（美元）
+    pub net_flow_usd: f64,
+    /// 截至当日的累计净流入（美元）
+    pub cumulative_usd: f64,
+    /// 各基金净流入（美元）
+    pub funds: BTreeMap<String, f64>,
+}
+
+/// 单个资产的ETF资金流向序列
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EtfFlowSeries {
+    /// 标的资产
+    pub asset: String,
+    /// 全部历史的累计净流入（美元）
+    pub total_net_flow_usd: f64,
+    /// 每日流向（按日期升序）
+    pub days: Vec<EtfFlowDay>,
+}
+
+/// 单日合计净流量
+fn day_total(funds: &BTreeMap<String, f64>) -> f64 {
+    funds.values().sum()
+}
+
+/// 现货ETF资金流向历史
+///
+/// 数据源通常会修正最近几天的数据，同一基金同一天以最后一次获取为准
+pub struct EtfFlowHistory {
+    /// 每日各基金净流量
+    flows: RwLock<DailyFundFlows>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<DailyFundFlows>>,
+    /// 配置
+    config: EtfFlowsConfig,
+}
+
+impl EtfFlowHistory {
+    /// 创建内存中的历史存储（不持久化）
+    pub fn new(config: EtfFlowsConfig) -> Self {
+        Self {
+            flows: RwLock::new(HashMap::new()),
+            store: None,
+            config,
+        }
+    }
+
+    /// 从状态文件加载历史存储
+    pub async fn open(path: impl Into<PathBuf>, config: EtfFlowsConfig) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let flows: DailyFundFlows = store.load().await?;
+        info!("🏛️ 已加载 {} 个资产的ETF资金流向历史", flows.len());
+        Ok(Self {
+            flows: RwLock::new(flows),
+            store: Some(store),
+            config,
+        })
+    }
+
+    /// 记录一批每日净流量
+    ///
+    /// 只对不早于已有最新交易日的数据检查大额流出，首次导入历史数据时不告警
+    ///
+    /// # 参数
+    /// * `records` - 各基金每日净流量
+    ///
+    /// # 返回
+    /// * `Result<Vec<Alert>>` - 单日合计净流出首次达到阈值时的告警
+    pub async fn record(&self, records: &[EtfFlowRecord]) -> Result<Vec<Alert>> {
+        let mut flows = self.flows.write().await;
+        let latest_before: HashMap<String, NaiveDate> = flows.iter()
+            .filter_map(|(asset, days)| Some((asset.clone(), *days.last_key_value()?.0)))
+            .collect();
+        let mut totals_before: HashMap<(String, NaiveDate), Option<f64>> = HashMap::new();
+
+        for record in records.iter().filter(|r| r.net_flow_usd.is_finite()) {
+            let asset = record.asset.to_uppercase();
+            let days = flows.entry(asset.clone()).or_default();
+            totals_before.entry((asset, record.date))
+                .or_insert_with(|| days.get(&record.date).map(day_total));
+            days.entry(record.date).or_default().insert(record.fund.to_uppercase(), record.net_flow_usd);
+        }
+
+        let threshold = -self.config.large_outflow_usd.abs();
+        let mut alerts = Vec::new();
+        for ((asset, date), before) in &totals_before {
+            let Some(latest) = latest_before.get(asset).filter(|latest| date >= *latest) else {
+                continue;
+            };
+            let Some(funds) = flows.get(asset).and_then(|days| days.get(date)) else {
+                continue;
+            };
+            let total = day_total(funds);
+            if total > threshold || before.is_some_and(|b| b <= threshold) {
+                continue;
+            }
+            let (worst_fund, worst_flow) = funds.iter()
+                .min_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(fund, flow)| (fund.clone(), *flow))
+                .unwrap_or_default();
+            alerts.push(Alert::new(
+                format!("etf_flows:outflow:{}", asset.to_lowercase()),
+                AlertSeverity::Warning,
+                format!("{} 现货ETF单日净流出 {:.1} 百万美元", asset, -total / 1e6),
+                format!("{} 合计净流出 {:.1} 百万美元，流出最多的是 {}（{:.1} 百万美元）", date, -total / 1e6, worst_fund, worst_flow / 1e6),
+            ).metadata(serde_json::json!({
+                "asset": asset,
+                "date": date,
+                "net_flow_usd": total,
+                "previous_latest_date": latest,
+                "threshold_usd": self.config.large_outflow_usd,
+                "source_key": ETF_FLOWS_KEY,
+            })));
+        }
+
+        for days in flows.values_mut() {
+            while days.len() > self.config.max_days {
+                days.pop_first();
+            }
+        }
+        if let Some(store) = &self.store {
+            store.save(&flows).await?;
+        }
+        debug!("🏛️ 记录 {} 条ETF资金流向", records.len());
+        Ok(alerts)
+    }
+
+    /// 查询资金流向序列
+    ///
+    /// 累计值始终从保存的最早交易日开始计算
+    ///
+    /// # 参数
+    /// * `asset` - 只返回指定资产（可选）
+    /// * `since` - 起始交易日（可选，包含）
+    pub async fn series(&self, asset: Option<&str>, since: Option<NaiveDate>) -> Vec<EtfFlowSeries> {
+        let flows = self.flows.read().await;
+        let mut assets: Vec<&String> = flows.keys()
+            .filter(|a| asset.is_none_or(|wanted| a.eq_ignore_ascii_case(wanted)))
+            .collect();
+        assets.sort();
+
+        assets.into_iter()
+            .map(|asset| {
+                let mut cumulative = 0.0;
+                let mut days = Vec::new();
+                for (date, funds) in &flows[asset] {
+                    let net_flow_usd = day_total(funds);
+                    cumulative += net_flow_usd;
+                    if since.is_none_or(|since| *date >= since) {
+                        days.push(EtfFlowDay {
+                            date: *date,
+                            net_flow_usd,
+                            cumulative_usd: cumulative,
+                            funds: funds.clone(),
+                        });
+                    }
+                }
+                EtfFlowSeries {
+                    asset: asset.clone(),
+                    total_net_flow_usd: cumulative,
+                    days,
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Reloadable for EtfFlowHistory {
+    fn name(&self) -> &str {
+        "etf_flows"
+    }
+
+    async fn reload(&self) -> Result<()> {
+        if let Some(store) = &self.store {
+            *self.flows.write().await = store.load().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flow(date: &str, fund: &str, net_flow_usd: f64) -> EtfFlowRecord {
+        EtfFlowRecord {
+            date: date.parse().unwrap(),
+            fund: fund.to_string(),
+            asset: "btc".to_string(),
+            net_flow_usd,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cumulative_and_outflow_alert() {
+        let history = EtfFlowHistory::new(EtfFlowsConfig::default());
+
+        // 首次导入历史数据不告警
+        let alerts = history.record(&[
+            flow("2024-01-11", "IBIT", 100e6),
+            flow("2024-01-11", "GBTC", -300e6),
+            flow("2024-01-12", "IBIT", 50e6),
+        ]).await.unwrap();
+        assert!(alerts.is_empty());
+
+        let alerts = history.record(&[
+            flow("2024-01-16", "IBIT", 20e6),
+            flow("2024-01-16", "GBTC", -250e6),
+        ]).await.unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule, "etf_flows:outflow:btc");
+
+        // 同一天重复获取不再告警
+        let alerts = history.record(&[flow("2024-01-16", "GBTC", -260e6)]).await.unwrap();
+        assert!(alerts.is_empty());
+
+        let series = history.series(Some("BTC"), "2024-01-12".parse().ok()).await;
+        assert_eq!(series[0].days.len(), 2);
+        assert_eq!(series[0].days[0].cumulative_usd, -150e6);
+        assert_eq!(series[0].total_net_flow_usd, -390e6);
+    }
+}
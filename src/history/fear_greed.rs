@@ -0,0 +1,191 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tracing::{info, debug};
+
+use crate::alerts::{Alert, AlertSeverity};
+use crate::config::FearGreedConfig;
+use crate::storage::{JsonFileStore, Reloadable};
+use crate::web::cache::FEAR_GREED_KEY;
+
+/// 贪婪恐惧指数读数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FearGreedReading {
+    /// 采集时间
+    pub timestamp: DateTime<Utc>,
+    /// 指数值 (0-100)
+    pub value: u8,
+    /// 相对24小时前的变化
+    pub change_24h: Option<i16>,
+    /// 相对7天前的变化
+    pub change_7d: Option<i16>,
+}
+
+impl FearGreedReading {
+    /// 变化字段
+    pub fn delta(&self) -> FearGreedDelta {
+        FearGreedDelta {
+            change_24h: self.change_24h,
+            change_7d: self.change_7d,
+        }
+    }
+}
+
+/// 贪婪恐惧指数变化
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct FearGreedDelta {
+    /// 相对24小时前的变化（历史不足24小时时为空）
+    pub change_24h: Option<i16>,
+    /// 相对7天前的变化（历史不足7天时为空）
+    pub change_7d: Option<i16>,
+}
+
+/// 查找不晚于指定时间的最近一条读数
+fn value_at(readings: &[FearGreedReading], at: DateTime<Utc>) -> Option<u8> {
+    let index = readings.partition_point(|r| r.timestamp <= at);
+    index.checked_sub(1).map(|i| readings[i].value)
+}
+
+/// 贪婪恐惧指数历史存储
+pub struct FearGreedHistory {
+    /// 按时间排序的读数
+    readings: RwLock<Vec<FearGreedReading>>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<Vec<FearGreedReading>>>,
+    /// 配置
+    config: FearGreedConfig,
+}
+
+impl FearGreedHistory {
+    /// 创建内存中的历史存储（不持久化）
+    pub fn new(config: FearGreedConfig) -> Self {
+        Self {
+            readings: RwLock::new(Vec::new()),
+            store: None,
+            config,
+        }
+    }
+
+    /// 从状态文件加载历史存储
+    pub async fn open(path: impl Into<PathBuf>, config: FearGreedConfig) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let readings: Vec<FearGreedReading> = store.load().await?;
+        info!("😱 已加载 {} 条贪婪恐惧指数历史", readings.len());
+        Ok(Self {
+            readings: RwLock::new(readings),
+            store: Some(store),
+            config,
+        })
+    }
+
+    /// 记录一次读数
+    /// 
+    /// # 参数
+    /// * `value` - 指数值
+    /// * `timestamp` - 采集时间
+    /// 
+    /// # 返回
+    /// * `Result<(FearGreedReading, Option<Alert>)>` - 带变化字段的读数，以及24小时变化首次超过阈值时的告警
+    pub async fn record(&self, value: u8, timestamp: DateTime<Utc>) -> Result<(FearGreedReading, Option<Alert>)> {
+        let mut readings = self.readings.write().await;
+        let change_since = |ago: Duration| {
+            value_at(&readings, timestamp - ago).map(|old| value as i16 - old as i16)
+        };
+        let reading = FearGreedReading {
+            timestamp,
+            value,
+            change_24h: change_since(Duration::hours(24)),
+            change_7d: change_since(Duration::days(7)),
+        };
+        
+        // 只在变化幅度刚超过阈值时告警，避免同一波动在24小时内重复触发
+        let threshold = self.config.swing_threshold as i16;
+        let is_swing = |r: &FearGreedReading| r.change_24h.is_some_and(|c| c.abs() >= threshold);
+        let alert = (is_swing(&reading) && !readings.last().is_some_and(is_swing)).then(|| {
+            let change = reading.change_24h.unwrap_or_default();
+            Alert::new(
+                "fear_greed:swing",
+                AlertSeverity::Warning,
+                format!("贪婪恐惧指数24小时{} {} 点", if change > 0 { "上升" } else { "下降" }, change.abs()),
+                format!("当前值 {}，24小时前 {}", value, value as i16 - change),
+            ).metadata(serde_json::json!({
+                "value": value,
+                "change_24h": change,
+                "threshold": threshold,
+                "source_key": FEAR_GREED_KEY,
+            }))
+        });
+        
+        readings.push(reading.clone());
+        let excess = readings.len().saturating_sub(self.config.max_history);
+        readings.drain(..excess);
+        
+        if let Some(store) = &self.store {
+            store.save(&readings).await?;
+        }
+        debug!("😱 记录贪婪恐惧指数: {} (24h {:?}, 7d {:?})", value, reading.change_24h, reading.change_7d);
+        Ok((reading, alert))
+    }
+
+    /// 查询历史读数
+    /// 
+    /// # 参数
+    /// * `since` - 起始时间（可选）
+    /// * `limit` - 最多返回最近的条数
+    pub async fn readings(&self, since: Option<DateTime<Utc>>, limit: usize) -> Vec<FearGreedReading> {
+        let readings = self.readings.read().await;
+        let start = since.map_or(0, |since| readings.partition_point(|r| r.timestamp < since));
+        let matched = &readings[start..];
+        matched[matched.len().saturating_sub(limit)..].to_vec()
+    }
+
+    /// 最新读数的变化字段
+    pub async fn latest_delta(&self) -> FearGreedDelta {
+        self.readings.read().await.last().map(FearGreedReading::delta).unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl Reloadable for FearGreedHistory {
+    fn name(&self) -> &str {
+        "fear_greed_history"
+    }
+
+    async fn reload(&self) -> Result<()> {
+        if let Some(store) = &self.store {
+            *self.readings.write().await = store.load().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_delta_and_swing_alert() {
+        let history = FearGreedHistory::new(FearGreedConfig::default());
+        let start = Utc::now() - Duration::days(8);
+        
+        let (first, alert) = history.record(50, start).await.unwrap();
+        assert_eq!(first.change_24h, None);
+        assert!(alert.is_none());
+        
+        let (reading, _) = history.record(55, start + Duration::days(7)).await.unwrap();
+        assert_eq!(reading.change_7d, Some(5));
+        
+        // 24小时内上升20点，首次超过阈值时告警，持续超过时不再告警
+        let (reading, alert) = history.record(75, start + Duration::days(8)).await.unwrap();
+        assert_eq!(reading.change_24h, Some(20));
+        assert!(alert.is_some());
+        let (_, alert) = history.record(76, start + Duration::days(8) + Duration::hours(1)).await.unwrap();
+        assert!(alert.is_none());
+        
+        assert_eq!(history.latest_delta().await.change_24h, Some(21));
+        assert_eq!(history.readings(Some(start + Duration::days(1)), 10).await.len(), 3);
+    }
+}
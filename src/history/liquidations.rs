@@ -0,0 +1,253 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tracing::{info, debug};
+
+use crate::alerts::Alert;
+use crate::clients::LiquidationRecord;
+use crate::config::LiquidationsConfig;
+use crate::storage::{JsonFileStore, Reloadable};
+use crate::web::cache::LIQUIDATIONS_KEY;
+
+/// 保存的单小时爆仓金额
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct StoredHour {
+    /// 多单爆仓金额（美元）
+    long_usd: f64,
+    /// 空单爆仓金额（美元）
+    short_usd: f64,
+}
+
+impl StoredHour {
+    /// 多空合计
+    fn total(&self) -> f64 {
+        self.long_usd + self.short_usd
+    }
+}
+
+/// 每小时爆仓金额：币种 -> 小时 -> 多/空金额
+type HourlyLiquidations = HashMap<String, BTreeMap<DateTime<Utc>, StoredHour>>;
+
+/// 单个币种一小时的爆仓金额
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationHour {
+    /// 小时起始时间
+    pub hour: DateTime<Utc>,
+    /// 多单爆仓金额（美元）
+    pub long_usd: f64,
+    /// 空单爆仓金额（美元）
+    pub short_usd: f64,
+    /// 多空合计（美元）
+    pub total_usd: f64,
+}
+
+/// 单个币种的爆仓序列
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationSeries {
+    /// 币种符号
+    pub symbol: String,
+    /// 查询区间内的多单爆仓合计（美元）
+    pub total_long_usd: f64,
+    /// 查询区间内的空单爆仓合计（美元）
+    pub total_short_usd: f64,
+    /// 每小时爆仓金额（按时间升序）
+    pub hours: Vec<LiquidationHour>,
+}
+
+/// 合约爆仓历史
+///
+/// 每次获取都包含尚未结束的当前小时，同一币种同一小时以最后一次获取为准
+pub struct LiquidationHistory {
+    /// 每小时爆仓金额
+    hours: RwLock<HourlyLiquidations>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<HourlyLiquidations>>,
+    /// 配置
+    config: LiquidationsConfig,
+}
+
+impl LiquidationHistory {
+    /// 创建内存中的历史存储（不持久化）
+    pub fn new(config: LiquidationsConfig) -> Self {
+        Self {
+            hours: RwLock::new(HashMap::new()),
+            store: None,
+            config,
+        }
+    }
+
+    /// 从状态文件加载历史存储
+    pub async fn open(path: impl Into<PathBuf>, config: LiquidationsConfig) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let hours: HourlyLiquidations = store.load().await?;
+        info!("💥 已加载 {} 个币种的爆仓历史", hours.len());
+        Ok(Self {
+            hours: RwLock::new(hours),
+            store: Some(store),
+            config,
+        })
+    }
+
+    /// 记录一批每小时爆仓金额
+    ///
+    /// 只对不早于已有最新小时的数据检查连环爆仓，首次导入历史数据时不告警
+    ///
+    /// # 参数
+    /// * `records` - 各币种每小时爆仓金额
+    ///
+    /// # 返回
+    /// * `Result<Vec<Alert>>` - 一小时多空爆仓合计首次达到阈值时的告警
+    pub async fn record(&self, records: &[LiquidationRecord]) -> Result<Vec<Alert>> {
+        let mut hours = self.hours.write().await;
+        let latest_before: HashMap<String, DateTime<Utc>> = hours.iter()
+            .filter_map(|(symbol, series)| Some((symbol.clone(), *series.last_key_value()?.0)))
+            .collect();
+        let mut totals_before: HashMap<(String, DateTime<Utc>), Option<f64>> = HashMap::new();
+
+        for record in records.iter().filter(|r| r.long_usd.is_finite() && r.short_usd.is_finite()) {
+            let symbol = record.symbol.to_uppercase();
+            let series = hours.entry(symbol.clone()).or_default();
+            totals_before.entry((symbol, record.hour))
+                .or_insert_with(|| series.get(&record.hour).map(StoredHour::total));
+            series.insert(record.hour, StoredHour { long_usd: record.long_usd, short_usd: record.short_usd });
+        }
+
+        let threshold = self.config.cascade_threshold_usd.abs();
+        let mut alerts = Vec::new();
+        for ((symbol, hour), before) in &totals_before {
+            if latest_before.get(symbol).is_none_or(|latest| hour < latest) {
+                continue;
+            }
+            let Some(stored) = hours.get(symbol).and_then(|series| series.get(hour)) else {
+                continue;
+            };
+            let total = stored.total();
+            if total < threshold || before.is_some_and(|b| b >= threshold) {
+                continue;
+            }
+            let (side, side_usd) = if stored.long_usd >= stored.short_usd {
+                ("多单", stored.long_usd)
+            } else {
+                ("空单", stored.short_usd)
+            };
+            alerts.push(Alert::new(
+                format!("liquidations:cascade:{}", symbol.to_lowercase()),
+                self.config.cascade_severity,
+                format!("{} 一小时爆仓 {:.1} 百万美元（{}为主）", symbol, total / 1e6, side),
+                format!(
+                    "{} 起一小时内多单爆仓 {:.1} 百万美元，空单爆仓 {:.1} 百万美元，{}占 {:.0}%",
+                    hour.format("%Y-%m-%d %H:%M UTC"), stored.long_usd / 1e6, stored.short_usd / 1e6, side, side_usd / total * 100.0,
+                ),
+            ).metadata(serde_json::json!({
+                "symbol": symbol,
+                "hour": hour,
+                "long_usd": stored.long_usd,
+                "short_usd": stored.short_usd,
+                "threshold_usd": threshold,
+                "source_key": LIQUIDATIONS_KEY,
+            })));
+        }
+
+        for series in hours.values_mut() {
+            while series.len() > self.config.max_hours {
+                series.pop_first();
+            }
+        }
+        if let Some(store) = &self.store {
+            store.save(&hours).await?;
+        }
+        debug!("💥 记录 {} 条每小时爆仓数据", records.len());
+        Ok(alerts)
+    }
+
+    /// 查询爆仓序列
+    ///
+    /// # 参数
+    /// * `symbol` - 只返回指定币种（可选）
+    /// * `since` - 起始小时（可选，包含）
+    pub async fn series(&self, symbol: Option<&str>, since: Option<DateTime<Utc>>) -> Vec<LiquidationSeries> {
+        let hours = self.hours.read().await;
+        let mut symbols: Vec<&String> = hours.keys()
+            .filter(|s| symbol.is_none_or(|wanted| s.eq_ignore_ascii_case(wanted)))
+            .collect();
+        symbols.sort();
+
+        symbols.into_iter()
+            .map(|symbol| {
+                let points: Vec<LiquidationHour> = hours[symbol].iter()
+                    .filter(|(hour, _)| since.is_none_or(|since| **hour >= since))
+                    .map(|(hour, stored)| LiquidationHour {
+                        hour: *hour,
+                        long_usd: stored.long_usd,
+                        short_usd: stored.short_usd,
+                        total_usd: stored.total(),
+                    })
+                    .collect();
+                LiquidationSeries {
+                    symbol: symbol.clone(),
+                    total_long_usd: points.iter().map(|p| p.long_usd).sum(),
+                    total_short_usd: points.iter().map(|p| p.short_usd).sum(),
+                    hours: points,
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Reloadable for LiquidationHistory {
+    fn name(&self) -> &str {
+        "liquidations"
+    }
+
+    async fn reload(&self) -> Result<()> {
+        if let Some(store) = &self.store {
+            *self.hours.write().await = store.load().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hour(at: &str, long_usd: f64, short_usd: f64) -> LiquidationRecord {
+        LiquidationRecord {
+            symbol: "btc".to_string(),
+            hour: at.parse().unwrap(),
+            long_usd,
+            short_usd,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cascade_alert_and_series() {
+        let history = LiquidationHistory::new(LiquidationsConfig::default());
+
+        // 首次导入历史数据不告警
+        let alerts = history.record(&[
+            hour("2024-08-05T00:00:00Z", 150e6, 10e6),
+            hour("2024-08-05T01:00:00Z", 20e6, 5e6),
+        ]).await.unwrap();
+        assert!(alerts.is_empty());
+
+        // 当前小时逐渐累积，首次越过阈值时告警
+        let alerts = history.record(&[hour("2024-08-05T02:00:00Z", 60e6, 5e6)]).await.unwrap();
+        assert!(alerts.is_empty());
+        let alerts = history.record(&[hour("2024-08-05T02:00:00Z", 120e6, 5e6)]).await.unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule, "liquidations:cascade:btc");
+        let alerts = history.record(&[hour("2024-08-05T02:00:00Z", 130e6, 5e6)]).await.unwrap();
+        assert!(alerts.is_empty());
+
+        let series = history.series(Some("BTC"), "2024-08-05T01:00:00Z".parse().ok()).await;
+        assert_eq!(series[0].hours.len(), 2);
+        assert_eq!(series[0].total_long_usd, 150e6);
+        assert_eq!(series[0].hours[1].total_usd, 135e6);
+    }
+}
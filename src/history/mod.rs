@@ -0,0 +1,304 @@
+pub mod altcoin_season;
+pub mod backfill;
+pub mod changes;
+pub mod etf_flows;
+pub mod fear_greed;
+pub mod liquidations;
+pub mod prices;
+pub mod ranks;
+pub mod trends;
+
+pub use altcoin_season::*;
+pub use backfill::*;
+pub use changes::*;
+pub use etf_flows::*;
+pub use fear_greed::*;
+pub use liquidations::*;
+pub use prices::*;
+pub use ranks::*;
+pub use trends::*;
+
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::{info, warn, error};
+
+use crate::alerts::{AlertManager, AlertSeverity};
+use crate::analytics::{NodeStatus, RatioReading};
+use crate::clients::{AltcoinSeasonConstituents, CmcListing, EtfFlowRecord, LiquidationRecord};
+use crate::web::cache::{DataCache, ALTCOIN_SEASON_CONSTITUENTS_KEY, ALTCOIN_SEASON_KEY, CMC_LISTINGS_KEY, ETF_FLOWS_KEY, FEAR_GREED_KEY, LIQUIDATIONS_KEY, NEW_LISTINGS_KEY, NODES_KEY, RATIOS_KEY};
+
+/// 指标历史记录器
+/// 
+/// 订阅缓存更新，把每次采集到的情绪指数、币种价格、市值排名、ETF资金流向和合约爆仓写入历史，比较相邻两次的CMC上市信息记录变化事件，
+/// 并在出现阶段切换、剧烈波动、大额流出、连环爆仓或上市信息变化时触发告警
+pub struct HistoryRecorder {
+    /// 数据缓存
+    cache: Arc<DataCache>,
+    /// 山寨币季节指数历史
+    altcoin_season: Arc<AltcoinSeasonHistory>,
+    /// 贪婪恐惧指数历史
+    fear_greed: Arc<FearGreedHistory>,
+    /// 币种日线价格历史
+    prices: Arc<PriceHistory>,
+    /// 币种每日市值排名历史（可选）
+    ranks: Option<Arc<RankHistory>>,
+    /// 价格比率突破告警（告警级别和回看天数，为None时不告警）
+    ratio_alerts: Option<(AlertSeverity, u32)>,
+    /// 自建节点不健康告警级别（为None时不告警）
+    node_alerts: Option<AlertSeverity>,
+    /// 现货ETF资金流向历史
+    etf_flows: Arc<EtfFlowHistory>,
+    /// 合约爆仓历史（可选）
+    liquidations: Option<Arc<LiquidationHistory>>,
+    /// 数据集变化历史
+    changes: Arc<ChangeHistory>,
+    /// 告警管理器
+    alert_manager: Arc<AlertManager>,
+}
+
+impl HistoryRecorder {
+    /// 创建新的历史记录器
+    pub fn new(
+        cache: Arc<DataCache>,
+        altcoin_season: Arc<AltcoinSeasonHistory>,
+        fear_greed: Arc<FearGreedHistory>,
+        prices: Arc<PriceHistory>,
+        etf_flows: Arc<EtfFlowHistory>,
+        changes: Arc<ChangeHistory>,
+        alert_manager: Arc<AlertManager>,
+    ) -> Self {
+        Self {
+            cache,
+            altcoin_season,
+            fear_greed,
+            prices,
+            ranks: None,
+            ratio_alerts: None,
+            node_alerts: None,
+            etf_flows,
+            liquidations: None,
+            changes,
+            alert_manager,
+        }
+    }
+
+    /// 设置市值排名历史，记录监控币种和CMC上市信息的每日排名
+    pub fn with_rank_history(mut self, ranks: Arc<RankHistory>) -> Self {
+        self.ranks = Some(ranks);
+        self
+    }
+
+    /// 设置合约爆仓历史，保存每小时爆仓金额并检查连环爆仓
+    pub fn with_liquidation_history(mut self, liquidations: Arc<LiquidationHistory>) -> Self {
+        self.liquidations = Some(liquidations);
+        self
+    }
+
+    /// 启用价格比率突破告警
+    ///
+    /// # 参数
+    /// * `severity` - 告警级别
+    /// * `lookback_days` - 判断突破的回看天数（用于告警文本）
+    pub fn with_ratio_alerts(mut self, severity: AlertSeverity, lookback_days: u32) -> Self {
+        self.ratio_alerts = Some((severity, lookback_days));
+        self
+    }
+
+    /// 启用自建节点告警，节点新变为无法访问或落后过多时告警
+    pub fn with_node_alerts(mut self, severity: AlertSeverity) -> Self {
+        self.node_alerts = Some(severity);
+        self
+    }
+
+    /// 持续处理缓存更新事件
+    pub async fn run(self) {
+        info!("📚 启动指标历史记录");
+        let mut updates = self.cache.subscribe();
+        
+        loop {
+            match updates.recv().await {
+                Ok(update) => {
+                    for key in &update.keys {
+                        self.record_update(key, update.updated_at).await;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("⚠️ 指标历史记录落后，跳过 {} 条更新事件", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// 处理单个数据集的更新
+    async fn record_update(&self, key: &str, updated_at: DateTime<Utc>) {
+        match key {
+            ALTCOIN_SEASON_KEY => self.record_altcoin_season(updated_at).await,
+            FEAR_GREED_KEY => self.record_fear_greed(updated_at).await,
+            ETF_FLOWS_KEY => self.record_etf_flows().await,
+            LIQUIDATIONS_KEY => self.record_liquidations().await,
+            CMC_LISTINGS_KEY => self.record_listing_changes(updated_at).await,
+            NEW_LISTINGS_KEY => self.notify_new_listings().await,
+            RATIOS_KEY => self.notify_ratio_breakouts().await,
+            NODES_KEY => self.notify_unhealthy_nodes().await,
+            key => {
+                if let Some(coin_id) = key.strip_prefix("market_data:") {
+                    self.record_price(coin_id, updated_at).await;
+                }
+            }
+        }
+    }
+
+    /// 记录山寨币季节指数并检查阶段切换
+    async fn record_altcoin_season(&self, timestamp: DateTime<Utc>) {
+        let Some(index) = self.cache.get_altcoin_season_index().await else {
+            return;
+        };
+        match self.altcoin_season.record(index.value, timestamp).await {
+            Ok(point) => {
+                if let Some(alert) = point.regime_alert(&index) {
+                    self.alert_manager.fire(alert).await;
+                }
+            }
+            Err(e) => error!("❌ 保存山寨币季节指数历史失败: {}", e),
+        }
+        if let Some(breakdown) = self.cache.get_dataset::<AltcoinSeasonConstituents>(ALTCOIN_SEASON_CONSTITUENTS_KEY).await {
+            if let Err(e) = self.altcoin_season.record_constituents(index.value, breakdown, timestamp).await {
+                error!("❌ 保存山寨币季节成分币种快照失败: {}", e);
+            }
+        }
+    }
+
+    /// 记录贪婪恐惧指数并检查单日波动
+    async fn record_fear_greed(&self, timestamp: DateTime<Utc>) {
+        let Some(value) = self.cache.metric_value(FEAR_GREED_KEY).await else {
+            return;
+        };
+        match self.fear_greed.record(value.round().clamp(0.0, 100.0) as u8, timestamp).await {
+            Ok((_, Some(alert))) => {
+                self.alert_manager.fire(alert).await;
+            }
+            Ok((_, None)) => {}
+            Err(e) => error!("❌ 保存贪婪恐惧指数历史失败: {}", e),
+        }
+    }
+
+    /// 记录现货ETF资金流向并检查大额流出
+    async fn record_etf_flows(&self) {
+        let Some(records) = self.cache.get_dataset::<Vec<EtfFlowRecord>>(ETF_FLOWS_KEY).await else {
+            return;
+        };
+        match self.etf_flows.record(&records).await {
+            Ok(alerts) => {
+                for alert in alerts {
+                    self.alert_manager.fire(alert).await;
+                }
+            }
+            Err(e) => error!("❌ 保存ETF资金流向历史失败: {}", e),
+        }
+    }
+
+    /// 记录合约每小时爆仓金额并检查连环爆仓
+    async fn record_liquidations(&self) {
+        let Some(history) = &self.liquidations else {
+            return;
+        };
+        let Some(records) = self.cache.get_dataset::<Vec<LiquidationRecord>>(LIQUIDATIONS_KEY).await else {
+            return;
+        };
+        match history.record(&records).await {
+            Ok(alerts) => {
+                for alert in alerts {
+                    self.alert_manager.fire(alert).await;
+                }
+            }
+            Err(e) => error!("❌ 保存爆仓历史失败: {}", e),
+        }
+    }
+
+    /// 比较CMC上市信息并记录变化事件
+    async fn record_listing_changes(&self, timestamp: DateTime<Utc>) {
+        let Some(listings) = self.cache.get_dataset::<Vec<CmcListing>>(CMC_LISTINGS_KEY).await else {
+            return;
+        };
+        if let Some(history) = &self.ranks {
+            let ranks: Vec<(String, u64)> = listings.iter()
+                .filter_map(|l| Some((l.symbol.to_lowercase(), l.cmc_rank?)))
+                .collect();
+            if let Err(e) = history.record(&ranks, timestamp).await {
+                error!("❌ 保存CMC排名历史失败: {}", e);
+            }
+        }
+        match self.changes.record_listings(&listings, timestamp).await {
+            Ok(events) => {
+                let config = self.changes.config();
+                if config.alerts {
+                    for event in &events {
+                        self.alert_manager.fire(event.to_alert(config.alert_severity)).await;
+                    }
+                }
+            }
+            Err(e) => error!("❌ 保存CMC上市信息变化失败: {}", e),
+        }
+    }
+
+    /// 为新上线币种事件触发告警（事件已由检测任务记录）
+    async fn notify_new_listings(&self) {
+        let config = self.changes.config();
+        if !config.alerts {
+            return;
+        }
+        let Some(events) = self.cache.get_dataset::<Vec<ChangeEvent>>(NEW_LISTINGS_KEY).await else {
+            return;
+        };
+        for event in &events {
+            self.alert_manager.fire(event.to_alert(config.alert_severity)).await;
+        }
+    }
+
+    /// 为新发生的价格比率突破触发告警
+    async fn notify_ratio_breakouts(&self) {
+        let Some((severity, lookback_days)) = self.ratio_alerts else {
+            return;
+        };
+        let Some(readings) = self.cache.get_dataset::<Vec<RatioReading>>(RATIOS_KEY).await else {
+            return;
+        };
+        for reading in readings.iter().filter(|r| r.new_breakout) {
+            if let Some(alert) = reading.breakout_alert(severity, lookback_days) {
+                self.alert_manager.fire(alert).await;
+            }
+        }
+    }
+
+    /// 为新变为不健康的自建节点触发告警
+    async fn notify_unhealthy_nodes(&self) {
+        let Some(severity) = self.node_alerts else {
+            return;
+        };
+        let Some(statuses) = self.cache.get_dataset::<Vec<NodeStatus>>(NODES_KEY).await else {
+            return;
+        };
+        for status in statuses.iter().filter(|s| s.newly_unhealthy) {
+            if let Some(alert) = status.unhealthy_alert(severity) {
+                self.alert_manager.fire(alert).await;
+            }
+        }
+    }
+
+    /// 记录币种日线价格和市值排名
+    async fn record_price(&self, coin_id: &str, timestamp: DateTime<Utc>) {
+        if let (Some(history), Some(rank)) = (&self.ranks, self.cache.metric_value(&format!("market_cap_rank:{}", coin_id)).await) {
+            if let Err(e) = history.record(&[(coin_id.to_string(), rank as u64)], timestamp).await {
+                error!("❌ 保存 {} 排名历史失败: {}", coin_id, e);
+            }
+        }
+        let Some(price) = self.cache.metric_value(&format!("price:{}", coin_id)).await else {
+            return;
+        };
+        if let Err(e) = self.prices.record(coin_id, price, timestamp).await {
+            error!("❌ 保存 {} 价格历史失败: {}", coin_id, e);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, debug};
+
+use crate::storage::JsonFileStore;
+
+/// 默认保留的日线天数（覆盖200日均线并留有余量）
+pub const DEFAULT_PRICE_HISTORY_DAYS: usize = 400;
+
+/// 同一天内价格更新时两次写入状态文件的最小间隔（分钟）
+const SAVE_INTERVAL_MINUTES: i64 = 10;
+
+/// 每日收盘价：币种ID -> 日期(UTC) -> 当日最后一次采集的价格
+type DailyCloses = HashMap<String, BTreeMap<NaiveDate, f64>>;
+
+/// 币种每日收盘价历史
+///
+/// 同一天内多次采集时以最后一次为准，供均线、新高新低等需要日线数据的分析使用
+pub struct PriceHistory {
+    /// 每日收盘价
+    closes: RwLock<DailyCloses>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<DailyCloses>>,
+    /// 每个币种最多保留的天数
+    max_days: usize,
+    /// 最后一次写入状态文件的时间
+    last_saved: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl PriceHistory {
+    /// 创建内存中的价格历史（不持久化）
+    pub fn new(max_days: usize) -> Self {
+        Self {
+            closes: RwLock::new(HashMap::new()),
+            store: None,
+            max_days,
+            last_saved: Mutex::new(None),
+        }
+    }
+
+    /// 从状态文件加载价格历史
+    pub async fn open(path: impl Into<PathBuf>, max_days: usize) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let closes: DailyCloses = store.load().await?;
+        info!("📅 已加载 {} 个币种的日线价格历史", closes.len());
+        Ok(Self {
+            closes: RwLock::new(closes),
+            store: Some(store),
+            max_days,
+            last_saved: Mutex::new(None),
+        })
+    }
+
+    /// 记录一次价格采集
+    ///
+    /// 新增一天的收盘价时立即写入状态文件；只更新当天收盘价时每 `SAVE_INTERVAL_MINUTES` 分钟最多写入一次，
+    /// 重启时最多丢失这段时间内的当天价格更新
+    ///
+    /// # 参数
+    /// * `coin_id` - 币种ID
+    /// * `price` - 价格
+    /// * `timestamp` - 采集时间
+    pub async fn record(&self, coin_id: &str, price: f64, timestamp: DateTime<Utc>) -> Result<()> {
+        if !price.is_finite() || price <= 0.0 {
+            return Ok(());
+        }
+
+        let mut closes = self.closes.write().await;
+        let days = closes.entry(coin_id.to_string()).or_default();
+        let new_day = days.insert(timestamp.date_naive(), price).is_none();
+        while days.len() > self.max_days {
+            days.pop_first();
+        }
+
+        if let Some(store) = &self.store {
+            let mut last_saved = self.last_saved.lock().await;
+            let due = last_saved.is_none_or(|saved| timestamp - saved >= Duration::minutes(SAVE_INTERVAL_MINUTES));
+            if new_day || due {
+                store.save(&closes).await?;
+                *last_saved = Some(timestamp);
+            }
+        }
+        debug!("📅 记录 {} 日线价格: {}", coin_id, price);
+        Ok(())
+    }
+
+    /// 回填历史收盘价
+    ///
+    /// 只补充缺失的日期，已采集的收盘价保持不变
+    ///
+    /// # 参数
+    /// * `coin_id` - 币种ID
+    /// * `closes` - （日期，收盘价）
+    ///
+    /// # 返回
+    /// * `Result<usize>` - 新增的天数
+    pub async fn backfill(&self, coin_id: &str, closes: impl IntoIterator<Item = (NaiveDate, f64)>) -> Result<usize> {
+        let mut all = self.closes.write().await;
+        let days = all.entry(coin_id.to_string()).or_default();
+        let mut added = 0;
+        for (date, price) in closes {
+            if price.is_finite() && price > 0.0 && !days.contains_key(&date) {
+                days.insert(date, price);
+                added += 1;
+            }
+        }
+        while days.len() > self.max_days {
+            days.pop_first();
+        }
+
+        if added > 0 {
+            if let Some(store) = &self.store {
+                store.save(&all).await?;
+            }
+        }
+        Ok(added)
+    }
+
+    /// 币种已有的日线天数
+    pub async fn day_count(&self, coin_id: &str) -> usize {
+        self.closes.read().await.get(coin_id).map_or(0, BTreeMap::len)
+    }
+
+    /// 获取指定日期之前的每日收盘价
+    ///
+    /// # 参数
+    /// * `coin_id` - 币种ID
+    /// * `before` - 截止日期（不包含）
+    ///
+    /// # 返回
+    /// * `Vec<f64>` - 按日期升序排列的收盘价
+    pub async fn closes_before(&self, coin_id: &str, before: NaiveDate) -> Vec<f64> {
+        self.closes.read().await
+            .get(coin_id)
+            .map(|days| days.range(..before).map(|(_, price)| *price).collect())
+            .unwrap_or_default()
+    }
+
+    /// 获取币种的全部每日收盘价
+    pub async fn daily_closes(&self, coin_id: &str) -> Vec<(NaiveDate, f64)> {
+        self.closes.read().await
+            .get(coin_id)
+            .map(|days| days.iter().map(|(date, price)| (*date, *price)).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_backfill_keeps_recorded_closes() {
+        let history = PriceHistory::new(3);
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        history.record("hype", 25.0, day(3).and_hms_opt(12, 0, 0).unwrap().and_utc()).await.unwrap();
+
+        let added = history.backfill("hype", [(day(1), 20.0), (day(2), 22.0), (day(3), 99.0), (day(4), -1.0)]).await.unwrap();
+        assert_eq!(added, 2);
+        assert_eq!(history.daily_closes("hype").await, vec![(day(1), 20.0), (day(2), 22.0), (day(3), 25.0)]);
+        assert_eq!(history.day_count("hype").await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_record_saves_new_days_and_debounces_updates() {
+        let path = std::env::temp_dir().join(format!("everscan-prices-{}.json", uuid::Uuid::new_v4()));
+        let history = PriceHistory::open(&path, 10).await.unwrap();
+        let at = |d: u32, h: u32, m: u32| NaiveDate::from_ymd_opt(2024, 1, d).unwrap().and_hms_opt(h, m, 0).unwrap().and_utc();
+        let saved = || async { PriceHistory::open(&path, 10).await.unwrap().daily_closes("hype").await };
+
+        history.record("hype", 25.0, at(1, 12, 0)).await.unwrap();
+        history.record("hype", 26.0, at(1, 12, 5)).await.unwrap();
+        assert_eq!(saved().await, vec![(at(1, 0, 0).date_naive(), 25.0)]);
+
+        history.record("hype", 27.0, at(1, 12, 10)).await.unwrap();
+        assert_eq!(saved().await, vec![(at(1, 0, 0).date_naive(), 27.0)]);
+
+        history.record("hype", 28.0, at(2, 0, 1)).await.unwrap();
+        assert_eq!(saved().await.len(), 2);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,195 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tracing::{info, debug};
+
+use crate::storage::{JsonFileStore, Reloadable};
+
+/// 默认保留的排名天数
+pub const DEFAULT_RANK_HISTORY_DAYS: usize = 400;
+
+/// 每日排名：币种ID -> 日期(UTC) -> 当日最后一次采集的市值排名
+type DailyRanks = HashMap<String, BTreeMap<NaiveDate, u64>>;
+
+/// 排名数据点
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankPoint {
+    /// 日期（UTC）
+    pub date: NaiveDate,
+    /// 市值排名
+    pub rank: u64,
+}
+
+/// 排名上升的币种
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankClimber {
+    /// 币种ID
+    pub coin_id: String,
+    /// 最新排名
+    pub rank: u64,
+    /// 比较日期的排名
+    pub previous_rank: u64,
+    /// 上升的名次
+    pub change: i64,
+    /// 最新排名的日期
+    pub date: NaiveDate,
+    /// 比较日期（不晚于最新日期减去统计天数的最近一次记录）
+    pub previous_date: NaiveDate,
+}
+
+/// 币种每日市值排名历史
+///
+/// 记录CMC上市信息和监控币种的 `cmc_rank`，同一天内多次采集时以最后一次为准，
+/// 币种ID为小写符号（与市场数据的币种ID一致）
+pub struct RankHistory {
+    /// 每日排名
+    ranks: RwLock<DailyRanks>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<DailyRanks>>,
+    /// 每个币种最多保留的天数
+    max_days: usize,
+}
+
+impl RankHistory {
+    /// 创建内存中的排名历史（不持久化）
+    pub fn new(max_days: usize) -> Self {
+        Self {
+            ranks: RwLock::new(HashMap::new()),
+            store: None,
+            max_days,
+        }
+    }
+
+    /// 从状态文件加载排名历史
+    pub async fn open(path: impl Into<PathBuf>, max_days: usize) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let ranks: DailyRanks = store.load().await?;
+        info!("🏅 已加载 {} 个币种的排名历史", ranks.len());
+        Ok(Self {
+            ranks: RwLock::new(ranks),
+            store: Some(store),
+            max_days,
+        })
+    }
+
+    /// 记录一批币种的排名
+    ///
+    /// # 参数
+    /// * `ranks` - （币种ID，排名）
+    /// * `timestamp` - 采集时间
+    pub async fn record(&self, ranks: &[(String, u64)], timestamp: DateTime<Utc>) -> Result<()> {
+        if ranks.is_empty() {
+            return Ok(());
+        }
+
+        let date = timestamp.date_naive();
+        let mut all = self.ranks.write().await;
+        for (coin_id, rank) in ranks {
+            let days = all.entry(coin_id.to_lowercase()).or_default();
+            days.insert(date, *rank);
+            while days.len() > self.max_days {
+                days.pop_first();
+            }
+        }
+
+        if let Some(store) = &self.store {
+            store.save(&all).await?;
+        }
+        debug!("🏅 记录 {} 个币种的排名", ranks.len());
+        Ok(())
+    }
+
+    /// 获取币种的每日排名
+    ///
+    /// # 参数
+    /// * `coin_id` - 币种ID
+    /// * `since` - 起始日期（包含，可选）
+    ///
+    /// # 返回
+    /// * `Vec<RankPoint>` - 按日期升序排列的排名
+    pub async fn history(&self, coin_id: &str, since: Option<NaiveDate>) -> Vec<RankPoint> {
+        let since = since.unwrap_or(NaiveDate::MIN);
+        self.ranks.read().await
+            .get(&coin_id.to_lowercase())
+            .map(|days| days.range(since..).map(|(date, rank)| RankPoint { date: *date, rank: *rank }).collect())
+            .unwrap_or_default()
+    }
+
+    /// 统计一段时间内排名上升最多的币种
+    ///
+    /// 只统计在 `today` 或前一天仍有排名记录的币种（已跌出榜单的币种不参与），
+    /// 与最新记录日期减去 `days` 天当天或之前最近一次记录比较
+    ///
+    /// # 参数
+    /// * `days` - 统计天数
+    /// * `limit` - 最多返回数量
+    /// * `today` - 当前日期
+    ///
+    /// # 返回
+    /// * `Vec<RankClimber>` - 按上升名次降序排列
+    pub async fn climbers(&self, days: i64, limit: usize, today: NaiveDate) -> Vec<RankClimber> {
+        let fresh_since = today - chrono::Duration::days(1);
+        let mut climbers: Vec<RankClimber> = self.ranks.read().await
+            .iter()
+            .filter_map(|(coin_id, ranks)| {
+                let (date, rank) = ranks.range(fresh_since..=today).next_back()?;
+                let (previous_date, previous_rank) = ranks.range(..=*date - chrono::Duration::days(days)).next_back()?;
+                let change = *previous_rank as i64 - *rank as i64;
+                (change > 0).then(|| RankClimber {
+                    coin_id: coin_id.clone(),
+                    rank: *rank,
+                    previous_rank: *previous_rank,
+                    change,
+                    date: *date,
+                    previous_date: *previous_date,
+                })
+            })
+            .collect();
+        climbers.sort_by(|a, b| b.change.cmp(&a.change).then(a.rank.cmp(&b.rank)));
+        climbers.truncate(limit);
+        climbers
+    }
+}
+
+#[async_trait]
+impl Reloadable for RankHistory {
+    fn name(&self) -> &str {
+        "rank_history"
+    }
+
+    async fn reload(&self) -> Result<()> {
+        if let Some(store) = &self.store {
+            *self.ranks.write().await = store.load().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_climbers_compare_with_a_week_ago() {
+        let history = RankHistory::new(30);
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        let at = |d: u32| day(d).and_hms_opt(12, 0, 0).unwrap().and_utc();
+        let ranks = |list: &[(&str, u64)]| list.iter().map(|(id, r)| (id.to_string(), *r)).collect::<Vec<_>>();
+
+        history.record(&ranks(&[("SOL", 8), ("hype", 30), ("pepe", 40), ("luna", 50)]), at(1)).await.unwrap();
+        history.record(&ranks(&[("sol", 5), ("hype", 12), ("pepe", 45)]), at(9)).await.unwrap();
+
+        let climbers = history.climbers(7, 10, day(9)).await;
+        let summary: Vec<(&str, i64)> = climbers.iter().map(|c| (c.coin_id.as_str(), c.change)).collect();
+        assert_eq!(summary, vec![("hype", 18), ("sol", 3)]);
+        assert_eq!(climbers[0].previous_date, day(1));
+
+        assert_eq!(history.history("HYPE", Some(day(2))).await, vec![RankPoint { date: day(9), rank: 12 }]);
+        // 已跌出榜单的币种不参与统计
+        assert!(history.climbers(7, 10, day(20)).await.is_empty());
+    }
+}
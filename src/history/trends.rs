@@ -0,0 +1,180 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tracing::{info, debug};
+
+use crate::storage::{JsonFileStore, Reloadable};
+
+/// 某个搜索词的每周热度
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct StoredSeries {
+    /// 最后一次获取时间
+    fetched_at: Option<DateTime<Utc>>,
+    /// 每周起始日期 -> 热度（0-100）
+    weeks: BTreeMap<NaiveDate, f64>,
+}
+
+/// 每周热度数据点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendPoint {
+    /// 每周起始日期
+    pub week: NaiveDate,
+    /// 热度（0-100，以该序列峰值为100）
+    pub value: f64,
+}
+
+/// 搜索词热度序列及逆向分析指标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendSeries {
+    /// 搜索词
+    pub term: String,
+    /// 最后一次获取时间
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// 最新一周热度
+    pub latest: Option<f64>,
+    /// 相对4周前的变化
+    pub change_4w: Option<f64>,
+    /// 最新热度在整个序列中的百分位（0-100，越高说明关注度越接近历史极值）
+    pub percentile: Option<f64>,
+    /// 每周热度（按日期升序）
+    pub points: Vec<TrendPoint>,
+}
+
+impl TrendSeries {
+    /// 由存储的序列计算分析指标
+    fn from_stored(term: &str, stored: &StoredSeries) -> Self {
+        let values: Vec<f64> = stored.weeks.values().copied().collect();
+        let latest = values.last().copied();
+        let change_4w = values.len().checked_sub(5).map(|i| values[values.len() - 1] - values[i]);
+        let percentile = latest.map(|latest| {
+            let below = values.iter().filter(|v| **v <= latest).count();
+            below as f64 / values.len() as f64 * 100.0
+        });
+        Self {
+            term: term.to_string(),
+            fetched_at: stored.fetched_at,
+            latest,
+            change_4w,
+            percentile,
+            points: stored.weeks.iter().map(|(week, value)| TrendPoint { week: *week, value: *value }).collect(),
+        }
+    }
+}
+
+/// Google Trends 搜索热度历史
+///
+/// Google 每次请求都会把序列重新归一化到0-100，不同请求的数值不能直接拼接，
+/// 因此每次获取都整体替换该搜索词的序列
+pub struct TrendsHistory {
+    /// 搜索词 -> 每周热度
+    series: RwLock<HashMap<String, StoredSeries>>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<HashMap<String, StoredSeries>>>,
+}
+
+impl TrendsHistory {
+    /// 创建内存中的热度历史（不持久化）
+    pub fn new() -> Self {
+        Self {
+            series: RwLock::new(HashMap::new()),
+            store: None,
+        }
+    }
+
+    /// 从状态文件加载热度历史
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let series: HashMap<String, StoredSeries> = store.load().await?;
+        info!("🔍 已加载 {} 个搜索词的热度历史", series.len());
+        Ok(Self {
+            series: RwLock::new(series),
+            store: Some(store),
+        })
+    }
+
+    /// 替换搜索词的每周热度
+    ///
+    /// # 参数
+    /// * `term` - 搜索词
+    /// * `weeks` - 每周起始日期和热度
+    /// * `fetched_at` - 获取时间
+    pub async fn replace(&self, term: &str, weeks: &[(NaiveDate, f64)], fetched_at: DateTime<Utc>) -> Result<TrendSeries> {
+        let stored = StoredSeries {
+            fetched_at: Some(fetched_at),
+            weeks: weeks.iter().copied().collect(),
+        };
+        let view = TrendSeries::from_stored(term, &stored);
+
+        let mut series = self.series.write().await;
+        series.insert(term.to_string(), stored);
+        if let Some(store) = &self.store {
+            store.save(&series).await?;
+        }
+        debug!("🔍 更新搜索词 {} 的 {} 周热度", term, weeks.len());
+        Ok(view)
+    }
+
+    /// 查询搜索词热度序列
+    ///
+    /// # 参数
+    /// * `term` - 只返回指定搜索词（可选，不区分大小写）
+    pub async fn series(&self, term: Option<&str>) -> Vec<TrendSeries> {
+        let series = self.series.read().await;
+        let mut result: Vec<TrendSeries> = series.iter()
+            .filter(|(name, _)| term.is_none_or(|wanted| name.eq_ignore_ascii_case(wanted)))
+            .map(|(name, stored)| TrendSeries::from_stored(name, stored))
+            .collect();
+        result.sort_by(|a, b| a.term.cmp(&b.term));
+        result
+    }
+}
+
+impl Default for TrendsHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Reloadable for TrendsHistory {
+    fn name(&self) -> &str {
+        "google_trends"
+    }
+
+    async fn reload(&self) -> Result<()> {
+        if let Some(store) = &self.store {
+            *self.series.write().await = store.load().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn test_replace_and_percentile() {
+        let history = TrendsHistory::new();
+        let start = NaiveDate::from_ymd_opt(2024, 1, 7).unwrap();
+        let weeks: Vec<(NaiveDate, f64)> = [20.0, 30.0, 100.0, 40.0, 35.0, 60.0]
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (start + Duration::weeks(i as i64), *v))
+            .collect();
+
+        let series = history.replace("bitcoin", &weeks, Utc::now()).await.unwrap();
+        assert_eq!(series.latest, Some(60.0));
+        assert_eq!(series.change_4w, Some(30.0));
+        assert_eq!(series.percentile.map(|p| p.round()), Some(83.0));
+
+        // 重新获取时整体替换
+        history.replace("bitcoin", &weeks[..2], Utc::now()).await.unwrap();
+        assert_eq!(history.series(Some("Bitcoin")).await[0].points.len(), 2);
+    }
+}
@@ -0,0 +1,302 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::Path;
+
+use crate::config::{AppConfig, ImportConfig};
+use crate::indicators::{indicator_metric_name, COIN_INDICATORS};
+use crate::models::{self, DataSource, MetricBuilder, MetricValue};
+use crate::storage::{MetricQuery, MetricStore};
+
+/// 每批写入的数据点数
+const IMPORT_BATCH_SIZE: usize = 1000;
+
+/// 导入选项
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// 配置文件路径
+    pub config_path: String,
+    /// CSV文件路径
+    pub file: String,
+    /// 目标指标（如 `price:bitcoin` 或指标存储中的名称）
+    pub metric: String,
+    /// 覆盖配置中的时间列
+    pub timestamp_column: Option<String>,
+    /// 覆盖配置中的数值列
+    pub value_column: Option<String>,
+    /// 覆盖配置中的时间格式
+    pub timestamp_format: Option<String>,
+    /// 覆盖配置中的数值单位
+    pub unit: Option<String>,
+    /// 只解析和校验，不写入
+    pub dry_run: bool,
+}
+
+impl ImportOptions {
+    /// 从命令行参数解析选项（不含子命令本身）
+    ///
+    /// 支持 `--file <path>`、`--metric <name>`、`--config <path>`、`--timestamp-column <列>`、
+    /// `--value-column <列>`、`--timestamp-format <格式>`、`--unit <单位>` 和 `--dry-run`
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut config_path = "config.toml".to_string();
+        let (mut file, mut metric) = (None, None);
+        let (mut timestamp_column, mut value_column, mut timestamp_format, mut unit) = (None, None, None, None);
+        let mut dry_run = false;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| args.next().ok_or_else(|| anyhow!("{} 需要指定参数值", name));
+            match arg.as_str() {
+                "--config" => config_path = value("--config")?,
+                "--file" => file = Some(value("--file")?),
+                "--metric" => metric = Some(value("--metric")?),
+                "--timestamp-column" => timestamp_column = Some(value("--timestamp-column")?),
+                "--value-column" => value_column = Some(value("--value-column")?),
+                "--timestamp-format" => timestamp_format = Some(value("--timestamp-format")?),
+                "--unit" => unit = Some(value("--unit")?),
+                "--dry-run" => dry_run = true,
+                other => bail!("未知参数: {}", other),
+            }
+        }
+
+        Ok(Self {
+            config_path,
+            file: file.ok_or_else(|| anyhow!("缺少 --file 参数"))?,
+            metric: metric.ok_or_else(|| anyhow!("缺少 --metric 参数"))?,
+            timestamp_column,
+            value_column,
+            timestamp_format,
+            unit,
+            dry_run,
+        })
+    }
+
+    /// 合并配置中的默认列映射和命令行覆盖项
+    fn mapping(&self, config: &ImportConfig) -> ImportConfig {
+        ImportConfig {
+            timestamp_column: self.timestamp_column.clone().unwrap_or_else(|| config.timestamp_column.clone()),
+            value_column: self.value_column.clone().unwrap_or_else(|| config.value_column.clone()),
+            timestamp_format: self.timestamp_format.clone().or_else(|| config.timestamp_format.clone()),
+            unit: self.unit.clone().or_else(|| config.unit.clone()),
+            ..config.clone()
+        }
+    }
+}
+
+/// 解析出的数据行
+#[derive(Debug, Clone, Default)]
+pub struct ParsedRows {
+    /// 数据点（按时间升序）
+    pub points: Vec<(DateTime<Utc>, f64)>,
+    /// 无法解析的行：（行号，原因）
+    pub rejected: Vec<(u64, String)>,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// 指标存储中的名称
+    pub metric_name: String,
+    /// 解析成功的行数
+    pub parsed: usize,
+    /// 写入的数据点数
+    pub imported: usize,
+    /// 已存在相同时间数据点而跳过的行数
+    pub duplicates: usize,
+    /// 超过保留期而跳过的行数
+    pub expired: usize,
+    /// 无法解析的行
+    pub rejected: Vec<(u64, String)>,
+}
+
+/// 把命令行中的指标名称转换为指标存储中的名称
+///
+/// `<指标>:<币种ID>` 形式且指标为标量币种指标（price、rsi）时映射为币种指标序列
+/// （如 `price:bitcoin` → `indicator_price_bitcoin`），供指标历史接口和图表使用；其余名称原样使用
+///
+/// # 参数
+/// * `metric` - 命令行中的指标名称
+///
+/// # 返回
+/// * `Result<String>` - 指标存储中的名称，名称为空或指向复合指标时返回错误
+pub fn storage_metric_name(metric: &str) -> Result<String> {
+    let metric = metric.trim();
+    if metric.is_empty() {
+        bail!("指标名称不能为空");
+    }
+    match metric.split_once(':') {
+        Some(("bollinger", _)) => bail!("布林带是复合指标，无法从单列CSV导入"),
+        Some((indicator, coin_id)) if COIN_INDICATORS.contains(&indicator) && !coin_id.is_empty() => {
+            Ok(indicator_metric_name(coin_id, indicator))
+        }
+        _ => Ok(metric.to_string()),
+    }
+}
+
+/// 解析CSV内容
+///
+/// 空行和数值为空的行会被跳过并记入无法解析的行
+///
+/// # 参数
+/// * `reader` - CSV内容
+/// * `mapping` - 列映射
+///
+/// # 返回
+/// * `Result<ParsedRows>` - 映射的列不存在时返回错误
+pub fn parse_csv(reader: impl Read, mapping: &ImportConfig) -> Result<ParsedRows> {
+    let delimiter = u8::try_from(mapping.delimiter).map_err(|_| anyhow!("分隔符必须是ASCII字符: {}", mapping.delimiter))?;
+    let mut csv = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(mapping.has_header)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    let headers = if mapping.has_header { Some(csv.headers()?.clone()) } else { None };
+    let resolve = |column: &str| -> Result<usize> {
+        if let Some(index) = headers.as_ref().and_then(|h| h.iter().position(|name| name.eq_ignore_ascii_case(column))) {
+            return Ok(index);
+        }
+        column.parse().map_err(|_| anyhow!("CSV中没有名为 {} 的列", column))
+    };
+    let timestamp_index = resolve(&mapping.timestamp_column)?;
+    let value_index = resolve(&mapping.value_column)?;
+
+    let mut rows = ParsedRows::default();
+    for record in csv.records() {
+        let record = record.context("读取CSV失败")?;
+        let line = record.position().map(|p| p.line()).unwrap_or_default();
+        let parsed = record.get(timestamp_index)
+            .ok_or_else(|| anyhow!("缺少时间列"))
+            .and_then(|raw| parse_timestamp(raw, mapping.timestamp_format.as_deref()))
+            .and_then(|timestamp| {
+                let raw = record.get(value_index).filter(|v| !v.is_empty()).ok_or_else(|| anyhow!("缺少数值"))?;
+                let value: f64 = raw.replace(',', "").parse().map_err(|_| anyhow!("无效的数值: {}", raw))?;
+                Ok((timestamp, value))
+            });
+        match parsed {
+            Ok(point) => rows.points.push(point),
+            Err(e) => rows.rejected.push((line, e.to_string())),
+        }
+    }
+    rows.points.sort_by_key(|(timestamp, _)| *timestamp);
+    Ok(rows)
+}
+
+/// 解析时间字段
+///
+/// 指定格式时按格式解析（不含时区的按UTC解释），否则依次尝试RFC 3339、Unix秒/毫秒和常见日期格式
+fn parse_timestamp(raw: &str, format: Option<&str>) -> Result<DateTime<Utc>> {
+    if let Some(format) = format {
+        if let Ok(timestamp) = DateTime::parse_from_str(raw, format) {
+            return Ok(timestamp.with_timezone(&Utc));
+        }
+        if let Ok(timestamp) = NaiveDateTime::parse_from_str(raw, format) {
+            return Ok(timestamp.and_utc());
+        }
+        return NaiveDate::parse_from_str(raw, format)
+            .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+            .map_err(|_| anyhow!("时间 {} 与格式 {} 不匹配", raw, format));
+    }
+
+    models::parse_timestamp(raw)
+}
+
+/// 把CSV文件中的历史数据导入指标存储
+///
+/// 与已有数据点时间相同的行会被跳过，重复导入同一文件不会产生重复数据；
+/// 早于保留期的行也会跳过，否则会在下次加载时被丢弃
+///
+/// # 参数
+/// * `options` - 导入选项
+///
+/// # 返回
+/// * `Result<ImportReport>` - 导入结果
+pub async fn run(options: &ImportOptions) -> Result<ImportReport> {
+    let config = AppConfig::from_file(&options.config_path)?;
+    let mapping = options.mapping(&config.import);
+    let metric_name = storage_metric_name(&options.metric)?;
+
+    let file = std::fs::File::open(&options.file)
+        .with_context(|| format!("无法打开CSV文件: {}", options.file))?;
+    let rows = parse_csv(std::io::BufReader::new(file), &mapping)?;
+
+    let path = Path::new(&config.storage.data_dir).join("metrics.jsonl");
+    let store = MetricStore::open(&path, config.storage.metrics_retention_days).await?;
+    let existing: HashSet<DateTime<Utc>> = store.query(&metric_name, &MetricQuery::default()).await
+        .into_iter()
+        .map(|m| m.timestamp)
+        .collect();
+    let cutoff = Utc::now() - chrono::Duration::days(config.storage.metrics_retention_days as i64);
+
+    let mut report = ImportReport {
+        metric_name: metric_name.clone(),
+        parsed: rows.points.len(),
+        rejected: rows.rejected,
+        ..ImportReport::default()
+    };
+    let mut metrics = Vec::new();
+    let mut seen = HashSet::new();
+    for (timestamp, value) in rows.points {
+        if timestamp < cutoff {
+            report.expired += 1;
+            continue;
+        }
+        if existing.contains(&timestamp) || !seen.insert(timestamp) {
+            report.duplicates += 1;
+            continue;
+        }
+        let value = match &mapping.unit {
+            Some(unit) => MetricValue::scalar(value, unit.as_str()),
+            None => MetricValue::from(value),
+        };
+        metrics.push(
+            MetricBuilder::new(DataSource::IMPORT, metric_name.as_str())
+                .value(value)
+                .timestamp(timestamp)
+                .metadata(serde_json::json!({ "imported_from": options.file }))
+                .build()?
+        );
+    }
+
+    report.imported = metrics.len();
+    if !options.dry_run {
+        for batch in metrics.chunks(IMPORT_BATCH_SIZE) {
+            store.save_metrics(batch).await?;
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_csv_with_mapping() {
+        let csv = "Date;Close;Volume\n2024-01-02;\"45,100.5\";10\n2024-01-01;42000;12\nbad;1;1\n2024-01-03;;5\n";
+        let mapping = ImportConfig {
+            timestamp_column: "date".to_string(),
+            value_column: "1".to_string(),
+            delimiter: ';',
+            ..ImportConfig::default()
+        };
+        let rows = parse_csv(csv.as_bytes(), &mapping).unwrap();
+        assert_eq!(rows.points.len(), 2);
+        assert_eq!(rows.points[0], (Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(), 42000.0));
+        assert_eq!(rows.points[1].1, 45100.5);
+        assert_eq!(rows.rejected.iter().map(|(line, _)| *line).collect::<Vec<_>>(), vec![4, 5]);
+
+        let missing = ImportConfig { value_column: "price".to_string(), ..mapping };
+        assert!(parse_csv(csv.as_bytes(), &missing).is_err());
+
+        assert_eq!(parse_timestamp("1704067200000", None).unwrap(), parse_timestamp("2024-01-01T00:00:00Z", None).unwrap());
+        assert_eq!(parse_timestamp("01/02/2024", Some("%m/%d/%Y")).unwrap(), Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap());
+
+        assert_eq!(storage_metric_name("price:bitcoin").unwrap(), "indicator_price_bitcoin");
+        assert_eq!(storage_metric_name("fear_greed_index").unwrap(), "fear_greed_index");
+        assert!(storage_metric_name("bollinger:bitcoin").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+//! 技术指标计算
+//! 
+//! 所有函数都接收按时间升序排列的价格序列，数据不足一个周期时返回None
+
+use serde::{Deserialize, Serialize};
+
+/// 简单移动平均（最后一个周期）
+/// 
+/// # 参数
+/// * `prices` - 价格序列
+/// * `period` - 周期
+pub fn sma(prices: &[f64], period: usize) -> Option<f64> {
+    if period == 0 || prices.len() < period {
+        return None;
+    }
+    Some(prices[prices.len() - period..].iter().sum::<f64>() / period as f64)
+}
+
+/// 指数移动平均（以首个周期的SMA作为初始值）
+/// 
+/// # 参数
+/// * `prices` - 价格序列
+/// * `period` - 周期
+pub fn ema(prices: &[f64], period: usize) -> Option<f64> {
+    let seed = sma(&prices[..period.min(prices.len())], period)?;
+    let alpha = 2.0 / (period as f64 + 1.0);
+    Some(prices[period..].iter().fold(seed, |ema, price| alpha * price + (1.0 - alpha) * ema))
+}
+
+/// 相对强弱指数（Wilder平滑）
+/// 
+/// # 参数
+/// * `prices` - 价格序列（至少 `period + 1` 个点）
+/// * `period` - 周期，通常为14
+/// 
+/// # 返回
+/// * `Option<f64>` - 0-100之间的RSI值
+pub fn rsi(prices: &[f64], period: usize) -> Option<f64> {
+    if period == 0 || prices.len() <= period {
+        return None;
+    }
+    
+    let mut changes = prices.windows(2).map(|w| w[1] - w[0]);
+    let (mut avg_gain, mut avg_loss) = changes.by_ref().take(period).fold((0.0, 0.0), |(gain, loss), change| {
+        (gain + change.max(0.0), loss + (-change).max(0.0))
+    });
+    avg_gain /= period as f64;
+    avg_loss /= period as f64;
+    
+    for change in changes {
+        avg_gain = (avg_gain * (period - 1) as f64 + change.max(0.0)) / period as f64;
+        avg_loss = (avg_loss * (period - 1) as f64 + (-change).max(0.0)) / period as f64;
+    }
+    
+    if avg_loss == 0.0 {
+        return Some(if avg_gain == 0.0 { 50.0 } else { 100.0 });
+    }
+    Some(100.0 - 100.0 / (1.0 + avg_gain / avg_loss))
+}
+
+/// 布林带
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BollingerBands {
+    /// 上轨
+    pub upper: f64,
+    /// 中轨（SMA）
+    pub middle: f64,
+    /// 下轨
+    pub lower: f64,
+}
+
+/// 计算布林带（最后一个周期）
+/// 
+/// # 参数
+/// * `prices` - 价格序列
+/// * `period` - 周期，通常为20
+/// * `multiplier` - 标准差倍数，通常为2
+pub fn bollinger_bands(prices: &[f64], period: usize, multiplier: f64) -> Option<BollingerBands> {
+    let middle = sma(prices, period)?;
+    let window = &prices[prices.len() - period..];
+    let variance = window.iter().map(|p| (p - middle).powi(2)).sum::<f64>() / period as f64;
+    let band = variance.sqrt() * multiplier;
+    Some(BollingerBands {
+        upper: middle + band,
+        middle,
+        lower: middle - band,
+    })
+}
+
+/// 按币种保存历史序列的指标
+pub const COIN_INDICATORS: &[&str] = &["price", "rsi", "bollinger"];
+
+/// 币种指标在指标存储中的名称（如 `indicator_rsi_hype`）
+/// 
+/// # 参数
+/// * `coin_id` - 币种ID
+/// * `indicator` - 指标名称，见 [`COIN_INDICATORS`]
+pub fn indicator_metric_name(coin_id: &str, indicator: &str) -> String {
+    format!("indicator_{}_{}", indicator.to_lowercase(), coin_id.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indicators_on_known_series() {
+        let prices: Vec<f64> = (1..=20).map(|p| p as f64).collect();
+        assert_eq!(sma(&prices, 5), Some(18.0));
+        assert_eq!(ema(&prices, 5), Some(18.0), "线性序列的EMA收敛到SMA");
+        assert_eq!(rsi(&prices, 14), Some(100.0), "只涨不跌时RSI为100");
+        assert_eq!(rsi(&prices[..10], 14), None);
+        
+        let flat = vec![10.0; 30];
+        let bands = bollinger_bands(&flat, 20, 2.0).unwrap();
+        assert_eq!((bands.upper, bands.middle, bands.lower), (10.0, 10.0, 10.0));
+        
+        let zigzag: Vec<f64> = (0..30).map(|i| if i % 2 == 0 { 10.0 } else { 11.0 }).collect();
+        let value = rsi(&zigzag, 14).unwrap();
+        assert!((45.0..=55.0).contains(&value), "涨跌交替时RSI接近50: {}", value);
+    }
+}
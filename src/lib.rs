@@ -1,5 +1,32 @@
+pub mod alerts;
+pub mod analytics;
+pub mod audit;
+pub mod build_info;
+pub mod calendar;
 pub mod config;
+pub mod doctor;
+pub mod events;
+pub mod feature_flags;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod history;
+pub mod import;
+pub mod indicators;
+pub mod logging;
+pub mod maintenance;
 pub mod clients;
 pub mod models;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+#[cfg(feature = "plugins")]
+pub mod plugins;
+pub mod portfolio;
+pub mod secrets;
+pub mod startup;
+pub mod storage;
 pub mod tasks;
+pub mod tenants;
+pub mod users;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 pub mod web; 
\ No newline at end of file
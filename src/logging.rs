@@ -0,0 +1,103 @@
+use anyhow::{Result, Context};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer, Registry};
+
+use crate::config::{LogFormat, LogRotation, LoggingConfig};
+use crate::secrets::RedactingMakeWriter;
+
+/// 日志层类型
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// 初始化日志系统
+/// 
+/// 控制台和文件输出都会经过脱敏写入器
+/// 
+/// # 参数
+/// * `config` - 日志配置
+/// 
+/// # 返回
+/// * `Result<Option<WorkerGuard>>` - 启用文件输出时返回后台写入线程的守卫，需要保持到程序退出
+pub fn init(config: &LoggingConfig) -> Result<Option<WorkerGuard>> {
+    let mut layers: Vec<BoxedLayer> = vec![
+        format_layer(config.format, true, RedactingMakeWriter::new(std::io::stdout)),
+    ];
+    
+    let guard = match &config.file {
+        Some(file) => {
+            // 先创建目录，避免清理旧文件时读取目录失败
+            std::fs::create_dir_all(&file.directory)
+                .with_context(|| format!("无法创建日志目录: {}", file.directory))?;
+            
+            let mut builder = RollingFileAppender::builder()
+                .rotation(rotation(file.rotation))
+                .filename_prefix(&file.prefix);
+            if file.max_files > 0 {
+                builder = builder.max_log_files(file.max_files);
+            }
+            let appender = builder
+                .build(&file.directory)
+                .with_context(|| format!("无法创建日志文件: {}", file.directory))?;
+            
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            layers.push(format_layer(
+                file.format.unwrap_or(config.format),
+                false,
+                RedactingMakeWriter::new(writer),
+            ));
+            Some(guard)
+        }
+        None => None,
+    };
+    
+    tracing_subscriber::registry()
+        .with(layers)
+        .with(env_filter(config)?)
+        .try_init()
+        .context("日志系统已初始化")?;
+    
+    Ok(guard)
+}
+
+/// 构建日志过滤器
+/// 
+/// 设置了 RUST_LOG 时直接使用，否则由默认级别和模块覆盖组合而成
+pub(crate) fn env_filter(config: &LoggingConfig) -> Result<EnvFilter> {
+    if std::env::var(EnvFilter::DEFAULT_ENV).is_ok() {
+        return Ok(EnvFilter::from_default_env());
+    }
+    
+    let mut directives = vec![config.level.clone()];
+    let mut modules: Vec<_> = config.modules.iter().collect();
+    modules.sort();
+    directives.extend(modules.into_iter().map(|(module, level)| format!("{}={}", module, level)));
+    
+    EnvFilter::try_new(directives.join(","))
+        .with_context(|| format!("无效的日志级别配置: {}", directives.join(",")))
+}
+
+/// 创建指定格式的输出层
+fn format_layer<W>(format: LogFormat, ansi: bool, writer: W) -> BoxedLayer
+where
+    W: for<'a> fmt::MakeWriter<'a> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => fmt::layer().with_ansi(ansi).with_writer(writer).boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_current_span(false)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+/// 转换轮转周期
+fn rotation(rotation: LogRotation) -> Rotation {
+    match rotation {
+        LogRotation::Minutely => Rotation::MINUTELY,
+        LogRotation::Hourly => Rotation::HOURLY,
+        LogRotation::Daily => Rotation::DAILY,
+        LogRotation::Never => Rotation::NEVER,
+    }
+}
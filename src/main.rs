@@ -5,110 +5,309 @@ use std::time::Duration;
 use tokio::signal;
 use tracing::{info, error};
 use tower_http::cors::CorsLayer;
-use tower_http::services::ServeDir;
-
-mod config;
-mod clients;
-mod models;
-mod tasks;
-mod web;
-
-use config::AppConfig;
-use clients::CoinMarketCapClient;
-use tasks::{
-    TaskManager,
+
+use everscan::alerts::{
+    AlertManager, AlertPolicy, AlertRuleStore, AnomalyDetector, AnomalyLog, InactiveCoinMonitor, PriceDivergenceMonitor, RuleEvaluator, StalenessMonitor, EventReminder,
+    PriceWatcher, WatchStore,
+    WebhookNotifier, DiscordNotifier, TelegramNotifier,
+};
+use everscan::audit::AuditLog;
+use everscan::build_info::BuildInfo;
+use everscan::calendar::EconomicCalendar;
+use everscan::config::{AppConfig, LoggingConfig};
+use everscan::doctor::{self, DoctorOptions};
+use everscan::import::{self, ImportOptions};
+use everscan::maintenance::{self, BackupScheduler, RestoreOptions, S3Client};
+use everscan::history::{AltcoinSeasonHistory, ChangeHistory, EtfFlowHistory, FearGreedHistory, HistoryRecorder, LiquidationHistory, PriceBackfill, PriceHistory, RankHistory, TrendsHistory, DEFAULT_PRICE_HISTORY_DAYS, DEFAULT_RANK_HISTORY_DAYS};
+use everscan::feature_flags::FeatureFlags;
+use everscan::events::{DeferredSink, EventBus, EventBusNotifier};
+use everscan::storage::{MetricArchive, MetricArchiver, MetricForwarder, MetricStore, QueryLimits, ReplicaSync, SnapshotWriter};
+use everscan::clients::{CalendarClient, ChainClient, CoinGeckoClient, CoinglassClient, CoinMarketCapClient, DeribitClient, EtfFlowClient, ExchangeTickerClient, GlassnodeClient, GoogleTrendsClient, LunarCrushClient, NodeRpcClient, OpenSeaClient, StakingClient};
+use everscan::tasks::{
+    JobQueue, TaskManager, DerivedMetricEngine,
     CryptoMarketTaskBuilder,
     FearGreedTaskBuilder,
     AltcoinSeasonTaskBuilder,
+    PortfolioValuationTask,
+    ExchangeSyncTask,
+    MarketBreadthTask, RatioTask, SectorTask,
+    BtcCycleTask,
+    ExchangeFlowTask,
+    OptionsTask,
+    EtfFlowTask,
+    LiquidationTask,
+    LiquidityTask,
+    NftTask,
+    NetworkTask,
+    NodeHealthTask,
+    OperationalMetricsTask,
+    FormulaTrialTask,
+    EconomicCalendarTask,
+    SocialSentimentTask,
+    GoogleTrendsTask,
+    NewListingTask,
 };
-use web::{api::create_api_routes, cache::DataCache};
+use everscan::portfolio::{ExchangeAccountStore, PortfolioStore};
+use everscan::logging;
+use everscan::secrets::SecretBox;
+use everscan::startup::{wait_for_market_data, Readiness};
+use everscan::tenants::{CoinUniverse, TenantRegistry};
+use everscan::users::{AuthService, UserStore};
+use everscan::web::{versioning::create_versioned_api_routes, assets::create_asset_routes, pages::create_page_routes, feeds::create_feed_routes, prometheus::create_prometheus_routes, cache::DataCache, refresh_api::RefreshLimiter, ingest_api::IngestSources, websocket::{websocket_handler, WebSocketGuard, WebSocketHub}, listener::{serve, BindTarget, Listener, Rebinder}, maintenance_mode::MaintenanceMode, read_only::ReadOnlyMode, AppState};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // 加载环境变量
     dotenv::dotenv().ok();
     
-    // 初始化日志系统
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
+    // `everscan doctor [--config <path>] [--offline] [--strict]` 运行启动自检
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("doctor") {
+        return run_doctor(DoctorOptions::from_args(args.into_iter().skip(1))?).await;
+    }
+
+    // `everscan import --file <csv> --metric <name> [...]` 导入历史数据
+    if args.first().map(String::as_str) == Some("import") {
+        return run_import(ImportOptions::from_args(args.into_iter().skip(1))?).await;
+    }
+
+    // `everscan backup [--config <path>]` 立即备份数据目录
+    if args.first().map(String::as_str) == Some("backup") {
+        return run_backup(&config_path_arg(&args[1..])?).await;
+    }
 
-    info!("🚀 启动 EverScan 区块链数据聚合平台");
+    // `everscan restore --file <archive> [--from-s3] [--force]` 从归档恢复数据目录
+    if args.first().map(String::as_str) == Some("restore") {
+        return run_restore(RestoreOptions::from_args(args.into_iter().skip(1))?).await;
+    }
     
-    // 调试：检查API密钥是否被加载
-    if let Ok(api_key) = env::var("COINMARKETCAP_API_KEY") {
-        info!("✅ CoinMarketCap API密钥已加载: {}...", &api_key[..8]);
+    // 兼容旧的测试模式环境变量
+    if env::var("EVERSCAN_TEST_MODE").is_ok() {
+        eprintln!("⚠️ EVERSCAN_TEST_MODE 已弃用，请改用 `everscan doctor`");
+        return run_doctor(DoctorOptions::default()).await;
+    }
+    
+    // 先加载配置以便按 [logging] 初始化日志系统
+    let config = AppConfig::from_file("config.toml");
+    let logging_config = config.as_ref().map(|c| c.logging.clone()).unwrap_or_default();
+    
+    // 初始化日志系统（文件输出的守卫需要保持到程序退出）
+    let _log_guard = logging::init(&logging_config)?;
+
+    info!("🚀 启动 EverScan 区块链数据聚合平台 {}", BuildInfo::current().describe());
+    
+    // 检查API密钥是否被加载（不输出密钥内容）
+    if env::var("COINMARKETCAP_API_KEY").is_ok() {
+        info!("✅ CoinMarketCap API密钥已加载");
     } else {
         error!("❌ CoinMarketCap API密钥未找到");
     }
 
-    // 检查是否为测试模式
-    if env::var("EVERSCAN_TEST_MODE").is_ok() {
-        info!("🧪 运行在测试模式");
-        run_test_mode().await?;
-        return Ok(());
-    }
+    // `everscan --api-only` 以只读API模式运行：不采集数据，从共享数据目录读取采集实例写入的数据
+    let api_only = args.iter().any(|arg| arg == "--api-only");
 
     // 生产模式
-    run_production_mode().await
+    run_production_mode(config?, api_only).await
 }
 
-/// 测试模式 - 仅测试API连接
-async fn run_test_mode() -> Result<()> {
-    info!("🔧 初始化测试环境");
-
-    // 从环境变量获取API密钥
-    let api_key = env::var("COINMARKETCAP_API_KEY").ok();
+/// 自检模式 - 检查配置、存储和数据源，按结果设置退出码
+async fn run_doctor(options: DoctorOptions) -> Result<()> {
+    // 只输出警告和错误日志，避免打断检查结果表格
+    let _log_guard = logging::init(&LoggingConfig {
+        level: "warn".to_string(),
+        ..LoggingConfig::default()
+    })?;
+
+    println!("🩺 EverScan 自检\n");
+    let report = doctor::run(&options).await;
+    print!("{}", report.render());
     
-    // 创建客户端
-    let coinmarketcap_client = Arc::new(CoinMarketCapClient::new(api_key, Duration::from_secs(30))?);
+    std::process::exit(report.exit_code(options.strict));
+}
 
-    // 测试CoinMarketCap贪婪恐惧指数
-    info!("🧪 测试CoinMarketCap贪婪恐惧指数API");
-    match coinmarketcap_client.get_fear_greed_index().await {
-        Ok(fear_greed) => info!("✅ CoinMarketCap贪婪恐惧指数获取成功: {} - {}", fear_greed.value, fear_greed.value_classification),
-        Err(e) => error!("❌ CoinMarketCap贪婪恐惧指数获取失败: {}", e),
+/// 导入模式 - 把CSV历史数据写入指标存储后退出
+/// 
+/// 采集实例运行时导入的数据要在其重启后才会出现在查询结果中
+async fn run_import(options: ImportOptions) -> Result<()> {
+    let _log_guard = logging::init(&LoggingConfig {
+        level: "warn".to_string(),
+        ..LoggingConfig::default()
+    })?;
+
+    println!("📥 导入 {} → {}", options.file, options.metric);
+    let report = import::run(&options).await?;
+    for (line, reason) in report.rejected.iter().take(20) {
+        println!("  ⚠️ 第 {} 行: {}", line, reason);
+    }
+    if report.rejected.len() > 20 {
+        println!("  ……另有 {} 行无法解析", report.rejected.len() - 20);
     }
+    println!(
+        "{} 指标 {}：解析 {} 行，{} {} 个数据点，跳过重复 {} 行、超过保留期 {} 行，无法解析 {} 行",
+        if options.dry_run { "🔎" } else { "✅" },
+        report.metric_name,
+        report.parsed,
+        if options.dry_run { "可导入" } else { "导入" },
+        report.imported,
+        report.duplicates,
+        report.expired,
+        report.rejected.len(),
+    );
+    Ok(())
+}
 
-    // 测试山寨币季节指数
-    info!("🧪 测试山寨币季节指数");
-    match coinmarketcap_client.get_altcoin_season_index().await {
-        Ok(altcoin_season) => info!("✅ 山寨币季节指数获取成功: {} - {}", altcoin_season.value, altcoin_season.classification_zh),
-        Err(e) => error!("❌ 山寨币季节指数获取失败: {}", e),
+/// 解析只接受 `--config <path>` 的子命令参数
+fn config_path_arg(args: &[String]) -> Result<String> {
+    match args {
+        [] => Ok("config.toml".to_string()),
+        [flag, path] if flag == "--config" => Ok(path.clone()),
+        _ => anyhow::bail!("用法: everscan backup [--config <path>]"),
     }
+}
 
-    info!("🧪 测试模式完成");
+/// 备份模式 - 立即把数据目录打包到备份目录（配置了S3时同时上传）后退出
+async fn run_backup(config_path: &str) -> Result<()> {
+    let _log_guard = logging::init(&LoggingConfig {
+        level: "info".to_string(),
+        ..LoggingConfig::default()
+    })?;
+
+    let config = AppConfig::from_file(config_path)?;
+    let scheduler = BackupScheduler::new(&config.storage.data_dir, config.backup.clone())?;
+    let path = scheduler.backup_once().await?;
+    println!("✅ 已备份到 {}", path.display());
     Ok(())
 }
 
-/// 生产模式 - 完整功能
-async fn run_production_mode() -> Result<()> {
-    info!("🔧 初始化生产环境");
+/// 恢复模式 - 用归档替换数据目录后退出
+/// 
+/// 应在服务停止时运行
+async fn run_restore(options: RestoreOptions) -> Result<()> {
+    let _log_guard = logging::init(&LoggingConfig {
+        level: "warn".to_string(),
+        ..LoggingConfig::default()
+    })?;
+
+    println!("📦 从 {}{} 恢复数据目录", if options.from_s3 { "S3 " } else { "" }, options.file);
+    match maintenance::restore(&options).await? {
+        Some(previous) => println!("✅ 恢复完成，原数据目录已保留在 {}", previous.display()),
+        None => println!("✅ 恢复完成"),
+    }
+    Ok(())
+}
 
-    // 加载配置
-    let config = AppConfig::from_file("config.toml")?;
+/// 生产模式 - 完整功能
+/// 
+/// `api_only` 为true时不运行调度器、告警评估和历史记录，只提供API
+async fn run_production_mode(config: AppConfig, api_only: bool) -> Result<()> {
+    info!("🔧 初始化生产环境{}", if api_only { "（只读API模式）" } else { "" });
     info!("📖 配置加载成功");
 
     // 创建数据缓存
-    let cache = Arc::new(DataCache::new());
+    let cache = Arc::new(
+        DataCache::new()
+            .with_quality_config(config.data_quality.clone())
+            .with_divergence_threshold(config.price_divergence.threshold_pct)
+            .with_limits(config.cache.clone())
+    );
     info!("💾 数据缓存初始化完成");
 
     // 创建客户端
     let coinmarketcap_client = Arc::new(CoinMarketCapClient::new(
-        config.data_sources.coinmarketcap.api_key.clone(),
+        config.data_sources.coinmarketcap.api_key.as_ref().map(|k| k.expose().to_string()),
         Duration::from_secs(config.data_sources.coinmarketcap.timeout_seconds),
-    )?);
+    )?.with_fear_greed_provider(config.fear_greed.provider));
 
     info!("🔗 API客户端创建完成");
 
-    // 创建任务管理器
-    let mut task_manager = TaskManager::new();
+    // 打开指标存储和日线价格历史
+    let metrics_path = std::path::Path::new(&config.storage.data_dir).join("metrics.jsonl");
+    let metric_store = if api_only {
+        MetricStore::open_read_only(metrics_path, config.storage.metrics_retention_days).await?
+    } else {
+        MetricStore::open(metrics_path, config.storage.metrics_retention_days).await?
+    };
+    // 冷指标归档：起始时间早于热数据窗口的查询透明读取归档
+    let metric_archive = if config.archive.enabled {
+        let s3 = config.archive.s3.as_ref().map(S3Client::new).transpose()?;
+        Some(Arc::new(
+            MetricArchive::open(std::path::Path::new(&config.storage.data_dir).join("archive"), s3, config.archive.keep_local).await?
+        ))
+    } else {
+        None
+    };
+    let metric_store = metric_store
+        .with_slow_query_threshold(config.storage.slow_query_ms)
+        .with_query_cache(config.storage.query_cache_entries)
+        .with_query_limits(QueryLimits::new(
+            config.storage.max_query_points,
+            config.storage.max_query_range_days,
+            config.storage.query_timeout_ms,
+        ));
+    let metric_store = Arc::new(match &metric_archive {
+        Some(archive) => metric_store.with_archive(archive.clone()),
+        None => metric_store,
+    });
+    let prices = Arc::new(
+        PriceHistory::open(
+            std::path::Path::new(&config.storage.data_dir).join("price_history.json"),
+            DEFAULT_PRICE_HISTORY_DAYS,
+        ).await?
+    );
+    let ranks = Arc::new(
+        RankHistory::open(
+            std::path::Path::new(&config.storage.data_dir).join("rank_history.json"),
+            DEFAULT_RANK_HISTORY_DAYS,
+        ).await?
+    );
+
+    // 租户的关注币种与监控币种合并为采集范围
+    let tenants = Arc::new(
+        TenantRegistry::open(std::path::Path::new(&config.storage.data_dir).join("tenants.json"), &config.tenants).await?
+    );
+    let coins = CoinUniverse::new(config.monitoring.coins.clone(), config.backfill.coin_ids.clone())
+        .with_tenants(tenants.clone());
+
+    // 功能开关（管理员覆盖值保存在数据目录中）
+    let feature_flags = Arc::new(
+        FeatureFlags::open(config.feature_flags.clone(), std::path::Path::new(&config.storage.data_dir).join("feature_flags.json")).await?
+    );
+
+    // 分阶段启动：外部依赖在后台初始化并重试，不阻塞Web服务器启动
+    let readiness = Arc::new(Readiness::new());
+    tokio::spawn(wait_for_market_data(readiness.clone(), "market_data", cache.clone()));
+
+    // 首次启动时回填监控币种的日线价格，使RSI和布林带尽快可用（CoinGecko不可用时在后台重试）
+    if config.backfill.enabled && !api_only {
+        let backfill = PriceBackfill::new(
+            CoinGeckoClient::new(Duration::from_secs(30))?,
+            prices.clone(),
+            config.backfill.clone(),
+        );
+        readiness.register("price_backfill", false).await;
+        let (readiness, coins) = (readiness.clone(), coins.clone());
+        tokio::spawn(async move {
+            readiness.retry_until_ready("price_backfill", || async {
+                backfill.run(&coins.coingecko_ids().await).await
+            }).await;
+        });
+    }
+
+    // 创建任务管理器，任务产出的指标写入指标存储
+    let mut task_manager = TaskManager::new().with_metric_store(metric_store.clone());
+    let derived_metrics = Arc::new(DerivedMetricEngine::new(&config.derived_metrics, metric_store.clone())?);
+    if !config.derived_metrics.is_empty() {
+        task_manager = task_manager.with_derived_metrics(derived_metrics.clone());
+    }
 
     // 创建并注册任务
     let crypto_task = CryptoMarketTaskBuilder::new()
         .name("加密货币市场数据采集".to_string())
         .coinmarketcap_client(coinmarketcap_client.clone())
         .interval_seconds(config.monitoring.update_interval_seconds)
+        .price_history(prices.clone())
+        .coins(coins.clone())
+        .max_quote_age_hours(config.inactive_coins.max_quote_age_hours)
         .build()?;
 
     let fear_greed_task = FearGreedTaskBuilder::new()
@@ -121,45 +320,646 @@ async fn run_production_mode() -> Result<()> {
         .name("山寨币季节指数采集".to_string())
         .client(coinmarketcap_client.clone())
         .interval_seconds(3600) // 1小时
+        .formula(config.altcoin_season.formula)
         .build()?;
 
     task_manager.register_task(Box::new(crypto_task)).await?;
     task_manager.register_task(Box::new(fear_greed_task)).await?;
     task_manager.register_task(Box::new(altcoin_season_task)).await?;
 
-    info!("📋 任务注册完成，共 {} 个任务", task_manager.get_tasks().await.len());
+    // 交易所余额同步和投资组合估值在行情任务之后执行
+    let portfolio = Arc::new(
+        PortfolioStore::open(std::path::Path::new(&config.storage.data_dir).join("portfolio.json")).await?
+    );
+    let exchanges = Arc::new(
+        ExchangeAccountStore::open(
+            std::path::Path::new(&config.storage.data_dir).join("exchange_accounts.json"),
+            SecretBox::from_env(),
+        ).await?
+    );
+    let exchange_sync_task = ExchangeSyncTask::new(
+        "交易所余额同步".to_string(),
+        exchanges.clone(),
+        portfolio.clone(),
+        3600, // 1小时
+    );
+    task_manager.register_task(Box::new(exchange_sync_task)).await?;
+    let portfolio_task = PortfolioValuationTask::new(
+        "投资组合估值".to_string(),
+        portfolio.clone(),
+        3600, // 1小时
+    );
+    task_manager.register_task(Box::new(portfolio_task)).await?;
+    let breadth_task = MarketBreadthTask::new(
+        "市场广度统计".to_string(),
+        prices.clone(),
+        3600, // 1小时
+    );
+    task_manager.register_task(Box::new(breadth_task)).await?;
+    if !config.sectors.sectors.is_empty() {
+        let sector_task = SectorTask::new(
+            "板块统计".to_string(),
+            config.sectors.sectors.clone(),
+            config.sectors.interval_seconds,
+        );
+        task_manager.register_task(Box::new(sector_task)).await?;
+    }
+    if !config.ratios.ratios.is_empty() {
+        let ratio_task = RatioTask::new(
+            "价格比率计算".to_string(),
+            config.ratios.ratios.clone(),
+            config.ratios.lookback_days,
+            metric_store.clone(),
+            config.ratios.interval_seconds,
+        );
+        task_manager.register_task(Box::new(ratio_task)).await?;
+    }
+    let cycle_task = BtcCycleTask::new(
+        "比特币减半周期".to_string(),
+        Arc::new(ChainClient::new(Duration::from_secs(30))?),
+        coinmarketcap_client.clone(),
+        3600, // 1小时
+    );
+    task_manager.register_task(Box::new(cycle_task)).await?;
+    let options_task = OptionsTask::new(
+        "期权数据采集".to_string(),
+        Arc::new(DeribitClient::new(Duration::from_secs(30))?),
+        3600, // 1小时
+    );
+    task_manager.register_task(Box::new(options_task)).await?;
+    if let Some(url) = config.etf_flows.source_url.as_deref().filter(|url| !url.trim().is_empty()) {
+        let etf_flow_task = EtfFlowTask::new(
+            "现货ETF资金流向".to_string(),
+            Arc::new(EtfFlowClient::new(url, Duration::from_secs(30))?),
+            metric_store.clone(),
+            config.etf_flows.interval_seconds,
+        );
+        task_manager.register_task(Box::new(etf_flow_task)).await?;
+    }
+    if let Some(api_key) = config.data_sources.coinglass.api_key.as_ref().filter(|k| !k.is_empty()) {
+        let liquidation_task = LiquidationTask::new(
+            "合约爆仓数据".to_string(),
+            Arc::new(CoinglassClient::new(
+                api_key.expose(),
+                Duration::from_secs(config.data_sources.coinglass.timeout_seconds),
+            )?),
+            config.liquidations.symbols.clone(),
+            config.liquidations.exchanges.clone(),
+            config.liquidations.interval_seconds,
+        );
+        task_manager.register_task(Box::new(liquidation_task)).await?;
+    }
+    if !config.liquidity.symbols.is_empty() {
+        let liquidity_task = LiquidityTask::new(
+            "订单簿流动性快照".to_string(),
+            Arc::new(ExchangeTickerClient::new(Duration::from_secs(30))?),
+            config.liquidity.symbols.clone(),
+            config.liquidity.depth_pct,
+            config.liquidity.interval_seconds,
+        );
+        task_manager.register_task(Box::new(liquidity_task)).await?;
+    }
+    if let Some(api_key) = config.data_sources.opensea.api_key.as_ref().filter(|k| !k.is_empty()) {
+        if !config.nft.collections.is_empty() {
+            let nft_task = NftTask::new(
+                "NFT合集跟踪".to_string(),
+                Arc::new(OpenSeaClient::new(
+                    api_key.expose(),
+                    Duration::from_secs(config.data_sources.opensea.timeout_seconds),
+                )?),
+                config.nft.collections.clone(),
+                config.nft.interval_seconds,
+            );
+            task_manager.register_task(Box::new(nft_task)).await?;
+        }
+    }
+    if config.network.enabled {
+        let network_task = NetworkTask::new(
+            "网络健康采集".to_string(),
+            Arc::new(ChainClient::new(Duration::from_secs(30))?),
+            Arc::new(StakingClient::new(&config.network.solana_rpc_url, Duration::from_secs(30))?),
+            !config.network.solana_rpc_url.trim().is_empty(),
+            config.network.interval_seconds,
+        );
+        task_manager.register_task(Box::new(network_task)).await?;
+    }
+    if !config.nodes.nodes.is_empty() {
+        let node_task = NodeHealthTask::new(
+            "自建节点健康监控".to_string(),
+            Arc::new(NodeRpcClient::new(Duration::from_secs(10))?),
+            config.nodes.nodes.clone(),
+            config.nodes.interval_seconds,
+        );
+        task_manager.register_task(Box::new(node_task)).await?;
+    }
+    let calendar = Arc::new(
+        EconomicCalendar::open(std::path::Path::new(&config.storage.data_dir).join("economic_calendar.json")).await?
+    );
+    if !config.calendar.source_url.trim().is_empty() {
+        let calendar_task = EconomicCalendarTask::new(
+            "经济日历".to_string(),
+            Arc::new(CalendarClient::new(&config.calendar.source_url, Duration::from_secs(30))?),
+            calendar.clone(),
+            config.calendar.countries.clone(),
+            config.calendar.min_importance,
+            config.calendar.interval_seconds,
+        );
+        task_manager.register_task(Box::new(calendar_task)).await?;
+    }
+    if let Some(api_key) = config.data_sources.lunarcrush.api_key.as_ref().filter(|k| !k.is_empty()) {
+        let social_task = SocialSentimentTask::new(
+            "社交情绪采集".to_string(),
+            Arc::new(LunarCrushClient::new(
+                api_key.expose(),
+                Duration::from_secs(config.data_sources.lunarcrush.timeout_seconds),
+            )?),
+            feature_flags.clone(),
+            Duration::from_millis(config.data_sources.lunarcrush.request_interval_ms),
+            3600, // 1小时
+        );
+        task_manager.register_task(Box::new(social_task)).await?;
+    }
+    let trends = Arc::new(
+        TrendsHistory::open(std::path::Path::new(&config.storage.data_dir).join("google_trends.json")).await?
+    );
+    if !config.trends.terms.is_empty() {
+        let trends_task = GoogleTrendsTask::new(
+            "搜索热度采集".to_string(),
+            Arc::new(GoogleTrendsClient::new(&config.trends.geo, Duration::from_secs(30))?),
+            trends.clone(),
+            config.trends.terms.clone(),
+            config.trends.interval_seconds,
+        );
+        task_manager.register_task(Box::new(trends_task)).await?;
+    }
+    let changes = Arc::new(
+        ChangeHistory::open(
+            std::path::Path::new(&config.storage.data_dir).join("dataset_changes.json"),
+            config.changes.clone(),
+        ).await?
+    );
+    let listing_sources = &config.changes.new_listing_sources;
+    let has_cmc_key = config.data_sources.coinmarketcap.api_key.as_ref().is_some_and(|k| !k.is_empty());
+    let listing_coingecko = if listing_sources.iter().any(|s| s == "coingecko") {
+        Some(Arc::new(CoinGeckoClient::new(Duration::from_secs(30))?))
+    } else {
+        None
+    };
+    let listing_coinmarketcap = (listing_sources.iter().any(|s| s == "coinmarketcap") && has_cmc_key)
+        .then(|| coinmarketcap_client.clone());
+    if listing_coingecko.is_some() || listing_coinmarketcap.is_some() {
+        let new_listing_task = NewListingTask::new(
+            "新上线币种检测".to_string(),
+            listing_coingecko,
+            listing_coinmarketcap,
+            changes.clone(),
+            config.changes.new_listing_interval_seconds,
+        );
+        task_manager.register_task(Box::new(new_listing_task)).await?;
+    }
+    if let Some(api_key) = config.data_sources.glassnode.api_key.as_ref().filter(|k| !k.is_empty()) {
+        let glassnode_client = Arc::new(GlassnodeClient::new(
+            api_key.expose(),
+            Duration::from_secs(config.data_sources.glassnode.timeout_seconds),
+        )?);
+        let exchange_flow_task = ExchangeFlowTask::new(
+            "交易所资金流向".to_string(),
+            glassnode_client,
+            metric_store.clone(),
+            86400, // 每天
+        );
+        task_manager.register_task(Box::new(exchange_flow_task)).await?;
+    } else {
+        info!("ℹ️ 未配置Glassnode API密钥，跳过交易所资金流向任务");
+    }
+    #[cfg(feature = "plugins")]
+    for task in everscan::plugins::load_plugin_tasks(&config.plugins)? {
+        task_manager.register_task(Box::new(task)).await?;
+    }
+    #[cfg(feature = "wasm")]
+    for source in &config.custom_sources {
+        task_manager.register_task(Box::new(everscan::tasks::CustomSourceTask::new(source)?)).await?;
+    }
+    #[cfg(not(feature = "wasm"))]
+    if !config.custom_sources.is_empty() {
+        tracing::warn!("⚠️ 配置了 {} 个自定义数据源，但程序未启用 wasm 特性，已跳过", config.custom_sources.len());
+    }
+
+    // 公式并行比较（山寨币季节指数按缓存中的成分币种重新计算，注册在指数任务之后）
+    let trial = &config.formula_trial;
+    if trial.altcoin_season_until.is_some() || trial.sentiment_until.is_some() {
+        let trial_task = FormulaTrialTask::new(
+            "公式并行比较".to_string(),
+            trial.clone(),
+            config.altcoin_season.formula,
+            config.sentiment.clone(),
+        );
+        task_manager.register_task(Box::new(trial_task)).await?;
+    }
+
+    // 运行指标最后注册，记录其他任务的成功率和执行耗时
+    let operational_task = OperationalMetricsTask::new(
+        "运行指标记录".to_string(),
+        task_manager.clone(),
+        300, // 5分钟
+    );
+    task_manager.register_task(Box::new(operational_task)).await?;
+
+    // 只读API模式下由采集实例执行任务，本实例不注册任何任务
+    let task_manager = if api_only {
+        info!("📋 只读API模式，跳过任务注册");
+        TaskManager::new()
+    } else {
+        info!("📋 任务注册完成，共 {} 个任务", task_manager.get_tasks().await.len());
+        task_manager
+    };
+
+    // 创建告警管理器
+    let mut alert_manager = AlertManager::new()
+        .with_policy(AlertPolicy::from_config(&config.alerts))
+        .with_state_file(std::path::Path::new(&config.storage.data_dir).join("alert_state.json"))
+        .await?;
+    for webhook in &config.alerts.webhooks {
+        alert_manager.add_notifier(Arc::new(WebhookNotifier::from_config(webhook)?), webhook.min_severity);
+    }
+    for discord in &config.alerts.discord {
+        alert_manager.add_notifier(Arc::new(DiscordNotifier::from_config(discord)?), discord.min_severity);
+    }
+    for telegram in &config.alerts.telegram {
+        alert_manager.add_notifier(Arc::new(TelegramNotifier::from_config(telegram)?), telegram.min_severity);
+    }
+    // 事件总线：采集实例把告警、任务结果和缓存更新发布到Kafka/NATS（连接失败时在后台重试）
+    let event_sink = if config.event_bus.enabled && !api_only {
+        everscan::events::ensure_supported(&config.event_bus)?;
+        Some(Arc::new(DeferredSink::new()))
+    } else {
+        None
+    };
+    if let Some(sink) = event_sink.as_ref().filter(|_| config.event_bus.alerts) {
+        alert_manager.add_notifier(Arc::new(EventBusNotifier::new(sink.clone())), config.event_bus.alert_min_severity);
+    }
+    let alert_manager = Arc::new(alert_manager);
+
+    // 加载告警规则、价格目标监控和异常事件记录
+    let alert_rules = Arc::new(
+        AlertRuleStore::open(std::path::Path::new(&config.storage.data_dir).join("alert_rules.json")).await?
+    );
+    let watches = Arc::new(
+        WatchStore::open(std::path::Path::new(&config.storage.data_dir).join("watches.json")).await?
+    );
+    let anomalies = Arc::new(
+        AnomalyLog::open(std::path::Path::new(&config.storage.data_dir).join("anomalies.json")).await?
+    );
+
+    // 告警只由采集实例评估和发送，避免多个只读实例重复通知
+    if !api_only {
+        // 启动任务存活监控
+        let staleness_monitor = StalenessMonitor::new(
+            task_manager.clone(),
+            alert_manager.clone(),
+            config.alerts.staleness_multiplier,
+            Duration::from_secs(config.alerts.staleness_check_interval_seconds),
+        );
+        tokio::spawn(staleness_monitor.run());
+
+        // 启动规则评估
+        alert_rules.seed(&config.alerts.rules).await?;
+        let rule_evaluator = RuleEvaluator::new(
+            cache.clone(),
+            alert_rules.clone(),
+            alert_manager.clone(),
+            Duration::from_secs(config.alerts.rule_check_interval_seconds),
+        );
+        tokio::spawn(rule_evaluator.run());
+
+        // 价格目标监控订阅缓存更新
+        tokio::spawn(PriceWatcher::new(cache.clone(), watches.clone(), alert_manager.clone()).run());
+
+        // 宏观事件发布前提醒
+        tokio::spawn(EventReminder::new(
+            calendar.clone(),
+            alert_manager.clone(),
+            config.calendar.reminders.clone(),
+            Duration::from_secs(60),
+        ).run());
+
+        // 启动指标异常检测
+        if !config.alerts.anomalies.is_empty() {
+            tokio::spawn(AnomalyDetector::new(
+                metric_store.clone(),
+                anomalies.clone(),
+                alert_manager.clone(),
+                config.alerts.anomalies.clone(),
+                Duration::from_secs(config.alerts.anomaly_check_interval_seconds.max(1)),
+            ).run());
+        }
+
+        // 启动多数据源价格偏离检测
+        if config.price_divergence.enabled && !config.price_divergence.coins.is_empty() {
+            tokio::spawn(PriceDivergenceMonitor::new(
+                cache.clone(),
+                CoinGeckoClient::new(Duration::from_secs(30))?,
+                ExchangeTickerClient::new(Duration::from_secs(30))?,
+                alert_manager.clone(),
+                config.price_divergence.clone(),
+            ).run());
+        }
+
+        // 启动停止更新币种检测
+        tokio::spawn(InactiveCoinMonitor::new(
+            cache.clone(),
+            alert_manager.clone(),
+            config.inactive_coins.clone(),
+        ).run());
+    }
+
+    // 定期备份数据目录（只由采集实例执行）
+    if config.backup.enabled && !api_only {
+        tokio::spawn(BackupScheduler::new(&config.storage.data_dir, config.backup.clone())?.run());
+    }
+
+    // 定期把冷指标移入归档（只由采集实例执行）
+    if let Some(archive) = metric_archive.filter(|_| !api_only) {
+        tokio::spawn(MetricArchiver::new(
+            metric_store.clone(),
+            archive,
+            config.archive.hot_days,
+            Duration::from_secs(config.archive.interval_hours.max(1) * 3600),
+        ).run());
+    }
+
+    // 记录情绪指数、ETF资金流向和爆仓历史，跟踪山寨币季节阶段切换、贪婪恐惧指数波动、ETF大额流出、连环爆仓和CMC上市信息变化
+    let altcoin_season = Arc::new(
+        AltcoinSeasonHistory::open(
+            std::path::Path::new(&config.storage.data_dir).join("altcoin_season_history.json"),
+            config.altcoin_season.clone(),
+        ).await?
+        .with_constituents_file(std::path::Path::new(&config.storage.data_dir).join("altcoin_season_constituents.json"))
+        .await?
+    );
+    let fear_greed = Arc::new(
+        FearGreedHistory::open(
+            std::path::Path::new(&config.storage.data_dir).join("fear_greed_history.json"),
+            config.fear_greed.clone(),
+        ).await?
+    );
+    let etf_flows = Arc::new(
+        EtfFlowHistory::open(
+            std::path::Path::new(&config.storage.data_dir).join("etf_flows.json"),
+            config.etf_flows.clone(),
+        ).await?
+    );
+    let liquidations = Arc::new(
+        LiquidationHistory::open(
+            std::path::Path::new(&config.storage.data_dir).join("liquidations.json"),
+            config.liquidations.clone(),
+        ).await?
+    );
+    let snapshot_path = std::path::Path::new(&config.storage.data_dir).join("cache_snapshot.json");
+    if api_only {
+        // 只读实例定期从共享数据目录加载缓存快照和各历史数据
+        let replica_sync = ReplicaSync::new(
+            cache.clone(),
+            snapshot_path,
+            vec![
+                metric_store.clone(),
+                altcoin_season.clone(),
+                fear_greed.clone(),
+                etf_flows.clone(),
+                liquidations.clone(),
+                changes.clone(),
+                ranks.clone(),
+                calendar.clone(),
+                trends.clone(),
+            ],
+            Duration::from_secs(config.replica.sync_interval_seconds.max(1)),
+        );
+        replica_sync.sync_once().await;
+        tokio::spawn(replica_sync.run());
+    } else {
+        let recorder = HistoryRecorder::new(
+            cache.clone(),
+            altcoin_season.clone(),
+            fear_greed.clone(),
+            prices.clone(),
+            etf_flows.clone(),
+            changes.clone(),
+            alert_manager.clone(),
+        )
+        .with_rank_history(ranks.clone())
+        .with_liquidation_history(liquidations.clone());
+        let recorder = if config.ratios.alerts {
+            recorder.with_ratio_alerts(config.ratios.alert_severity, config.ratios.lookback_days)
+        } else {
+            recorder
+        };
+        let recorder = if config.nodes.alerts {
+            recorder.with_node_alerts(config.nodes.alert_severity)
+        } else {
+            recorder
+        };
+        tokio::spawn(recorder.run());
+
+        // 缓存快照供只读API实例读取
+        tokio::spawn(SnapshotWriter::new(
+            cache.clone(),
+            snapshot_path,
+            Duration::from_secs(config.replica.snapshot_interval_seconds),
+        ).run());
+
+        if let Some(sink) = event_sink {
+            readiness.register("event_bus", false).await;
+            let (readiness, bus_config, cache, metric_store) =
+                (readiness.clone(), config.event_bus.clone(), cache.clone(), metric_store.clone());
+            tokio::spawn(async move {
+                let connected = readiness.retry_until_ready("event_bus", || everscan::events::connect(&bus_config)).await;
+                sink.connected(connected);
+                EventBus::new(sink, &bus_config).run(cache, metric_store).await;
+            });
+        }
+
+        // 新保存的数据点转发到InfluxDB/Graphite
+        for forwarder in &config.forwarders {
+            tokio::spawn(MetricForwarder::new(forwarder)?.run(metric_store.clone()));
+        }
+
+        // 指数和币价发布到MQTT代理
+        #[cfg(feature = "mqtt")]
+        if config.mqtt.enabled {
+            tokio::spawn(everscan::mqtt::MqttPublisher::new(cache.clone(), &config.mqtt)?.run());
+        }
+        #[cfg(not(feature = "mqtt"))]
+        if config.mqtt.enabled {
+            tracing::warn!("⚠️ 配置启用了MQTT发布，但程序未启用 mqtt 特性，已跳过");
+        }
+    }
+
+    // 加载用户账户
+    let users = Arc::new(
+        UserStore::open(std::path::Path::new(&config.storage.data_dir).join("users.json")).await?
+    );
+    let auth = Arc::new(AuthService::from_config(&config.auth));
+
+    // 打开审计日志
+    let audit = Arc::new(
+        AuditLog::open(std::path::Path::new(&config.storage.data_dir).join("audit_log.jsonl")).await?
+    );
+
+    // 创建任务队列：定时执行、手动触发和刷新请求都通过队列执行
+    let jobs = JobQueue::start(task_manager.clone(), cache.clone(), config.jobs.clone());
+
+    // 运行中切换监听地址（管理接口或 SIGHUP 重新加载配置）
+    let scheme = if config.server.tls.enabled { "https" } else { "http" };
+    let (rebinder, rebinds) = Rebinder::new(&config.server, scheme);
+    let rebinder = Arc::new(rebinder);
+
+    let state = AppState {
+        cache: cache.clone(),
+        alert_manager: alert_manager.clone(),
+        alert_rules: alert_rules.clone(),
+        watches: watches.clone(),
+        users,
+        auth,
+        portfolio,
+        exchanges,
+        tasks: task_manager.clone(),
+        jobs: jobs.clone(),
+        audit,
+        anomalies,
+        altcoin_season,
+        fear_greed,
+        etf_flows,
+        liquidations,
+        changes,
+        ranks,
+        calendar,
+        sentiment: Arc::new(config.sentiment.clone()),
+        derived_metrics,
+        trends,
+        metrics: metric_store,
+        refresh: Arc::new(RefreshLimiter::from_config(&config.refresh)),
+        ingest: Arc::new(IngestSources::from_config(&config.ingest, api_only)?),
+        websocket: Arc::new(WebSocketGuard::from_config(&config.websocket)),
+        websocket_hub: Arc::new(WebSocketHub::from_config(&config.websocket)),
+        rebinder: rebinder.clone(),
+        maintenance: Arc::new(MaintenanceMode::new()),
+        read_only: ReadOnlyMode::new(api_only),
+        readiness,
+        tenants,
+        coins,
+        feature_flags,
+    };
+
+    // 启动gRPC接口（与Web服务器共用缓存和指标存储）
+    #[cfg(feature = "grpc")]
+    if config.grpc.enabled {
+        let addr: std::net::SocketAddr = format!("{}:{}", config.grpc.host, config.grpc.port).parse()
+            .map_err(|e| anyhow::anyhow!("解析gRPC监听地址失败: {}", e))?;
+        let service = everscan::grpc::EverScanService::new(state.cache.clone(), state.metrics.clone());
+        tokio::spawn(async move {
+            if let Err(e) = service.serve(addr).await {
+                error!("❌ gRPC服务异常退出: {}", e);
+            }
+        });
+    }
+    #[cfg(not(feature = "grpc"))]
+    if config.grpc.enabled {
+        tracing::warn!("⚠️ 配置启用了gRPC接口，但程序未启用 grpc 特性，已跳过");
+    }
 
     // 创建Web服务器
     let app = axum::Router::new()
-        .nest("/api", create_api_routes(cache.clone()))
-        .nest_service("/", ServeDir::new("static").append_index_html_on_directories(true))
+        .route("/ws", axum::routing::get(websocket_handler))
+        .merge(create_versioned_api_routes(state.clone()))
+        .merge(create_page_routes())
+        .merge(create_feed_routes())
+        .merge(create_prometheus_routes())
+        .merge(create_asset_routes(config.server.static_dir.as_deref()))
         .layer(CorsLayer::permissive())
-        .with_state(cache.clone());
+        .with_state(state);
 
-    // 启动Web服务器
-    let addr = format!("{}:{}", config.server.host, config.server.port);
-    info!("🌐 启动Web服务器: http://{}", addr);
+    // 加载HTTPS证书（证书无效时在启动阶段报错）
+    #[cfg(feature = "tls")]
+    let tls = match config.server.tls.enabled {
+        true => Some(everscan::web::tls::load_server_config(&config.server.tls)?),
+        false => None,
+    };
+    #[cfg(not(feature = "tls"))]
+    if config.server.tls.enabled {
+        anyhow::bail!("配置启用了HTTPS，但程序未启用 tls 特性，请使用 --features tls 重新编译");
+    }
 
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    // 启动Web服务器（systemd 传入的套接字、Unix套接字或TCP端口）
+    let listener = Listener::bind(&config.server).await?;
+    info!("🌐 启动Web服务器: {}", listener.describe(scheme));
+    #[cfg(unix)]
+    tokio::spawn(rebind_on_sighup(rebinder));
 
-    // 启动任务管理器（在后台运行）
-    let task_cache = cache.clone();
-    tokio::spawn(async move {
-        if let Err(e) = task_manager.start(task_cache).await {
-            error!("❌ 任务管理器启动失败: {}", e);
-        }
-    });
+    // 启动任务调度器（在后台运行），启动时所有任务立即到期以获取初始数据
+    if !api_only {
+        tokio::spawn(jobs.run_scheduler());
+    }
 
     // 启动Web服务器
     info!("✅ EverScan 启动完成，等待连接...");
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    #[cfg(feature = "tls")]
+    if let Some(tls) = tls {
+        // HTTP到HTTPS的重定向
+        if let Some(port) = config.server.tls.redirect_http_port {
+            let redirect_addr = format!("{}:{}", config.server.host, port);
+            let redirect_listener = tokio::net::TcpListener::bind(&redirect_addr).await?;
+            let redirect = everscan::web::tls::redirect_router(config.server.port);
+            info!("↪️ HTTP重定向到HTTPS: http://{}", redirect_addr);
+            tokio::spawn(async move {
+                if let Err(e) = axum::serve(redirect_listener, redirect).await {
+                    error!("❌ HTTP重定向服务异常退出: {}", e);
+                }
+            });
+        }
+
+        everscan::web::tls::serve_tls(listener, rebinds, app, tls, shutdown_signal()).await?;
+        info!("👋 EverScan 已停止");
+        return Ok(());
+    }
+
+    serve(listener, rebinds, app, shutdown_signal()).await?;
 
     info!("👋 EverScan 已停止");
     Ok(())
 }
 
+/// 收到 SIGHUP 时重新读取配置文件，监听地址有变化时切换监听器
+///
+/// 只重新加载 `[server]` 中的监听地址，其他配置修改仍需重启
+#[cfg(unix)]
+async fn rebind_on_sighup(rebinder: Arc<Rebinder>) {
+    let mut hangup = match signal::unix::signal(signal::unix::SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            error!("❌ 注册 SIGHUP 处理失败: {}", e);
+            return;
+        }
+    };
+    while hangup.recv().await.is_some() {
+        info!("🔄 收到 SIGHUP，重新加载监听地址");
+        let target = match AppConfig::from_file("config.toml") {
+            Ok(config) => BindTarget::from_config(&config.server),
+            Err(e) => {
+                error!("❌ 重新加载配置失败，保持当前监听地址: {}", e);
+                continue;
+            }
+        };
+        if target == rebinder.current().await {
+            info!("监听地址未变化");
+            continue;
+        }
+        match rebinder.rebind(target).await {
+            Ok(address) => info!("🔁 监听地址已切换到 {}", address),
+            Err(e) => error!("❌ 切换监听地址失败，保持当前监听地址: {:#}", e),
+        }
+    }
+}
+
 /// 优雅关闭信号处理
 async fn shutdown_signal() {
     let ctrl_c = async {
@@ -0,0 +1,325 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::config::{AppConfig, BackupConfig};
+use crate::maintenance::S3Client;
+
+/// 归档文件名前缀
+const ARCHIVE_PREFIX: &str = "everscan-backup-";
+
+/// 归档文件扩展名
+const ARCHIVE_EXTENSION: &str = ".tar.gz";
+
+/// 生成归档文件名（如 `everscan-backup-20240101T000000Z.tar.gz`）
+pub fn archive_name(now: DateTime<Utc>) -> String {
+    format!("{}{}{}", ARCHIVE_PREFIX, now.format("%Y%m%dT%H%M%SZ"), ARCHIVE_EXTENSION)
+}
+
+/// 把数据目录打包为归档
+///
+/// 备份目录位于数据目录内时会被排除
+///
+/// # 参数
+/// * `data_dir` - 数据目录
+/// * `backup_dir` - 归档保存目录
+/// * `now` - 备份时间（用于文件名）
+///
+/// # 返回
+/// * `Result<PathBuf>` - 归档文件路径
+pub fn create_archive(data_dir: &Path, backup_dir: &Path, now: DateTime<Utc>) -> Result<PathBuf> {
+    if !data_dir.is_dir() {
+        bail!("数据目录不存在: {}", data_dir.display());
+    }
+    std::fs::create_dir_all(backup_dir)
+        .with_context(|| format!("无法创建备份目录: {}", backup_dir.display()))?;
+
+    let path = backup_dir.join(archive_name(now));
+    // 先写临时文件，完成后再改名，避免中断时留下不完整的归档
+    let partial = path.with_extension("partial");
+    let file = std::fs::File::create(&partial)
+        .with_context(|| format!("无法创建归档文件: {}", partial.display()))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let excluded = backup_dir.canonicalize().ok();
+    append_dir(&mut builder, data_dir, Path::new(""), excluded.as_deref())?;
+    builder.into_inner()?.finish()?;
+    std::fs::rename(&partial, &path)?;
+    Ok(path)
+}
+
+/// 递归追加目录内容
+fn append_dir<W: std::io::Write>(
+    builder: &mut tar::Builder<W>,
+    dir: &Path,
+    prefix: &Path,
+    excluded: Option<&Path>,
+) -> Result<()> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?.collect::<std::io::Result<_>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let path = entry.path();
+        if excluded.is_some_and(|excluded| path.canonicalize().is_ok_and(|p| p == excluded)) {
+            continue;
+        }
+        let name = prefix.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            append_dir(builder, &path, &name, excluded)?;
+        } else {
+            builder.append_path_with_name(&path, &name)
+                .with_context(|| format!("无法归档文件: {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// 删除多余的旧归档
+///
+/// # 参数
+/// * `backup_dir` - 归档保存目录
+/// * `keep` - 保留的归档数量
+///
+/// # 返回
+/// * `Result<usize>` - 删除的归档数量
+pub fn prune_archives(backup_dir: &Path, keep: usize) -> Result<usize> {
+    let mut archives: Vec<PathBuf> = std::fs::read_dir(backup_dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.file_name().and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with(ARCHIVE_PREFIX) && n.ends_with(ARCHIVE_EXTENSION)))
+        .collect();
+    // 文件名中的时间戳保证按名称排序即按时间排序
+    archives.sort();
+    let excess = archives.len().saturating_sub(keep);
+    for path in &archives[..excess] {
+        std::fs::remove_file(path).with_context(|| format!("无法删除旧归档: {}", path.display()))?;
+    }
+    Ok(excess)
+}
+
+/// 从归档恢复数据目录
+///
+/// 数据目录非空时需要 `force`，原目录会被改名保留（`<数据目录>.before-restore-<时间>`）
+///
+/// # 参数
+/// * `archive` - 归档内容（`.tar.gz`）
+/// * `data_dir` - 数据目录
+/// * `force` - 是否替换非空的数据目录
+///
+/// # 返回
+/// * `Result<Option<PathBuf>>` - 原数据目录被改名后的路径
+pub fn restore_archive(archive: &[u8], data_dir: &Path, force: bool) -> Result<Option<PathBuf>> {
+    // 先解压到临时目录，归档损坏时不影响现有数据
+    let staging = data_dir.with_extension("restoring");
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)?;
+    if let Err(e) = tar::Archive::new(GzDecoder::new(archive)).unpack(&staging) {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(anyhow!("解压归档失败: {}", e));
+    }
+
+    let not_empty = data_dir.is_dir() && std::fs::read_dir(data_dir)?.next().is_some();
+    let previous = if not_empty {
+        if !force {
+            std::fs::remove_dir_all(&staging)?;
+            bail!("数据目录 {} 非空，确认覆盖请加 --force（原目录会被改名保留）", data_dir.display());
+        }
+        let mut moved = data_dir.as_os_str().to_owned();
+        moved.push(format!(".before-restore-{}", Utc::now().format("%Y%m%dT%H%M%SZ")));
+        let moved = PathBuf::from(moved);
+        std::fs::rename(data_dir, &moved)?;
+        Some(moved)
+    } else {
+        if data_dir.exists() {
+            std::fs::remove_dir(data_dir)?;
+        }
+        None
+    };
+    std::fs::rename(&staging, data_dir)?;
+    Ok(previous)
+}
+
+/// 定期备份
+///
+/// 按间隔把数据目录打包到本地备份目录，清理多余的旧归档，配置了S3时同时上传
+pub struct BackupScheduler {
+    /// 数据目录
+    data_dir: PathBuf,
+    /// 备份配置
+    config: BackupConfig,
+    /// S3客户端（可选）
+    s3: Option<S3Client>,
+}
+
+impl BackupScheduler {
+    /// 创建定期备份
+    ///
+    /// # 参数
+    /// * `data_dir` - 数据目录
+    /// * `config` - 备份配置
+    ///
+    /// # 返回
+    /// * `Result<Self>` - S3配置不完整时返回错误
+    pub fn new(data_dir: impl Into<PathBuf>, config: BackupConfig) -> Result<Self> {
+        let s3 = config.s3.as_ref().map(S3Client::new).transpose()?;
+        Ok(Self {
+            data_dir: data_dir.into(),
+            config,
+            s3,
+        })
+    }
+
+    /// 持续运行备份循环（启动后等待一个间隔再执行第一次备份）
+    pub async fn run(self) {
+        let period = Duration::from_secs(self.config.interval_hours.max(1) * 3600);
+        info!("💾 启动定期备份，每 {} 小时备份到 {}", self.config.interval_hours, self.config.directory);
+
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.backup_once().await {
+                error!("❌ 数据备份失败: {:#}", e);
+            }
+        }
+    }
+
+    /// 执行一次备份
+    ///
+    /// # 返回
+    /// * `Result<PathBuf>` - 本地归档路径
+    pub async fn backup_once(&self) -> Result<PathBuf> {
+        let data_dir = self.data_dir.clone();
+        let backup_dir = PathBuf::from(&self.config.directory);
+        let keep = self.config.keep.max(1);
+        let now = Utc::now();
+        let path = tokio::task::spawn_blocking(move || -> Result<PathBuf> {
+            let path = create_archive(&data_dir, &backup_dir, now)?;
+            let pruned = prune_archives(&backup_dir, keep)?;
+            if pruned > 0 {
+                info!("🧹 删除 {} 个旧备份归档", pruned);
+            }
+            Ok(path)
+        }).await??;
+        info!("💾 数据已备份到 {}", path.display());
+
+        if let Some(s3) = &self.s3 {
+            let key = s3.object_key(&archive_name(now));
+            let body = tokio::fs::read(&path).await?;
+            match s3.put_object(&key, body).await {
+                Ok(()) => info!("☁️ 备份已上传到S3: {}", key),
+                Err(e) => warn!("⚠️ 上传备份到S3失败（本地归档已保留）: {:#}", e),
+            }
+        }
+        Ok(path)
+    }
+}
+
+/// `everscan restore` 命令行选项
+#[derive(Debug, Clone)]
+pub struct RestoreOptions {
+    /// 配置文件路径
+    pub config_path: String,
+    /// 归档文件路径（`--from-s3` 时为S3对象名，不含前缀）
+    pub file: String,
+    /// 从配置的S3存储桶下载归档
+    pub from_s3: bool,
+    /// 替换非空的数据目录
+    pub force: bool,
+}
+
+impl RestoreOptions {
+    /// 从命令行参数解析选项（不含子命令本身）
+    ///
+    /// 支持 `--file <归档>`、`--config <path>`、`--from-s3` 和 `--force`
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut config_path = "config.toml".to_string();
+        let mut file = None;
+        let (mut from_s3, mut force) = (false, false);
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| args.next().ok_or_else(|| anyhow!("{} 需要指定参数值", name));
+            match arg.as_str() {
+                "--config" => config_path = value("--config")?,
+                "--file" => file = Some(value("--file")?),
+                "--from-s3" => from_s3 = true,
+                "--force" => force = true,
+                other => bail!("未知参数: {}", other),
+            }
+        }
+
+        Ok(Self {
+            config_path,
+            file: file.ok_or_else(|| anyhow!("缺少 --file 参数"))?,
+            from_s3,
+            force,
+        })
+    }
+}
+
+/// 按选项恢复数据目录
+///
+/// 应在服务停止时运行，否则运行中的实例会用内存中的状态覆盖恢复的文件
+///
+/// # 参数
+/// * `options` - 恢复选项
+///
+/// # 返回
+/// * `Result<Option<PathBuf>>` - 原数据目录被改名后的路径
+pub async fn restore(options: &RestoreOptions) -> Result<Option<PathBuf>> {
+    let config = AppConfig::from_file(&options.config_path)?;
+    let archive = if options.from_s3 {
+        let s3_config = config.backup.s3.as_ref()
+            .ok_or_else(|| anyhow!("--from-s3 需要在 [backup.s3] 中配置存储桶"))?;
+        let s3 = S3Client::new(s3_config)?;
+        s3.get_object(&s3.object_key(&options.file)).await?
+    } else {
+        tokio::fs::read(&options.file).await
+            .with_context(|| format!("无法读取归档文件: {}", options.file))?
+    };
+
+    let data_dir = PathBuf::from(&config.storage.data_dir);
+    let force = options.force;
+    tokio::task::spawn_blocking(move || restore_archive(&archive, &data_dir, force)).await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_and_restore_round_trip() {
+        let root = std::env::temp_dir().join(format!("everscan-backup-test-{}", uuid::Uuid::new_v4()));
+        let data_dir = root.join("data");
+        let backup_dir = data_dir.join("backups");
+        std::fs::create_dir_all(data_dir.join("nested")).unwrap();
+        std::fs::write(data_dir.join("metrics.jsonl"), "{}\n").unwrap();
+        std::fs::write(data_dir.join("nested/state.json"), "[]").unwrap();
+
+        let now = Utc::now();
+        let archive = create_archive(&data_dir, &backup_dir, now).unwrap();
+        create_archive(&data_dir, &backup_dir, now + chrono::Duration::seconds(1)).unwrap();
+        assert_eq!(prune_archives(&backup_dir, 1).unwrap(), 1);
+        assert!(!archive.exists());
+        let archive = backup_dir.join(archive_name(now + chrono::Duration::seconds(1)));
+        let bytes = std::fs::read(&archive).unwrap();
+
+        // 恢复到新目录；非空目录需要 force，原目录被保留
+        let restored = root.join("restored");
+        assert!(restore_archive(&bytes, &restored, false).unwrap().is_none());
+        assert_eq!(std::fs::read_to_string(restored.join("nested/state.json")).unwrap(), "[]");
+        assert!(!restored.join("backups").exists());
+        assert!(restore_archive(&bytes, &restored, false).is_err());
+        let previous = restore_archive(&bytes, &restored, true).unwrap().unwrap();
+        assert!(previous.join("metrics.jsonl").exists());
+        assert!(restore_archive(b"not an archive", &restored, true).is_err());
+        assert!(restored.join("metrics.jsonl").exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
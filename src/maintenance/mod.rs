@@ -0,0 +1,5 @@
+pub mod backup;
+pub mod s3;
+
+pub use backup::*;
+pub use s3::*;
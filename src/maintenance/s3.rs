@@ -0,0 +1,185 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use tracing::debug;
+
+use crate::clients::HttpClientBuilder;
+use crate::config::S3Config;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 最小化的S3客户端
+///
+/// 只支持备份所需的上传和下载单个对象，使用路径风格URL和AWS Signature V4签名，
+/// 兼容MinIO等S3兼容存储
+pub struct S3Client {
+    /// HTTP客户端
+    client: Client,
+    /// 端点（不含末尾的 `/`）
+    endpoint: String,
+    /// 存储桶
+    bucket: String,
+    /// 区域
+    region: String,
+    /// 对象键前缀
+    prefix: String,
+    /// 访问密钥ID
+    access_key_id: String,
+    /// 访问密钥
+    secret_access_key: String,
+}
+
+impl S3Client {
+    /// 创建S3客户端
+    ///
+    /// # 参数
+    /// * `config` - S3配置
+    ///
+    /// # 返回
+    /// * `Result<Self>` - 缺少存储桶或凭证时返回错误
+    pub fn new(config: &S3Config) -> Result<Self> {
+        if config.bucket.trim().is_empty() {
+            return Err(anyhow!("S3未配置存储桶"));
+        }
+        let access_key_id = config.access_key_id.as_ref().filter(|k| !k.is_empty())
+            .ok_or_else(|| anyhow!("S3缺少访问密钥ID（access_key_id 或 AWS_ACCESS_KEY_ID）"))?;
+        let secret_access_key = config.secret_access_key.as_ref().filter(|k| !k.is_empty())
+            .ok_or_else(|| anyhow!("S3缺少访问密钥（secret_access_key 或 AWS_SECRET_ACCESS_KEY）"))?;
+        let endpoint = config.endpoint.clone()
+            .filter(|e| !e.trim().is_empty())
+            .unwrap_or_else(|| format!("https://s3.{}.amazonaws.com", config.region));
+
+        Ok(Self {
+            client: HttpClientBuilder::new().timeout(Duration::from_secs(300)).build()?,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            bucket: config.bucket.clone(),
+            region: config.region.clone(),
+            prefix: config.prefix.clone(),
+            access_key_id: access_key_id.expose().to_string(),
+            secret_access_key: secret_access_key.expose().to_string(),
+        })
+    }
+
+    /// 带前缀的完整对象键
+    pub fn object_key(&self, name: &str) -> String {
+        format!("{}{}", self.prefix, name)
+    }
+
+    /// 上传对象
+    ///
+    /// # 参数
+    /// * `key` - 完整对象键
+    /// * `body` - 内容
+    pub async fn put_object(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        self.request(Method::PUT, key, body).await?;
+        Ok(())
+    }
+
+    /// 下载对象
+    ///
+    /// # 参数
+    /// * `key` - 完整对象键
+    pub async fn get_object(&self, key: &str) -> Result<Vec<u8>> {
+        self.request(Method::GET, key, Vec::new()).await
+    }
+
+    /// 发送签名请求并返回响应内容
+    async fn request(&self, method: Method, key: &str, body: Vec<u8>) -> Result<Vec<u8>> {
+        let path = format!("/{}/{}", uri_encode(&self.bucket, false), uri_encode(key, true));
+        let url = format!("{}{}", self.endpoint, path);
+        let host = reqwest::Url::parse(&url)
+            .ok()
+            .and_then(|u| Some(match u.port() {
+                Some(port) => format!("{}:{}", u.host_str()?, port),
+                None => u.host_str()?.to_string(),
+            }))
+            .ok_or_else(|| anyhow!("无效的S3端点: {}", self.endpoint))?;
+
+        let now = Utc::now();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+        let authorization = self.authorization(method.as_str(), &path, &host, &payload_hash, now);
+        debug!("☁️ S3 {} {}", method, key);
+
+        let response = self.client.request(method.clone(), &url)
+            .header("x-amz-date", now.format("%Y%m%dT%H%M%SZ").to_string())
+            .header("x-amz-content-sha256", &payload_hash)
+            .header("authorization", authorization)
+            .body(body)
+            .send().await
+            .with_context(|| format!("S3请求失败: {} {}", method, key))?;
+        let status = response.status();
+        let content = response.bytes().await?.to_vec();
+        if !status.is_success() {
+            return Err(anyhow!("S3 {} {} 返回 HTTP {}: {}", method, key, status, String::from_utf8_lossy(&content)));
+        }
+        Ok(content)
+    }
+
+    /// 生成Signature V4授权头
+    fn authorization(&self, method: &str, path: &str, host: &str, payload_hash: &str, now: DateTime<Utc>) -> String {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method, path, host, payload_hash, amz_date, signed_headers, payload_hash,
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date, scope, hex::encode(Sha256::digest(canonical_request.as_bytes())),
+        );
+        let key = signing_key(&self.secret_access_key, &date, &self.region, "s3");
+        let signature = hex::encode(hmac(&key, string_to_sign.as_bytes()));
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, scope, signed_headers, signature,
+        )
+    }
+}
+
+/// HMAC-SHA256
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC可以接受任意长度的密钥");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// 派生Signature V4签名密钥
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let date_key = hmac(format!("AWS4{}", secret).as_bytes(), date.as_bytes());
+    let region_key = hmac(&date_key, region.as_bytes());
+    let service_key = hmac(&region_key, service.as_bytes());
+    hmac(&service_key, b"aws4_request")
+}
+
+/// 按Signature V4规则对URI编码（`keep_slash` 为true时保留路径分隔符）
+fn uri_encode(value: &str, keep_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            b'/' if keep_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_key_and_encoding() {
+        // AWS文档中的签名密钥派生示例
+        let key = signing_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY", "20120215", "us-east-1", "iam");
+        assert_eq!(hex::encode(key), "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d");
+
+        assert_eq!(uri_encode("everscan/backup 1.tar.gz", true), "everscan/backup%201.tar.gz");
+        assert_eq!(uri_encode("a/b", false), "a%2Fb");
+    }
+}
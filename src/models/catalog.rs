@@ -0,0 +1,126 @@
+use regex::Regex;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+/// 指标定义
+///
+/// `pattern` 中的 `{...}` 表示名称中的可变部分（如币种、资产）
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct MetricDefinition {
+    /// 指标名称模式（如 `etf_netflow_{asset}`）
+    pub pattern: &'static str,
+    /// 产出该指标的任务ID
+    pub task_id: &'static str,
+    /// 说明
+    pub description: &'static str,
+}
+
+/// 内置任务产出的指标
+///
+/// 前缀相同的模式中，更具体的放在前面
+pub const METRIC_DEFINITIONS: &[MetricDefinition] = &[
+    MetricDefinition { pattern: "{coin_id}_market_data", task_id: "crypto_market_task", description: "币种价格，元数据包含市值、成交量、涨跌幅和技术分析" },
+    MetricDefinition { pattern: "indicator_{indicator}_{coin_id}", task_id: "crypto_market_task", description: "币种技术指标历史（price、rsi、bollinger）" },
+    MetricDefinition { pattern: "fear_greed_index", task_id: "fear_greed", description: "贪婪恐惧指数（0-100）" },
+    MetricDefinition { pattern: "altcoin_season_index", task_id: "altcoin_season", description: "山寨币季节指数（0-100）" },
+    MetricDefinition { pattern: "formula_trial_altcoin_season", task_id: "formula_trial", description: "山寨币季节指数现行与候选公式的并行计算值（variant、formula 标签）" },
+    MetricDefinition { pattern: "formula_trial_composite_sentiment", task_id: "formula_trial", description: "综合情绪分现行与候选权重的并行计算值（variant、formula 标签）" },
+    MetricDefinition { pattern: "market_cap_total", task_id: "altcoin_season", description: "CMC市值前100的总市值" },
+    MetricDefinition { pattern: "market_cap_total2", task_id: "altcoin_season", description: "剔除BTC的总市值（Total2）" },
+    MetricDefinition { pattern: "market_cap_total3", task_id: "altcoin_season", description: "剔除BTC和ETH的总市值（Total3）" },
+    MetricDefinition { pattern: "btc_days_since_halving", task_id: "btc_cycle", description: "距上次比特币减半的天数" },
+    MetricDefinition { pattern: "btc_blocks_until_halving", task_id: "btc_cycle", description: "距下次比特币减半的区块数" },
+    MetricDefinition { pattern: "btc_cycle_progress_pct", task_id: "btc_cycle", description: "当前减半周期的进度" },
+    MetricDefinition { pattern: "btc_return_since_halving_pct", task_id: "btc_cycle", description: "上次减半以来的BTC涨幅" },
+    MetricDefinition { pattern: "everscan_cache_items", task_id: "operational_metrics", description: "缓存中的币种数" },
+    MetricDefinition { pattern: "everscan_cache_memory_bytes", task_id: "operational_metrics", description: "缓存估算内存占用（字节）" },
+    MetricDefinition { pattern: "everscan_cache_hit_ratio", task_id: "operational_metrics", description: "两次记录之间的缓存命中率" },
+    MetricDefinition { pattern: "everscan_task_success_rate", task_id: "operational_metrics", description: "两次记录之间各任务的执行成功率（task 标签）" },
+    MetricDefinition { pattern: "everscan_task_latency_max_ms", task_id: "operational_metrics", description: "两次记录之间各任务的最长执行耗时" },
+    MetricDefinition { pattern: "everscan_task_latency_ms", task_id: "operational_metrics", description: "两次记录之间各任务的平均执行耗时" },
+    MetricDefinition { pattern: "breadth_advancers", task_id: "market_breadth", description: "24小时上涨的币种数" },
+    MetricDefinition { pattern: "breadth_decliners", task_id: "market_breadth", description: "24小时下跌的币种数" },
+    MetricDefinition { pattern: "breadth_pct_above_sma50", task_id: "market_breadth", description: "价格高于50日均线的币种占比" },
+    MetricDefinition { pattern: "breadth_pct_above_sma200", task_id: "market_breadth", description: "价格高于200日均线的币种占比" },
+    MetricDefinition { pattern: "breadth_new_highs_30d", task_id: "market_breadth", description: "创30日新高的币种数" },
+    MetricDefinition { pattern: "breadth_new_lows_30d", task_id: "market_breadth", description: "创30日新低的币种数" },
+    MetricDefinition { pattern: "sector_{sector}_market_cap", task_id: "sectors", description: "板块总市值" },
+    MetricDefinition { pattern: "sector_{sector}_flow_24h", task_id: "sectors", description: "板块24小时市值变化（资金流向）" },
+    MetricDefinition { pattern: "sector_{sector}_change_24h", task_id: "sectors", description: "板块24小时市值加权涨跌幅" },
+    MetricDefinition { pattern: "ratio_{name}", task_id: "ratios", description: "配置的价格比率（如ETH/BTC）" },
+    MetricDefinition { pattern: "exchange_balance_{asset}", task_id: "exchange_flows", description: "交易所持有的资产余额" },
+    MetricDefinition { pattern: "exchange_netflow_7d_{asset}", task_id: "exchange_flows", description: "交易所7日净流入" },
+    MetricDefinition { pattern: "exchange_netflow_30d_{asset}", task_id: "exchange_flows", description: "交易所30日净流入" },
+    MetricDefinition { pattern: "exchange_netflow_{asset}", task_id: "exchange_flows", description: "交易所24小时净流入" },
+    MetricDefinition { pattern: "options_dvol_{currency}", task_id: "options", description: "Deribit波动率指数DVOL" },
+    MetricDefinition { pattern: "options_atm_iv_{currency}", task_id: "options", description: "最近到期日的平值隐含波动率" },
+    MetricDefinition { pattern: "options_put_call_oi_{currency}", task_id: "options", description: "期权看跌/看涨持仓量比" },
+    MetricDefinition { pattern: "options_put_call_volume_{currency}", task_id: "options", description: "期权看跌/看涨成交量比" },
+    MetricDefinition { pattern: "etf_netflow_{asset}", task_id: "etf_flows", description: "现货ETF最近交易日的合计净流入" },
+    MetricDefinition { pattern: "liquidations_long_{asset}", task_id: "liquidations", description: "最近一小时的多单爆仓金额" },
+    MetricDefinition { pattern: "liquidations_short_{asset}", task_id: "liquidations", description: "最近一小时的空单爆仓金额" },
+    MetricDefinition { pattern: "liquidations_total_{asset}", task_id: "liquidations", description: "最近一小时的多空爆仓合计" },
+    MetricDefinition { pattern: "liquidity_spread_bps_{symbol}", task_id: "liquidity", description: "交易对买卖价差（基点）" },
+    MetricDefinition { pattern: "liquidity_bid_depth_{symbol}", task_id: "liquidity", description: "中间价下方范围内的买单金额" },
+    MetricDefinition { pattern: "liquidity_ask_depth_{symbol}", task_id: "liquidity", description: "中间价上方范围内的卖单金额" },
+    MetricDefinition { pattern: "nft_floor_usd_{collection}", task_id: "nft", description: "NFT合集地板价（美元）" },
+    MetricDefinition { pattern: "nft_floor_{collection}", task_id: "nft", description: "NFT合集地板价（计价币种）" },
+    MetricDefinition { pattern: "nft_volume_24h_{collection}", task_id: "nft", description: "NFT合集24小时成交额（计价币种）" },
+    MetricDefinition { pattern: "network_hashrate_{chain}", task_id: "network", description: "全网算力（EH/s）" },
+    MetricDefinition { pattern: "network_difficulty_change_{chain}", task_id: "network", description: "预计下次难度调整幅度（百分比）" },
+    MetricDefinition { pattern: "network_difficulty_{chain}", task_id: "network", description: "挖矿难度" },
+    MetricDefinition { pattern: "network_staking_apr_{chain}", task_id: "network", description: "质押年化收益率（百分比）" },
+    MetricDefinition { pattern: "network_staking_ratio_{chain}", task_id: "network", description: "质押量占总供应量的比例（百分比）" },
+    MetricDefinition { pattern: "node_healthy_{node}", task_id: "nodes", description: "自建节点是否健康（1为健康）" },
+    MetricDefinition { pattern: "node_lag_{node}", task_id: "nodes", description: "自建节点落后参考高度的区块数" },
+    MetricDefinition { pattern: "node_peers_{node}", task_id: "nodes", description: "自建节点连接的对等节点数" },
+    MetricDefinition { pattern: "node_latency_ms_{node}", task_id: "nodes", description: "自建节点JSON-RPC响应延迟（毫秒）" },
+    MetricDefinition { pattern: "calendar_high_importance_7d", task_id: "economic_calendar", description: "未来7天的高重要性宏观事件数" },
+    MetricDefinition { pattern: "social_volume_24h_{coin_id}", task_id: "social_sentiment", description: "24小时社交提及量" },
+    MetricDefinition { pattern: "social_interactions_24h_{coin_id}", task_id: "social_sentiment", description: "24小时社交互动数" },
+    MetricDefinition { pattern: "social_sentiment_{coin_id}", task_id: "social_sentiment", description: "正面情绪占比" },
+    MetricDefinition { pattern: "social_galaxy_score_{coin_id}", task_id: "social_sentiment", description: "LunarCrush Galaxy Score" },
+    MetricDefinition { pattern: "trends_{term}", task_id: "google_trends", description: "搜索词的Google Trends每周热度" },
+];
+
+/// 由指标名称模式生成的正则表达式
+fn compiled_patterns() -> &'static [Regex] {
+    static PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        let placeholder = Regex::new(r"\\\{[a-z_]+\\\}").unwrap();
+        METRIC_DEFINITIONS.iter()
+            .map(|definition| {
+                let escaped = regex::escape(definition.pattern);
+                Regex::new(&format!("^{}$", placeholder.replace_all(&escaped, ".+"))).unwrap()
+            })
+            .collect()
+    })
+}
+
+/// 查找指标名称对应的定义
+///
+/// # 参数
+/// * `metric_name` - 指标名称（如 `etf_netflow_btc`）
+///
+/// # 返回
+/// * `Option<&MetricDefinition>` - 第一个匹配的定义
+pub fn describe_metric(metric_name: &str) -> Option<&'static MetricDefinition> {
+    compiled_patterns().iter()
+        .position(|pattern| pattern.is_match(metric_name))
+        .map(|i| &METRIC_DEFINITIONS[i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_metric() {
+        assert_eq!(describe_metric("exchange_netflow_7d_btc").unwrap().description, "交易所7日净流入");
+        assert_eq!(describe_metric("exchange_netflow_btc").unwrap().description, "交易所24小时净流入");
+        assert_eq!(describe_metric("indicator_rsi_hype").unwrap().task_id, "crypto_market_task");
+        assert_eq!(describe_metric("bitcoin_market_data").unwrap().task_id, "crypto_market_task");
+        assert!(describe_metric("fear_greed_index_x").is_none());
+        assert!(describe_metric("unknown").is_none());
+    }
+}
@@ -1,7 +1,18 @@
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+use super::{DataSource, MetricValue};
+
+/// 标签：链（如 `ethereum`、`arbitrum`）
+pub const LABEL_CHAIN: &str = "chain";
+/// 标签：资产（如 `btc`、`eth`）
+pub const LABEL_ASSET: &str = "asset";
+/// 标签：交易所（如 `binance`）
+pub const LABEL_EXCHANGE: &str = "exchange";
+
 /// 聚合指标数据模型
 /// 
 /// 这是系统中所有数据的统一存储格式
@@ -14,8 +25,8 @@ pub struct AggregatedMetric {
     pub source: String,
     /// 指标名称（如"eth_active_addresses"、"uniswap_v3_volume"）
     pub metric_name: String,
-    /// 指标值（使用JSON格式存储，支持复杂数据结构）
-    pub value: serde_json::Value,
+    /// 指标值（标量、时间序列、分布或复合值）
+    pub value: MetricValue,
     /// 数据时间戳
     pub timestamp: DateTime<Utc>,
     /// 记录创建时间
@@ -24,47 +35,15 @@ pub struct AggregatedMetric {
     pub updated_at: DateTime<Utc>,
     /// 扩展元数据（可选）
     pub metadata: Option<serde_json::Value>,
+    /// 标签（链、资产、交易所等维度，可用于查询过滤）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
 }
 
-/// 数据源枚举
-/// 
-/// 定义系统支持的所有数据源类型
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum DataSource {
-    /// Dune Analytics
-    Dune,
-    /// Glassnode
-    Glassnode,
-    /// DeBank
-    DeBank,
-    /// CoinGecko
-    CoinGecko,
-    /// CoinMarketCap
-    CoinMarketCap,
-    /// Arkham Intelligence
-    Arkham,
-    /// Bitget
-    Bitget,
-}
-
-impl DataSource {
-    /// 转换为字符串
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            DataSource::Dune => "dune",
-            DataSource::Glassnode => "glassnode",
-            DataSource::DeBank => "debank",
-            DataSource::CoinGecko => "coingecko",
-            DataSource::CoinMarketCap => "coinmarketcap",
-            DataSource::Arkham => "arkham",
-            DataSource::Bitget => "bitget",
-        }
-    }
-}
-
-impl std::fmt::Display for DataSource {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.as_str())
+impl AggregatedMetric {
+    /// 是否包含全部指定标签（值须相等）
+    pub fn has_labels(&self, labels: &BTreeMap<String, String>) -> bool {
+        labels.iter().all(|(key, value)| self.labels.get(key) == Some(value))
     }
 }
 
@@ -74,9 +53,10 @@ impl std::fmt::Display for DataSource {
 pub struct MetricBuilder {
     source: String,
     metric_name: String,
-    value: serde_json::Value,
+    value: Option<MetricValue>,
     timestamp: Option<DateTime<Utc>>,
     metadata: Option<serde_json::Value>,
+    labels: BTreeMap<String, String>,
 }
 
 impl MetricBuilder {
@@ -85,15 +65,16 @@ impl MetricBuilder {
         Self {
             source: source.to_string(),
             metric_name: metric_name.into(),
-            value: serde_json::Value::Null,
+            value: None,
             timestamp: None,
             metadata: None,
+            labels: BTreeMap::new(),
         }
     }
     
     /// 设置指标值
-    pub fn value(mut self, value: serde_json::Value) -> Self {
-        self.value = value;
+    pub fn value(mut self, value: impl Into<MetricValue>) -> Self {
+        self.value = Some(value.into());
         self
     }
     
@@ -108,20 +89,39 @@ impl MetricBuilder {
         self.metadata = Some(metadata);
         self
     }
+
+    /// 添加标签（键和值统一转为小写）
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into().to_lowercase(), value.into().to_lowercase());
+        self
+    }
     
     /// 构建聚合指标
-    pub fn build(self) -> AggregatedMetric {
+    /// 
+    /// # 返回
+    /// * `Result<AggregatedMetric>` - 指标名称为空、未设置指标值、指标值无效或标签为空时返回错误
+    pub fn build(self) -> Result<AggregatedMetric> {
+        if self.metric_name.trim().is_empty() {
+            return Err(anyhow!("指标名称不能为空"));
+        }
+        let value = self.value.ok_or_else(|| anyhow!("指标 {} 未设置指标值", self.metric_name))?;
+        value.validate().map_err(|e| anyhow!("指标 {} 的值无效: {}", self.metric_name, e))?;
+        if self.labels.iter().any(|(key, value)| key.trim().is_empty() || value.trim().is_empty()) {
+            return Err(anyhow!("指标 {} 的标签键和值不能为空", self.metric_name));
+        }
+
         let now = Utc::now();
-        AggregatedMetric {
+        Ok(AggregatedMetric {
             id: Uuid::new_v4(),
             source: self.source,
             metric_name: self.metric_name,
-            value: self.value,
+            value,
             timestamp: self.timestamp.unwrap_or(now),
             created_at: now,
             updated_at: now,
             metadata: self.metadata,
-        }
+            labels: self.labels,
+        })
     }
 }
 
@@ -140,6 +140,9 @@ pub struct MetricFilter {
     pub limit: Option<i64>,
     /// 偏移量
     pub offset: Option<i64>,
+    /// 标签过滤（须全部匹配）
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
 }
 
 /// 时间范围
@@ -160,6 +163,7 @@ impl MetricFilter {
             time_range: None,
             limit: None,
             offset: None,
+            labels: BTreeMap::new(),
         }
     }
     
@@ -192,6 +196,20 @@ impl MetricFilter {
         self.offset = Some(offset);
         self
     }
+
+    /// 添加标签过滤
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into().to_lowercase(), value.into().to_lowercase());
+        self
+    }
+
+    /// 指标是否满足数据源、名称、时间范围和标签条件（不考虑分页）
+    pub fn matches(&self, metric: &AggregatedMetric) -> bool {
+        self.source.as_ref().is_none_or(|source| &metric.source == source)
+            && self.metric_name.as_ref().is_none_or(|name| &metric.metric_name == name)
+            && self.time_range.as_ref().is_none_or(|range| range.start <= metric.timestamp && metric.timestamp <= range.end)
+            && metric.has_labels(&self.labels)
+    }
 }
 
 impl Default for MetricFilter {
@@ -203,7 +221,7 @@ impl Default for MetricFilter {
 /// 聚合统计结果
 /// 
 /// 用于返回数据统计信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MetricStats {
     /// 总记录数
     pub total_count: i64,
@@ -215,4 +233,25 @@ pub struct MetricStats {
     pub latest_timestamp: Option<DateTime<Utc>>,
     /// 最早数据时间
     pub earliest_timestamp: Option<DateTime<Utc>>,
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_filter() {
+        let metric = MetricBuilder::new(DataSource::EVERSCAN, "gas_price_gwei")
+            .value(0.01)
+            .label(LABEL_CHAIN, "Arbitrum")
+            .label(LABEL_ASSET, "eth")
+            .build()
+            .unwrap();
+        assert_eq!(metric.labels[LABEL_CHAIN], "arbitrum");
+
+        assert!(MetricFilter::new().label(LABEL_CHAIN, "arbitrum").matches(&metric));
+        assert!(!MetricFilter::new().label(LABEL_CHAIN, "ethereum").matches(&metric));
+        assert!(!MetricFilter::new().label(LABEL_EXCHANGE, "binance").matches(&metric));
+
+        assert!(MetricBuilder::new(DataSource::EVERSCAN, "gas_price_gwei").value(1.0).label(LABEL_CHAIN, " ").build().is_err());
+    }
+}
@@ -1,3 +1,11 @@
+pub mod catalog;
 pub mod metric;
+pub mod source;
+pub mod time;
+pub mod value;
 
-pub use metric::*; 
\ No newline at end of file
+pub use catalog::*;
+pub use metric::*;
+pub use source::*;
+pub use time::*;
+pub use value::*; 
\ No newline at end of file
@@ -0,0 +1,215 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{OnceLock, RwLock};
+
+/// 数据源类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    /// 官方API
+    Api,
+    /// 配置的URL或非官方接口
+    Feed,
+    /// 本地计算的派生数据
+    Derived,
+    /// 外部系统推送的数据（`POST /api/ingest/:source`）
+    Push,
+}
+
+/// 数据源提供的数据类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceCapability {
+    /// 行情（价格、市值、成交量）
+    MarketData,
+    /// 市场情绪指数
+    Sentiment,
+    /// 链上数据
+    OnChain,
+    /// 衍生品（期权、期货）
+    Derivatives,
+    /// 社交热度
+    Social,
+    /// ETF资金流向
+    EtfFlows,
+    /// 宏观事件
+    Macro,
+    /// 搜索热度
+    SearchTrends,
+    /// NFT合集（地板价、成交额）
+    Nft,
+}
+
+/// 数据源描述
+///
+/// 每个客户端以关联常量 `SOURCE` 声明自己的描述，并在创建时登记到全局注册表；
+/// 指标记录的 `source` 字段统一使用描述中的 `name`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DataSource {
+    /// 数据源标识（写入指标记录，小写）
+    pub name: &'static str,
+    /// 显示名称
+    pub display_name: &'static str,
+    /// 数据源类型
+    pub kind: SourceKind,
+    /// 提供的数据类别
+    pub capabilities: &'static [SourceCapability],
+}
+
+impl DataSource {
+    /// 本地计算的派生数据（市场广度、减半周期等）
+    pub const EVERSCAN: DataSource = DataSource {
+        name: "everscan",
+        display_name: "EverScan",
+        kind: SourceKind::Derived,
+        capabilities: &[],
+    };
+
+    /// 从CSV文件导入的历史数据
+    pub const IMPORT: DataSource = DataSource {
+        name: "import",
+        display_name: "CSV导入",
+        kind: SourceKind::Feed,
+        capabilities: &[],
+    };
+
+    /// 转换为字符串
+    pub fn as_str(&self) -> &'static str {
+        self.name
+    }
+
+    /// 是否提供指定类别的数据
+    pub fn has_capability(&self, capability: SourceCapability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+}
+
+impl std::fmt::Display for DataSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// 已登记的数据源
+fn registry() -> &'static RwLock<BTreeMap<&'static str, DataSource>> {
+    static REGISTRY: OnceLock<RwLock<BTreeMap<&'static str, DataSource>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(BTreeMap::from([(DataSource::EVERSCAN.name, DataSource::EVERSCAN)])))
+}
+
+/// 登记数据源，同名数据源以最后一次登记为准
+///
+/// # 参数
+/// * `source` - 数据源描述
+pub fn register_source(source: DataSource) {
+    if let Ok(mut sources) = registry().write() {
+        sources.insert(source.name, source);
+    }
+}
+
+/// 登记数据源，标识或显示名称与已登记的数据源相同时（不区分大小写）不登记
+///
+/// 用于插件等外部提供的数据源，防止覆盖内置数据源的描述
+///
+/// # 返回
+/// * `bool` - 是否已登记
+pub fn register_new_source(source: DataSource) -> bool {
+    let Ok(mut sources) = registry().write() else {
+        return false;
+    };
+    let taken = sources.values().any(|s| {
+        [s.name, s.display_name].iter().any(|n| n.eq_ignore_ascii_case(source.name) || n.eq_ignore_ascii_case(source.display_name))
+    });
+    if !taken {
+        sources.insert(source.name, source);
+    }
+    !taken
+}
+
+/// 按标识或显示名称查找已登记的数据源（不区分大小写）
+///
+/// # 参数
+/// * `name` - 数据源标识（如 `coinmarketcap`）或显示名称（如 `CoinMarketCap`）
+pub fn lookup_source(name: &str) -> Option<DataSource> {
+    let sources = registry().read().ok()?;
+    sources.values()
+        .find(|s| s.name.eq_ignore_ascii_case(name) || s.display_name.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// 所有已登记的数据源（按标识排序）
+pub fn registered_sources() -> Vec<DataSource> {
+    registry().read().map(|sources| sources.values().copied().collect()).unwrap_or_default()
+}
+
+/// 数据源的上游调用统计（进程启动以来）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SourceUsage {
+    /// 成功解析的请求次数
+    pub requests: u64,
+    /// 上游报告消耗的积分（不计积分的数据源为0）
+    pub credits: u64,
+    /// 最后一次请求时间
+    pub last_request_at: Option<DateTime<Utc>>,
+}
+
+/// 各数据源的调用统计
+fn usage() -> &'static RwLock<BTreeMap<&'static str, SourceUsage>> {
+    static USAGE: OnceLock<RwLock<BTreeMap<&'static str, SourceUsage>>> = OnceLock::new();
+    USAGE.get_or_init(|| RwLock::new(BTreeMap::new()))
+}
+
+/// 记录一次上游调用，供运维概览估算配额消耗
+///
+/// # 参数
+/// * `name` - 数据源标识
+/// * `credits` - 本次调用消耗的积分
+pub fn record_source_usage(name: &'static str, credits: u64) {
+    if let Ok(mut usage) = usage().write() {
+        let entry = usage.entry(name).or_default();
+        entry.requests += 1;
+        entry.credits += credits;
+        entry.last_request_at = Some(Utc::now());
+    }
+}
+
+/// 所有数据源的调用统计（按标识排序）
+pub fn source_usage() -> BTreeMap<&'static str, SourceUsage> {
+    usage().read().map(|usage| usage.clone()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_and_lookup() {
+        const TEST_SOURCE: DataSource = DataSource {
+            name: "test_feed",
+            display_name: "Test Feed",
+            kind: SourceKind::Feed,
+            capabilities: &[SourceCapability::Macro],
+        };
+        assert!(lookup_source("everscan").is_some());
+        assert!(lookup_source("test feed").is_none());
+
+        register_source(TEST_SOURCE);
+        assert_eq!(lookup_source("Test Feed"), Some(TEST_SOURCE));
+        assert!(lookup_source("TEST_FEED").unwrap().has_capability(SourceCapability::Macro));
+        assert!(registered_sources().iter().any(|s| s.name == "test_feed"));
+
+        // 外部数据源不能覆盖同名数据源
+        let shadow = DataSource { name: "TEST_FEED", display_name: "Shadow", kind: SourceKind::Api, capabilities: &[] };
+        assert!(!register_new_source(shadow));
+        assert_eq!(lookup_source("test_feed"), Some(TEST_SOURCE));
+    }
+
+    #[test]
+    fn test_record_source_usage() {
+        record_source_usage("usage_feed", 2);
+        record_source_usage("usage_feed", 1);
+        let usage = &source_usage()["usage_feed"];
+        assert_eq!((usage.requests, usage.credits), (2, 3));
+        assert!(usage.last_request_at.is_some());
+    }
+}
@@ -0,0 +1,91 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Deserializer};
+
+/// 无时区的时间格式（按UTC解释）
+const NAIVE_DATETIME_FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M"];
+
+/// 日期格式（按UTC零点解释）
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d"];
+
+/// 解析Unix时间戳，超过10^11的数值按毫秒解释（10^11秒约为公元5138年）
+pub fn from_unix(number: i64) -> Option<DateTime<Utc>> {
+    if number.abs() >= 100_000_000_000 {
+        Utc.timestamp_millis_opt(number).single()
+    } else {
+        Utc.timestamp_opt(number, 0).single()
+    }
+}
+
+/// 解析上游返回的时间
+///
+/// 依次尝试RFC 3339、Unix秒/毫秒、无时区的日期时间和日期，结果统一转换为UTC
+///
+/// # 参数
+/// * `raw` - 原始时间字符串
+pub fn parse_timestamp(raw: &str) -> Result<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    if let Ok(number) = raw.parse::<i64>() {
+        return from_unix(number).ok_or_else(|| anyhow!("无效的Unix时间戳: {}", raw));
+    }
+    if let Some(timestamp) = NAIVE_DATETIME_FORMATS.iter().find_map(|f| NaiveDateTime::parse_from_str(raw, f).ok()) {
+        return Ok(timestamp.and_utc());
+    }
+    DATE_FORMATS.iter()
+        .find_map(|f| NaiveDate::parse_from_str(raw, f).ok())
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|timestamp| timestamp.and_utc())
+        .ok_or_else(|| anyhow!("无法识别的时间格式: {}", raw))
+}
+
+/// 字符串或数字形式的时间
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawTimestamp {
+    Unix(i64),
+    Text(String),
+}
+
+impl RawTimestamp {
+    fn parse(self) -> Result<DateTime<Utc>> {
+        match self {
+            Self::Unix(number) => from_unix(number).ok_or_else(|| anyhow!("无效的Unix时间戳: {}", number)),
+            Self::Text(raw) => parse_timestamp(&raw),
+        }
+    }
+}
+
+/// 反序列化任意格式的时间（`#[serde(deserialize_with = "deserialize_timestamp")]`）
+///
+/// 用于上游响应和旧版本保存的数据：RFC 3339字符串、Unix秒/毫秒（字符串或数字）都转换为UTC
+pub fn deserialize_timestamp<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+    RawTimestamp::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Reading {
+        #[serde(deserialize_with = "deserialize_timestamp")]
+        timestamp: DateTime<Utc>,
+    }
+
+    #[test]
+    fn test_upstream_formats_normalize_to_utc() {
+        let expected = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        for raw in ["2024-01-01T00:00:00Z", "2024-01-01T08:00:00+08:00", "2024-01-01T00:00:00.000Z", "1704067200", "1704067200000", "2024-01-01 00:00:00", "2024-01-01"] {
+            assert_eq!(parse_timestamp(raw).unwrap(), expected, "{}", raw);
+        }
+        assert!(parse_timestamp("yesterday").is_err());
+
+        let reading: Reading = serde_json::from_str(r#"{"timestamp":"1704067200"}"#).unwrap();
+        assert_eq!(reading.timestamp, expected);
+        let reading: Reading = serde_json::from_str(r#"{"timestamp":1704067200}"#).unwrap();
+        assert_eq!(reading.timestamp, expected);
+    }
+}
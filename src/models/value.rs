@@ -0,0 +1,215 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// 时间序列中的数据点
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeriesPoint {
+    /// 时间
+    pub timestamp: DateTime<Utc>,
+    /// 数值
+    pub value: f64,
+}
+
+/// 分布中的一个区间
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistributionBucket {
+    /// 区间标签（如 `0-25`、`>100k`）
+    pub label: String,
+    /// 区间权重（数量或占比）
+    pub weight: f64,
+}
+
+/// 指标值
+///
+/// 与存储中的JSON格式保持兼容：
+/// * 无单位的标量即JSON数字，有单位时为 `{"value": 1.0, "unit": "usd"}`
+/// * 时间序列为 `{"series": [{"timestamp": ..., "value": ...}]}`
+/// * 分布为 `{"distribution": [{"label": ..., "weight": ...}]}`
+/// * 其他JSON对象为复合值，每个字段本身也是指标值（如布林带的 upper/middle/lower）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "Value", try_from = "Value")]
+pub enum MetricValue {
+    /// 标量
+    Scalar {
+        /// 数值
+        value: f64,
+        /// 单位（如 `usd`、`pct`、`count`）
+        unit: Option<String>,
+    },
+    /// 时间序列（按时间升序）
+    Series(Vec<SeriesPoint>),
+    /// 分布
+    Distribution(Vec<DistributionBucket>),
+    /// 复合值（字段名 -> 指标值）
+    Composite(BTreeMap<String, MetricValue>),
+}
+
+impl MetricValue {
+    /// 带单位的标量
+    pub fn scalar(value: f64, unit: impl Into<String>) -> Self {
+        MetricValue::Scalar { value, unit: Some(unit.into()) }
+    }
+
+    /// 标量数值，其他类型返回None
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            MetricValue::Scalar { value, .. } => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// 标量单位
+    pub fn unit(&self) -> Option<&str> {
+        match self {
+            MetricValue::Scalar { unit, .. } => unit.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// 校验数值有效
+    ///
+    /// 数值必须有限，单位和区间标签不能为空，时间序列须按时间升序，
+    /// 序列、分布和复合值不能为空，分布权重不能为负
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            MetricValue::Scalar { value, unit } => {
+                if !value.is_finite() {
+                    bail!("标量数值无效: {}", value);
+                }
+                if unit.as_ref().is_some_and(|u| u.trim().is_empty()) {
+                    bail!("单位不能为空字符串");
+                }
+            }
+            MetricValue::Series(points) => {
+                if points.is_empty() {
+                    bail!("时间序列不能为空");
+                }
+                if let Some(point) = points.iter().find(|p| !p.value.is_finite()) {
+                    bail!("时间序列在 {} 的数值无效", point.timestamp);
+                }
+                if points.windows(2).any(|w| w[0].timestamp > w[1].timestamp) {
+                    bail!("时间序列未按时间升序排列");
+                }
+            }
+            MetricValue::Distribution(buckets) => {
+                if buckets.is_empty() {
+                    bail!("分布不能为空");
+                }
+                for bucket in buckets {
+                    if bucket.label.trim().is_empty() {
+                        bail!("分布区间标签不能为空");
+                    }
+                    if !bucket.weight.is_finite() || bucket.weight < 0.0 {
+                        bail!("分布区间 {} 的权重无效: {}", bucket.label, bucket.weight);
+                    }
+                }
+            }
+            MetricValue::Composite(fields) => {
+                if fields.is_empty() {
+                    bail!("复合值不能为空");
+                }
+                for (name, value) in fields {
+                    value.validate().map_err(|e| anyhow!("字段 {}: {}", name, e))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for MetricValue {
+                fn from(value: $ty) -> Self {
+                    MetricValue::Scalar { value: value as f64, unit: None }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(f64, f32, i64, u64, u32, u8, usize);
+
+impl From<MetricValue> for Value {
+    fn from(value: MetricValue) -> Self {
+        match value {
+            MetricValue::Scalar { value, unit: None } => serde_json::json!(value),
+            MetricValue::Scalar { value, unit: Some(unit) } => serde_json::json!({ "value": value, "unit": unit }),
+            MetricValue::Series(points) => serde_json::json!({ "series": points }),
+            MetricValue::Distribution(buckets) => serde_json::json!({ "distribution": buckets }),
+            MetricValue::Composite(fields) => Value::Object(
+                fields.into_iter().map(|(name, value)| (name, Value::from(value))).collect()
+            ),
+        }
+    }
+}
+
+impl TryFrom<Value> for MetricValue {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Number(n) => n.as_f64()
+                .map(|value| MetricValue::Scalar { value, unit: None })
+                .ok_or_else(|| anyhow!("无法表示的数值: {}", n)),
+            Value::Object(fields) => object_to_value(fields),
+            other => Err(anyhow!("不支持的指标值: {}", other)),
+        }
+    }
+}
+
+/// 按保留字段识别对象类型，其余对象视为复合值
+fn object_to_value(mut fields: Map<String, Value>) -> Result<MetricValue> {
+    let keys: Vec<&str> = fields.keys().map(String::as_str).collect();
+    match keys.as_slice() {
+        ["series"] => Ok(MetricValue::Series(serde_json::from_value(fields.remove("series").unwrap_or_default())?)),
+        ["distribution"] => Ok(MetricValue::Distribution(serde_json::from_value(fields.remove("distribution").unwrap_or_default())?)),
+        ["unit", "value"] | ["value", "unit"] if fields["value"].is_number() && fields["unit"].is_string() => {
+            Ok(MetricValue::Scalar {
+                value: fields["value"].as_f64().unwrap_or_default(),
+                unit: fields["unit"].as_str().map(str::to_string),
+            })
+        }
+        _ => fields.into_iter()
+            .map(|(name, value)| {
+                let value = MetricValue::try_from(value).map_err(|e| anyhow!("字段 {}: {}", name, e))?;
+                Ok((name, value))
+            })
+            .collect::<Result<BTreeMap<_, _>>>()
+            .map(MetricValue::Composite),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip_and_validation() {
+        // 旧格式：裸数字和布林带对象
+        let legacy: MetricValue = serde_json::from_str("42.5").unwrap();
+        assert_eq!(legacy, MetricValue::from(42.5));
+        let bands: MetricValue = serde_json::from_str(r#"{"upper": 2.0, "middle": 1.5, "lower": 1.0}"#).unwrap();
+        assert!(matches!(&bands, MetricValue::Composite(fields) if fields["upper"].as_f64() == Some(2.0)));
+
+        let values = [
+            MetricValue::scalar(1200.0, "usd"),
+            MetricValue::Series(vec![SeriesPoint { timestamp: Utc::now(), value: 1.0 }]),
+            MetricValue::Distribution(vec![DistributionBucket { label: "0-25".to_string(), weight: 3.0 }]),
+            bands,
+        ];
+        for value in values {
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(serde_json::from_str::<MetricValue>(&json).unwrap(), value);
+            assert!(value.validate().is_ok());
+        }
+
+        assert!(serde_json::from_str::<MetricValue>("null").is_err());
+        assert!(MetricValue::from(f64::NAN).validate().is_err());
+        assert!(MetricValue::Distribution(vec![DistributionBucket { label: "a".to_string(), weight: -1.0 }]).validate().is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::{bail, Result};
+use rumqttc::{AsyncClient, EventLoop, MqttOptions, QoS};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::config::MqttConfig;
+use crate::web::cache::DataCache;
+
+/// 发送队列容量
+const REQUEST_CAPACITY: usize = 64;
+
+/// 连接断开后的重连间隔
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// MQTT指标发布器
+///
+/// 订阅缓存更新，把受影响的指标以纯文本数值发布到对应主题；数值未变化时不重复发布
+pub struct MqttPublisher {
+    /// 数据缓存
+    cache: Arc<DataCache>,
+    /// MQTT客户端
+    client: AsyncClient,
+    /// 连接事件循环（由 `run` 在后台驱动）
+    eventloop: EventLoop,
+    /// 发布的指标（指标键 -> 主题）
+    topics: Vec<(String, String)>,
+    /// 服务质量等级
+    qos: QoS,
+    /// 是否作为保留消息发布
+    retain: bool,
+}
+
+impl MqttPublisher {
+    /// 按配置创建发布器（不立即连接）
+    ///
+    /// # 参数
+    /// * `cache` - 数据缓存
+    /// * `config` - MQTT发布配置
+    ///
+    /// # 返回
+    /// * `Result<Self>` - 指标键或服务质量等级无效时返回错误
+    pub fn new(cache: Arc<DataCache>, config: &MqttConfig) -> Result<Self> {
+        let qos = match config.qos {
+            0 => QoS::AtMostOnce,
+            1 => QoS::AtLeastOnce,
+            2 => QoS::ExactlyOnce,
+            other => bail!("无效的MQTT服务质量等级: {}", other),
+        };
+        let topics = resolve_topics(config)?;
+
+        let mut options = MqttOptions::new(&config.client_id, &config.host, config.port);
+        options.set_keep_alive(Duration::from_secs(30));
+        if let Some(username) = &config.username {
+            let password = config.password.as_ref().map(|p| p.expose()).unwrap_or_default();
+            options.set_credentials(username, password);
+        }
+        let (client, eventloop) = AsyncClient::new(options, REQUEST_CAPACITY);
+
+        Ok(Self {
+            cache,
+            client,
+            eventloop,
+            topics,
+            qos,
+            retain: config.retain,
+        })
+    }
+
+    /// 持续发布缓存更新
+    pub async fn run(self) {
+        let Self { cache, client, mut eventloop, topics, qos, retain } = self;
+        info!("📡 启动MQTT发布，共 {} 个指标", topics.len());
+
+        // 事件循环负责连接、重连和实际发送
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = eventloop.poll().await {
+                    warn!("⚠️ MQTT连接异常: {}，{}秒后重连", e, RECONNECT_DELAY.as_secs());
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+        });
+
+        let mut last_values: HashMap<String, f64> = HashMap::new();
+        let mut updates = cache.subscribe();
+
+        // 启动时发布一次已有数据
+        let mut pending: Vec<&(String, String)> = topics.iter().collect();
+        loop {
+            for (key, topic) in pending.drain(..) {
+                let Some(value) = cache.metric_value(key).await else {
+                    continue;
+                };
+                if last_values.get(key) == Some(&value) {
+                    continue;
+                }
+                match client.try_publish(topic.as_str(), qos, retain, format_value(value)) {
+                    Ok(()) => {
+                        debug!("📡 MQTT发布 {} = {}", topic, value);
+                        last_values.insert(key.clone(), value);
+                    }
+                    Err(e) => warn!("⚠️ MQTT发布 {} 失败: {}", topic, e),
+                }
+            }
+
+            match updates.recv().await {
+                Ok(update) => pending.extend(topics.iter().filter(|(key, _)| update.keys.iter().any(|updated| depends_on(key, updated)))),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("⚠️ MQTT发布落后，跳过 {} 条更新事件", skipped);
+                    pending.extend(topics.iter());
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+/// 解析要发布的指标及其主题
+fn resolve_topics(config: &MqttConfig) -> Result<Vec<(String, String)>> {
+    let prefix = config.topic_prefix.trim_end_matches('/');
+    let mut topics: Vec<(String, String)> = config.metrics.iter()
+        .filter(|key| !config.topics.contains_key(*key))
+        .map(|key| (key.clone(), format!("{}/{}", prefix, key.replace(':', "/"))))
+        .collect();
+    topics.extend(config.topics.iter().map(|(key, topic)| (key.clone(), topic.clone())));
+
+    for (key, topic) in &topics {
+        if !DataCache::is_known_metric_key(key) {
+            bail!("MQTT发布不支持的指标键: {}", key);
+        }
+        if topic.is_empty() || topic.contains(['+', '#']) {
+            bail!("指标 {} 的MQTT主题无效: {}", key, topic);
+        }
+    }
+    Ok(topics)
+}
+
+/// 缓存更新是否影响该指标
+fn depends_on(metric_key: &str, update_key: &str) -> bool {
+    match (metric_key.split_once(':'), update_key.strip_prefix("market_data:")) {
+        (Some((_, coin_id)), Some(updated)) => coin_id == updated,
+        (None, None) => metric_key == update_key,
+        _ => false,
+    }
+}
+
+/// 格式化发布的数值（去掉多余的小数位，便于显示屏直接展示）
+fn format_value(value: f64) -> String {
+    let text = format!("{:.8}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topics_and_dependencies() {
+        let mut config = MqttConfig::default();
+        config.topics.insert("price:bitcoin".to_string(), "ticker/btc".to_string());
+        let topics = resolve_topics(&config).unwrap();
+        assert!(topics.contains(&("fear_greed_index".to_string(), "everscan/fear_greed_index".to_string())));
+        assert!(topics.contains(&("price:ethereum".to_string(), "everscan/price/ethereum".to_string())));
+        assert!(topics.contains(&("price:bitcoin".to_string(), "ticker/btc".to_string())));
+        assert_eq!(topics.len(), 4);
+
+        config.metrics.push("tvl".to_string());
+        assert!(resolve_topics(&config).is_err());
+
+        assert!(depends_on("price:bitcoin", "market_data:bitcoin"));
+        assert!(!depends_on("price:bitcoin", "market_data:ethereum"));
+        assert!(depends_on("fear_greed_index", "fear_greed_index"));
+        assert!(!depends_on("fear_greed_index", "market_data:bitcoin"));
+
+        assert_eq!(format_value(42.0), "42");
+        assert_eq!(format_value(0.12345), "0.12345");
+    }
+}
@@ -0,0 +1,204 @@
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::BTreeMap;
+use tracing::{info, warn};
+
+use everscan_plugin::{ApiClient, Metric, Plugin, TaskContext, PLUGIN_API_VERSION};
+
+use crate::models::{register_new_source, AggregatedMetric, DataSource, MetricBuilder, MetricValue, SourceKind};
+use crate::tasks::Task;
+use crate::web::cache::DataCache;
+
+/// 插件任务适配器
+///
+/// 把插件的 [`everscan_plugin::Task`] 包装为内置任务接口，任务ID为 `<插件名>.<任务ID>`，
+/// 指标的数据源为插件名
+pub struct PluginTask {
+    /// 所属插件
+    plugin: &'static Plugin,
+    /// 插件任务
+    inner: Box<dyn everscan_plugin::Task>,
+    /// 任务ID
+    id: String,
+}
+
+impl PluginTask {
+    /// 包装插件任务
+    ///
+    /// # 参数
+    /// * `plugin` - 所属插件
+    /// * `inner` - 插件任务
+    pub fn new(plugin: &'static Plugin, inner: Box<dyn everscan_plugin::Task>) -> Self {
+        let id = format!("{}.{}", plugin.name, inner.id());
+        Self { plugin, inner, id }
+    }
+
+    /// 插件对应的数据源
+    fn source(&self) -> DataSource {
+        source_of(self.plugin)
+    }
+
+    /// 把插件指标转换为聚合指标
+    fn convert(&self, metric: Metric) -> Result<AggregatedMetric> {
+        let value = match metric.unit {
+            Some(unit) => MetricValue::scalar(metric.value, unit),
+            None => MetricValue::from(metric.value),
+        };
+        let mut builder = MetricBuilder::new(self.source(), metric.name).value(value);
+        if let Some(timestamp) = metric.timestamp {
+            builder = builder.timestamp(timestamp);
+        }
+        if let Some(metadata) = metric.metadata {
+            builder = builder.metadata(metadata);
+        }
+        for (key, value) in metric.labels {
+            builder = builder.label(key, value);
+        }
+        builder.build()
+    }
+}
+
+#[async_trait]
+impl Task for PluginTask {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.inner.interval_seconds()
+    }
+
+    async fn execute(&self, _cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let ctx = TaskContext { now: Utc::now() };
+        let metrics = self.inner.collect(&ctx).await
+            .with_context(|| format!("插件任务 {} 采集失败", self.id))?;
+        metrics.into_iter().map(|metric| self.convert(metric)).collect()
+    }
+}
+
+/// 插件对应的数据源
+fn source_of(plugin: &'static Plugin) -> DataSource {
+    DataSource {
+        name: plugin.name,
+        display_name: plugin.display_name,
+        kind: SourceKind::Api,
+        capabilities: &[],
+    }
+}
+
+/// 已链接且接口版本兼容的插件中，配置了 `[plugins.<插件名>]` 的插件
+///
+/// 配置了但未链接的插件名会记录警告
+fn enabled_plugins(configs: &BTreeMap<String, serde_json::Value>) -> Vec<(&'static Plugin, &serde_json::Value)> {
+    let linked = everscan_plugin::plugins();
+    for name in configs.keys() {
+        if !linked.iter().any(|p| p.name == name) {
+            warn!("⚠️ 配置了插件 {}，但程序未链接该插件", name);
+        }
+    }
+    linked.into_iter()
+        .filter(|plugin| {
+            if plugin.api_version != PLUGIN_API_VERSION {
+                warn!("⚠️ 插件 {} 的接口版本 {} 与程序的 {} 不一致，已跳过", plugin.name, plugin.api_version, PLUGIN_API_VERSION);
+                return false;
+            }
+            true
+        })
+        .filter_map(|plugin| configs.get(plugin.name).map(|config| (plugin, config)))
+        .collect()
+}
+
+/// 加载插件任务
+///
+/// # 参数
+/// * `configs` - 配置中的 `[plugins]` 表
+///
+/// # 返回
+/// * `Result<Vec<PluginTask>>` - 插件创建的任务，任一插件创建失败或与已登记的数据源同名时返回错误
+pub fn load_plugin_tasks(configs: &BTreeMap<String, serde_json::Value>) -> Result<Vec<PluginTask>> {
+    let mut tasks = Vec::new();
+    for (plugin, config) in enabled_plugins(configs) {
+        if !register_new_source(source_of(plugin)) {
+            bail!("插件 {} 与已登记的数据源同名，请修改插件名称", plugin.name);
+        }
+        let created = (plugin.create)(config)
+            .with_context(|| format!("插件 {} 创建任务失败", plugin.name))?;
+        info!("🧩 加载插件 {} v{}（{} 个任务）", plugin.display_name, plugin.version, created.len());
+        tasks.extend(created.into_iter().map(|task| PluginTask::new(plugin, task)));
+    }
+    Ok(tasks)
+}
+
+/// 插件及其API客户端的创建结果
+pub type PluginClients = (&'static Plugin, Result<Vec<Box<dyn ApiClient>>>);
+
+/// 创建插件的API客户端（供启动自检使用）
+///
+/// # 参数
+/// * `configs` - 配置中的 `[plugins]` 表
+///
+/// # 返回
+/// * `Vec<PluginClients>` - 每个提供了客户端的插件及其创建结果
+pub fn load_plugin_clients(configs: &BTreeMap<String, serde_json::Value>) -> Vec<PluginClients> {
+    enabled_plugins(configs).into_iter()
+        .filter_map(|(plugin, config)| plugin.clients.map(|create| (plugin, create(config))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use everscan_plugin::MetricBuilder as PluginMetricBuilder;
+
+    struct EchoTask;
+
+    #[async_trait]
+    impl everscan_plugin::Task for EchoTask {
+        fn id(&self) -> &str {
+            "echo"
+        }
+
+        fn name(&self) -> &str {
+            "回显"
+        }
+
+        fn interval_seconds(&self) -> u64 {
+            60
+        }
+
+        async fn collect(&self, _ctx: &TaskContext) -> Result<Vec<Metric>> {
+            Ok(vec![PluginMetricBuilder::new("echo_value").value(42.0).unit("count").label("Chain", "Base").build()?])
+        }
+    }
+
+    everscan_plugin::register_plugin! {
+        name: "echo",
+        display_name: "Echo",
+        version: "0.1.0",
+        create: |_config| Ok(vec![Box::new(EchoTask) as Box<dyn everscan_plugin::Task>]),
+    }
+
+    #[tokio::test]
+    async fn test_load_and_execute_plugin_task() {
+        assert!(load_plugin_tasks(&BTreeMap::new()).unwrap().is_empty());
+
+        let configs = BTreeMap::from([("echo".to_string(), serde_json::json!({}))]);
+        let tasks = load_plugin_tasks(&configs).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id(), "echo.echo");
+
+        let metrics = tasks[0].execute(&DataCache::new()).await.unwrap();
+        assert_eq!(metrics[0].source, "echo");
+        assert_eq!(metrics[0].value, MetricValue::scalar(42.0, "count"));
+        assert_eq!(metrics[0].labels.get("chain").map(String::as_str), Some("base"));
+    }
+}
@@ -0,0 +1,247 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::clients::{connector_for, ExchangeKind};
+use crate::secrets::{SecretBox, MASTER_KEY_ENV};
+use crate::storage::JsonFileStore;
+use crate::web::cache::DataCache;
+use super::PortfolioStore;
+
+/// 交易所账户
+/// 
+/// API密钥以密文形式保存，只在同步时解密
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeAccount {
+    /// 唯一标识符
+    pub id: Uuid,
+    /// 所属用户
+    pub owner: Uuid,
+    /// 交易所
+    pub exchange: ExchangeKind,
+    /// 账户备注名
+    pub label: String,
+    /// 加密后的API密钥
+    pub api_key: String,
+    /// 加密后的API私钥
+    pub api_secret: String,
+    /// 最后一次成功同步时间
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// 最后一次同步错误
+    pub last_error: Option<String>,
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+}
+
+/// 交易所账户公开信息（不含密钥）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeAccountView {
+    /// 唯一标识符
+    pub id: Uuid,
+    /// 交易所
+    pub exchange: ExchangeKind,
+    /// 账户备注名
+    pub label: String,
+    /// 最后一次成功同步时间
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// 最后一次同步错误
+    pub last_error: Option<String>,
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&ExchangeAccount> for ExchangeAccountView {
+    fn from(account: &ExchangeAccount) -> Self {
+        Self {
+            id: account.id,
+            exchange: account.exchange,
+            label: account.label.clone(),
+            last_synced_at: account.last_synced_at,
+            last_error: account.last_error.clone(),
+            created_at: account.created_at,
+        }
+    }
+}
+
+/// 交易所账户输入
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExchangeAccountInput {
+    /// 交易所
+    pub exchange: ExchangeKind,
+    /// 账户备注名（默认使用交易所名称）
+    #[serde(default)]
+    pub label: Option<String>,
+    /// API密钥（只读权限）
+    pub api_key: String,
+    /// API私钥
+    pub api_secret: String,
+}
+
+/// 交易所账户存储
+pub struct ExchangeAccountStore {
+    /// 账户列表
+    accounts: RwLock<Vec<ExchangeAccount>>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<Vec<ExchangeAccount>>>,
+    /// 凭据加密器（未设置主密钥时为空，此时无法添加账户）
+    secret_box: Option<SecretBox>,
+}
+
+impl ExchangeAccountStore {
+    /// 创建内存中的账户存储（不持久化）
+    pub fn new(secret_box: Option<SecretBox>) -> Self {
+        Self {
+            accounts: RwLock::new(Vec::new()),
+            store: None,
+            secret_box,
+        }
+    }
+
+    /// 从状态文件加载账户存储
+    pub async fn open(path: impl Into<PathBuf>, secret_box: Option<SecretBox>) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let accounts: Vec<ExchangeAccount> = store.load().await?;
+        info!("🏦 已加载 {} 个交易所账户", accounts.len());
+        if secret_box.is_none() && !accounts.is_empty() {
+            warn!("⚠️ 未设置 {}，交易所账户将无法同步", MASTER_KEY_ENV);
+        }
+        Ok(Self {
+            accounts: RwLock::new(accounts),
+            store: Some(store),
+            secret_box,
+        })
+    }
+
+    /// 获取用户的交易所账户
+    pub async fn list(&self, owner: Uuid) -> Vec<ExchangeAccountView> {
+        self.accounts.read().await
+            .iter()
+            .filter(|a| a.owner == owner)
+            .map(ExchangeAccountView::from)
+            .collect()
+    }
+
+    /// 添加交易所账户
+    pub async fn add(&self, owner: Uuid, input: ExchangeAccountInput) -> Result<ExchangeAccountView> {
+        let secret_box = self.secret_box()?;
+        if input.api_key.trim().is_empty() || input.api_secret.trim().is_empty() {
+            return Err(anyhow!("API密钥不能为空"));
+        }
+        
+        let account = ExchangeAccount {
+            id: Uuid::new_v4(),
+            owner,
+            exchange: input.exchange,
+            label: input.label.unwrap_or_else(|| input.exchange.to_string()),
+            api_key: secret_box.seal(input.api_key.trim())?,
+            api_secret: secret_box.seal(input.api_secret.trim())?,
+            last_synced_at: None,
+            last_error: None,
+            created_at: Utc::now(),
+        };
+        
+        let mut accounts = self.accounts.write().await;
+        accounts.push(account.clone());
+        self.persist(&accounts).await?;
+        info!("🏦 已添加 {} 账户: {}", account.exchange, account.label);
+        Ok(ExchangeAccountView::from(&account))
+    }
+
+    /// 删除交易所账户及其导入的持仓
+    pub async fn delete(&self, owner: Uuid, id: Uuid, portfolio: &PortfolioStore) -> Result<ExchangeAccountView> {
+        let account = {
+            let mut accounts = self.accounts.write().await;
+            let index = accounts.iter()
+                .position(|a| a.id == id && a.owner == owner)
+                .ok_or_else(|| anyhow!("交易所账户 {} 不存在", id))?;
+            let account = accounts.remove(index);
+            self.persist(&accounts).await?;
+            account
+        };
+        portfolio.remove_imported(id).await?;
+        Ok(ExchangeAccountView::from(&account))
+    }
+
+    /// 同步单个账户的余额到投资组合
+    /// 
+    /// # 返回
+    /// * `Result<usize>` - 导入的资产数量或错误
+    pub async fn sync(&self, id: Uuid, portfolio: &PortfolioStore, cache: &DataCache) -> Result<usize> {
+        let account = self.accounts.read().await
+            .iter()
+            .find(|a| a.id == id)
+            .cloned()
+            .ok_or_else(|| anyhow!("交易所账户 {} 不存在", id))?;
+        
+        let result = self.fetch_and_import(&account, portfolio, cache).await;
+        
+        let mut accounts = self.accounts.write().await;
+        if let Some(stored) = accounts.iter_mut().find(|a| a.id == id) {
+            match &result {
+                Ok(_) => {
+                    stored.last_synced_at = Some(Utc::now());
+                    stored.last_error = None;
+                }
+                Err(e) => stored.last_error = Some(e.to_string()),
+            }
+        }
+        self.persist(&accounts).await?;
+        result
+    }
+
+    /// 同步所有账户
+    /// 
+    /// # 返回
+    /// * `(usize, usize)` - (成功数, 失败数)
+    pub async fn sync_all(&self, portfolio: &PortfolioStore, cache: &DataCache) -> (usize, usize) {
+        let ids: Vec<Uuid> = self.accounts.read().await.iter().map(|a| a.id).collect();
+        let mut succeeded = 0;
+        let mut failed = 0;
+        for id in ids {
+            match self.sync(id, portfolio, cache).await {
+                Ok(count) => {
+                    info!("🏦 交易所账户 {} 同步完成，{} 种资产", id, count);
+                    succeeded += 1;
+                }
+                Err(e) => {
+                    warn!("⚠️ 交易所账户 {} 同步失败: {}", id, e);
+                    failed += 1;
+                }
+            }
+        }
+        (succeeded, failed)
+    }
+
+    /// 解密凭据、拉取余额并写入投资组合
+    async fn fetch_and_import(&self, account: &ExchangeAccount, portfolio: &PortfolioStore, cache: &DataCache) -> Result<usize> {
+        let secret_box = self.secret_box()?;
+        let connector = connector_for(
+            account.exchange,
+            secret_box.open(&account.api_key)?,
+            secret_box.open(&account.api_secret)?,
+        )?;
+        
+        let balances = connector.fetch_balances().await?;
+        portfolio.sync_imported(account.owner, account.id, &balances, cache).await?;
+        Ok(balances.len())
+    }
+
+    /// 获取凭据加密器
+    fn secret_box(&self) -> Result<&SecretBox> {
+        self.secret_box
+            .as_ref()
+            .ok_or_else(|| anyhow!("未设置 {}，无法加密或解密交易所密钥", MASTER_KEY_ENV))
+    }
+
+    /// 持久化
+    async fn persist(&self, accounts: &Vec<ExchangeAccount>) -> Result<()> {
+        if let Some(store) = &self.store {
+            store.save(accounts).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,486 @@
+pub mod exchange;
+
+pub use exchange::*;
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::clients::ExchangeBalance;
+use crate::storage::JsonFileStore;
+use crate::web::cache::DataCache;
+
+/// 每个用户保留的最大估值快照数
+const MAX_SNAPSHOTS_PER_OWNER: usize = 2000;
+
+/// 持仓
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    /// 唯一标识符
+    pub id: Uuid,
+    /// 所属用户
+    pub owner: Uuid,
+    /// 币种ID
+    pub coin_id: String,
+    /// 持有数量
+    pub amount: f64,
+    /// 总成本（USD）
+    pub cost_basis: f64,
+    /// 备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// 导入来源的交易所账户（手动录入时为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<Uuid>,
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+    /// 更新时间
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 持仓输入（新增/编辑）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionInput {
+    /// 币种ID
+    pub coin_id: String,
+    /// 持有数量
+    pub amount: f64,
+    /// 总成本（USD）
+    pub cost_basis: f64,
+    /// 备注
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl PositionInput {
+    /// 校验输入
+    pub fn validate(&self) -> Result<()> {
+        if self.coin_id.trim().is_empty() {
+            return Err(anyhow!("币种ID不能为空"));
+        }
+        if !self.amount.is_finite() || self.amount <= 0.0 {
+            return Err(anyhow!("持有数量必须是正数"));
+        }
+        if !self.cost_basis.is_finite() || self.cost_basis < 0.0 {
+            return Err(anyhow!("成本不能为负数"));
+        }
+        Ok(())
+    }
+}
+
+/// 单个持仓的估值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionValuation {
+    /// 持仓
+    #[serde(flatten)]
+    pub position: Position,
+    /// 当前价格（缓存中没有该币种时为空）
+    pub price: Option<f64>,
+    /// 当前市值
+    pub market_value: Option<f64>,
+    /// 未实现盈亏
+    pub unrealized_pnl: Option<f64>,
+    /// 未实现盈亏百分比
+    pub unrealized_pnl_percent: Option<f64>,
+    /// 占组合市值的百分比
+    pub allocation_percent: Option<f64>,
+}
+
+/// 投资组合估值
+/// 
+/// 汇总数据只统计有价格的持仓，无法定价的币种列在 `unpriced` 中
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioValuation {
+    /// 总市值
+    pub total_value: f64,
+    /// 已定价持仓的总成本
+    pub total_cost: f64,
+    /// 未实现盈亏
+    pub unrealized_pnl: f64,
+    /// 未实现盈亏百分比
+    pub unrealized_pnl_percent: Option<f64>,
+    /// 各持仓估值
+    pub positions: Vec<PositionValuation>,
+    /// 无法定价的币种
+    pub unpriced: Vec<String>,
+    /// 估值时间
+    pub valued_at: DateTime<Utc>,
+}
+
+impl PortfolioValuation {
+    /// 根据价格表计算组合估值
+    /// 
+    /// # 参数
+    /// * `positions` - 持仓列表
+    /// * `prices` - 币种价格表
+    /// 
+    /// # 返回
+    /// * `PortfolioValuation` - 组合估值
+    pub fn compute(positions: &[Position], prices: &HashMap<String, f64>) -> Self {
+        let mut total_value = 0.0;
+        let mut total_cost = 0.0;
+        let mut unpriced = Vec::new();
+        
+        let mut valuations: Vec<PositionValuation> = positions.iter().map(|position| {
+            let price = prices.get(&position.coin_id).copied();
+            let market_value = price.map(|p| p * position.amount);
+            match market_value {
+                Some(value) => {
+                    total_value += value;
+                    total_cost += position.cost_basis;
+                }
+                None => {
+                    if !unpriced.contains(&position.coin_id) {
+                        unpriced.push(position.coin_id.clone());
+                    }
+                }
+            }
+            
+            let unrealized_pnl = market_value.map(|v| v - position.cost_basis);
+            PositionValuation {
+                position: position.clone(),
+                price,
+                market_value,
+                unrealized_pnl,
+                unrealized_pnl_percent: unrealized_pnl.and_then(|pnl| percent(pnl, position.cost_basis)),
+                allocation_percent: None,
+            }
+        }).collect();
+        
+        for valuation in &mut valuations {
+            valuation.allocation_percent = valuation.market_value.and_then(|v| percent(v, total_value));
+        }
+        
+        let unrealized_pnl = total_value - total_cost;
+        Self {
+            total_value,
+            total_cost,
+            unrealized_pnl,
+            unrealized_pnl_percent: percent(unrealized_pnl, total_cost),
+            positions: valuations,
+            unpriced,
+            valued_at: Utc::now(),
+        }
+    }
+}
+
+/// 计算百分比（分母为0时返回None）
+fn percent(value: f64, base: f64) -> Option<f64> {
+    if base > 0.0 {
+        Some(value / base * 100.0)
+    } else {
+        None
+    }
+}
+
+/// 组合估值快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioSnapshot {
+    /// 总市值
+    pub total_value: f64,
+    /// 已定价持仓的总成本
+    pub total_cost: f64,
+    /// 未实现盈亏
+    pub unrealized_pnl: f64,
+    /// 快照时间
+    pub taken_at: DateTime<Utc>,
+}
+
+impl From<&PortfolioValuation> for PortfolioSnapshot {
+    fn from(valuation: &PortfolioValuation) -> Self {
+        Self {
+            total_value: valuation.total_value,
+            total_cost: valuation.total_cost,
+            unrealized_pnl: valuation.unrealized_pnl,
+            taken_at: valuation.valued_at,
+        }
+    }
+}
+
+/// 投资组合存储状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PortfolioState {
+    /// 持仓列表
+    pub positions: Vec<Position>,
+    /// 估值快照
+    /// key: 用户ID, value: 快照（最新的在末尾）
+    pub snapshots: HashMap<Uuid, VecDeque<PortfolioSnapshot>>,
+}
+
+/// 投资组合存储
+pub struct PortfolioStore {
+    /// 存储状态
+    state: RwLock<PortfolioState>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<PortfolioState>>,
+}
+
+impl PortfolioStore {
+    /// 创建内存中的组合存储（不持久化）
+    pub fn new() -> Self {
+        Self {
+            state: RwLock::new(PortfolioState::default()),
+            store: None,
+        }
+    }
+
+    /// 从状态文件加载组合存储
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let state: PortfolioState = store.load().await?;
+        info!("💼 已加载 {} 个持仓", state.positions.len());
+        Ok(Self {
+            state: RwLock::new(state),
+            store: Some(store),
+        })
+    }
+
+    /// 获取用户的所有持仓
+    pub async fn positions(&self, owner: Uuid) -> Vec<Position> {
+        self.state.read().await.positions
+            .iter()
+            .filter(|p| p.owner == owner)
+            .cloned()
+            .collect()
+    }
+
+    /// 获取所有持有仓位的用户
+    pub async fn owners(&self) -> Vec<Uuid> {
+        let mut owners: Vec<Uuid> = self.state.read().await.positions.iter().map(|p| p.owner).collect();
+        owners.sort();
+        owners.dedup();
+        owners
+    }
+
+    /// 新增持仓
+    pub async fn add(&self, owner: Uuid, input: PositionInput) -> Result<Position> {
+        input.validate()?;
+        let now = Utc::now();
+        let position = Position {
+            id: Uuid::new_v4(),
+            owner,
+            coin_id: input.coin_id.trim().to_lowercase(),
+            amount: input.amount,
+            cost_basis: input.cost_basis,
+            note: input.note,
+            source: None,
+            created_at: now,
+            updated_at: now,
+        };
+        
+        let mut state = self.state.write().await;
+        state.positions.push(position.clone());
+        self.persist(&state).await?;
+        info!("💼 新增持仓: {} {}", position.amount, position.coin_id);
+        Ok(position)
+    }
+
+    /// 编辑持仓
+    pub async fn update(&self, owner: Uuid, id: Uuid, input: PositionInput) -> Result<Position> {
+        input.validate()?;
+        let mut state = self.state.write().await;
+        let position = state.positions.iter_mut()
+            .find(|p| p.id == id && p.owner == owner)
+            .ok_or_else(|| anyhow!("持仓 {} 不存在", id))?;
+        
+        position.coin_id = input.coin_id.trim().to_lowercase();
+        position.amount = input.amount;
+        position.cost_basis = input.cost_basis;
+        position.note = input.note;
+        position.updated_at = Utc::now();
+        let position = position.clone();
+        
+        self.persist(&state).await?;
+        Ok(position)
+    }
+
+    /// 删除持仓
+    pub async fn delete(&self, owner: Uuid, id: Uuid) -> Result<Position> {
+        let mut state = self.state.write().await;
+        let index = state.positions.iter()
+            .position(|p| p.id == id && p.owner == owner)
+            .ok_or_else(|| anyhow!("持仓 {} 不存在", id))?;
+        let position = state.positions.remove(index);
+        
+        self.persist(&state).await?;
+        Ok(position)
+    }
+
+    /// 使用缓存中的最新价格为用户组合估值
+    /// 
+    /// # 参数
+    /// * `owner` - 用户ID
+    /// * `cache` - 数据缓存
+    pub async fn valuate(&self, owner: Uuid, cache: &DataCache) -> PortfolioValuation {
+        let positions = self.positions(owner).await;
+        let mut prices = HashMap::new();
+        for position in &positions {
+            if prices.contains_key(&position.coin_id) {
+                continue;
+            }
+            if let Some(price) = cache.metric_value(&format!("price:{}", position.coin_id)).await {
+                prices.insert(position.coin_id.clone(), price);
+            }
+        }
+        PortfolioValuation::compute(&positions, &prices)
+    }
+
+    /// 同步从交易所导入的持仓
+    /// 
+    /// 交易所资产代码按缓存行情中的币种符号映射为币种ID（如 `BTC` -> `bitcoin`），
+    /// 缓存中没有的资产使用小写代码。按币种更新数量，新增的币种成本记为0（可手动编辑），
+    /// 交易所中已不存在的币种会被移除
+    /// 
+    /// # 参数
+    /// * `owner` - 用户ID
+    /// * `source` - 交易所账户ID
+    /// * `balances` - 交易所余额
+    /// * `cache` - 数据缓存
+    pub async fn sync_imported(&self, owner: Uuid, source: Uuid, balances: &[ExchangeBalance], cache: &DataCache) -> Result<()> {
+        let coin_ids = coin_ids_by_symbol(cache).await;
+        let now = Utc::now();
+        let mut state = self.state.write().await;
+        let mut seen = Vec::new();
+        
+        for balance in balances {
+            let asset = balance.asset.to_lowercase();
+            let coin_id = coin_ids.get(&asset).cloned().unwrap_or(asset);
+            let existing = state.positions.iter_mut()
+                .find(|p| p.source == Some(source) && p.coin_id == coin_id);
+            match existing {
+                Some(position) => {
+                    if position.amount != balance.amount {
+                        position.amount = balance.amount;
+                        position.updated_at = now;
+                    }
+                }
+                None => state.positions.push(Position {
+                    id: Uuid::new_v4(),
+                    owner,
+                    coin_id: coin_id.clone(),
+                    amount: balance.amount,
+                    cost_basis: 0.0,
+                    note: None,
+                    source: Some(source),
+                    created_at: now,
+                    updated_at: now,
+                }),
+            }
+            seen.push(coin_id);
+        }
+        
+        state.positions.retain(|p| p.source != Some(source) || seen.contains(&p.coin_id));
+        self.persist(&state).await
+    }
+
+    /// 移除某个交易所账户导入的全部持仓
+    pub async fn remove_imported(&self, source: Uuid) -> Result<()> {
+        let mut state = self.state.write().await;
+        state.positions.retain(|p| p.source != Some(source));
+        self.persist(&state).await
+    }
+
+    /// 记录估值快照
+    pub async fn record_snapshot(&self, owner: Uuid, snapshot: PortfolioSnapshot) {
+        let mut state = self.state.write().await;
+        let snapshots = state.snapshots.entry(owner).or_default();
+        snapshots.push_back(snapshot);
+        while snapshots.len() > MAX_SNAPSHOTS_PER_OWNER {
+            snapshots.pop_front();
+        }
+        if let Err(e) = self.persist(&state).await {
+            warn!("⚠️ 保存组合估值快照失败: {}", e);
+        }
+    }
+
+    /// 获取估值快照
+    /// 
+    /// # 返回
+    /// * `Vec<PortfolioSnapshot>` - 快照（按时间升序，最多 `limit` 条最新记录）
+    pub async fn snapshots(&self, owner: Uuid, limit: usize) -> Vec<PortfolioSnapshot> {
+        let state = self.state.read().await;
+        let Some(snapshots) = state.snapshots.get(&owner) else {
+            return Vec::new();
+        };
+        snapshots.iter().skip(snapshots.len().saturating_sub(limit)).cloned().collect()
+    }
+
+    /// 持久化状态
+    async fn persist(&self, state: &PortfolioState) -> Result<()> {
+        if let Some(store) = &self.store {
+            store.save(state).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for PortfolioStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 缓存行情的小写币种符号 -> 币种ID
+/// 
+/// 多个币种符号相同时取市值排名最靠前的
+async fn coin_ids_by_symbol(cache: &DataCache) -> HashMap<String, String> {
+    let mut coins = cache.get_all_market_data().await;
+    coins.sort_by_key(|coin| std::cmp::Reverse(coin.market_cap_rank.unwrap_or(u64::MAX)));
+    coins.into_iter()
+        .map(|coin| (coin.symbol.to_lowercase(), coin.coin_id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_valuation_pnl_and_allocation() {
+        let store = PortfolioStore::new();
+        let owner = Uuid::new_v4();
+        let input = |coin: &str, amount: f64, cost: f64| PositionInput {
+            coin_id: coin.to_string(),
+            amount,
+            cost_basis: cost,
+            note: None,
+        };
+        store.add(owner, input("BTC", 2.0, 100.0)).await.unwrap();
+        store.add(owner, input("eth", 10.0, 100.0)).await.unwrap();
+        store.add(owner, input("unknown", 1.0, 50.0)).await.unwrap();
+        
+        let prices = HashMap::from([("btc".to_string(), 75.0), ("eth".to_string(), 5.0)]);
+        let valuation = PortfolioValuation::compute(&store.positions(owner).await, &prices);
+        
+        assert_eq!(valuation.total_value, 200.0);
+        assert_eq!(valuation.total_cost, 200.0);
+        assert_eq!(valuation.unrealized_pnl, 0.0);
+        assert_eq!(valuation.unpriced, vec!["unknown"]);
+        
+        let btc = &valuation.positions[0];
+        assert_eq!(btc.unrealized_pnl, Some(50.0));
+        assert_eq!(btc.allocation_percent, Some(75.0));
+        let eth = &valuation.positions[1];
+        assert_eq!(eth.unrealized_pnl_percent, Some(-50.0));
+    }
+
+    #[tokio::test]
+    async fn test_synced_positions_use_coin_ids() {
+        let store = PortfolioStore::new();
+        let cache = DataCache::new();
+        cache.set_coin_data("bitcoin", serde_json::json!({"current_price": 50000.0, "symbol": "BTC", "name": "Bitcoin"})).await;
+        let (owner, source) = (Uuid::new_v4(), Uuid::new_v4());
+        let balance = |asset: &str, amount: f64| ExchangeBalance { asset: asset.to_string(), amount };
+
+        store.sync_imported(owner, source, &[balance("BTC", 0.5), balance("USDT", 100.0)], &cache).await.unwrap();
+        let valuation = store.valuate(owner, &cache).await;
+        assert_eq!(valuation.total_value, 25000.0);
+        assert_eq!(valuation.positions[0].position.coin_id, "bitcoin");
+        assert_eq!(valuation.unpriced, vec!["usdt"]);
+    }
+}
@@ -0,0 +1,206 @@
+pub mod redact;
+
+pub use redact::*;
+
+use anyhow::{Result, Context, anyhow};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+/// 主密钥环境变量
+pub const MASTER_KEY_ENV: &str = "EVERSCAN_MASTER_KEY";
+
+/// 主密钥文件路径环境变量（适用于 Docker secrets / systemd credentials）
+pub const MASTER_KEY_FILE_ENV: &str = "EVERSCAN_MASTER_KEY_FILE";
+
+/// 密文格式前缀（便于以后更换算法）
+const SEALED_PREFIX: &str = "v1:";
+
+/// 配置中加密值的前缀
+pub const ENCRYPTED_VALUE_PREFIX: &str = "enc:";
+
+/// 随机数长度（ChaCha20-Poly1305 使用96位随机数）
+const NONCE_LEN: usize = 12;
+
+/// 凭据加密器
+/// 
+/// 使用 ChaCha20-Poly1305 加密存储的凭据，密文格式为 `v1:<base64(nonce || ciphertext)>`
+pub struct SecretBox {
+    /// AEAD 加密器
+    cipher: ChaCha20Poly1305,
+}
+
+impl SecretBox {
+    /// 使用32字节密钥创建加密器
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(&key)),
+        }
+    }
+
+    /// 从主密钥字符串创建加密器
+    /// 
+    /// 64位十六进制字符串直接作为密钥，其他字符串视为口令并取 SHA-256
+    pub fn from_master_key(master_key: &str) -> Self {
+        let master_key = master_key.trim();
+        let mut key = [0u8; 32];
+        match hex::decode(master_key) {
+            Ok(bytes) if bytes.len() == 32 => key.copy_from_slice(&bytes),
+            _ => key.copy_from_slice(&Sha256::digest(master_key.as_bytes())),
+        }
+        Self::new(key)
+    }
+
+    /// 从环境变量创建加密器
+    /// 
+    /// 优先读取 `EVERSCAN_MASTER_KEY`，其次读取 `EVERSCAN_MASTER_KEY_FILE` 指向的文件
+    /// 
+    /// # 返回
+    /// * `Option<Self>` - 未设置主密钥时返回None
+    pub fn from_env() -> Option<Self> {
+        let master_key = std::env::var(MASTER_KEY_ENV).ok().or_else(|| {
+            std::env::var(MASTER_KEY_FILE_ENV)
+                .ok()
+                .and_then(|path| std::fs::read_to_string(path).ok())
+        })?;
+        
+        let master_key = master_key.trim();
+        if master_key.is_empty() {
+            return None;
+        }
+        redact::register_secret(master_key);
+        Some(Self::from_master_key(master_key))
+    }
+
+    /// 加密明文
+    pub fn seal(&self, plaintext: &str) -> Result<String> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self.cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| anyhow!("加密失败"))?;
+        
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}", SEALED_PREFIX, BASE64.encode(sealed)))
+    }
+
+    /// 解密密文
+    /// 
+    /// 解密得到的明文会登记到日志脱敏列表
+    pub fn open(&self, sealed: &str) -> Result<String> {
+        let encoded = sealed
+            .strip_prefix(SEALED_PREFIX)
+            .ok_or_else(|| anyhow!("不支持的密文格式"))?;
+        let bytes = BASE64.decode(encoded).map_err(|_| anyhow!("密文不是有效的base64"))?;
+        if bytes.len() <= NONCE_LEN {
+            return Err(anyhow!("密文长度无效"));
+        }
+        
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let plaintext = self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("解密失败，主密钥可能不正确"))?;
+        let plaintext = String::from_utf8(plaintext).map_err(|_| anyhow!("解密结果不是有效的UTF-8"))?;
+        redact::register_secret(&plaintext);
+        Ok(plaintext)
+    }
+}
+
+/// 敏感字符串
+/// 
+/// `Debug`、`Display` 和序列化输出都只显示 `***`，需要明文时调用 [`SecretString::expose`]。
+/// 反序列化时支持 `enc:v1:...` 形式的加密值，使用主密钥自动解密
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// 创建敏感字符串并登记到日志脱敏列表
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        redact::register_secret(&value);
+        Self(value)
+    }
+
+    /// 解析可能加密的值
+    /// 
+    /// # 参数
+    /// * `value` - 明文或 `enc:` 开头的密文
+    /// 
+    /// # 返回
+    /// * `Result<Self>` - 明文敏感字符串或错误（未设置主密钥/解密失败）
+    pub fn resolve(value: &str) -> Result<Self> {
+        match value.strip_prefix(ENCRYPTED_VALUE_PREFIX) {
+            Some(sealed) => {
+                let secret_box = SecretBox::from_env()
+                    .ok_or_else(|| anyhow!("配置中包含加密值，但未设置 {} 或 {}", MASTER_KEY_ENV, MASTER_KEY_FILE_ENV))?;
+                let plaintext = secret_box.open(sealed).context("解密配置值失败")?;
+                Ok(Self(plaintext))
+            }
+            None => Ok(Self::new(value)),
+        }
+    }
+
+    /// 获取明文
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl std::fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", REDACTED)
+    }
+}
+
+impl std::fmt::Display for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", REDACTED)
+    }
+}
+
+impl Serialize for SecretString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Self::resolve(&value).map_err(|e| serde::de::Error::custom(format!("{:#}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let secret_box = SecretBox::from_master_key("correct horse battery staple");
+        let sealed = secret_box.seal("api-secret").unwrap();
+        assert!(sealed.starts_with(SEALED_PREFIX));
+        assert!(!sealed.contains("api-secret"));
+        assert_eq!(secret_box.open(&sealed).unwrap(), "api-secret");
+        
+        let other = SecretBox::from_master_key("another key");
+        assert!(other.open(&sealed).is_err());
+    }
+
+    #[test]
+    fn test_secret_string_is_redacted() {
+        let secret = SecretString::new("sk-live-1234567890");
+        assert_eq!(format!("{:?}", secret), REDACTED);
+        assert_eq!(serde_json::to_string(&secret).unwrap(), format!("\"{}\"", REDACTED));
+        assert_eq!(redact("request failed for key sk-live-1234567890"), format!("request failed for key {}", REDACTED));
+    }
+}
@@ -0,0 +1,249 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::web::cache::DataCache;
+
+/// 后台初始化首次重试前的等待时间
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// 后台初始化重试的最长等待时间
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// 等待行情数据时检查缓存的间隔（从快照恢复的缓存不会广播更新事件）
+const MARKET_DATA_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 依赖的就绪状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyState {
+    /// 正在初始化
+    Pending,
+    /// 已就绪
+    Ready,
+    /// 初始化失败，正在重试
+    Retrying,
+}
+
+/// 单个依赖的状态
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyStatus {
+    /// 是否影响就绪状态（非必需的依赖未就绪时只标记为降级）
+    pub required: bool,
+    /// 当前状态
+    pub state: DependencyState,
+    /// 已尝试的次数
+    pub attempts: u32,
+    /// 最近一次失败的原因
+    pub last_error: Option<String>,
+    /// 就绪时间
+    pub ready_at: Option<DateTime<Utc>>,
+}
+
+/// 就绪状态报告
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    /// 全部必需依赖是否已就绪
+    pub ready: bool,
+    /// 是否有非必需依赖尚未就绪
+    pub degraded: bool,
+    /// 进程启动时间
+    pub started_at: DateTime<Utc>,
+    /// 各依赖的状态
+    pub dependencies: BTreeMap<String, DependencyStatus>,
+}
+
+/// 分阶段启动的就绪状态
+///
+/// Web服务器在本地存储加载完成后立即启动，上游数据源、事件总线等外部依赖在后台初始化并重试；
+/// 全部必需依赖就绪后 `/api/ready` 返回200，此前返回503，供负载均衡和编排系统判断是否转发流量
+#[derive(Debug)]
+pub struct Readiness {
+    /// 进程启动时间
+    started_at: DateTime<Utc>,
+    /// 首次重试前的等待时间
+    initial_retry_delay: Duration,
+    /// 各依赖的状态
+    dependencies: RwLock<BTreeMap<String, DependencyStatus>>,
+}
+
+impl Default for Readiness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Readiness {
+    /// 创建就绪状态（尚无依赖时视为就绪）
+    pub fn new() -> Self {
+        Self {
+            started_at: Utc::now(),
+            initial_retry_delay: INITIAL_RETRY_DELAY,
+            dependencies: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// 设置首次重试前的等待时间（之后每次加倍，最长5分钟）
+    pub fn with_initial_retry_delay(mut self, delay: Duration) -> Self {
+        self.initial_retry_delay = delay;
+        self
+    }
+
+    /// 登记依赖
+    ///
+    /// # 参数
+    /// * `name` - 依赖名称
+    /// * `required` - 是否影响就绪状态
+    pub async fn register(&self, name: &str, required: bool) {
+        self.dependencies.write().await.insert(name.to_string(), DependencyStatus {
+            required,
+            state: DependencyState::Pending,
+            attempts: 0,
+            last_error: None,
+            ready_at: None,
+        });
+    }
+
+    /// 标记依赖已就绪
+    pub async fn mark_ready(&self, name: &str) {
+        if let Some(status) = self.dependencies.write().await.get_mut(name) {
+            status.attempts += 1;
+            status.state = DependencyState::Ready;
+            status.ready_at = Some(Utc::now());
+        }
+    }
+
+    /// 记录依赖初始化失败
+    pub async fn mark_failed(&self, name: &str, error: &str) {
+        if let Some(status) = self.dependencies.write().await.get_mut(name) {
+            status.attempts += 1;
+            status.state = DependencyState::Retrying;
+            status.last_error = Some(error.to_string());
+        }
+    }
+
+    /// 全部必需依赖是否已就绪
+    pub async fn is_ready(&self) -> bool {
+        self.dependencies.read().await.values()
+            .all(|status| !status.required || status.state == DependencyState::Ready)
+    }
+
+    /// 当前状态报告
+    pub async fn report(&self) -> ReadinessReport {
+        let dependencies = self.dependencies.read().await.clone();
+        let pending = |required: bool| dependencies.values()
+            .any(|status| status.required == required && status.state != DependencyState::Ready);
+        ReadinessReport {
+            ready: !pending(true),
+            degraded: pending(false),
+            started_at: self.started_at,
+            dependencies,
+        }
+    }
+
+    /// 在后台初始化依赖，失败时按指数退避重试直到成功
+    ///
+    /// # 参数
+    /// * `name` - 依赖名称（需先登记）
+    /// * `init` - 初始化过程，每次重试重新调用
+    ///
+    /// # 返回
+    /// * `T` - 初始化结果
+    pub async fn retry_until_ready<T, F, Fut>(&self, name: &str, mut init: F) -> T
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut delay = self.initial_retry_delay;
+        loop {
+            match init().await {
+                Ok(value) => {
+                    self.mark_ready(name).await;
+                    info!("✅ 依赖 {} 已就绪", name);
+                    return value;
+                }
+                Err(e) => {
+                    self.mark_failed(name, &format!("{:#}", e)).await;
+                    warn!("⚠️ 依赖 {} 初始化失败，{:?} 后重试: {:#}", name, delay, e);
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(MAX_RETRY_DELAY);
+                }
+            }
+        }
+    }
+}
+
+/// 等待缓存中出现行情数据后标记依赖就绪
+///
+/// 上游在启动时不可用时，行情采集任务按调度重试，首次采集成功（或只读实例加载到快照）后就绪
+///
+/// # 参数
+/// * `readiness` - 就绪状态
+/// * `name` - 依赖名称
+/// * `cache` - 数据缓存
+pub async fn wait_for_market_data(readiness: Arc<Readiness>, name: &'static str, cache: Arc<DataCache>) {
+    readiness.register(name, true).await;
+    let mut updates = cache.subscribe();
+    while cache.get_all_market_data().await.is_empty() {
+        tokio::select! {
+            _ = updates.recv() => {}
+            _ = tokio::time::sleep(MARKET_DATA_POLL_INTERVAL) => {}
+        }
+    }
+    readiness.mark_ready(name).await;
+    info!("✅ 依赖 {} 已就绪", name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_readiness_follows_required_dependencies() {
+        let readiness = Readiness::new();
+        assert!(readiness.is_ready().await);
+
+        readiness.register("market_data", true).await;
+        readiness.register("event_bus", false).await;
+        assert!(!readiness.is_ready().await);
+
+        readiness.mark_failed("market_data", "上游不可用").await;
+        let report = readiness.report().await;
+        assert!(!report.ready && report.degraded);
+        assert_eq!(report.dependencies["market_data"].state, DependencyState::Retrying);
+
+        // 非必需依赖未就绪时仍然就绪，但标记为降级
+        readiness.mark_ready("market_data").await;
+        let report = readiness.report().await;
+        assert!(report.ready && report.degraded);
+        assert_eq!(report.dependencies["market_data"].attempts, 2);
+
+        readiness.mark_ready("event_bus").await;
+        assert!(!readiness.report().await.degraded);
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_ready() {
+        let readiness = Readiness::new().with_initial_retry_delay(Duration::from_millis(1));
+        readiness.register("upstream", true).await;
+        let mut calls = 0;
+        let value = readiness.retry_until_ready("upstream", || {
+            calls += 1;
+            let attempt = calls;
+            async move {
+                if attempt < 3 { anyhow::bail!("连接被拒绝") } else { Ok(attempt) }
+            }
+        }).await;
+        assert_eq!(value, 3);
+        let report = readiness.report().await;
+        assert!(report.ready);
+        assert_eq!(report.dependencies["upstream"].attempts, 3);
+        assert_eq!(report.dependencies["upstream"].last_error.as_deref(), Some("连接被拒绝"));
+    }
+}
@@ -0,0 +1,465 @@
+use anyhow::{anyhow, Context, Result};
+use bytes::Bytes;
+use chrono::{DateTime, Duration, Utc};
+use parquet::basic::Compression;
+use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::writer::{SerializedColumnWriter, SerializedFileWriter};
+use parquet::record::RowAccessor;
+use parquet::schema::parser::parse_message_type;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
+
+use super::{JsonFileStore, MetricQuery, MetricStore};
+use crate::maintenance::S3Client;
+use crate::models::AggregatedMetric;
+
+/// Parquet文件结构，值、元数据和标签以JSON字符串保存
+const PARQUET_SCHEMA: &str = "
+message aggregated_metric {
+    required binary id (UTF8);
+    required binary source (UTF8);
+    required binary metric_name (UTF8);
+    required int64 timestamp (TIMESTAMP(MICROS,true));
+    required binary value (UTF8);
+    required int64 created_at (TIMESTAMP(MICROS,true));
+    required int64 updated_at (TIMESTAMP(MICROS,true));
+    optional binary metadata (UTF8);
+    optional binary labels (UTF8);
+}
+";
+
+/// 每个行组的最大行数
+const ROW_GROUP_SIZE: usize = 100_000;
+
+/// 归档文件描述
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveFile {
+    /// 文件名（本地文件名和S3对象名相同）
+    pub name: String,
+    /// 最早数据时间
+    pub earliest: DateTime<Utc>,
+    /// 最新数据时间
+    pub latest: DateTime<Utc>,
+    /// 数据点数
+    pub rows: usize,
+    /// 包含的指标名称
+    pub metric_names: BTreeSet<String>,
+    /// 是否已上传到S3
+    #[serde(default)]
+    pub uploaded: bool,
+}
+
+/// 归档清单
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    /// 早于该时间的数据已移入归档
+    pub archived_until: Option<DateTime<Utc>>,
+    /// 归档文件
+    pub files: Vec<ArchiveFile>,
+}
+
+/// 冷指标归档
+///
+/// 每次归档写入一个Parquet文件并登记到 `manifest.json`；配置了S3时同时上传，
+/// 读取时优先使用本地文件，本地不存在时从S3下载
+pub struct MetricArchive {
+    /// 本地归档目录
+    dir: PathBuf,
+    /// 清单文件
+    manifest_store: JsonFileStore<ArchiveManifest>,
+    /// 清单
+    manifest: RwLock<ArchiveManifest>,
+    /// S3客户端（可选）
+    s3: Option<S3Client>,
+    /// 上传后是否保留本地文件
+    keep_local: bool,
+}
+
+impl MetricArchive {
+    /// 打开归档目录
+    ///
+    /// # 参数
+    /// * `dir` - 本地归档目录
+    /// * `s3` - S3客户端（可选）
+    /// * `keep_local` - 上传到S3后是否保留本地文件
+    pub async fn open(dir: impl Into<PathBuf>, s3: Option<S3Client>, keep_local: bool) -> Result<Self> {
+        let dir = dir.into();
+        let manifest_store = JsonFileStore::new(dir.join("manifest.json"));
+        let manifest: ArchiveManifest = manifest_store.load().await?;
+        info!("🗄️ 已加载 {} 个指标归档文件", manifest.files.len());
+        Ok(Self {
+            dir,
+            manifest_store,
+            manifest: RwLock::new(manifest),
+            keep_local: keep_local || s3.is_none(),
+            s3,
+        })
+    }
+
+    /// 早于该时间的数据已移入归档
+    pub async fn archived_until(&self) -> Option<DateTime<Utc>> {
+        self.manifest.read().await.archived_until
+    }
+
+    /// 当前清单
+    pub async fn manifest(&self) -> ArchiveManifest {
+        self.manifest.read().await.clone()
+    }
+
+    /// 本地归档文件占用的磁盘空间（字节，已上传且未保留的文件不计入）
+    pub async fn disk_bytes(&self) -> u64 {
+        let names: Vec<String> = self.manifest.read().await.files.iter().map(|f| f.name.clone()).collect();
+        let mut total = 0;
+        for name in names {
+            if let Ok(meta) = tokio::fs::metadata(self.dir.join(name)).await {
+                total += meta.len();
+            }
+        }
+        total
+    }
+
+    /// 重新读取清单（只读实例同步采集实例的归档结果）
+    pub async fn reload(&self) -> Result<()> {
+        *self.manifest.write().await = self.manifest_store.load().await?;
+        Ok(())
+    }
+
+    /// 从归档查询单个指标（忽略 `limit`）
+    ///
+    /// # 参数
+    /// * `metric_name` - 指标名称
+    /// * `query` - 查询条件
+    ///
+    /// # 返回
+    /// * `Result<Vec<AggregatedMetric>>` - 按时间升序排列的数据点
+    pub async fn query(&self, metric_name: &str, query: &MetricQuery) -> Result<Vec<AggregatedMetric>> {
+        let files = self.files_for(metric_name, query).await;
+        let mut points = Vec::new();
+        for file in &files {
+            points.extend(self.query_file(file, metric_name, query).await?);
+        }
+        points.sort_by_key(|m| m.timestamp);
+        debug!("🗄️ 从 {} 个归档文件读取指标 {} 的 {} 个数据点", files.len(), metric_name, points.len());
+        Ok(points)
+    }
+
+    /// 可能包含匹配数据点的归档文件（按最早数据时间排序）
+    ///
+    /// # 参数
+    /// * `metric_name` - 指标名称
+    /// * `query` - 查询条件（只使用时间范围）
+    pub async fn files_for(&self, metric_name: &str, query: &MetricQuery) -> Vec<ArchiveFile> {
+        let mut files: Vec<ArchiveFile> = self.manifest.read().await.files.iter()
+            .filter(|f| f.metric_names.contains(metric_name))
+            .filter(|f| query.since.is_none_or(|since| f.latest >= since))
+            .filter(|f| query.until.is_none_or(|until| f.earliest <= until))
+            .cloned()
+            .collect();
+        files.sort_by_key(|f| f.earliest);
+        files
+    }
+
+    /// 从单个归档文件查询指标（忽略 `limit`），供逐个文件流式导出
+    ///
+    /// # 参数
+    /// * `file` - 归档文件
+    /// * `metric_name` - 指标名称
+    /// * `query` - 查询条件
+    ///
+    /// # 返回
+    /// * `Result<Vec<AggregatedMetric>>` - 按时间升序排列的数据点
+    pub async fn query_file(&self, file: &ArchiveFile, metric_name: &str, query: &MetricQuery) -> Result<Vec<AggregatedMetric>> {
+        let content = self.read_file(file).await?;
+        let name = file.name.clone();
+        let rows = tokio::task::spawn_blocking(move || read_parquet(content))
+            .await?
+            .with_context(|| format!("无法读取归档文件: {}", name))?;
+        let mut points: Vec<AggregatedMetric> = rows.into_iter()
+            .filter(|m| m.metric_name == metric_name)
+            .filter(|m| query.since.is_none_or(|since| m.timestamp >= since))
+            .filter(|m| query.until.is_none_or(|until| m.timestamp <= until))
+            .filter(|m| query.source.as_ref().is_none_or(|source| &m.source == source))
+            .filter(|m| m.has_labels(&query.labels))
+            .collect();
+        points.sort_by_key(|m| m.timestamp);
+        Ok(points)
+    }
+
+    /// 写入一批数据点
+    ///
+    /// # 参数
+    /// * `points` - 数据点
+    /// * `until` - 本次归档的边界（早于该时间的数据已全部归档）
+    ///
+    /// # 返回
+    /// * `Result<()>` - 写入本地文件和清单失败时返回错误；上传失败只记录警告，下次归档时重试
+    pub async fn write(&self, points: Vec<AggregatedMetric>, until: DateTime<Utc>) -> Result<()> {
+        let (Some(earliest), Some(latest)) = (
+            points.iter().map(|m| m.timestamp).min(),
+            points.iter().map(|m| m.timestamp).max(),
+        ) else {
+            return Ok(());
+        };
+        let file = ArchiveFile {
+            name: format!("metrics-{}-{}.parquet", until.format("%Y%m%dT%H%M%SZ"), &uuid::Uuid::new_v4().simple().to_string()[..8]),
+            earliest,
+            latest,
+            rows: points.len(),
+            metric_names: points.iter().map(|m| m.metric_name.clone()).collect(),
+            uploaded: false,
+        };
+        let content = tokio::task::spawn_blocking(move || write_parquet(&points)).await??;
+
+        // 先写本地文件，上传成功且不保留本地文件时再删除
+        tokio::fs::create_dir_all(&self.dir).await
+            .with_context(|| format!("无法创建归档目录: {}", self.dir.display()))?;
+        let path = self.dir.join(&file.name);
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, &content).await
+            .with_context(|| format!("无法写入归档文件: {}", tmp_path.display()))?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        info!("🗄️ 已归档 {} 个数据点（{} 至 {}）到 {}", file.rows, earliest, latest, file.name);
+
+        let mut manifest = self.manifest.write().await;
+        manifest.files.push(file);
+        manifest.archived_until = Some(manifest.archived_until.map_or(until, |t| t.max(until)));
+        self.manifest_store.save(&manifest).await?;
+        drop(manifest);
+
+        self.upload_pending().await
+    }
+
+    /// 上传尚未上传到S3的归档文件
+    pub async fn upload_pending(&self) -> Result<()> {
+        let Some(s3) = &self.s3 else {
+            return Ok(());
+        };
+        let mut manifest = self.manifest.write().await;
+        let mut changed = false;
+        for file in manifest.files.iter_mut().filter(|f| !f.uploaded) {
+            let path = self.dir.join(&file.name);
+            let content = match tokio::fs::read(&path).await {
+                Ok(content) => content,
+                Err(e) => {
+                    warn!("⚠️ 无法读取待上传的归档文件 {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            if let Err(e) = s3.put_object(&s3.object_key(&file.name), content).await {
+                warn!("⚠️ 上传归档文件 {} 失败，下次归档时重试: {:#}", file.name, e);
+                continue;
+            }
+            file.uploaded = true;
+            changed = true;
+            if !self.keep_local {
+                tokio::fs::remove_file(&path).await.ok();
+            }
+        }
+        if changed {
+            self.manifest_store.save(&manifest).await?;
+        }
+        Ok(())
+    }
+
+    /// 读取归档文件内容
+    async fn read_file(&self, file: &ArchiveFile) -> Result<Bytes> {
+        let path = self.dir.join(&file.name);
+        match tokio::fs::read(&path).await {
+            Ok(content) => Ok(Bytes::from(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => match (&self.s3, file.uploaded) {
+                (Some(s3), true) => Ok(Bytes::from(s3.get_object(&s3.object_key(&file.name)).await?)),
+                _ => Err(anyhow!("归档文件不存在: {}", path.display())),
+            },
+            Err(e) => Err(e).with_context(|| format!("无法读取归档文件: {}", path.display())),
+        }
+    }
+}
+
+/// 把数据点编码为Parquet
+pub fn write_parquet(points: &[AggregatedMetric]) -> Result<Vec<u8>> {
+    let schema = Arc::new(parse_message_type(PARQUET_SCHEMA)?);
+    let props = Arc::new(WriterProperties::builder().set_compression(Compression::SNAPPY).build());
+    let mut writer = SerializedFileWriter::new(Vec::new(), schema, props)?;
+
+    for chunk in points.chunks(ROW_GROUP_SIZE) {
+        let mut row_group = writer.next_row_group()?;
+        let mut index = 0;
+        while let Some(mut column) = row_group.next_column()? {
+            match index {
+                0 => write_strings(&mut column, chunk.iter().map(|m| Some(m.id.to_string())), false)?,
+                1 => write_strings(&mut column, chunk.iter().map(|m| Some(m.source.clone())), false)?,
+                2 => write_strings(&mut column, chunk.iter().map(|m| Some(m.metric_name.clone())), false)?,
+                3 => write_timestamps(&mut column, chunk.iter().map(|m| m.timestamp))?,
+                4 => write_strings(&mut column, chunk.iter().map(|m| serde_json::to_string(&m.value).ok()), false)?,
+                5 => write_timestamps(&mut column, chunk.iter().map(|m| m.created_at))?,
+                6 => write_timestamps(&mut column, chunk.iter().map(|m| m.updated_at))?,
+                7 => write_strings(&mut column, chunk.iter().map(|m| m.metadata.as_ref().map(|v| v.to_string())), true)?,
+                _ => write_strings(
+                    &mut column,
+                    chunk.iter().map(|m| (!m.labels.is_empty()).then(|| serde_json::to_string(&m.labels).unwrap_or_default())),
+                    true,
+                )?,
+            }
+            column.close()?;
+            index += 1;
+        }
+        row_group.close()?;
+    }
+    Ok(writer.into_inner()?)
+}
+
+/// 写入字符串列（`optional` 为false时缺失值写为空字符串）
+fn write_strings(
+    column: &mut SerializedColumnWriter<'_>,
+    values: impl Iterator<Item = Option<String>>,
+    optional: bool,
+) -> Result<()> {
+    let values: Vec<Option<String>> = values.collect();
+    let levels: Vec<i16> = values.iter().map(|v| i16::from(v.is_some())).collect();
+    let present: Vec<ByteArray> = if optional {
+        values.into_iter().flatten().map(|v| ByteArray::from(v.into_bytes())).collect()
+    } else {
+        values.into_iter().map(|v| ByteArray::from(v.unwrap_or_default().into_bytes())).collect()
+    };
+    column.typed::<ByteArrayType>().write_batch(&present, optional.then_some(levels.as_slice()), None)?;
+    Ok(())
+}
+
+/// 写入微秒时间戳列
+fn write_timestamps(column: &mut SerializedColumnWriter<'_>, values: impl Iterator<Item = DateTime<Utc>>) -> Result<()> {
+    let values: Vec<i64> = values.map(|t| t.timestamp_micros()).collect();
+    column.typed::<Int64Type>().write_batch(&values, None, None)?;
+    Ok(())
+}
+
+/// 解码Parquet中的数据点
+pub fn read_parquet(content: Bytes) -> Result<Vec<AggregatedMetric>> {
+    let reader = SerializedFileReader::new(content)?;
+    let timestamp = |micros: i64| DateTime::from_timestamp_micros(micros).ok_or_else(|| anyhow!("无效的时间戳: {}", micros));
+    let mut points = Vec::new();
+    for row in reader.get_row_iter(None)? {
+        let row = row?;
+        points.push(AggregatedMetric {
+            id: row.get_string(0)?.parse()?,
+            source: row.get_string(1)?.clone(),
+            metric_name: row.get_string(2)?.clone(),
+            timestamp: timestamp(row.get_timestamp_micros(3)?)?,
+            value: serde_json::from_str(row.get_string(4)?)?,
+            created_at: timestamp(row.get_timestamp_micros(5)?)?,
+            updated_at: timestamp(row.get_timestamp_micros(6)?)?,
+            metadata: row.get_string(7).ok().map(|v| serde_json::from_str(v)).transpose()?,
+            labels: row.get_string(8).ok().map(|v| serde_json::from_str(v)).transpose()?.unwrap_or_default(),
+        });
+    }
+    Ok(points)
+}
+
+/// 冷指标归档任务
+///
+/// 按间隔把早于热数据窗口的数据点写入归档，成功后从指标存储中删除
+pub struct MetricArchiver {
+    /// 指标存储
+    store: Arc<MetricStore>,
+    /// 归档
+    archive: Arc<MetricArchive>,
+    /// 热数据窗口
+    hot: Duration,
+    /// 归档间隔
+    interval: std::time::Duration,
+}
+
+impl MetricArchiver {
+    /// 创建归档任务
+    ///
+    /// # 参数
+    /// * `store` - 指标存储
+    /// * `archive` - 归档
+    /// * `hot_days` - 热数据窗口（天）
+    /// * `interval` - 归档间隔
+    pub fn new(store: Arc<MetricStore>, archive: Arc<MetricArchive>, hot_days: u32, interval: std::time::Duration) -> Self {
+        Self {
+            store,
+            archive,
+            hot: Duration::days(hot_days as i64),
+            interval,
+        }
+    }
+
+    /// 持续运行归档循环（启动时立即执行一次）
+    pub async fn run(self) {
+        info!("🗄️ 启动指标归档，保留最近 {} 天的热数据", self.hot.num_days());
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.archive_once().await {
+                error!("❌ 指标归档失败: {:#}", e);
+            }
+        }
+    }
+
+    /// 执行一次归档
+    ///
+    /// # 返回
+    /// * `Result<usize>` - 移入归档的数据点数
+    pub async fn archive_once(&self) -> Result<usize> {
+        let cutoff = Utc::now() - self.hot;
+        let points = self.store.points_before(cutoff).await;
+        if points.is_empty() {
+            return self.archive.upload_pending().await.map(|_| 0);
+        }
+        let ids: HashSet<uuid::Uuid> = points.iter().map(|m| m.id).collect();
+        self.archive.write(points, cutoff).await?;
+        self.store.remove_archived(&ids).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataSource, MetricBuilder};
+
+    #[tokio::test]
+    async fn test_archive_and_transparent_query() {
+        let root = std::env::temp_dir().join(format!("everscan-archive-{}", uuid::Uuid::new_v4()));
+        let now = Utc::now();
+        let metric = |value: f64, days: i64| {
+            let mut metric = MetricBuilder::new(DataSource::EVERSCAN, "breadth_advancers".to_string())
+                .value(value)
+                .timestamp(now - Duration::days(days))
+                .build()
+                .unwrap();
+            metric.labels.insert("chain".to_string(), "ethereum".to_string());
+            metric
+        };
+
+        let archive = Arc::new(MetricArchive::open(root.join("archive"), None, true).await.unwrap());
+        let store = Arc::new(MetricStore::open(root.join("metrics.jsonl"), 365).await.unwrap().with_archive(archive.clone()));
+        store.save_metrics(&[metric(1.0, 100), metric(2.0, 50), metric(3.0, 1)]).await.unwrap();
+
+        let archiver = MetricArchiver::new(store.clone(), archive.clone(), 30, std::time::Duration::from_secs(3600));
+        assert_eq!(archiver.archive_once().await.unwrap(), 2);
+        assert_eq!(archiver.archive_once().await.unwrap(), 0);
+
+        // 不指定起始时间只查询热数据；起始时间早于归档边界时合并归档
+        assert_eq!(store.query("breadth_advancers", &MetricQuery::default()).await.len(), 1);
+        let query = MetricQuery { since: Some(now - Duration::days(200)), ..MetricQuery::default() };
+        let points = store.query("breadth_advancers", &query).await;
+        let values: Vec<f64> = points.iter().map(|m| m.value.as_f64().unwrap()).collect();
+        assert_eq!(values, vec![1.0, 2.0, 3.0]);
+        assert_eq!(points[0].labels.get("chain").map(String::as_str), Some("ethereum"));
+        let limited = MetricQuery { limit: Some(2), ..query };
+        assert_eq!(store.query("breadth_advancers", &limited).await[0].value.as_f64(), Some(2.0));
+
+        // 重新打开后热数据文件中不再包含已归档的数据
+        let reopened = MetricStore::open(root.join("metrics.jsonl"), 365).await.unwrap();
+        assert_eq!(reopened.query("breadth_advancers", &MetricQuery::default()).await.len(), 1);
+
+        tokio::fs::remove_dir_all(&root).await.ok();
+    }
+}
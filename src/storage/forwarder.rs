@@ -0,0 +1,310 @@
+use anyhow::{anyhow, Context, Result};
+use reqwest::Client;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, warn};
+
+use crate::clients::HttpClientBuilder;
+use crate::config::{ForwarderConfig, ForwarderKind};
+use crate::models::{AggregatedMetric, MetricValue};
+use crate::secrets::SecretString;
+use crate::storage::MetricStore;
+
+/// 发送失败时最多保留的待发送行数（超出时丢弃最早的行）
+const MAX_PENDING_LINES: usize = 10_000;
+
+/// 转发目标
+enum Target {
+    /// InfluxDB HTTP写入接口
+    Influxdb {
+        /// HTTP客户端
+        client: Client,
+        /// 写入URL
+        url: String,
+        /// 令牌
+        token: Option<SecretString>,
+    },
+    /// Graphite明文协议地址
+    Graphite {
+        /// `host:port`
+        address: String,
+    },
+}
+
+/// 指标转发器
+///
+/// 订阅指标存储新保存的数据点，转换为InfluxDB行协议或Graphite明文协议后批量发送。
+/// 标量指标写为 `value` 字段，复合指标的标量字段各写一个字段，时间序列和分布不转发
+pub struct MetricForwarder {
+    /// 转发目标名称
+    name: String,
+    /// 转发目标
+    target: Target,
+    /// 指标名称前缀过滤
+    prefixes: Vec<String>,
+    /// Graphite指标路径前缀
+    path_prefix: String,
+    /// 批量行数
+    batch_size: usize,
+    /// 定期发送间隔
+    flush_interval: Duration,
+    /// 待发送的行
+    pending: Vec<String>,
+}
+
+impl MetricForwarder {
+    /// 按配置创建转发器
+    ///
+    /// # 参数
+    /// * `config` - 指标转发配置
+    pub fn new(config: &ForwarderConfig) -> Result<Self> {
+        let target = match config.kind {
+            ForwarderKind::Influxdb => {
+                reqwest::Url::parse(&config.url)
+                    .with_context(|| format!("转发目标 {} 的InfluxDB写入URL无效", config.name))?;
+                Target::Influxdb {
+                    client: HttpClientBuilder::new().timeout(Duration::from_secs(30)).build()?,
+                    url: config.url.clone(),
+                    token: config.token.clone(),
+                }
+            }
+            ForwarderKind::Graphite => {
+                if !config.url.contains(':') {
+                    return Err(anyhow!("转发目标 {} 的Graphite地址应为 host:port", config.name));
+                }
+                Target::Graphite { address: config.url.clone() }
+            }
+        };
+        Ok(Self {
+            name: config.name.clone(),
+            target,
+            prefixes: config.prefixes.clone(),
+            path_prefix: config.path_prefix.trim_end_matches('.').to_string(),
+            batch_size: config.batch_size.max(1),
+            flush_interval: Duration::from_secs(config.flush_interval_seconds.max(1)),
+            pending: Vec::new(),
+        })
+    }
+
+    /// 持续转发新保存的数据点
+    ///
+    /// # 参数
+    /// * `store` - 指标存储
+    pub async fn run(mut self, store: Arc<MetricStore>) {
+        info!("📤 启动指标转发: {}", self.name);
+        let mut saved = store.subscribe();
+        let mut interval = tokio::time::interval(self.flush_interval);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => self.flush().await,
+                batch = saved.recv() => match batch {
+                    Ok(batch) => {
+                        let lines: Vec<String> = batch.iter()
+                            .filter(|m| self.matches(&m.metric_name))
+                            .flat_map(|m| self.format(m))
+                            .collect();
+                        self.pending.extend(lines);
+                        if self.pending.len() >= self.batch_size {
+                            self.flush().await;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("⚠️ 指标转发 {} 落后，跳过 {} 批数据点", self.name, skipped);
+                    }
+                    Err(RecvError::Closed) => {
+                        self.flush().await;
+                        break;
+                    }
+                },
+            }
+        }
+    }
+
+    /// 指标是否需要转发
+    fn matches(&self, metric_name: &str) -> bool {
+        self.prefixes.is_empty() || self.prefixes.iter().any(|p| metric_name.starts_with(p.as_str()))
+    }
+
+    /// 转换为目标协议的行
+    fn format(&self, metric: &AggregatedMetric) -> Vec<String> {
+        match self.target {
+            Target::Influxdb { .. } => influx_line(metric).into_iter().collect(),
+            Target::Graphite { .. } => graphite_lines(metric, &self.path_prefix),
+        }
+    }
+
+    /// 发送待发送的行，失败时保留以便下次重试
+    async fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let mut payload = self.pending.join("\n");
+        payload.push('\n');
+        match self.send(payload).await {
+            Ok(()) => {
+                debug!("📤 指标转发 {} 发送 {} 行", self.name, self.pending.len());
+                self.pending.clear();
+            }
+            Err(e) => {
+                warn!("⚠️ 指标转发 {} 发送失败: {}", self.name, e);
+                if self.pending.len() > MAX_PENDING_LINES {
+                    let excess = self.pending.len() - MAX_PENDING_LINES;
+                    self.pending.drain(..excess);
+                    warn!("⚠️ 指标转发 {} 积压过多，丢弃最早的 {} 行", self.name, excess);
+                }
+            }
+        }
+    }
+
+    /// 发送一批数据
+    async fn send(&self, payload: String) -> Result<()> {
+        match &self.target {
+            Target::Influxdb { client, url, token } => {
+                let mut request = client.post(url).body(payload);
+                if let Some(token) = token {
+                    request = request.header("Authorization", format!("Token {}", token.expose()));
+                }
+                let response = request.send().await?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let body = response.text().await.unwrap_or_default();
+                    return Err(anyhow!("HTTP {}: {}", status, body.trim()));
+                }
+            }
+            Target::Graphite { address } => {
+                let mut stream = tokio::time::timeout(Duration::from_secs(10), TcpStream::connect(address)).await
+                    .map_err(|_| anyhow!("连接 {} 超时", address))??;
+                stream.write_all(payload.as_bytes()).await?;
+                stream.shutdown().await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 数据点的数值字段（字段名 -> 值）
+fn numeric_fields(value: &MetricValue) -> BTreeMap<String, f64> {
+    let fields = match value {
+        MetricValue::Scalar { value, .. } => BTreeMap::from([("value".to_string(), *value)]),
+        MetricValue::Composite(parts) => parts.iter()
+            .filter_map(|(field, part)| Some((field.clone(), part.as_f64()?)))
+            .collect(),
+        MetricValue::Series(_) | MetricValue::Distribution(_) => BTreeMap::new(),
+    };
+    fields.into_iter().filter(|(_, v)| v.is_finite()).collect()
+}
+
+/// 数据点的标签（数据源、单位和自定义标签）
+fn tags(metric: &AggregatedMetric) -> BTreeMap<&str, &str> {
+    let mut tags: BTreeMap<&str, &str> = metric.labels.iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    tags.insert("source", &metric.source);
+    if let Some(unit) = metric.value.unit() {
+        tags.insert("unit", unit);
+    }
+    tags.retain(|k, v| !k.is_empty() && !v.is_empty());
+    tags
+}
+
+/// 转义行协议中的度量名、标签和字段名
+fn escape_influx(value: &str, escape_equals: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == ',' || c == ' ' || (escape_equals && c == '=') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 转换为InfluxDB行协议（纳秒时间戳）
+///
+/// # 返回
+/// * `Option<String>` - 没有数值字段时返回None
+pub fn influx_line(metric: &AggregatedMetric) -> Option<String> {
+    let fields = numeric_fields(&metric.value);
+    if fields.is_empty() {
+        return None;
+    }
+    let mut line = escape_influx(&metric.metric_name, false);
+    for (key, value) in tags(metric) {
+        line.push_str(&format!(",{}={}", escape_influx(key, true), escape_influx(value, true)));
+    }
+    let fields: Vec<String> = fields.iter()
+        .map(|(key, value)| format!("{}={}", escape_influx(key, true), value))
+        .collect();
+    line.push(' ');
+    line.push_str(&fields.join(","));
+    line.push_str(&format!(" {}", metric.timestamp.timestamp_nanos_opt()?));
+    Some(line)
+}
+
+/// 替换Graphite路径和标签中不允许的字符
+fn sanitize_graphite(value: &str) -> String {
+    value.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect()
+}
+
+/// 转换为Graphite明文协议（带标签格式，秒级时间戳）
+///
+/// 标量指标的路径为 `<前缀>.<指标名称>`，复合指标每个字段一行，路径为 `<前缀>.<指标名称>.<字段>`
+pub fn graphite_lines(metric: &AggregatedMetric, path_prefix: &str) -> Vec<String> {
+    let mut base = sanitize_graphite(&metric.metric_name);
+    if !path_prefix.is_empty() {
+        base = format!("{}.{}", path_prefix, base);
+    }
+    let tags: String = tags(metric).into_iter()
+        .map(|(key, value)| format!(";{}={}", sanitize_graphite(key), sanitize_graphite(value)))
+        .collect();
+    let timestamp = metric.timestamp.timestamp();
+    let composite = matches!(metric.value, MetricValue::Composite(_));
+    numeric_fields(&metric.value).into_iter()
+        .map(|(field, value)| {
+            let path = if composite { format!("{}.{}", base, sanitize_graphite(&field)) } else { base.clone() };
+            format!("{}{} {} {}", path, tags, value, timestamp)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataSource, MetricBuilder};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_line_formats() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut metric = MetricBuilder::new(DataSource::EVERSCAN, "etf_flow")
+            .value(MetricValue::scalar(1.5e8, "usd"))
+            .timestamp(timestamp)
+            .label("fund", "ibit,us")
+            .build()
+            .unwrap();
+        metric.metric_name = "etf flow".to_string();
+        assert_eq!(
+            influx_line(&metric).unwrap(),
+            "etf\\ flow,fund=ibit\\,us,source=everscan,unit=usd value=150000000 1704067200000000000",
+        );
+        assert_eq!(
+            graphite_lines(&metric, "everscan"),
+            vec!["everscan.etf_flow;fund=ibit_us;source=everscan;unit=usd 150000000 1704067200"],
+        );
+
+        let bands = MetricValue::Composite(BTreeMap::from([
+            ("upper".to_string(), MetricValue::from(2.0)),
+            ("lower".to_string(), MetricValue::from(1.0)),
+        ]));
+        let composite = MetricBuilder::new(DataSource::EVERSCAN, "bollinger").value(bands).timestamp(timestamp).build().unwrap();
+        assert_eq!(influx_line(&composite).unwrap(), "bollinger,source=everscan lower=1,upper=2 1704067200000000000");
+        let lines = graphite_lines(&composite, "");
+        assert_eq!(lines, vec!["bollinger.lower;source=everscan 1 1704067200", "bollinger.upper;source=everscan 2 1704067200"]);
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use tokio::sync::{RwLock, RwLockReadGuard};
+use uuid::Uuid;
+
+use super::JsonFileStore;
+
+/// 数据点作废记录
+///
+/// 作废的数据点不会被物理删除，默认查询会将其排除
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Invalidation {
+    /// 数据点ID
+    pub id: Uuid,
+    /// 指标名称
+    pub metric_name: String,
+    /// 数据点时间戳
+    pub timestamp: DateTime<Utc>,
+    /// 作废原因（如上游错误报价）
+    pub reason: String,
+    /// 操作人
+    pub invalidated_by: Option<String>,
+    /// 作废时间
+    pub invalidated_at: DateTime<Utc>,
+    /// 替代该数据点的更正数据点ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corrected_by: Option<Uuid>,
+}
+
+/// 要作废的数据点
+///
+/// 按ID或时间戳选择（满足其一即可），可再按数据源过滤
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct InvalidationTarget {
+    /// 指标名称
+    pub metric_name: String,
+    /// 数据点ID
+    #[serde(default)]
+    pub ids: Vec<Uuid>,
+    /// 数据点时间戳
+    #[serde(default)]
+    pub timestamps: Vec<DateTime<Utc>>,
+    /// 按数据源过滤
+    pub source: Option<String>,
+}
+
+/// 数据点作废记录集合
+///
+/// 以数据点ID为键保存在内存中，并持久化到JSON文件
+pub struct InvalidationLog {
+    /// 数据点ID -> 作废记录
+    entries: RwLock<HashMap<Uuid, Invalidation>>,
+    /// 持久化文件（None表示不持久化）
+    file: Option<JsonFileStore<Vec<Invalidation>>>,
+}
+
+impl InvalidationLog {
+    /// 创建内存中的作废记录集合（不持久化）
+    pub fn in_memory() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            file: None,
+        }
+    }
+
+    /// 从JSON文件加载作废记录
+    ///
+    /// # 参数
+    /// * `path` - 文件路径
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let file: JsonFileStore<Vec<Invalidation>> = JsonFileStore::new(path);
+        let entries = file.load().await?.into_iter().map(|e| (e.id, e)).collect();
+        Ok(Self {
+            entries: RwLock::new(entries),
+            file: Some(file),
+        })
+    }
+
+    /// 重新读取其他实例写入的作废记录
+    ///
+    /// # 返回
+    /// * `Result<bool>` - 记录是否有变化
+    pub async fn reload(&self) -> Result<bool> {
+        let Some(file) = &self.file else {
+            return Ok(false);
+        };
+        let loaded: HashMap<Uuid, Invalidation> = file.load().await?.into_iter().map(|e| (e.id, e)).collect();
+        let mut entries = self.entries.write().await;
+        if *entries == loaded {
+            return Ok(false);
+        }
+        *entries = loaded;
+        Ok(true)
+    }
+
+    /// 获取读锁（查询时用于排除作废的数据点）
+    pub async fn read(&self) -> RwLockReadGuard<'_, HashMap<Uuid, Invalidation>> {
+        self.entries.read().await
+    }
+
+    /// 添加作废记录并保存
+    pub async fn insert(&self, records: Vec<Invalidation>) -> Result<()> {
+        let mut entries = self.entries.write().await;
+        entries.extend(records.into_iter().map(|e| (e.id, e)));
+        self.save(&entries).await
+    }
+
+    /// 撤销作废记录并保存
+    ///
+    /// # 返回
+    /// * `Result<Vec<Invalidation>>` - 被撤销的记录
+    pub async fn remove(&self, ids: &HashSet<Uuid>) -> Result<Vec<Invalidation>> {
+        let mut entries = self.entries.write().await;
+        let removed: Vec<Invalidation> = ids.iter().filter_map(|id| entries.remove(id)).collect();
+        if !removed.is_empty() {
+            self.save(&entries).await?;
+        }
+        Ok(removed)
+    }
+
+    /// 作废记录（按作废时间倒序）
+    ///
+    /// # 参数
+    /// * `metric_name` - 只返回该指标的记录（可选）
+    pub async fn list(&self, metric_name: Option<&str>) -> Vec<Invalidation> {
+        let mut records: Vec<Invalidation> = self.entries.read().await.values()
+            .filter(|e| metric_name.is_none_or(|name| e.metric_name == name))
+            .cloned()
+            .collect();
+        records.sort_by_key(|e| std::cmp::Reverse(e.invalidated_at));
+        records
+    }
+
+    /// 写入文件
+    async fn save(&self, entries: &HashMap<Uuid, Invalidation>) -> Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        let mut records: Vec<Invalidation> = entries.values().cloned().collect();
+        records.sort_by_key(|e| (e.timestamp, e.id));
+        file.save(&records).await
+    }
+}
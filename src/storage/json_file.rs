@@ -0,0 +1,83 @@
+use anyhow::{Result, Context};
+use serde::{de::DeserializeOwned, Serialize};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// JSON文件存储
+/// 
+/// 将单个可序列化的状态对象持久化到磁盘，
+/// 写入时先写临时文件再原子重命名，避免进程中断导致文件损坏
+#[derive(Debug, Clone)]
+pub struct JsonFileStore<T> {
+    /// 文件路径
+    path: PathBuf,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> JsonFileStore<T>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    /// 创建新的JSON文件存储
+    /// 
+    /// # 参数
+    /// * `path` - 文件路径
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// 获取文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 加载状态
+    /// 
+    /// 文件不存在时返回默认值
+    /// 
+    /// # 返回
+    /// * `Result<T>` - 状态对象或错误
+    pub async fn load(&self) -> Result<T> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => {
+                let value = serde_json::from_slice(&bytes)
+                    .with_context(|| format!("无法解析状态文件: {}", self.path.display()))?;
+                debug!("📂 已加载状态文件: {}", self.path.display());
+                Ok(value)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                debug!("📂 状态文件不存在，使用默认值: {}", self.path.display());
+                Ok(T::default())
+            }
+            Err(e) => Err(e).with_context(|| format!("无法读取状态文件: {}", self.path.display())),
+        }
+    }
+
+    /// 保存状态
+    /// 
+    /// # 参数
+    /// * `value` - 状态对象
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 成功或错误
+    pub async fn save(&self, value: &T) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await
+                .with_context(|| format!("无法创建目录: {}", parent.display()))?;
+        }
+        
+        let bytes = serde_json::to_vec_pretty(value).context("序列化状态失败")?;
+        let tmp_path = self.path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, bytes).await
+            .with_context(|| format!("无法写入状态文件: {}", tmp_path.display()))?;
+        tokio::fs::rename(&tmp_path, &self.path).await
+            .with_context(|| format!("无法替换状态文件: {}", self.path.display()))?;
+        
+        debug!("💾 已保存状态文件: {}", self.path.display());
+        Ok(())
+    }
+}
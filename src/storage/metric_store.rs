@@ -0,0 +1,1185 @@
+use anyhow::{anyhow, Result, Context};
+use chrono::{DateTime, Duration, Utc};
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::{broadcast, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tracing::{info, warn, debug};
+
+use super::{ArchiveFile, BucketedSeries, Invalidation, InvalidationLog, InvalidationTarget, MetricArchive, QueryCache, QueryLimitError, QueryLimits};
+use crate::models::{AggregatedMetric, MetricStats, MetricValue};
+
+/// 指标查询条件
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MetricQuery {
+    /// 起始时间（包含）
+    pub since: Option<DateTime<Utc>>,
+    /// 结束时间（包含）
+    pub until: Option<DateTime<Utc>>,
+    /// 按数据源过滤
+    pub source: Option<String>,
+    /// 最多返回最近的条数
+    pub limit: Option<usize>,
+    /// 按标签过滤（须全部匹配）
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// 是否包含已作废的数据点（默认排除）
+    #[serde(default)]
+    pub include_invalid: bool,
+}
+
+/// 单个指标的存储概况
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricSummary {
+    /// 指标名称
+    pub metric_name: String,
+    /// 最新数据点的数据源
+    pub source: String,
+    /// 最新数据点的单位
+    pub unit: Option<String>,
+    /// 数据点数
+    pub count: usize,
+    /// 最早数据时间
+    pub earliest: DateTime<Utc>,
+    /// 最新数据时间
+    pub latest: DateTime<Utc>,
+    /// 相邻数据点间隔的中位数（秒，少于两个数据点时为None）
+    pub median_interval_seconds: Option<i64>,
+}
+
+/// 指标存储运行状况
+#[derive(Debug, Clone, Serialize)]
+pub struct StoreDiagnostics {
+    /// 序列数
+    pub series: usize,
+    /// 内存中的数据点数
+    pub points: usize,
+    /// 已读取或写入的文件字节数
+    pub file_bytes: u64,
+    /// 查询次数
+    pub queries: u64,
+    /// 慢查询次数
+    pub slow_queries: u64,
+    /// 慢查询阈值（毫秒，0表示不记录）
+    pub slow_query_threshold_ms: u64,
+    /// 获取读写锁的次数
+    pub lock_acquisitions: u64,
+    /// 平均等待锁的时间（微秒）
+    pub avg_lock_wait_micros: u64,
+    /// 最长等待锁的时间（微秒）
+    pub max_lock_wait_micros: u64,
+    /// 分桶查询缓存的条目数
+    pub query_cache_entries: usize,
+    /// 分桶查询缓存命中次数
+    pub query_cache_hits: u64,
+    /// 分桶查询缓存未命中次数
+    pub query_cache_misses: u64,
+}
+
+/// 查询和锁等待计数
+#[derive(Debug, Default)]
+struct QueryCounters {
+    /// 查询次数
+    queries: AtomicU64,
+    /// 慢查询次数
+    slow_queries: AtomicU64,
+    /// 获取读写锁的次数
+    lock_acquisitions: AtomicU64,
+    /// 累计等待锁的时间（微秒）
+    lock_wait_micros: AtomicU64,
+    /// 最长等待锁的时间（微秒）
+    max_lock_wait_micros: AtomicU64,
+}
+
+impl QueryCounters {
+    /// 记录一次锁等待
+    fn record_lock_wait(&self, waited: std::time::Duration) {
+        let micros = waited.as_micros() as u64;
+        self.lock_acquisitions.fetch_add(1, Ordering::Relaxed);
+        self.lock_wait_micros.fetch_add(micros, Ordering::Relaxed);
+        self.max_lock_wait_micros.fetch_max(micros, Ordering::Relaxed);
+    }
+}
+
+/// 时间桶的最大长度（天）
+pub const MAX_BUCKET_DAYS: i64 = 366;
+
+/// 解析时间桶长度（如 `30s`、`5m`、`1h`、`1d`、`1w`）
+///
+/// # 返回
+/// * `Option<Duration>` - 格式无效、长度不为正或超过 `MAX_BUCKET_DAYS` 时为None
+pub fn parse_bucket(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let unit_start = value.find(|c: char| !c.is_ascii_digit())?;
+    let count: i64 = value[..unit_start].parse().ok().filter(|n| *n > 0)?;
+    let bucket = match &value[unit_start..] {
+        "s" => Duration::try_seconds(count),
+        "m" => Duration::try_minutes(count),
+        "h" => Duration::try_hours(count),
+        "d" => Duration::try_days(count),
+        "w" => Duration::try_weeks(count),
+        _ => None,
+    }?;
+    (bucket <= Duration::days(MAX_BUCKET_DAYS)).then_some(bucket)
+}
+
+/// 按时间桶对数值型数据点取平均
+///
+/// 时间桶按Unix纪元对齐，因此不同指标的同一时间桶起点一致；非数值的数据点被忽略
+///
+/// # 参数
+/// * `points` - 数据点
+/// * `bucket` - 时间桶长度
+///
+/// # 返回
+/// * `BTreeMap<DateTime<Utc>, f64>` - 时间桶起点 -> 平均值
+pub fn bucket_average(points: &[AggregatedMetric], bucket: Duration) -> BTreeMap<DateTime<Utc>, f64> {
+    let width = bucket.num_seconds().max(1);
+    let mut sums: BTreeMap<DateTime<Utc>, (f64, usize)> = BTreeMap::new();
+    for point in points {
+        let Some(value) = point.value.as_f64() else {
+            continue;
+        };
+        let start = point.timestamp.timestamp().div_euclid(width) * width;
+        let Some(start) = DateTime::from_timestamp(start, 0) else {
+            continue;
+        };
+        let entry = sums.entry(start).or_default();
+        entry.0 += value;
+        entry.1 += 1;
+    }
+    sums.into_iter().map(|(start, (sum, count))| (start, sum / count as f64)).collect()
+}
+
+/// 指标时间序列存储
+///
+/// 任务产出的指标按名称分组、按时间排序保存在内存中，
+/// 同时以JSONL格式追加写入文件，启动时重新加载并丢弃超过保留期的数据。
+/// 配置了归档时，起始时间早于归档边界的查询会合并归档中的数据
+pub struct MetricStore {
+    /// 指标名称 -> 按时间排序的数据点
+    series: RwLock<BTreeMap<String, Vec<AggregatedMetric>>>,
+    /// JSONL文件路径（None表示不持久化）
+    path: Option<PathBuf>,
+    /// 保留期
+    retention: Duration,
+    /// 已读取或写入的文件字节数（只读实例据此增量加载）
+    loaded_bytes: AtomicU64,
+    /// 冷数据归档（可选）
+    archive: Option<Arc<MetricArchive>>,
+    /// 慢查询阈值（None表示不记录）
+    slow_query_threshold: Option<std::time::Duration>,
+    /// 查询和锁等待计数
+    counters: QueryCounters,
+    /// 分桶查询缓存
+    query_cache: QueryCache,
+    /// API查询限制
+    limits: QueryLimits,
+    /// 数据点作废记录
+    invalidations: InvalidationLog,
+    /// 新保存数据点的通知（供转发器订阅）
+    saved: broadcast::Sender<Arc<Vec<AggregatedMetric>>>,
+}
+
+/// 新数据点通知的通道容量（按批计）
+const SAVED_CHANNEL_CAPACITY: usize = 256;
+
+/// 导出时每页读取的热数据点数
+pub const EXPORT_PAGE_SIZE: usize = 5_000;
+
+/// 流式导出的进度
+enum ExportCursor {
+    /// 尚未开始
+    Start,
+    /// 逐个读取归档文件，之后从归档边界开始读取热数据
+    Archive { files: VecDeque<ArchiveFile>, archived_until: DateTime<Utc> },
+    /// 分页读取热数据（`after` 为上一页最后一个数据点的时间）
+    Hot { from: Option<DateTime<Utc>>, after: Option<DateTime<Utc>> },
+    /// 已结束
+    Done,
+}
+
+/// 解析JSONL内容
+///
+/// # 返回
+/// * `(Vec<AggregatedMetric>, usize)` - 保留期内的数据点和超过保留期被丢弃的数量
+fn parse_lines(content: &str, cutoff: DateTime<Utc>) -> (Vec<AggregatedMetric>, usize) {
+    let mut metrics = Vec::new();
+    let mut expired = 0usize;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str::<AggregatedMetric>(line) {
+            Ok(metric) if metric.timestamp < cutoff => expired += 1,
+            Ok(metric) => metrics.push(metric),
+            Err(e) => warn!("⚠️ 跳过无法解析的指标记录: {}", e),
+        }
+    }
+    (metrics, expired)
+}
+
+/// 把数据点按名称分组并按时间排序
+fn group_by_name(metrics: Vec<AggregatedMetric>) -> BTreeMap<String, Vec<AggregatedMetric>> {
+    let mut series: BTreeMap<String, Vec<AggregatedMetric>> = BTreeMap::new();
+    for metric in metrics {
+        series.entry(metric.metric_name.clone()).or_default().push(metric);
+    }
+    for points in series.values_mut() {
+        points.sort_by_key(|m| m.timestamp);
+    }
+    series
+}
+
+/// 把一批数据点按时间合并到各序列
+///
+/// 每个序列只做一次归并：新数据点都不早于序列末尾时直接追加，否则只归并受影响的尾部。
+/// 时间相同的数据点中已有的排在前面，结果与逐个按时间插入一致
+fn merge_into(series: &mut BTreeMap<String, Vec<AggregatedMetric>>, metrics: &[AggregatedMetric]) {
+    let mut batches: BTreeMap<&str, Vec<&AggregatedMetric>> = BTreeMap::new();
+    for metric in metrics {
+        batches.entry(metric.metric_name.as_str()).or_default().push(metric);
+    }
+    for (name, mut batch) in batches {
+        batch.sort_by_key(|m| m.timestamp);
+        let points = match series.get_mut(name) {
+            Some(points) => points,
+            None => series.entry(name.to_string()).or_default(),
+        };
+        let start = points.partition_point(|m| m.timestamp <= batch[0].timestamp);
+        if start == points.len() {
+            points.extend(batch.into_iter().cloned());
+            continue;
+        }
+
+        let mut tail = points.split_off(start).into_iter().peekable();
+        points.reserve(tail.len() + batch.len());
+        for metric in batch {
+            while let Some(existing) = tail.next_if(|m| m.timestamp <= metric.timestamp) {
+                points.push(existing);
+            }
+            points.push(metric.clone());
+        }
+        points.extend(tail);
+    }
+}
+
+impl MetricStore {
+    /// 创建内存中的指标存储（不持久化）
+    pub fn new(retention_days: u32) -> Self {
+        Self {
+            series: RwLock::new(BTreeMap::new()),
+            path: None,
+            retention: Duration::days(retention_days as i64),
+            loaded_bytes: AtomicU64::new(0),
+            archive: None,
+            slow_query_threshold: None,
+            counters: QueryCounters::default(),
+            query_cache: QueryCache::new(0),
+            limits: QueryLimits::default(),
+            invalidations: InvalidationLog::in_memory(),
+            saved: broadcast::channel(SAVED_CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// 设置API查询限制（只作用于 `*_checked` 查询）
+    pub fn with_query_limits(mut self, limits: QueryLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 设置分桶查询缓存的容量（0表示不缓存）
+    pub fn with_query_cache(mut self, capacity: usize) -> Self {
+        self.query_cache = QueryCache::new(capacity);
+        self
+    }
+
+    /// 设置慢查询阈值，超过时记录查询条件（0表示不记录）
+    pub fn with_slow_query_threshold(mut self, threshold_ms: u64) -> Self {
+        self.slow_query_threshold = (threshold_ms > 0).then(|| std::time::Duration::from_millis(threshold_ms));
+        self
+    }
+
+    /// 设置冷数据归档
+    pub fn with_archive(mut self, archive: Arc<MetricArchive>) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    /// 从JSONL文件加载指标存储
+    ///
+    /// 加载时丢弃超过保留期的数据；有数据被丢弃时重写文件
+    ///
+    /// # 参数
+    /// * `path` - 文件路径
+    /// * `retention_days` - 保留天数
+    pub async fn open(path: impl Into<PathBuf>, retention_days: u32) -> Result<Self> {
+        Self::open_with(path.into(), retention_days, true).await
+    }
+
+    /// 以只读方式加载指标存储
+    ///
+    /// 与 [`MetricStore::open`] 相同，但不重写文件，供只读API实例与采集实例共享数据目录时使用
+    pub async fn open_read_only(path: impl Into<PathBuf>, retention_days: u32) -> Result<Self> {
+        Self::open_with(path.into(), retention_days, false).await
+    }
+
+    /// 加载指标存储，`prune_file` 为true时重写文件以丢弃过期数据
+    async fn open_with(path: PathBuf, retention_days: u32, prune_file: bool) -> Result<Self> {
+        let retention = Duration::days(retention_days as i64);
+        let cutoff = Utc::now() - retention;
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e).with_context(|| format!("无法读取指标文件: {}", path.display())),
+        };
+        let (metrics, expired) = parse_lines(&content, cutoff);
+        let series = group_by_name(metrics);
+        let count: usize = series.values().map(Vec::len).sum();
+        info!("📈 已加载 {} 个指标的 {} 个数据点", series.len(), count);
+        let invalidations = InvalidationLog::open(path.with_extension("invalidations.json")).await?;
+
+        let store = Self {
+            series: RwLock::new(series),
+            path: Some(path),
+            retention,
+            loaded_bytes: AtomicU64::new(content.len() as u64),
+            archive: None,
+            slow_query_threshold: None,
+            counters: QueryCounters::default(),
+            query_cache: QueryCache::new(0),
+            limits: QueryLimits::default(),
+            invalidations,
+            saved: broadcast::channel(SAVED_CHANNEL_CAPACITY).0,
+        };
+        if expired > 0 && prune_file {
+            info!("🧹 丢弃 {} 个超过保留期的数据点", expired);
+            store.rewrite(&*store.series.read().await).await?;
+        }
+        Ok(store)
+    }
+
+    /// 保存一批指标
+    ///
+    /// # 参数
+    /// * `metrics` - 指标列表
+    ///
+    /// # 返回
+    /// * `Result<()>` - 写入文件失败时返回错误（内存中的数据已更新）
+    pub async fn save_metrics(&self, metrics: &[AggregatedMetric]) -> Result<()> {
+        if metrics.is_empty() {
+            return Ok(());
+        }
+
+        // 在获取写锁之前完成序列化，缩短持锁时间
+        let lines = match &self.path {
+            Some(_) => {
+                let mut lines = Vec::with_capacity(metrics.len() * 256);
+                for metric in metrics {
+                    serde_json::to_writer(&mut lines, metric)?;
+                    lines.push(b'\n');
+                }
+                Some(lines)
+            }
+            None => None,
+        };
+
+        let mut series = self.write_series().await;
+        merge_into(&mut series, metrics);
+        self.query_cache.invalidate(metrics.iter().map(|m| m.metric_name.as_str()));
+        debug!("📈 保存 {} 个指标数据点", metrics.len());
+        if self.saved.receiver_count() > 0 {
+            let _ = self.saved.send(Arc::new(metrics.to_vec()));
+        }
+
+        if let (Some(path), Some(lines)) = (&self.path, lines) {
+            Self::append(path, &lines).await
+                .with_context(|| format!("无法写入指标文件: {}", path.display()))?;
+            self.loaded_bytes.fetch_add(lines.len() as u64, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// 订阅新保存的数据点
+    ///
+    /// 每次 [`MetricStore::save_metrics`] 发送一批；只读实例重新加载的数据点不会发送
+    ///
+    /// # 返回
+    /// * `broadcast::Receiver<Arc<Vec<AggregatedMetric>>>` - 数据点批次接收端
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<Vec<AggregatedMetric>>> {
+        self.saved.subscribe()
+    }
+
+    /// 重新读取其他实例写入文件的数据点
+    ///
+    /// 供只读API实例使用：文件变短（被采集实例重写）时整体重新加载，否则只解析新追加的完整行
+    pub async fn reload(&self) -> Result<()> {
+        if let Some(archive) = &self.archive {
+            archive.reload().await?;
+        }
+        if self.invalidations.reload().await? {
+            self.query_cache.clear();
+        }
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let len = match tokio::fs::metadata(path).await {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e).with_context(|| format!("无法读取指标文件: {}", path.display())),
+        };
+        let offset = self.loaded_bytes.load(Ordering::Relaxed);
+        if len == offset {
+            return Ok(());
+        }
+        let cutoff = Utc::now() - self.retention;
+
+        if len < offset {
+            let content = tokio::fs::read_to_string(path).await
+                .with_context(|| format!("无法读取指标文件: {}", path.display()))?;
+            let (metrics, _) = parse_lines(&content, cutoff);
+            *self.series.write().await = group_by_name(metrics);
+            self.query_cache.clear();
+            self.loaded_bytes.store(content.len() as u64, Ordering::Relaxed);
+            debug!("📈 指标文件已被重写，重新加载全部数据");
+            return Ok(());
+        }
+
+        let mut file = tokio::fs::File::open(path).await
+            .with_context(|| format!("无法读取指标文件: {}", path.display()))?;
+        file.seek(std::io::SeekFrom::Start(offset)).await?;
+        let mut appended = Vec::new();
+        file.read_to_end(&mut appended).await?;
+        // 只处理完整的行，写入中的最后一行留到下次
+        let complete = appended.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+        let (metrics, _) = parse_lines(&String::from_utf8_lossy(&appended[..complete]), cutoff);
+
+        merge_into(&mut *self.series.write().await, &metrics);
+        self.query_cache.invalidate(metrics.iter().map(|m| m.metric_name.as_str()));
+        self.loaded_bytes.fetch_add(complete as u64, Ordering::Relaxed);
+        debug!("📈 增量加载 {} 个指标数据点", metrics.len());
+        Ok(())
+    }
+
+    /// 查询单个指标的时间序列
+    ///
+    /// 起始时间早于归档边界时合并归档中的数据；读取归档失败时记录警告并只返回热数据
+    ///
+    /// # 参数
+    /// * `metric_name` - 指标名称
+    /// * `query` - 查询条件
+    ///
+    /// # 返回
+    /// * `Vec<AggregatedMetric>` - 按时间升序排列的数据点
+    pub async fn query(&self, metric_name: &str, query: &MetricQuery) -> Vec<AggregatedMetric> {
+        let started = Instant::now();
+        let points = self.query_merged(metric_name, query).await;
+        let elapsed = started.elapsed();
+        self.counters.queries.fetch_add(1, Ordering::Relaxed);
+        if self.slow_query_threshold.is_some_and(|threshold| elapsed >= threshold) {
+            self.counters.slow_queries.fetch_add(1, Ordering::Relaxed);
+            warn!(
+                "🐢 指标慢查询 {} 耗时 {} ms，返回 {} 个数据点（since={:?} until={:?} source={:?} labels={:?} limit={:?}）",
+                metric_name, elapsed.as_millis(), points.len(),
+                query.since, query.until, query.source, query.labels, query.limit,
+            );
+        }
+        points
+    }
+
+    /// 查询热数据，需要时合并归档
+    async fn query_merged(&self, metric_name: &str, query: &MetricQuery) -> Vec<AggregatedMetric> {
+        let Some(archive) = &self.archive else {
+            return self.query_hot(metric_name, query).await;
+        };
+        let archived_until = archive.archived_until().await;
+        if query.since.zip(archived_until).is_none_or(|(since, until)| since >= until) {
+            return self.query_hot(metric_name, query).await;
+        }
+
+        let unlimited = MetricQuery { limit: None, ..query.clone() };
+        let mut points = match archive.query(metric_name, &unlimited).await {
+            Ok(points) => points,
+            Err(e) => {
+                warn!("⚠️ 读取指标 {} 的归档失败: {:#}", metric_name, e);
+                return self.query_hot(metric_name, query).await;
+            }
+        };
+        // 只读实例可能尚未重新加载已归档后重写的文件，按ID去重
+        let hot = self.query_hot(metric_name, &unlimited).await;
+        let hot_ids: HashSet<_> = hot.iter().map(|m| m.id).collect();
+        let invalid = self.invalidations.read().await;
+        points.retain(|m| !hot_ids.contains(&m.id) && (query.include_invalid || !invalid.contains_key(&m.id)));
+        drop(invalid);
+        points.extend(hot);
+        points.sort_by_key(|m| m.timestamp);
+        let limit = query.limit.unwrap_or(points.len());
+        points.split_off(points.len().saturating_sub(limit))
+    }
+
+    /// 按时间桶取平均的查询，结果经LRU缓存
+    ///
+    /// 该指标有新数据写入时缓存失效
+    ///
+    /// # 参数
+    /// * `metric_name` - 指标名称
+    /// * `query` - 查询条件
+    /// * `bucket` - 时间桶长度
+    ///
+    /// # 返回
+    /// * `BucketedSeries` - 时间桶起点 -> 平均值
+    pub async fn query_bucketed(&self, metric_name: &str, query: &MetricQuery, bucket: Duration) -> BucketedSeries {
+        let bucket_seconds = bucket.num_seconds();
+        if let Some(series) = self.query_cache.get(metric_name, query, bucket_seconds) {
+            return series;
+        }
+        let generation = self.query_cache.generation();
+        let series = Arc::new(bucket_average(&self.query(metric_name, query).await, bucket));
+        self.query_cache.insert(metric_name, query, bucket_seconds, series.clone(), generation);
+        series
+    }
+
+    /// 带API限制的原始数据点查询
+    ///
+    /// 依次检查时间范围、超时和返回的数据点数
+    ///
+    /// # 返回
+    /// * `Result<Vec<AggregatedMetric>, QueryLimitError>` - 超出限制时返回错误，提示客户端分页或分桶
+    pub async fn query_checked(&self, metric_name: &str, query: &MetricQuery) -> Result<Vec<AggregatedMetric>, QueryLimitError> {
+        self.limits.check_range(query)?;
+        let points = self.with_timeout(self.query(metric_name, query)).await?;
+        self.limits.check_points(points.len())?;
+        Ok(points)
+    }
+
+    /// 带API限制的分桶查询（不限制时间范围，按时间桶数检查数据点上限）
+    pub async fn query_bucketed_checked(
+        &self,
+        metric_name: &str,
+        query: &MetricQuery,
+        bucket: Duration,
+    ) -> Result<BucketedSeries, QueryLimitError> {
+        let series = self.with_timeout(self.query_bucketed(metric_name, query, bucket)).await?;
+        self.limits.check_points(series.len())?;
+        Ok(series)
+    }
+
+    /// 按API查询超时等待
+    async fn with_timeout<F: std::future::Future>(&self, future: F) -> Result<F::Output, QueryLimitError> {
+        match self.limits.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, future).await.map_err(|_| self.limits.timeout_error()),
+            None => Ok(future.await),
+        }
+    }
+
+    /// 只查询内存中的热数据
+    async fn query_hot(&self, metric_name: &str, query: &MetricQuery) -> Vec<AggregatedMetric> {
+        let series = self.read_series().await;
+        let Some(points) = series.get(metric_name) else {
+            return Vec::new();
+        };
+        let invalid = self.invalidations.read().await;
+
+        let start = query.since.map_or(0, |since| points.partition_point(|m| m.timestamp < since));
+        let end = query.until.map_or(points.len(), |until| points.partition_point(|m| m.timestamp <= until));
+        let matched: Vec<&AggregatedMetric> = points[start..end.max(start)]
+            .iter()
+            .filter(|m| query.source.as_ref().is_none_or(|source| &m.source == source))
+            .filter(|m| m.has_labels(&query.labels))
+            .filter(|m| query.include_invalid || !invalid.contains_key(&m.id))
+            .collect();
+        let limit = query.limit.unwrap_or(matched.len());
+        matched[matched.len().saturating_sub(limit)..].iter().map(|m| (*m).clone()).collect()
+    }
+
+    /// 流式导出单个指标的全部匹配数据点（忽略 `limit` 和API查询限制）
+    ///
+    /// 先逐个读取归档文件，再按时间分页读取热数据，每页读取时才持有读锁，
+    /// 内存占用与单个归档文件或单页大小相当，与导出的总行数无关
+    ///
+    /// # 参数
+    /// * `metric_name` - 指标名称
+    /// * `query` - 查询条件
+    ///
+    /// # 返回
+    /// * 按时间升序的数据点分页，读取归档失败时产出错误并结束
+    pub fn export(self: &Arc<Self>, metric_name: &str, query: &MetricQuery) -> impl Stream<Item = Result<Vec<AggregatedMetric>>> + Send + 'static {
+        let state = (self.clone(), metric_name.to_string(), query.clone(), ExportCursor::Start);
+        futures_util::stream::unfold(state, |(store, metric_name, query, mut cursor)| async move {
+            loop {
+                cursor = match cursor {
+                    ExportCursor::Start => match &store.archive {
+                        Some(archive) => match archive.archived_until().await {
+                            Some(archived_until) if query.since.is_none_or(|since| since < archived_until) => ExportCursor::Archive {
+                                files: archive.files_for(&metric_name, &query).await.into(),
+                                archived_until,
+                            },
+                            archived_until => ExportCursor::Hot { from: archived_until, after: None },
+                        },
+                        None => ExportCursor::Hot { from: None, after: None },
+                    },
+                    ExportCursor::Archive { mut files, archived_until } => {
+                        let Some(file) = files.pop_front() else {
+                            cursor = ExportCursor::Hot { from: Some(archived_until), after: None };
+                            continue;
+                        };
+                        let archive = store.archive.as_ref().expect("归档阶段必然配置了归档");
+                        match archive.query_file(&file, &metric_name, &query).await {
+                            Ok(mut points) => {
+                                let invalid = store.invalidations.read().await;
+                                points.retain(|m| m.timestamp < archived_until && (query.include_invalid || !invalid.contains_key(&m.id)));
+                                drop(invalid);
+                                let cursor = ExportCursor::Archive { files, archived_until };
+                                if points.is_empty() {
+                                    cursor
+                                } else {
+                                    return Some((Ok(points), (store, metric_name, query, cursor)));
+                                }
+                            }
+                            Err(e) => return Some((Err(e), (store, metric_name, query, ExportCursor::Done))),
+                        }
+                    }
+                    ExportCursor::Hot { from, after } => {
+                        let page = store.export_page(&metric_name, &query, from, after).await;
+                        let last = page.last().map(|m| m.timestamp)?;
+                        let cursor = ExportCursor::Hot { from, after: Some(last) };
+                        return Some((Ok(page), (store, metric_name, query, cursor)));
+                    }
+                    ExportCursor::Done => return None,
+                };
+            }
+        })
+    }
+
+    /// 读取一页热数据
+    ///
+    /// 每页约 [`EXPORT_PAGE_SIZE`] 个数据点，与最后一个数据点时间相同的数据点归入同一页，
+    /// 下一页从该时间之后开始，翻页期间写入的数据不会重复或遗漏已读部分
+    async fn export_page(
+        &self,
+        metric_name: &str,
+        query: &MetricQuery,
+        from: Option<DateTime<Utc>>,
+        after: Option<DateTime<Utc>>,
+    ) -> Vec<AggregatedMetric> {
+        let series = self.read_series().await;
+        let Some(points) = series.get(metric_name) else {
+            return Vec::new();
+        };
+        let invalid = self.invalidations.read().await;
+
+        let since = query.since.max(from);
+        let start = match after {
+            Some(after) => points.partition_point(|m| m.timestamp <= after),
+            None => since.map_or(0, |since| points.partition_point(|m| m.timestamp < since)),
+        };
+        let mut page: Vec<AggregatedMetric> = Vec::new();
+        for m in &points[start..] {
+            if query.until.is_some_and(|until| m.timestamp > until) {
+                break;
+            }
+            if page.len() >= EXPORT_PAGE_SIZE && page.last().is_some_and(|last| last.timestamp < m.timestamp) {
+                break;
+            }
+            if query.source.as_ref().is_none_or(|source| &m.source == source)
+                && m.has_labels(&query.labels)
+                && (query.include_invalid || !invalid.contains_key(&m.id))
+            {
+                page.push(m.clone());
+            }
+        }
+        page
+    }
+
+    /// 早于指定时间的全部数据点（供归档使用）
+    pub async fn points_before(&self, cutoff: DateTime<Utc>) -> Vec<AggregatedMetric> {
+        let series = self.series.read().await;
+        series.values()
+            .flat_map(|points| points[..points.partition_point(|m| m.timestamp < cutoff)].iter().cloned())
+            .collect()
+    }
+
+    /// 删除已写入归档的数据点并重写文件
+    ///
+    /// # 参数
+    /// * `ids` - 已归档的数据点ID
+    ///
+    /// # 返回
+    /// * `Result<usize>` - 删除的数据点数
+    pub async fn remove_archived(&self, ids: &HashSet<uuid::Uuid>) -> Result<usize> {
+        let mut series = self.series.write().await;
+        let mut removed = 0;
+        for points in series.values_mut() {
+            let before = points.len();
+            points.retain(|m| !ids.contains(&m.id));
+            removed += before - points.len();
+        }
+        series.retain(|_, points| !points.is_empty());
+        if removed > 0 {
+            self.query_cache.clear();
+            self.rewrite(&series).await?;
+        }
+        Ok(removed)
+    }
+
+    /// 获取指标的最新数据点（不含已作废的数据点）
+    pub async fn latest(&self, metric_name: &str) -> Option<AggregatedMetric> {
+        // 与查询相同，先获取序列锁再获取作废记录锁
+        let series = self.series.read().await;
+        let invalid = self.invalidations.read().await;
+        series.get(metric_name)?.iter().rev().find(|m| !invalid.contains_key(&m.id)).cloned()
+    }
+
+    /// 作废数据点（软删除），可同时写入一个更正后的数据点
+    ///
+    /// 作废的数据点保留在存储中，默认查询将其排除，`include_invalid` 查询仍可取回
+    ///
+    /// # 参数
+    /// * `target` - 要作废的数据点
+    /// * `reason` - 作废原因
+    /// * `operator` - 操作人（可选）
+    /// * `corrected` - 更正后的数值（可选，只能选择一个数据点）
+    ///
+    /// # 返回
+    /// * `Result<Vec<Invalidation>>` - 新增的作废记录；没有匹配的有效数据点时返回错误
+    pub async fn invalidate(
+        &self,
+        target: &InvalidationTarget,
+        reason: &str,
+        operator: Option<&str>,
+        corrected: Option<MetricValue>,
+    ) -> Result<Vec<Invalidation>> {
+        if target.ids.is_empty() && target.timestamps.is_empty() {
+            return Err(anyhow!("需要指定数据点ID或时间戳"));
+        }
+        let query = MetricQuery {
+            since: target.timestamps.iter().min().copied(),
+            until: target.timestamps.iter().max().copied(),
+            source: target.source.clone(),
+            ..MetricQuery::default()
+        };
+        let ids: HashSet<uuid::Uuid> = target.ids.iter().copied().collect();
+        let timestamps: HashSet<DateTime<Utc>> = target.timestamps.iter().copied().collect();
+        let matched: Vec<AggregatedMetric> = self.query(&target.metric_name, &query).await
+            .into_iter()
+            .filter(|m| ids.contains(&m.id) || timestamps.contains(&m.timestamp))
+            .collect();
+        if matched.is_empty() {
+            return Err(anyhow!("指标 {} 没有匹配的有效数据点", target.metric_name));
+        }
+
+        let now = Utc::now();
+        let correction = match corrected {
+            Some(_) if matched.len() > 1 => {
+                return Err(anyhow!("更正数值时只能选择一个数据点，当前匹配 {} 个", matched.len()));
+            }
+            Some(value) => {
+                let original = &matched[0];
+                let mut metadata = original.metadata.clone().unwrap_or_else(|| serde_json::json!({}));
+                if let Some(object) = metadata.as_object_mut() {
+                    object.insert("corrects".to_string(), serde_json::json!(original.id));
+                }
+                Some(AggregatedMetric {
+                    id: uuid::Uuid::new_v4(),
+                    value,
+                    created_at: now,
+                    updated_at: now,
+                    metadata: Some(metadata),
+                    ..original.clone()
+                })
+            }
+            None => None,
+        };
+
+        let records: Vec<Invalidation> = matched.iter()
+            .map(|m| Invalidation {
+                id: m.id,
+                metric_name: m.metric_name.clone(),
+                timestamp: m.timestamp,
+                reason: reason.to_string(),
+                invalidated_by: operator.map(str::to_string),
+                invalidated_at: now,
+                corrected_by: correction.as_ref().map(|c| c.id),
+            })
+            .collect();
+        self.invalidations.insert(records.clone()).await?;
+        self.query_cache.invalidate([target.metric_name.as_str()]);
+        if let Some(correction) = correction {
+            self.save_metrics(&[correction]).await?;
+        }
+        info!("🚫 作废指标 {} 的 {} 个数据点: {}", target.metric_name, records.len(), reason);
+        Ok(records)
+    }
+
+    /// 撤销数据点的作废
+    ///
+    /// 已写入的更正数据点不会被删除，需要时可再将其作废
+    ///
+    /// # 返回
+    /// * `Result<Vec<Invalidation>>` - 被撤销的作废记录
+    pub async fn restore_invalidated(&self, ids: &HashSet<uuid::Uuid>) -> Result<Vec<Invalidation>> {
+        let restored = self.invalidations.remove(ids).await?;
+        self.query_cache.invalidate(restored.iter().map(|e| e.metric_name.as_str()));
+        Ok(restored)
+    }
+
+    /// 作废记录（按作废时间倒序）
+    ///
+    /// # 参数
+    /// * `metric_name` - 只返回该指标的记录（可选）
+    pub async fn invalidations(&self, metric_name: Option<&str>) -> Vec<Invalidation> {
+        self.invalidations.list(metric_name).await
+    }
+
+    /// 已存储的指标名称
+    pub async fn metric_names(&self) -> Vec<String> {
+        self.series.read().await.keys().cloned().collect()
+    }
+
+    /// 全部指标的汇总统计
+    pub async fn stats(&self) -> MetricStats {
+        let series = self.series.read().await;
+        let mut stats = MetricStats::default();
+        for (name, points) in series.iter() {
+            stats.total_count += points.len() as i64;
+            stats.by_metric.insert(name.clone(), points.len() as i64);
+            for point in points {
+                *stats.by_source.entry(point.source.clone()).or_insert(0) += 1;
+            }
+            if let (Some(first), Some(last)) = (points.first(), points.last()) {
+                stats.earliest_timestamp = Some(stats.earliest_timestamp.map_or(first.timestamp, |t| t.min(first.timestamp)));
+                stats.latest_timestamp = Some(stats.latest_timestamp.map_or(last.timestamp, |t| t.max(last.timestamp)));
+            }
+        }
+        stats
+    }
+
+    /// 各指标的存储概况（按名称排序）
+    pub async fn summaries(&self) -> Vec<MetricSummary> {
+        let series = self.series.read().await;
+        series.iter()
+            .filter_map(|(name, points)| {
+                let (first, last) = (points.first()?, points.last()?);
+                let mut gaps: Vec<i64> = points.windows(2)
+                    .map(|w| (w[1].timestamp - w[0].timestamp).num_seconds())
+                    .collect();
+                gaps.sort_unstable();
+                Some(MetricSummary {
+                    metric_name: name.clone(),
+                    source: last.source.clone(),
+                    unit: last.value.unit().map(str::to_string),
+                    count: points.len(),
+                    earliest: first.timestamp,
+                    latest: last.timestamp,
+                    median_interval_seconds: gaps.get(gaps.len() / 2).copied(),
+                })
+            })
+            .collect()
+    }
+
+    /// 保留期
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    /// 数据文件和本地归档占用的磁盘空间（字节）
+    pub async fn disk_bytes(&self) -> u64 {
+        let mut total = match &self.path {
+            Some(path) => tokio::fs::metadata(path).await.map(|m| m.len()).unwrap_or(0),
+            None => 0,
+        };
+        if let Some(archive) = &self.archive {
+            total += archive.disk_bytes().await;
+        }
+        total
+    }
+
+    /// 运行状况：数据规模、查询次数和锁等待时间
+    pub async fn diagnostics(&self) -> StoreDiagnostics {
+        let (series, points) = {
+            let series = self.series.read().await;
+            (series.len(), series.values().map(Vec::len).sum())
+        };
+        let (query_cache_entries, query_cache_hits, query_cache_misses) = self.query_cache.stats();
+        let counters = &self.counters;
+        let acquisitions = counters.lock_acquisitions.load(Ordering::Relaxed);
+        StoreDiagnostics {
+            series,
+            points,
+            file_bytes: self.loaded_bytes.load(Ordering::Relaxed),
+            queries: counters.queries.load(Ordering::Relaxed),
+            slow_queries: counters.slow_queries.load(Ordering::Relaxed),
+            slow_query_threshold_ms: self.slow_query_threshold.map_or(0, |t| t.as_millis() as u64),
+            lock_acquisitions: acquisitions,
+            avg_lock_wait_micros: counters.lock_wait_micros.load(Ordering::Relaxed).checked_div(acquisitions).unwrap_or(0),
+            max_lock_wait_micros: counters.max_lock_wait_micros.load(Ordering::Relaxed),
+            query_cache_entries,
+            query_cache_hits,
+            query_cache_misses,
+        }
+    }
+
+    /// 获取读锁并记录等待时间
+    async fn read_series(&self) -> RwLockReadGuard<'_, BTreeMap<String, Vec<AggregatedMetric>>> {
+        let started = Instant::now();
+        let guard = self.series.read().await;
+        self.counters.record_lock_wait(started.elapsed());
+        guard
+    }
+
+    /// 获取写锁并记录等待时间
+    async fn write_series(&self) -> RwLockWriteGuard<'_, BTreeMap<String, Vec<AggregatedMetric>>> {
+        let started = Instant::now();
+        let guard = self.series.write().await;
+        self.counters.record_lock_wait(started.elapsed());
+        guard
+    }
+
+    /// 追加写入文件
+    async fn append(path: &PathBuf, lines: &[u8]) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+        file.write_all(lines).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// 用内存中的数据重写文件
+    async fn rewrite(&self, series: &BTreeMap<String, Vec<AggregatedMetric>>) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut lines = String::new();
+        for metric in series.values().flatten() {
+            lines.push_str(&serde_json::to_string(metric)?);
+            lines.push('\n');
+        }
+        let lines_len = lines.len() as u64;
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, lines).await
+            .with_context(|| format!("无法写入指标文件: {}", tmp_path.display()))?;
+        tokio::fs::rename(&tmp_path, path).await
+            .with_context(|| format!("无法替换指标文件: {}", path.display()))?;
+        self.loaded_bytes.store(lines_len, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataSource, MetricBuilder};
+    use uuid::Uuid;
+
+    fn metric(value: f64, timestamp: DateTime<Utc>) -> AggregatedMetric {
+        MetricBuilder::new(DataSource::EVERSCAN, "breadth_advancers".to_string())
+            .value(value)
+            .timestamp(timestamp)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_save_query_and_retention() {
+        let path = std::env::temp_dir().join(format!("everscan-metrics-{}.jsonl", Uuid::new_v4()));
+        let now = Utc::now();
+
+        let store = MetricStore::open(&path, 30).await.unwrap();
+        store.save_metrics(&[
+            metric(1.0, now - Duration::days(40)),
+            metric(3.0, now),
+            metric(2.0, now - Duration::days(1)),
+        ]).await.unwrap();
+
+        let points = store.query("breadth_advancers", &MetricQuery::default()).await;
+        let values: Vec<f64> = points.iter().map(|m| m.value.as_f64().unwrap()).collect();
+        assert_eq!(values, vec![1.0, 2.0, 3.0]);
+
+        // 重新打开时丢弃超过保留期的数据
+        let reopened = MetricStore::open(&path, 30).await.unwrap();
+        let query = MetricQuery { limit: Some(1), ..MetricQuery::default() };
+        assert_eq!(reopened.query("breadth_advancers", &query).await[0].value.as_f64(), Some(3.0));
+        assert_eq!(reopened.query("breadth_advancers", &MetricQuery::default()).await.len(), 2);
+        let diagnostics = reopened.diagnostics().await;
+        assert_eq!((diagnostics.points, diagnostics.queries, diagnostics.slow_queries), (2, 2, 0));
+
+        tokio::fs::remove_file(&path).await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_read_only_reload_picks_up_appends() {
+        let path = std::env::temp_dir().join(format!("everscan-metrics-{}.jsonl", Uuid::new_v4()));
+        let now = Utc::now();
+
+        let writer = MetricStore::open(&path, 30).await.unwrap();
+        writer.save_metrics(&[metric(1.0, now - Duration::hours(2))]).await.unwrap();
+        let reader = MetricStore::open_read_only(&path, 30).await.unwrap();
+
+        writer.save_metrics(&[metric(2.0, now)]).await.unwrap();
+        reader.reload().await.unwrap();
+        assert_eq!(reader.query("breadth_advancers", &MetricQuery::default()).await.len(), 2);
+
+        // 文件被重写后整体重新加载
+        writer.rewrite(&BTreeMap::new()).await.unwrap();
+        reader.reload().await.unwrap();
+        assert!(reader.query("breadth_advancers", &MetricQuery::default()).await.is_empty());
+
+        tokio::fs::remove_file(&path).await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_batch_merge_keeps_time_order() {
+        let store = MetricStore::new(30);
+        let base = Utc::now() - Duration::days(1);
+        store.save_metrics(&[metric(1.0, base), metric(2.0, base + Duration::hours(2))]).await.unwrap();
+
+        // 乱序批次：追加、插入中间和与已有时间相同的数据点
+        store.save_metrics(&[
+            metric(5.0, base + Duration::hours(3)),
+            metric(3.0, base + Duration::hours(2)),
+            metric(0.0, base - Duration::hours(1)),
+            metric(1.5, base + Duration::hours(1)),
+        ]).await.unwrap();
+
+        let values: Vec<f64> = store.query("breadth_advancers", &MetricQuery::default()).await
+            .iter()
+            .map(|m| m.value.as_f64().unwrap())
+            .collect();
+        assert_eq!(values, vec![0.0, 1.0, 1.5, 2.0, 3.0, 5.0]);
+    }
+
+    #[tokio::test]
+    async fn test_export_pages_keep_equal_timestamps_together() {
+        use futures_util::StreamExt;
+
+        let store = Arc::new(MetricStore::new(30));
+        let base = Utc::now() - Duration::days(1);
+        let total = EXPORT_PAGE_SIZE + 10;
+        // 第一页的最后一个数据点与下一个数据点时间相同
+        let points: Vec<AggregatedMetric> = (0..total)
+            .map(|i| {
+                let second = if i == EXPORT_PAGE_SIZE { i - 1 } else { i };
+                metric(i as f64, base + Duration::seconds(second as i64))
+            })
+            .collect();
+        store.save_metrics(&points).await.unwrap();
+
+        let pages: Vec<Vec<AggregatedMetric>> = store.export("breadth_advancers", &MetricQuery::default())
+            .map(|page| page.unwrap())
+            .collect()
+            .await;
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].len(), EXPORT_PAGE_SIZE + 1);
+        let values: Vec<f64> = pages.iter().flatten().map(|m| m.value.as_f64().unwrap()).collect();
+        assert_eq!(values, (0..total).map(|i| i as f64).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_bucketed_query_cache_invalidation() {
+        let store = MetricStore::new(30).with_query_cache(8);
+        let base = DateTime::from_timestamp(1_700_000_000 / 3600 * 3600, 0).unwrap();
+        store.save_metrics(&[metric(1.0, base), metric(3.0, base + Duration::minutes(30))]).await.unwrap();
+
+        let query = MetricQuery::default();
+        let first = store.query_bucketed("breadth_advancers", &query, Duration::hours(1)).await;
+        let second = store.query_bucketed("breadth_advancers", &query, Duration::hours(1)).await;
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.get(&base), Some(&2.0));
+
+        // 写入该指标后重新计算
+        store.save_metrics(&[metric(5.0, base + Duration::minutes(45))]).await.unwrap();
+        let third = store.query_bucketed("breadth_advancers", &query, Duration::hours(1)).await;
+        assert_eq!(third.get(&base), Some(&3.0));
+        let diagnostics = store.diagnostics().await;
+        assert_eq!((diagnostics.query_cache_hits, diagnostics.query_cache_misses), (1, 2));
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_and_correct() {
+        let path = std::env::temp_dir().join(format!("everscan-metrics-{}.jsonl", Uuid::new_v4()));
+        let now = Utc::now();
+        let bad = metric(900.0, now - Duration::hours(1));
+        let store = MetricStore::open(&path, 30).await.unwrap();
+        store.save_metrics(&[metric(1.0, now - Duration::hours(2)), bad.clone()]).await.unwrap();
+
+        let target = InvalidationTarget {
+            metric_name: "breadth_advancers".to_string(),
+            timestamps: vec![bad.timestamp],
+            ..InvalidationTarget::default()
+        };
+        let records = store.invalidate(&target, "上游错误报价", Some("ops"), Some(MetricValue::from(9.0))).await.unwrap();
+        assert_eq!(records[0].id, bad.id);
+        // 已作废的数据点不能再次作废
+        let by_id = InvalidationTarget { ids: vec![bad.id], timestamps: Vec::new(), ..target.clone() };
+        assert!(store.invalidate(&by_id, "重复", None, None).await.is_err());
+
+        // 默认查询排除作废的数据点，包含更正后的数据点；重新打开后仍然有效
+        let reopened = MetricStore::open(&path, 30).await.unwrap();
+        let values: Vec<f64> = reopened.query("breadth_advancers", &MetricQuery::default()).await
+            .iter()
+            .map(|m| m.value.as_f64().unwrap())
+            .collect();
+        assert_eq!(values, vec![1.0, 9.0]);
+        let all = MetricQuery { include_invalid: true, ..MetricQuery::default() };
+        assert_eq!(reopened.query("breadth_advancers", &all).await.len(), 3);
+
+        reopened.restore_invalidated(&HashSet::from([bad.id])).await.unwrap();
+        assert_eq!(reopened.query("breadth_advancers", &MetricQuery::default()).await.len(), 3);
+        assert!(reopened.invalidations(None).await.is_empty());
+
+        tokio::fs::remove_file(&path).await.ok();
+        tokio::fs::remove_file(path.with_extension("invalidations.json")).await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_checked_query_limits() {
+        let now = Utc::now();
+        let store = MetricStore::new(30).with_query_limits(QueryLimits::new(2, 7, 0));
+        store.save_metrics(&[
+            metric(1.0, now - Duration::days(3)),
+            metric(2.0, now - Duration::days(2)),
+            metric(3.0, now - Duration::days(1)),
+        ]).await.unwrap();
+
+        let too_wide = MetricQuery { since: Some(now - Duration::days(10)), ..MetricQuery::default() };
+        assert_eq!(
+            store.query_checked("breadth_advancers", &too_wide).await.unwrap_err(),
+            QueryLimitError::RangeTooLarge { max_days: 7 },
+        );
+        assert_eq!(
+            store.query_checked("breadth_advancers", &MetricQuery::default()).await.unwrap_err(),
+            QueryLimitError::TooManyPoints { count: 3, max: 2 },
+        );
+        let page = MetricQuery { limit: Some(2), ..MetricQuery::default() };
+        assert_eq!(store.query_checked("breadth_advancers", &page).await.unwrap().len(), 2);
+        // 分桶查询按时间桶数计，不受时间范围限制
+        assert!(store.query_bucketed_checked("breadth_advancers", &too_wide, Duration::weeks(1)).await.is_ok());
+    }
+
+    #[test]
+    fn test_bucket_average() {
+        assert_eq!(parse_bucket("15m"), Some(Duration::minutes(15)));
+        assert_eq!(parse_bucket("0h"), None);
+        assert_eq!(parse_bucket("1y"), None);
+        assert_eq!(parse_bucket("200000000000000d"), None);
+        assert_eq!(parse_bucket("100w"), None);
+
+        let base = DateTime::from_timestamp(1_700_000_000 / 3600 * 3600, 0).unwrap();
+        let points = vec![
+            metric(1.0, base),
+            metric(3.0, base + Duration::minutes(30)),
+            metric(10.0, base + Duration::minutes(90)),
+        ];
+        let buckets = bucket_average(&points, Duration::hours(1));
+        assert_eq!(buckets.values().copied().collect::<Vec<_>>(), vec![2.0, 10.0]);
+        assert_eq!(buckets.keys().next(), Some(&base));
+    }
+}
@@ -0,0 +1,17 @@
+pub mod archive;
+pub mod forwarder;
+pub mod invalidation;
+pub mod json_file;
+pub mod metric_store;
+pub mod query_cache;
+pub mod query_limits;
+pub mod replica;
+
+pub use archive::*;
+pub use forwarder::*;
+pub use invalidation::*;
+pub use json_file::*;
+pub use metric_store::*;
+pub use query_cache::*;
+pub use query_limits::*;
+pub use replica::*;
@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::MetricQuery;
+
+/// 分桶查询结果
+pub type BucketedSeries = Arc<BTreeMap<DateTime<Utc>, f64>>;
+
+/// 缓存键：指标、查询条件和时间桶
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct QueryKey {
+    /// 指标名称
+    metric_name: String,
+    /// 起始时间
+    since: Option<DateTime<Utc>>,
+    /// 结束时间
+    until: Option<DateTime<Utc>>,
+    /// 数据源
+    source: Option<String>,
+    /// 标签过滤
+    labels: BTreeMap<String, String>,
+    /// 条数限制
+    limit: Option<usize>,
+    /// 是否包含已作废的数据点
+    include_invalid: bool,
+    /// 时间桶长度（秒）
+    bucket_seconds: i64,
+}
+
+impl QueryKey {
+    fn new(metric_name: &str, query: &MetricQuery, bucket_seconds: i64) -> Self {
+        Self {
+            metric_name: metric_name.to_string(),
+            since: query.since,
+            until: query.until,
+            source: query.source.clone(),
+            labels: query.labels.clone(),
+            limit: query.limit,
+            include_invalid: query.include_invalid,
+            bucket_seconds,
+        }
+    }
+}
+
+/// 缓存条目
+struct CachedQuery {
+    /// 最近访问序号
+    last_used: u64,
+    /// 查询结果
+    series: BucketedSeries,
+}
+
+/// 分桶查询的LRU缓存
+///
+/// 看板会反复请求同一组序列，缓存按（指标、查询条件、时间桶）保存结果；
+/// 指标有新数据写入时由指标存储调用 [`QueryCache::invalidate`] 清除该指标的全部条目
+pub struct QueryCache {
+    /// 最多缓存的条目数
+    capacity: usize,
+    /// 缓存条目和访问序号
+    entries: Mutex<(HashMap<QueryKey, CachedQuery>, u64)>,
+    /// 失效次数，查询期间有写入时不保存结果
+    generation: AtomicU64,
+    /// 命中次数
+    hits: AtomicU64,
+    /// 未命中次数
+    misses: AtomicU64,
+}
+
+impl QueryCache {
+    /// 创建缓存
+    ///
+    /// # 参数
+    /// * `capacity` - 最多缓存的条目数
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new((HashMap::new(), 0)),
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// 读取缓存的查询结果
+    pub fn get(&self, metric_name: &str, query: &MetricQuery, bucket_seconds: i64) -> Option<BucketedSeries> {
+        let key = QueryKey::new(metric_name, query, bucket_seconds);
+        let mut guard = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let (entries, clock) = &mut *guard;
+        *clock += 1;
+        match entries.get_mut(&key) {
+            Some(entry) => {
+                entry.last_used = *clock;
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(entry.series.clone())
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// 当前失效序号，查询前读取并传给 [`QueryCache::insert`]
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// 保存查询结果，超出容量时淘汰最久未使用的条目
+    ///
+    /// `generation` 与当前失效序号不同（查询期间有写入）时不保存
+    pub fn insert(&self, metric_name: &str, query: &MetricQuery, bucket_seconds: i64, series: BucketedSeries, generation: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+        let key = QueryKey::new(metric_name, query, bucket_seconds);
+        let mut guard = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if self.generation() != generation {
+            return;
+        }
+        let (entries, clock) = &mut *guard;
+        *clock += 1;
+        if !entries.contains_key(&key) && entries.len() >= self.capacity {
+            if let Some(oldest) = entries.iter().min_by_key(|(_, e)| e.last_used).map(|(k, _)| k.clone()) {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, CachedQuery { last_used: *clock, series });
+    }
+
+    /// 清除指定指标的全部条目
+    pub fn invalidate<'a>(&self, metric_names: impl IntoIterator<Item = &'a str>) {
+        let mut guard = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        self.generation.fetch_add(1, Ordering::Release);
+        let (entries, _) = &mut *guard;
+        if entries.is_empty() {
+            return;
+        }
+        let names: HashSet<&str> = metric_names.into_iter().collect();
+        entries.retain(|key, _| !names.contains(key.metric_name.as_str()));
+    }
+
+    /// 清除全部条目
+    pub fn clear(&self) {
+        let mut guard = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        self.generation.fetch_add(1, Ordering::Release);
+        guard.0.clear();
+    }
+
+    /// （当前条目数，命中次数，未命中次数）
+    pub fn stats(&self) -> (usize, u64, u64) {
+        let len = self.entries.lock().unwrap_or_else(|e| e.into_inner()).0.len();
+        (len, self.hits.load(Ordering::Relaxed), self.misses.load(Ordering::Relaxed))
+    }
+}
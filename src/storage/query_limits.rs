@@ -0,0 +1,93 @@
+use chrono::{Duration, Utc};
+
+use super::MetricQuery;
+
+/// API查询超出限制
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum QueryLimitError {
+    /// 时间范围过大
+    #[error("查询时间范围超过 {max_days} 天，请缩小时间范围分段查询，或指定时间桶（bucket）")]
+    RangeTooLarge {
+        /// 允许的最大天数
+        max_days: i64,
+    },
+    /// 返回的数据点过多
+    #[error("查询结果有 {count} 个数据点，超过上限 {max}，请缩小时间范围、用 limit 分页，或指定时间桶（bucket）")]
+    TooManyPoints {
+        /// 实际数据点数
+        count: usize,
+        /// 上限
+        max: usize,
+    },
+    /// 查询超时
+    #[error("查询超过 {timeout_ms} ms 未完成，请缩小时间范围或指定时间桶（bucket）")]
+    Timeout {
+        /// 超时时间（毫秒）
+        timeout_ms: u64,
+    },
+}
+
+/// API查询限制
+///
+/// 只约束API发起的查询，告警评估、归档等内部查询不受影响
+#[derive(Debug, Clone, Copy)]
+pub struct QueryLimits {
+    /// 单次查询最多返回的数据点数（分桶查询按时间桶数计）
+    pub max_points: usize,
+    /// 单次查询的最大时间范围（None表示不限制）
+    pub max_range: Option<Duration>,
+    /// 查询超时（None表示不限制）
+    pub timeout: Option<std::time::Duration>,
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self {
+            max_points: usize::MAX,
+            max_range: None,
+            timeout: None,
+        }
+    }
+}
+
+impl QueryLimits {
+    /// 按配置创建限制（各项为0表示不限制）
+    ///
+    /// # 参数
+    /// * `max_points` - 单次查询最多返回的数据点数
+    /// * `max_range_days` - 最大时间范围（天）
+    /// * `timeout_ms` - 查询超时（毫秒）
+    pub fn new(max_points: usize, max_range_days: u32, timeout_ms: u64) -> Self {
+        Self {
+            max_points: if max_points == 0 { usize::MAX } else { max_points },
+            max_range: (max_range_days > 0).then(|| Duration::days(max_range_days as i64)),
+            timeout: (timeout_ms > 0).then(|| std::time::Duration::from_millis(timeout_ms)),
+        }
+    }
+
+    /// 检查时间范围（只有起始时间时按到当前为止计算，无起始时间时由数据点数限制）
+    pub fn check_range(&self, query: &MetricQuery) -> Result<(), QueryLimitError> {
+        let (Some(max_range), Some(since)) = (self.max_range, query.since) else {
+            return Ok(());
+        };
+        if query.until.unwrap_or_else(Utc::now) - since > max_range {
+            return Err(QueryLimitError::RangeTooLarge { max_days: max_range.num_days() });
+        }
+        Ok(())
+    }
+
+    /// 检查返回的数据点数
+    pub fn check_points(&self, count: usize) -> Result<(), QueryLimitError> {
+        if count > self.max_points {
+            return Err(QueryLimitError::TooManyPoints { count, max: self.max_points });
+        }
+        Ok(())
+    }
+
+    /// 超时错误
+    pub fn timeout_error(&self) -> QueryLimitError {
+        QueryLimitError::Timeout {
+            timeout_ms: self.timeout.map_or(0, |t| t.as_millis() as u64),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn, error, debug};
+
+use crate::storage::{JsonFileStore, MetricStore};
+use crate::web::cache::{CacheSnapshot, DataCache};
+
+/// 可从共享数据目录重新加载的存储
+///
+/// 只读API实例定期调用，读取采集实例写入的最新状态
+#[async_trait]
+pub trait Reloadable: Send + Sync {
+    /// 存储名称（用于日志）
+    fn name(&self) -> &str;
+
+    /// 重新加载状态文件
+    async fn reload(&self) -> Result<()>;
+}
+
+#[async_trait]
+impl Reloadable for MetricStore {
+    fn name(&self) -> &str {
+        "metrics"
+    }
+
+    async fn reload(&self) -> Result<()> {
+        MetricStore::reload(self).await
+    }
+}
+
+/// 缓存快照写入器
+///
+/// 运行在采集实例上：缓存有更新时，按最小间隔把缓存快照写入共享数据目录
+pub struct SnapshotWriter {
+    /// 数据缓存
+    cache: Arc<DataCache>,
+    /// 快照文件
+    store: JsonFileStore<CacheSnapshot>,
+    /// 两次写入的最小间隔
+    min_interval: Duration,
+}
+
+impl SnapshotWriter {
+    /// 创建快照写入器
+    ///
+    /// # 参数
+    /// * `cache` - 数据缓存
+    /// * `path` - 快照文件路径
+    /// * `min_interval` - 两次写入的最小间隔
+    pub fn new(cache: Arc<DataCache>, path: impl Into<PathBuf>, min_interval: Duration) -> Self {
+        Self {
+            cache,
+            store: JsonFileStore::new(path),
+            min_interval,
+        }
+    }
+
+    /// 订阅缓存更新并持续写入快照
+    pub async fn run(self) {
+        info!("📸 启动缓存快照写入: {}", self.store.path().display());
+        let mut updates = self.cache.subscribe();
+        loop {
+            match updates.recv().await {
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return,
+            }
+            // 合并最小间隔内的多次更新，只写一次
+            tokio::time::sleep(self.min_interval).await;
+            while updates.try_recv().is_ok() {}
+
+            if let Err(e) = self.store.save(&self.cache.snapshot().await).await {
+                error!("❌ 写入缓存快照失败: {}", e);
+            } else {
+                debug!("📸 缓存快照已更新");
+            }
+        }
+    }
+}
+
+/// 只读实例的数据同步
+///
+/// 运行在只读API实例上：定期加载缓存快照并重新加载各存储，
+/// 使多个无状态API副本与单个采集实例共享同一数据目录
+pub struct ReplicaSync {
+    /// 数据缓存
+    cache: Arc<DataCache>,
+    /// 快照文件
+    snapshot: JsonFileStore<CacheSnapshot>,
+    /// 需要重新加载的存储
+    stores: Vec<Arc<dyn Reloadable>>,
+    /// 同步间隔
+    interval: Duration,
+}
+
+impl ReplicaSync {
+    /// 创建数据同步
+    ///
+    /// # 参数
+    /// * `cache` - 数据缓存
+    /// * `snapshot_path` - 快照文件路径
+    /// * `stores` - 需要重新加载的存储
+    /// * `interval` - 同步间隔
+    pub fn new(
+        cache: Arc<DataCache>,
+        snapshot_path: impl Into<PathBuf>,
+        stores: Vec<Arc<dyn Reloadable>>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            cache,
+            snapshot: JsonFileStore::new(snapshot_path),
+            stores,
+            interval,
+        }
+    }
+
+    /// 执行一次同步
+    pub async fn sync_once(&self) {
+        match self.snapshot.load().await {
+            Ok(snapshot) if snapshot.saved_at.is_some() => self.cache.restore(snapshot).await,
+            Ok(_) => debug!("📸 缓存快照尚未生成"),
+            Err(e) => warn!("⚠️ 加载缓存快照失败: {}", e),
+        }
+        for store in &self.stores {
+            if let Err(e) = store.reload().await {
+                warn!("⚠️ 重新加载 {} 失败: {}", store.name(), e);
+            }
+        }
+    }
+
+    /// 按间隔持续同步
+    pub async fn run(self) {
+        info!("🔁 只读模式：每 {} 秒从 {} 同步数据", self.interval.as_secs(), self.snapshot.path().display());
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            self.sync_once().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web::cache::MARKET_BREADTH_KEY;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let path = std::env::temp_dir().join(format!("everscan-snapshot-{}.json", Uuid::new_v4()));
+        let collector = DataCache::new();
+        collector.set_dataset(MARKET_BREADTH_KEY, &serde_json::json!({ "advancers": 12 })).await.unwrap();
+        JsonFileStore::new(&path).save(&collector.snapshot().await).await.unwrap();
+
+        let replica = Arc::new(DataCache::new());
+        ReplicaSync::new(replica.clone(), &path, Vec::new(), Duration::from_secs(1)).sync_once().await;
+        let breadth: serde_json::Value = replica.get_dataset(MARKET_BREADTH_KEY).await.unwrap();
+        assert_eq!(breadth["advancers"], 12);
+        assert!(replica.get_entry_status(MARKET_BREADTH_KEY).await.unwrap().last_success.is_some());
+
+        tokio::fs::remove_file(&path).await.ok();
+    }
+}
@@ -1,14 +1,14 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use std::sync::Arc;
-use std::time::Duration;
-use tracing::{info, error, warn};
+use tracing::{info, error};
 use chrono::Utc;
 
-use crate::clients::CoinMarketCapClient;
-use crate::models::{AggregatedMetric, MetricBuilder, DataSource};
+use crate::analytics::{compute_aggregates, MarketAggregates};
+use crate::clients::{AltcoinSeasonFormula, CoinMarketCapClient};
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue};
 use crate::tasks::Task;
-use crate::web::cache::DataCache;
+use crate::web::cache::{DataCache, ALTCOIN_SEASON_CONSTITUENTS_KEY, ALTCOIN_SEASON_KEY, CMC_LISTINGS_KEY, MARKET_AGGREGATES_KEY};
 
 /// 山寨币季节指数任务
 pub struct AltcoinSeasonTask {
@@ -18,6 +18,8 @@ pub struct AltcoinSeasonTask {
     client: Arc<CoinMarketCapClient>,
     /// 任务执行间隔（秒）
     interval_seconds: u64,
+    /// 指数计算公式
+    formula: AltcoinSeasonFormula,
 }
 
 impl AltcoinSeasonTask {
@@ -34,18 +36,59 @@ impl AltcoinSeasonTask {
             name,
             client,
             interval_seconds,
+            formula: AltcoinSeasonFormula::default(),
         }
     }
+
+    /// 设置指数计算公式
+    pub fn with_formula(mut self, formula: AltcoinSeasonFormula) -> Self {
+        self.formula = formula;
+        self
+    }
+    
+    /// 将汇总市值转换为指标（`market_cap_total`、`market_cap_total2`、`market_cap_total3`）
+    fn aggregate_metrics(aggregates: &MarketAggregates) -> Result<Vec<AggregatedMetric>> {
+        [
+            ("market_cap_total", &aggregates.total),
+            ("market_cap_total2", &aggregates.total2),
+            ("market_cap_total3", &aggregates.total3),
+        ]
+        .into_iter()
+        .map(|(name, value)| {
+            MetricBuilder::new(CoinMarketCapClient::SOURCE, name)
+                .value(MetricValue::scalar(value.market_cap, "usd"))
+                .timestamp(aggregates.timestamp)
+                .metadata(serde_json::json!({
+                    "coins": aggregates.coins,
+                    "change_24h_pct": value.change_24h_pct,
+                }))
+                .build()
+        })
+        .collect()
+    }
     
     /// 收集山寨币季节指数数据
     async fn collect_altcoin_season_data(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
         info!("📊 开始收集山寨币季节指数数据");
         
         // 获取真实的山寨币季节指数数据
-        match self.client.get_altcoin_season_index().await {
-            Ok(altcoin_data) => {
+        match self.client.get_altcoin_season_with_listings().await {
+            Ok((altcoin_data, constituents, listings)) => {
+                let altcoin_data = match self.formula {
+                    AltcoinSeasonFormula::Change24h => altcoin_data,
+                    formula => CoinMarketCapClient::altcoin_season_index(&constituents, formula),
+                };
                 info!("✅ 山寨币季节指数获取成功: {} - {}", altcoin_data.value, altcoin_data.classification_zh);
                 
+                // 先缓存成分币种，历史记录在指数更新时一并保存
+                cache.set_dataset(ALTCOIN_SEASON_CONSTITUENTS_KEY, &constituents).await?;
+                // 上市信息用于检测排名、流通量和标签变化
+                cache.set_dataset(CMC_LISTINGS_KEY, &listings).await?;
+                let aggregates = compute_aggregates(&listings, Utc::now());
+                if let Some(aggregates) = &aggregates {
+                    cache.set_dataset(MARKET_AGGREGATES_KEY, aggregates).await?;
+                }
+
                 // 缓存数据
                 let json_data = serde_json::json!({
                     "value": altcoin_data.value,
@@ -65,12 +108,13 @@ impl AltcoinSeasonTask {
                 
                 // 山寨币季节指数值
                 metrics.push(MetricBuilder::new(
-                    DataSource::CoinMarketCap,
+                    CoinMarketCapClient::SOURCE,
                     "altcoin_season_index".to_string()
                 )
-                .value(serde_json::json!(altcoin_data.value))
+                .value(MetricValue::scalar(altcoin_data.value as f64, "index"))
                 .timestamp(timestamp)
                 .metadata(serde_json::json!({
+                    "formula": self.formula.as_str(),
                     "classification": altcoin_data.classification,
                     "classification_zh": altcoin_data.classification_zh,
                     "outperforming_count": altcoin_data.outperforming_count,
@@ -78,7 +122,10 @@ impl AltcoinSeasonTask {
                     "outperforming_percentage": altcoin_data.outperforming_percentage,
                     "market_advice": altcoin_data.market_advice
                 }))
-                .build());
+                .build()?);
+                if let Some(aggregates) = &aggregates {
+                    metrics.extend(Self::aggregate_metrics(aggregates)?);
+                }
                 
                 info!("📦 山寨币季节指数数据已缓存");
                 info!("🎯 山寨币季节指数: {} - {} ({})", altcoin_data.value, altcoin_data.classification_zh, altcoin_data.market_advice);
@@ -87,6 +134,7 @@ impl AltcoinSeasonTask {
             }
             Err(e) => {
                 error!("❌ 获取山寨币季节指数失败: {}", e);
+                cache.record_failure(ALTCOIN_SEASON_KEY, &e).await;
                 Err(e)
             }
         }
@@ -132,6 +180,7 @@ pub struct AltcoinSeasonTaskBuilder {
     client: Option<Arc<CoinMarketCapClient>>,
     interval_seconds: Option<u64>,
     name: Option<String>,
+    formula: AltcoinSeasonFormula,
 }
 
 impl AltcoinSeasonTaskBuilder {
@@ -141,6 +190,7 @@ impl AltcoinSeasonTaskBuilder {
             client: None,
             interval_seconds: None,
             name: None,
+            formula: AltcoinSeasonFormula::default(),
         }
     }
     
@@ -162,13 +212,19 @@ impl AltcoinSeasonTaskBuilder {
         self
     }
     
+    /// 设置指数计算公式
+    pub fn formula(mut self, formula: AltcoinSeasonFormula) -> Self {
+        self.formula = formula;
+        self
+    }
+    
     /// 构建任务
     pub fn build(self) -> Result<AltcoinSeasonTask> {
         let client = self.client.ok_or_else(|| anyhow::anyhow!("缺少CoinMarketCap客户端"))?;
         let interval_seconds = self.interval_seconds.unwrap_or(3600); // 默认1小时
         let name = self.name.unwrap_or_else(|| "山寨币季节指数采集".to_string());
         
-        Ok(AltcoinSeasonTask::new(name, client, interval_seconds))
+        Ok(AltcoinSeasonTask::new(name, client, interval_seconds).with_formula(self.formula))
     }
 }
 
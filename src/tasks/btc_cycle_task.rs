@@ -0,0 +1,130 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::analytics::{compute_cycle, CyclePosition, TARGET_BLOCK_SECONDS};
+use crate::clients::{ChainClient, CoinMarketCapClient};
+use crate::models::{AggregatedMetric, DataSource, MetricBuilder, MetricValue};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, BTC_CYCLE_KEY};
+
+/// 比特币减半周期任务
+/// 
+/// 从链上API获取区块高度和平均出块时间，计算距上次/下次减半的时间与周期内涨幅
+pub struct BtcCycleTask {
+    /// 任务名称
+    name: String,
+    /// 链上数据客户端
+    chain: Arc<ChainClient>,
+    /// CoinMarketCap客户端（缓存中没有BTC价格时使用）
+    coinmarketcap: Arc<CoinMarketCapClient>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl BtcCycleTask {
+    /// 创建新的减半周期任务
+    pub fn new(
+        name: String,
+        chain: Arc<ChainClient>,
+        coinmarketcap: Arc<CoinMarketCapClient>,
+        interval_seconds: u64,
+    ) -> Self {
+        info!("🚀 创建比特币减半周期任务: {}", name);
+        Self {
+            name,
+            chain,
+            coinmarketcap,
+            interval_seconds,
+        }
+    }
+
+    /// 获取当前BTC价格
+    /// 
+    /// 优先使用缓存中的行情，其次请求CoinMarketCap
+    async fn btc_price(&self, cache: &DataCache) -> Option<f64> {
+        for coin_id in ["bitcoin", "btc"] {
+            if let Some(price) = cache.metric_value(&format!("price:{}", coin_id)).await {
+                return Some(price);
+            }
+        }
+        match self.coinmarketcap.get_cryptocurrency_data("BTC").await {
+            Ok(data) => Some(data.price),
+            Err(e) => {
+                warn!("⚠️ 获取BTC价格失败，跳过周期涨幅计算: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 将周期位置转换为指标
+    fn to_metrics(cycle: &CyclePosition) -> Result<Vec<AggregatedMetric>> {
+        let metric = |name: &str, value: MetricValue| {
+            MetricBuilder::new(DataSource::EVERSCAN, name)
+                .value(value)
+                .timestamp(cycle.timestamp)
+                .metadata(serde_json::json!({
+                    "block_height": cycle.block_height,
+                    "height_estimated": cycle.height_estimated,
+                }))
+                .build()
+        };
+        
+        let mut metrics = vec![
+            metric("btc_days_since_halving", MetricValue::scalar(cycle.days_since_halving as f64, "days"))?,
+            metric("btc_blocks_until_halving", MetricValue::scalar(cycle.blocks_until_halving as f64, "blocks"))?,
+            metric("btc_cycle_progress_pct", MetricValue::scalar(cycle.cycle_progress_pct, "pct"))?,
+        ];
+        if let Some(return_pct) = cycle.return_since_halving_pct {
+            metrics.push(metric("btc_return_since_halving_pct", MetricValue::scalar(return_pct, "pct"))?);
+        }
+        Ok(metrics)
+    }
+}
+
+#[async_trait]
+impl Task for BtcCycleTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn description(&self) -> &str {
+        "根据区块高度计算比特币减半周期位置，以及本周期与历次周期的涨幅对比"
+    }
+    
+    fn id(&self) -> &str {
+        "btc_cycle"
+    }
+    
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+    
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        // 链上API不可用时退回按时间推算，避免周期数据整体缺失
+        let height = match self.chain.tip_height().await {
+            Ok(height) => Some(height),
+            Err(e) => {
+                warn!("⚠️ 获取区块高度失败，按时间估算: {}", e);
+                None
+            }
+        };
+        let block_seconds = match height {
+            Some(_) => self.chain.average_block_time().await
+                .map(|d| d.as_secs_f64())
+                .unwrap_or(TARGET_BLOCK_SECONDS),
+            None => TARGET_BLOCK_SECONDS,
+        };
+        
+        let cycle = compute_cycle(Utc::now(), height, block_seconds, self.btc_price(cache).await);
+        info!(
+            "⛏️ 比特币周期: 区块 {}，距上次减半 {} 天，距下次减半约 {} 天",
+            cycle.block_height, cycle.days_since_halving, cycle.days_until_halving
+        );
+        cache.set_dataset(BTC_CYCLE_KEY, &cycle).await?;
+        
+        Self::to_metrics(&cycle)
+    }
+}
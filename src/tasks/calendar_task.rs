@@ -0,0 +1,96 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::calendar::{EconomicCalendar, EventImportance};
+use crate::clients::CalendarClient;
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, CALENDAR_KEY};
+
+/// 经济日历任务
+/// 
+/// 定期获取宏观事件并合并到经济日历，按国家和重要性过滤
+pub struct EconomicCalendarTask {
+    /// 任务名称
+    name: String,
+    /// 日历数据源客户端
+    client: Arc<CalendarClient>,
+    /// 经济日历
+    calendar: Arc<EconomicCalendar>,
+    /// 保留的国家/货币代码（为空表示全部）
+    countries: Vec<String>,
+    /// 最低重要性
+    min_importance: EventImportance,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl EconomicCalendarTask {
+    /// 创建新的经济日历任务
+    pub fn new(
+        name: String,
+        client: Arc<CalendarClient>,
+        calendar: Arc<EconomicCalendar>,
+        countries: Vec<String>,
+        min_importance: EventImportance,
+        interval_seconds: u64,
+    ) -> Self {
+        info!("🚀 创建经济日历任务: {}", name);
+        Self {
+            name,
+            client,
+            calendar,
+            countries,
+            min_importance,
+            interval_seconds,
+        }
+    }
+}
+
+#[async_trait]
+impl Task for EconomicCalendarTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn description(&self) -> &str {
+        "获取FOMC、CPI等宏观经济事件的发布时间和重要性"
+    }
+    
+    fn id(&self) -> &str {
+        "economic_calendar"
+    }
+    
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+    
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let now = Utc::now();
+        let events: Vec<_> = self.client.fetch().await?
+            .into_iter()
+            .filter(|e| e.importance >= self.min_importance)
+            .filter(|e| self.countries.is_empty() || self.countries.iter().any(|c| c.eq_ignore_ascii_case(&e.country)))
+            .collect();
+        
+        let fetched = events.len();
+        let added = self.calendar.merge(events, now).await?;
+        cache.record_success(CALENDAR_KEY).await;
+        
+        let high_7d = self.calendar
+            .events_between(now, now + Duration::days(7), EventImportance::High)
+            .await
+            .len();
+        info!("🗓️ 获取 {} 个宏观事件（新增 {}），未来7天高重要性事件 {} 个", fetched, added, high_7d);
+        
+        Ok(vec![
+            MetricBuilder::new(CalendarClient::SOURCE, "calendar_high_importance_7d")
+                .value(MetricValue::scalar(high_7d as f64, "count"))
+                .timestamp(now)
+                .build()?,
+        ])
+    }
+}
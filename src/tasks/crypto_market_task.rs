@@ -1,14 +1,22 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
-use std::time::Duration;
-use tracing::{info, error, warn};
-use chrono::Utc;
+use tracing::{info, error};
 
-use crate::clients::CoinMarketCapClient;
-use crate::models::{AggregatedMetric, MetricBuilder, DataSource};
+use crate::clients::{CoinMarketCapClient, CoinNotListed, CryptocurrencyData};
+use crate::history::PriceHistory;
+use crate::indicators::{bollinger_bands, indicator_metric_name, rsi};
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue};
 use crate::tasks::Task;
-use crate::web::cache::DataCache;
+use crate::tenants::CoinUniverse;
+use crate::web::cache::{market_data_key, DataCache};
+
+/// 未设置采集币种范围时采集的币种
+const DEFAULT_COIN_ID: &str = "hype";
+
+/// 默认报价最长未更新时间（小时），超过后视为停止更新
+const DEFAULT_MAX_QUOTE_AGE_HOURS: u64 = 48;
 
 /// 加密货币市场数据任务
 pub struct CryptoMarketTask {
@@ -18,6 +26,12 @@ pub struct CryptoMarketTask {
     coinmarketcap_client: Arc<CoinMarketCapClient>,
     /// 任务执行间隔（秒）
     interval_seconds: u64,
+    /// 日线价格历史（可选，用于计算RSI和布林带）
+    prices: Option<Arc<PriceHistory>>,
+    /// 采集币种范围（可选，未设置时只采集HYPE）
+    coins: Option<CoinUniverse>,
+    /// 报价最长未更新时间（小时），超过后标记为停止更新
+    max_quote_age_hours: u64,
 }
 
 impl CryptoMarketTask {
@@ -31,69 +45,169 @@ impl CryptoMarketTask {
             name,
             coinmarketcap_client,
             interval_seconds,
+            prices: None,
+            coins: None,
+            max_quote_age_hours: DEFAULT_MAX_QUOTE_AGE_HOURS,
         }
     }
 
+    /// 设置报价最长未更新时间（小时），超过后标记为停止更新
+    pub fn with_max_quote_age_hours(mut self, hours: u64) -> Self {
+        self.max_quote_age_hours = hours;
+        self
+    }
+
+    /// 设置日线价格历史，RSI和布林带按日线收盘价计算
+    pub fn with_price_history(mut self, prices: Arc<PriceHistory>) -> Self {
+        self.prices = Some(prices);
+        self
+    }
+
+    /// 设置采集币种范围
+    ///
+    /// 每个币种按缓存币种ID的大写形式（如 `hype` -> `HYPE`）向CoinMarketCap查询
+    pub fn with_coins(mut self, coins: CoinUniverse) -> Self {
+        self.coins = Some(coins);
+        self
+    }
+
     /// 收集市场数据
+    ///
+    /// 单个币种失败时记录失败并继续，全部失败时返回错误；
+    /// 数据源标记为不活跃、不再收录或报价过期的币种在缓存中标记为停止更新
     async fn collect_market_data(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
         info!("📊 开始收集加密货币市场数据");
 
+        let coin_ids = match &self.coins {
+            Some(coins) => coins.coin_ids().await,
+            None => vec![DEFAULT_COIN_ID.to_string()],
+        };
         let mut metrics = Vec::new();
-
-        // 收集HYPE代币数据
-        match self.collect_hype_data().await {
-            Ok(coin_data) => {
-                info!("✅ 成功获取HYPE代币数据");
-                
-                // 存储到缓存
-                cache.set_coin_data("hype", serde_json::to_value(&coin_data)?).await;
-
-                // 创建指标
-                let metric = MetricBuilder::new(
-                    DataSource::from_str(&coin_data.data_source),
-                    "hype_market_data".to_string()
-                )
-                .value(serde_json::json!(coin_data.current_price))
-                .metadata(serde_json::json!({
-                    "coin_id": "hype",
-                    "market_cap": coin_data.market_cap,
-                    "volume_24h": coin_data.total_volume,
-                    "price_change_24h": coin_data.price_change_percentage_24h,
-                    "price_change_7d": coin_data.price_change_percentage_7d,
-                    "market_cap_rank": coin_data.market_cap_rank,
-                    "rsi": coin_data.rsi,
-                    "bollinger_bands": coin_data.bollinger_bands,
-                    "technical_analysis": coin_data.technical_analysis,
-                    "investment_advice": coin_data.investment_advice,
-                    "data_source": coin_data.data_source
-                }))
-                .build();
-
-                metrics.push(metric);
+        let mut last_error = None;
+
+        for coin_id in &coin_ids {
+            match self.collect_coin_data(coin_id).await {
+                Ok((coin_data, inactive)) => {
+                    info!("✅ 成功获取 {} 数据", coin_data.symbol);
+
+                    // 存储到缓存
+                    cache.set_coin_data(coin_id, serde_json::to_value(&coin_data)?).await;
+                    cache.set_coin_inactive(coin_id, inactive).await;
+                    metrics.extend(Self::coin_metrics(coin_id, &coin_data)?);
+                }
+                Err(e) => {
+                    error!("❌ 获取 {} 数据失败: {}", coin_id, e);
+                    if e.is::<CoinNotListed>() {
+                        cache.set_coin_inactive(coin_id, Some(e.to_string())).await;
+                    }
+                    cache.record_failure(&market_data_key(coin_id), &e).await;
+                    last_error = Some(e);
+                }
             }
-            Err(e) => {
-                error!("❌ 获取HYPE代币数据失败: {}", e);
+        }
+
+        if metrics.is_empty() {
+            if let Some(e) = last_error {
                 return Err(e);
             }
         }
+        info!("✅ 市场数据收集完成，{} 个币种共收集到 {} 个指标", coin_ids.len(), metrics.len());
+        Ok(metrics)
+    }
 
-        info!("✅ 市场数据收集完成，共收集到 {} 个指标", metrics.len());
+    /// 由币种数据创建指标
+    fn coin_metrics(coin_id: &str, coin_data: &CoinData) -> Result<Vec<AggregatedMetric>> {
+        let mut metrics = vec![
+            MetricBuilder::new(
+                CoinMarketCapClient::SOURCE,
+                format!("{}_market_data", coin_id)
+            )
+            .value(MetricValue::scalar(coin_data.current_price, "usd"))
+            .metadata(serde_json::json!({
+                "coin_id": coin_id,
+                "market_cap": coin_data.market_cap,
+                "volume_24h": coin_data.total_volume,
+                "price_change_24h": coin_data.price_change_percentage_24h,
+                "price_change_7d": coin_data.price_change_percentage_7d,
+                "market_cap_rank": coin_data.market_cap_rank,
+                "circulating_supply": coin_data.circulating_supply,
+                "max_supply": coin_data.max_supply,
+                "fully_diluted_market_cap": coin_data.fully_diluted_market_cap,
+                "rsi": coin_data.rsi,
+                "bollinger_bands": coin_data.bollinger_bands,
+                "technical_analysis": coin_data.technical_analysis,
+                "investment_advice": coin_data.investment_advice,
+                "data_source": coin_data.data_source
+            }))
+            .build()?,
+        ];
+
+        // 单独保存各指标的时间序列，供图表叠加到价格历史上
+        let bands = ["upper", "middle", "lower"]
+            .into_iter()
+            .filter_map(|band| {
+                let value = coin_data.bollinger_bands.get(band)?.as_f64()?;
+                Some((band.to_string(), MetricValue::scalar(value, "usd")))
+            })
+            .collect();
+        for (indicator, value) in [
+            ("price", MetricValue::scalar(coin_data.current_price, "usd")),
+            ("rsi", MetricValue::scalar(coin_data.rsi, "index")),
+            ("bollinger", MetricValue::Composite(bands)),
+        ] {
+            metrics.push(
+                MetricBuilder::new(CoinMarketCapClient::SOURCE, indicator_metric_name(coin_id, indicator))
+                    .value(value)
+                    .metadata(serde_json::json!({ "coin_id": coin_id, "indicator": indicator }))
+                    .build()?
+            );
+        }
         Ok(metrics)
     }
 
-    /// 收集HYPE代币数据
-    async fn collect_hype_data(&self) -> Result<CoinData> {
-        info!("💰 开始收集HYPE代币数据");
+    /// 判断币种是否已停止更新
+    ///
+    /// # 参数
+    /// * `data` - CoinMarketCap币种数据
+    /// * `max_quote_age_hours` - 报价最长未更新时间（小时）
+    /// * `now` - 当前时间
+    ///
+    /// # 返回
+    /// * `Option<String>` - 停止更新的原因，正常更新时为None
+    fn inactive_reason(data: &CryptocurrencyData, max_quote_age_hours: u64, now: DateTime<Utc>) -> Option<String> {
+        if !data.is_active {
+            return Some("CoinMarketCap已将该币种标记为不活跃".to_string());
+        }
+        let age_hours = (now - data.last_updated).num_hours();
+        (age_hours >= max_quote_age_hours as i64)
+            .then(|| format!("报价已 {} 小时未更新（最后更新 {}）", age_hours, data.last_updated.format("%Y-%m-%d %H:%M UTC")))
+    }
+
+    /// 收集单个币种数据
+    ///
+    /// # 返回
+    /// * `Result<(CoinData, Option<String>)>` - 币种数据及停止更新的原因（正常时为None）
+    async fn collect_coin_data(&self, coin_id: &str) -> Result<(CoinData, Option<String>)> {
+        let symbol = coin_id.to_uppercase();
+        info!("💰 开始收集 {} 数据", symbol);
 
-        // 直接使用CoinMarketCap API获取HYPE数据
-        match self.coinmarketcap_client.get_cryptocurrency_data("HYPE").await {
+        match self.coinmarketcap_client.get_cryptocurrency_data(&symbol).await {
             Ok(cmc_data) => {
-                info!("✅ 从CoinMarketCap获取HYPE数据成功");
-                Ok(CoinData::from_coinmarketcap(cmc_data))
+                info!("✅ 从CoinMarketCap获取 {} 数据成功", symbol);
+                let closes = match &self.prices {
+                    Some(prices) => prices.closes_before(coin_id, Utc::now().date_naive()).await,
+                    None => Vec::new(),
+                };
+                let inactive = Self::inactive_reason(&cmc_data, self.max_quote_age_hours, Utc::now());
+                Ok((CoinData::from_coinmarketcap(cmc_data, closes), inactive))
+            }
+            Err(e) if e.is::<CoinNotListed>() => {
+                error!("❌ {}", e);
+                Err(e)
             }
             Err(e) => {
-                error!("❌ CoinMarketCap HYPE数据获取失败: {}", e);
-                Err(anyhow::anyhow!("无法从CoinMarketCap获取HYPE数据: {}", e))
+                error!("❌ CoinMarketCap {} 数据获取失败: {}", symbol, e);
+                Err(anyhow::anyhow!("无法从CoinMarketCap获取{}数据: {}", symbol, e))
             }
         }
     }
@@ -111,6 +225,11 @@ struct CoinData {
     price_change_24h: f64,
     price_change_percentage_24h: f64,
     price_change_percentage_7d: Option<f64>,
+    circulating_supply: Option<f64>,
+    total_supply: Option<f64>,
+    max_supply: Option<f64>,
+    fully_diluted_market_cap: Option<f64>,
+    tags: Vec<String>,
     data_source: String,
     bollinger_bands: serde_json::Value,
     rsi: f64,
@@ -120,9 +239,12 @@ struct CoinData {
 
 impl CoinData {
     /// 从CoinMarketCap数据创建CoinData
-    fn from_coinmarketcap(data: crate::clients::CryptocurrencyData) -> Self {
-        let rsi = Self::calculate_rsi(data.price);
-        let bollinger_bands = Self::calculate_bollinger_bands(data.price);
+    /// 
+    /// `closes` 为当天之前的日线收盘价，与当前价格一起计算RSI和布林带
+    fn from_coinmarketcap(data: CryptocurrencyData, mut closes: Vec<f64>) -> Self {
+        closes.push(data.price);
+        let rsi = Self::calculate_rsi(&closes);
+        let bollinger_bands = Self::calculate_bollinger_bands(&closes);
         let technical_analysis = Self::generate_technical_analysis_cmc(rsi, &data);
         let investment_advice = Self::generate_investment_advice_cmc(&data);
 
@@ -136,7 +258,12 @@ impl CoinData {
             price_change_24h: data.percent_change_24h,
             price_change_percentage_24h: data.percent_change_24h,
             price_change_percentage_7d: data.percent_change_7d,
-            data_source: "CoinMarketCap".to_string(),
+            circulating_supply: data.circulating_supply,
+            total_supply: data.total_supply,
+            max_supply: data.max_supply,
+            fully_diluted_market_cap: data.fully_diluted_market_cap,
+            tags: data.tags,
+            data_source: CoinMarketCapClient::SOURCE.display_name.to_string(),
             bollinger_bands,
             rsi,
             investment_advice,
@@ -144,14 +271,22 @@ impl CoinData {
         }
     }
 
-    /// 计算RSI指标（简化版）
-    fn calculate_rsi(price: f64) -> f64 {
-        // 简化的RSI计算，实际应用中需要历史价格数据
-        (price % 100.0).max(0.0).min(100.0)
+    /// 计算14日RSI（日线不足时退回简化估算）
+    fn calculate_rsi(closes: &[f64]) -> f64 {
+        let price = closes.last().copied().unwrap_or_default();
+        rsi(closes, 14).unwrap_or_else(|| (price % 100.0).clamp(0.0, 100.0))
     }
 
-    /// 计算布林带指标（简化版）
-    fn calculate_bollinger_bands(price: f64) -> serde_json::Value {
+    /// 计算20日布林带（日线不足时退回简化估算）
+    fn calculate_bollinger_bands(closes: &[f64]) -> serde_json::Value {
+        if let Some(bands) = bollinger_bands(closes, 20, 2.0) {
+            return serde_json::json!({
+                "upper": bands.upper,
+                "middle": bands.middle,
+                "lower": bands.lower
+            });
+        }
+        let price = closes.last().copied().unwrap_or_default();
         let std_dev = price * 0.02; // 假设标准差为价格的2%
         serde_json::json!({
             "upper": price + (2.0 * std_dev),
@@ -161,7 +296,7 @@ impl CoinData {
     }
 
     /// 生成技术分析（CoinMarketCap版本）
-    fn generate_technical_analysis_cmc(rsi: f64, data: &crate::clients::CryptocurrencyData) -> String {
+    fn generate_technical_analysis_cmc(rsi: f64, data: &CryptocurrencyData) -> String {
         let mut analysis = Vec::new();
         
         // RSI分析
@@ -184,7 +319,7 @@ impl CoinData {
     }
 
     /// 生成投资建议（CoinMarketCap版本）
-    fn generate_investment_advice_cmc(data: &crate::clients::CryptocurrencyData) -> String {
+    fn generate_investment_advice_cmc(data: &CryptocurrencyData) -> String {
         if data.percent_change_24h > 15.0 {
             "涨幅过大，建议观望或止盈".to_string()
         } else if data.percent_change_24h > 5.0 {
@@ -236,6 +371,9 @@ pub struct CryptoMarketTaskBuilder {
     coinmarketcap_client: Option<Arc<CoinMarketCapClient>>,
     interval_seconds: Option<u64>,
     name: Option<String>,
+    prices: Option<Arc<PriceHistory>>,
+    coins: Option<CoinUniverse>,
+    max_quote_age_hours: Option<u64>,
 }
 
 impl CryptoMarketTaskBuilder {
@@ -245,6 +383,9 @@ impl CryptoMarketTaskBuilder {
             coinmarketcap_client: None,
             interval_seconds: None,
             name: None,
+            prices: None,
+            coins: None,
+            max_quote_age_hours: None,
         }
     }
 
@@ -266,6 +407,24 @@ impl CryptoMarketTaskBuilder {
         self
     }
 
+    /// 设置日线价格历史
+    pub fn price_history(mut self, prices: Arc<PriceHistory>) -> Self {
+        self.prices = Some(prices);
+        self
+    }
+
+    /// 设置采集币种范围
+    pub fn coins(mut self, coins: CoinUniverse) -> Self {
+        self.coins = Some(coins);
+        self
+    }
+
+    /// 设置报价最长未更新时间（小时）
+    pub fn max_quote_age_hours(mut self, hours: u64) -> Self {
+        self.max_quote_age_hours = Some(hours);
+        self
+    }
+
     /// 构建任务
     pub fn build(self) -> Result<CryptoMarketTask> {
         let coinmarketcap_client = self.coinmarketcap_client
@@ -273,7 +432,17 @@ impl CryptoMarketTaskBuilder {
         let interval_seconds = self.interval_seconds.unwrap_or(14400); // 默认4小时
         let name = self.name.unwrap_or_else(|| "加密货币市场数据任务".to_string());
 
-        Ok(CryptoMarketTask::new(name, coinmarketcap_client, interval_seconds))
+        let mut task = CryptoMarketTask::new(name, coinmarketcap_client, interval_seconds);
+        if let Some(prices) = self.prices {
+            task = task.with_price_history(prices);
+        }
+        if let Some(coins) = self.coins {
+            task = task.with_coins(coins);
+        }
+        if let Some(hours) = self.max_quote_age_hours {
+            task = task.with_max_quote_age_hours(hours);
+        }
+        Ok(task)
     }
 }
 
@@ -282,13 +451,3 @@ impl Default for CryptoMarketTaskBuilder {
         Self::new()
     }
 }
-
-impl DataSource {
-    /// 从字符串创建数据源
-    fn from_str(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
-            "coinmarketcap" => DataSource::CoinMarketCap,
-            _ => DataSource::CoinMarketCap,
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,129 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Client;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info};
+
+use crate::clients::HttpClientBuilder;
+use crate::config::CustomSourceConfig;
+use crate::models::{register_source, AggregatedMetric, DataSource, MetricBuilder, MetricValue, SourceKind};
+use crate::tasks::Task;
+use crate::wasm::WasmTransform;
+use crate::web::cache::DataCache;
+
+/// 自定义HTTP数据源任务
+///
+/// 定时请求配置的URL，把响应原样交给WASM转换模块解析为指标
+pub struct CustomSourceTask {
+    /// 任务ID（`custom.<数据源标识>`）
+    id: String,
+    /// 任务名称
+    name: String,
+    /// 数据源
+    source: DataSource,
+    /// HTTP客户端
+    client: Client,
+    /// 请求URL
+    url: String,
+    /// 请求头
+    headers: HeaderMap,
+    /// 转换模块
+    transform: Arc<WasmTransform>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl CustomSourceTask {
+    /// 按配置创建自定义数据源任务（加载并编译转换模块）
+    ///
+    /// # 参数
+    /// * `config` - 自定义数据源配置
+    pub fn new(config: &CustomSourceConfig) -> Result<Self> {
+        let transform = WasmTransform::open(&config.transform)
+            .with_context(|| format!("自定义数据源 {} 加载转换模块失败", config.name))?;
+        let mut headers = HeaderMap::new();
+        for (key, value) in &config.headers {
+            headers.insert(
+                HeaderName::try_from(key.as_str()).with_context(|| format!("无效的请求头名称: {}", key))?,
+                HeaderValue::try_from(value.expose()).with_context(|| format!("请求头 {} 的值无效", key))?,
+            );
+        }
+        // 数据源登记表以 &'static str 为键，自定义数据源只在启动时按配置创建一次
+        let name: &'static str = Box::leak(config.name.to_lowercase().into_boxed_str());
+        let source = DataSource {
+            name,
+            display_name: name,
+            kind: SourceKind::Feed,
+            capabilities: &[],
+        };
+        register_source(source);
+        info!("🚀 创建自定义数据源任务: {}（转换模块 {}）", name, transform.name());
+        Ok(Self {
+            id: format!("custom.{}", name),
+            name: format!("自定义数据源 {}", name),
+            source,
+            client: HttpClientBuilder::new().timeout(Duration::from_secs(config.timeout_seconds)).build()?,
+            url: config.url.clone(),
+            headers,
+            transform: Arc::new(transform),
+            interval_seconds: config.interval_seconds,
+        })
+    }
+
+    /// 请求数据源，返回原始响应
+    async fn fetch(&self) -> Result<Vec<u8>> {
+        debug!("🌐 请求自定义数据源 {}: {}", self.source.name, self.url);
+        let response = self.client.get(&self.url).headers(self.headers.clone()).send().await
+            .with_context(|| format!("请求自定义数据源 {} 失败", self.source.name))?;
+        if !response.status().is_success() {
+            return Err(anyhow!("自定义数据源 {} 请求失败: HTTP {}", self.source.name, response.status()));
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+#[async_trait]
+impl Task for CustomSourceTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "请求自定义HTTP数据源，由WASM转换模块解析为指标"
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+
+    async fn execute(&self, _cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let payload = self.fetch().await?;
+        let transform = self.transform.clone();
+        let transformed = tokio::task::spawn_blocking(move || transform.transform(&payload)).await??;
+
+        let metrics = transformed.into_iter()
+            .map(|metric| {
+                let value = match metric.unit {
+                    Some(unit) => MetricValue::scalar(metric.value, unit),
+                    None => MetricValue::from(metric.value),
+                };
+                let mut builder = MetricBuilder::new(self.source, metric.name).value(value);
+                if let Some(timestamp) = metric.timestamp {
+                    builder = builder.timestamp(timestamp);
+                }
+                for (key, value) in metric.labels {
+                    builder = builder.label(key, value);
+                }
+                builder.build()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        info!("✅ 自定义数据源 {} 转换得到 {} 个指标", self.source.name, metrics.len());
+        Ok(metrics)
+    }
+}
@@ -0,0 +1,243 @@
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
+
+use crate::analytics::MetricExpression;
+use crate::config::DerivedMetricConfig;
+use crate::models::{AggregatedMetric, DataSource, MetricBuilder, MetricValue};
+use crate::storage::MetricStore;
+use crate::web::cache::DataCache;
+
+/// 模拟计算的派生指标值
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedMetric {
+    /// 指标名称
+    pub name: String,
+    /// 单位（可选）
+    pub unit: Option<String>,
+    /// 计算结果（输入不完整或计算失败时为None）
+    pub value: Option<f64>,
+}
+
+/// 已解析的派生指标
+struct DerivedMetric {
+    /// 配置
+    config: DerivedMetricConfig,
+    /// 计算表达式
+    expression: MetricExpression,
+}
+
+/// 派生指标引擎
+///
+/// 按配置的表达式由已有指标计算新指标。每次任务执行后检查输入值，
+/// 任一输入变化时重新计算并写入指标存储，与任务产出的指标一样可查询；
+/// 派生指标可以引用配置中排在它之前的派生指标
+pub struct DerivedMetricEngine {
+    /// 派生指标（按配置顺序计算）
+    metrics: Vec<DerivedMetric>,
+    /// 指标存储
+    store: Arc<MetricStore>,
+    /// 每个派生指标上次计算时的输入值
+    last_inputs: RwLock<HashMap<String, BTreeMap<String, f64>>>,
+}
+
+impl DerivedMetricEngine {
+    /// 创建派生指标引擎
+    ///
+    /// # 参数
+    /// * `configs` - 派生指标配置
+    /// * `store` - 指标存储，输入指标从中读取，计算结果写入其中
+    ///
+    /// # 返回
+    /// * `Result<Self>` - 名称为空或重复、表达式无效、引用自身或之后定义的派生指标时返回错误
+    pub fn new(configs: &[DerivedMetricConfig], store: Arc<MetricStore>) -> Result<Self> {
+        let all_names: HashSet<&str> = configs.iter().map(|c| c.name.as_str()).collect();
+        let mut defined: HashSet<&str> = HashSet::new();
+        let mut metrics = Vec::new();
+        for config in configs {
+            if config.name.trim().is_empty() {
+                bail!("派生指标名称不能为空");
+            }
+            if DataCache::is_known_metric_key(&config.name) {
+                bail!("派生指标名称 {} 与缓存指标键冲突", config.name);
+            }
+            let expression = MetricExpression::parse(&config.expression)
+                .map_err(|e| anyhow!("派生指标 {} 的表达式无效: {}", config.name, e))?;
+            for input in expression.inputs() {
+                if all_names.contains(input.as_str()) && !defined.contains(input.as_str()) {
+                    bail!("派生指标 {} 引用了自身或之后定义的派生指标 {}", config.name, input);
+                }
+            }
+            if !defined.insert(&config.name) {
+                bail!("派生指标 {} 重复定义", config.name);
+            }
+            metrics.push(DerivedMetric { config: config.clone(), expression });
+        }
+
+        info!("🧮 已加载 {} 个派生指标", metrics.len());
+        Ok(Self {
+            metrics,
+            store,
+            last_inputs: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// 重新计算输入发生变化的派生指标并写入指标存储
+    ///
+    /// 缺少输入值时跳过，计算失败时记录警告
+    ///
+    /// # 参数
+    /// * `cache` - 数据缓存，缓存指标键（如 `price:bitcoin`）从中读取
+    ///
+    /// # 返回
+    /// * `Vec<AggregatedMetric>` - 本次新计算的指标
+    pub async fn recompute(&self, cache: &DataCache) -> Vec<AggregatedMetric> {
+        let mut computed: BTreeMap<String, f64> = BTreeMap::new();
+        let mut results = Vec::new();
+        let mut last_inputs = self.last_inputs.write().await;
+
+        for metric in &self.metrics {
+            let name = &metric.config.name;
+            let Some(inputs) = self.resolve_inputs(&metric.expression, &computed, cache).await else {
+                debug!("⏭️ 派生指标 {} 的输入尚不完整，跳过", name);
+                continue;
+            };
+            if last_inputs.get(name) == Some(&inputs) {
+                continue;
+            }
+
+            let value = match metric.expression.evaluate(&inputs) {
+                Ok(value) => value,
+                Err(e) => {
+                    warn!("⚠️ 计算派生指标 {} 失败: {}", name, e);
+                    continue;
+                }
+            };
+            let metric_value = match &metric.config.unit {
+                Some(unit) => MetricValue::scalar(value, unit.as_str()),
+                None => MetricValue::from(value),
+            };
+            let built = MetricBuilder::new(DataSource::EVERSCAN, name.as_str())
+                .value(metric_value)
+                .metadata(serde_json::json!({
+                    "expression": metric.config.expression,
+                    "inputs": inputs,
+                }))
+                .build();
+            match built {
+                Ok(built) => {
+                    computed.insert(name.clone(), value);
+                    last_inputs.insert(name.clone(), inputs);
+                    results.push(built);
+                }
+                Err(e) => warn!("⚠️ 构建派生指标 {} 失败: {}", name, e),
+            }
+        }
+        drop(last_inputs);
+
+        if !results.is_empty() {
+            if let Err(e) = self.store.save_metrics(&results).await {
+                error!("❌ 保存派生指标失败: {}", e);
+            }
+            debug!("🧮 重新计算了 {} 个派生指标", results.len());
+        }
+        results
+    }
+
+    /// 在给定数据下计算全部派生指标（不写入指标存储，也不更新输入记录）
+    ///
+    /// # 参数
+    /// * `cache` - 数据缓存（可以是模拟情景的缓存）
+    /// * `stored` - 指标存储中指标的假设值，优先于存储中的最新值
+    ///
+    /// # 返回
+    /// * `Vec<SimulatedMetric>` - 按配置顺序的计算结果
+    pub async fn simulate(&self, cache: &DataCache, stored: &BTreeMap<String, f64>) -> Vec<SimulatedMetric> {
+        let mut computed = stored.clone();
+        let mut results = Vec::new();
+        for metric in &self.metrics {
+            let name = &metric.config.name;
+            let value = match computed.get(name) {
+                Some(value) => Some(*value),
+                None => self.resolve_inputs(&metric.expression, &computed, cache).await
+                    .and_then(|inputs| metric.expression.evaluate(&inputs).ok()),
+            };
+            if let Some(value) = value {
+                computed.insert(name.clone(), value);
+            }
+            results.push(SimulatedMetric {
+                name: name.clone(),
+                unit: metric.config.unit.clone(),
+                value,
+            });
+        }
+        results
+    }
+
+    /// 读取表达式全部输入的当前值，任一输入缺失时返回None
+    ///
+    /// 本轮已计算的派生指标优先，缓存指标键从缓存读取，其余取指标存储中的最新标量值
+    async fn resolve_inputs(
+        &self,
+        expression: &MetricExpression,
+        computed: &BTreeMap<String, f64>,
+        cache: &DataCache,
+    ) -> Option<BTreeMap<String, f64>> {
+        let mut inputs = BTreeMap::new();
+        for input in expression.inputs() {
+            let value = match computed.get(&input) {
+                Some(value) => *value,
+                None if DataCache::is_known_metric_key(&input) => cache.metric_value(&input).await?,
+                None => self.store.latest(&input).await?.value.as_f64()?,
+            };
+            inputs.insert(input, value);
+        }
+        Some(inputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web::cache::FEAR_GREED_KEY;
+
+    fn config(name: &str, expression: &str) -> DerivedMetricConfig {
+        DerivedMetricConfig {
+            name: name.to_string(),
+            expression: expression.to_string(),
+            unit: None,
+            description: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recompute_on_input_change() {
+        let store = Arc::new(MetricStore::new(30));
+        let cache = DataCache::new();
+        let engine = DerivedMetricEngine::new(&[
+            config("breadth_ratio", "breadth_advancers / breadth_decliners"),
+            config("breadth_ratio_pct", "breadth_ratio * 100"),
+        ], store.clone()).unwrap();
+
+        // 缺少输入时不计算
+        assert!(engine.recompute(&cache).await.is_empty());
+
+        let point = |name: &str, value: f64| {
+            MetricBuilder::new(DataSource::EVERSCAN, name).value(value).build().unwrap()
+        };
+        store.save_metrics(&[point("breadth_advancers", 30.0), point("breadth_decliners", 20.0)]).await.unwrap();
+        let results = engine.recompute(&cache).await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(store.latest("breadth_ratio_pct").await.unwrap().value.as_f64(), Some(150.0));
+
+        // 输入未变化时不重复写入
+        assert!(engine.recompute(&cache).await.is_empty());
+
+        assert!(DerivedMetricEngine::new(&[config("a", "b + a")], store.clone()).is_err());
+        assert!(DerivedMetricEngine::new(&[config("a", "b"), config("a", "c")], store.clone()).is_err());
+        assert!(DerivedMetricEngine::new(&[config(FEAR_GREED_KEY, "1")], store).is_err());
+    }
+}
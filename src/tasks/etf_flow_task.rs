@@ -0,0 +1,109 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::clients::{EtfFlowClient, EtfFlowRecord};
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue, LABEL_ASSET};
+use crate::storage::MetricStore;
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, ETF_FLOWS_KEY};
+
+/// 现货ETF资金流向任务
+/// 
+/// 轮询配置的数据源，把获取到的每日各基金净流量写入缓存，
+/// 由历史记录器保存并检查大额流出。每个交易日的合计净流入指标只写入一次
+pub struct EtfFlowTask {
+    /// 任务名称
+    name: String,
+    /// 数据源客户端
+    client: Arc<EtfFlowClient>,
+    /// 指标存储（判断交易日是否已经写入）
+    store: Arc<MetricStore>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl EtfFlowTask {
+    /// 创建新的ETF资金流向任务
+    pub fn new(name: String, client: Arc<EtfFlowClient>, store: Arc<MetricStore>, interval_seconds: u64) -> Self {
+        info!("🚀 创建ETF资金流向任务: {}", name);
+        Self {
+            name,
+            client,
+            store,
+            interval_seconds,
+        }
+    }
+
+    /// 只保留晚于指标存储中最新交易日的指标
+    async fn new_days(&self, metrics: Vec<AggregatedMetric>) -> Vec<AggregatedMetric> {
+        let mut fresh = Vec::new();
+        for metric in metrics {
+            let stored = self.store.latest(&metric.metric_name).await;
+            if stored.is_none_or(|stored| metric.timestamp > stored.timestamp) {
+                fresh.push(metric);
+            }
+        }
+        fresh
+    }
+
+    /// 每个资产最近一个交易日的合计净流入指标
+    fn to_metrics(records: &[EtfFlowRecord]) -> Result<Vec<AggregatedMetric>> {
+        let mut latest: BTreeMap<String, (NaiveDate, f64, usize)> = BTreeMap::new();
+        for record in records {
+            let entry = latest.entry(record.asset.to_lowercase()).or_insert((record.date, 0.0, 0));
+            if record.date > entry.0 {
+                *entry = (record.date, 0.0, 0);
+            }
+            if record.date == entry.0 {
+                entry.1 += record.net_flow_usd;
+                entry.2 += 1;
+            }
+        }
+        
+        latest.into_iter()
+            .map(|(asset, (date, net_flow, funds))| {
+                MetricBuilder::new(EtfFlowClient::SOURCE, format!("etf_netflow_{}", asset))
+                    .value(MetricValue::scalar(net_flow, "usd"))
+                    .timestamp(date.and_hms_opt(0, 0, 0).expect("有效时间").and_utc())
+                    .metadata(serde_json::json!({ "date": date, "funds": funds }))
+                    .label(LABEL_ASSET, asset.as_str())
+                    .build()
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Task for EtfFlowTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn description(&self) -> &str {
+        "轮询现货BTC/ETH ETF每日各基金净流量"
+    }
+    
+    fn id(&self) -> &str {
+        "etf_flows"
+    }
+    
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+    
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let records = self.client.fetch().await?;
+        if records.is_empty() {
+            return Err(anyhow!("ETF资金流向数据源返回空列表"));
+        }
+        
+        info!("🏛️ 获取到 {} 条ETF资金流向记录", records.len());
+        cache.set_dataset(ETF_FLOWS_KEY, &records).await?;
+        
+        Ok(self.new_days(Self::to_metrics(&records)?).await)
+    }
+}
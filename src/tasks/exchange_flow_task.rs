@@ -0,0 +1,136 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::analytics::{summarize_flows, AssetExchangeFlows, ExchangeFlows};
+use crate::clients::GlassnodeClient;
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue, LABEL_ASSET};
+use crate::storage::MetricStore;
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, EXCHANGE_FLOWS_KEY};
+
+/// 跟踪交易所余额的资产
+pub const EXCHANGE_FLOW_ASSETS: [&str; 2] = ["BTC", "ETH"];
+
+/// 获取的历史天数（覆盖30日趋势并留有余量）
+const LOOKBACK_DAYS: i64 = 35;
+
+/// Glassnode交易所余额指标
+const BALANCE_METRIC: &str = "distribution/balance_exchanges";
+
+/// 交易所资金流向任务
+/// 
+/// 每日从Glassnode获取BTC/ETH的交易所余额，计算净流量和7/30日趋势，
+/// 每天的指标只写入一次
+pub struct ExchangeFlowTask {
+    /// 任务名称
+    name: String,
+    /// Glassnode客户端
+    glassnode: Arc<GlassnodeClient>,
+    /// 指标存储（判断当天的指标是否已经写入）
+    store: Arc<MetricStore>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl ExchangeFlowTask {
+    /// 创建新的交易所资金流向任务
+    pub fn new(name: String, glassnode: Arc<GlassnodeClient>, store: Arc<MetricStore>, interval_seconds: u64) -> Self {
+        info!("🚀 创建交易所资金流向任务: {}", name);
+        Self {
+            name,
+            glassnode,
+            store,
+            interval_seconds,
+        }
+    }
+
+    /// 只保留晚于指标存储中最新一天的指标
+    async fn new_days(&self, metrics: Vec<AggregatedMetric>) -> Vec<AggregatedMetric> {
+        let mut fresh = Vec::new();
+        for metric in metrics {
+            let stored = self.store.latest(&metric.metric_name).await;
+            if stored.is_none_or(|stored| metric.timestamp > stored.timestamp) {
+                fresh.push(metric);
+            }
+        }
+        fresh
+    }
+
+    /// 将单个资产的资金流向转换为指标
+    fn to_metrics(flows: &AssetExchangeFlows) -> Result<Vec<AggregatedMetric>> {
+        let asset = flows.asset.to_lowercase();
+        let timestamp = flows.as_of.and_hms_opt(0, 0, 0).expect("有效时间").and_utc();
+        let metric = |name: String, value: f64| {
+            MetricBuilder::new(GlassnodeClient::SOURCE, name)
+                .value(MetricValue::scalar(value, asset.as_str()))
+                .timestamp(timestamp)
+                .metadata(serde_json::json!({ "asset": flows.asset }))
+                .label(LABEL_ASSET, asset.as_str())
+                .build()
+        };
+        
+        let mut metrics = vec![
+            metric(format!("exchange_balance_{}", asset), flows.balance)?,
+            metric(format!("exchange_netflow_7d_{}", asset), flows.trend_7d.net_flow)?,
+            metric(format!("exchange_netflow_30d_{}", asset), flows.trend_30d.net_flow)?,
+        ];
+        if let Some(net_flow) = flows.net_flow_24h {
+            metrics.push(metric(format!("exchange_netflow_{}", asset), net_flow)?);
+        }
+        Ok(metrics)
+    }
+}
+
+#[async_trait]
+impl Task for ExchangeFlowTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn description(&self) -> &str {
+        "跟踪BTC/ETH交易所余额和净流量的7/30日趋势"
+    }
+    
+    fn id(&self) -> &str {
+        "exchange_flows"
+    }
+    
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+    
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let since = (Utc::now() - Duration::days(LOOKBACK_DAYS)).timestamp();
+        let mut assets = Vec::new();
+        let mut metrics = Vec::new();
+        
+        for asset in EXCHANGE_FLOW_ASSETS {
+            let series = match self.glassnode.get_daily_series(BALANCE_METRIC, asset, since).await {
+                Ok(series) => series,
+                Err(e) => {
+                    warn!("⚠️ 获取 {} 交易所余额失败: {}", asset, e);
+                    continue;
+                }
+            };
+            let balances: Vec<_> = series.iter().map(|p| (p.timestamp, p.value)).collect();
+            if let Some(flows) = summarize_flows(asset, &balances) {
+                info!(
+                    "🏦 {} 交易所余额: {:.0}，7日净流入 {:.0}，30日净流入 {:.0}",
+                    flows.asset, flows.balance, flows.trend_7d.net_flow, flows.trend_30d.net_flow
+                );
+                metrics.extend(Self::to_metrics(&flows)?);
+                assets.push(flows);
+            }
+        }
+        
+        if assets.is_empty() {
+            return Err(anyhow!("未获取到任何资产的交易所余额"));
+        }
+        cache.set_dataset(EXCHANGE_FLOWS_KEY, &ExchangeFlows { timestamp: Utc::now(), assets }).await?;
+        
+        Ok(self.new_days(metrics).await)
+    }
+}
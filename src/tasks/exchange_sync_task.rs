@@ -0,0 +1,70 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::models::AggregatedMetric;
+use crate::portfolio::{ExchangeAccountStore, PortfolioStore};
+use crate::tasks::Task;
+use crate::web::cache::DataCache;
+
+/// 交易所余额同步任务
+/// 
+/// 定期把交易所账户余额导入投资组合，应注册在组合估值任务之前
+pub struct ExchangeSyncTask {
+    /// 任务名称
+    name: String,
+    /// 交易所账户存储
+    accounts: Arc<ExchangeAccountStore>,
+    /// 组合存储
+    portfolio: Arc<PortfolioStore>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl ExchangeSyncTask {
+    /// 创建新的交易所余额同步任务
+    pub fn new(
+        name: String,
+        accounts: Arc<ExchangeAccountStore>,
+        portfolio: Arc<PortfolioStore>,
+        interval_seconds: u64,
+    ) -> Self {
+        info!("🚀 创建交易所余额同步任务: {}", name);
+        Self {
+            name,
+            accounts,
+            portfolio,
+            interval_seconds,
+        }
+    }
+}
+
+#[async_trait]
+impl Task for ExchangeSyncTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn description(&self) -> &str {
+        "使用只读API密钥同步交易所余额到投资组合"
+    }
+    
+    fn id(&self) -> &str {
+        "exchange_sync"
+    }
+    
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+    
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let (succeeded, failed) = self.accounts.sync_all(&self.portfolio, cache).await;
+        
+        // 只有全部账户都失败时才视为任务失败，单个账户的错误记录在账户上
+        if succeeded == 0 && failed > 0 {
+            return Err(anyhow!("{} 个交易所账户全部同步失败", failed));
+        }
+        Ok(Vec::new())
+    }
+}
@@ -1,14 +1,12 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use std::sync::Arc;
-use std::time::Duration;
-use tracing::{info, error, warn};
-use chrono::Utc;
+use tracing::{info, error};
 
 use crate::clients::CoinMarketCapClient;
-use crate::models::{AggregatedMetric, MetricBuilder, DataSource};
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue};
 use crate::tasks::Task;
-use crate::web::cache::DataCache;
+use crate::web::cache::{DataCache, FEAR_GREED_KEY};
 
 /// 贪婪恐惧指数任务
 pub struct FearGreedTask {
@@ -46,50 +44,35 @@ impl FearGreedTask {
             Ok(fear_greed_data) => {
                 info!("✅ 贪婪恐惧指数获取成功: {} - {}", fear_greed_data.value, fear_greed_data.value_classification);
                 
-                // 获取中文分类和投资建议
-                let chinese_classification = CoinMarketCapClient::get_chinese_classification(&fear_greed_data.value_classification);
-                let sentiment_description = CoinMarketCapClient::get_sentiment_description(fear_greed_data.value);
-                let investment_advice = CoinMarketCapClient::get_investment_advice(fear_greed_data.value);
-                
-                // 缓存数据
-                let cached_data = serde_json::json!({
-                    "value": fear_greed_data.value,
-                    "value_classification": fear_greed_data.value_classification,
-                    "value_classification_zh": chinese_classification,
-                    "sentiment_description": sentiment_description,
-                    "investment_advice": investment_advice,
-                    "timestamp": fear_greed_data.timestamp,
-                    "time_until_update": fear_greed_data.time_until_update
-                });
-                cache.set_fear_greed_index(cached_data).await;
-                
                 // 转换为指标格式
                 let mut metrics = Vec::new();
-                let timestamp = Utc::now();
                 
                 // 贪婪恐惧指数值
                 metrics.push(MetricBuilder::new(
-                    DataSource::CoinMarketCap,
+                    CoinMarketCapClient::SOURCE,
                     "fear_greed_index".to_string()
                 )
-                .value(serde_json::json!(fear_greed_data.value))
-                .timestamp(timestamp)
+                .value(MetricValue::scalar(fear_greed_data.value as f64, "index"))
+                .timestamp(fear_greed_data.fetched_at)
                 .metadata(serde_json::json!({
                     "classification": fear_greed_data.value_classification,
-                    "classification_zh": chinese_classification,
-                    "sentiment_description": sentiment_description,
-                    "investment_advice": investment_advice,
-                    "time_until_update": fear_greed_data.time_until_update
+                    "classification_zh": fear_greed_data.value_classification_zh,
+                    "sentiment_description": fear_greed_data.sentiment_description,
+                    "investment_advice": fear_greed_data.investment_advice,
+                    "time_until_update": fear_greed_data.time_until_update,
+                    "source": fear_greed_data.source
                 }))
-                .build());
+                .build()?);
                 
+                info!("🎯 贪婪恐惧指数: {} - {} ({})", fear_greed_data.value, fear_greed_data.value_classification_zh, fear_greed_data.investment_advice);
+                cache.set_fear_greed_index(fear_greed_data).await;
                 info!("📦 贪婪恐惧指数数据已缓存");
-                info!("🎯 贪婪恐惧指数: {} - {} ({})", fear_greed_data.value, chinese_classification, investment_advice);
                 
                 Ok(metrics)
             }
             Err(e) => {
                 error!("❌ 获取贪婪恐惧指数失败: {}", e);
+                cache.record_failure(FEAR_GREED_KEY, &e).await;
                 Err(e)
             }
         }
@@ -0,0 +1,167 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+use crate::analytics::{current_sentiment, TrialIndex, TrialVariant, LABEL_FORMULA, LABEL_VARIANT};
+use crate::clients::{AltcoinSeasonConstituents, AltcoinSeasonFormula};
+use crate::config::{FormulaTrialConfig, SentimentConfig};
+use crate::models::{AggregatedMetric, DataSource, MetricBuilder, MetricValue};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, ALTCOIN_SEASON_CONSTITUENTS_KEY};
+
+/// 公式并行比较任务
+///
+/// 在配置的截止时间之前，按现行和候选两个版本计算山寨币季节指数和综合情绪分并写入指标存储，
+/// 供 `/api/formulas/{index}/comparison` 比较。山寨币季节指数由缓存中的成分币种明细重新计算，
+/// 应注册在山寨币季节指数任务之后
+pub struct FormulaTrialTask {
+    /// 任务名称
+    name: String,
+    /// 比较配置
+    config: FormulaTrialConfig,
+    /// 现行的山寨币季节指数公式
+    altcoin_season_formula: AltcoinSeasonFormula,
+    /// 现行的综合情绪权重
+    sentiment: SentimentConfig,
+}
+
+impl FormulaTrialTask {
+    /// 创建新的公式并行比较任务
+    ///
+    /// # 参数
+    /// * `name` - 任务名称
+    /// * `config` - 比较配置
+    /// * `altcoin_season_formula` - 现行的山寨币季节指数公式
+    /// * `sentiment` - 现行的综合情绪权重
+    pub fn new(name: String, config: FormulaTrialConfig, altcoin_season_formula: AltcoinSeasonFormula, sentiment: SentimentConfig) -> Self {
+        info!("🚀 创建公式并行比较任务: {}", name);
+        Self {
+            name,
+            config,
+            altcoin_season_formula,
+            sentiment,
+        }
+    }
+}
+
+/// 构建一个版本的指数值
+fn trial_metric(
+    index: TrialIndex,
+    variant: TrialVariant,
+    formula: &str,
+    value: f64,
+    timestamp: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<AggregatedMetric> {
+    MetricBuilder::new(DataSource::EVERSCAN, index.metric_name())
+        .value(MetricValue::scalar(value, "index"))
+        .timestamp(timestamp)
+        .label(LABEL_VARIANT, variant.as_str())
+        .label(LABEL_FORMULA, formula)
+        .metadata(serde_json::json!({ "trial_until": until }))
+        .build()
+}
+
+#[async_trait]
+impl Task for FormulaTrialTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "并行计算指数公式的现行和候选版本，供切换前比较"
+    }
+
+    fn id(&self) -> &str {
+        "formula_trial"
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.config.interval_seconds
+    }
+
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let now = Utc::now();
+        let mut metrics = Vec::new();
+
+        if let Some(until) = self.config.altcoin_season_until.filter(|until| now <= *until) {
+            if let Some(breakdown) = cache.get_dataset::<AltcoinSeasonConstituents>(ALTCOIN_SEASON_CONSTITUENTS_KEY).await {
+                for (variant, formula) in [
+                    (TrialVariant::Baseline, self.altcoin_season_formula),
+                    (TrialVariant::Candidate, self.config.altcoin_season_candidate),
+                ] {
+                    let value = breakdown.index_value_with(formula) as f64;
+                    metrics.push(trial_metric(TrialIndex::AltcoinSeason, variant, formula.as_str(), value, now, until)?);
+                }
+            }
+        }
+
+        if let Some(until) = self.config.sentiment_until.filter(|until| now <= *until) {
+            let baseline = current_sentiment(cache, &self.sentiment).await;
+            let candidate = current_sentiment(cache, &self.config.sentiment_candidate).await;
+            if let (Some(baseline), Some(candidate)) = (baseline, candidate) {
+                metrics.push(trial_metric(TrialIndex::CompositeSentiment, TrialVariant::Baseline, "configured_weights", baseline.score, now, until)?);
+                metrics.push(trial_metric(TrialIndex::CompositeSentiment, TrialVariant::Candidate, "candidate_weights", candidate.score, now, until)?);
+            }
+        }
+
+        info!("⚖️ 已记录 {} 个公式比较样本", metrics.len() / 2);
+        Ok(metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::AltcoinSeasonConstituent;
+
+    fn constituent(symbol: &str, change_24h: f64, change_90d: Option<f64>) -> AltcoinSeasonConstituent {
+        AltcoinSeasonConstituent {
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            cmc_rank: None,
+            change_24h,
+            change_90d,
+            outperforming: change_24h > 1.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_both_variants_until_deadline() {
+        let cache = DataCache::new();
+        let breakdown = AltcoinSeasonConstituents {
+            btc_change_24h: 1.0,
+            btc_change_90d: Some(10.0),
+            constituents: vec![
+                constituent("ETH", 2.0, Some(5.0)),
+                constituent("SOL", 3.0, Some(20.0)),
+                constituent("NEW", 0.5, None),
+                constituent("XRP", 0.0, Some(30.0)),
+            ],
+            excluded: Vec::new(),
+        };
+        cache.set_dataset(ALTCOIN_SEASON_CONSTITUENTS_KEY, &breakdown).await.unwrap();
+
+        let config = FormulaTrialConfig {
+            altcoin_season_until: Some(Utc::now() + chrono::Duration::days(7)),
+            ..FormulaTrialConfig::default()
+        };
+        let task = FormulaTrialTask::new("公式比较".to_string(), config.clone(), AltcoinSeasonFormula::Change24h, SentimentConfig::default());
+        let metrics = task.execute(&cache).await.unwrap();
+        let value = |variant: &str| metrics.iter()
+            .find(|m| m.labels.get(LABEL_VARIANT).map(String::as_str) == Some(variant))
+            .and_then(|m| m.value.as_f64());
+        // 24小时：2/4 跑赢；90天：缺少数据的币种不参与，2/3 跑赢
+        assert_eq!(value("baseline"), Some(50.0));
+        assert_eq!(value("candidate"), Some(67.0));
+
+        // 截止时间之后不再记录
+        let expired = FormulaTrialConfig {
+            altcoin_season_until: Some(Utc::now() - chrono::Duration::seconds(1)),
+            ..config
+        };
+        let task = FormulaTrialTask::new("公式比较".to_string(), expired, AltcoinSeasonFormula::Change24h, SentimentConfig::default());
+        assert!(task.execute(&cache).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::clients::GoogleTrendsClient;
+use crate::history::TrendsHistory;
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, TRENDS_KEY};
+
+/// 两个搜索词之间的请求间隔（降低触发频率限制的概率）
+const TERM_DELAY: Duration = Duration::from_secs(5);
+
+/// Google Trends 搜索热度任务
+/// 
+/// 获取配置的搜索词近5年的每周热度并整体替换保存的序列
+pub struct GoogleTrendsTask {
+    /// 任务名称
+    name: String,
+    /// Google Trends客户端
+    client: Arc<GoogleTrendsClient>,
+    /// 搜索热度历史
+    history: Arc<TrendsHistory>,
+    /// 搜索词
+    terms: Vec<String>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl GoogleTrendsTask {
+    /// 创建新的搜索热度任务
+    pub fn new(
+        name: String,
+        client: Arc<GoogleTrendsClient>,
+        history: Arc<TrendsHistory>,
+        terms: Vec<String>,
+        interval_seconds: u64,
+    ) -> Self {
+        info!("🚀 创建搜索热度任务: {}（{} 个搜索词）", name, terms.len());
+        Self {
+            name,
+            client,
+            history,
+            terms,
+            interval_seconds,
+        }
+    }
+}
+
+/// 把搜索词转换为指标名称片段（如 `crypto crash` -> `crypto_crash`）
+fn metric_suffix(term: &str) -> String {
+    term.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[async_trait]
+impl Task for GoogleTrendsTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn description(&self) -> &str {
+        "获取配置搜索词的Google Trends每周热度，用于逆向情绪分析"
+    }
+    
+    fn id(&self) -> &str {
+        "google_trends"
+    }
+    
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+    
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let mut metrics = Vec::new();
+        
+        for (index, term) in self.terms.iter().enumerate() {
+            if index > 0 {
+                tokio::time::sleep(TERM_DELAY).await;
+            }
+            let weeks = match self.client.weekly_interest(term).await {
+                Ok(weeks) if !weeks.is_empty() => weeks,
+                Ok(_) => {
+                    warn!("⚠️ 搜索词 {} 没有热度数据", term);
+                    continue;
+                }
+                Err(e) => {
+                    warn!("⚠️ 获取搜索词 {} 热度失败: {:#}", term, e);
+                    continue;
+                }
+            };
+            
+            let series = self.history.replace(term, &weeks, Utc::now()).await?;
+            info!("🔍 搜索词 {} 最新热度 {:?}，百分位 {:?}", term, series.latest, series.percentile);
+            if let (Some(latest), Some(point)) = (series.latest, series.points.last()) {
+                metrics.push(
+                    MetricBuilder::new(GoogleTrendsClient::SOURCE, format!("trends_{}", metric_suffix(term)))
+                        .value(MetricValue::scalar(latest, "score"))
+                        .timestamp(point.week.and_hms_opt(0, 0, 0).expect("有效时间").and_utc())
+                        .metadata(serde_json::json!({
+                            "term": term,
+                            "source": "google_trends",
+                            "percentile": series.percentile,
+                        }))
+                        .build()?
+                );
+            }
+        }
+        
+        if metrics.is_empty() {
+            return Err(anyhow!("未获取到任何搜索词的热度"));
+        }
+        cache.record_success(TRENDS_KEY).await;
+        
+        Ok(metrics)
+    }
+}
@@ -0,0 +1,354 @@
+use anyhow::Result;
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::{oneshot, Mutex, Semaphore};
+use tracing::{info, warn, error, debug};
+
+use crate::config::JobsConfig;
+use crate::web::cache::DataCache;
+use super::{TaskExecutionResult, TaskManager};
+
+/// 作业优先级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobPriority {
+    /// 低（定时执行）
+    Low,
+    /// 普通（外部刷新请求）
+    Normal,
+    /// 高（管理员手动触发）
+    High,
+}
+
+impl JobPriority {
+    /// 队列下标，优先级越高越先被取出
+    fn index(self) -> usize {
+        match self {
+            JobPriority::High => 0,
+            JobPriority::Normal => 1,
+            JobPriority::Low => 2,
+        }
+    }
+}
+
+/// 作业来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobSource {
+    /// 调度器按执行间隔触发
+    Scheduled,
+    /// 管理员手动触发
+    Manual,
+    /// 外部系统刷新请求
+    Refresh,
+}
+
+impl JobSource {
+    /// 来源对应的优先级
+    pub fn priority(self) -> JobPriority {
+        match self {
+            JobSource::Scheduled => JobPriority::Low,
+            JobSource::Refresh => JobPriority::Normal,
+            JobSource::Manual => JobPriority::High,
+        }
+    }
+}
+
+/// 队列中的作业
+struct Job {
+    /// 任务ID
+    task_id: String,
+    /// 来源
+    source: JobSource,
+    /// 已重试次数
+    attempt: u32,
+    /// 等待执行结果的调用方（可选）
+    reply: Option<oneshot::Sender<TaskExecutionResult>>,
+}
+
+/// 任务队列状态
+#[derive(Debug, Clone, Serialize)]
+pub struct JobQueueStats {
+    /// 各优先级排队中的作业数（高、普通、低）
+    pub queued: [usize; 3],
+    /// 正在执行的作业数
+    pub running: usize,
+    /// 工作者数量
+    pub workers: usize,
+}
+
+/// 任务队列
+///
+/// 定时执行、手动触发和刷新请求都作为作业进入按优先级划分的有界队列，
+/// 由固定数量的工作者按优先级取出执行。队列满时手动触发和刷新请求立即被拒绝，
+/// 调度器则等待队列腾出空间；定时作业失败后按退避时间重试
+#[derive(Clone)]
+pub struct JobQueue {
+    /// 任务管理器
+    tasks: TaskManager,
+    /// 各优先级队列的发送端（按 `JobPriority::index` 排列）
+    senders: Arc<[mpsc::Sender<Job>; 3]>,
+    /// 已在排队或执行中的定时任务
+    scheduled: Arc<Mutex<HashSet<String>>>,
+    /// 正在执行的作业数
+    running: Arc<AtomicUsize>,
+    /// 配置
+    config: JobsConfig,
+}
+
+impl JobQueue {
+    /// 创建任务队列并启动分发器
+    ///
+    /// # 参数
+    /// * `tasks` - 任务管理器
+    /// * `cache` - 数据缓存
+    /// * `config` - 队列配置
+    pub fn start(tasks: TaskManager, cache: Arc<DataCache>, config: JobsConfig) -> Self {
+        let capacity = config.queue_capacity.max(1);
+        let (high_tx, high_rx) = mpsc::channel(capacity);
+        let (normal_tx, normal_rx) = mpsc::channel(capacity);
+        let (low_tx, low_rx) = mpsc::channel(capacity);
+
+        let queue = Self {
+            tasks,
+            senders: Arc::new([high_tx, normal_tx, low_tx]),
+            scheduled: Arc::new(Mutex::new(HashSet::new())),
+            running: Arc::new(AtomicUsize::new(0)),
+            config,
+        };
+        info!("🧵 启动任务队列，{} 个工作者，队列容量 {}", queue.config.workers.max(1), capacity);
+        tokio::spawn(queue.clone().dispatch(cache, [high_rx, normal_rx, low_rx]));
+        queue
+    }
+
+    /// 提交作业，不等待执行结果
+    ///
+    /// # 参数
+    /// * `task_id` - 任务ID
+    /// * `source` - 作业来源
+    ///
+    /// # 返回
+    /// * `Result<()>` - 任务不存在或队列已满时返回错误
+    pub async fn enqueue(&self, task_id: &str, source: JobSource) -> Result<()> {
+        self.submit(task_id, source, None).await
+    }
+
+    /// 提交作业并等待执行结果
+    ///
+    /// # 参数
+    /// * `task_id` - 任务ID
+    /// * `source` - 作业来源
+    ///
+    /// # 返回
+    /// * `Result<TaskExecutionResult>` - 执行结果，任务不存在或队列已满时返回错误
+    pub async fn run_now(&self, task_id: &str, source: JobSource) -> Result<TaskExecutionResult> {
+        let (tx, rx) = oneshot::channel();
+        self.submit(task_id, source, Some(tx)).await?;
+        rx.await.map_err(|_| anyhow::anyhow!("任务 '{}' 执行被中断", task_id))
+    }
+
+    /// 队列当前状态
+    pub fn stats(&self) -> JobQueueStats {
+        JobQueueStats {
+            queued: [0, 1, 2].map(|i| self.senders[i].max_capacity() - self.senders[i].capacity()),
+            running: self.running.load(Ordering::Relaxed),
+            workers: self.config.workers.max(1),
+        }
+    }
+
+    /// 运行调度器
+    ///
+    /// 定期把到期的任务作为低优先级作业放入队列；同一任务已在排队或执行时不重复提交
+    pub async fn run_scheduler(self) {
+        let tick = std::time::Duration::from_secs(self.config.scheduler_tick_seconds.max(1));
+        let mut interval = tokio::time::interval(tick);
+        loop {
+            interval.tick().await;
+            for task_id in self.tasks.due_tasks(Utc::now()).await {
+                if !self.scheduled.lock().await.insert(task_id.clone()) {
+                    debug!("⏭️ 任务 {} 已在队列中，跳过本次调度", task_id);
+                    continue;
+                }
+                let job = Job { task_id, source: JobSource::Scheduled, attempt: 0, reply: None };
+                // 队列满时等待，形成对调度器的背压
+                if self.senders[JobPriority::Low.index()].send(job).await.is_err() {
+                    error!("❌ 任务队列已关闭，调度器退出");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// 校验任务并放入对应优先级的队列
+    async fn submit(
+        &self,
+        task_id: &str,
+        source: JobSource,
+        reply: Option<oneshot::Sender<TaskExecutionResult>>,
+    ) -> Result<()> {
+        if !self.tasks.has_task(task_id).await {
+            return Err(anyhow::anyhow!("任务 '{}' 不存在", task_id));
+        }
+        let job = Job { task_id: task_id.to_string(), source, attempt: 0, reply };
+        self.senders[source.priority().index()].try_send(job).map_err(|e| match e {
+            TrySendError::Full(_) => anyhow::anyhow!("任务队列已满，请稍后重试"),
+            TrySendError::Closed(_) => anyhow::anyhow!("任务队列已关闭"),
+        })?;
+        debug!("📥 任务 {} 已加入队列（{:?}）", task_id, source);
+        Ok(())
+    }
+
+    /// 按优先级取出作业并交给空闲的工作者
+    async fn dispatch(self, cache: Arc<DataCache>, receivers: [mpsc::Receiver<Job>; 3]) {
+        let workers = Arc::new(Semaphore::new(self.config.workers.max(1)));
+        let [mut high, mut normal, mut low] = receivers;
+        loop {
+            // 先等到空闲工作者再取作业，保证取出的始终是当前最高优先级的作业
+            let Ok(permit) = workers.clone().acquire_owned().await else {
+                return;
+            };
+            let job = tokio::select! {
+                biased;
+                Some(job) = high.recv() => job,
+                Some(job) = normal.recv() => job,
+                Some(job) = low.recv() => job,
+                else => return,
+            };
+            let queue = self.clone();
+            let cache = cache.clone();
+            tokio::spawn(async move {
+                queue.run_job(job, &cache).await;
+                drop(permit);
+            });
+        }
+    }
+
+    /// 执行作业，定时作业失败时安排重试
+    async fn run_job(&self, mut job: Job, cache: &DataCache) {
+        self.running.fetch_add(1, Ordering::Relaxed);
+        let result = self.tasks.execute_task(&job.task_id, cache).await;
+        self.running.fetch_sub(1, Ordering::Relaxed);
+
+        match result {
+            Ok(result) if result.success => {
+                info!("✅ 任务 {} 执行成功，获取 {} 条数据，耗时 {}ms",
+                      result.task_name, result.metrics_count, result.execution_time_ms);
+                if let Some(reply) = job.reply.take() {
+                    let _ = reply.send(result);
+                }
+            }
+            Ok(result) => {
+                error!("❌ 任务 {} 执行失败: {}",
+                       result.task_name, result.error.as_deref().unwrap_or("未知错误"));
+                if job.source == JobSource::Scheduled && job.attempt < self.config.max_retries {
+                    self.retry(job);
+                    return;
+                }
+                if let Some(reply) = job.reply.take() {
+                    let _ = reply.send(result);
+                }
+            }
+            Err(e) => error!("❌ 任务 {} 无法执行: {}", job.task_id, e),
+        }
+
+        if job.source == JobSource::Scheduled {
+            self.scheduled.lock().await.remove(&job.task_id);
+        }
+    }
+
+    /// 退避后重新放入队列
+    fn retry(&self, mut job: Job) {
+        let backoff = self.config.retry_backoff_seconds.saturating_mul(1 << job.attempt.min(16));
+        job.attempt += 1;
+        warn!("🔁 任务 {} 将在 {} 秒后第 {} 次重试", job.task_id, backoff, job.attempt);
+        let sender = self.senders[job.source.priority().index()].clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_secs(backoff)).await;
+            let _ = sender.send(job).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::models::AggregatedMetric;
+    use crate::tasks::Task;
+
+    struct FlakyTask {
+        runs: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Task for FlakyTask {
+        fn name(&self) -> &str { "flaky" }
+        fn description(&self) -> &str { "fails on the first run" }
+        fn id(&self) -> &str { "flaky" }
+        fn interval_seconds(&self) -> u64 { 3600 }
+        async fn execute(&self, _cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+            if self.runs.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err(anyhow::anyhow!("upstream down"));
+            }
+            Ok(Vec::new())
+        }
+    }
+
+    struct PanickingTask;
+
+    #[async_trait]
+    impl Task for PanickingTask {
+        fn name(&self) -> &str { "panicking" }
+        fn description(&self) -> &str { "unwraps missing upstream data" }
+        fn id(&self) -> &str { "panicking" }
+        fn interval_seconds(&self) -> u64 { 3600 }
+        async fn execute(&self, _cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+            let price = serde_json::Value::Null.as_f64().expect("上游缺少价格");
+            assert!(price > 0.0);
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_panicking_task_fails_run() {
+        let mut tasks = TaskManager::new();
+        tasks.register_task(Box::new(PanickingTask)).await.unwrap();
+        let queue = JobQueue::start(tasks.clone(), Arc::new(DataCache::new()), JobsConfig::default());
+
+        // panic被记为执行失败，工作者继续处理后续作业
+        for _ in 0..2 {
+            let result = queue.run_now("panicking", JobSource::Manual).await.unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().contains("panic"));
+        }
+        assert_eq!(queue.stats().running, 0);
+        assert!(tasks.get_task_health().await[0].last_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_retry_and_manual_run() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut tasks = TaskManager::new();
+        tasks.register_task(Box::new(FlakyTask { runs: runs.clone() })).await.unwrap();
+        let config = JobsConfig { retry_backoff_seconds: 0, ..JobsConfig::default() };
+        let queue = JobQueue::start(tasks, Arc::new(DataCache::new()), config);
+
+        assert!(queue.enqueue("missing", JobSource::Refresh).await.is_err());
+
+        // 定时作业首次失败后自动重试
+        queue.enqueue("flaky", JobSource::Scheduled).await.unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while runs.load(Ordering::SeqCst) < 2 {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        }).await.unwrap();
+
+        let result = queue.run_now("flaky", JobSource::Manual).await.unwrap();
+        assert!(result.success);
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+}
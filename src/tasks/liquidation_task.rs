@@ -0,0 +1,125 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::clients::{CoinglassClient, LiquidationRecord};
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue, LABEL_ASSET};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, LIQUIDATIONS_KEY};
+
+/// 每次获取的小时数（覆盖轮询间隔内可能遗漏的小时和数据源的修正）
+const FETCH_HOURS: usize = 48;
+
+/// 合约爆仓数据任务
+///
+/// 从Coinglass获取配置币种最近的每小时多/空爆仓金额写入缓存，
+/// 由历史记录器保存并检查连环爆仓
+pub struct LiquidationTask {
+    /// 任务名称
+    name: String,
+    /// Coinglass客户端
+    client: Arc<CoinglassClient>,
+    /// 采集的币种符号
+    symbols: Vec<String>,
+    /// 参与聚合的交易所
+    exchanges: Vec<String>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl LiquidationTask {
+    /// 创建新的合约爆仓数据任务
+    ///
+    /// # 参数
+    /// * `name` - 任务名称
+    /// * `client` - Coinglass客户端
+    /// * `symbols` - 采集的币种符号
+    /// * `exchanges` - 参与聚合的交易所
+    /// * `interval_seconds` - 执行间隔
+    pub fn new(name: String, client: Arc<CoinglassClient>, symbols: Vec<String>, exchanges: Vec<String>, interval_seconds: u64) -> Self {
+        info!("🚀 创建合约爆仓数据任务: {}（{} 个币种）", name, symbols.len());
+        Self {
+            name,
+            client,
+            symbols,
+            exchanges,
+            interval_seconds,
+        }
+    }
+
+    /// 每个币种最近一个已结束小时的爆仓指标
+    fn to_metrics(records: &[LiquidationRecord]) -> Result<Vec<AggregatedMetric>> {
+        let Some(symbol) = records.first().map(|r| r.symbol.to_lowercase()) else {
+            return Ok(Vec::new());
+        };
+        let now = Utc::now();
+        let Some(latest) = records.iter().rev().find(|r| r.hour + Duration::hours(1) <= now) else {
+            return Ok(Vec::new());
+        };
+
+        [
+            ("liquidations_long", latest.long_usd),
+            ("liquidations_short", latest.short_usd),
+            ("liquidations_total", latest.long_usd + latest.short_usd),
+        ]
+        .into_iter()
+        .map(|(name, value)| {
+            MetricBuilder::new(CoinglassClient::SOURCE, format!("{}_{}", name, symbol))
+                .value(MetricValue::scalar(value, "usd"))
+                .timestamp(latest.hour)
+                .metadata(serde_json::json!({ "symbol": latest.symbol, "interval": "1h" }))
+                .label(LABEL_ASSET, symbol.as_str())
+                .build()
+        })
+        .collect()
+    }
+}
+
+#[async_trait]
+impl Task for LiquidationTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "从Coinglass采集合约每小时多/空爆仓金额"
+    }
+
+    fn id(&self) -> &str {
+        "liquidations"
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let mut records = Vec::new();
+        let mut metrics = Vec::new();
+
+        for symbol in &self.symbols {
+            match self.client.get_liquidation_history(symbol, &self.exchanges, FETCH_HOURS).await {
+                Ok(hours) => {
+                    if let Some(last) = hours.last() {
+                        info!(
+                            "💥 {} 当前小时爆仓: 多单 {:.0} 美元，空单 {:.0} 美元",
+                            last.symbol, last.long_usd, last.short_usd
+                        );
+                    }
+                    metrics.extend(Self::to_metrics(&hours)?);
+                    records.extend(hours);
+                }
+                Err(e) => warn!("⚠️ 获取 {} 爆仓数据失败: {}", symbol, e),
+            }
+        }
+
+        if records.is_empty() {
+            return Err(anyhow!("未获取到任何币种的爆仓数据"));
+        }
+        cache.set_dataset(LIQUIDATIONS_KEY, &records).await?;
+
+        Ok(metrics)
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::analytics::{summarize_order_book, LiquiditySnapshot};
+use crate::clients::ExchangeTickerClient;
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue, LABEL_EXCHANGE};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, LIQUIDITY_KEY};
+
+/// 每侧获取的订单簿价位数（Binance允许的最大值）
+const ORDER_BOOK_LIMIT: usize = 5000;
+
+/// 流动性指标名称
+///
+/// # 参数
+/// * `field` - 字段（`spread_bps`、`bid_depth`、`ask_depth`）
+/// * `symbol` - 交易对
+pub fn liquidity_metric_name(field: &str, symbol: &str) -> String {
+    format!("liquidity_{}_{}", field, symbol.to_lowercase())
+}
+
+/// 订单簿流动性快照任务
+///
+/// 定期获取配置交易对的Binance现货订单簿，记录买卖价差和中间价附近的挂单深度
+pub struct LiquidityTask {
+    /// 任务名称
+    name: String,
+    /// 交易所行情客户端
+    client: Arc<ExchangeTickerClient>,
+    /// 交易对
+    symbols: Vec<String>,
+    /// 统计深度的价格范围（距中间价的百分比）
+    depth_pct: f64,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl LiquidityTask {
+    /// 创建新的订单簿流动性快照任务
+    ///
+    /// # 参数
+    /// * `name` - 任务名称
+    /// * `client` - 交易所行情客户端
+    /// * `symbols` - 交易对
+    /// * `depth_pct` - 统计深度的价格范围（百分比）
+    /// * `interval_seconds` - 执行间隔
+    pub fn new(name: String, client: Arc<ExchangeTickerClient>, symbols: Vec<String>, depth_pct: f64, interval_seconds: u64) -> Self {
+        info!("🚀 创建订单簿流动性快照任务: {}（{} 个交易对）", name, symbols.len());
+        Self {
+            name,
+            client,
+            symbols,
+            depth_pct,
+            interval_seconds,
+        }
+    }
+
+    /// 将流动性快照转换为指标
+    fn to_metrics(snapshot: &LiquiditySnapshot) -> Result<Vec<AggregatedMetric>> {
+        [
+            ("spread_bps", MetricValue::scalar(snapshot.spread_bps, "bps")),
+            ("bid_depth", MetricValue::scalar(snapshot.bid_depth, "quote")),
+            ("ask_depth", MetricValue::scalar(snapshot.ask_depth, "quote")),
+        ]
+        .into_iter()
+        .map(|(field, value)| {
+            MetricBuilder::new(ExchangeTickerClient::SOURCE, liquidity_metric_name(field, &snapshot.symbol))
+                .value(value)
+                .timestamp(snapshot.timestamp)
+                .metadata(serde_json::json!({
+                    "symbol": snapshot.symbol,
+                    "depth_pct": snapshot.depth_pct,
+                    "mid_price": snapshot.mid_price,
+                    "truncated": snapshot.truncated,
+                }))
+                .label(LABEL_EXCHANGE, ExchangeTickerClient::SOURCE.name)
+                .build()
+        })
+        .collect()
+    }
+}
+
+#[async_trait]
+impl Task for LiquidityTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "记录交易对的买卖价差和中间价附近的订单簿深度"
+    }
+
+    fn id(&self) -> &str {
+        "liquidity"
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let now = Utc::now();
+        let mut snapshots = Vec::new();
+        let mut metrics = Vec::new();
+
+        for symbol in &self.symbols {
+            let book = match self.client.get_order_book(&symbol.to_uppercase(), ORDER_BOOK_LIMIT).await {
+                Ok(book) => book,
+                Err(e) => {
+                    warn!("⚠️ 获取 {} 订单簿失败: {}", symbol, e);
+                    continue;
+                }
+            };
+            let Some(snapshot) = summarize_order_book(symbol, &book, self.depth_pct, now) else {
+                warn!("⚠️ {} 订单簿为空或价格无效，跳过", symbol);
+                continue;
+            };
+            info!(
+                "💧 {} 价差 {:.2} bps，±{}% 深度：买 {:.0} / 卖 {:.0}",
+                snapshot.symbol, snapshot.spread_bps, self.depth_pct, snapshot.bid_depth, snapshot.ask_depth
+            );
+            metrics.extend(Self::to_metrics(&snapshot)?);
+            snapshots.push(snapshot);
+        }
+
+        if snapshots.is_empty() {
+            return Err(anyhow!("未获取到任何交易对的订单簿"));
+        }
+        cache.set_dataset(LIQUIDITY_KEY, &snapshots).await?;
+
+        Ok(metrics)
+    }
+}
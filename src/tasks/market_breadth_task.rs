@@ -0,0 +1,100 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::analytics::{compute_breadth, BreadthInput, MarketBreadth};
+use crate::history::PriceHistory;
+use crate::models::{AggregatedMetric, DataSource, MetricBuilder, MetricValue};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, MARKET_BREADTH_KEY};
+
+/// 市场广度任务
+/// 
+/// 基于缓存中的监控币种和日线价格历史计算涨跌家数、均线上方占比和30日新高新低，
+/// 应注册在行情采集任务之后
+pub struct MarketBreadthTask {
+    /// 任务名称
+    name: String,
+    /// 日线价格历史
+    prices: Arc<PriceHistory>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl MarketBreadthTask {
+    /// 创建新的市场广度任务
+    pub fn new(name: String, prices: Arc<PriceHistory>, interval_seconds: u64) -> Self {
+        info!("🚀 创建市场广度任务: {}", name);
+        Self {
+            name,
+            prices,
+            interval_seconds,
+        }
+    }
+
+    /// 将广度统计转换为指标
+    fn to_metrics(breadth: &MarketBreadth) -> Result<Vec<AggregatedMetric>> {
+        let metric = |name: &str, value: MetricValue| {
+            MetricBuilder::new(DataSource::EVERSCAN, name)
+                .value(value)
+                .timestamp(breadth.timestamp)
+                .metadata(serde_json::json!({ "universe": breadth.universe }))
+                .build()
+        };
+        
+        let values = [
+            ("breadth_advancers", "count", Some(breadth.advancers as f64)),
+            ("breadth_decliners", "count", Some(breadth.decliners as f64)),
+            ("breadth_pct_above_sma50", "pct", breadth.above_sma50.percent),
+            ("breadth_pct_above_sma200", "pct", breadth.above_sma200.percent),
+            ("breadth_new_highs_30d", "count", Some(breadth.new_highs_30d.count as f64)),
+            ("breadth_new_lows_30d", "count", Some(breadth.new_lows_30d.count as f64)),
+        ];
+        values.into_iter()
+            .filter_map(|(name, unit, value)| Some(metric(name, MetricValue::scalar(value?, unit))))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Task for MarketBreadthTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn description(&self) -> &str {
+        "统计监控币种的涨跌家数、50/200日均线上方占比和30日新高新低"
+    }
+    
+    fn id(&self) -> &str {
+        "market_breadth"
+    }
+    
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+    
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let now = Utc::now();
+        let today = now.date_naive();
+        
+        let mut inputs = Vec::new();
+        // 已停止更新的币种价格不再变化，会拉低广度统计
+        for data in cache.get_all_market_data().await.into_iter().filter(|d| d.inactive.is_none()) {
+            inputs.push(BreadthInput {
+                closes: self.prices.closes_before(&data.coin_id, today).await,
+                coin_id: data.coin_id,
+                price: data.current_price,
+                change_24h: data.price_change_24h,
+            });
+        }
+        
+        let breadth = compute_breadth(&inputs, now);
+        info!("📊 市场广度: {} 涨 / {} 跌，共 {} 个币种", breadth.advancers, breadth.decliners, breadth.universe);
+        cache.set_dataset(MARKET_BREADTH_KEY, &breadth).await?;
+        
+        Self::to_metrics(&breadth)
+    }
+}
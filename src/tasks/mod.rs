@@ -1,22 +1,73 @@
 pub mod task_manager;
+pub mod job_queue;
 pub mod crypto_market_task;
 pub mod fear_greed_task;
 pub mod altcoin_season_task;
+pub mod exchange_sync_task;
+pub mod portfolio_valuation_task;
+pub mod market_breadth_task;
+pub mod sector_task;
+pub mod ratio_task;
+pub mod btc_cycle_task;
+pub mod exchange_flow_task;
+pub mod options_task;
+pub mod etf_flow_task;
+pub mod liquidation_task;
+pub mod liquidity_task;
+pub mod calendar_task;
+pub mod social_sentiment_task;
+pub mod google_trends_task;
+pub mod new_listing_task;
+pub mod nft_task;
+pub mod network_task;
+pub mod node_health_task;
+pub mod derived_metrics;
+pub mod operational_metrics_task;
+pub mod formula_trial_task;
+#[cfg(feature = "wasm")]
+pub mod custom_source_task;
 
-pub use task_manager::*;
+pub use job_queue::*;
 pub use crypto_market_task::*;
 pub use fear_greed_task::*;
 pub use altcoin_season_task::*;
+pub use exchange_sync_task::*;
+pub use portfolio_valuation_task::*;
+pub use market_breadth_task::*;
+pub use sector_task::*;
+pub use ratio_task::*;
+pub use btc_cycle_task::*;
+pub use exchange_flow_task::*;
+pub use options_task::*;
+pub use etf_flow_task::*;
+pub use liquidation_task::*;
+pub use liquidity_task::*;
+pub use calendar_task::*;
+pub use social_sentiment_task::*;
+pub use google_trends_task::*;
+pub use new_listing_task::*;
+pub use nft_task::*;
+pub use network_task::*;
+pub use node_health_task::*;
+pub use derived_metrics::*;
+pub use operational_metrics_task::*;
+pub use formula_trial_task::*;
+#[cfg(feature = "wasm")]
+pub use custom_source_task::*;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use futures_util::FutureExt;
+use std::any::Any;
 use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{info, error, debug};
+use tracing::{info, error};
 
 use crate::models::AggregatedMetric;
+use crate::storage::MetricStore;
 use crate::web::cache::DataCache;
 
 /// 任务执行特征
@@ -74,8 +125,10 @@ impl std::fmt::Display for TaskStatus {
 }
 
 /// 任务执行结果
-#[derive(Debug, Clone)] // 添加Clone trait
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct TaskExecutionResult {
+    /// 任务ID
+    pub task_id: String,
     /// 任务名称
     pub task_name: String,
     /// 是否成功
@@ -90,6 +143,47 @@ pub struct TaskExecutionResult {
     pub executed_at: DateTime<Utc>,
 }
 
+/// 任务健康状况
+/// 
+/// 由执行历史汇总而来，供存活监控等模块使用
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TaskHealth {
+    /// 任务ID
+    pub task_id: String,
+    /// 任务名称
+    pub task_name: String,
+    /// 执行间隔（秒）
+    pub interval_seconds: u64,
+    /// 最后一次成功执行时间
+    pub last_success: Option<DateTime<Utc>>,
+    /// 最后一次执行时间
+    pub last_run: Option<DateTime<Utc>>,
+    /// 最后一次错误信息
+    pub last_error: Option<String>,
+}
+
+/// 一段时间内的任务执行统计
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TaskRunStats {
+    /// 任务ID
+    pub task_id: String,
+    /// 执行次数
+    pub runs: usize,
+    /// 成功次数
+    pub successes: usize,
+    /// 平均执行耗时（毫秒）
+    pub avg_execution_ms: f64,
+    /// 最长执行耗时（毫秒）
+    pub max_execution_ms: u128,
+}
+
+impl TaskRunStats {
+    /// 成功率（百分比）
+    pub fn success_rate(&self) -> f64 {
+        self.successes as f64 / self.runs as f64 * 100.0
+    }
+}
+
 /// 任务管理器
 /// 
 /// 负责管理和调度所有数据收集任务
@@ -99,6 +193,10 @@ pub struct TaskManager {
     tasks: Arc<RwLock<Vec<Box<dyn Task>>>>,
     /// 任务执行历史
     execution_history: Arc<RwLock<HashMap<String, Vec<TaskExecutionResult>>>>,
+    /// 指标存储（可选，配置后任务产出的指标会被持久化）
+    metric_store: Option<Arc<MetricStore>>,
+    /// 派生指标引擎（可选，配置后每次任务执行成功时重新计算派生指标）
+    derived_metrics: Option<Arc<DerivedMetricEngine>>,
 }
 
 impl TaskManager {
@@ -107,9 +205,29 @@ impl TaskManager {
         Self {
             tasks: Arc::new(RwLock::new(Vec::new())),
             execution_history: Arc::new(RwLock::new(HashMap::new())),
+            metric_store: None,
+            derived_metrics: None,
         }
     }
     
+    /// 设置指标存储
+    /// 
+    /// # 参数
+    /// * `store` - 指标存储，任务成功执行后保存其产出的指标
+    pub fn with_metric_store(mut self, store: Arc<MetricStore>) -> Self {
+        self.metric_store = Some(store);
+        self
+    }
+
+    /// 设置派生指标引擎
+    /// 
+    /// # 参数
+    /// * `engine` - 派生指标引擎，任务成功执行后重新计算输入有变化的派生指标
+    pub fn with_derived_metrics(mut self, engine: Arc<DerivedMetricEngine>) -> Self {
+        self.derived_metrics = Some(engine);
+        self
+    }
+    
     /// 注册任务
     /// 
     /// # 参数
@@ -138,35 +256,6 @@ impl TaskManager {
         Ok(())
     }
     
-    /// 启动任务管理器
-    /// 
-    /// # 参数
-    /// * `cache` - 数据缓存
-    /// 
-    /// # 返回
-    /// * `Result<()>` - 成功或错误
-    pub async fn start(&mut self, cache: Arc<DataCache>) -> Result<()> {
-        info!("🚀 启动任务管理器");
-        
-        // 立即执行一次所有任务以获取初始数据
-        info!("🔄 启动时执行所有任务，获取初始数据...");
-        if let Err(e) = self.check_and_execute_tasks(&cache).await {
-            error!("❌ 初始任务执行失败: {}", e);
-        }
-        
-        // 启动任务调度循环
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600)); // 每小时检查一次
-        
-        loop {
-            interval.tick().await;
-            
-            // 检查并执行到期的任务
-            if let Err(e) = self.check_and_execute_tasks(&cache).await {
-                error!("❌ 任务执行检查失败: {}", e);
-            }
-        }
-    }
-    
     /// 停止任务管理器
     /// 
     /// # 返回
@@ -177,83 +266,120 @@ impl TaskManager {
         Ok(())
     }
     
-    /// 检查并执行到期的任务
+    /// 执行所有任务
     /// 
     /// # 参数
     /// * `cache` - 数据缓存
     /// 
     /// # 返回
-    /// * `Result<()>` - 成功或错误
-    async fn check_and_execute_tasks(&self, cache: &DataCache) -> Result<()> {
-        debug!("🔍 检查待执行任务");
-        
-        let results = self.execute_all(cache).await?;
+    /// * `Result<Vec<TaskExecutionResult>>` - 执行结果列表
+    pub async fn execute_all(&self, cache: &DataCache) -> Result<Vec<TaskExecutionResult>> {
+        let mut results = Vec::new();
         
-        // 记录执行结果
-        for result in results {
-            if result.success {
-                info!("✅ 任务 {} 执行成功，获取 {} 条数据，耗时 {}ms", 
-                      result.task_name, result.metrics_count, result.execution_time_ms);
-            } else {
-                error!("❌ 任务 {} 执行失败: {}", 
-                      result.task_name, result.error.unwrap_or_else(|| "未知错误".to_string()));
-            }
+        // 获取所有任务并执行
+        let tasks = self.tasks.read().await;
+        for task in tasks.iter() {
+            results.push(self.run_task(task.as_ref(), cache).await);
         }
         
-        Ok(())
+        Ok(results)
     }
     
-    /// 执行所有任务
+    /// 立即执行单个任务
     /// 
     /// # 参数
+    /// * `task_id` - 任务ID
     /// * `cache` - 数据缓存
     /// 
     /// # 返回
-    /// * `Result<Vec<TaskExecutionResult>>` - 执行结果列表
-    pub async fn execute_all(&self, cache: &DataCache) -> Result<Vec<TaskExecutionResult>> {
-        let mut results = Vec::new();
+    /// * `Result<TaskExecutionResult>` - 执行结果，任务不存在时返回错误
+    pub async fn execute_task(&self, task_id: &str, cache: &DataCache) -> Result<TaskExecutionResult> {
+        let tasks = self.tasks.read().await;
+        let task = tasks.iter()
+            .find(|t| t.id() == task_id)
+            .ok_or_else(|| anyhow::anyhow!("任务 '{}' 不存在", task_id))?;
         
-        // 获取所有任务并执行
+        info!("▶️ 手动执行任务: {}", task.name());
+        Ok(self.run_task(task.as_ref(), cache).await)
+    }
+    
+    /// 是否注册了指定ID的任务
+    pub async fn has_task(&self, task_id: &str) -> bool {
+        self.tasks.read().await.iter().any(|t| t.id() == task_id)
+    }
+    
+    /// 获取到期需要执行的任务
+    /// 
+    /// 从未执行过或距上次执行已超过执行间隔的任务视为到期
+    /// 
+    /// # 参数
+    /// * `now` - 当前时间
+    /// 
+    /// # 返回
+    /// * `Vec<String>` - 到期任务的ID
+    pub async fn due_tasks(&self, now: DateTime<Utc>) -> Vec<String> {
         let tasks = self.tasks.read().await;
-        for task in tasks.iter() {
-            let start_time = std::time::Instant::now();
-            let task_name = task.name().to_string();
-            
-            let result = match task.execute(cache).await {
-                    Ok(metrics) => {
-                        let execution_time = start_time.elapsed();
-                        TaskExecutionResult {
-                            task_name: task_name.clone(),
-                            success: true,
-                            error: None,
-                            metrics_count: metrics.len(),
-                            execution_time_ms: execution_time.as_millis(),
-                            executed_at: Utc::now(),
-                        }
-                    }
-                    Err(e) => {
-                        let execution_time = start_time.elapsed();
-                        TaskExecutionResult {
-                            task_name: task_name.clone(),
-                            success: false,
-                            error: Some(e.to_string()),
-                            metrics_count: 0,
-                            execution_time_ms: execution_time.as_millis(),
-                            executed_at: Utc::now(),
-                        }
-                    }
-                };
-                
-            // 保存执行历史
-            {
-                let mut history = self.execution_history.write().await;
-                history.entry(task_name).or_insert_with(Vec::new).push(result.clone());
-            }
-            
-            results.push(result);
+        let history = self.execution_history.read().await;
+        tasks.iter()
+            .filter(|task| {
+                history.get(task.name())
+                    .and_then(|runs| runs.last())
+                    .is_none_or(|last| now - last.executed_at >= chrono::Duration::seconds(task.interval_seconds() as i64))
+            })
+            .map(|task| task.id().to_string())
+            .collect()
+    }
+    
+    /// 执行任务并记录执行历史
+    /// 
+    /// 任务内部的panic（如对上游数据 `unwrap`）被捕获并记为本次执行失败，不会终止工作者或调度器
+    async fn run_task(&self, task: &dyn Task, cache: &DataCache) -> TaskExecutionResult {
+        let start_time = std::time::Instant::now();
+        let task_name = task.name().to_string();
+        
+        let outcome = AssertUnwindSafe(self.execute_and_store(task, cache))
+            .catch_unwind()
+            .await
+            .unwrap_or_else(|panic| {
+                let message = panic_message(panic.as_ref());
+                error!("💥 任务 {} 执行时发生panic: {}", task_name, message);
+                Err(anyhow::anyhow!("任务执行时发生panic: {}", message))
+            });
+        let result = TaskExecutionResult {
+            task_id: task.id().to_string(),
+            task_name: task_name.clone(),
+            success: outcome.is_ok(),
+            error: outcome.as_ref().err().map(|e| e.to_string()),
+            metrics_count: outcome.unwrap_or(0),
+            execution_time_ms: start_time.elapsed().as_millis(),
+            executed_at: Utc::now(),
+        };
+        
+        // 保存执行历史
+        {
+            let mut history = self.execution_history.write().await;
+            history.entry(task_name).or_insert_with(Vec::new).push(result.clone());
         }
         
-        Ok(results)
+        result
+    }
+
+    /// 执行任务，保存产出的指标并重新计算派生指标
+    /// 
+    /// # 返回
+    /// * `Result<usize>` - 产出的指标数量
+    async fn execute_and_store(&self, task: &dyn Task, cache: &DataCache) -> Result<usize> {
+        // 一次任务写入的全部数据集合并为一个缓存更新事件
+        let metrics = cache.batched(task.execute(cache)).await?;
+        if let Some(store) = &self.metric_store {
+            if let Err(e) = store.save_metrics(&metrics).await {
+                error!("❌ 保存任务 {} 的指标失败: {}", task.name(), e);
+            }
+        }
+        if let Some(engine) = &self.derived_metrics {
+            engine.recompute(cache).await;
+        }
+        Ok(metrics.len())
     }
     
     /// 获取任务列表
@@ -262,6 +388,59 @@ impl TaskManager {
         tasks.iter().map(|task| task.name().to_string()).collect()
     }
     
+    /// 获取所有任务的健康状况
+    /// 
+    /// # 返回
+    /// * `Vec<TaskHealth>` - 每个任务的最后成功/执行时间
+    pub async fn get_task_health(&self) -> Vec<TaskHealth> {
+        let tasks = self.tasks.read().await;
+        let history = self.execution_history.read().await;
+        
+        tasks.iter().map(|task| {
+            let runs = history.get(task.name());
+            let last_run = runs.and_then(|runs| runs.last());
+            TaskHealth {
+                task_id: task.id().to_string(),
+                task_name: task.name().to_string(),
+                interval_seconds: task.interval_seconds(),
+                last_success: runs
+                    .and_then(|runs| runs.iter().rev().find(|r| r.success))
+                    .map(|r| r.executed_at),
+                last_run: last_run.map(|r| r.executed_at),
+                last_error: last_run.and_then(|r| r.error.clone()),
+            }
+        }).collect()
+    }
+    
+    /// 统计指定时间之后（不含）的任务执行情况
+    /// 
+    /// 只读取执行历史，可以在任务执行期间调用
+    /// 
+    /// # 参数
+    /// * `since` - 起始时间（不含）
+    /// 
+    /// # 返回
+    /// * `Vec<TaskRunStats>` - 期间执行过的任务的统计，按任务ID排序
+    pub async fn run_stats_since(&self, since: DateTime<Utc>) -> Vec<TaskRunStats> {
+        let history = self.execution_history.read().await;
+        let mut stats: Vec<TaskRunStats> = history.values()
+            .filter_map(|runs| {
+                let recent: Vec<&TaskExecutionResult> = runs.iter().filter(|r| r.executed_at > since).collect();
+                let last = recent.last()?;
+                let total_ms: u128 = recent.iter().map(|r| r.execution_time_ms).sum();
+                Some(TaskRunStats {
+                    task_id: last.task_id.clone(),
+                    runs: recent.len(),
+                    successes: recent.iter().filter(|r| r.success).count(),
+                    avg_execution_ms: total_ms as f64 / recent.len() as f64,
+                    max_execution_ms: recent.iter().map(|r| r.execution_time_ms).max().unwrap_or_default(),
+                })
+            })
+            .collect();
+        stats.sort_by(|a, b| a.task_id.cmp(&b.task_id));
+        stats
+    }
+    
     /// 获取任务状态
     pub async fn get_task_status(&self) -> Vec<(String, String)> {
         let tasks = self.tasks.read().await;
@@ -269,4 +448,17 @@ impl TaskManager {
             (task.name().to_string(), "运行中".to_string())
         }).collect()
     }
+}
+
+/// 取出panic携带的信息
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "未知panic".to_string())
+}
+
+impl Default for TaskManager {
+    fn default() -> Self {
+        Self::new()
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,159 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::clients::{ChainClient, MiningStats, StakingClient, StakingYield};
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, NETWORK_KEY};
+
+/// 单条链的网络健康快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkSnapshot {
+    /// 链标识（btc、eth、sol）
+    pub chain: String,
+    /// 采集时间
+    pub timestamp: DateTime<Utc>,
+    /// 挖矿概况（工作量证明链）
+    pub mining: Option<MiningStats>,
+    /// 质押收益率（权益证明链）
+    pub staking: Option<StakingYield>,
+}
+
+/// 网络健康指标名称
+///
+/// # 参数
+/// * `field` - 字段（`hashrate`、`difficulty`、`difficulty_change`、`staking_apr`、`staking_ratio`）
+/// * `chain` - 链标识
+pub fn network_metric_name(field: &str, chain: &str) -> String {
+    format!("network_{}_{}", field, chain.to_lowercase())
+}
+
+/// 网络健康采集任务
+///
+/// 采集比特币全网算力和难度，以及ETH、SOL的质押收益率
+pub struct NetworkTask {
+    /// 任务名称
+    name: String,
+    /// 比特币链上数据客户端
+    chain: Arc<ChainClient>,
+    /// 质押收益率客户端
+    staking: Arc<StakingClient>,
+    /// 是否采集SOL质押收益率
+    include_solana: bool,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl NetworkTask {
+    /// 创建新的网络健康采集任务
+    ///
+    /// # 参数
+    /// * `name` - 任务名称
+    /// * `chain` - 比特币链上数据客户端
+    /// * `staking` - 质押收益率客户端
+    /// * `include_solana` - 是否采集SOL质押收益率
+    /// * `interval_seconds` - 执行间隔
+    pub fn new(name: String, chain: Arc<ChainClient>, staking: Arc<StakingClient>, include_solana: bool, interval_seconds: u64) -> Self {
+        info!("🚀 创建网络健康采集任务: {}", name);
+        Self {
+            name,
+            chain,
+            staking,
+            include_solana,
+            interval_seconds,
+        }
+    }
+
+    /// 将网络快照转换为指标
+    fn to_metrics(snapshot: &NetworkSnapshot) -> Result<Vec<AggregatedMetric>> {
+        let mining = snapshot.mining.as_ref();
+        let staking = snapshot.staking.as_ref();
+        let source = if mining.is_some() { ChainClient::SOURCE } else { StakingClient::SOURCE };
+        let values: [(&str, Option<MetricValue>); 5] = [
+            ("hashrate", mining.map(|m| MetricValue::scalar(m.hashrate / 1e18, "EH/s"))),
+            ("difficulty", mining.map(|m| MetricValue::scalar(m.difficulty, "difficulty"))),
+            ("difficulty_change", mining.and_then(|m| m.next_difficulty_change_pct).map(|v| MetricValue::scalar(v, "%"))),
+            ("staking_apr", staking.map(|s| MetricValue::scalar(s.apr_pct, "%"))),
+            ("staking_ratio", staking.and_then(|s| s.staked_ratio_pct).map(|v| MetricValue::scalar(v, "%"))),
+        ];
+        values
+            .into_iter()
+            .filter_map(|(field, value)| Some((field, value?)))
+            .map(|(field, value)| {
+                MetricBuilder::new(source, network_metric_name(field, &snapshot.chain))
+                    .value(value)
+                    .timestamp(snapshot.timestamp)
+                    .metadata(serde_json::json!({ "chain": snapshot.chain }))
+                    .build()
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Task for NetworkTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "采集比特币算力、难度以及ETH、SOL质押收益率"
+    }
+
+    fn id(&self) -> &str {
+        "network"
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let now = Utc::now();
+        let mut snapshots = Vec::new();
+
+        match self.chain.mining_stats().await {
+            Ok(mining) => {
+                info!(
+                    "⛏️ 比特币算力 {:.1} EH/s，难度 {:.3e}，预计下次调整 {:?}%",
+                    mining.hashrate / 1e18, mining.difficulty, mining.next_difficulty_change_pct
+                );
+                snapshots.push(NetworkSnapshot { chain: "btc".to_string(), timestamp: now, mining: Some(mining), staking: None });
+            }
+            Err(e) => warn!("⚠️ 获取比特币算力和难度失败: {}", e),
+        }
+
+        match self.staking.get_eth_staking_yield().await {
+            Ok(staking) => {
+                info!("🥩 ETH质押年化收益率 {:.2}%", staking.apr_pct);
+                snapshots.push(NetworkSnapshot { chain: "eth".to_string(), timestamp: now, mining: None, staking: Some(staking) });
+            }
+            Err(e) => warn!("⚠️ 获取ETH质押收益率失败: {}", e),
+        }
+
+        if self.include_solana {
+            match self.staking.get_sol_staking_yield().await {
+                Ok(staking) => {
+                    info!("🥩 SOL质押年化收益率 {:.2}%，质押比例 {:?}%", staking.apr_pct, staking.staked_ratio_pct);
+                    snapshots.push(NetworkSnapshot { chain: "sol".to_string(), timestamp: now, mining: None, staking: Some(staking) });
+                }
+                Err(e) => warn!("⚠️ 获取SOL质押收益率失败: {}", e),
+            }
+        }
+
+        if snapshots.is_empty() {
+            return Err(anyhow!("未获取到任何网络健康数据"));
+        }
+        let mut metrics = Vec::new();
+        for snapshot in &snapshots {
+            metrics.extend(Self::to_metrics(snapshot)?);
+        }
+        cache.set_dataset(NETWORK_KEY, &snapshots).await?;
+
+        Ok(metrics)
+    }
+}
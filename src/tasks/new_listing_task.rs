@@ -0,0 +1,154 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::clients::{CoinGeckoClient, CoinMarketCapClient, ListedCoin};
+use crate::history::{ChangeEvent, ChangeHistory};
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, NEW_LISTINGS_KEY};
+
+/// 新上线币种检测任务
+///
+/// 定期获取各数据源收录的币种，与已知的币种ID比较，新出现的ID记录为 `new_listing` 事件，
+/// 本次检测到的事件写入数据集 `new_listings`，由历史记录器转发为告警
+pub struct NewListingTask {
+    /// 任务名称
+    name: String,
+    /// CoinGecko客户端（可选）
+    coingecko: Option<Arc<CoinGeckoClient>>,
+    /// CoinMarketCap客户端（可选）
+    coinmarketcap: Option<Arc<CoinMarketCapClient>>,
+    /// 数据集变化历史
+    changes: Arc<ChangeHistory>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl NewListingTask {
+    /// 创建新的新上线币种检测任务
+    ///
+    /// # 参数
+    /// * `name` - 任务名称
+    /// * `coingecko` - CoinGecko客户端（为None时不检测CoinGecko）
+    /// * `coinmarketcap` - CoinMarketCap客户端（为None时不检测CoinMarketCap）
+    /// * `changes` - 数据集变化历史
+    /// * `interval_seconds` - 执行间隔
+    pub fn new(
+        name: String,
+        coingecko: Option<Arc<CoinGeckoClient>>,
+        coinmarketcap: Option<Arc<CoinMarketCapClient>>,
+        changes: Arc<ChangeHistory>,
+        interval_seconds: u64,
+    ) -> Self {
+        info!("🚀 创建新上线币种检测任务: {}", name);
+        Self {
+            name,
+            coingecko,
+            coinmarketcap,
+            changes,
+            interval_seconds,
+        }
+    }
+
+    /// 获取CoinGecko币种列表，并为新币种补充首次价格和市值
+    async fn coingecko_coins(&self, client: &CoinGeckoClient) -> Result<Vec<ListedCoin>> {
+        let mut coins = client.get_coin_list().await?;
+        let ids: Vec<&String> = coins.iter().map(|c| &c.id).collect();
+        let Some(mut unknown) = self.changes.unknown_ids(CoinGeckoClient::SOURCE.name, ids).await else {
+            return Ok(coins);
+        };
+        let limit = self.changes.config().max_new_listing_quotes;
+        if unknown.len() > limit {
+            warn!("⚠️ CoinGecko新币种 {} 个，只查询前 {} 个的首次价格", unknown.len(), limit);
+            unknown.truncate(limit);
+        }
+        if unknown.is_empty() {
+            return Ok(coins);
+        }
+        match client.get_usd_quotes(&unknown).await {
+            Ok(quotes) => {
+                for coin in coins.iter_mut() {
+                    if let Some((price, market_cap)) = quotes.get(&coin.id) {
+                        coin.price = Some(*price);
+                        coin.market_cap = *market_cap;
+                    }
+                }
+            }
+            Err(e) => warn!("⚠️ 查询CoinGecko新币种价格失败: {}", e),
+        }
+        Ok(coins)
+    }
+}
+
+#[async_trait]
+impl Task for NewListingTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "跟踪CoinGecko和CoinMarketCap收录的币种ID，发现新上线的币种"
+    }
+
+    fn id(&self) -> &str {
+        "new_listings"
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let now = Utc::now();
+        let mut events: Vec<ChangeEvent> = Vec::new();
+        let mut metrics = Vec::new();
+        let mut errors = Vec::new();
+
+        if let Some(client) = &self.coingecko {
+            match self.coingecko_coins(client).await {
+                Ok(coins) => {
+                    let found = self.changes.record_coin_ids(CoinGeckoClient::SOURCE.name, &coins, now).await?;
+                    metrics.push(
+                        MetricBuilder::new(CoinGeckoClient::SOURCE, "new_listings")
+                            .value(MetricValue::scalar(found.len() as f64, "count"))
+                            .timestamp(now)
+                            .build()?,
+                    );
+                    events.extend(found);
+                }
+                Err(e) => errors.push(format!("CoinGecko: {}", e)),
+            }
+        }
+        if let Some(client) = &self.coinmarketcap {
+            match client.get_recently_added(self.changes.config().cmc_recent_limit).await {
+                Ok(coins) => {
+                    let found = self.changes.record_coin_ids(CoinMarketCapClient::SOURCE.name, &coins, now).await?;
+                    metrics.push(
+                        MetricBuilder::new(CoinMarketCapClient::SOURCE, "new_listings")
+                            .value(MetricValue::scalar(found.len() as f64, "count"))
+                            .timestamp(now)
+                            .build()?,
+                    );
+                    events.extend(found);
+                }
+                Err(e) => errors.push(format!("CoinMarketCap: {}", e)),
+            }
+        }
+
+        if metrics.is_empty() && !errors.is_empty() {
+            let error = anyhow!("获取币种列表失败: {}", errors.join("; "));
+            cache.record_failure(NEW_LISTINGS_KEY, &error).await;
+            return Err(error);
+        }
+        for error in &errors {
+            warn!("⚠️ 新上线币种检测部分失败: {}", error);
+        }
+
+        info!("🆕 新上线币种检测完成，发现 {} 个新币种", events.len());
+        cache.set_dataset(NEW_LISTINGS_KEY, &events).await?;
+        Ok(metrics)
+    }
+}
@@ -0,0 +1,159 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::clients::{NftCollectionStats, OpenSeaClient};
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, NFT_COLLECTIONS_KEY};
+
+/// NFT合集的最新统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftCollectionSnapshot {
+    /// 合集标识
+    pub slug: String,
+    /// 采集时间
+    pub timestamp: DateTime<Utc>,
+    /// 合集统计（计价币种）
+    #[serde(flatten)]
+    pub stats: NftCollectionStats,
+    /// 地板价（美元，计价币种价格不在缓存中时为None）
+    pub floor_price_usd: Option<f64>,
+    /// 24小时成交额（美元）
+    pub volume_24h_usd: Option<f64>,
+}
+
+/// NFT指标名称
+///
+/// # 参数
+/// * `field` - 字段（`floor`、`floor_usd`、`volume_24h`）
+/// * `slug` - 合集标识
+pub fn nft_metric_name(field: &str, slug: &str) -> String {
+    format!("nft_{}_{}", field, slug.to_lowercase().replace('-', "_"))
+}
+
+/// NFT合集跟踪任务
+///
+/// 从OpenSea获取配置合集的地板价和成交额，按缓存中计价币种的价格折算为美元
+pub struct NftTask {
+    /// 任务名称
+    name: String,
+    /// OpenSea客户端
+    client: Arc<OpenSeaClient>,
+    /// 合集标识
+    collections: Vec<String>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl NftTask {
+    /// 创建新的NFT合集跟踪任务
+    ///
+    /// # 参数
+    /// * `name` - 任务名称
+    /// * `client` - OpenSea客户端
+    /// * `collections` - 合集标识
+    /// * `interval_seconds` - 执行间隔
+    pub fn new(name: String, client: Arc<OpenSeaClient>, collections: Vec<String>, interval_seconds: u64) -> Self {
+        info!("🚀 创建NFT合集跟踪任务: {}（{} 个合集）", name, collections.len());
+        Self {
+            name,
+            client,
+            collections,
+            interval_seconds,
+        }
+    }
+
+    /// 计价币种的美元价格（WETH按ETH计算）
+    async fn currency_price(cache: &DataCache, symbol: Option<&str>) -> Option<f64> {
+        let symbol = symbol?.to_lowercase();
+        let symbol = symbol.strip_prefix('w').filter(|s| *s == "eth").unwrap_or(&symbol);
+        cache.metric_value(&format!("price:{}", symbol)).await
+    }
+
+    /// 将合集统计转换为指标
+    fn to_metrics(snapshot: &NftCollectionSnapshot) -> Result<Vec<AggregatedMetric>> {
+        let currency = snapshot.stats.floor_price_symbol.as_deref().unwrap_or("eth").to_lowercase();
+        [
+            ("floor", snapshot.stats.floor_price.map(|v| MetricValue::scalar(v, &currency))),
+            ("floor_usd", snapshot.floor_price_usd.map(|v| MetricValue::scalar(v, "usd"))),
+            ("volume_24h", snapshot.stats.volume_24h.map(|v| MetricValue::scalar(v, &currency))),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| Some((field, value?)))
+        .map(|(field, value)| {
+            MetricBuilder::new(OpenSeaClient::SOURCE, nft_metric_name(field, &snapshot.slug))
+                .value(value)
+                .timestamp(snapshot.timestamp)
+                .metadata(serde_json::json!({
+                    "collection": snapshot.slug,
+                    "sales_24h": snapshot.stats.sales_24h,
+                    "num_owners": snapshot.stats.num_owners,
+                }))
+                .build()
+        })
+        .collect()
+    }
+}
+
+#[async_trait]
+impl Task for NftTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "从OpenSea采集NFT合集的地板价和成交额"
+    }
+
+    fn id(&self) -> &str {
+        "nft"
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let now = Utc::now();
+        let mut snapshots = Vec::new();
+        let mut metrics = Vec::new();
+
+        for slug in &self.collections {
+            let stats = match self.client.get_collection_stats(slug).await {
+                Ok(stats) => stats,
+                Err(e) => {
+                    warn!("⚠️ 获取NFT合集 {} 统计失败: {}", slug, e);
+                    continue;
+                }
+            };
+            let price = Self::currency_price(cache, stats.floor_price_symbol.as_deref()).await;
+            let snapshot = NftCollectionSnapshot {
+                slug: slug.to_lowercase(),
+                timestamp: now,
+                floor_price_usd: stats.floor_price.zip(price).map(|(floor, price)| floor * price),
+                volume_24h_usd: stats.volume_24h.zip(price).map(|(volume, price)| volume * price),
+                stats,
+            };
+            info!(
+                "🖼️ NFT合集 {}: 地板价 {:?} {}，24小时成交额 {:?}",
+                snapshot.slug,
+                snapshot.stats.floor_price,
+                snapshot.stats.floor_price_symbol.as_deref().unwrap_or(""),
+                snapshot.stats.volume_24h
+            );
+            metrics.extend(Self::to_metrics(&snapshot)?);
+            snapshots.push(snapshot);
+        }
+
+        if snapshots.is_empty() {
+            return Err(anyhow!("未获取到任何NFT合集的统计"));
+        }
+        cache.set_dataset(NFT_COLLECTIONS_KEY, &snapshots).await?;
+
+        Ok(metrics)
+    }
+}
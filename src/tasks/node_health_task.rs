@@ -0,0 +1,165 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::analytics::NodeStatus;
+use crate::clients::{NodeChain, NodeRpcClient};
+use crate::config::NodeEndpoint;
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, NODES_KEY};
+
+/// 节点指标名称
+///
+/// # 参数
+/// * `field` - 字段（`healthy`、`lag`、`peers`、`latency_ms`）
+/// * `node` - 节点名称
+pub fn node_metric_name(field: &str, node: &str) -> String {
+    format!("node_{}_{}", field, node.to_lowercase().replace('-', "_"))
+}
+
+/// 自建节点健康监控任务
+///
+/// 探测每个节点的高度、对等节点数和响应延迟，与同链节点及参考节点的最高高度比较得出落后区块数
+pub struct NodeHealthTask {
+    /// 任务名称
+    name: String,
+    /// 节点 JSON-RPC 客户端
+    client: Arc<NodeRpcClient>,
+    /// 节点列表
+    nodes: Vec<NodeEndpoint>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl NodeHealthTask {
+    /// 创建新的自建节点健康监控任务
+    ///
+    /// # 参数
+    /// * `name` - 任务名称
+    /// * `client` - 节点 JSON-RPC 客户端
+    /// * `nodes` - 节点列表
+    /// * `interval_seconds` - 执行间隔
+    pub fn new(name: String, client: Arc<NodeRpcClient>, nodes: Vec<NodeEndpoint>, interval_seconds: u64) -> Self {
+        info!("🚀 创建自建节点健康监控任务: {}（{} 个节点）", name, nodes.len());
+        Self {
+            name,
+            client,
+            nodes,
+            interval_seconds,
+        }
+    }
+
+    /// 将节点状态转换为指标
+    fn to_metrics(status: &NodeStatus) -> Result<Vec<AggregatedMetric>> {
+        [
+            ("healthy", Some(MetricValue::scalar(if status.healthy { 1.0 } else { 0.0 }, "bool"))),
+            ("lag", status.lag.map(|v| MetricValue::scalar(v as f64, "blocks"))),
+            ("peers", status.peers.map(|v| MetricValue::scalar(v as f64, "peers"))),
+            ("latency_ms", status.latency_ms.map(|v| MetricValue::scalar(v, "ms"))),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| Some((field, value?)))
+        .map(|(field, value)| {
+            MetricBuilder::new(NodeRpcClient::SOURCE, node_metric_name(field, &status.name))
+                .value(value)
+                .timestamp(status.timestamp)
+                .metadata(serde_json::json!({
+                    "node": status.name,
+                    "chain": status.chain,
+                    "height": status.height,
+                    "reference_height": status.reference_height,
+                }))
+                .build()
+        })
+        .collect()
+    }
+}
+
+#[async_trait]
+impl Task for NodeHealthTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "探测自建节点的区块高度落后、对等节点数和响应延迟"
+    }
+
+    fn id(&self) -> &str {
+        "nodes"
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let now = Utc::now();
+        let mut probes = Vec::new();
+        for node in &self.nodes {
+            let probe = self.client.probe(node.chain, &node.url).await;
+            if let Err(e) = &probe {
+                warn!("⚠️ 节点 {} 探测失败: {}", node.name, e);
+            }
+            probes.push(probe);
+        }
+
+        // 参考高度：同链节点和参考节点中的最高值
+        let mut reference_heights: HashMap<NodeChain, u64> = HashMap::new();
+        for (node, probe) in self.nodes.iter().zip(&probes) {
+            if let Ok(probe) = probe {
+                let height = reference_heights.entry(node.chain).or_default();
+                *height = (*height).max(probe.height);
+            }
+        }
+        let mut reference_urls: Vec<(NodeChain, &str)> = Vec::new();
+        for node in &self.nodes {
+            if let Some(url) = node.reference_url.as_deref().filter(|url| !url.trim().is_empty()) {
+                if !reference_urls.contains(&(node.chain, url)) {
+                    reference_urls.push((node.chain, url));
+                }
+            }
+        }
+        for (chain, url) in reference_urls {
+            match self.client.probe(chain, url).await {
+                Ok(probe) => {
+                    let height = reference_heights.entry(chain).or_default();
+                    *height = (*height).max(probe.height);
+                }
+                Err(e) => warn!("⚠️ 参考节点 {} 探测失败: {}", url, e),
+            }
+        }
+
+        let previous = cache.get_dataset::<Vec<NodeStatus>>(NODES_KEY).await.unwrap_or_default();
+        let mut statuses = Vec::new();
+        let mut metrics = Vec::new();
+        for (node, probe) in self.nodes.iter().zip(&probes) {
+            let status = NodeStatus::evaluate(
+                node,
+                probe,
+                reference_heights.get(&node.chain).copied(),
+                previous.iter().find(|p| p.name == node.name),
+                now,
+            );
+            if status.healthy {
+                info!("🖧 节点 {} 高度 {:?}，落后 {:?}，{:?} 个对等节点", status.name, status.height, status.lag, status.peers);
+            } else {
+                warn!("⚠️ 节点 {} 不健康: 高度 {:?}，参考高度 {:?}", status.name, status.height, status.reference_height);
+            }
+            metrics.extend(Self::to_metrics(&status)?);
+            statuses.push(status);
+        }
+
+        // 全部节点无法访问时也保存状态，以便触发告警
+        cache.set_dataset(NODES_KEY, &statuses).await?;
+        if statuses.iter().all(|s| !s.reachable) {
+            return Err(anyhow!("所有节点均无法访问"));
+        }
+
+        Ok(metrics)
+    }
+}
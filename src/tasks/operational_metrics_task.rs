@@ -0,0 +1,144 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Mutex;
+use tracing::info;
+
+use crate::models::{AggregatedMetric, DataSource, MetricBuilder, MetricValue};
+use crate::tasks::{Task, TaskManager};
+use crate::web::cache::DataCache;
+
+/// 上次记录时的状态
+#[derive(Debug, Clone, Copy)]
+struct Checkpoint {
+    /// 记录时间
+    at: DateTime<Utc>,
+    /// 累计缓存命中次数
+    hits: u64,
+    /// 累计缓存未命中次数
+    misses: u64,
+}
+
+/// 运行指标任务
+///
+/// 定期把缓存命中率、内存占用和各任务的成功率、执行耗时写入指标存储，
+/// 使everscan自身的运行历史可以和行情数据一样查询、导出和绘图。
+/// 命中率、成功率和耗时按两次记录之间的增量计算
+pub struct OperationalMetricsTask {
+    /// 任务名称
+    name: String,
+    /// 任务管理器（读取执行历史）
+    tasks: TaskManager,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+    /// 上次记录时的状态
+    checkpoint: Mutex<Option<Checkpoint>>,
+}
+
+impl OperationalMetricsTask {
+    /// 创建新的运行指标任务
+    pub fn new(name: String, tasks: TaskManager, interval_seconds: u64) -> Self {
+        info!("🚀 创建运行指标任务: {}", name);
+        Self {
+            name,
+            tasks,
+            interval_seconds,
+            checkpoint: Mutex::new(None),
+        }
+    }
+}
+
+/// 构建一个运行指标
+fn metric(name: &str, value: f64, unit: &str, timestamp: DateTime<Utc>) -> MetricBuilder {
+    MetricBuilder::new(DataSource::EVERSCAN, name)
+        .value(MetricValue::scalar(value, unit))
+        .timestamp(timestamp)
+}
+
+#[async_trait]
+impl Task for OperationalMetricsTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "记录缓存命中率、内存占用和各任务的成功率与执行耗时"
+    }
+
+    fn id(&self) -> &str {
+        "operational_metrics"
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let now = Utc::now();
+        let stats = cache.get_stats().await;
+        let current = Checkpoint { at: now, hits: stats.hits, misses: stats.misses };
+        let previous = self.checkpoint.lock().unwrap().replace(current);
+        // 首次执行统计启动以来的全部数据；缓存被清空后计数归零，同样从零开始
+        let previous = previous
+            .filter(|p| p.hits <= current.hits && p.misses <= current.misses)
+            .unwrap_or(Checkpoint { at: DateTime::<Utc>::MIN_UTC, hits: 0, misses: 0 });
+
+        let mut metrics = vec![
+            metric("everscan_cache_items", stats.total_items as f64, "count", now).build()?,
+            metric("everscan_cache_memory_bytes", stats.memory.approx_bytes as f64, "bytes", now).build()?,
+        ];
+        let lookups = (current.hits - previous.hits) + (current.misses - previous.misses);
+        if lookups > 0 {
+            let ratio = (current.hits - previous.hits) as f64 / lookups as f64 * 100.0;
+            metrics.push(metric("everscan_cache_hit_ratio", ratio, "pct", now).build()?);
+        }
+
+        let runs = self.tasks.run_stats_since(previous.at).await;
+        for run in &runs {
+            metrics.push(metric("everscan_task_success_rate", run.success_rate(), "pct", now).label("task", &run.task_id).build()?);
+            metrics.push(metric("everscan_task_latency_ms", run.avg_execution_ms, "ms", now).label("task", &run.task_id).build()?);
+            metrics.push(metric("everscan_task_latency_max_ms", run.max_execution_ms as f64, "ms", now).label("task", &run.task_id).build()?);
+        }
+        info!("📈 已记录运行指标：{} 次缓存查询，{} 个任务执行过", lookups, runs.len());
+        Ok(metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FailingTask;
+
+    #[async_trait]
+    impl Task for FailingTask {
+        fn name(&self) -> &str { "failing" }
+        fn description(&self) -> &str { "upstream is down" }
+        fn id(&self) -> &str { "failing" }
+        fn interval_seconds(&self) -> u64 { 60 }
+        async fn execute(&self, _cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+            anyhow::bail!("连接被拒绝")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_cache_and_task_kpis() {
+        let mut tasks = TaskManager::new();
+        tasks.register_task(Box::new(FailingTask)).await.unwrap();
+        let cache = DataCache::new();
+        tasks.execute_all(&cache).await.unwrap();
+        assert!(cache.get_market_data("bitcoin").await.is_none());
+
+        let task = OperationalMetricsTask::new("运行指标".to_string(), tasks.clone(), 300);
+        let metrics = task.execute(&cache).await.unwrap();
+        let value = |name: &str| metrics.iter().find(|m| m.metric_name == name).and_then(|m| m.value.as_f64());
+        assert_eq!(value("everscan_cache_hit_ratio"), Some(0.0));
+        assert_eq!(value("everscan_task_success_rate"), Some(0.0));
+        let success = metrics.iter().find(|m| m.metric_name == "everscan_task_success_rate").unwrap();
+        assert_eq!(success.labels.get("task").map(String::as_str), Some("failing"));
+
+        // 第二次只统计两次记录之间的增量
+        let metrics = task.execute(&cache).await.unwrap();
+        assert!(metrics.iter().all(|m| m.metric_name != "everscan_cache_hit_ratio" && m.metric_name != "everscan_task_success_rate"));
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::analytics::{summarize_options, OptionsData, OptionsSnapshot};
+use crate::clients::DeribitClient;
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue, LABEL_ASSET};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, OPTIONS_KEY};
+
+/// 采集期权数据的币种
+pub const OPTIONS_CURRENCIES: [&str; 2] = ["BTC", "ETH"];
+
+/// 期权市场数据任务
+/// 
+/// 从Deribit采集BTC/ETH的DVOL、平值隐含波动率和看跌/看涨比
+pub struct OptionsTask {
+    /// 任务名称
+    name: String,
+    /// Deribit客户端
+    deribit: Arc<DeribitClient>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl OptionsTask {
+    /// 创建新的期权数据任务
+    pub fn new(name: String, deribit: Arc<DeribitClient>, interval_seconds: u64) -> Self {
+        info!("🚀 创建期权数据任务: {}", name);
+        Self {
+            name,
+            deribit,
+            interval_seconds,
+        }
+    }
+
+    /// 将单个币种的期权概况转换为指标
+    fn to_metrics(snapshot: &OptionsSnapshot, timestamp: chrono::DateTime<Utc>) -> Result<Vec<AggregatedMetric>> {
+        let currency = snapshot.currency.to_lowercase();
+        let values = [
+            ("options_dvol", "index", snapshot.dvol),
+            ("options_atm_iv", "pct", snapshot.atm_iv),
+            ("options_put_call_oi", "ratio", snapshot.put_call_oi_ratio),
+            ("options_put_call_volume", "ratio", snapshot.put_call_volume_ratio),
+        ];
+        
+        values.into_iter()
+            .filter_map(|(name, unit, value)| Some((name, unit, value?)))
+            .map(|(name, unit, value)| {
+                MetricBuilder::new(DeribitClient::SOURCE, format!("{}_{}", name, currency))
+                    .value(MetricValue::scalar(value, unit))
+                    .timestamp(timestamp)
+                    .metadata(serde_json::json!({
+                        "currency": snapshot.currency,
+                        "atm_expiry": snapshot.atm_expiry,
+                    }))
+                    .label(LABEL_ASSET, currency.as_str())
+                    .build()
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Task for OptionsTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn description(&self) -> &str {
+        "采集BTC/ETH期权的DVOL、平值隐含波动率和看跌/看涨比"
+    }
+    
+    fn id(&self) -> &str {
+        "options"
+    }
+    
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+    
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let now = Utc::now();
+        let mut currencies = Vec::new();
+        let mut metrics = Vec::new();
+        
+        for currency in OPTIONS_CURRENCIES {
+            let summaries = match self.deribit.get_option_summaries(currency).await {
+                Ok(summaries) => summaries,
+                Err(e) => {
+                    warn!("⚠️ 获取 {} 期权行情失败: {}", currency, e);
+                    continue;
+                }
+            };
+            let dvol = match self.deribit.get_dvol(currency).await {
+                Ok(dvol) => Some(dvol),
+                Err(e) => {
+                    warn!("⚠️ 获取 {} DVOL失败: {}", currency, e);
+                    None
+                }
+            };
+            
+            let snapshot = summarize_options(currency, dvol, &summaries, now.date_naive());
+            info!(
+                "📉 {} 期权: DVOL {:?}，ATM IV {:?}，看跌/看涨比 {:?}",
+                snapshot.currency, snapshot.dvol, snapshot.atm_iv, snapshot.put_call_oi_ratio
+            );
+            metrics.extend(Self::to_metrics(&snapshot, now)?);
+            currencies.push(snapshot);
+        }
+        
+        if currencies.is_empty() {
+            return Err(anyhow!("未获取到任何币种的期权数据"));
+        }
+        cache.set_dataset(OPTIONS_KEY, &OptionsData { timestamp: now, currencies }).await?;
+        
+        Ok(metrics)
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::{info, debug};
+
+use crate::models::AggregatedMetric;
+use crate::portfolio::{PortfolioSnapshot, PortfolioStore};
+use crate::tasks::Task;
+use crate::web::cache::DataCache;
+
+/// 投资组合估值任务
+/// 
+/// 使用缓存中的最新价格为每个用户的组合估值并记录快照，
+/// 应注册在行情采集任务之后
+pub struct PortfolioValuationTask {
+    /// 任务名称
+    name: String,
+    /// 组合存储
+    portfolio: Arc<PortfolioStore>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl PortfolioValuationTask {
+    /// 创建新的投资组合估值任务
+    pub fn new(name: String, portfolio: Arc<PortfolioStore>, interval_seconds: u64) -> Self {
+        info!("🚀 创建投资组合估值任务: {}", name);
+        Self {
+            name,
+            portfolio,
+            interval_seconds,
+        }
+    }
+}
+
+#[async_trait]
+impl Task for PortfolioValuationTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn description(&self) -> &str {
+        "按最新价格计算用户持仓的市值与未实现盈亏，并记录估值快照"
+    }
+    
+    fn id(&self) -> &str {
+        "portfolio_valuation"
+    }
+    
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+    
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let owners = self.portfolio.owners().await;
+        for owner in &owners {
+            let valuation = self.portfolio.valuate(*owner, cache).await;
+            debug!("💼 用户 {} 组合市值 {:.2}，未实现盈亏 {:.2}", owner, valuation.total_value, valuation.unrealized_pnl);
+            self.portfolio.record_snapshot(*owner, PortfolioSnapshot::from(&valuation)).await;
+        }
+        
+        info!("💼 已完成 {} 个投资组合的估值", owners.len());
+        // 组合估值属于用户私有数据，不作为公共指标输出
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::analytics::RatioReading;
+use crate::config::RatioConfig;
+use crate::models::{AggregatedMetric, DataSource, MetricBuilder};
+use crate::storage::{MetricQuery, MetricStore};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, RATIOS_KEY};
+
+/// 价格比率任务
+///
+/// 每个周期由缓存价格（或指标存储中的指标）计算配置的比率（如ETH/BTC），
+/// 以 `ratio_<名称>` 写入指标存储作为历史，并判断是否突破回看区间的最高/最低值
+pub struct RatioTask {
+    /// 任务名称
+    name: String,
+    /// 比率定义
+    ratios: Vec<RatioConfig>,
+    /// 判断突破时回看的天数
+    lookback_days: u32,
+    /// 指标存储（读取比率历史和非缓存输入）
+    store: Arc<MetricStore>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl RatioTask {
+    /// 创建新的价格比率任务
+    ///
+    /// # 参数
+    /// * `name` - 任务名称
+    /// * `ratios` - 比率定义
+    /// * `lookback_days` - 判断突破时回看的天数
+    /// * `store` - 指标存储
+    /// * `interval_seconds` - 执行间隔
+    pub fn new(name: String, ratios: Vec<RatioConfig>, lookback_days: u32, store: Arc<MetricStore>, interval_seconds: u64) -> Self {
+        info!("🚀 创建价格比率任务: {}（{} 个比率）", name, ratios.len());
+        Self {
+            name,
+            ratios,
+            lookback_days,
+            store,
+            interval_seconds,
+        }
+    }
+
+    /// 读取比率输入的当前值
+    ///
+    /// 缓存指标键从缓存读取，其余取指标存储中的最新标量值
+    async fn input_value(&self, cache: &DataCache, key: &str) -> Option<f64> {
+        if DataCache::is_known_metric_key(key) {
+            cache.metric_value(key).await
+        } else {
+            self.store.latest(key).await?.value.as_f64()
+        }
+    }
+}
+
+/// 比率在指标存储中的名称
+pub fn ratio_metric_name(name: &str) -> String {
+    format!("ratio_{}", name)
+}
+
+#[async_trait]
+impl Task for RatioTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "计算ETH/BTC等价格比率，记录历史并检测区间突破"
+    }
+
+    fn id(&self) -> &str {
+        "ratios"
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        let now = Utc::now();
+        let previous: HashMap<String, RatioReading> = cache.get_dataset::<Vec<RatioReading>>(RATIOS_KEY).await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|r| (r.name.clone(), r))
+            .collect();
+        let query = MetricQuery {
+            since: Some(now - Duration::days(self.lookback_days.max(1) as i64)),
+            ..MetricQuery::default()
+        };
+
+        let mut readings = Vec::new();
+        let mut metrics = Vec::new();
+        for ratio in &self.ratios {
+            let numerator = self.input_value(cache, &ratio.numerator).await;
+            let denominator = self.input_value(cache, &ratio.denominator).await;
+            let (Some(numerator), Some(denominator)) = (numerator, denominator.filter(|d| *d != 0.0)) else {
+                debug!("⏭️ 比率 {} 的输入尚不完整，跳过", ratio.name);
+                continue;
+            };
+            let value = numerator / denominator;
+            let metric_name = ratio_metric_name(&ratio.name);
+            let history: Vec<_> = self.store.query(&metric_name, &query).await
+                .into_iter()
+                .filter_map(|m| Some((m.timestamp, m.value.as_f64()?)))
+                .collect();
+            readings.push(RatioReading::assess(ratio, value, &history, previous.get(&ratio.name), now));
+            metrics.push(
+                MetricBuilder::new(DataSource::EVERSCAN, metric_name)
+                    .value(value)
+                    .timestamp(now)
+                    .metadata(serde_json::json!({
+                        "numerator": ratio.numerator,
+                        "denominator": ratio.denominator,
+                        "inputs": [numerator, denominator],
+                    }))
+                    .build()?,
+            );
+        }
+
+        info!("➗ 价格比率计算完成: {}/{} 个比率", readings.len(), self.ratios.len());
+        cache.set_dataset(RATIOS_KEY, &readings).await?;
+        Ok(metrics)
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::info;
+
+use crate::analytics::{compute_sectors, SectorInput, SectorSnapshot};
+use crate::config::SectorConfig;
+use crate::models::{AggregatedMetric, DataSource, MetricBuilder, MetricValue};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, SECTORS_KEY};
+
+/// 板块统计任务
+///
+/// 按CoinMarketCap分类标签把监控币种归入板块（L1、DeFi、AI、Meme等），
+/// 统计各板块的市值、24小时资金流向和平均涨跌幅，应注册在行情采集任务之后
+pub struct SectorTask {
+    /// 任务名称
+    name: String,
+    /// 板块定义
+    sectors: Vec<SectorConfig>,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl SectorTask {
+    /// 创建新的板块统计任务
+    ///
+    /// # 参数
+    /// * `name` - 任务名称
+    /// * `sectors` - 板块定义
+    /// * `interval_seconds` - 执行间隔
+    pub fn new(name: String, sectors: Vec<SectorConfig>, interval_seconds: u64) -> Self {
+        info!("🚀 创建板块统计任务: {}（{} 个板块）", name, sectors.len());
+        Self {
+            name,
+            sectors,
+            interval_seconds,
+        }
+    }
+
+    /// 将板块统计转换为指标
+    fn to_metrics(snapshot: &SectorSnapshot) -> Result<Vec<AggregatedMetric>> {
+        let mut metrics = Vec::new();
+        for sector in &snapshot.sectors {
+            let values = [
+                ("market_cap", Some(MetricValue::scalar(sector.market_cap, "usd"))),
+                ("flow_24h", Some(MetricValue::scalar(sector.market_cap_change_24h, "usd"))),
+                ("change_24h", sector.weighted_change_24h.map(|v| MetricValue::scalar(v, "pct"))),
+            ];
+            for (field, value) in values {
+                let Some(value) = value else {
+                    continue;
+                };
+                metrics.push(
+                    MetricBuilder::new(DataSource::EVERSCAN, format!("sector_{}_{}", sector.name, field))
+                        .value(value)
+                        .timestamp(snapshot.timestamp)
+                        .metadata(serde_json::json!({ "sector": sector.name, "coins": sector.coins.len() }))
+                        .build()?,
+                );
+            }
+        }
+        Ok(metrics)
+    }
+}
+
+#[async_trait]
+impl Task for SectorTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "按分类标签统计监控币种的板块市值、24小时资金流向和平均涨跌幅"
+    }
+
+    fn id(&self) -> &str {
+        "sectors"
+    }
+
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        // 已停止更新的币种不参与统计
+        let inputs: Vec<SectorInput> = cache.get_all_market_data().await
+            .into_iter()
+            .filter(|d| d.inactive.is_none())
+            .map(|d| SectorInput {
+                coin_id: d.coin_id,
+                tags: d.tags,
+                market_cap: d.market_cap,
+                volume_24h: d.volume_24h,
+                change_24h: d.price_change_24h,
+            })
+            .collect();
+
+        let snapshot = compute_sectors(&inputs, &self.sectors, Utc::now());
+        info!(
+            "🧩 板块统计完成: {} 个板块，{} 个币种未归类",
+            snapshot.sectors.len(), snapshot.unclassified.len()
+        );
+        cache.set_dataset(SECTORS_KEY, &snapshot).await?;
+
+        Self::to_metrics(&snapshot)
+    }
+}
@@ -0,0 +1,134 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::analytics::{CoinSocial, SocialSentiment};
+use crate::clients::LunarCrushClient;
+use crate::feature_flags::{FeatureFlags, SOCIAL_SENTIMENT};
+use crate::models::{AggregatedMetric, MetricBuilder, MetricValue, LABEL_ASSET};
+use crate::tasks::Task;
+use crate::web::cache::{DataCache, SOCIAL_KEY};
+
+/// 社交情绪任务
+/// 
+/// 从LunarCrush采集缓存中各监控币种的社交热度和情绪，应注册在行情采集任务之后
+pub struct SocialSentimentTask {
+    /// 任务名称
+    name: String,
+    /// LunarCrush客户端
+    client: Arc<LunarCrushClient>,
+    /// 功能开关（关闭 `social_sentiment` 时跳过采集）
+    flags: Arc<FeatureFlags>,
+    /// 两个币种请求之间的间隔
+    request_interval: Duration,
+    /// 任务执行间隔（秒）
+    interval_seconds: u64,
+}
+
+impl SocialSentimentTask {
+    /// 创建新的社交情绪任务
+    /// 
+    /// # 参数
+    /// * `name` - 任务名称
+    /// * `client` - LunarCrush客户端
+    /// * `flags` - 功能开关
+    /// * `request_interval` - 两个币种请求之间的间隔（`lunarcrush.request_interval_ms`）
+    /// * `interval_seconds` - 执行间隔
+    pub fn new(
+        name: String,
+        client: Arc<LunarCrushClient>,
+        flags: Arc<FeatureFlags>,
+        request_interval: Duration,
+        interval_seconds: u64,
+    ) -> Self {
+        info!("🚀 创建社交情绪任务: {}", name);
+        Self {
+            name,
+            client,
+            flags,
+            request_interval,
+            interval_seconds,
+        }
+    }
+
+    /// 将单个币种的社交数据转换为指标
+    fn to_metrics(coin: &CoinSocial, timestamp: chrono::DateTime<Utc>) -> Result<Vec<AggregatedMetric>> {
+        let values = [
+            ("social_volume_24h", "count", coin.metrics.social_volume_24h),
+            ("social_interactions_24h", "count", coin.metrics.interactions_24h),
+            ("social_sentiment", "pct", coin.metrics.sentiment),
+            ("social_galaxy_score", "score", coin.metrics.galaxy_score),
+        ];
+        
+        values.into_iter()
+            .filter_map(|(name, unit, value)| Some((name, unit, value?)))
+            .map(|(name, unit, value)| {
+                MetricBuilder::new(LunarCrushClient::SOURCE, format!("{}_{}", name, coin.coin_id))
+                    .value(MetricValue::scalar(value, unit))
+                    .timestamp(timestamp)
+                    .metadata(serde_json::json!({ "coin_id": coin.coin_id, "symbol": coin.symbol }))
+                    .label(LABEL_ASSET, coin.coin_id.as_str())
+                    .build()
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Task for SocialSentimentTask {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn description(&self) -> &str {
+        "采集监控币种的社交热度、互动数和正面情绪占比"
+    }
+    
+    fn id(&self) -> &str {
+        "social_sentiment"
+    }
+    
+    fn interval_seconds(&self) -> u64 {
+        self.interval_seconds
+    }
+    
+    async fn execute(&self, cache: &DataCache) -> Result<Vec<AggregatedMetric>> {
+        if !self.flags.is_enabled(SOCIAL_SENTIMENT) {
+            info!("🚩 功能开关 {} 已关闭，跳过社交情绪采集", SOCIAL_SENTIMENT);
+            return Ok(Vec::new());
+        }
+        let now = Utc::now();
+        let mut coins = Vec::new();
+        let mut metrics = Vec::new();
+        
+        for (index, data) in cache.get_all_market_data().await.into_iter().enumerate() {
+            if index > 0 {
+                tokio::time::sleep(self.request_interval).await;
+            }
+            match self.client.get_coin_metrics(&data.symbol).await {
+                Ok(social) => {
+                    let coin = CoinSocial {
+                        coin_id: data.coin_id,
+                        symbol: data.symbol,
+                        metrics: social,
+                    };
+                    metrics.extend(Self::to_metrics(&coin, now)?);
+                    coins.push(coin);
+                }
+                Err(e) => warn!("⚠️ 获取 {} 社交指标失败: {}", data.symbol, e),
+            }
+        }
+        
+        if coins.is_empty() {
+            return Err(anyhow!("未获取到任何币种的社交指标"));
+        }
+        let social = SocialSentiment { timestamp: now, coins };
+        info!("💬 社交情绪: {} 个币种，综合正面占比 {:?}", social.coins.len(), social.sentiment_score());
+        cache.set_dataset(SOCIAL_KEY, &social).await?;
+        
+        Ok(metrics)
+    }
+}
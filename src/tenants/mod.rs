@@ -0,0 +1,390 @@
+pub mod usage;
+
+pub use usage::*;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::config::TenantConfig;
+use crate::storage::JsonFileStore;
+
+/// 单个租户最多关注的币种数
+const MAX_WATCHLIST_LEN: usize = 100;
+
+/// 租户的持久化状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TenantState {
+    /// 租户在告警规则、价格监控等数据中的所有者ID
+    owner_id: Uuid,
+    /// 关注币种（CoinGecko ID）
+    watchlist: Vec<String>,
+}
+
+/// 租户信息
+#[derive(Debug, Clone, Serialize)]
+pub struct TenantInfo {
+    /// 租户ID
+    pub id: String,
+    /// 显示名称
+    pub name: String,
+    /// 所有者ID（告警规则、价格监控按该ID隔离）
+    pub owner_id: Uuid,
+    /// 关注币种（CoinGecko ID）
+    pub watchlist: Vec<String>,
+    /// 每分钟最多请求数（0表示不限制）
+    pub requests_per_minute: u32,
+    /// 最近一小时最多请求数（0表示不限制）
+    pub requests_per_hour: u32,
+}
+
+/// 租户注册表
+///
+/// API密钥只以SHA-256摘要保存在内存中；关注列表在首次启动时取自配置，
+/// 之后的修改保存在状态文件中
+pub struct TenantRegistry {
+    /// 租户配置（按ID）
+    tenants: BTreeMap<String, TenantConfig>,
+    /// API密钥摘要 -> 租户ID
+    keys: HashMap<String, String>,
+    /// 租户ID -> 持久化状态
+    states: RwLock<BTreeMap<String, TenantState>>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<BTreeMap<String, TenantState>>>,
+    /// 租户ID -> （当前限流窗口起点，窗口内请求数）
+    windows: Mutex<HashMap<String, (DateTime<Utc>, u32)>>,
+    /// 各租户最近一小时的用量
+    usage: UsageTracker,
+}
+
+/// API密钥摘要
+fn key_digest(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// 规范化关注币种：小写、去空白、去重并保持顺序
+fn normalize_coins(coins: &[String]) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    coins.iter()
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty() && seen.insert(c.clone()))
+        .collect()
+}
+
+impl TenantRegistry {
+    /// 创建内存中的租户注册表（不持久化）
+    ///
+    /// # 返回
+    /// * `Result<Self>` - 租户ID或API密钥重复时返回错误
+    pub fn new(configs: &[TenantConfig]) -> Result<Self> {
+        Self::build(configs, BTreeMap::new(), None)
+    }
+
+    /// 从状态文件加载租户注册表
+    ///
+    /// # 参数
+    /// * `path` - 状态文件路径
+    /// * `configs` - 租户配置
+    pub async fn open(path: impl Into<PathBuf>, configs: &[TenantConfig]) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let states = store.load().await?;
+        let registry = Self::build(configs, states, Some(store))?;
+        if !registry.tenants.is_empty() {
+            registry.persist(&*registry.states.read().await).await?;
+            info!("🏢 已加载 {} 个租户", registry.tenants.len());
+        }
+        Ok(registry)
+    }
+
+    /// 校验配置并补齐新租户的状态
+    fn build(
+        configs: &[TenantConfig],
+        mut states: BTreeMap<String, TenantState>,
+        store: Option<JsonFileStore<BTreeMap<String, TenantState>>>,
+    ) -> Result<Self> {
+        let mut tenants = BTreeMap::new();
+        let mut keys = HashMap::new();
+        for config in configs {
+            let id = config.id.trim().to_lowercase();
+            if id.is_empty() {
+                return Err(anyhow!("租户ID不能为空"));
+            }
+            if config.api_keys.iter().all(|k| k.is_empty()) {
+                return Err(anyhow!("租户 {} 没有配置API密钥", id));
+            }
+            for key in config.api_keys.iter().filter(|k| !k.is_empty()) {
+                if keys.insert(key_digest(key.expose()), id.clone()).is_some() {
+                    return Err(anyhow!("租户 {} 的API密钥与其他租户重复", id));
+                }
+            }
+            states.entry(id.clone()).or_insert_with(|| TenantState {
+                owner_id: Uuid::new_v4(),
+                watchlist: normalize_coins(&config.coins),
+            });
+            if tenants.insert(id.clone(), TenantConfig { id: id.clone(), ..config.clone() }).is_some() {
+                return Err(anyhow!("租户ID重复: {}", id));
+            }
+        }
+        // 已从配置中移除的租户不再保留状态
+        states.retain(|id, _| tenants.contains_key(id));
+
+        Ok(Self {
+            tenants,
+            keys,
+            states: RwLock::new(states),
+            store,
+            windows: Mutex::new(HashMap::new()),
+            usage: UsageTracker::new(),
+        })
+    }
+
+    /// 是否配置了租户
+    pub fn is_empty(&self) -> bool {
+        self.tenants.is_empty()
+    }
+
+    /// 按API密钥识别租户
+    pub async fn authenticate(&self, api_key: &str) -> Option<TenantInfo> {
+        let id = self.keys.get(&key_digest(api_key.trim()))?;
+        self.get(id).await
+    }
+
+    /// 获取租户信息
+    pub async fn get(&self, id: &str) -> Option<TenantInfo> {
+        let config = self.tenants.get(id)?;
+        let states = self.states.read().await;
+        let state = states.get(id)?;
+        Some(TenantInfo {
+            id: config.id.clone(),
+            name: if config.name.is_empty() { config.id.clone() } else { config.name.clone() },
+            owner_id: state.owner_id,
+            watchlist: state.watchlist.clone(),
+            requests_per_minute: config.requests_per_minute,
+            requests_per_hour: config.requests_per_hour,
+        })
+    }
+
+    /// 全部租户信息（按ID排序）
+    pub async fn list(&self) -> Vec<TenantInfo> {
+        let mut tenants = Vec::with_capacity(self.tenants.len());
+        for id in self.tenants.keys() {
+            tenants.extend(self.get(id).await);
+        }
+        tenants
+    }
+
+    /// 替换租户的关注币种
+    ///
+    /// # 参数
+    /// * `id` - 租户ID
+    /// * `coins` - 关注币种（CoinGecko ID）
+    ///
+    /// # 返回
+    /// * `Result<TenantInfo>` - 更新后的租户信息
+    pub async fn set_watchlist(&self, id: &str, coins: &[String]) -> Result<TenantInfo> {
+        let watchlist = normalize_coins(coins);
+        if watchlist.len() > MAX_WATCHLIST_LEN {
+            return Err(anyhow!("关注列表最多 {} 个币种", MAX_WATCHLIST_LEN));
+        }
+        {
+            let mut states = self.states.write().await;
+            let state = states.get_mut(id).ok_or_else(|| anyhow!("租户 {} 不存在", id))?;
+            state.watchlist = watchlist;
+            self.persist(&states).await?;
+        }
+        info!("🏢 租户 {} 更新关注列表", id);
+        self.get(id).await.ok_or_else(|| anyhow!("租户 {} 不存在", id))
+    }
+
+    /// 全部租户关注币种的并集
+    pub async fn watched_coins(&self) -> BTreeSet<String> {
+        self.states.read().await.values()
+            .flat_map(|state| state.watchlist.iter().cloned())
+            .collect()
+    }
+
+    /// 为租户占用一次请求配额
+    ///
+    /// 每分钟请求数按固定窗口计算；最近一小时请求数按用量统计的滚动窗口计算，
+    /// 超出时等到窗口中最早一分钟的请求滑出为止。被拒绝的请求记入用量统计的拒绝次数
+    ///
+    /// # 参数
+    /// * `id` - 租户ID
+    /// * `now` - 当前时间
+    ///
+    /// # 返回
+    /// * `Result<(), i64>` - 超出配额时返回需要等待的秒数
+    pub async fn try_acquire(&self, id: &str, now: DateTime<Utc>) -> Result<(), i64> {
+        let result = self.check_limits(id, now).await;
+        if result.is_err() {
+            self.usage.record_rejected(id, now);
+        }
+        result
+    }
+
+    /// 检查每分钟和每小时请求数限制
+    async fn check_limits(&self, id: &str, now: DateTime<Utc>) -> Result<(), i64> {
+        let Some(config) = self.tenants.get(id) else {
+            return Ok(());
+        };
+        if config.requests_per_hour > 0 {
+            let (requests, oldest) = self.usage.window_requests(id, now);
+            if requests >= u64::from(config.requests_per_hour) {
+                let oldest = oldest.unwrap_or(now);
+                return Err((oldest + Duration::minutes(USAGE_WINDOW_MINUTES) - now).num_seconds().max(1));
+            }
+        }
+        let limit = config.requests_per_minute;
+        if limit == 0 {
+            return Ok(());
+        }
+        let mut windows = self.windows.lock().await;
+        let window = windows.entry(id.to_string()).or_insert((now, 0));
+        if now - window.0 >= Duration::minutes(1) {
+            *window = (now, 0);
+        }
+        if window.1 >= limit {
+            return Err((window.0 + Duration::minutes(1) - now).num_seconds().max(1));
+        }
+        window.1 += 1;
+        Ok(())
+    }
+
+    /// 记录一次已处理的请求
+    ///
+    /// # 参数
+    /// * `id` - 租户ID
+    /// * `endpoint` - 接口路由
+    /// * `status` - 响应状态码
+    /// * `now` - 请求时间
+    pub fn record_usage(&self, id: &str, endpoint: &str, status: u16, now: DateTime<Utc>) {
+        self.usage.record(id, endpoint, status, now);
+    }
+
+    /// 全部租户最近一小时的用量（按ID排序）
+    pub fn usage(&self, now: DateTime<Utc>) -> Vec<ConsumerUsage> {
+        self.tenants.keys().map(|id| self.usage.report(id, now)).collect()
+    }
+
+    /// 保存状态
+    async fn persist(&self, states: &BTreeMap<String, TenantState>) -> Result<()> {
+        if let Some(store) = &self.store {
+            store.save(states).await?;
+        }
+        Ok(())
+    }
+}
+
+/// 采集币种范围
+///
+/// 监控币种与全部租户关注币种的并集，采集任务每轮只按并集采集一次
+#[derive(Clone)]
+pub struct CoinUniverse {
+    /// 监控币种（CoinGecko ID）
+    base: Vec<String>,
+    /// CoinGecko ID -> 缓存币种ID
+    coin_ids: BTreeMap<String, String>,
+    /// 租户注册表（可选）
+    tenants: Option<Arc<TenantRegistry>>,
+}
+
+impl CoinUniverse {
+    /// 创建采集币种范围
+    ///
+    /// # 参数
+    /// * `base` - 监控币种（CoinGecko ID）
+    /// * `coin_ids` - CoinGecko ID到缓存币种ID的映射，未列出的币种直接使用CoinGecko ID
+    pub fn new(base: Vec<String>, coin_ids: BTreeMap<String, String>) -> Self {
+        Self { base, coin_ids, tenants: None }
+    }
+
+    /// 合并租户的关注币种
+    pub fn with_tenants(mut self, tenants: Arc<TenantRegistry>) -> Self {
+        self.tenants = Some(tenants);
+        self
+    }
+
+    /// 全部币种的CoinGecko ID（去重排序）
+    pub async fn coingecko_ids(&self) -> Vec<String> {
+        let mut coins: BTreeSet<String> = normalize_coins(&self.base).into_iter().collect();
+        if let Some(tenants) = &self.tenants {
+            coins.extend(tenants.watched_coins().await);
+        }
+        coins.into_iter().collect()
+    }
+
+    /// 全部币种的缓存币种ID（去重排序）
+    pub async fn coin_ids(&self) -> Vec<String> {
+        let ids: BTreeSet<String> = self.coingecko_ids().await.iter()
+            .map(|id| self.coin_id(id).to_string())
+            .collect();
+        ids.into_iter().collect()
+    }
+
+    /// CoinGecko ID对应的缓存币种ID
+    pub fn coin_id<'a>(&'a self, coingecko_id: &'a str) -> &'a str {
+        self.coin_ids.get(coingecko_id).map_or(coingecko_id, String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::SecretString;
+
+    fn tenant(id: &str, key: &str, coins: &[&str], requests_per_minute: u32) -> TenantConfig {
+        TenantConfig {
+            id: id.to_string(),
+            name: String::new(),
+            api_keys: vec![SecretString::new(key)],
+            coins: coins.iter().map(|c| c.to_string()).collect(),
+            requests_per_minute,
+            requests_per_hour: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tenant_keys_limits_and_universe() {
+        let registry = Arc::new(TenantRegistry::new(&[
+            tenant("team-a", "key-a", &["bitcoin", "Hyperliquid"], 2),
+            tenant("team-b", "key-b", &["solana", "bitcoin"], 0),
+        ]).unwrap());
+        assert!(TenantRegistry::new(&[tenant("x", "same", &[], 0), tenant("y", "same", &[], 0)]).is_err());
+
+        let a = registry.authenticate("key-a").await.unwrap();
+        assert_eq!((a.id.as_str(), a.watchlist.clone()), ("team-a", vec!["bitcoin".to_string(), "hyperliquid".to_string()]));
+        assert!(registry.authenticate("unknown").await.is_none());
+
+        let now = Utc::now();
+        assert!(registry.try_acquire("team-a", now).await.is_ok());
+        assert!(registry.try_acquire("team-a", now).await.is_ok());
+        assert_eq!(registry.try_acquire("team-a", now + Duration::seconds(45)).await, Err(15));
+        assert!(registry.try_acquire("team-a", now + Duration::seconds(60)).await.is_ok());
+        assert!(registry.try_acquire("team-b", now).await.is_ok());
+
+        // 每小时限制按用量统计的滚动窗口计算
+        let hourly = TenantRegistry::new(&[TenantConfig { requests_per_hour: 2, ..tenant("team-c", "key-c", &[], 0) }]).unwrap();
+        for _ in 0..2 {
+            assert!(hourly.try_acquire("team-c", now).await.is_ok());
+            hourly.record_usage("team-c", "/api/prices", 200, now);
+        }
+        assert!(hourly.try_acquire("team-c", now + Duration::minutes(30)).await.is_err());
+        assert!(hourly.try_acquire("team-c", now + Duration::minutes(61)).await.is_ok());
+        assert_eq!(hourly.usage(now + Duration::minutes(30))[0].rejected, 1);
+
+        let universe = CoinUniverse::new(
+            vec!["hyperliquid".to_string()],
+            BTreeMap::from([("hyperliquid".to_string(), "hype".to_string())]),
+        ).with_tenants(registry.clone());
+        assert_eq!(universe.coingecko_ids().await, vec!["bitcoin", "hyperliquid", "solana"]);
+        registry.set_watchlist("team-b", &["ethereum".to_string()]).await.unwrap();
+        assert_eq!(universe.coin_ids().await, vec!["bitcoin", "ethereum", "hype"]);
+    }
+}
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// 用量统计的滚动窗口长度（分钟）
+pub const USAGE_WINDOW_MINUTES: i64 = 60;
+
+/// 单个接口在一分钟内的请求数
+#[derive(Debug, Clone, Copy, Default)]
+struct EndpointCounts {
+    /// 请求数
+    requests: u64,
+    /// 错误响应数（HTTP 4xx/5xx）
+    errors: u64,
+}
+
+/// 一分钟内的用量
+#[derive(Debug, Clone)]
+struct MinuteBucket {
+    /// 分钟起点
+    minute: DateTime<Utc>,
+    /// 被限流拒绝的请求数（不计入请求数）
+    rejected: u64,
+    /// 接口 -> 请求数
+    endpoints: HashMap<String, EndpointCounts>,
+}
+
+/// 单个接口在窗口内的用量
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointUsage {
+    /// 接口路由（如 `/api/nft/:collection`）
+    pub endpoint: String,
+    /// 请求数
+    pub requests: u64,
+    /// 错误响应数
+    pub errors: u64,
+}
+
+/// 单个调用方在窗口内的用量
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsumerUsage {
+    /// 租户ID
+    pub tenant: String,
+    /// 窗口长度（分钟）
+    pub window_minutes: i64,
+    /// 请求数
+    pub requests: u64,
+    /// 错误响应数
+    pub errors: u64,
+    /// 错误率（没有请求时为None）
+    pub error_rate: Option<f64>,
+    /// 被限流拒绝的请求数
+    pub rejected: u64,
+    /// 最近一次请求所在的分钟
+    pub last_request: Option<DateTime<Utc>>,
+    /// 各接口用量（按请求数降序）
+    pub endpoints: Vec<EndpointUsage>,
+}
+
+/// API用量统计
+///
+/// 按租户以分钟为粒度记录请求数、访问的接口和错误响应数，只保留最近 `USAGE_WINDOW_MINUTES` 分钟；
+/// 统计只在内存中，重启后清零
+#[derive(Default)]
+pub struct UsageTracker {
+    /// 租户ID -> 按时间升序的分钟用量
+    buckets: Mutex<HashMap<String, VecDeque<MinuteBucket>>>,
+}
+
+impl UsageTracker {
+    /// 创建空的用量统计
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 取得租户当前分钟的用量，并丢弃窗口外的分钟
+    fn bucket<'a>(buckets: &'a mut HashMap<String, VecDeque<MinuteBucket>>, tenant: &str, now: DateTime<Utc>) -> &'a mut MinuteBucket {
+        let minute = now.duration_trunc(Duration::minutes(1)).unwrap_or(now);
+        let series = buckets.entry(tenant.to_string()).or_default();
+        while series.front().is_some_and(|b| b.minute <= minute - Duration::minutes(USAGE_WINDOW_MINUTES)) {
+            series.pop_front();
+        }
+        if series.back().is_none_or(|b| b.minute < minute) {
+            series.push_back(MinuteBucket { minute, rejected: 0, endpoints: HashMap::new() });
+        }
+        series.back_mut().expect("刚插入的分钟用量")
+    }
+
+    /// 记录一次已处理的请求
+    ///
+    /// # 参数
+    /// * `tenant` - 租户ID
+    /// * `endpoint` - 接口路由
+    /// * `status` - 响应状态码（4xx/5xx计为错误）
+    /// * `now` - 请求时间
+    pub fn record(&self, tenant: &str, endpoint: &str, status: u16, now: DateTime<Utc>) {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let counts = Self::bucket(&mut buckets, tenant, now).endpoints.entry(endpoint.to_string()).or_default();
+        counts.requests += 1;
+        if status >= 400 {
+            counts.errors += 1;
+        }
+    }
+
+    /// 记录一次被限流拒绝的请求
+    pub fn record_rejected(&self, tenant: &str, now: DateTime<Utc>) {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        Self::bucket(&mut buckets, tenant, now).rejected += 1;
+    }
+
+    /// 租户在窗口内的请求数，以及最早一个有请求的分钟
+    ///
+    /// # 参数
+    /// * `tenant` - 租户ID
+    /// * `now` - 当前时间
+    pub fn window_requests(&self, tenant: &str, now: DateTime<Utc>) -> (u64, Option<DateTime<Utc>>) {
+        let buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let start = now - Duration::minutes(USAGE_WINDOW_MINUTES);
+        let mut total = 0;
+        let mut oldest = None;
+        for bucket in buckets.get(tenant).into_iter().flatten().filter(|b| b.minute > start) {
+            let requests: u64 = bucket.endpoints.values().map(|c| c.requests).sum();
+            if requests > 0 && oldest.is_none() {
+                oldest = Some(bucket.minute);
+            }
+            total += requests;
+        }
+        (total, oldest)
+    }
+
+    /// 生成租户在窗口内的用量报告
+    ///
+    /// # 参数
+    /// * `tenant` - 租户ID
+    /// * `now` - 当前时间
+    pub fn report(&self, tenant: &str, now: DateTime<Utc>) -> ConsumerUsage {
+        let buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let start = now - Duration::minutes(USAGE_WINDOW_MINUTES);
+        let mut endpoints: BTreeMap<&str, EndpointCounts> = BTreeMap::new();
+        let mut rejected = 0;
+        let mut last_request = None;
+        for bucket in buckets.get(tenant).into_iter().flatten().filter(|b| b.minute > start) {
+            rejected += bucket.rejected;
+            for (endpoint, counts) in &bucket.endpoints {
+                let total = endpoints.entry(endpoint).or_default();
+                total.requests += counts.requests;
+                total.errors += counts.errors;
+                last_request = Some(bucket.minute);
+            }
+        }
+
+        let requests = endpoints.values().map(|c| c.requests).sum();
+        let errors = endpoints.values().map(|c| c.errors).sum();
+        let mut endpoints: Vec<EndpointUsage> = endpoints.into_iter()
+            .map(|(endpoint, counts)| EndpointUsage { endpoint: endpoint.to_string(), requests: counts.requests, errors: counts.errors })
+            .collect();
+        endpoints.sort_by_key(|e| std::cmp::Reverse(e.requests));
+
+        ConsumerUsage {
+            tenant: tenant.to_string(),
+            window_minutes: USAGE_WINDOW_MINUTES,
+            requests,
+            errors,
+            error_rate: (requests > 0).then(|| errors as f64 / requests as f64),
+            rejected,
+            last_request,
+            endpoints,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_window() {
+        let tracker = UsageTracker::new();
+        let start: DateTime<Utc> = "2024-06-01T12:00:30Z".parse().unwrap();
+        tracker.record("team-a", "/api/nft/:collection", 200, start);
+        tracker.record("team-a", "/api/nft/:collection", 404, start + Duration::seconds(10));
+        tracker.record("team-a", "/api/network/:chain", 200, start + Duration::minutes(30));
+        tracker.record_rejected("team-a", start + Duration::minutes(30));
+
+        let report = tracker.report("team-a", start + Duration::minutes(30));
+        assert_eq!((report.requests, report.errors, report.rejected), (3, 1, 1));
+        assert_eq!(report.endpoints[0].endpoint, "/api/nft/:collection");
+        assert!((report.error_rate.unwrap() - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(tracker.window_requests("team-a", start + Duration::minutes(30)), (3, Some("2024-06-01T12:00:00Z".parse().unwrap())));
+
+        // 第一分钟的请求滑出窗口
+        let later = start + Duration::minutes(61);
+        assert_eq!(tracker.window_requests("team-a", later).0, 1);
+        assert_eq!(tracker.report("team-b", later).requests, 0);
+    }
+}
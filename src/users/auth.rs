@@ -0,0 +1,132 @@
+use anyhow::{Result, anyhow};
+use argon2::{
+    Argon2,
+    password_hash::{rand_core::{OsRng, RngCore}, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+};
+use chrono::Utc;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::config::AuthConfig;
+use super::User;
+
+/// 计算密码的 Argon2 哈希
+/// 
+/// # 参数
+/// * `password` - 明文密码
+/// 
+/// # 返回
+/// * `Result<String>` - PHC格式的哈希字符串
+pub fn hash_password(password: &str) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| anyhow!("密码哈希失败: {}", e))
+}
+
+/// 校验密码是否与哈希匹配
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+        .unwrap_or(false)
+}
+
+/// JWT声明
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    /// 用户ID
+    pub sub: Uuid,
+    /// 用户名
+    pub username: String,
+    /// 签发时间（Unix秒）
+    pub iat: i64,
+    /// 过期时间（Unix秒）
+    pub exp: i64,
+}
+
+/// 会话令牌服务
+/// 
+/// 使用 HS256 签发和校验 JWT
+pub struct AuthService {
+    /// 签名密钥
+    encoding_key: EncodingKey,
+    /// 校验密钥
+    decoding_key: DecodingKey,
+    /// 令牌有效期（秒）
+    token_ttl_seconds: i64,
+    /// 是否允许注册
+    allow_registration: bool,
+    /// 管理员用户名（小写）
+    admin_users: Vec<String>,
+}
+
+impl AuthService {
+    /// 创建令牌服务
+    /// 
+    /// # 参数
+    /// * `secret` - 签名密钥
+    /// * `token_ttl_hours` - 令牌有效期（小时）
+    pub fn new(secret: &[u8], token_ttl_hours: u64) -> Self {
+        Self {
+            encoding_key: EncodingKey::from_secret(secret),
+            decoding_key: DecodingKey::from_secret(secret),
+            token_ttl_seconds: (token_ttl_hours * 3600) as i64,
+            allow_registration: true,
+            admin_users: Vec::new(),
+        }
+    }
+
+    /// 根据配置创建令牌服务
+    pub fn from_config(config: &AuthConfig) -> Self {
+        let secret = match &config.jwt_secret {
+            Some(secret) => secret.expose().as_bytes().to_vec(),
+            None => {
+                warn!("⚠️ 未配置 auth.jwt_secret，使用随机密钥，重启后需要重新登录");
+                let mut secret = vec![0u8; 32];
+                OsRng.fill_bytes(&mut secret);
+                secret
+            }
+        };
+        
+        let mut service = Self::new(&secret, config.token_ttl_hours);
+        service.allow_registration = config.allow_registration;
+        service.admin_users = config.admin_users.iter().map(|u| u.trim().to_lowercase()).collect();
+        service
+    }
+
+    /// 是否允许注册新用户
+    pub fn allow_registration(&self) -> bool {
+        self.allow_registration
+    }
+
+    /// 用户是否为管理员
+    pub fn is_admin(&self, username: &str) -> bool {
+        self.admin_users.iter().any(|u| u == username)
+    }
+
+    /// 为用户签发令牌
+    pub fn issue(&self, user: &User) -> Result<String> {
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            sub: user.id,
+            username: user.username.clone(),
+            iat: now,
+            exp: now + self.token_ttl_seconds,
+        };
+        encode(&Header::default(), &claims, &self.encoding_key)
+            .map_err(|e| anyhow!("签发令牌失败: {}", e))
+    }
+
+    /// 校验令牌
+    /// 
+    /// # 返回
+    /// * `Result<Claims>` - 令牌声明或错误（签名无效/已过期）
+    pub fn verify(&self, token: &str) -> Result<Claims> {
+        decode::<Claims>(token, &self.decoding_key, &Validation::default())
+            .map(|data| data.claims)
+            .map_err(|e| anyhow!("令牌无效: {}", e))
+    }
+}
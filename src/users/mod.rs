@@ -0,0 +1,225 @@
+pub mod auth;
+
+pub use auth::*;
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::storage::JsonFileStore;
+
+/// 用户名最小长度
+const MIN_USERNAME_LEN: usize = 3;
+/// 密码最小长度
+const MIN_PASSWORD_LEN: usize = 8;
+
+/// 用户账户
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    /// 唯一标识符
+    pub id: Uuid,
+    /// 用户名（小写，唯一）
+    pub username: String,
+    /// Argon2 密码哈希
+    pub password_hash: String,
+    /// 关注的币种列表
+    #[serde(default)]
+    pub watchlist: Vec<String>,
+    /// 仪表板偏好设置
+    #[serde(default)]
+    pub preferences: serde_json::Map<String, serde_json::Value>,
+    /// 注册时间
+    pub created_at: DateTime<Utc>,
+    /// 最后登录时间
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+/// 用户公开资料（不含密码哈希）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserProfile {
+    /// 唯一标识符
+    pub id: Uuid,
+    /// 用户名
+    pub username: String,
+    /// 关注的币种列表
+    pub watchlist: Vec<String>,
+    /// 仪表板偏好设置
+    pub preferences: serde_json::Map<String, serde_json::Value>,
+    /// 注册时间
+    pub created_at: DateTime<Utc>,
+    /// 最后登录时间
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+impl From<&User> for UserProfile {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username.clone(),
+            watchlist: user.watchlist.clone(),
+            preferences: user.preferences.clone(),
+            created_at: user.created_at,
+            last_login_at: user.last_login_at,
+        }
+    }
+}
+
+/// 用户存储
+pub struct UserStore {
+    /// 用户列表
+    users: RwLock<Vec<User>>,
+    /// 持久化存储（可选）
+    store: Option<JsonFileStore<Vec<User>>>,
+}
+
+impl UserStore {
+    /// 创建内存中的用户存储（不持久化）
+    pub fn new() -> Self {
+        Self {
+            users: RwLock::new(Vec::new()),
+            store: None,
+        }
+    }
+
+    /// 从状态文件加载用户存储
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let store = JsonFileStore::new(path);
+        let users: Vec<User> = store.load().await?;
+        info!("👤 已加载 {} 个用户", users.len());
+        Ok(Self {
+            users: RwLock::new(users),
+            store: Some(store),
+        })
+    }
+
+    /// 注册新用户
+    /// 
+    /// # 参数
+    /// * `username` - 用户名（不区分大小写）
+    /// * `password` - 明文密码
+    /// 
+    /// # 返回
+    /// * `Result<User>` - 新用户或错误
+    pub async fn register(&self, username: &str, password: &str) -> Result<User> {
+        let username = username.trim().to_lowercase();
+        if username.len() < MIN_USERNAME_LEN {
+            return Err(anyhow!("用户名至少需要 {} 个字符", MIN_USERNAME_LEN));
+        }
+        if password.len() < MIN_PASSWORD_LEN {
+            return Err(anyhow!("密码至少需要 {} 个字符", MIN_PASSWORD_LEN));
+        }
+        
+        // 哈希计算较慢，先在锁外完成
+        let password_hash = hash_password(password)?;
+        
+        let mut users = self.users.write().await;
+        if users.iter().any(|u| u.username == username) {
+            return Err(anyhow!("用户名 {} 已被占用", username));
+        }
+        
+        let user = User {
+            id: Uuid::new_v4(),
+            username,
+            password_hash,
+            watchlist: Vec::new(),
+            preferences: serde_json::Map::new(),
+            created_at: Utc::now(),
+            last_login_at: None,
+        };
+        users.push(user.clone());
+        self.persist(&users).await?;
+        info!("👤 新用户注册: {}", user.username);
+        Ok(user)
+    }
+
+    /// 校验用户名和密码
+    /// 
+    /// # 返回
+    /// * `Result<User>` - 校验通过的用户或错误
+    pub async fn authenticate(&self, username: &str, password: &str) -> Result<User> {
+        let username = username.trim().to_lowercase();
+        let user = self.users.read().await
+            .iter()
+            .find(|u| u.username == username)
+            .cloned()
+            .ok_or_else(|| anyhow!("用户名或密码错误"))?;
+        
+        if !verify_password(password, &user.password_hash) {
+            return Err(anyhow!("用户名或密码错误"));
+        }
+        
+        self.update(user.id, |u| u.last_login_at = Some(Utc::now())).await
+    }
+
+    /// 获取用户
+    pub async fn get(&self, id: Uuid) -> Option<User> {
+        self.users.read().await.iter().find(|u| u.id == id).cloned()
+    }
+
+    /// 设置关注列表
+    pub async fn set_watchlist(&self, id: Uuid, coins: Vec<String>) -> Result<User> {
+        let mut watchlist: Vec<String> = Vec::new();
+        for coin in coins {
+            let coin = coin.trim().to_lowercase();
+            if !coin.is_empty() && !watchlist.contains(&coin) {
+                watchlist.push(coin);
+            }
+        }
+        self.update(id, |u| u.watchlist = watchlist).await
+    }
+
+    /// 设置仪表板偏好
+    pub async fn set_preferences(&self, id: Uuid, preferences: serde_json::Map<String, serde_json::Value>) -> Result<User> {
+        self.update(id, |u| u.preferences = preferences).await
+    }
+
+    /// 修改用户并持久化
+    async fn update(&self, id: Uuid, f: impl FnOnce(&mut User)) -> Result<User> {
+        let mut users = self.users.write().await;
+        let user = users.iter_mut()
+            .find(|u| u.id == id)
+            .ok_or_else(|| anyhow!("用户 {} 不存在", id))?;
+        f(user);
+        let user = user.clone();
+        self.persist(&users).await?;
+        Ok(user)
+    }
+
+    /// 持久化
+    async fn persist(&self, users: &Vec<User>) -> Result<()> {
+        if let Some(store) = &self.store {
+            store.save(users).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for UserStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_register_and_authenticate() {
+        let store = UserStore::new();
+        store.register("Alice", "correct horse").await.unwrap();
+        
+        assert!(store.register("alice", "another password").await.is_err(), "用户名不区分大小写");
+        assert!(store.authenticate("alice", "wrong password").await.is_err());
+        
+        let user = store.authenticate("ALICE", "correct horse").await.unwrap();
+        assert!(user.last_login_at.is_some());
+        
+        let user = store.set_watchlist(user.id, vec!["BTC".into(), "btc".into(), "eth".into()]).await.unwrap();
+        assert_eq!(user.watchlist, vec!["btc", "eth"]);
+    }
+}
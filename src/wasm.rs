@@ -0,0 +1,165 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// 单次转换可消耗的燃料（约等于执行的WASM指令数，防止死循环）
+const TRANSFORM_FUEL: u64 = 500_000_000;
+
+/// 转换模块可使用的最大线性内存（字节）
+const TRANSFORM_MEMORY_BYTES: usize = 64 << 20;
+
+/// 转换输出的最大长度（字节）
+const TRANSFORM_OUTPUT_BYTES: usize = 16 << 20;
+
+/// 转换模块输出的指标
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TransformedMetric {
+    /// 指标名称
+    pub name: String,
+    /// 数值
+    pub value: f64,
+    /// 单位（可选）
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// 数据时间戳（缺省为采集时间）
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+    /// 标签（可选）
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+/// WASM转换模块
+///
+/// 模块需要导出：
+/// * `memory` - 线性内存
+/// * `alloc(len: i32) -> i32` - 分配输入缓冲区，返回起始地址
+/// * `transform(ptr: i32, len: i32) -> i64` - 解析输入的原始JSON，返回输出JSON的位置（高32位为地址，低32位为长度）
+///
+/// 输出为 [`TransformedMetric`] 组成的JSON数组。模块不能导入任何宿主函数，
+/// 每次转换使用新的实例，执行受燃料和内存上限约束
+#[derive(Clone)]
+pub struct WasmTransform {
+    /// 模块名称（用于日志和错误信息）
+    name: String,
+    /// 编译引擎
+    engine: Engine,
+    /// 编译后的模块
+    module: Module,
+}
+
+impl WasmTransform {
+    /// 从文件加载转换模块
+    ///
+    /// # 参数
+    /// * `path` - `.wasm` 文件路径（也支持 `.wat` 文本格式）
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).with_context(|| format!("读取WASM转换模块失败: {}", path.display()))?;
+        Self::from_bytes(path.display().to_string(), &bytes)
+    }
+
+    /// 从字节加载转换模块
+    ///
+    /// # 参数
+    /// * `name` - 模块名称
+    /// * `bytes` - WASM二进制或WAT文本
+    pub fn from_bytes(name: impl Into<String>, bytes: &[u8]) -> Result<Self> {
+        let name = name.into();
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let module = Module::new(&engine, bytes).with_context(|| format!("编译WASM转换模块 {} 失败", name))?;
+        if module.imports().len() > 0 {
+            return Err(anyhow!("WASM转换模块 {} 不能导入宿主函数", name));
+        }
+        Ok(Self { name, engine, module })
+    }
+
+    /// 模块名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 执行转换（同步执行，异步环境中应放到阻塞线程池）
+    ///
+    /// # 参数
+    /// * `payload` - 数据源返回的原始JSON
+    ///
+    /// # 返回
+    /// * `Result<Vec<TransformedMetric>>` - 转换得到的指标，模块执行失败或输出无效时返回错误
+    pub fn transform(&self, payload: &[u8]) -> Result<Vec<TransformedMetric>> {
+        let mut store = Store::new(&self.engine, StoreLimitsBuilder::new().memory_size(TRANSFORM_MEMORY_BYTES).build());
+        store.limiter(|limits: &mut StoreLimits| limits);
+        store.set_fuel(TRANSFORM_FUEL)?;
+
+        let instance = Instance::new(&mut store, &self.module, &[])
+            .with_context(|| format!("实例化WASM转换模块 {} 失败", self.name))?;
+        let memory = instance.get_memory(&mut store, "memory")
+            .ok_or_else(|| anyhow!("WASM转换模块 {} 未导出 memory", self.name))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+        let transform = instance.get_typed_func::<(i32, i32), i64>(&mut store, "transform")?;
+
+        let len = i32::try_from(payload.len()).map_err(|_| anyhow!("输入过大: {} 字节", payload.len()))?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, ptr as u32 as usize, payload)
+            .with_context(|| format!("写入WASM转换模块 {} 的输入失败", self.name))?;
+        let packed = transform.call(&mut store, (ptr, len))
+            .with_context(|| format!("WASM转换模块 {} 执行失败", self.name))?;
+
+        let (out_ptr, out_len) = ((packed as u64 >> 32) as usize, (packed as u64 & 0xffff_ffff) as usize);
+        if out_len > TRANSFORM_OUTPUT_BYTES {
+            bail!("WASM转换模块 {} 的输出过大: {} 字节（上限 {} 字节）", self.name, out_len, TRANSFORM_OUTPUT_BYTES);
+        }
+        if out_ptr.checked_add(out_len).is_none_or(|end| end > memory.data_size(&store)) {
+            bail!("WASM转换模块 {} 的输出超出线性内存范围", self.name);
+        }
+        let mut output = vec![0u8; out_len];
+        memory.read(&store, out_ptr, &mut output)
+            .with_context(|| format!("读取WASM转换模块 {} 的输出失败", self.name))?;
+        serde_json::from_slice(&output).with_context(|| format!("WASM转换模块 {} 的输出不是有效的指标数组", self.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 原样返回输入的转换模块
+    const ECHO_MODULE: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (global $next (mut i32) (i32.const 1024))
+          (func (export "alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+            (local.get $ptr))
+          (func (export "transform") (param $ptr i32) (param $len i32) (result i64)
+            (i64.or
+              (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+              (i64.extend_i32_u (local.get $len)))))
+    "#;
+
+    #[test]
+    fn test_transform_echo_and_fuel_limit() {
+        let echo = WasmTransform::from_bytes("echo", ECHO_MODULE.as_bytes()).unwrap();
+        let metrics = echo.transform(br#"[{"name":"tvl_usd","value":12.5,"unit":"usd","labels":{"chain":"base"}}]"#).unwrap();
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].name, "tvl_usd");
+        assert_eq!(metrics[0].labels.get("chain").map(String::as_str), Some("base"));
+        assert!(echo.transform(b"{\"not\":\"metrics\"}").is_err());
+
+        let looping = ECHO_MODULE.replace("(i64.or", "(loop $l (br $l)) (i64.or");
+        let looping = WasmTransform::from_bytes("loop", looping.as_bytes()).unwrap();
+        assert!(looping.transform(b"[]").is_err());
+
+        // 输出长度超出线性内存时在分配前拒绝
+        let oversized = ECHO_MODULE.replace("(i64.extend_i32_u (local.get $len))", "(i64.const 0xffffffff)");
+        let oversized = WasmTransform::from_bytes("oversized", oversized.as_bytes()).unwrap();
+        assert!(oversized.transform(b"[]").is_err());
+    }
+}
@@ -0,0 +1,454 @@
+use axum::{
+    Router,
+    routing::{get, post, put},
+    extract::{Path, Query, State},
+    response::Json,
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::api::ApiResponse;
+use super::auth::{audit_entry, AdminUser};
+use super::cache::{CacheStats, DataCache, EntryStatus};
+use super::listener::{BindTarget, Rebinder};
+use super::websocket::{WebSocketHub, WebSocketStats};
+use super::maintenance_mode::{MaintenanceMode, MaintenanceStatus, DEFAULT_RETRY_AFTER_SECONDS};
+use super::AppState;
+use crate::audit::{AuditEntry, AuditLog, AuditQuery};
+use crate::build_info::BuildInfo;
+use crate::feature_flags::{self, FeatureFlags, FlagStatus};
+use crate::models::{source_usage, MetricValue, SourceUsage};
+use crate::startup::Readiness;
+use crate::storage::{Invalidation, InvalidationTarget, MetricStore, StoreDiagnostics};
+use crate::tasks::{JobQueue, JobQueueStats, JobSource, TaskExecutionResult, TaskHealth, TaskManager};
+
+/// 创建管理路由
+/// 
+/// 所有接口都需要管理员权限，操作会写入审计日志
+/// 
+/// # 返回
+/// * `Router<AppState>` - 管理接口路由
+pub fn create_admin_routes() -> Router<AppState> {
+    Router::new()
+        // 运维概览
+        .route("/admin/overview", get(get_overview))
+        // 审计日志
+        .route("/admin/audit", get(get_audit_log))
+        // 立即执行任务
+        .route("/admin/tasks/:id/run", post(run_task))
+        // 任务队列状态
+        .route("/admin/jobs", get(get_job_stats))
+        // 指标存储运行状况
+        .route("/admin/storage", get(get_storage_diagnostics))
+        // 作废指标数据点（可同时写入更正值）
+        .route("/admin/metrics/invalidate", post(invalidate_metric_points))
+        // 撤销数据点的作废
+        .route("/admin/metrics/restore", post(restore_metric_points))
+        // 作废记录
+        .route("/admin/metrics/invalidations", get(get_invalidations))
+        // 清空缓存
+        .route("/admin/cache/clear", post(clear_cache))
+        // 当前监听地址
+        .route("/admin/server/listener", get(get_listener))
+        // 切换监听地址
+        .route("/admin/server/rebind", post(rebind_listener))
+        // 维护模式
+        .route("/admin/maintenance", get(get_maintenance).put(set_maintenance))
+        // WebSocket推送统计
+        .route("/admin/websocket", get(get_websocket_stats))
+        // 功能开关
+        .route("/admin/feature-flags", get(get_feature_flags))
+        // 覆盖或恢复单个功能开关
+        .route("/admin/feature-flags/:name", put(set_feature_flag).delete(clear_feature_flag))
+}
+
+/// 指标存储概况
+#[derive(Debug, Serialize)]
+pub struct StorageOverview {
+    /// 数据文件和本地归档占用的磁盘空间（字节）
+    pub disk_bytes: u64,
+    /// 运行状况
+    #[serde(flatten)]
+    pub diagnostics: StoreDiagnostics,
+}
+
+/// 运维概览
+///
+/// 汇总任务、数据源、存储和缓存的运行状况，供运维页面快速人工检查；
+/// 需要长期趋势时使用 `/metrics` 和运行指标
+#[derive(Debug, Serialize)]
+pub struct AdminOverview {
+    /// 生成时间
+    pub generated_at: DateTime<Utc>,
+    /// 进程启动时间
+    pub started_at: DateTime<Utc>,
+    /// 已运行时间（秒）
+    pub uptime_seconds: i64,
+    /// 编译信息
+    pub build: BuildInfo,
+    /// 全部必需依赖是否已就绪
+    pub ready: bool,
+    /// 维护模式状态
+    pub maintenance: MaintenanceStatus,
+    /// 各任务的最后执行情况和错误
+    pub tasks: Vec<TaskHealth>,
+    /// 任务队列状态
+    pub jobs: JobQueueStats,
+    /// 最近一次采集失败的数据集
+    pub failing_datasets: BTreeMap<String, EntryStatus>,
+    /// 各数据源的调用次数和消耗的积分
+    pub sources: BTreeMap<&'static str, SourceUsage>,
+    /// 指标存储概况
+    pub storage: StorageOverview,
+    /// 缓存统计
+    pub cache: CacheStats,
+    /// WebSocket推送统计
+    pub websocket: WebSocketStats,
+}
+
+/// 获取运维概览
+#[allow(clippy::too_many_arguments)]
+async fn get_overview(
+    _admin: AdminUser,
+    State(tasks): State<TaskManager>,
+    State(jobs): State<JobQueue>,
+    State(cache): State<Arc<DataCache>>,
+    State(metrics): State<Arc<MetricStore>>,
+    State(readiness): State<Arc<Readiness>>,
+    State(maintenance): State<Arc<MaintenanceMode>>,
+    State(hub): State<Arc<WebSocketHub>>,
+) -> Json<ApiResponse<AdminOverview>> {
+    let now = Utc::now();
+    let report = readiness.report().await;
+    let failing_datasets = cache.get_all_entry_status().await.into_iter()
+        .filter(|(_, status)| status.consecutive_failures > 0)
+        .collect();
+    Json(ApiResponse::success(AdminOverview {
+        generated_at: now,
+        started_at: report.started_at,
+        uptime_seconds: (now - report.started_at).num_seconds(),
+        build: BuildInfo::current(),
+        ready: report.ready,
+        maintenance: maintenance.status().await,
+        tasks: tasks.get_task_health().await,
+        jobs: jobs.stats(),
+        failing_datasets,
+        sources: source_usage(),
+        storage: StorageOverview {
+            disk_bytes: metrics.disk_bytes().await,
+            diagnostics: metrics.diagnostics().await,
+        },
+        cache: cache.get_stats().await,
+        websocket: hub.stats(),
+    }))
+}
+
+/// 查询审计日志
+async fn get_audit_log(
+    _admin: AdminUser,
+    State(audit): State<Arc<AuditLog>>,
+    Query(query): Query<AuditQuery>,
+) -> Json<ApiResponse<Vec<AuditEntry>>> {
+    Json(ApiResponse::success(audit.query(&query).await))
+}
+
+/// 立即执行任务
+/// 
+/// 以最高优先级放入任务队列并等待执行结果
+async fn run_task(
+    AdminUser(admin): AdminUser,
+    State(jobs): State<JobQueue>,
+    State(audit): State<Arc<AuditLog>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<ApiResponse<TaskExecutionResult>>, StatusCode> {
+    match jobs.run_now(&task_id, JobSource::Manual).await {
+        Ok(result) => {
+            audit.record(
+                audit_entry(&Some(admin), "task.trigger")
+                    .target(&task_id)
+                    .payload(serde_json::json!({ "success": result.success, "error": result.error }))
+            ).await;
+            Ok(Json(ApiResponse::success(result)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 获取任务队列状态
+async fn get_job_stats(
+    _admin: AdminUser,
+    State(jobs): State<JobQueue>,
+) -> Json<ApiResponse<JobQueueStats>> {
+    Json(ApiResponse::success(jobs.stats()))
+}
+
+/// 获取WebSocket推送统计（连接数、已发送和丢弃的消息数）
+async fn get_websocket_stats(
+    _admin: AdminUser,
+    State(hub): State<Arc<WebSocketHub>>,
+) -> Json<ApiResponse<WebSocketStats>> {
+    Json(ApiResponse::success(hub.stats()))
+}
+
+/// 获取指标存储运行状况（数据规模、慢查询次数、锁等待时间）
+async fn get_storage_diagnostics(
+    _admin: AdminUser,
+    State(metrics): State<Arc<MetricStore>>,
+) -> Json<ApiResponse<StoreDiagnostics>> {
+    Json(ApiResponse::success(metrics.diagnostics().await))
+}
+
+/// 作废数据点请求
+#[derive(Debug, Deserialize)]
+pub struct InvalidateRequest {
+    /// 要作废的数据点
+    #[serde(flatten)]
+    pub target: InvalidationTarget,
+    /// 作废原因
+    pub reason: String,
+    /// 更正后的数值（可选，只能选择一个数据点）
+    pub corrected_value: Option<MetricValue>,
+}
+
+/// 撤销作废请求
+#[derive(Debug, Deserialize)]
+pub struct RestoreRequest {
+    /// 数据点ID
+    pub ids: Vec<Uuid>,
+}
+
+/// 作废记录查询参数
+#[derive(Debug, Deserialize)]
+pub struct InvalidationQuery {
+    /// 指标名称（可选）
+    pub metric_name: Option<String>,
+}
+
+/// 切换监听地址请求
+///
+/// 设置 `unix_socket` 时监听Unix套接字，否则监听 `host:port`，未提供的主机和端口沿用当前值
+#[derive(Debug, Deserialize)]
+pub struct RebindRequest {
+    /// 监听主机
+    pub host: Option<String>,
+    /// 监听端口
+    pub port: Option<u16>,
+    /// Unix套接字路径
+    pub unix_socket: Option<String>,
+}
+
+/// 维护模式开关请求
+#[derive(Debug, Deserialize)]
+pub struct MaintenanceRequest {
+    /// 是否开启维护模式
+    pub enabled: bool,
+    /// 返回给客户端的说明（可选）
+    pub message: Option<String>,
+    /// 建议客户端重试的间隔（秒，默认300）
+    pub retry_after_seconds: Option<u64>,
+}
+
+/// 功能开关覆盖请求
+#[derive(Debug, Deserialize)]
+pub struct FeatureFlagRequest {
+    /// 是否开启
+    pub enabled: bool,
+}
+
+/// 作废指标数据点
+///
+/// 数据点不会被物理删除，默认查询将其排除，查询时加 `include_invalid=true` 仍可取回
+async fn invalidate_metric_points(
+    AdminUser(admin): AdminUser,
+    State(metrics): State<Arc<MetricStore>>,
+    State(audit): State<Arc<AuditLog>>,
+    Json(request): Json<InvalidateRequest>,
+) -> Json<ApiResponse<Vec<Invalidation>>> {
+    if request.reason.trim().is_empty() {
+        return Json(ApiResponse::error("需要填写作废原因"));
+    }
+    let operator = admin.username.clone();
+    match metrics.invalidate(&request.target, request.reason.trim(), Some(&operator), request.corrected_value).await {
+        Ok(records) => {
+            audit.record(
+                audit_entry(&Some(admin), "metric.invalidate")
+                    .target(&request.target.metric_name)
+                    .payload(serde_json::json!({
+                        "reason": request.reason.trim(),
+                        "ids": records.iter().map(|r| r.id).collect::<Vec<_>>(),
+                        "corrected_by": records.first().and_then(|r| r.corrected_by),
+                    }))
+            ).await;
+            Json(ApiResponse::success(records))
+        }
+        Err(e) => Json(ApiResponse::error(e.to_string())),
+    }
+}
+
+/// 撤销数据点的作废
+async fn restore_metric_points(
+    AdminUser(admin): AdminUser,
+    State(metrics): State<Arc<MetricStore>>,
+    State(audit): State<Arc<AuditLog>>,
+    Json(request): Json<RestoreRequest>,
+) -> Json<ApiResponse<Vec<Invalidation>>> {
+    let ids: HashSet<Uuid> = request.ids.into_iter().collect();
+    match metrics.restore_invalidated(&ids).await {
+        Ok(restored) => {
+            audit.record(
+                audit_entry(&Some(admin), "metric.restore")
+                    .payload(serde_json::json!({ "ids": restored.iter().map(|r| r.id).collect::<Vec<_>>() }))
+            ).await;
+            Json(ApiResponse::success(restored))
+        }
+        Err(e) => Json(ApiResponse::error(e.to_string())),
+    }
+}
+
+/// 查询作废记录
+async fn get_invalidations(
+    _admin: AdminUser,
+    State(metrics): State<Arc<MetricStore>>,
+    Query(query): Query<InvalidationQuery>,
+) -> Json<ApiResponse<Vec<Invalidation>>> {
+    Json(ApiResponse::success(metrics.invalidations(query.metric_name.as_deref()).await))
+}
+
+/// 清空缓存
+async fn clear_cache(
+    AdminUser(admin): AdminUser,
+    State(cache): State<Arc<DataCache>>,
+    State(audit): State<Arc<AuditLog>>,
+) -> Json<ApiResponse<usize>> {
+    let cleared = cache.size().await;
+    cache.clear_all().await;
+    audit.record(
+        audit_entry(&Some(admin), "cache.clear").payload(serde_json::json!({ "entries": cleared }))
+    ).await;
+    Json(ApiResponse::success(cleared))
+}
+
+/// 获取当前配置的监听地址
+async fn get_listener(
+    _admin: AdminUser,
+    State(rebinder): State<Arc<Rebinder>>,
+) -> Json<ApiResponse<BindTarget>> {
+    Json(ApiResponse::success(rebinder.current().await))
+}
+
+/// 切换监听地址
+///
+/// 新地址绑定成功后才替换原监听器，原监听器上的连接处理完当前请求后关闭；
+/// 修改只在本次运行中生效，重启后仍使用配置文件中的地址
+async fn rebind_listener(
+    AdminUser(admin): AdminUser,
+    State(rebinder): State<Arc<Rebinder>>,
+    State(audit): State<Arc<AuditLog>>,
+    Json(request): Json<RebindRequest>,
+) -> Json<ApiResponse<String>> {
+    let current = rebinder.current().await;
+    let target = BindTarget {
+        host: request.host.unwrap_or(current.host),
+        port: request.port.unwrap_or(current.port),
+        unix_socket: request.unix_socket.filter(|path| !path.trim().is_empty()),
+    };
+    match rebinder.rebind(target.clone()).await {
+        Ok(address) => {
+            audit.record(
+                audit_entry(&Some(admin), "server.rebind")
+                    .target(&address)
+                    .payload(serde_json::json!(target))
+            ).await;
+            Json(ApiResponse::success(address))
+        }
+        Err(e) => Json(ApiResponse::error(e.to_string())),
+    }
+}
+
+/// 获取维护模式状态
+async fn get_maintenance(
+    _admin: AdminUser,
+    State(maintenance): State<Arc<MaintenanceMode>>,
+) -> Json<ApiResponse<MaintenanceStatus>> {
+    Json(ApiResponse::success(maintenance.status().await))
+}
+
+/// 开启或关闭维护模式
+///
+/// 维护期间数据接口返回503，调度器和采集任务继续运行
+async fn set_maintenance(
+    AdminUser(admin): AdminUser,
+    State(maintenance): State<Arc<MaintenanceMode>>,
+    State(audit): State<Arc<AuditLog>>,
+    Json(request): Json<MaintenanceRequest>,
+) -> Json<ApiResponse<MaintenanceStatus>> {
+    let status = if request.enabled {
+        let message = request.message.map(|m| m.trim().to_string()).filter(|m| !m.is_empty());
+        let retry_after = request.retry_after_seconds.unwrap_or(DEFAULT_RETRY_AFTER_SECONDS);
+        maintenance.enable(message, retry_after, &admin.username).await
+    } else {
+        maintenance.disable().await
+    };
+    audit.record(
+        audit_entry(&Some(admin), if request.enabled { "maintenance.enable" } else { "maintenance.disable" })
+            .payload(serde_json::json!({ "message": status.message, "retry_after_seconds": status.retry_after_seconds }))
+    ).await;
+    Json(ApiResponse::success(status))
+}
+
+/// 获取所有功能开关的状态
+async fn get_feature_flags(
+    _admin: AdminUser,
+    State(flags): State<Arc<FeatureFlags>>,
+) -> Json<ApiResponse<Vec<FlagStatus>>> {
+    Json(ApiResponse::success(flags.list()))
+}
+
+/// 覆盖功能开关
+///
+/// 覆盖值优先于配置文件，重启后仍然生效
+async fn set_feature_flag(
+    AdminUser(admin): AdminUser,
+    State(flags): State<Arc<FeatureFlags>>,
+    State(audit): State<Arc<AuditLog>>,
+    Path(name): Path<String>,
+    Json(request): Json<FeatureFlagRequest>,
+) -> Result<Json<ApiResponse<FlagStatus>>, StatusCode> {
+    if feature_flags::definition(&name).is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    match flags.set_override(&name, request.enabled, &admin.username).await {
+        Ok(status) => {
+            audit.record(
+                audit_entry(&Some(admin), "feature_flag.override")
+                    .target(&name)
+                    .payload(serde_json::json!({ "enabled": request.enabled }))
+            ).await;
+            Ok(Json(ApiResponse::success(status)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 清除功能开关的覆盖值，恢复为配置值或默认值
+async fn clear_feature_flag(
+    AdminUser(admin): AdminUser,
+    State(flags): State<Arc<FeatureFlags>>,
+    State(audit): State<Arc<AuditLog>>,
+    Path(name): Path<String>,
+) -> Result<Json<ApiResponse<FlagStatus>>, StatusCode> {
+    if feature_flags::definition(&name).is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    match flags.clear_override(&name).await {
+        Ok(status) => {
+            audit.record(audit_entry(&Some(admin), "feature_flag.clear").target(&name)).await;
+            Ok(Json(ApiResponse::success(status)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
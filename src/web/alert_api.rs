@@ -0,0 +1,239 @@
+use axum::{
+    Router,
+    routing::{get, post},
+    extract::{Path, Query, State},
+    response::Json,
+    http::StatusCode,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::api::ApiResponse;
+use super::auth::{audit_entry, user_id, AuthUser, MaybeAuthUser};
+use super::AppState;
+use crate::alerts::{Alert, AlertManager, AlertRule, AlertRuleInput, AlertRuleStore, AnomalyEvent, AnomalyLog, RuleTrigger};
+use crate::audit::AuditLog;
+use crate::users::AuthService;
+
+/// 历史查询参数
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    /// 最多返回条数（默认50）
+    pub limit: Option<usize>,
+}
+
+/// 异常事件查询参数
+#[derive(Debug, Deserialize)]
+pub struct AnomalyQuery {
+    /// 只返回该指标的事件（可选）
+    pub metric: Option<String>,
+    /// 最多返回条数（默认50）
+    pub limit: Option<usize>,
+}
+
+/// 创建告警管理路由
+/// 
+/// 查看规则不需要登录，携带登录令牌时只能看到全局规则及自己的规则；
+/// 创建、编辑、暂停和删除规则需要登录，新建规则归属当前用户（管理员新建的为全局规则），全局规则只有管理员可以修改
+/// 
+/// # 返回
+/// * `Router<AppState>` - 告警规则的增删改查路由
+pub fn create_alert_routes() -> Router<AppState> {
+    Router::new()
+        // 规则列表 / 创建规则
+        .route("/alerts", get(list_rules).post(create_rule))
+        // 最近发送的告警
+        .route("/alerts/history", get(recent_alerts))
+        // 单条规则的查看 / 编辑 / 删除
+        .route("/alerts/:id", get(get_rule).put(update_rule).delete(delete_rule))
+        // 暂停 / 恢复规则
+        .route("/alerts/:id/pause", post(pause_rule))
+        .route("/alerts/:id/resume", post(resume_rule))
+        // 规则触发记录
+        .route("/alerts/:id/history", get(rule_history))
+        // 指标异常事件
+        .route("/anomalies", get(recent_anomalies))
+}
+
+/// 获取所有告警规则
+async fn list_rules(
+    MaybeAuthUser(user): MaybeAuthUser,
+    State(rules): State<Arc<AlertRuleStore>>,
+) -> Json<ApiResponse<Vec<AlertRule>>> {
+    let owner = user_id(&user);
+    let visible = rules.list().await
+        .into_iter()
+        .filter(|r| r.is_visible_to(owner))
+        .collect();
+    Json(ApiResponse::success(visible))
+}
+
+/// 校验规则对当前用户可见
+/// 
+/// 不可见的规则与不存在的规则返回相同的错误
+async fn check_access(rules: &AlertRuleStore, id: Uuid, user: &Option<Uuid>) -> Result<AlertRule, String> {
+    match rules.get(id).await {
+        Some(rule) if rule.is_visible_to(*user) => Ok(rule),
+        _ => Err(format!("规则 {} 不存在", id)),
+    }
+}
+
+/// 校验当前用户可以修改规则
+/// 
+/// 不可见的规则返回不存在；全局规则只有管理员可以修改，其他用户返回403
+async fn check_modify(
+    rules: &AlertRuleStore,
+    id: Uuid,
+    user: &AuthUser,
+    auth: &AuthService,
+) -> Result<Result<AlertRule, String>, StatusCode> {
+    let rule = match check_access(rules, id, &Some(user.id)).await {
+        Ok(rule) => rule,
+        Err(e) => return Ok(Err(e)),
+    };
+    if !user.can_modify(rule.owner, auth) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(Ok(rule))
+}
+
+/// 获取单条告警规则
+async fn get_rule(
+    MaybeAuthUser(user): MaybeAuthUser,
+    State(rules): State<Arc<AlertRuleStore>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<AlertRule>>, StatusCode> {
+    match check_access(&rules, id, &user_id(&user)).await {
+        Ok(rule) => Ok(Json(ApiResponse::success(rule))),
+        Err(e) => Ok(Json(ApiResponse::error(e))),
+    }
+}
+
+/// 创建告警规则
+async fn create_rule(
+    user: AuthUser,
+    State(rules): State<Arc<AlertRuleStore>>,
+    State(auth): State<Arc<AuthService>>,
+    State(audit): State<Arc<AuditLog>>,
+    Json(input): Json<AlertRuleInput>,
+) -> Result<Json<ApiResponse<AlertRule>>, StatusCode> {
+    let owner = (!user.is_admin(&auth)).then_some(user.id);
+    match rules.create(owner, input).await {
+        Ok(rule) => {
+            audit.record(audit_entry(&Some(user), "alert_rule.create").target(rule.id).payload(&rule)).await;
+            Ok(Json(ApiResponse::success(rule)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 编辑告警规则
+async fn update_rule(
+    user: AuthUser,
+    State(rules): State<Arc<AlertRuleStore>>,
+    State(auth): State<Arc<AuthService>>,
+    State(audit): State<Arc<AuditLog>>,
+    Path(id): Path<Uuid>,
+    Json(input): Json<AlertRuleInput>,
+) -> Result<Json<ApiResponse<AlertRule>>, StatusCode> {
+    if let Err(e) = check_modify(&rules, id, &user, &auth).await? {
+        return Ok(Json(ApiResponse::error(e)));
+    }
+    match rules.update(id, input).await {
+        Ok(rule) => {
+            audit.record(audit_entry(&Some(user), "alert_rule.update").target(rule.id).payload(&rule)).await;
+            Ok(Json(ApiResponse::success(rule)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 暂停告警规则
+async fn pause_rule(
+    user: AuthUser,
+    State(rules): State<Arc<AlertRuleStore>>,
+    State(auth): State<Arc<AuthService>>,
+    State(audit): State<Arc<AuditLog>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<AlertRule>>, StatusCode> {
+    if let Err(e) = check_modify(&rules, id, &user, &auth).await? {
+        return Ok(Json(ApiResponse::error(e)));
+    }
+    match rules.set_enabled(id, false).await {
+        Ok(rule) => {
+            audit.record(audit_entry(&Some(user), "alert_rule.pause").target(rule.id).payload(&rule)).await;
+            Ok(Json(ApiResponse::success(rule)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 恢复告警规则
+async fn resume_rule(
+    user: AuthUser,
+    State(rules): State<Arc<AlertRuleStore>>,
+    State(auth): State<Arc<AuthService>>,
+    State(audit): State<Arc<AuditLog>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<AlertRule>>, StatusCode> {
+    if let Err(e) = check_modify(&rules, id, &user, &auth).await? {
+        return Ok(Json(ApiResponse::error(e)));
+    }
+    match rules.set_enabled(id, true).await {
+        Ok(rule) => {
+            audit.record(audit_entry(&Some(user), "alert_rule.resume").target(rule.id).payload(&rule)).await;
+            Ok(Json(ApiResponse::success(rule)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 删除告警规则
+async fn delete_rule(
+    user: AuthUser,
+    State(rules): State<Arc<AlertRuleStore>>,
+    State(auth): State<Arc<AuthService>>,
+    State(audit): State<Arc<AuditLog>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<AlertRule>>, StatusCode> {
+    if let Err(e) = check_modify(&rules, id, &user, &auth).await? {
+        return Ok(Json(ApiResponse::error(e)));
+    }
+    match rules.delete(id).await {
+        Ok(rule) => {
+            audit.record(audit_entry(&Some(user), "alert_rule.delete").target(rule.id).payload(&rule)).await;
+            Ok(Json(ApiResponse::success(rule)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 获取规则触发记录
+async fn rule_history(
+    MaybeAuthUser(user): MaybeAuthUser,
+    State(rules): State<Arc<AlertRuleStore>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<ApiResponse<Vec<RuleTrigger>>>, StatusCode> {
+    if let Err(e) = check_access(&rules, id, &user_id(&user)).await {
+        return Ok(Json(ApiResponse::error(e)));
+    }
+    Ok(Json(ApiResponse::success(rules.triggers(id, query.limit.unwrap_or(50)).await)))
+}
+
+/// 获取最近发送的告警
+async fn recent_alerts(
+    State(alert_manager): State<Arc<AlertManager>>,
+    Query(query): Query<HistoryQuery>,
+) -> Json<ApiResponse<Vec<Alert>>> {
+    Json(ApiResponse::success(alert_manager.recent_alerts(query.limit.unwrap_or(50)).await))
+}
+
+/// 获取最近检测到的指标异常
+async fn recent_anomalies(
+    State(anomalies): State<Arc<AnomalyLog>>,
+    Query(query): Query<AnomalyQuery>,
+) -> Json<ApiResponse<Vec<AnomalyEvent>>> {
+    Json(ApiResponse::success(anomalies.recent(query.metric.as_deref(), query.limit.unwrap_or(50)).await))
+}
@@ -1,17 +1,50 @@
 use axum::{
     Router,
+    middleware,
     routing::get,
-    extract::State,
-    response::Json,
-    http::StatusCode,
+    body::Body,
+    extract::{Query, State},
+    response::{IntoResponse, Json, Response},
+    http::{header::CONTENT_TYPE, StatusCode},
 };
+use bytes::Bytes;
 use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
-use super::cache::{DataCache, CachedMarketData, CacheStats};
+use super::AppState;
+use super::admin_api::create_admin_routes;
+use super::alert_api::create_alert_routes;
+use super::exchange_api::create_exchange_routes;
+use super::fields::{FieldsQuery, Sparse};
+use super::formatting::{FormatQuery, Humanized};
+use super::maintenance_mode::{maintenance_guard, MaintenanceMode};
+use super::read_only::read_only_guard;
+use super::metrics_api::create_metrics_routes;
+use super::portfolio_api::create_portfolio_routes;
+use super::refresh_api::create_refresh_routes;
+use super::simulate_api::create_simulate_routes;
+use super::ingest_api::create_ingest_routes;
+use super::tenant_api::{create_tenant_routes, tenant_guard};
+use super::user_api::create_user_routes;
+use super::watch_api::create_watch_routes;
+use super::cache::{
+    DataCache, CachedMarketData, CacheStats, EntryStatus,
+    FEAR_GREED_KEY, ALTCOIN_SEASON_KEY, MARKET_AGGREGATES_KEY, MARKET_BREADTH_KEY, SECTORS_KEY, RATIOS_KEY, BTC_CYCLE_KEY, EXCHANGE_FLOWS_KEY, OPTIONS_KEY, ETF_FLOWS_KEY, LIQUIDATIONS_KEY, LIQUIDITY_KEY, NFT_COLLECTIONS_KEY, NETWORK_KEY, NODES_KEY, CALENDAR_KEY, SOCIAL_KEY, TRENDS_KEY, market_data_key,
+};
+use crate::analytics::{current_sentiment, CompositeSentiment, CyclePosition, ExchangeFlows, LiquiditySnapshot, MarketAggregates, MarketBreadth, NodeStatus, OptionsData, RatioReading, SectorSnapshot, SocialSentiment};
 // 新增：导入山寨季节指数类型
-use crate::clients::coinmarketcap_client::AltcoinSeasonIndex;
+use crate::clients::coinmarketcap_client::{AltcoinSeasonIndex, FearGreedSnapshot};
+use crate::build_info::BuildInfo;
+use crate::config::SentimentConfig;
+use crate::feature_flags::{FeatureFlags, COMPOSITE_SENTIMENT};
+use crate::calendar::{EconomicCalendar, EconomicEvent, EventImportance};
+use crate::indicators::{indicator_metric_name, COIN_INDICATORS};
+use crate::tasks::{liquidity_metric_name, network_metric_name, nft_metric_name, NetworkSnapshot, NftCollectionSnapshot};
+use crate::models::{registered_sources, DataSource, MetricValue};
+use crate::startup::{Readiness, ReadinessReport};
+use crate::storage::{MetricQuery, MetricStore, QueryLimitError};
+use crate::history::{AltcoinSeasonHistory, ChangeEvent, ChangeHistory, ChangeQuery, ConstituentSnapshot, EtfFlowHistory, EtfFlowSeries, FearGreedDelta, FearGreedHistory, FearGreedReading, LiquidationHistory, LiquidationSeries, RankClimber, RankHistory, RankPoint, SeasonPoint, SeasonState, TrendSeries, TrendsHistory};
 
 /// API响应结构
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,6 +57,9 @@ pub struct ApiResponse<T> {
     /// 错误信息
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    /// 数据集状态（最后成功/失败时间、是否为过期数据）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<EntryStatus>,
     /// 响应时间戳
     pub timestamp: DateTime<Utc>,
 }
@@ -35,6 +71,7 @@ impl<T> ApiResponse<T> {
             success: true,
             data: Some(data),
             message: None,
+            status: None,
             timestamp: Utc::now(),
         }
     }
@@ -45,67 +82,252 @@ impl<T> ApiResponse<T> {
             success: false,
             data: None,
             message: Some(message.into()),
+            status: None,
             timestamp: Utc::now(),
         }
     }
+    
+    /// 附加数据集状态
+    pub fn with_status(mut self, status: Option<EntryStatus>) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+/// 用已序列化的JSON作为 `data` 构造成功响应
+/// 
+/// 响应体由信封片段和 `data` 分块组成，`data` 的字节直接共享、不复制，
+/// 格式与 `ApiResponse::success` 相同
+/// 
+/// # 参数
+/// * `data` - 已序列化的JSON
+pub fn raw_json_success(data: Bytes) -> Response {
+    let timestamp = serde_json::to_string(&Utc::now()).unwrap_or_default();
+    let chunks = [
+        Bytes::from_static(br#"{"success":true,"data":"#),
+        data,
+        Bytes::from(format!(r#","timestamp":{}}}"#, timestamp)),
+    ];
+    let body = Body::from_stream(futures_util::stream::iter(chunks.map(Ok::<_, std::convert::Infallible>)));
+    ([(CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+/// 可能因查询超出限制而返回HTTP 400的响应
+pub type LimitedResponse<T> = Result<Json<ApiResponse<T>>, (StatusCode, Json<ApiResponse<T>>)>;
+
+/// 查询超出限制时的HTTP 400响应，错误信息提示客户端分页或分桶
+pub fn query_limit_rejection<T>(err: QueryLimitError) -> (StatusCode, Json<ApiResponse<T>>) {
+    (StatusCode::BAD_REQUEST, Json(ApiResponse::error(err.to_string())))
 }
 
 /// 创建API路由
 /// 
 /// # 参数
-/// * `cache` - 数据缓存
+/// * `state` - 应用共享状态
 /// 
 /// # 返回
-/// * `Router<Arc<DataCache>>` - 配置好的API路由器
+/// * `Router<AppState>` - 配置好的API路由器
 pub fn create_api_routes(
-    cache: Arc<DataCache>,
-) -> Router<Arc<DataCache>> {
+    state: AppState,
+) -> Router<AppState> {
     Router::new()
         // 健康检查端点
         .route("/health", get(health_check))
+        // 就绪检查端点（必需依赖未就绪时返回503）
+        .route("/ready", get(readiness_check))
+        // 版本和编译信息
+        .route("/version", get(get_version))
         // 获取所有市场数据
         .route("/market-data", get(get_all_market_data))
         // 获取特定币种数据
         .route("/market-data/:coin_id", get(get_market_data))
+        // 获取币种技术指标历史
+        .route("/coins/:coin_id/indicators", get(get_coin_indicators))
+        // 获取币种每日市值排名
+        .route("/coins/:coin_id/rank-history", get(get_rank_history))
+        // 获取排名上升最多的币种
+        .route("/coins/rank-climbers", get(get_rank_climbers))
         // 获取缓存统计信息
         .route("/cache-stats", get(get_cache_stats))
+        // 已登记的数据源
+        .route("/sources", get(get_sources))
         // 获取恐惧贪婪指数
         .route("/fear-greed-index", get(get_fear_greed_index))
+        // 获取恐惧贪婪指数历史
+        .route("/fear-greed-index/history", get(get_fear_greed_history))
         // 获取山寨币季节指数
         .route("/altcoin-season-index", get(get_altcoin_season_index))
-        .with_state(cache)
+        // 获取山寨币季节指数历史
+        .route("/altcoin-season-index/history", get(get_altcoin_season_history))
+        // 获取山寨币季节指数的成分币种快照
+        .route("/altcoin-season-index/constituents", get(get_altcoin_season_constituents))
+        // 获取汇总市值（Total/Total2/Total3）
+        .route("/aggregates", get(get_market_aggregates))
+        // 获取市场广度
+        .route("/breadth", get(get_market_breadth))
+        // 获取板块统计
+        .route("/sectors", get(get_sectors))
+        // 获取价格比率
+        .route("/ratios", get(get_ratios))
+        // 获取比特币减半周期位置
+        .route("/cycle", get(get_btc_cycle))
+        // 获取交易所资金流向
+        .route("/exchange-flows", get(get_exchange_flows))
+        // 获取期权隐含波动率和看跌/看涨比
+        .route("/options", get(get_options))
+        // 获取现货ETF每日资金流向
+        .route("/etf-flows", get(get_etf_flows))
+        // 获取合约每小时多/空爆仓金额
+        .route("/liquidations", get(get_liquidations))
+        // 获取交易对的买卖价差和订单簿深度
+        .route("/liquidity/:symbol", get(get_liquidity))
+        // 获取NFT合集地板价和成交额
+        .route("/nft/:collection", get(get_nft_collection))
+        // 获取算力、难度或质押收益率
+        .route("/network/:chain", get(get_network))
+        // 获取自建节点的健康状态
+        .route("/nodes", get(get_nodes))
+        // 获取数据集变化事件（CMC排名、流通量、标签变化）
+        .route("/events", get(get_change_events))
+        // 获取即将发生的宏观经济事件
+        .route("/calendar", get(get_calendar))
+        // 获取综合情绪分
+        .route("/sentiment", get(get_composite_sentiment))
+        // 获取监控币种的社交热度与情绪
+        .route("/social", get(get_social_sentiment))
+        // 获取搜索词的Google Trends每周热度
+        .route("/trends", get(get_trends))
+        // 告警规则管理
+        .merge(create_alert_routes())
+        // 价格目标监控
+        .merge(create_watch_routes())
+        // 用户与个人数据
+        .merge(create_user_routes())
+        // 投资组合
+        .merge(create_portfolio_routes())
+        // 交易所账户导入
+        .merge(create_exchange_routes())
+        // 指标批量查询
+        .merge(create_metrics_routes())
+        // 按需刷新数据集
+        .merge(create_refresh_routes())
+        // 外部系统推送指标
+        .merge(create_ingest_routes())
+        // 情景模拟
+        .merge(create_simulate_routes())
+        // 管理接口
+        .merge(create_admin_routes())
+        // 租户
+        .merge(create_tenant_routes())
+        // 租户API密钥校验和限流
+        .layer(middleware::from_fn_with_state(state.clone(), tenant_guard))
+        // 只读API实例拒绝写入请求
+        .layer(middleware::from_fn_with_state(state.clone(), read_only_guard))
+        // 维护模式（在租户限流之前拦截，维护期间的请求不计入用量）
+        .layer(middleware::from_fn_with_state(state.clone(), maintenance_guard))
+        .with_state(state)
 }
 
 /// 健康检查端点
-async fn health_check() -> Json<ApiResponse<serde_json::Value>> {
+/// 
+/// 任一数据集正在提供过期数据时整体状态为 `degraded`；维护模式下为 `maintenance`
+async fn health_check(
+    State(cache): State<Arc<DataCache>>,
+    State(maintenance): State<Arc<MaintenanceMode>>,
+    State(readiness): State<Arc<Readiness>>,
+) -> Json<ApiResponse<serde_json::Value>> {
+    let datasets = cache.get_all_entry_status().await;
+    let degraded = datasets.values().any(|status| status.stale || status.consecutive_failures > 0);
+    let status = match (maintenance.is_enabled().await, degraded) {
+        (true, _) => "maintenance",
+        (false, true) => "degraded",
+        (false, false) => "healthy",
+    };
+    
     Json(ApiResponse::success(serde_json::json!({
-        "status": "healthy",
+        "status": status,
+        "ready": readiness.is_ready().await,
         "service": "EverScan API",
-        "version": "1.0.0"
+        "version": env!("CARGO_PKG_VERSION"),
+        "datasets": datasets
     })))
 }
 
+/// 版本和编译信息
+///
+/// 返回版本号、git提交、编译时间和启用的特性，用于识别已部署实例的具体构建
+async fn get_version() -> Json<ApiResponse<BuildInfo>> {
+    Json(ApiResponse::success(BuildInfo::current()))
+}
+
+/// 就绪检查端点
+///
+/// 启动时Web服务器先提供服务，上游和事件总线等依赖在后台初始化；
+/// 必需依赖全部就绪前返回503，非必需依赖未就绪时返回200并标记 `degraded`
+async fn readiness_check(
+    State(readiness): State<Arc<Readiness>>,
+) -> (StatusCode, Json<ApiResponse<ReadinessReport>>) {
+    let report = readiness.report().await;
+    let status = if report.ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(ApiResponse::success(report)))
+}
+
 /// 获取所有市场数据
+///
+/// 支持 `?fields=price,rsi,market_cap` 只返回指定字段，`?format=human`（可加 `&tz=`）附加可读字符串；
+/// 都不指定时直接返回缓存中预序列化的JSON，不为每个请求重新序列化
 async fn get_all_market_data(
     State(cache): State<Arc<DataCache>>,
-) -> Result<Json<ApiResponse<Vec<CachedMarketData>>>, StatusCode> {
-    let market_data = cache.get_all_market_data();
+    Query(query): Query<FieldsQuery>,
+    Query(format): Query<FormatQuery>,
+) -> Response {
+    let tz = match format.resolve() {
+        Ok(tz) => tz,
+        Err(message) => return Json(ApiResponse::<()>::error(message)).into_response(),
+    };
+    let fields = match query.market_data() {
+        Ok(None) if tz.is_none() => return match cache.get_all_market_data_json().await {
+            Ok(json) if json.as_ref() == b"[]" => Json(ApiResponse::<()>::error("暂无市场数据")).into_response(),
+            Ok(json) => raw_json_success(json),
+            Err(e) => Json(ApiResponse::<()>::error(format!("序列化市场数据失败: {}", e))).into_response(),
+        },
+        Ok(fields) => fields,
+        Err(message) => return Json(ApiResponse::<()>::error(message)).into_response(),
+    };
+    let market_data = cache.get_all_market_data().await;
     
     if market_data.is_empty() {
-        return Ok(Json(ApiResponse::error("暂无市场数据")));
+        return Json(ApiResponse::<()>::error("暂无市场数据")).into_response();
     }
     
-    Ok(Json(ApiResponse::success(market_data)))
+    Json(ApiResponse::success(
+        market_data.into_iter()
+            .map(|data| Humanized::new(Sparse::new(data, fields.clone()), tz))
+            .collect::<Vec<_>>()
+    )).into_response()
 }
 
 /// 获取特定币种的市场数据
+///
+/// 支持 `?fields=` 只返回指定字段，`?format=human`（可加 `&tz=`）附加可读字符串
 async fn get_market_data(
     State(cache): State<Arc<DataCache>>,
     axum::extract::Path(coin_id): axum::extract::Path<String>,
-) -> Result<Json<ApiResponse<CachedMarketData>>, StatusCode> {
-    match cache.get_market_data(&coin_id) {
-        Some(data) => Ok(Json(ApiResponse::success(data))),
-        None => Ok(Json(ApiResponse::error(format!("未找到币种 {} 的数据", coin_id)))),
+    Query(query): Query<FieldsQuery>,
+    Query(format): Query<FormatQuery>,
+) -> Result<Json<ApiResponse<Humanized<Sparse<CachedMarketData>>>>, StatusCode> {
+    let fields = match query.market_data() {
+        Ok(fields) => fields,
+        Err(message) => return Ok(Json(ApiResponse::error(message))),
+    };
+    let tz = match format.resolve() {
+        Ok(tz) => tz,
+        Err(message) => return Ok(Json(ApiResponse::error(message))),
+    };
+    let status = cache.get_entry_status(&market_data_key(&coin_id)).await;
+    match cache.get_market_data(&coin_id).await {
+        Some(data) => Ok(Json(ApiResponse::success(Humanized::new(Sparse::new(data, fields), tz)).with_status(status))),
+        None => Ok(Json(ApiResponse::error(format!("未找到币种 {} 的数据", coin_id)).with_status(status))),
     }
 }
 
@@ -113,26 +335,728 @@ async fn get_market_data(
 async fn get_cache_stats(
     State(cache): State<Arc<DataCache>>,
 ) -> Json<ApiResponse<CacheStats>> {
-    let stats = cache.get_stats();
+    let stats = cache.get_stats().await;
     Json(ApiResponse::success(stats))
 }
 
+/// 获取已登记的数据源及其类型和提供的数据类别
+async fn get_sources() -> Json<ApiResponse<Vec<DataSource>>> {
+    Json(ApiResponse::success(registered_sources()))
+}
+
+/// 恐惧贪婪指数响应
+#[derive(Debug, Serialize)]
+pub struct FearGreedIndexResponse {
+    /// 最新快照
+    #[serde(flatten)]
+    pub snapshot: FearGreedSnapshot,
+    /// 变化量
+    #[serde(flatten)]
+    pub delta: FearGreedDelta,
+}
+
 /// 获取恐惧贪婪指数
+/// 
+/// 附带相对24小时和7天前的变化字段 `change_24h`、`change_7d`
 async fn get_fear_greed_index(
     State(cache): State<Arc<DataCache>>,
-) -> Result<Json<ApiResponse<serde_json::Value>>, StatusCode> {
-    match cache.get_fear_greed_index() {
-        Some(data) => Ok(Json(ApiResponse::success(data))),
-        None => Ok(Json(ApiResponse::error("恐惧贪婪指数数据不可用"))),
+    State(history): State<Arc<FearGreedHistory>>,
+) -> Result<Json<ApiResponse<FearGreedIndexResponse>>, StatusCode> {
+    let status = cache.get_entry_status(FEAR_GREED_KEY).await;
+    match cache.get_fear_greed_index().await {
+        Some(snapshot) => {
+            let delta = history.latest_delta().await;
+            Ok(Json(ApiResponse::success(FearGreedIndexResponse { snapshot, delta }).with_status(status)))
+        }
+        None => Ok(Json(ApiResponse::error("恐惧贪婪指数数据不可用").with_status(status))),
     }
 }
 
+/// 获取恐惧贪婪指数历史
+async fn get_fear_greed_history(
+    State(history): State<Arc<FearGreedHistory>>,
+    Query(query): Query<IndexHistoryQuery>,
+) -> Json<ApiResponse<Vec<FearGreedReading>>> {
+    Json(ApiResponse::success(history.readings(query.since, query.limit.unwrap_or(500)).await))
+}
+
+/// 山寨币季节指数及当前阶段
+#[derive(Debug, Serialize)]
+pub struct AltcoinSeasonView {
+    /// 最新指数
+    #[serde(flatten)]
+    pub index: AltcoinSeasonIndex,
+    /// 阶段与趋势（尚无历史记录时为空）
+    #[serde(flatten)]
+    pub season: Option<SeasonState>,
+}
+
 /// 获取山寨币季节指数
 async fn get_altcoin_season_index(
     State(cache): State<Arc<DataCache>>,
-) -> Result<Json<ApiResponse<AltcoinSeasonIndex>>, StatusCode> {
-    match cache.get_altcoin_season_index() {
-        Some(data) => Ok(Json(ApiResponse::success(data))),
-        None => Ok(Json(ApiResponse::error("山寨币季节指数数据不可用"))),
+    State(history): State<Arc<AltcoinSeasonHistory>>,
+) -> Result<Json<ApiResponse<AltcoinSeasonView>>, StatusCode> {
+    let status = cache.get_entry_status(ALTCOIN_SEASON_KEY).await;
+    match cache.get_altcoin_season_index().await {
+        Some(index) => {
+            let view = AltcoinSeasonView { index, season: history.state().await };
+            Ok(Json(ApiResponse::success(view).with_status(status)))
+        }
+        None => Ok(Json(ApiResponse::error("山寨币季节指数数据不可用").with_status(status))),
+    }
+}
+
+/// 指数历史查询参数
+#[derive(Debug, Deserialize)]
+pub struct IndexHistoryQuery {
+    /// 起始时间（可选）
+    pub since: Option<DateTime<Utc>>,
+    /// 最多返回条数（默认500）
+    pub limit: Option<usize>,
+}
+
+/// 获取山寨币季节指数历史
+async fn get_altcoin_season_history(
+    State(history): State<Arc<AltcoinSeasonHistory>>,
+    Query(query): Query<IndexHistoryQuery>,
+) -> Json<ApiResponse<Vec<SeasonPoint>>> {
+    Json(ApiResponse::success(history.points(query.since, query.limit.unwrap_or(500)).await))
+}
+
+/// 成分币种快照查询参数
+#[derive(Debug, Deserialize)]
+pub struct ConstituentsQuery {
+    /// 返回该时间及之前最近的快照（默认最新）
+    pub at: Option<DateTime<Utc>>,
+}
+
+/// 获取山寨币季节指数的成分币种快照
+async fn get_altcoin_season_constituents(
+    State(history): State<Arc<AltcoinSeasonHistory>>,
+    Query(query): Query<ConstituentsQuery>,
+) -> Json<ApiResponse<ConstituentSnapshot>> {
+    match history.constituents(query.at).await {
+        Some(snapshot) => Json(ApiResponse::success(snapshot)),
+        None => Json(ApiResponse::error("暂无山寨币季节指数成分币种快照")),
+    }
+}
+
+/// 按时间戳合并多个标量指标的历史
+///
+/// # 参数
+/// * `metrics` - 指标存储
+/// * `names` - 指标名称
+/// * `query` - 查询条件
+///
+/// # 返回
+/// * `Vec<(DateTime<Utc>, Vec<Option<f64>>)>` - 按时间升序，每行的值与 `names` 一一对应
+async fn merged_history(metrics: &MetricStore, names: &[String], query: &MetricQuery) -> Vec<(DateTime<Utc>, Vec<Option<f64>>)> {
+    let mut rows: std::collections::BTreeMap<DateTime<Utc>, Vec<Option<f64>>> = std::collections::BTreeMap::new();
+    for (index, name) in names.iter().enumerate() {
+        for metric in metrics.query(name, query).await {
+            rows.entry(metric.timestamp).or_insert_with(|| vec![None; names.len()])[index] = metric.value.as_f64();
+        }
     }
-} 
\ No newline at end of file
+    rows.into_iter().collect()
+}
+
+/// 汇总市值查询参数
+#[derive(Debug, Deserialize)]
+pub struct AggregatesQuery {
+    /// 历史向前查询的天数（默认30）
+    pub days: Option<i64>,
+}
+
+/// 汇总市值历史数据点
+#[derive(Debug, Serialize)]
+pub struct AggregatePoint {
+    /// 采集时间
+    pub timestamp: DateTime<Utc>,
+    /// 总市值
+    pub total: Option<f64>,
+    /// 剔除BTC的总市值
+    pub total2: Option<f64>,
+    /// 剔除BTC和ETH的总市值
+    pub total3: Option<f64>,
+}
+
+/// 汇总市值响应
+#[derive(Debug, Serialize)]
+pub struct AggregatesResponse {
+    /// 最新汇总市值
+    pub latest: MarketAggregates,
+    /// 历史数据点（按时间升序）
+    pub history: Vec<AggregatePoint>,
+}
+
+/// 获取汇总市值（Total/Total2/Total3）及其历史
+///
+/// 支持 `?format=human`（可加 `&tz=`）附加可读字符串
+async fn get_market_aggregates(
+    State(cache): State<Arc<DataCache>>,
+    State(metrics): State<Arc<MetricStore>>,
+    Query(query): Query<AggregatesQuery>,
+    Query(format): Query<FormatQuery>,
+) -> Json<ApiResponse<Humanized<AggregatesResponse>>> {
+    let tz = match format.resolve() {
+        Ok(tz) => tz,
+        Err(message) => return Json(ApiResponse::error(message)),
+    };
+    let status = cache.get_entry_status(MARKET_AGGREGATES_KEY).await;
+    let Some(latest) = cache.get_dataset::<MarketAggregates>(MARKET_AGGREGATES_KEY).await else {
+        return Json(ApiResponse::error("汇总市值尚未计算").with_status(status));
+    };
+
+    let metric_query = MetricQuery {
+        since: Some(Utc::now() - chrono::Duration::days(query.days.unwrap_or(30).clamp(1, 3650))),
+        ..MetricQuery::default()
+    };
+    let names = ["market_cap_total", "market_cap_total2", "market_cap_total3"].map(String::from);
+    let history = merged_history(&metrics, &names, &metric_query).await
+        .into_iter()
+        .map(|(timestamp, values)| AggregatePoint {
+            timestamp,
+            total: values[0],
+            total2: values[1],
+            total3: values[2],
+        })
+        .collect();
+
+    Json(ApiResponse::success(Humanized::new(AggregatesResponse { latest, history }, tz)).with_status(status))
+}
+
+/// 获取市场广度
+async fn get_market_breadth(
+    State(cache): State<Arc<DataCache>>,
+) -> Json<ApiResponse<MarketBreadth>> {
+    let status = cache.get_entry_status(MARKET_BREADTH_KEY).await;
+    match cache.get_dataset::<MarketBreadth>(MARKET_BREADTH_KEY).await {
+        Some(breadth) => Json(ApiResponse::success(breadth).with_status(status)),
+        None => Json(ApiResponse::error("市场广度数据尚未计算").with_status(status)),
+    }
+}
+
+/// 获取板块统计（按市值加权涨跌幅降序）
+async fn get_sectors(
+    State(cache): State<Arc<DataCache>>,
+) -> Json<ApiResponse<SectorSnapshot>> {
+    let status = cache.get_entry_status(SECTORS_KEY).await;
+    match cache.get_dataset::<SectorSnapshot>(SECTORS_KEY).await {
+        Some(sectors) => Json(ApiResponse::success(sectors).with_status(status)),
+        None => Json(ApiResponse::error("板块统计尚未计算（需要配置 [[sectors.sectors]]）").with_status(status)),
+    }
+}
+
+/// 获取价格比率的最新读数（含24小时/7天变化和区间突破状态）
+async fn get_ratios(
+    State(cache): State<Arc<DataCache>>,
+) -> Json<ApiResponse<Vec<RatioReading>>> {
+    let status = cache.get_entry_status(RATIOS_KEY).await;
+    match cache.get_dataset::<Vec<RatioReading>>(RATIOS_KEY).await {
+        Some(ratios) => Json(ApiResponse::success(ratios).with_status(status)),
+        None => Json(ApiResponse::error("价格比率尚未计算（需要配置 [[ratios.ratios]]）").with_status(status)),
+    }
+}
+
+/// 获取比特币减半周期位置
+async fn get_btc_cycle(
+    State(cache): State<Arc<DataCache>>,
+) -> Json<ApiResponse<CyclePosition>> {
+    let status = cache.get_entry_status(BTC_CYCLE_KEY).await;
+    match cache.get_dataset::<CyclePosition>(BTC_CYCLE_KEY).await {
+        Some(cycle) => Json(ApiResponse::success(cycle).with_status(status)),
+        None => Json(ApiResponse::error("比特币周期数据尚未计算").with_status(status)),
+    }
+}
+
+/// 获取BTC/ETH交易所余额与资金流向
+async fn get_exchange_flows(
+    State(cache): State<Arc<DataCache>>,
+) -> Json<ApiResponse<ExchangeFlows>> {
+    let status = cache.get_entry_status(EXCHANGE_FLOWS_KEY).await;
+    match cache.get_dataset::<ExchangeFlows>(EXCHANGE_FLOWS_KEY).await {
+        Some(flows) => Json(ApiResponse::success(flows).with_status(status)),
+        None => Json(ApiResponse::error("交易所资金流向数据不可用（需要配置Glassnode API密钥）").with_status(status)),
+    }
+}
+
+/// 获取综合情绪分
+async fn get_composite_sentiment(
+    State(cache): State<Arc<DataCache>>,
+    State(weights): State<Arc<SentimentConfig>>,
+    State(flags): State<Arc<FeatureFlags>>,
+) -> Json<ApiResponse<CompositeSentiment>> {
+    if !flags.is_enabled(COMPOSITE_SENTIMENT) {
+        return Json(ApiResponse::error(format!("功能 {} 未开启", COMPOSITE_SENTIMENT)));
+    }
+    match current_sentiment(&cache, &weights).await {
+        Some(sentiment) => Json(ApiResponse::success(sentiment)),
+        None => Json(ApiResponse::error("情绪数据尚未采集")),
+    }
+}
+
+/// 获取BTC/ETH期权隐含波动率和看跌/看涨比
+async fn get_options(
+    State(cache): State<Arc<DataCache>>,
+) -> Json<ApiResponse<OptionsData>> {
+    let status = cache.get_entry_status(OPTIONS_KEY).await;
+    match cache.get_dataset::<OptionsData>(OPTIONS_KEY).await {
+        Some(options) => Json(ApiResponse::success(options).with_status(status)),
+        None => Json(ApiResponse::error("期权数据尚未采集").with_status(status)),
+    }
+}
+
+/// ETF资金流向查询参数
+#[derive(Debug, Deserialize)]
+pub struct EtfFlowsQuery {
+    /// 只返回指定资产（如 BTC）
+    pub asset: Option<String>,
+    /// 返回最近的天数（默认90，累计值仍从最早交易日开始计算）
+    pub days: Option<i64>,
+}
+
+/// 获取现货ETF每日资金流向及累计值
+async fn get_etf_flows(
+    State(cache): State<Arc<DataCache>>,
+    State(history): State<Arc<EtfFlowHistory>>,
+    Query(query): Query<EtfFlowsQuery>,
+) -> Json<ApiResponse<Vec<EtfFlowSeries>>> {
+    let status = cache.get_entry_status(ETF_FLOWS_KEY).await;
+    let since = Utc::now().date_naive() - chrono::Duration::days(query.days.unwrap_or(90).clamp(1, 3650));
+    let series = history.series(query.asset.as_deref(), Some(since)).await;
+    if series.is_empty() {
+        return Json(ApiResponse::error("暂无ETF资金流向数据（需要配置 [etf_flows] source_url）").with_status(status));
+    }
+    Json(ApiResponse::success(series).with_status(status))
+}
+
+/// 爆仓数据查询参数
+#[derive(Debug, Deserialize)]
+pub struct LiquidationsQuery {
+    /// 只返回指定币种（如 BTC）
+    pub symbol: Option<String>,
+    /// 返回最近的小时数（默认24）
+    pub hours: Option<i64>,
+}
+
+/// 获取合约每小时多/空爆仓金额
+async fn get_liquidations(
+    State(cache): State<Arc<DataCache>>,
+    State(history): State<Arc<LiquidationHistory>>,
+    Query(query): Query<LiquidationsQuery>,
+) -> Json<ApiResponse<Vec<LiquidationSeries>>> {
+    let status = cache.get_entry_status(LIQUIDATIONS_KEY).await;
+    let since = Utc::now() - chrono::Duration::hours(query.hours.unwrap_or(24).clamp(1, 24 * 365));
+    let series = history.series(query.symbol.as_deref(), Some(since)).await;
+    if series.is_empty() {
+        return Json(ApiResponse::error("暂无爆仓数据（需要配置 [data_sources.coinglass] api_key）").with_status(status));
+    }
+    Json(ApiResponse::success(series).with_status(status))
+}
+
+/// 流动性查询参数
+#[derive(Debug, Deserialize)]
+pub struct LiquidityQuery {
+    /// 历史向前查询的小时数（默认24）
+    pub hours: Option<i64>,
+}
+
+/// 流动性历史数据点
+#[derive(Debug, Serialize)]
+pub struct LiquidityPoint {
+    /// 快照时间
+    pub timestamp: DateTime<Utc>,
+    /// 买卖价差（基点）
+    pub spread_bps: Option<f64>,
+    /// 范围内的买单金额
+    pub bid_depth: Option<f64>,
+    /// 范围内的卖单金额
+    pub ask_depth: Option<f64>,
+}
+
+/// 交易对流动性响应
+#[derive(Debug, Serialize)]
+pub struct LiquidityResponse {
+    /// 最新快照
+    pub latest: LiquiditySnapshot,
+    /// 历史数据点（按时间升序）
+    pub history: Vec<LiquidityPoint>,
+}
+
+/// 获取交易对的最新流动性快照及其历史
+async fn get_liquidity(
+    State(cache): State<Arc<DataCache>>,
+    State(metrics): State<Arc<MetricStore>>,
+    axum::extract::Path(symbol): axum::extract::Path<String>,
+    Query(query): Query<LiquidityQuery>,
+) -> Json<ApiResponse<LiquidityResponse>> {
+    let status = cache.get_entry_status(LIQUIDITY_KEY).await;
+    let latest = cache.get_dataset::<Vec<LiquiditySnapshot>>(LIQUIDITY_KEY).await
+        .unwrap_or_default()
+        .into_iter()
+        .find(|s| s.symbol.eq_ignore_ascii_case(&symbol));
+    let Some(latest) = latest else {
+        return Json(ApiResponse::error(format!("暂无交易对 {} 的流动性快照（需要加入 [liquidity] symbols）", symbol)).with_status(status));
+    };
+
+    let metric_query = MetricQuery {
+        since: Some(Utc::now() - chrono::Duration::hours(query.hours.unwrap_or(24).clamp(1, 24 * 365))),
+        ..MetricQuery::default()
+    };
+    let names = ["spread_bps", "bid_depth", "ask_depth"].map(|field| liquidity_metric_name(field, &latest.symbol));
+    let history = merged_history(&metrics, &names, &metric_query).await
+        .into_iter()
+        .map(|(timestamp, values)| LiquidityPoint {
+            timestamp,
+            spread_bps: values[0],
+            bid_depth: values[1],
+            ask_depth: values[2],
+        })
+        .collect();
+
+    Json(ApiResponse::success(LiquidityResponse { latest, history }).with_status(status))
+}
+
+/// NFT合集查询参数
+#[derive(Debug, Deserialize)]
+pub struct NftQuery {
+    /// 历史向前查询的天数（默认30）
+    pub days: Option<i64>,
+}
+
+/// NFT合集历史数据点
+#[derive(Debug, Serialize)]
+pub struct NftPoint {
+    /// 采集时间
+    pub timestamp: DateTime<Utc>,
+    /// 地板价（计价币种）
+    pub floor_price: Option<f64>,
+    /// 地板价（美元）
+    pub floor_price_usd: Option<f64>,
+    /// 24小时成交额（计价币种）
+    pub volume_24h: Option<f64>,
+}
+
+/// NFT合集响应
+#[derive(Debug, Serialize)]
+pub struct NftResponse {
+    /// 最新统计
+    pub latest: NftCollectionSnapshot,
+    /// 历史数据点（按时间升序）
+    pub history: Vec<NftPoint>,
+}
+
+/// 获取NFT合集的最新地板价、成交额及其历史
+async fn get_nft_collection(
+    State(cache): State<Arc<DataCache>>,
+    State(metrics): State<Arc<MetricStore>>,
+    axum::extract::Path(collection): axum::extract::Path<String>,
+    Query(query): Query<NftQuery>,
+) -> Json<ApiResponse<NftResponse>> {
+    let status = cache.get_entry_status(NFT_COLLECTIONS_KEY).await;
+    let latest = cache.get_dataset::<Vec<NftCollectionSnapshot>>(NFT_COLLECTIONS_KEY).await
+        .unwrap_or_default()
+        .into_iter()
+        .find(|c| c.slug.eq_ignore_ascii_case(&collection));
+    let Some(latest) = latest else {
+        return Json(ApiResponse::error(format!("暂无NFT合集 {} 的统计（需要加入 [nft] collections）", collection)).with_status(status));
+    };
+
+    let metric_query = MetricQuery {
+        since: Some(Utc::now() - chrono::Duration::days(query.days.unwrap_or(30).clamp(1, 3650))),
+        ..MetricQuery::default()
+    };
+    let names = ["floor", "floor_usd", "volume_24h"].map(|field| nft_metric_name(field, &latest.slug));
+    let history = merged_history(&metrics, &names, &metric_query).await
+        .into_iter()
+        .map(|(timestamp, values)| NftPoint {
+            timestamp,
+            floor_price: values[0],
+            floor_price_usd: values[1],
+            volume_24h: values[2],
+        })
+        .collect();
+
+    Json(ApiResponse::success(NftResponse { latest, history }).with_status(status))
+}
+
+/// 网络健康查询参数
+#[derive(Debug, Deserialize)]
+pub struct NetworkQuery {
+    /// 历史向前查询的天数（默认30）
+    pub days: Option<i64>,
+}
+
+/// 网络健康历史数据点（只包含该链适用的字段）
+#[derive(Debug, Serialize)]
+pub struct NetworkPoint {
+    /// 采集时间
+    pub timestamp: DateTime<Utc>,
+    /// 全网算力（EH/s）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashrate_ehs: Option<f64>,
+    /// 难度
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<f64>,
+    /// 预计下次难度调整幅度（百分比）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty_change_pct: Option<f64>,
+    /// 质押年化收益率（百分比）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staking_apr_pct: Option<f64>,
+    /// 质押比例（百分比）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staked_ratio_pct: Option<f64>,
+}
+
+/// 网络健康响应
+#[derive(Debug, Serialize)]
+pub struct NetworkResponse {
+    /// 最新快照
+    pub latest: NetworkSnapshot,
+    /// 历史数据点（按时间升序）
+    pub history: Vec<NetworkPoint>,
+}
+
+/// 获取单条链的算力/难度或质押收益率及其历史
+async fn get_network(
+    State(cache): State<Arc<DataCache>>,
+    State(metrics): State<Arc<MetricStore>>,
+    axum::extract::Path(chain): axum::extract::Path<String>,
+    Query(query): Query<NetworkQuery>,
+) -> Json<ApiResponse<NetworkResponse>> {
+    let status = cache.get_entry_status(NETWORK_KEY).await;
+    let latest = cache.get_dataset::<Vec<NetworkSnapshot>>(NETWORK_KEY).await
+        .unwrap_or_default()
+        .into_iter()
+        .find(|n| n.chain.eq_ignore_ascii_case(&chain));
+    let Some(latest) = latest else {
+        return Json(ApiResponse::error(format!("暂无 {} 的网络数据（支持 btc、eth、sol）", chain)).with_status(status));
+    };
+
+    let metric_query = MetricQuery {
+        since: Some(Utc::now() - chrono::Duration::days(query.days.unwrap_or(30).clamp(1, 3650))),
+        ..MetricQuery::default()
+    };
+    let names = ["hashrate", "difficulty", "difficulty_change", "staking_apr", "staking_ratio"]
+        .map(|field| network_metric_name(field, &latest.chain));
+    let history = merged_history(&metrics, &names, &metric_query).await
+        .into_iter()
+        .map(|(timestamp, values)| NetworkPoint {
+            timestamp,
+            hashrate_ehs: values[0],
+            difficulty: values[1],
+            difficulty_change_pct: values[2],
+            staking_apr_pct: values[3],
+            staked_ratio_pct: values[4],
+        })
+        .collect();
+
+    Json(ApiResponse::success(NetworkResponse { latest, history }).with_status(status))
+}
+
+/// 获取自建节点的高度落后、对等节点数和响应延迟
+async fn get_nodes(
+    State(cache): State<Arc<DataCache>>,
+) -> Json<ApiResponse<Vec<NodeStatus>>> {
+    let status = cache.get_entry_status(NODES_KEY).await;
+    match cache.get_dataset::<Vec<NodeStatus>>(NODES_KEY).await {
+        Some(nodes) => Json(ApiResponse::success(nodes).with_status(status)),
+        None => Json(ApiResponse::error("暂无节点状态（需要在 [nodes] 中配置节点）").with_status(status)),
+    }
+}
+
+/// 获取数据集变化事件
+///
+/// 支持按 `symbol`、`type`（如 `rank_changed`、`supply_changed`）、`since` 和 `limit` 过滤，最新的在前
+async fn get_change_events(
+    State(changes): State<Arc<ChangeHistory>>,
+    Query(query): Query<ChangeQuery>,
+) -> Json<ApiResponse<Vec<ChangeEvent>>> {
+    Json(ApiResponse::success(changes.query(&query).await))
+}
+
+/// 经济日历查询参数
+#[derive(Debug, Deserialize)]
+pub struct CalendarQuery {
+    /// 向后查询的天数（默认7）
+    pub days: Option<i64>,
+    /// 最低重要性（默认low）
+    pub importance: Option<EventImportance>,
+}
+
+/// 获取即将发生的宏观经济事件
+async fn get_calendar(
+    State(cache): State<Arc<DataCache>>,
+    State(calendar): State<Arc<EconomicCalendar>>,
+    Query(query): Query<CalendarQuery>,
+) -> Json<ApiResponse<Vec<EconomicEvent>>> {
+    let status = cache.get_entry_status(CALENDAR_KEY).await;
+    let now = Utc::now();
+    let until = now + chrono::Duration::days(query.days.unwrap_or(7).clamp(1, 90));
+    let events = calendar
+        .events_between(now, until, query.importance.unwrap_or(EventImportance::Low))
+        .await;
+    Json(ApiResponse::success(events).with_status(status))
+}
+
+/// 获取监控币种的社交热度与情绪
+async fn get_social_sentiment(
+    State(cache): State<Arc<DataCache>>,
+) -> Json<ApiResponse<SocialSentiment>> {
+    let status = cache.get_entry_status(SOCIAL_KEY).await;
+    match cache.get_dataset::<SocialSentiment>(SOCIAL_KEY).await {
+        Some(social) => Json(ApiResponse::success(social).with_status(status)),
+        None => Json(ApiResponse::error("社交情绪数据不可用（需要配置LunarCrush API密钥）").with_status(status)),
+    }
+}
+
+/// 搜索热度查询参数
+#[derive(Debug, Deserialize)]
+pub struct TrendsQuery {
+    /// 只返回指定搜索词
+    pub term: Option<String>,
+}
+
+/// 获取搜索词的Google Trends每周热度
+async fn get_trends(
+    State(cache): State<Arc<DataCache>>,
+    State(history): State<Arc<TrendsHistory>>,
+    Query(query): Query<TrendsQuery>,
+) -> Json<ApiResponse<Vec<TrendSeries>>> {
+    let status = cache.get_entry_status(TRENDS_KEY).await;
+    let series = history.series(query.term.as_deref()).await;
+    if series.is_empty() {
+        return Json(ApiResponse::error("暂无搜索热度数据").with_status(status));
+    }
+    Json(ApiResponse::success(series).with_status(status))
+}
+
+/// 币种指标历史查询参数
+#[derive(Debug, Deserialize)]
+pub struct IndicatorQuery {
+    /// 指标名称（price、rsi、bollinger，不指定时返回全部）
+    pub indicator: Option<String>,
+    /// 起始时间（包含）
+    pub from: Option<DateTime<Utc>>,
+    /// 结束时间（包含）
+    pub to: Option<DateTime<Utc>>,
+    /// 每个指标最多返回最近的条数
+    pub limit: Option<usize>,
+    /// 是否包含已作废的数据点
+    #[serde(default)]
+    pub include_invalid: bool,
+}
+
+/// 指标数据点
+#[derive(Debug, Serialize)]
+pub struct IndicatorPoint {
+    /// 采集时间
+    pub timestamp: DateTime<Utc>,
+    /// 指标值（布林带为包含 upper/middle/lower 的复合值）
+    pub value: MetricValue,
+}
+
+/// 单个指标的时间序列
+#[derive(Debug, Serialize)]
+pub struct IndicatorSeries {
+    /// 币种ID
+    pub coin_id: String,
+    /// 指标名称
+    pub indicator: String,
+    /// 数据点（按时间升序）
+    pub points: Vec<IndicatorPoint>,
+}
+
+/// 获取币种技术指标的历史序列
+async fn get_coin_indicators(
+    State(metrics): State<Arc<MetricStore>>,
+    axum::extract::Path(coin_id): axum::extract::Path<String>,
+    Query(query): Query<IndicatorQuery>,
+) -> LimitedResponse<Vec<IndicatorSeries>> {
+    let coin_id = coin_id.to_lowercase();
+    let indicators: Vec<String> = match query.indicator {
+        Some(indicator) => {
+            let indicator = indicator.to_lowercase();
+            if !COIN_INDICATORS.contains(&indicator.as_str()) {
+                return Ok(Json(ApiResponse::error(format!(
+                    "未知指标: {}（支持: {}）", indicator, COIN_INDICATORS.join(", ")
+                ))));
+            }
+            vec![indicator]
+        }
+        None => COIN_INDICATORS.iter().map(|i| i.to_string()).collect(),
+    };
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from > to {
+            return Ok(Json(ApiResponse::error("起始时间不能晚于结束时间")));
+        }
+    }
+
+    let metric_query = MetricQuery {
+        since: query.from,
+        until: query.to,
+        source: None,
+        limit: query.limit,
+        include_invalid: query.include_invalid,
+        ..MetricQuery::default()
+    };
+    let mut series = Vec::new();
+    for indicator in indicators {
+        let points: Vec<IndicatorPoint> = metrics
+            .query_checked(&indicator_metric_name(&coin_id, &indicator), &metric_query)
+            .await
+            .map_err(query_limit_rejection)?
+            .into_iter()
+            .map(|m| IndicatorPoint { timestamp: m.timestamp, value: m.value })
+            .collect();
+        if !points.is_empty() {
+            series.push(IndicatorSeries { coin_id: coin_id.clone(), indicator, points });
+        }
+    }
+
+    if series.is_empty() {
+        return Ok(Json(ApiResponse::error(format!("暂无币种 {} 的指标历史", coin_id))));
+    }
+    Ok(Json(ApiResponse::success(series)))
+}
+
+/// 排名历史查询参数
+#[derive(Debug, Deserialize)]
+pub struct RankHistoryQuery {
+    /// 向前查询的天数（默认90）
+    pub days: Option<i64>,
+}
+
+/// 获取币种的每日市值排名
+async fn get_rank_history(
+    State(ranks): State<Arc<RankHistory>>,
+    axum::extract::Path(coin_id): axum::extract::Path<String>,
+    Query(query): Query<RankHistoryQuery>,
+) -> Json<ApiResponse<Vec<RankPoint>>> {
+    let since = Utc::now().date_naive() - chrono::Duration::days(query.days.unwrap_or(90).clamp(1, 3650));
+    let points = ranks.history(&coin_id, Some(since)).await;
+    if points.is_empty() {
+        return Json(ApiResponse::error(format!("暂无币种 {} 的排名历史", coin_id)));
+    }
+    Json(ApiResponse::success(points))
+}
+
+/// 排名上升榜查询参数
+#[derive(Debug, Deserialize)]
+pub struct RankClimbersQuery {
+    /// 统计天数（默认7）
+    pub days: Option<i64>,
+    /// 最多返回数量（默认10）
+    pub limit: Option<usize>,
+}
+
+/// 获取一段时间内排名上升最多的币种
+async fn get_rank_climbers(
+    State(ranks): State<Arc<RankHistory>>,
+    Query(query): Query<RankClimbersQuery>,
+) -> Json<ApiResponse<Vec<RankClimber>>> {
+    let days = query.days.unwrap_or(7).clamp(1, 365);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
+    Json(ApiResponse::success(ranks.climbers(days, limit, Utc::now().date_naive()).await))
+}
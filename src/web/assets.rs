@@ -0,0 +1,87 @@
+use axum::Router;
+use tower_http::services::ServeDir;
+use tracing::info;
+
+/// 编译进二进制的前端静态资源
+#[cfg(feature = "embedded-assets")]
+#[derive(rust_embed::RustEmbed)]
+#[folder = "static/"]
+struct EmbeddedAssets;
+
+/// 创建前端静态资源路由
+///
+/// 配置了 `server.static_dir` 时从该目录读取（便于前端开发时热更新），
+/// 否则使用编译进二进制的资源；关闭 `embedded-assets` 特性构建时不提供前端页面
+///
+/// # 参数
+/// * `static_dir` - 覆盖使用的静态文件目录（可选）
+///
+/// # 返回
+/// * `Router<S>` - 作为兜底路由合并到应用中
+pub fn create_asset_routes<S>(static_dir: Option<&str>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if let Some(dir) = static_dir.filter(|d| !d.is_empty()) {
+        info!("📁 从目录 {} 提供前端静态文件", dir);
+        return Router::new().fallback_service(ServeDir::new(dir).append_index_html_on_directories(true));
+    }
+    embedded_routes()
+}
+
+#[cfg(feature = "embedded-assets")]
+fn embedded_routes<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new().fallback(serve_embedded)
+}
+
+#[cfg(not(feature = "embedded-assets"))]
+fn embedded_routes<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    info!("📁 未编译前端资源，仅提供API");
+    Router::new()
+}
+
+/// 返回嵌入的静态文件
+#[cfg(feature = "embedded-assets")]
+async fn serve_embedded(uri: axum::http::Uri) -> axum::response::Response {
+    use axum::http::{header, StatusCode};
+    use axum::response::IntoResponse;
+
+    let path = embedded_path(uri.path());
+    match EmbeddedAssets::get(&path) {
+        Some(file) => (
+            [(header::CONTENT_TYPE, file.metadata.mimetype().to_string())],
+            file.data,
+        ).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// 请求路径对应的资源路径，目录请求返回其中的 `index.html`
+#[cfg(feature = "embedded-assets")]
+fn embedded_path(request_path: &str) -> String {
+    let path = request_path.trim_start_matches('/');
+    if path.is_empty() || path.ends_with('/') {
+        format!("{}index.html", path)
+    } else {
+        path.to_string()
+    }
+}
+
+#[cfg(all(test, feature = "embedded-assets"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_index() {
+        assert_eq!(embedded_path("/"), "index.html");
+        assert_eq!(embedded_path("/dashboard.html"), "dashboard.html");
+        assert!(EmbeddedAssets::get(&embedded_path("/")).is_some());
+        assert!(EmbeddedAssets::get("missing.js").is_none());
+    }
+}
@@ -0,0 +1,148 @@
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::audit::AuditEntry;
+use crate::tenants::TenantRegistry;
+use crate::users::AuthService;
+
+/// 租户API密钥请求头
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// 已登录用户
+/// 
+/// 从 `Authorization: Bearer <token>` 请求头中解析；携带 `X-Api-Key` 时识别为租户，
+/// 以租户的所有者ID作为用户ID，告警规则、价格监控等按用户隔离的数据因此按租户隔离。
+/// 允许匿名访问的接口使用 [`MaybeAuthUser`]
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    /// 用户ID
+    pub id: Uuid,
+    /// 用户名（租户为 `tenant:<租户ID>`）
+    pub username: String,
+    /// 通过API密钥识别的租户ID
+    pub tenant: Option<String>,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthUser
+where
+    Arc<AuthService>: FromRef<S>,
+    Arc<TenantRegistry>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(api_key) = parts.headers.get(API_KEY_HEADER).and_then(|v| v.to_str().ok()) {
+            let tenants = Arc::<TenantRegistry>::from_ref(state);
+            let tenant = tenants.authenticate(api_key).await.ok_or(StatusCode::UNAUTHORIZED)?;
+            return Ok(Self {
+                id: tenant.owner_id,
+                username: format!("tenant:{}", tenant.id),
+                tenant: Some(tenant.id),
+            });
+        }
+
+        let token = parts.headers
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+        
+        let auth = Arc::<AuthService>::from_ref(state);
+        let claims = auth.verify(token.trim()).map_err(|_| StatusCode::UNAUTHORIZED)?;
+        Ok(Self {
+            id: claims.sub,
+            username: claims.username,
+            tenant: None,
+        })
+    }
+}
+
+impl AuthUser {
+    /// 是否为管理员（租户不是管理员）
+    pub fn is_admin(&self, auth: &AuthService) -> bool {
+        self.tenant.is_none() && auth.is_admin(&self.username)
+    }
+
+    /// 是否可以修改归属 `owner` 的数据
+    /// 
+    /// 用户只能修改自己的数据，全局数据（`owner` 为None）只有管理员可以修改
+    pub fn can_modify(&self, owner: Option<Uuid>, auth: &AuthService) -> bool {
+        match owner {
+            Some(owner) => owner == self.id,
+            None => self.is_admin(auth),
+        }
+    }
+}
+
+/// 可选登录用户
+/// 
+/// 未携带 `Authorization` 和 `X-Api-Key` 请求头时为 `None`；携带了但令牌或密钥无效时返回401，
+/// 不会退回匿名访问
+#[derive(Debug, Clone)]
+pub struct MaybeAuthUser(pub Option<AuthUser>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for MaybeAuthUser
+where
+    Arc<AuthService>: FromRef<S>,
+    Arc<TenantRegistry>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if !parts.headers.contains_key(AUTHORIZATION) && !parts.headers.contains_key(API_KEY_HEADER) {
+            return Ok(Self(None));
+        }
+        AuthUser::from_request_parts(parts, state).await.map(|user| Self(Some(user)))
+    }
+}
+
+/// 管理员用户
+/// 
+/// 用户名需要在 `[auth] admin_users` 中，未登录返回401，非管理员返回403
+#[derive(Debug, Clone)]
+pub struct AdminUser(pub AuthUser);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminUser
+where
+    Arc<AuthService>: FromRef<S>,
+    Arc<TenantRegistry>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state).await?;
+        let auth = Arc::<AuthService>::from_ref(state);
+        if !user.is_admin(&auth) {
+            return Err(StatusCode::FORBIDDEN);
+        }
+        Ok(Self(user))
+    }
+}
+
+/// 获取可选登录用户的ID
+pub fn user_id(user: &Option<AuthUser>) -> Option<Uuid> {
+    user.as_ref().map(|u| u.id)
+}
+
+/// 以当前用户为操作者创建审计记录
+/// 
+/// # 参数
+/// * `user` - 当前用户（未登录时记为 `anonymous`）
+/// * `action` - 操作类型
+pub fn audit_entry(user: &Option<AuthUser>, action: &str) -> AuditEntry {
+    match user {
+        Some(user) => AuditEntry::new(&user.username, Some(user.id), action),
+        None => AuditEntry::new("anonymous", None, action),
+    }
+}
@@ -1,11 +1,15 @@
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
-use anyhow::Result;
+use tokio::sync::{broadcast, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tracing::{info, debug, warn};
 
-use crate::clients::AltcoinSeasonIndex;
+use crate::clients::{AltcoinSeasonIndex, FearGreedSnapshot};
+use crate::config::{CacheConfig, DataQualityConfig, PriceDivergenceConfig};
+use crate::models::parse_timestamp;
 
 /// 缓存的市场数据
 /// 
@@ -26,12 +30,288 @@ pub struct CachedMarketData {
     pub price_change_24h: Option<f64>,
     /// 市值
     pub market_cap: Option<f64>,
+    /// 市值排名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_cap_rank: Option<u64>,
+    /// 分类标签（来自CoinMarketCap，如 `layer-1`、`defi`、`memes`）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     /// 技术指标
     pub technical_indicators: TechnicalIndicatorsData,
     /// 数据更新时间
     pub updated_at: DateTime<Utc>,
     /// 数据来源
     pub source: String,
+    /// 其他数据源的参考报价，用于交叉校验当前价格
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reference_quotes: Vec<PriceQuote>,
+    /// 数据质量（读取时按当前时间计算）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_quality: Option<DataQuality>,
+    /// 多数据源价格偏离（读取时计算，少于两个有效报价时为None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_divergence: Option<PriceDivergence>,
+    /// 停止更新状态（已下架或不再活跃时设置，这类币种不参与市场广度计算）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inactive: Option<InactiveStatus>,
+    /// 供应量与稀释指标（数据源未提供流通量时为None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supply: Option<SupplyMetrics>,
+}
+
+/// 估算释放速度所用的观察窗口（天），基准超过该时间后向前滚动
+const EMISSION_WINDOW_DAYS: i64 = 30;
+
+/// 供应量与稀释指标
+/// 
+/// 日均释放量按流通量相对基准值的变化估算，观察不足一天时为None
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SupplyMetrics {
+    /// 流通量
+    pub circulating_supply: f64,
+    /// 总供应量
+    pub total_supply: Option<f64>,
+    /// 最大供应量（无上限时为None）
+    pub max_supply: Option<f64>,
+    /// 完全稀释市值
+    pub fully_diluted_market_cap: Option<f64>,
+    /// 稀释比例：市值占完全稀释市值的比例（0-1，越低未来稀释越多）
+    pub dilution_ratio: Option<f64>,
+    /// 尚未流通的供应量（最大供应量，没有时用总供应量，减去流通量）
+    pub unreleased_supply: Option<f64>,
+    /// 估算的日均新增流通量
+    pub emission_per_day: Option<f64>,
+    /// 估算的未来30天新增流通量（不超过尚未流通的供应量）
+    pub emission_30d: Option<f64>,
+    /// 未来30天新增流通量占当前流通量的百分比
+    pub emission_30d_pct: Option<f64>,
+    /// 估算释放速度的基准流通量
+    pub baseline_supply: f64,
+    /// 基准流通量的观察时间
+    pub baseline_at: DateTime<Utc>,
+    /// 本次观察时间
+    pub observed_at: DateTime<Utc>,
+}
+
+impl SupplyMetrics {
+    /// 由最新供应数据计算稀释指标
+    /// 
+    /// # 参数
+    /// * `circulating_supply` - 流通量
+    /// * `total_supply` - 总供应量
+    /// * `max_supply` - 最大供应量
+    /// * `fully_diluted_market_cap` - 完全稀释市值
+    /// * `market_cap` - 市值
+    /// * `previous` - 上一次的供应指标（用于估算释放速度）
+    /// * `now` - 当前时间
+    /// 
+    /// # 返回
+    /// * `SupplyMetrics` - 供应量与稀释指标
+    pub fn assess(
+        circulating_supply: f64,
+        total_supply: Option<f64>,
+        max_supply: Option<f64>,
+        fully_diluted_market_cap: Option<f64>,
+        market_cap: Option<f64>,
+        previous: Option<&SupplyMetrics>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let positive = |v: Option<f64>| v.filter(|v| *v > 0.0);
+        let cap_supply = positive(max_supply).or(positive(total_supply));
+        let dilution_ratio = match (positive(market_cap), positive(fully_diluted_market_cap)) {
+            (Some(market_cap), Some(fdv)) => Some(market_cap / fdv),
+            _ => cap_supply.map(|cap| circulating_supply / cap),
+        }.map(|ratio| ratio.min(1.0));
+        let unreleased_supply = cap_supply.map(|cap| (cap - circulating_supply).max(0.0));
+
+        // 基准超过观察窗口后滚动到上一次观察值，使估算反映近期的释放速度
+        let (baseline_supply, baseline_at) = match previous {
+            Some(p) if (now - p.baseline_at).num_days() < EMISSION_WINDOW_DAYS => (p.baseline_supply, p.baseline_at),
+            Some(p) => (p.circulating_supply, p.observed_at),
+            None => (circulating_supply, now),
+        };
+        let elapsed_days = (now - baseline_at).num_seconds() as f64 / 86400.0;
+        let emission_per_day = (elapsed_days >= 1.0)
+            .then(|| ((circulating_supply - baseline_supply) / elapsed_days).max(0.0));
+        let emission_30d = emission_per_day.map(|per_day| {
+            let emission = per_day * 30.0;
+            unreleased_supply.map_or(emission, |remaining| emission.min(remaining))
+        });
+        let emission_30d_pct = emission_30d
+            .filter(|_| circulating_supply > 0.0)
+            .map(|emission| emission / circulating_supply * 100.0);
+
+        Self {
+            circulating_supply,
+            total_supply,
+            max_supply,
+            fully_diluted_market_cap,
+            dilution_ratio,
+            unreleased_supply,
+            emission_per_day,
+            emission_30d,
+            emission_30d_pct,
+            baseline_supply,
+            baseline_at,
+            observed_at: now,
+        }
+    }
+}
+
+/// 币种停止更新状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InactiveStatus {
+    /// 原因（如数据源标记为不活跃、不再收录或报价长时间未更新）
+    pub reason: String,
+    /// 首次发现的时间
+    pub since: DateTime<Utc>,
+}
+
+/// 参考价格报价
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceQuote {
+    /// 数据来源
+    pub source: String,
+    /// 价格（美元）
+    pub price: f64,
+    /// 报价时间
+    pub quoted_at: DateTime<Utc>,
+}
+
+/// 币种数据质量
+/// 
+/// 综合新鲜度（40分）、多数据源一致性（30分）和近期采集可靠性（30分），
+/// 供使用方判断价格是否可信
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataQuality {
+    /// 综合评分（0-100）
+    pub score: u8,
+    /// 当前价格的数据来源
+    pub source: String,
+    /// 距上次更新的秒数
+    pub staleness_seconds: i64,
+    /// 与当前价格相差在容差内的数据源数量（含主数据源）
+    pub agreeing_sources: usize,
+    /// 参与比较的数据源数量（含主数据源，不含过期的参考报价）
+    pub total_sources: usize,
+    /// 统计窗口内的采集失败次数
+    pub recent_failures: usize,
+}
+
+/// 采集失败次数达到该值时可靠性得分为零
+const QUALITY_MAX_FAILURES: f64 = 5.0;
+
+impl DataQuality {
+    /// 评估币种数据质量
+    /// 
+    /// 只有一个数据源时无法交叉校验，一致性按半分计算
+    /// 
+    /// # 参数
+    /// * `data` - 市场数据
+    /// * `status` - 对应的条目状态（可选）
+    /// * `config` - 评分配置
+    /// * `now` - 当前时间
+    /// 
+    /// # 返回
+    /// * `DataQuality` - 数据质量
+    pub fn assess(
+        data: &CachedMarketData,
+        status: Option<&EntryStatus>,
+        config: &DataQualityConfig,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let staleness_seconds = (now - data.updated_at).num_seconds().max(0);
+        let stale_after = config.stale_after_seconds.max(1) as f64;
+        let freshness = ((3.0 * stale_after - staleness_seconds as f64) / (2.0 * stale_after)).clamp(0.0, 1.0);
+
+        let fresh_quotes: Vec<&PriceQuote> = data.reference_quotes.iter()
+            .filter(|q| q.source != data.source && (now - q.quoted_at).num_seconds() as f64 <= stale_after)
+            .collect();
+        let total_sources = fresh_quotes.len() + 1;
+        let agreeing_sources = 1 + fresh_quotes.iter()
+            .filter(|q| data.current_price > 0.0
+                && (q.price - data.current_price).abs() / data.current_price * 100.0 <= config.tolerance_pct)
+            .count();
+        let agreement = if total_sources == 1 { 0.5 } else { agreeing_sources as f64 / total_sources as f64 };
+
+        let window_start = now - chrono::Duration::hours(config.failure_window_hours as i64);
+        let recent_failures = status
+            .map(|s| s.recent_failures.iter().filter(|at| **at >= window_start).count())
+            .unwrap_or(0);
+        let reliability = (1.0 - recent_failures as f64 / QUALITY_MAX_FAILURES).max(0.0);
+
+        Self {
+            score: (40.0 * freshness + 30.0 * agreement + 30.0 * reliability).round() as u8,
+            source: data.source.clone(),
+            staleness_seconds,
+            agreeing_sources,
+            total_sources,
+            recent_failures,
+        }
+    }
+}
+
+/// 多数据源价格偏离
+/// 
+/// 同一币种在不同数据源的报价差距过大时，往往是某个数据源的上市信息过期或流动性过低导致定价错误
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceDivergence {
+    /// 最高价与最低价之差占中位价的百分比
+    pub divergence_pct: f64,
+    /// 偏离阈值（百分比）
+    pub threshold_pct: f64,
+    /// 是否超过阈值
+    pub flagged: bool,
+    /// 最低报价
+    pub lowest: PriceQuote,
+    /// 最高报价
+    pub highest: PriceQuote,
+}
+
+impl PriceDivergence {
+    /// 计算主数据源价格与未过期参考报价之间的偏离
+    /// 
+    /// # 参数
+    /// * `data` - 市场数据
+    /// * `threshold_pct` - 偏离阈值（百分比）
+    /// * `max_quote_age_seconds` - 参考报价的最长有效时间（秒）
+    /// * `now` - 当前时间
+    /// 
+    /// # 返回
+    /// * `Option<PriceDivergence>` - 有效报价少于两个时返回None
+    pub fn assess(data: &CachedMarketData, threshold_pct: f64, max_quote_age_seconds: u64, now: DateTime<Utc>) -> Option<Self> {
+        let mut quotes: Vec<PriceQuote> = data.reference_quotes.iter()
+            .filter(|q| q.source != data.source && q.price > 0.0)
+            .filter(|q| (now - q.quoted_at).num_seconds() <= max_quote_age_seconds as i64)
+            .cloned()
+            .collect();
+        if quotes.is_empty() || data.current_price <= 0.0 {
+            return None;
+        }
+        quotes.push(PriceQuote {
+            source: data.source.clone(),
+            price: data.current_price,
+            quoted_at: data.updated_at,
+        });
+        quotes.sort_by(|a, b| a.price.total_cmp(&b.price));
+
+        let mid = quotes.len() / 2;
+        let median = if quotes.len().is_multiple_of(2) {
+            (quotes[mid - 1].price + quotes[mid].price) / 2.0
+        } else {
+            quotes[mid].price
+        };
+        let lowest = quotes.first()?.clone();
+        let highest = quotes.last()?.clone();
+        let divergence_pct = (highest.price - lowest.price) / median * 100.0;
+        Some(Self {
+            divergence_pct,
+            threshold_pct,
+            flagged: divergence_pct > threshold_pct,
+            lowest,
+            highest,
+        })
+    }
 }
 
 /// 技术指标数据
@@ -84,19 +364,205 @@ pub enum RSISignal {
     Oversold,
 }
 
+/// 贪婪恐惧指数的数据集键
+pub const FEAR_GREED_KEY: &str = "fear_greed_index";
+
+/// 山寨币季节指数的数据集键
+pub const ALTCOIN_SEASON_KEY: &str = "altcoin_season_index";
+
+/// 山寨币季节指数成分币种的数据集键
+pub const ALTCOIN_SEASON_CONSTITUENTS_KEY: &str = "altcoin_season_constituents";
+
+/// CMC市值前100币种上市信息的数据集键（排名、流通量、标签）
+pub const CMC_LISTINGS_KEY: &str = "cmc_listings";
+
+/// 最近一次检测到的新上线币种事件的数据集键
+pub const NEW_LISTINGS_KEY: &str = "new_listings";
+
+/// 市场广度的数据集键
+pub const MARKET_BREADTH_KEY: &str = "market_breadth";
+
+/// 板块统计的数据集键
+pub const SECTORS_KEY: &str = "sectors";
+
+/// 汇总市值（Total/Total2/Total3）的数据集键
+pub const MARKET_AGGREGATES_KEY: &str = "market_aggregates";
+
+/// 价格比率的数据集键
+pub const RATIOS_KEY: &str = "ratios";
+
+/// 比特币减半周期的数据集键
+pub const BTC_CYCLE_KEY: &str = "btc_cycle";
+
+/// 交易所资金流向的数据集键
+pub const EXCHANGE_FLOWS_KEY: &str = "exchange_flows";
+
+/// 期权市场数据的数据集键
+pub const OPTIONS_KEY: &str = "options";
+
+/// 现货ETF每日资金流向的数据集键
+pub const ETF_FLOWS_KEY: &str = "etf_flows";
+
+/// NFT合集统计的数据集键
+pub const NFT_COLLECTIONS_KEY: &str = "nft_collections";
+
+/// 网络健康（算力、质押收益率）的数据集键
+pub const NETWORK_KEY: &str = "network";
+
+/// 自建节点健康状态的数据集键
+pub const NODES_KEY: &str = "nodes";
+
+/// 交易对订单簿流动性快照的数据集键
+pub const LIQUIDITY_KEY: &str = "liquidity";
+
+/// 合约每小时爆仓金额的数据集键
+pub const LIQUIDATIONS_KEY: &str = "liquidations";
+
+/// 经济日历的状态键
+pub const CALENDAR_KEY: &str = "economic_calendar";
+
+/// 社交情绪的数据集键
+pub const SOCIAL_KEY: &str = "social_sentiment";
+
+/// 搜索热度的状态键
+pub const TRENDS_KEY: &str = "google_trends";
+
+/// 生成币种市场数据的数据集键
+/// 
+/// # 参数
+/// * `coin_id` - 币种ID
+/// 
+/// # 返回
+/// * `String` - 形如 `market_data:hype` 的数据集键
+pub fn market_data_key(coin_id: &str) -> String {
+    format!("market_data:{}", coin_id)
+}
+
+/// 缓存更新通知通道容量
+const UPDATE_CHANNEL_CAPACITY: usize = 256;
+
+/// 预序列化负载的最长复用时间
+/// 
+/// 数据质量和过期状态按读取时间计算，即使没有写入也定期重新序列化
+const SERIALIZED_MAX_AGE: Duration = Duration::from_secs(5);
+
+tokio::task_local! {
+    /// 当前合并批次中已更新的数据集键（见 [`DataCache::batched`]）
+    static PENDING_UPDATES: std::cell::RefCell<Vec<String>>;
+}
+
+/// 缓存更新事件
+/// 
+/// 数据集成功写入缓存后广播，供价格监控等订阅方使用；
+/// 同一批次（如一次行情采集更新的全部币种）内的更新合并为一个事件
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheUpdate {
+    /// 本次更新的数据集键（按更新顺序，不重复）
+    pub keys: Vec<String>,
+    /// 更新时间
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CacheUpdate {
+    /// 本次更新的币种ID（来自 `market_data:<币种ID>` 键）
+    pub fn coin_ids(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().filter_map(|key| key.strip_prefix("market_data:"))
+    }
+}
+
+/// 缓存条目状态
+/// 
+/// 记录每个数据集最近一次成功/失败的采集情况。
+/// 上游请求失败时缓存继续提供最后一次成功的数据，并通过 `stale` 明确标记
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct EntryStatus {
+    /// 最后一次成功更新时间
+    pub last_success: Option<DateTime<Utc>>,
+    /// 最后一次错误信息
+    pub last_error: Option<String>,
+    /// 最后一次错误时间
+    pub last_error_at: Option<DateTime<Utc>>,
+    /// 连续失败次数
+    pub consecutive_failures: u32,
+    /// 当前数据是否为过期的最后已知有效值
+    pub stale: bool,
+    /// 数据质量统计窗口内的采集失败时间
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_failures: Vec<DateTime<Utc>>,
+}
+
+/// 缓存快照
+/// 
+/// 采集实例定期写入共享数据目录，只读API实例加载后对外提供相同的数据
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CacheSnapshot {
+    /// 快照生成时间
+    pub saved_at: Option<DateTime<Utc>>,
+    /// 市场数据
+    pub market_data: HashMap<String, CachedMarketData>,
+    /// 贪婪恐惧指数（旧版本保存的格式无法解析时丢弃，下次采集后恢复）
+    #[serde(default, deserialize_with = "deserialize_fear_greed")]
+    pub fear_greed_index: Option<FearGreedSnapshot>,
+    /// 山寨币季节指数
+    pub altcoin_season_index: Option<AltcoinSeasonIndex>,
+    /// 分析类数据集
+    pub datasets: HashMap<String, serde_json::Value>,
+    /// 各数据集的条目状态
+    pub entry_status: HashMap<String, EntryStatus>,
+}
+
+/// 读取快照中的贪婪恐惧指数，无法解析的旧格式视为None
+fn deserialize_fear_greed<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<FearGreedSnapshot>, D::Error> {
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.and_then(|value| match serde_json::from_value(value) {
+        Ok(snapshot) => Some(snapshot),
+        Err(e) => {
+            warn!("⚠️ 忽略无法解析的贪婪恐惧指数快照: {}", e);
+            None
+        }
+    }))
+}
+
 /// 数据缓存管理器
 /// 
 /// 提供高效的读写操作和数据过期管理
+/// 
+/// 所有锁均为 `tokio::sync::RwLock`，在异步上下文中等待时不会阻塞工作线程，
+/// 也不存在锁中毒问题；命中/未命中计数使用原子变量，读路径无需获取写锁
 pub struct DataCache {
     /// 市场数据缓存
     /// key: 币种ID, value: 缓存的市场数据
     market_data: RwLock<HashMap<String, CachedMarketData>>,
     /// 贪婪恐惧指数缓存
-    fear_greed_index: RwLock<Option<serde_json::Value>>,
+    fear_greed_index: RwLock<Option<FearGreedSnapshot>>,
     /// 山寨币季节指数缓存
     altcoin_season_index: RwLock<Option<AltcoinSeasonIndex>>,
-    /// 缓存统计信息
+    /// 分析类数据集缓存（市场广度等派生数据）
+    /// key: 数据集键, value: 序列化后的数据
+    datasets: RwLock<HashMap<String, serde_json::Value>>,
+    /// 缓存统计信息（命中/未命中计数除外）
     stats: RwLock<CacheStats>,
+    /// 缓存命中次数
+    hits: AtomicU64,
+    /// 缓存未命中次数
+    misses: AtomicU64,
+    /// 锁竞争统计
+    contention: LockContention,
+    /// 各数据集的条目状态
+    /// key: 数据集键, value: 条目状态
+    entry_status: RwLock<HashMap<String, EntryStatus>>,
+    /// 缓存更新广播
+    updates: broadcast::Sender<CacheUpdate>,
+    /// 数据质量评分配置
+    quality: DataQualityConfig,
+    /// 多数据源价格偏离阈值（百分比）
+    divergence_threshold_pct: f64,
+    /// 预序列化的全部市场数据
+    market_data_json: SerializedPayload,
+    /// 容量上限
+    limits: CacheConfig,
+    /// 内存占用记录
+    memory: std::sync::Mutex<MemoryUsage>,
 }
 
 /// 缓存统计信息
@@ -112,6 +578,209 @@ pub struct CacheStats {
     pub last_updated: Option<DateTime<Utc>>,
     /// 数据来源统计
     pub sources: HashMap<String, u64>,
+    /// 锁竞争统计
+    pub lock_contention: LockContentionStats,
+    /// 预序列化的市场数据负载被复用的次数
+    pub serialized_hits: u64,
+    /// 预序列化的市场数据负载重新序列化的次数
+    pub serialized_misses: u64,
+    /// 内存占用估算和容量上限
+    pub memory: CacheMemoryStats,
+}
+
+/// 缓存内存占用统计
+/// 
+/// 只统计币种行情和分析类数据集，按条目序列化为JSON后的大小估算
+#[derive(Debug, Default, Serialize, Clone)]
+pub struct CacheMemoryStats {
+    /// 计入容量的条目数
+    pub entries: usize,
+    /// 估算占用字节数
+    pub approx_bytes: usize,
+    /// 估算内存占用上限（0表示不限制）
+    pub max_bytes: usize,
+    /// 条目数上限（0表示不限制）
+    pub max_entries: usize,
+    /// 因超出上限被淘汰的条目数
+    pub evictions: u64,
+}
+
+/// 锁竞争统计快照
+#[derive(Debug, Default, Serialize, Clone)]
+pub struct LockContentionStats {
+    /// 获取锁的总次数
+    pub acquisitions: u64,
+    /// 需要等待才能获取锁的次数
+    pub contended: u64,
+    /// 累计等待时间（微秒）
+    pub total_wait_us: u64,
+    /// 单次最长等待时间（微秒）
+    pub max_wait_us: u64,
+}
+
+/// 预序列化的JSON负载
+/// 
+/// 热点接口（全部市场数据的HTTP响应和WebSocket推送）直接复用序列化后的字节，
+/// 写入时递增代数使其失效；`Bytes` 按引用计数共享，各请求之间不复制
+#[derive(Debug, Default)]
+struct SerializedPayload {
+    /// 数据代数，每次写入后递增
+    generation: AtomicU64,
+    /// 已序列化的负载：(代数, 序列化时间, JSON字节)
+    cached: std::sync::Mutex<Option<(u64, Instant, Bytes)>>,
+    /// 复用次数
+    hits: AtomicU64,
+    /// 重新序列化次数
+    misses: AtomicU64,
+}
+
+impl SerializedPayload {
+    /// 使已序列化的负载失效（须在写锁释放后调用）
+    fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// 获取仍然有效的负载，同时返回当前代数供重新序列化后保存
+    fn get(&self) -> (u64, Option<Bytes>) {
+        let generation = self.generation.load(Ordering::Acquire);
+        let cached = self.cached.lock().unwrap();
+        match cached.as_ref() {
+            Some((cached_generation, serialized_at, bytes))
+                if *cached_generation == generation && serialized_at.elapsed() < SERIALIZED_MAX_AGE =>
+            {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                (generation, Some(bytes.clone()))
+            }
+            _ => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                (generation, None)
+            }
+        }
+    }
+
+    /// 保存重新序列化的负载（序列化期间发生过写入时丢弃）
+    fn store(&self, generation: u64, bytes: Bytes) {
+        if self.generation.load(Ordering::Acquire) == generation {
+            *self.cached.lock().unwrap() = Some((generation, Instant::now(), bytes));
+        }
+    }
+}
+
+/// 缓存条目的内存占用记录（LRU）
+#[derive(Debug, Default)]
+struct MemoryUsage {
+    /// 缓存键（`market_data:<币种ID>` 或数据集键） -> (估算字节数, 最近访问序号)
+    entries: HashMap<String, (usize, u64)>,
+    /// 估算总字节数
+    total_bytes: usize,
+    /// 访问序号
+    clock: u64,
+    /// 累计淘汰条目数
+    evictions: u64,
+}
+
+impl MemoryUsage {
+    /// 记录写入的条目大小并标记为最近访问
+    fn record(&mut self, key: &str, bytes: usize) {
+        self.clock += 1;
+        let bytes = bytes + key.len();
+        if let Some((previous, _)) = self.entries.insert(key.to_string(), (bytes, self.clock)) {
+            self.total_bytes -= previous;
+        }
+        self.total_bytes += bytes;
+    }
+
+    /// 标记条目为最近访问
+    fn touch(&mut self, key: &str) {
+        if let Some((_, accessed)) = self.entries.get_mut(key) {
+            self.clock += 1;
+            *accessed = self.clock;
+        }
+    }
+
+    /// 移除条目
+    fn remove(&mut self, key: &str) {
+        if let Some((bytes, _)) = self.entries.remove(key) {
+            self.total_bytes -= bytes;
+        }
+    }
+
+    /// 超出上限时按最久未访问的顺序选出要淘汰的条目（`keep` 除外）并移除记录
+    fn evict(&mut self, config: &CacheConfig, keep: &str) -> Vec<String> {
+        let over = |usage: &Self| {
+            (config.max_bytes > 0 && usage.total_bytes > config.max_bytes)
+                || (config.max_entries > 0 && usage.entries.len() > config.max_entries)
+        };
+        let mut victims = Vec::new();
+        while over(self) {
+            let Some(oldest) = self.entries.iter()
+                .filter(|(key, _)| key.as_str() != keep)
+                .min_by_key(|(_, (_, accessed))| *accessed)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.remove(&oldest);
+            self.evictions += 1;
+            victims.push(oldest);
+        }
+        victims
+    }
+}
+
+/// 锁竞争计数器
+/// 
+/// 先尝试无等待获取锁，失败时才计入竞争并统计等待时长
+#[derive(Debug, Default)]
+struct LockContention {
+    acquisitions: AtomicU64,
+    contended: AtomicU64,
+    total_wait_us: AtomicU64,
+    max_wait_us: AtomicU64,
+}
+
+impl LockContention {
+    /// 获取读锁并记录竞争情况
+    async fn read<'a, T>(&self, lock: &'a RwLock<T>) -> RwLockReadGuard<'a, T> {
+        self.acquisitions.fetch_add(1, Ordering::Relaxed);
+        if let Ok(guard) = lock.try_read() {
+            return guard;
+        }
+        let started = Instant::now();
+        let guard = lock.read().await;
+        self.record_wait(started);
+        guard
+    }
+
+    /// 获取写锁并记录竞争情况
+    async fn write<'a, T>(&self, lock: &'a RwLock<T>) -> RwLockWriteGuard<'a, T> {
+        self.acquisitions.fetch_add(1, Ordering::Relaxed);
+        if let Ok(guard) = lock.try_write() {
+            return guard;
+        }
+        let started = Instant::now();
+        let guard = lock.write().await;
+        self.record_wait(started);
+        guard
+    }
+
+    /// 记录一次等待
+    fn record_wait(&self, started: Instant) {
+        let waited = started.elapsed().as_micros() as u64;
+        self.contended.fetch_add(1, Ordering::Relaxed);
+        self.total_wait_us.fetch_add(waited, Ordering::Relaxed);
+        self.max_wait_us.fetch_max(waited, Ordering::Relaxed);
+    }
+
+    /// 生成统计快照
+    fn snapshot(&self) -> LockContentionStats {
+        LockContentionStats {
+            acquisitions: self.acquisitions.load(Ordering::Relaxed),
+            contended: self.contended.load(Ordering::Relaxed),
+            total_wait_us: self.total_wait_us.load(Ordering::Relaxed),
+            max_wait_us: self.max_wait_us.load(Ordering::Relaxed),
+        }
+    }
 }
 
 impl DataCache {
@@ -125,11 +794,156 @@ impl DataCache {
             market_data: RwLock::new(HashMap::new()),
             fear_greed_index: RwLock::new(None),
             altcoin_season_index: RwLock::new(None),
+            datasets: RwLock::new(HashMap::new()),
             stats: RwLock::new(CacheStats::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            contention: LockContention::default(),
+            entry_status: RwLock::new(HashMap::new()),
+            updates: broadcast::channel(UPDATE_CHANNEL_CAPACITY).0,
+            quality: DataQualityConfig::default(),
+            divergence_threshold_pct: PriceDivergenceConfig::default().threshold_pct,
+            market_data_json: SerializedPayload::default(),
+            limits: CacheConfig::default(),
+            memory: std::sync::Mutex::new(MemoryUsage::default()),
+        }
+    }
+
+    /// 设置容量上限
+    /// 
+    /// # 参数
+    /// * `config` - 容量配置
+    pub fn with_limits(mut self, config: CacheConfig) -> Self {
+        self.limits = config;
+        self
+    }
+
+    /// 设置多数据源价格偏离阈值
+    /// 
+    /// # 参数
+    /// * `threshold_pct` - 最高价与最低价之差占中位价的百分比
+    pub fn with_divergence_threshold(mut self, threshold_pct: f64) -> Self {
+        self.divergence_threshold_pct = threshold_pct;
+        self
+    }
+
+    /// 设置数据质量评分配置
+    /// 
+    /// # 参数
+    /// * `config` - 评分配置
+    pub fn with_quality_config(mut self, config: DataQualityConfig) -> Self {
+        self.quality = config;
+        self
+    }
+
+    /// 订阅缓存更新事件
+    /// 
+    /// # 返回
+    /// * `broadcast::Receiver<CacheUpdate>` - 更新事件接收端
+    pub fn subscribe(&self) -> broadcast::Receiver<CacheUpdate> {
+        self.updates.subscribe()
+    }
+
+    /// 执行 `future`，期间的缓存更新合并为一个事件在结束时广播
+    /// 
+    /// 批量任务一次更新大量币种时，订阅方只需处理一个事件；
+    /// 合并范围只包含 `future` 本身，同时运行的其他任务的更新照常立即广播
+    /// 
+    /// # 参数
+    /// * `future` - 要执行的异步操作
+    pub async fn batched<F: std::future::Future>(&self, future: F) -> F::Output {
+        let (output, keys) = PENDING_UPDATES.scope(std::cell::RefCell::new(Vec::new()), async {
+            let output = future.await;
+            (output, PENDING_UPDATES.with(|keys| keys.take()))
+        }).await;
+        if !keys.is_empty() {
+            let _ = self.updates.send(CacheUpdate { keys, updated_at: Utc::now() });
+        }
+        output
+    }
+
+    /// 记录数据集采集成功并广播更新事件
+    /// 
+    /// 在 [`DataCache::batched`] 中调用时推迟到批次结束时统一广播
+    /// 
+    /// # 参数
+    /// * `key` - 数据集键
+    pub async fn record_success(&self, key: &str) {
+        let now = Utc::now();
+        {
+            let mut statuses = self.contention.write(&self.entry_status).await;
+            let status = statuses.entry(key.to_string()).or_default();
+            status.last_success = Some(now);
+            status.consecutive_failures = 0;
+            status.stale = false;
+        }
+        self.market_data_json.invalidate();
+        
+        let deferred = PENDING_UPDATES.try_with(|keys| {
+            let mut keys = keys.borrow_mut();
+            if !keys.iter().any(|k| k == key) {
+                keys.push(key.to_string());
+            }
+        });
+        if deferred.is_err() {
+            // 没有订阅者时发送会失败，忽略即可
+            let _ = self.updates.send(CacheUpdate {
+                keys: vec![key.to_string()],
+                updated_at: now,
+            });
         }
     }
-    
 
+    /// 记录数据集采集失败
+    /// 
+    /// 缓存中的旧数据保持不变，作为最后已知有效值继续提供并标记为过期
+    /// 
+    /// # 参数
+    /// * `key` - 数据集键
+    /// * `error` - 错误信息
+    pub async fn record_failure(&self, key: &str, error: impl std::fmt::Display) {
+        let mut statuses = self.contention.write(&self.entry_status).await;
+        let status = statuses.entry(key.to_string()).or_default();
+        let now = Utc::now();
+        let window_start = now - chrono::Duration::hours(self.quality.failure_window_hours as i64);
+        status.recent_failures.retain(|at| *at >= window_start);
+        status.recent_failures.push(now);
+        status.last_error = Some(error.to_string());
+        status.last_error_at = Some(now);
+        status.consecutive_failures += 1;
+        status.stale = status.last_success.is_some();
+        warn!("⚠️ 数据集 {} 采集失败（连续 {} 次），继续提供最后已知有效数据", key, status.consecutive_failures);
+        drop(statuses);
+        self.market_data_json.invalidate();
+    }
+
+    /// 获取数据集的条目状态
+    /// 
+    /// # 参数
+    /// * `key` - 数据集键
+    /// 
+    /// # 返回
+    /// * `Option<EntryStatus>` - 条目状态或None
+    pub async fn get_entry_status(&self, key: &str) -> Option<EntryStatus> {
+        self.contention.read(&self.entry_status).await.get(key).cloned()
+    }
+
+    /// 获取所有数据集的条目状态
+    /// 
+    /// # 返回
+    /// * `HashMap<String, EntryStatus>` - 数据集键到条目状态的映射
+    pub async fn get_all_entry_status(&self) -> HashMap<String, EntryStatus> {
+        self.contention.read(&self.entry_status).await.clone()
+    }
+
+    /// 记录一次缓存查询结果
+    fn record_lookup(&self, hit: bool) {
+        if hit {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+    }
     
     /// 获取市场数据
     /// 
@@ -138,20 +952,19 @@ impl DataCache {
     /// 
     /// # 返回
     /// * `Option<CachedMarketData>` - 缓存的市场数据或None
-    pub fn get_market_data(&self, coin_id: &str) -> Option<CachedMarketData> {
-        let cache = self.market_data.read().unwrap();
-        let result = cache.get(coin_id).cloned();
+    pub async fn get_market_data(&self, coin_id: &str) -> Option<CachedMarketData> {
+        let mut result = self.contention.read(&self.market_data).await.get(coin_id).cloned();
         
         // 更新统计信息
-        {
-            let mut stats = self.stats.write().unwrap();
-            if result.is_some() {
-                stats.hits += 1;
-            } else {
-                stats.misses += 1;
-            }
+        self.record_lookup(result.is_some());
+        if result.is_some() {
+            self.memory.lock().unwrap().touch(&market_data_key(coin_id));
         }
         
+        if let Some(data) = result.as_mut() {
+            let statuses = self.contention.read(&self.entry_status).await;
+            self.attach_quality(data, &statuses, Utc::now());
+        }
         result
     }
     
@@ -159,9 +972,31 @@ impl DataCache {
     /// 
     /// # 返回
     /// * `Vec<CachedMarketData>` - 所有缓存的市场数据
-    pub fn get_all_market_data(&self) -> Vec<CachedMarketData> {
-        let cache = self.market_data.read().unwrap();
-        cache.values().cloned().collect()
+    pub async fn get_all_market_data(&self) -> Vec<CachedMarketData> {
+        let mut result: Vec<CachedMarketData> = self.contention.read(&self.market_data).await.values().cloned().collect();
+        let statuses = self.contention.read(&self.entry_status).await;
+        let now = Utc::now();
+        for data in &mut result {
+            self.attach_quality(data, &statuses, now);
+        }
+        result
+    }
+    
+    /// 获取序列化为JSON数组的全部市场数据
+    /// 
+    /// 与 [`DataCache::get_all_market_data`] 内容相同；缓存写入前（最长5秒）复用上次序列化的结果，
+    /// 供高频请求的接口直接写入响应
+    /// 
+    /// # 返回
+    /// * `Result<Bytes>` - JSON字节
+    pub async fn get_all_market_data_json(&self) -> serde_json::Result<Bytes> {
+        let (generation, cached) = self.market_data_json.get();
+        if let Some(bytes) = cached {
+            return Ok(bytes);
+        }
+        let bytes = Bytes::from(serde_json::to_vec(&self.get_all_market_data().await)?);
+        self.market_data_json.store(generation, bytes.clone());
+        Ok(bytes)
     }
     
     /// 获取指定币种列表的市场数据
@@ -171,25 +1006,99 @@ impl DataCache {
     /// 
     /// # 返回
     /// * `HashMap<String, CachedMarketData>` - 币种ID到市场数据的映射
-    pub fn get_multiple_market_data(&self, coin_ids: &[String]) -> HashMap<String, CachedMarketData> {
-        let cache = self.market_data.read().unwrap();
+    pub async fn get_multiple_market_data(&self, coin_ids: &[String]) -> HashMap<String, CachedMarketData> {
         let mut result = HashMap::new();
         
-        for coin_id in coin_ids {
-            if let Some(data) = cache.get(coin_id) {
-                result.insert(coin_id.clone(), data.clone());
+        {
+            let cache = self.contention.read(&self.market_data).await;
+            for coin_id in coin_ids {
+                if let Some(data) = cache.get(coin_id) {
+                    result.insert(coin_id.clone(), data.clone());
+                }
             }
         }
         
         // 更新统计信息
-        {
-            let mut stats = self.stats.write().unwrap();
-            stats.hits += result.len() as u64;
-            stats.misses += (coin_ids.len() - result.len()) as u64;
-        }
+        self.hits.fetch_add(result.len() as u64, Ordering::Relaxed);
+        self.misses.fetch_add((coin_ids.len() - result.len()) as u64, Ordering::Relaxed);
         
+        let statuses = self.contention.read(&self.entry_status).await;
+        let now = Utc::now();
+        for data in result.values_mut() {
+            self.attach_quality(data, &statuses, now);
+        }
         result
     }
+
+    /// 为市场数据附加按当前时间计算的数据质量和价格偏离
+    fn attach_quality(&self, data: &mut CachedMarketData, statuses: &HashMap<String, EntryStatus>, now: DateTime<Utc>) {
+        let status = statuses.get(&market_data_key(&data.coin_id));
+        data.data_quality = Some(DataQuality::assess(data, status, &self.quality, now));
+        data.price_divergence = PriceDivergence::assess(data, self.divergence_threshold_pct, self.quality.stale_after_seconds, now);
+    }
+
+    /// 记录其他数据源的参考报价
+    /// 
+    /// 参考报价用于交叉校验主数据源的价格，同一数据源只保留最新一条；
+    /// 币种尚无主数据时忽略
+    /// 
+    /// # 参数
+    /// * `coin_id` - 币种ID
+    /// * `source` - 数据来源
+    /// * `price` - 价格（美元）
+    /// 
+    /// # 返回
+    /// * `bool` - 是否已记录
+    pub async fn record_price_quote(&self, coin_id: &str, source: &str, price: f64) -> bool {
+        {
+            let mut cache = self.contention.write(&self.market_data).await;
+            let Some(data) = cache.get_mut(coin_id) else {
+                debug!("⏭️ 币种 {} 尚无市场数据，忽略 {} 的参考报价", coin_id, source);
+                return false;
+            };
+            data.reference_quotes.retain(|q| q.source != source);
+            data.reference_quotes.push(PriceQuote {
+                source: source.to_string(),
+                price,
+                quoted_at: Utc::now(),
+            });
+        }
+        self.market_data_json.invalidate();
+        true
+    }
+    
+    /// 设置或清除币种的停止更新状态
+    /// 
+    /// 已处于停止更新状态时只更新原因，保留首次发现的时间；币种尚无市场数据时忽略
+    /// 
+    /// # 参数
+    /// * `coin_id` - 币种ID
+    /// * `reason` - 停止更新的原因，为None时清除标记
+    /// 
+    /// # 返回
+    /// * `bool` - 币种是否存在于缓存中
+    pub async fn set_coin_inactive(&self, coin_id: &str, reason: Option<String>) -> bool {
+        let mut cache = self.contention.write(&self.market_data).await;
+        let Some(data) = cache.get_mut(coin_id) else {
+            return false;
+        };
+        data.inactive = match (reason, data.inactive.take()) {
+            (Some(reason), Some(previous)) => Some(InactiveStatus { reason, since: previous.since }),
+            (Some(reason), None) => {
+                warn!("🪦 币种 {} 已停止更新: {}", coin_id, reason);
+                Some(InactiveStatus { reason, since: Utc::now() })
+            }
+            (None, previous) => {
+                if previous.is_some() {
+                    info!("✅ 币种 {} 已恢复更新", coin_id);
+                }
+                None
+            }
+        };
+        drop(cache);
+        self.market_data_json.invalidate();
+        true
+    }
     
     /// 清理过期数据
     /// 
@@ -198,20 +1107,31 @@ impl DataCache {
     /// 
     /// # 返回
     /// * `usize` - 清理的数据项数量
-    pub fn cleanup_expired_data(&self, max_age_hours: i64) -> usize {
+    pub async fn cleanup_expired_data(&self, max_age_hours: i64) -> usize {
         let cutoff_time = Utc::now() - chrono::Duration::hours(max_age_hours);
-        let mut cache = self.market_data.write().unwrap();
+        let mut cache = self.contention.write(&self.market_data).await;
         
         let initial_count = cache.len();
-        cache.retain(|_, data| data.updated_at > cutoff_time);
+        {
+            let mut memory = self.memory.lock().unwrap();
+            cache.retain(|coin_id, data| {
+                let keep = data.updated_at > cutoff_time;
+                if !keep {
+                    memory.remove(&market_data_key(coin_id));
+                }
+                keep
+            });
+        }
         let removed_count = initial_count - cache.len();
         
         if removed_count > 0 {
             info!("🧹 清理了 {} 条过期数据 (超过 {} 小时)", removed_count, max_age_hours);
             
             // 更新统计信息
-            let mut stats = self.stats.write().unwrap();
+            let mut stats = self.contention.write(&self.stats).await;
             stats.total_items = cache.len();
+            drop(cache);
+            self.market_data_json.invalidate();
         }
         
         removed_count
@@ -221,28 +1141,101 @@ impl DataCache {
     /// 
     /// # 返回
     /// * `Vec<String>` - 币种ID列表
-    pub fn get_supported_coins(&self) -> Vec<String> {
-        let cache = self.market_data.read().unwrap();
+    pub async fn get_supported_coins(&self) -> Vec<String> {
+        let cache = self.contention.read(&self.market_data).await;
         cache.keys().cloned().collect()
     }
 
+    /// 记录条目的估算大小，超出容量上限时淘汰最久未访问的其他条目
+    /// 
+    /// # 参数
+    /// * `key` - 缓存键（`market_data:<币种ID>` 或数据集键）
+    /// * `bytes` - 序列化后的字节数
+    async fn account(&self, key: &str, bytes: usize) {
+        let victims = {
+            let mut memory = self.memory.lock().unwrap();
+            memory.record(key, bytes);
+            memory.evict(&self.limits, key)
+        };
+        if victims.is_empty() {
+            return;
+        }
+        let (coins, datasets): (Vec<&String>, Vec<&String>) = victims.iter().partition(|key| key.starts_with("market_data:"));
+        if !coins.is_empty() {
+            let mut cache = self.contention.write(&self.market_data).await;
+            for key in &coins {
+                cache.remove(&key["market_data:".len()..]);
+            }
+            self.contention.write(&self.stats).await.total_items = cache.len();
+            drop(cache);
+            self.market_data_json.invalidate();
+        }
+        if !datasets.is_empty() {
+            let mut cache = self.contention.write(&self.datasets).await;
+            for key in &datasets {
+                cache.remove(key.as_str());
+            }
+        }
+        warn!("🧹 缓存超出容量上限，淘汰了 {} 个最久未访问的条目", victims.len());
+        debug!("🧹 淘汰的缓存条目: {:?}", victims);
+    }
+
+    /// 按当前内容重新估算全部条目的大小（从快照恢复后调用）
+    async fn reaccount(&self) {
+        let mut usage = MemoryUsage::default();
+        for (coin_id, data) in self.contention.read(&self.market_data).await.iter() {
+            usage.record(&market_data_key(coin_id), serde_json::to_vec(data).map(|json| json.len()).unwrap_or_default());
+        }
+        for (key, value) in self.contention.read(&self.datasets).await.iter() {
+            usage.record(key, serde_json::to_vec(value).map(|json| json.len()).unwrap_or_default());
+        }
+        let mut memory = self.memory.lock().unwrap();
+        usage.evictions = memory.evictions;
+        *memory = usage;
+    }
+
     /// 获取缓存统计信息
     /// 
     /// # 返回
     /// * `CacheStats` - 缓存统计信息
-    pub fn get_stats(&self) -> CacheStats {
-        let stats = self.stats.read().unwrap();
-        stats.clone()
+    pub async fn get_stats(&self) -> CacheStats {
+        let mut stats = self.contention.read(&self.stats).await.clone();
+        stats.hits = self.hits.load(Ordering::Relaxed);
+        stats.misses = self.misses.load(Ordering::Relaxed);
+        stats.lock_contention = self.contention.snapshot();
+        stats.serialized_hits = self.market_data_json.hits.load(Ordering::Relaxed);
+        stats.serialized_misses = self.market_data_json.misses.load(Ordering::Relaxed);
+        let memory = self.memory.lock().unwrap();
+        stats.memory = CacheMemoryStats {
+            entries: memory.entries.len(),
+            approx_bytes: memory.total_bytes,
+            max_bytes: self.limits.max_bytes,
+            max_entries: self.limits.max_entries,
+            evictions: memory.evictions,
+        };
+        drop(memory);
+        stats
     }
     
     /// 清空所有缓存
-    pub fn clear_all(&self) {
-        let mut cache = self.market_data.write().unwrap();
-        let mut stats = self.stats.write().unwrap();
+    pub async fn clear_all(&self) {
+        let mut cache = self.contention.write(&self.market_data).await;
+        let mut stats = self.contention.write(&self.stats).await;
         
         let cleared_count = cache.len();
+        {
+            let mut memory = self.memory.lock().unwrap();
+            for coin_id in cache.keys() {
+                memory.remove(&market_data_key(coin_id));
+            }
+        }
         cache.clear();
         *stats = CacheStats::default();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+        
+        drop(cache);
+        self.market_data_json.invalidate();
         
         warn!("🗑️ 已清空所有缓存数据 ({} 项)", cleared_count);
     }
@@ -251,9 +1244,8 @@ impl DataCache {
     /// 
     /// # 返回
     /// * `usize` - 缓存中的数据项数量
-    pub fn size(&self) -> usize {
-        let cache = self.market_data.read().unwrap();
-        cache.len()
+    pub async fn size(&self) -> usize {
+        self.contention.read(&self.market_data).await.len()
     }
     
     /// 检查是否包含指定币种的数据
@@ -263,26 +1255,23 @@ impl DataCache {
     /// 
     /// # 返回
     /// * `bool` - 是否包含数据
-    pub fn contains(&self, coin_id: &str) -> bool {
-        let cache = self.market_data.read().unwrap();
-        cache.contains_key(coin_id)
+    pub async fn contains(&self, coin_id: &str) -> bool {
+        self.contention.read(&self.market_data).await.contains_key(coin_id)
     }
 
     /// 设置贪婪恐惧指数数据
     /// 
     /// # 参数
-    /// * `data` - 贪婪恐惧指数数据
-    pub async fn set_fear_greed_index(&self, data: serde_json::Value) {
+    /// * `data` - 贪婪恐惧指数快照
+    pub async fn set_fear_greed_index(&self, data: FearGreedSnapshot) {
         debug!("💾 更新贪婪恐惧指数缓存");
         
-        {
-            let mut cache = self.fear_greed_index.write().unwrap();
-            *cache = Some(data);
-        }
+        *self.contention.write(&self.fear_greed_index).await = Some(data);
+        self.record_success(FEAR_GREED_KEY).await;
 
         // 更新统计信息
         {
-            let mut stats = self.stats.write().unwrap();
+            let mut stats = self.contention.write(&self.stats).await;
             stats.last_updated = Some(Utc::now());
             *stats.sources.entry("CoinMarketCap".to_string()).or_insert(0) += 1;
         }
@@ -293,29 +1282,20 @@ impl DataCache {
     /// 获取贪婪恐惧指数数据
     /// 
     /// # 返回
-    /// * `Option<serde_json::Value>` - 贪婪恐惧指数数据
-    pub fn get_fear_greed_index(&self) -> Option<serde_json::Value> {
+    /// * `Option<FearGreedSnapshot>` - 贪婪恐惧指数快照
+    pub async fn get_fear_greed_index(&self) -> Option<FearGreedSnapshot> {
         debug!("📖 读取贪婪恐惧指数缓存");
         
-        let cache = self.fear_greed_index.read().unwrap();
+        let result = self.contention.read(&self.fear_greed_index).await.clone();
         
-        if cache.is_some() {
-            // 更新命中统计
-            {
-                let mut stats = self.stats.write().unwrap();
-                stats.hits += 1;
-            }
+        self.record_lookup(result.is_some());
+        if result.is_some() {
             debug!("✅ 贪婪恐惧指数缓存命中");
         } else {
-            // 更新未命中统计
-            {
-                let mut stats = self.stats.write().unwrap();
-                stats.misses += 1;
-            }
             debug!("❌ 贪婪恐惧指数缓存未命中");
         }
         
-        cache.clone()
+        result
     }
 
     /// 设置山寨币季节指数数据
@@ -326,7 +1306,7 @@ impl DataCache {
         debug!("💾 更新山寨币季节指数缓存");
         
         {
-            let mut cache = self.altcoin_season_index.write().unwrap();
+            let mut cache = self.contention.write(&self.altcoin_season_index).await;
             // 尝试解析为AltcoinSeasonIndex，如果失败就存储JSON
             if let Ok(parsed_data) = serde_json::from_value::<AltcoinSeasonIndex>(data.clone()) {
                 *cache = Some(parsed_data);
@@ -336,14 +1316,14 @@ impl DataCache {
                     data.get("value").and_then(|v| v.as_u64()).map(|v| v as u8),
                     data.get("classification").and_then(|v| v.as_str()),
                     data.get("classification_zh").and_then(|v| v.as_str()),
-                    data.get("timestamp").and_then(|v| v.as_str()),
+                    data.get("timestamp").and_then(|v| v.as_str()).and_then(|raw| parse_timestamp(raw).ok()),
                     data.get("market_advice").and_then(|v| v.as_str()),
                 ) {
                     let altcoin_data = AltcoinSeasonIndex {
                         value,
                         classification: classification.to_string(),
                         classification_zh: classification_zh.to_string(),
-                        timestamp: timestamp.to_string(),
+                        timestamp,
                         outperforming_count: data.get("outperforming_count").and_then(|v| v.as_u64()).unwrap_or(0) as u8,
                         total_count: data.get("total_count").and_then(|v| v.as_u64()).unwrap_or(100) as u8,
                         outperforming_percentage: data.get("outperforming_percentage").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
@@ -353,10 +1333,11 @@ impl DataCache {
                 }
             }
         }
+        self.record_success(ALTCOIN_SEASON_KEY).await;
 
         // 更新统计信息
         {
-            let mut stats = self.stats.write().unwrap();
+            let mut stats = self.contention.write(&self.stats).await;
             stats.last_updated = Some(Utc::now());
             *stats.sources.entry("CoinMarketCap".to_string()).or_insert(0) += 1;
         }
@@ -368,28 +1349,146 @@ impl DataCache {
     /// 
     /// # 返回
     /// * `Option<AltcoinSeasonIndex>` - 山寨币季节指数数据
-    pub fn get_altcoin_season_index(&self) -> Option<AltcoinSeasonIndex> {
+    pub async fn get_altcoin_season_index(&self) -> Option<AltcoinSeasonIndex> {
         debug!("📖 读取山寨币季节指数缓存");
         
-        let cache = self.altcoin_season_index.read().unwrap();
+        let result = self.contention.read(&self.altcoin_season_index).await.clone();
         
-        if cache.is_some() {
-            // 更新命中统计
-            {
-                let mut stats = self.stats.write().unwrap();
-                stats.hits += 1;
-            }
+        self.record_lookup(result.is_some());
+        if result.is_some() {
             debug!("✅ 山寨币季节指数缓存命中");
         } else {
-            // 更新未命中统计
-            {
-                let mut stats = self.stats.write().unwrap();
-                stats.misses += 1;
-            }
             debug!("❌ 山寨币季节指数缓存未命中");
         }
         
-        cache.clone()
+        result
+    }
+
+    /// 按指标键读取当前数值
+    /// 
+    /// 支持的指标键：
+    /// * `fear_greed_index` - 贪婪恐惧指数
+    /// * `altcoin_season_index` - 山寨币季节指数
+    /// * `price:<coin_id>`、`price_change_24h:<coin_id>`、`market_cap:<coin_id>`、
+    ///   `volume_24h:<coin_id>`、`rsi:<coin_id>` - 币种市场数据字段
+    /// 
+    /// 该方法不计入缓存命中统计
+    /// 
+    /// # 参数
+    /// * `key` - 指标键
+    /// 
+    /// # 返回
+    /// * `Option<f64>` - 当前数值或None
+    pub async fn metric_value(&self, key: &str) -> Option<f64> {
+        match key {
+            FEAR_GREED_KEY => self.contention.read(&self.fear_greed_index).await
+                .as_ref()
+                .map(|data| data.value as f64),
+            ALTCOIN_SEASON_KEY => self.contention.read(&self.altcoin_season_index).await
+                .as_ref()
+                .map(|data| data.value as f64),
+            _ => {
+                let (field, coin_id) = key.split_once(':')?;
+                let cache = self.contention.read(&self.market_data).await;
+                let data = cache.get(coin_id)?;
+                match field {
+                    "price" => Some(data.current_price),
+                    "price_change_24h" => data.price_change_24h,
+                    "market_cap" => data.market_cap,
+                    "volume_24h" => data.volume_24h,
+                    "market_cap_rank" => data.market_cap_rank.map(|rank| rank as f64),
+                    "rsi" => Some(data.technical_indicators.rsi.value),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// 检查指标键格式是否受支持
+    /// 
+    /// # 参数
+    /// * `key` - 指标键
+    /// 
+    /// # 返回
+    /// * `bool` - 是否受支持
+    pub fn is_known_metric_key(key: &str) -> bool {
+        match key {
+            FEAR_GREED_KEY | ALTCOIN_SEASON_KEY => true,
+            _ => matches!(
+                key.split_once(':'),
+                Some(("price" | "price_change_24h" | "market_cap" | "market_cap_rank" | "volume_24h" | "rsi", coin)) if !coin.is_empty()
+            ),
+        }
+    }
+
+    /// 写入分析类数据集
+    /// 
+    /// # 参数
+    /// * `key` - 数据集键
+    /// * `data` - 数据集内容
+    pub async fn set_dataset<T: Serialize>(&self, key: &str, data: &T) -> anyhow::Result<()> {
+        let value = serde_json::to_value(data)?;
+        let bytes = serde_json::to_vec(&value)?.len();
+        self.contention.write(&self.datasets).await.insert(key.to_string(), value);
+        self.account(key, bytes).await;
+        self.record_success(key).await;
+        debug!("💾 更新数据集缓存: {}", key);
+        Ok(())
+    }
+
+    /// 读取分析类数据集
+    /// 
+    /// # 参数
+    /// * `key` - 数据集键
+    /// 
+    /// # 返回
+    /// * `Option<T>` - 数据集内容，不存在或类型不匹配时为None
+    pub async fn get_dataset<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let result = self.contention.read(&self.datasets).await
+            .get(key)
+            .and_then(|value| serde_json::from_value(value.clone()).ok());
+        self.record_lookup(result.is_some());
+        if result.is_some() {
+            self.memory.lock().unwrap().touch(key);
+        }
+        result
+    }
+
+    /// 生成缓存快照
+    /// 
+    /// # 返回
+    /// * `CacheSnapshot` - 当前缓存内容
+    pub async fn snapshot(&self) -> CacheSnapshot {
+        CacheSnapshot {
+            saved_at: Some(Utc::now()),
+            market_data: self.contention.read(&self.market_data).await.clone(),
+            fear_greed_index: self.contention.read(&self.fear_greed_index).await.clone(),
+            altcoin_season_index: self.contention.read(&self.altcoin_season_index).await.clone(),
+            datasets: self.contention.read(&self.datasets).await.clone(),
+            entry_status: self.contention.read(&self.entry_status).await.clone(),
+        }
+    }
+
+    /// 用快照替换缓存内容
+    /// 
+    /// 命中统计保持不变，不广播更新事件
+    /// 
+    /// # 参数
+    /// * `snapshot` - 缓存快照
+    pub async fn restore(&self, snapshot: CacheSnapshot) {
+        let total_items = snapshot.market_data.len();
+        *self.contention.write(&self.market_data).await = snapshot.market_data;
+        *self.contention.write(&self.fear_greed_index).await = snapshot.fear_greed_index;
+        *self.contention.write(&self.altcoin_season_index).await = snapshot.altcoin_season_index;
+        *self.contention.write(&self.datasets).await = snapshot.datasets;
+        *self.contention.write(&self.entry_status).await = snapshot.entry_status;
+        self.market_data_json.invalidate();
+        self.reaccount().await;
+
+        let mut stats = self.contention.write(&self.stats).await;
+        stats.total_items = total_items;
+        stats.last_updated = snapshot.saved_at;
+        debug!("💾 已从快照恢复缓存（{} 个币种）", total_items);
     }
 
     /// 设置币种数据（简化版本）
@@ -406,7 +1505,21 @@ impl DataCache {
             data.get("symbol").and_then(|v| v.as_str()),
             data.get("name").and_then(|v| v.as_str()),
         ) {
-            let cached_data = CachedMarketData {
+            let source = match data.get("data_source").and_then(|v| v.as_str()) {
+                Some(source) => source,
+                None if data.get("mock_data").is_some() => "Mock",
+                None => "CoinGecko",
+            };
+            let band = |name: &str| data.get("bollinger_bands").and_then(|b| b.get(name)).and_then(|v| v.as_f64());
+            let rsi = data.get("rsi").and_then(|v| v.as_f64()).unwrap_or(50.0); // 未提供时使用中性RSI
+            let signal = if rsi > 70.0 {
+                RSISignal::Overbought
+            } else if rsi < 30.0 {
+                RSISignal::Oversold
+            } else {
+                RSISignal::Normal
+            };
+            let mut cached_data = CachedMarketData {
                 coin_id: coin_id.to_string(),
                 name: name.to_string(),
                 symbol: symbol.to_string(),
@@ -414,39 +1527,68 @@ impl DataCache {
                 volume_24h: data.get("total_volume").and_then(|v| v.as_f64()),
                 price_change_24h: data.get("price_change_percentage_24h").and_then(|v| v.as_f64()),
                 market_cap: data.get("market_cap").and_then(|v| v.as_f64()),
+                market_cap_rank: data.get("market_cap_rank").and_then(|v| v.as_u64()),
+                tags: data.get("tags")
+                    .and_then(|v| v.as_array())
+                    .map(|tags| tags.iter().filter_map(|t| t.as_str()).map(str::to_string).collect())
+                    .unwrap_or_default(),
                 technical_indicators: TechnicalIndicatorsData {
                     bollinger_bands: BollingerBandsData {
-                        upper: current_price * 1.02, // 模拟数据
-                        middle: current_price,
-                        lower: current_price * 0.98,
+                        upper: band("upper").unwrap_or(current_price * 1.02), // 未提供时使用模拟数据
+                        middle: band("middle").unwrap_or(current_price),
+                        lower: band("lower").unwrap_or(current_price * 0.98),
                         period: 20,
                         std_dev_multiplier: 2.0,
                     },
                     rsi: RSIData {
-                        value: 50.0, // 模拟中性RSI
+                        value: rsi,
                         period: 14,
                         overbought_threshold: 70.0,
                         oversold_threshold: 30.0,
-                        signal: RSISignal::Normal,
+                        signal,
                     },
                 },
                 updated_at: Utc::now(),
-                source: if data.get("mock_data").is_some() { "Mock" } else { "CoinGecko" }.to_string(),
+                source: source.to_string(),
+                reference_quotes: Vec::new(),
+                data_quality: None,
+                price_divergence: None,
+                inactive: None,
+                supply: None,
             };
+            let circulating_supply = data.get("circulating_supply").and_then(|v| v.as_f64());
 
-            {
-                let mut cache = self.market_data.write().unwrap();
+            let (total_items, bytes) = {
+                let mut cache = self.contention.write(&self.market_data).await;
+                // 保留其他数据源的参考报价和停止更新状态
+                let previous = cache.remove(coin_id);
+                cached_data.supply = circulating_supply.map(|circulating_supply| SupplyMetrics::assess(
+                    circulating_supply,
+                    data.get("total_supply").and_then(|v| v.as_f64()),
+                    data.get("max_supply").and_then(|v| v.as_f64()),
+                    data.get("fully_diluted_market_cap").and_then(|v| v.as_f64()),
+                    cached_data.market_cap,
+                    previous.as_ref().and_then(|p| p.supply.as_ref()),
+                    cached_data.updated_at,
+                ));
+                if let Some(previous) = previous {
+                    cached_data.reference_quotes = previous.reference_quotes;
+                    cached_data.inactive = previous.inactive;
+                }
+                let bytes = serde_json::to_vec(&cached_data).map(|json| json.len()).unwrap_or_default();
                 cache.insert(coin_id.to_string(), cached_data);
-            }
+                (cache.len(), bytes)
+            };
+            self.record_success(&market_data_key(coin_id)).await;
 
             // 更新统计信息
             {
-                let mut stats = self.stats.write().unwrap();
+                let mut stats = self.contention.write(&self.stats).await;
                 stats.last_updated = Some(Utc::now());
-                stats.total_items = self.market_data.read().unwrap().len();
-                let source = if data.get("mock_data").is_some() { "Mock" } else { "CoinGecko" };
+                stats.total_items = total_items;
                 *stats.sources.entry(source.to_string()).or_insert(0) += 1;
             }
+            self.account(&market_data_key(coin_id), bytes).await;
 
             info!("✅ 币种数据缓存已更新: {}", coin_id);
         } else {
@@ -459,4 +1601,164 @@ impl Default for DataCache {
     fn default() -> Self {
         Self::new()
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lru_eviction_when_over_entry_limit() {
+        let cache = DataCache::new().with_limits(CacheConfig { max_bytes: 0, max_entries: 2 });
+        for coin_id in ["bitcoin", "ethereum"] {
+            cache.set_coin_data(coin_id, serde_json::json!({"current_price": 1.0, "symbol": coin_id, "name": coin_id})).await;
+        }
+        // 访问bitcoin后，ethereum成为最久未访问的条目
+        assert!(cache.get_market_data("bitcoin").await.is_some());
+        cache.set_coin_data("solana", serde_json::json!({"current_price": 1.0, "symbol": "SOL", "name": "Solana"})).await;
+
+        assert!(cache.contains("bitcoin").await);
+        assert!(!cache.contains("ethereum").await);
+        assert!(cache.contains("solana").await);
+        let stats = cache.get_stats().await;
+        assert_eq!(stats.total_items, 2);
+        assert_eq!((stats.memory.entries, stats.memory.evictions), (2, 1));
+        assert!(stats.memory.approx_bytes > 0);
+    }
+
+    #[tokio::test]
+    async fn test_serialized_market_data_invalidated_on_write() {
+        let cache = DataCache::new();
+        cache.set_coin_data("hype", serde_json::json!({"current_price": 40.0, "symbol": "HYPE", "name": "Hyperliquid"})).await;
+
+        let first = cache.get_all_market_data_json().await.unwrap();
+        let second = cache.get_all_market_data_json().await.unwrap();
+        // 复用同一份字节
+        assert_eq!(first.as_ptr(), second.as_ptr());
+        let parsed: Vec<CachedMarketData> = serde_json::from_slice(&first).unwrap();
+        assert_eq!(parsed[0].current_price, 40.0);
+
+        cache.set_coin_data("hype", serde_json::json!({"current_price": 41.0, "symbol": "HYPE", "name": "Hyperliquid"})).await;
+        let third = cache.get_all_market_data_json().await.unwrap();
+        let parsed: Vec<CachedMarketData> = serde_json::from_slice(&third).unwrap();
+        assert_eq!(parsed[0].current_price, 41.0);
+
+        let stats = cache.get_stats().await;
+        assert_eq!((stats.serialized_hits, stats.serialized_misses), (1, 2));
+    }
+
+    #[tokio::test]
+    async fn test_batched_updates_are_coalesced() {
+        let cache = DataCache::new();
+        let mut updates = cache.subscribe();
+
+        cache.batched(async {
+            for coin_id in ["bitcoin", "ethereum", "bitcoin"] {
+                cache.record_success(&format!("market_data:{}", coin_id)).await;
+            }
+        }).await;
+        let update = updates.try_recv().unwrap();
+        assert_eq!(update.keys, vec!["market_data:bitcoin", "market_data:ethereum"]);
+        assert_eq!(update.coin_ids().collect::<Vec<_>>(), vec!["bitcoin", "ethereum"]);
+        assert!(updates.try_recv().is_err());
+
+        // 批次外的更新立即广播
+        cache.record_success(FEAR_GREED_KEY).await;
+        assert_eq!(updates.try_recv().unwrap().keys, vec![FEAR_GREED_KEY]);
+    }
+
+    #[tokio::test]
+    async fn test_hit_miss_and_contention_stats() {
+        let cache = DataCache::new();
+        cache.set_coin_data("hype", serde_json::json!({
+            "current_price": 42.0,
+            "symbol": "HYPE",
+            "name": "Hyperliquid"
+        })).await;
+
+        assert!(cache.get_market_data("hype").await.is_some());
+        assert!(cache.get_market_data("unknown").await.is_none());
+        assert!(cache.get_fear_greed_index().await.is_none());
+
+        let stats = cache.get_stats().await;
+        assert_eq!(stats.total_items, 1);
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 2);
+        assert!(stats.lock_contention.acquisitions > 0);
+    }
+
+    #[tokio::test]
+    async fn test_data_quality() {
+        let cache = DataCache::new();
+        cache.set_coin_data("hype", serde_json::json!({
+            "current_price": 40.0,
+            "symbol": "HYPE",
+            "name": "Hyperliquid",
+            "data_source": "CoinMarketCap"
+        })).await;
+
+        // 单一数据源、刚更新、无失败
+        let quality = cache.get_market_data("hype").await.unwrap().data_quality.unwrap();
+        assert_eq!(quality.source, "CoinMarketCap");
+        assert_eq!((quality.agreeing_sources, quality.total_sources), (1, 1));
+        assert_eq!(quality.score, 85);
+
+        // 一个一致、一个偏离的参考报价，外加两次采集失败
+        assert!(cache.record_price_quote("hype", "Hyperliquid", 40.2).await);
+        assert!(cache.record_price_quote("hype", "Binance", 44.0).await);
+        assert!(!cache.record_price_quote("unknown", "Binance", 1.0).await);
+        cache.record_failure(&market_data_key("hype"), "HTTP 500").await;
+        cache.record_failure(&market_data_key("hype"), "HTTP 500").await;
+        let quality = cache.get_market_data("hype").await.unwrap().data_quality.unwrap();
+        assert_eq!((quality.agreeing_sources, quality.total_sources, quality.recent_failures), (2, 3, 2));
+        assert_eq!(quality.score, 78);
+
+        // 参考报价在重新采集后保留，过期数据降分
+        let mut data = cache.get_market_data("hype").await.unwrap();
+        data.updated_at -= chrono::Duration::hours(24);
+        let old = DataQuality::assess(&data, None, &DataQualityConfig::default(), Utc::now());
+        assert_eq!(old.total_sources, 3);
+        assert!(old.score < 60);
+    }
+
+    #[tokio::test]
+    async fn test_failure_marks_last_known_good_as_stale() {
+        let cache = DataCache::new();
+        cache.set_fear_greed_index(FearGreedSnapshot::from_value(30, Utc::now(), "test")).await;
+        cache.record_failure(FEAR_GREED_KEY, "HTTP 500").await;
+        cache.record_failure(FEAR_GREED_KEY, "HTTP 500").await;
+
+        let status = cache.get_entry_status(FEAR_GREED_KEY).await.unwrap();
+        assert!(status.stale);
+        assert_eq!(status.consecutive_failures, 2);
+        assert!(cache.get_fear_greed_index().await.is_some());
+
+        cache.set_fear_greed_index(FearGreedSnapshot::from_value(31, Utc::now(), "test")).await;
+        let status = cache.get_entry_status(FEAR_GREED_KEY).await.unwrap();
+        assert!(!status.stale);
+        assert_eq!(status.consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_supply_metrics() {
+        let start = Utc::now() - chrono::Duration::days(10);
+        let first = SupplyMetrics::assess(300.0, Some(900.0), Some(1000.0), Some(10_000.0), Some(3_000.0), None, start);
+        assert_eq!(first.dilution_ratio, Some(0.3));
+        assert_eq!(first.unreleased_supply, Some(700.0));
+        assert_eq!(first.emission_per_day, None);
+
+        // 10天新增100，日均10，30天释放300（占流通量75%）
+        let now = start + chrono::Duration::days(10);
+        let second = SupplyMetrics::assess(400.0, Some(900.0), Some(1000.0), None, None, Some(&first), now);
+        assert_eq!(second.dilution_ratio, Some(0.4));
+        assert_eq!(second.baseline_at, start);
+        assert_eq!(second.emission_per_day, Some(10.0));
+        assert_eq!(second.emission_30d, Some(300.0));
+        assert_eq!(second.emission_30d_pct, Some(75.0));
+
+        // 估算释放量不超过尚未流通的供应量
+        let later = now + chrono::Duration::days(10);
+        let third = SupplyMetrics::assess(950.0, None, Some(1000.0), None, None, Some(&second), later);
+        assert_eq!(third.emission_30d, Some(50.0));
+    }
+}
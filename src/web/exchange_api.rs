@@ -0,0 +1,94 @@
+use axum::{
+    Router,
+    routing::{delete, get, post},
+    extract::{Path, State},
+    response::Json,
+    http::StatusCode,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::api::ApiResponse;
+use super::auth::{audit_entry, AuthUser};
+use super::cache::DataCache;
+use super::AppState;
+use crate::audit::AuditLog;
+use crate::portfolio::{ExchangeAccountInput, ExchangeAccountStore, ExchangeAccountView, PortfolioStore};
+
+/// 创建交易所账户路由
+/// 
+/// 所有接口都需要登录，返回结果中不包含API密钥
+/// 
+/// # 返回
+/// * `Router<AppState>` - 交易所账户管理路由
+pub fn create_exchange_routes() -> Router<AppState> {
+    Router::new()
+        // 账户列表 / 添加账户
+        .route("/exchanges", get(list_accounts).post(add_account))
+        // 删除账户
+        .route("/exchanges/:id", delete(delete_account))
+        // 立即同步
+        .route("/exchanges/:id/sync", post(sync_account))
+}
+
+/// 获取当前用户的交易所账户
+async fn list_accounts(
+    user: AuthUser,
+    State(accounts): State<Arc<ExchangeAccountStore>>,
+) -> Json<ApiResponse<Vec<ExchangeAccountView>>> {
+    Json(ApiResponse::success(accounts.list(user.id).await))
+}
+
+/// 添加交易所账户
+async fn add_account(
+    user: AuthUser,
+    State(audit): State<Arc<AuditLog>>,
+    State(accounts): State<Arc<ExchangeAccountStore>>,
+    Json(input): Json<ExchangeAccountInput>,
+) -> Result<Json<ApiResponse<ExchangeAccountView>>, StatusCode> {
+    match accounts.add(user.id, input).await {
+        Ok(account) => {
+            audit.record(audit_entry(&Some(user), "exchange_account.add").target(account.id).payload(&account)).await;
+            Ok(Json(ApiResponse::success(account)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 删除交易所账户及其导入的持仓
+async fn delete_account(
+    user: AuthUser,
+    State(audit): State<Arc<AuditLog>>,
+    State(accounts): State<Arc<ExchangeAccountStore>>,
+    State(portfolio): State<Arc<PortfolioStore>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ExchangeAccountView>>, StatusCode> {
+    match accounts.delete(user.id, id, &portfolio).await {
+        Ok(account) => {
+            audit.record(audit_entry(&Some(user), "exchange_account.delete").target(account.id).payload(&account)).await;
+            Ok(Json(ApiResponse::success(account)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 立即同步交易所账户
+async fn sync_account(
+    user: AuthUser,
+    State(audit): State<Arc<AuditLog>>,
+    State(accounts): State<Arc<ExchangeAccountStore>>,
+    State(portfolio): State<Arc<PortfolioStore>>,
+    State(cache): State<Arc<DataCache>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<usize>>, StatusCode> {
+    if !accounts.list(user.id).await.iter().any(|a| a.id == id) {
+        return Ok(Json(ApiResponse::error(format!("交易所账户 {} 不存在", id))));
+    }
+    match accounts.sync(id, &portfolio, &cache).await {
+        Ok(count) => {
+            audit.record(audit_entry(&Some(user), "exchange_account.sync").target(id).payload(serde_json::json!({ "assets": count }))).await;
+            Ok(Json(ApiResponse::success(count)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
@@ -0,0 +1,44 @@
+use axum::{
+    Router,
+    routing::get,
+    extract::{Query, State},
+    http::header,
+    response::IntoResponse,
+};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use super::api::CalendarQuery;
+use super::AppState;
+use crate::calendar::{render_ics, EconomicCalendar, EventImportance};
+
+/// 订阅中保留的已过去事件天数
+const PAST_DAYS: i64 = 7;
+
+/// 创建订阅源路由
+///
+/// # 返回
+/// * `Router<AppState>` - 订阅源路由
+pub fn create_feed_routes() -> Router<AppState> {
+    Router::new()
+        // 日历订阅（可直接添加到 Google Calendar）
+        .route("/feeds/events.ics", get(events_feed))
+}
+
+/// 宏观事件日历订阅
+///
+/// 默认包含最近7天和未来30天的中、高重要性事件
+async fn events_feed(
+    State(calendar): State<Arc<EconomicCalendar>>,
+    Query(query): Query<CalendarQuery>,
+) -> impl IntoResponse {
+    let now = Utc::now();
+    let until = now + Duration::days(query.days.unwrap_or(30).clamp(1, 90));
+    let events = calendar
+        .events_between(now - Duration::days(PAST_DAYS), until, query.importance.unwrap_or(EventImportance::Medium))
+        .await;
+    (
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        render_ics("EverScan 宏观事件", &events, now),
+    )
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::sync::Arc;
+
+/// 行情数据（`CachedMarketData`）可选择的顶层字段
+pub const MARKET_DATA_FIELDS: &[&str] = &[
+    "coin_id", "name", "symbol", "current_price", "volume_24h", "price_change_24h", "market_cap",
+    "market_cap_rank", "tags", "technical_indicators", "updated_at", "source", "reference_quotes",
+    "data_quality", "price_divergence", "inactive", "supply",
+];
+
+/// 行情数据字段的简写
+pub const MARKET_DATA_ALIASES: &[(&str, &str)] = &[
+    ("price", "current_price"),
+    ("volume", "volume_24h"),
+    ("change_24h", "price_change_24h"),
+    ("rank", "market_cap_rank"),
+    ("rsi", "technical_indicators.rsi"),
+    ("bollinger", "technical_indicators.bollinger_bands"),
+];
+
+/// 稀疏字段集
+///
+/// 由 `?fields=price,rsi,market_cap` 解析，只序列化列出的字段，支持简写和以 `.` 分隔的嵌套路径；
+/// 标识字段（如 `coin_id`）总是保留，以便调用方区分列表中的条目
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSelection {
+    /// 字段路径
+    paths: Vec<Vec<String>>,
+}
+
+impl FieldSelection {
+    /// 解析字段列表
+    ///
+    /// # 参数
+    /// * `fields` - 逗号分隔的字段列表
+    /// * `known` - 可选择的顶层字段
+    /// * `aliases` - 字段简写
+    /// * `always` - 总是保留的字段
+    ///
+    /// # 返回
+    /// * `Result<Option<Self>, String>` - 字段列表为空时为None，含未知字段时返回错误信息
+    pub fn parse(fields: &str, known: &[&str], aliases: &[(&str, &str)], always: &[&str]) -> Result<Option<Self>, String> {
+        let mut paths: Vec<Vec<String>> = always.iter().map(|f| vec![f.to_string()]).collect();
+        let mut requested = false;
+        for field in fields.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            let field = aliases.iter().find(|(alias, _)| *alias == field).map_or(field, |(_, path)| *path);
+            let path: Vec<String> = field.split('.').map(str::to_string).collect();
+            if !known.contains(&path[0].as_str()) || path.iter().any(String::is_empty) {
+                return Err(format!("未知字段: {}（可选 {}）", field, known.join(", ")));
+            }
+            requested = true;
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        Ok(requested.then_some(Self { paths }))
+    }
+
+    /// 从序列化后的对象中只保留选择的字段（对象中不存在的字段忽略）
+    pub fn apply(&self, value: Value) -> Value {
+        let Value::Object(source) = value else {
+            return value;
+        };
+        let mut selected = Map::new();
+        for path in &self.paths {
+            copy_path(&source, &mut selected, path);
+        }
+        Value::Object(selected)
+    }
+}
+
+/// 字段选择查询参数
+#[derive(Debug, Default, Deserialize)]
+pub struct FieldsQuery {
+    /// 逗号分隔的字段列表（如 `price,rsi,market_cap`）
+    pub fields: Option<String>,
+}
+
+impl FieldsQuery {
+    /// 解析行情数据的字段集（总是保留 `coin_id`）
+    pub fn market_data(&self) -> Result<Option<Arc<FieldSelection>>, String> {
+        let Some(fields) = self.fields.as_deref() else {
+            return Ok(None);
+        };
+        Ok(FieldSelection::parse(fields, MARKET_DATA_FIELDS, MARKET_DATA_ALIASES, &["coin_id"])?.map(Arc::new))
+    }
+}
+
+/// 把 `source` 中的路径复制到 `target`，中间层级按需创建
+fn copy_path(source: &Map<String, Value>, target: &mut Map<String, Value>, path: &[String]) {
+    let Some((key, rest)) = path.split_first() else {
+        return;
+    };
+    let Some(value) = source.get(key) else {
+        return;
+    };
+    match (rest.is_empty(), value) {
+        (true, value) => {
+            target.insert(key.clone(), value.clone());
+        }
+        (false, Value::Object(child)) => {
+            let entry = target.entry(key.clone()).or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(entry) = entry {
+                copy_path(child, entry, rest);
+            }
+        }
+        (false, _) => {}
+    }
+}
+
+/// 按字段集序列化的响应数据（未选择字段时原样序列化）
+pub struct Sparse<T> {
+    /// 数据
+    value: T,
+    /// 字段集
+    fields: Option<Arc<FieldSelection>>,
+}
+
+impl<T> Sparse<T> {
+    /// 包装响应数据
+    pub fn new(value: T, fields: Option<Arc<FieldSelection>>) -> Self {
+        Self { value, fields }
+    }
+}
+
+impl<T: Serialize> Serialize for Sparse<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(fields) = &self.fields else {
+            return self.value.serialize(serializer);
+        };
+        let value = serde_json::to_value(&self.value).map_err(serde::ser::Error::custom)?;
+        fields.apply(value).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_field_selection() {
+        let parse = |fields| FieldSelection::parse(fields, MARKET_DATA_FIELDS, MARKET_DATA_ALIASES, &["coin_id"]);
+        assert_eq!(parse(" ,").unwrap(), None);
+        assert!(parse("price,bogus").is_err());
+
+        let fields = Arc::new(parse("price,rsi,market_cap,supply").unwrap().unwrap());
+        let data = json!({
+            "coin_id": "bitcoin",
+            "name": "Bitcoin",
+            "current_price": 65000.0,
+            "market_cap": 1.2e12,
+            "technical_indicators": { "rsi": { "value": 55.0 }, "bollinger_bands": { "upper": 70000.0 } },
+        });
+        let sparse = serde_json::to_value(Sparse::new(data.clone(), Some(fields))).unwrap();
+        assert_eq!(sparse, json!({
+            "coin_id": "bitcoin",
+            "current_price": 65000.0,
+            "market_cap": 1.2e12,
+            "technical_indicators": { "rsi": { "value": 55.0 } },
+        }));
+        assert_eq!(serde_json::to_value(Sparse::new(data.clone(), None)).unwrap(), data);
+    }
+}
@@ -0,0 +1,259 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+/// 数值的展示方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberKind {
+    /// 价格（`$65000.00`，低价币保留更多小数位）
+    Price,
+    /// 美元金额，按数量级缩写（`$1.24B`）
+    Usd,
+    /// 数量，按数量级缩写（`19.70M`）
+    Count,
+    /// 百分比（`52.3%`）
+    Percent,
+    /// 带符号的涨跌幅（`+3.20%`）
+    Change,
+}
+
+/// 需要附加可读字符串的字段（按字段名匹配，嵌套对象中同样生效）
+pub const HUMAN_FIELDS: &[(&str, NumberKind)] = &[
+    ("current_price", NumberKind::Price),
+    ("market_cap", NumberKind::Usd),
+    ("volume_24h", NumberKind::Usd),
+    ("fully_diluted_market_cap", NumberKind::Usd),
+    ("total", NumberKind::Usd),
+    ("total2", NumberKind::Usd),
+    ("total3", NumberKind::Usd),
+    ("circulating_supply", NumberKind::Count),
+    ("total_supply", NumberKind::Count),
+    ("max_supply", NumberKind::Count),
+    ("unreleased_supply", NumberKind::Count),
+    ("emission_30d", NumberKind::Count),
+    ("price_change_24h", NumberKind::Change),
+    ("change_24h_pct", NumberKind::Change),
+    ("emission_30d_pct", NumberKind::Percent),
+    ("btc_dominance", NumberKind::Percent),
+    ("eth_dominance", NumberKind::Percent),
+];
+
+/// 可读字符串字段名的后缀
+pub const HUMAN_SUFFIX: &str = "_human";
+
+/// 按数量级缩写（K/M/B/T），不足一千时保留整数
+fn compact(value: f64) -> String {
+    let abs = value.abs();
+    match abs {
+        v if v >= 1e12 => format!("{:.2}T", value / 1e12),
+        v if v >= 1e9 => format!("{:.2}B", value / 1e9),
+        v if v >= 1e6 => format!("{:.2}M", value / 1e6),
+        v if v >= 1e3 => format!("{:.2}K", value / 1e3),
+        _ => format!("{:.0}", value),
+    }
+}
+
+/// 格式化价格，低价币保留更多小数位
+pub fn format_price(price: f64) -> String {
+    if price.abs() >= 1.0 {
+        format!("${:.2}", price)
+    } else {
+        format!("${:.6}", price)
+    }
+}
+
+/// 格式化美元金额（市值、成交量），如 `$1.24B`
+pub fn format_usd(amount: f64) -> String {
+    if amount < 0.0 {
+        format!("-${}", compact(-amount))
+    } else {
+        format!("${}", compact(amount))
+    }
+}
+
+/// 格式化数量（供应量），如 `19.70M`
+pub fn format_count(count: f64) -> String {
+    compact(count)
+}
+
+/// 格式化百分比，如 `52.3%`
+pub fn format_percent(pct: f64) -> String {
+    format!("{:.1}%", pct)
+}
+
+/// 格式化涨跌幅，正数带 `+` 号，如 `+3.20%`
+pub fn format_change(pct: f64) -> String {
+    if pct >= 0.0 {
+        format!("+{:.2}%", pct)
+    } else {
+        format!("{:.2}%", pct)
+    }
+}
+
+/// 解析IANA时区名称（如 `Asia/Shanghai`、`UTC`）
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.trim().parse::<Tz>().map_err(|_| format!("未知时区: {}（使用IANA名称，如 Asia/Shanghai）", name))
+}
+
+/// 按时区格式化时间，如 `2024-01-01 08:00 Asia/Shanghai`
+///
+/// 时间在存储和原始响应中统一为UTC，只在面向人的输出中转换为本地时间
+pub fn format_time(time: DateTime<Utc>, tz: Tz) -> String {
+    format!("{} {}", time.with_timezone(&tz).format("%Y-%m-%d %H:%M"), tz.name())
+}
+
+/// 是否为时间字段（`timestamp` 或以 `_at` 结尾）
+fn is_time_field(key: &str) -> bool {
+    key == "timestamp" || key.ends_with("_at")
+}
+
+impl NumberKind {
+    /// 按展示方式格式化数值
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            Self::Price => format_price(value),
+            Self::Usd => format_usd(value),
+            Self::Count => format_count(value),
+            Self::Percent => format_percent(value),
+            Self::Change => format_change(value),
+        }
+    }
+}
+
+/// 响应的数值格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    /// 只返回原始数值（默认）
+    #[default]
+    Raw,
+    /// 在原始数值旁附加 `<字段>_human` 可读字符串
+    Human,
+}
+
+/// 数值格式查询参数
+#[derive(Debug, Default, Deserialize)]
+pub struct FormatQuery {
+    /// `raw`（默认）或 `human`
+    pub format: Option<OutputFormat>,
+    /// 可读时间使用的IANA时区（默认UTC，指定时隐含 `format=human`）
+    pub tz: Option<String>,
+}
+
+impl FormatQuery {
+    /// 可读时间使用的时区
+    pub fn timezone(&self) -> Result<Tz, String> {
+        self.tz.as_deref().map_or(Ok(Tz::UTC), parse_timezone)
+    }
+
+    /// 需要附加可读字符串时返回渲染时间使用的时区，原样输出时为None
+    pub fn resolve(&self) -> Result<Option<Tz>, String> {
+        let tz = self.timezone()?;
+        Ok((self.format == Some(OutputFormat::Human) || self.tz.is_some()).then_some(tz))
+    }
+}
+
+/// 为对象中的已知数值字段和时间字段附加 `<字段>_human` 可读字符串（递归处理嵌套对象和数组）
+///
+/// # 参数
+/// * `value` - 序列化后的数据
+/// * `tz` - 可读时间使用的时区
+pub fn humanize(value: Value, tz: Tz) -> Value {
+    match value {
+        Value::Object(source) => {
+            let mut target = Map::with_capacity(source.len());
+            for (key, value) in source {
+                let human = match &value {
+                    Value::Number(number) => HUMAN_FIELDS.iter()
+                        .find(|(field, _)| *field == key)
+                        .and_then(|(_, kind)| Some(kind.format(number.as_f64()?))),
+                    Value::String(raw) if is_time_field(&key) => DateTime::parse_from_rfc3339(raw).ok()
+                        .map(|time| format_time(time.with_timezone(&Utc), tz)),
+                    _ => None,
+                };
+                if let Some(human) = human {
+                    target.insert(format!("{}{}", key, HUMAN_SUFFIX), Value::String(human));
+                }
+                target.insert(key, humanize(value, tz));
+            }
+            Value::Object(target)
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(|item| humanize(item, tz)).collect()),
+        value => value,
+    }
+}
+
+/// 按需附加可读字符串的响应数据（不需要时原样序列化）
+pub struct Humanized<T> {
+    /// 数据
+    value: T,
+    /// 可读时间使用的时区，None表示原样序列化
+    tz: Option<Tz>,
+}
+
+impl<T> Humanized<T> {
+    /// 包装响应数据
+    ///
+    /// # 参数
+    /// * `value` - 响应数据
+    /// * `tz` - 由 `FormatQuery::resolve` 得到的时区
+    pub fn new(value: T, tz: Option<Tz>) -> Self {
+        Self { value, tz }
+    }
+}
+
+impl<T: Serialize> Serialize for Humanized<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(tz) = self.tz else {
+            return self.value.serialize(serializer);
+        };
+        let value = serde_json::to_value(&self.value).map_err(serde::ser::Error::custom)?;
+        humanize(value, tz).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_formatters() {
+        assert_eq!(format_usd(1_240_000_000.0), "$1.24B");
+        assert_eq!(format_usd(2.35e12), "$2.35T");
+        assert_eq!(format_usd(950.0), "$950");
+        assert_eq!(format_count(19_700_000.0), "19.70M");
+        assert_eq!(format_price(65000.0), "$65000.00");
+        assert_eq!(format_price(0.000123), "$0.000123");
+        assert_eq!(format_percent(3.2), "3.2%");
+        assert_eq!(format_change(3.2), "+3.20%");
+        assert_eq!(format_change(-1.5), "-1.50%");
+    }
+
+    #[test]
+    fn test_humanize_adds_sibling_strings() {
+        let data = json!({
+            "coin_id": "bitcoin",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "market_cap": 1.24e9,
+            "price_change_24h": null,
+            "supply": { "circulating_supply": 19_700_000.0 },
+            "history": [{ "total": 2.35e12 }],
+        });
+        let human = serde_json::to_value(Humanized::new(data.clone(), Some(Tz::UTC))).unwrap();
+        assert_eq!(human["market_cap"], json!(1.24e9));
+        assert_eq!(human["market_cap_human"], json!("$1.24B"));
+        assert!(human.get("price_change_24h_human").is_none());
+        assert_eq!(human["supply"]["circulating_supply_human"], json!("19.70M"));
+        assert_eq!(human["history"][0]["total_human"], json!("$2.35T"));
+        assert_eq!(human["updated_at_human"], json!("2024-01-01 00:00 UTC"));
+        assert_eq!(serde_json::to_value(Humanized::new(data.clone(), None)).unwrap(), data);
+
+        let shanghai = parse_timezone("Asia/Shanghai").unwrap();
+        let human = serde_json::to_value(Humanized::new(data, Some(shanghai))).unwrap();
+        assert_eq!(human["updated_at"], json!("2024-01-01T00:00:00Z"));
+        assert_eq!(human["updated_at_human"], json!("2024-01-01 08:00 Asia/Shanghai"));
+        assert!(parse_timezone("Mars/Olympus").is_err());
+    }
+}
@@ -0,0 +1,353 @@
+use anyhow::{anyhow, bail, Context, Result};
+use axum::{
+    Router,
+    routing::post,
+    body::Bytes,
+    extract::{Path, Query, State},
+    response::Json,
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use super::api::ApiResponse;
+use super::cache::DataCache;
+use super::signature::SignatureVerifier;
+use super::AppState;
+use crate::config::IngestSourceConfig;
+use crate::models::{register_source, AggregatedMetric, DataSource, MetricBuilder, MetricValue, SourceKind};
+use crate::storage::MetricStore;
+
+/// 推送令牌请求头（也可以用 `Authorization: Bearer <令牌>` 或 `?token=`）
+pub const INGEST_TOKEN_HEADER: &str = "x-ingest-token";
+
+/// 单次推送最多的指标数
+pub const MAX_INGEST_METRICS: usize = 500;
+
+/// 推送数据点的时间戳最多允许超前的时间
+const MAX_CLOCK_SKEW_MINUTES: i64 = 5;
+
+/// 生成推送数据源最新值的数据集键
+pub fn ingest_key(source: &str) -> String {
+    format!("ingest:{}", source)
+}
+
+/// 推送数据源
+struct IngestSource {
+    /// 数据源描述
+    source: DataSource,
+    /// 推送令牌
+    token: Option<String>,
+    /// 请求签名校验器
+    signature: Option<SignatureVerifier>,
+}
+
+/// 已配置的推送数据源
+pub struct IngestSources {
+    /// 数据源标识 -> 数据源
+    sources: HashMap<String, IngestSource>,
+    /// 只读API实例不接受推送（指标存储由采集实例写入）
+    read_only: bool,
+}
+
+impl IngestSources {
+    /// 根据配置创建推送数据源，并登记到数据源登记表
+    ///
+    /// # 参数
+    /// * `configs` - 推送数据源配置
+    /// * `read_only` - 是否为只读API实例
+    ///
+    /// # 返回
+    /// * `Result<Self>` - 标识无效、重复，既未配置令牌也未配置签名校验，或签名配置无效时返回错误
+    pub fn from_config(configs: &[IngestSourceConfig], read_only: bool) -> Result<Self> {
+        let mut sources = HashMap::new();
+        for config in configs {
+            let name = config.name.to_lowercase();
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+                bail!("推送数据源标识只能包含字母、数字、下划线和连字符: {}", config.name);
+            }
+            let token = config.token.as_ref().map(|t| t.expose().to_string()).filter(|t| !t.is_empty());
+            let signature = config.signature.as_ref()
+                .map(SignatureVerifier::from_config)
+                .transpose()
+                .with_context(|| format!("推送数据源 {} 的签名校验配置无效", name))?;
+            if token.is_none() && signature.is_none() {
+                bail!("推送数据源 {} 未配置令牌或签名校验", name);
+            }
+            if sources.contains_key(&name) {
+                bail!("推送数据源 {} 重复配置", name);
+            }
+            // 数据源登记表以 &'static str 为键，推送数据源只在启动时按配置创建一次
+            let static_name: &'static str = Box::leak(name.clone().into_boxed_str());
+            let source = DataSource {
+                name: static_name,
+                display_name: static_name,
+                kind: SourceKind::Push,
+                capabilities: &[],
+            };
+            register_source(source);
+            info!("📥 已启用推送数据源: {}", name);
+            sources.insert(name, IngestSource { source, token, signature });
+        }
+        Ok(Self { sources, read_only })
+    }
+
+    /// 校验推送令牌和请求签名（只校验已配置的项）
+    ///
+    /// # 参数
+    /// * `name` - 数据源标识
+    /// * `provided` - 请求提供的令牌
+    /// * `headers` - 请求头
+    /// * `body` - 原始请求体
+    ///
+    /// # 返回
+    /// * `Result<DataSource, StatusCode>` - 数据源不存在时返回404，令牌错误或签名校验失败时返回401
+    fn authorize(&self, name: &str, provided: Option<&str>, headers: &HeaderMap, body: &[u8]) -> Result<DataSource, StatusCode> {
+        let source = self.sources.get(name).ok_or(StatusCode::NOT_FOUND)?;
+        if let Some(token) = &source.token {
+            if !provided.is_some_and(|provided| token_matches(provided, token)) {
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+        }
+        if let Some(signature) = &source.signature {
+            if let Err(e) = signature.verify(headers, body, Utc::now()) {
+                warn!("⚠️ 推送数据源 {} 签名校验失败: {}", name, e);
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+        }
+        Ok(source.source)
+    }
+}
+
+/// 比较令牌（耗时与不匹配的位置无关）
+pub(crate) fn token_matches(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided.bytes().zip(expected.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// 推送的指标
+///
+/// `value` 可以是数字或数字字符串（便于TradingView等模板直接填入占位符）
+#[derive(Debug, Clone, Deserialize)]
+pub struct IngestMetric {
+    /// 指标名称
+    pub name: String,
+    /// 数值
+    pub value: serde_json::Value,
+    /// 单位（可选）
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// 时间戳（RFC 3339，默认为接收时间）
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+    /// 标签
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+/// 推送请求体：`{"metrics": [...]}`、指标数组或单个指标
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum IngestPayload {
+    /// 批量
+    Batch { metrics: Vec<IngestMetric> },
+    /// 数组
+    List(Vec<IngestMetric>),
+    /// 单个指标
+    Single(IngestMetric),
+}
+
+impl IngestPayload {
+    /// 解析请求体（不依赖Content-Type，TradingView以text/plain发送JSON）
+    fn parse(body: &[u8]) -> Result<Vec<IngestMetric>> {
+        let payload: IngestPayload = serde_json::from_slice(body)
+            .map_err(|e| anyhow!("请求体应为指标对象、指标数组或 {{\"metrics\": [...]}}: {}", e))?;
+        Ok(match payload {
+            IngestPayload::Batch { metrics } | IngestPayload::List(metrics) => metrics,
+            IngestPayload::Single(metric) => vec![metric],
+        })
+    }
+}
+
+/// 推送数据源的最新值（缓存中的数据集）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestedValue {
+    /// 数值
+    pub value: f64,
+    /// 单位（可选）
+    pub unit: Option<String>,
+    /// 标签
+    pub labels: BTreeMap<String, String>,
+    /// 数据点时间
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 推送结果
+#[derive(Debug, Serialize)]
+pub struct IngestAccepted {
+    /// 数据源
+    pub source: String,
+    /// 写入的数据点数
+    pub accepted: usize,
+}
+
+/// 推送令牌查询参数（用于无法设置请求头的调用方）
+#[derive(Debug, Deserialize)]
+pub struct IngestTokenQuery {
+    /// 推送令牌
+    pub token: Option<String>,
+}
+
+/// 创建推送路由
+///
+/// # 返回
+/// * `Router<AppState>` - 推送接口路由
+pub fn create_ingest_routes() -> Router<AppState> {
+    Router::new()
+        // 推送指标 / 查看推送数据源的最新值
+        .route("/ingest/:source", post(ingest).get(latest_values))
+}
+
+/// 把推送的指标转换为数据点
+///
+/// # 参数
+/// * `source` - 数据源
+/// * `metrics` - 推送的指标
+/// * `now` - 接收时间
+///
+/// # 返回
+/// * `Result<Vec<AggregatedMetric>>` - 任一指标无效时返回错误（整批不写入）
+pub fn build_metrics(source: DataSource, metrics: Vec<IngestMetric>, now: DateTime<Utc>) -> Result<Vec<AggregatedMetric>> {
+    if metrics.is_empty() {
+        bail!("至少需要一个指标");
+    }
+    if metrics.len() > MAX_INGEST_METRICS {
+        bail!("单次最多推送 {} 个指标", MAX_INGEST_METRICS);
+    }
+    metrics.into_iter()
+        .enumerate()
+        .map(|(index, metric)| {
+            let value = match &metric.value {
+                serde_json::Value::Number(n) => n.as_f64(),
+                serde_json::Value::String(s) => s.trim().parse::<f64>().ok(),
+                _ => None,
+            }.filter(|v| v.is_finite())
+                .ok_or_else(|| anyhow!("第 {} 个指标 {} 的值不是有效数字", index + 1, metric.name))?;
+            let timestamp = metric.timestamp.unwrap_or(now);
+            if timestamp > now + Duration::minutes(MAX_CLOCK_SKEW_MINUTES) {
+                bail!("第 {} 个指标 {} 的时间戳晚于当前时间", index + 1, metric.name);
+            }
+            let value = match metric.unit.filter(|u| !u.is_empty()) {
+                Some(unit) => MetricValue::scalar(value, unit),
+                None => MetricValue::from(value),
+            };
+            let mut builder = MetricBuilder::new(source, metric.name.trim()).value(value).timestamp(timestamp);
+            for (key, value) in metric.labels {
+                builder = builder.label(key, value);
+            }
+            builder.build().map_err(|e| anyhow!("第 {} 个指标无效: {}", index + 1, e))
+        })
+        .collect()
+}
+
+/// 接收推送的指标
+///
+/// 写入指标存储，并把每个指标的最新值写入缓存数据集 `ingest:<数据源>`
+async fn ingest(
+    State(sources): State<Arc<IngestSources>>,
+    State(metrics): State<Arc<MetricStore>>,
+    State(cache): State<Arc<DataCache>>,
+    Path(source): Path<String>,
+    Query(query): Query<IngestTokenQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<ApiResponse<IngestAccepted>>, StatusCode> {
+    let provided = headers.get(INGEST_TOKEN_HEADER)
+        .or_else(|| headers.get(AUTHORIZATION))
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.strip_prefix("Bearer ").unwrap_or(v))
+        .or(query.token.as_deref());
+    let source = sources.authorize(&source.to_lowercase(), provided, &headers, &body)?;
+    if sources.read_only {
+        return Ok(Json(ApiResponse::error("只读API实例不接受推送，请推送到采集实例")));
+    }
+
+    let points = match IngestPayload::parse(&body).and_then(|m| build_metrics(source, m, Utc::now())) {
+        Ok(points) => points,
+        Err(e) => return Ok(Json(ApiResponse::error(e.to_string()))),
+    };
+    if let Err(e) = metrics.save_metrics(&points).await {
+        return Ok(Json(ApiResponse::error(format!("保存推送数据失败: {}", e))));
+    }
+
+    let key = ingest_key(source.name);
+    let mut latest: BTreeMap<String, IngestedValue> = cache.get_dataset(&key).await.unwrap_or_default();
+    for point in &points {
+        let newer = latest.get(&point.metric_name).is_none_or(|v| v.timestamp <= point.timestamp);
+        if let (true, Some(value)) = (newer, point.value.as_f64()) {
+            latest.insert(point.metric_name.clone(), IngestedValue {
+                value,
+                unit: point.value.unit().map(str::to_string),
+                labels: point.labels.clone(),
+                timestamp: point.timestamp,
+            });
+        }
+    }
+    if let Err(e) = cache.set_dataset(&key, &latest).await {
+        return Ok(Json(ApiResponse::error(format!("更新缓存失败: {}", e))));
+    }
+    debug!("📥 推送数据源 {} 写入 {} 个数据点", source.name, points.len());
+
+    Ok(Json(ApiResponse::success(IngestAccepted {
+        source: source.name.to_string(),
+        accepted: points.len(),
+    })))
+}
+
+/// 推送数据源各指标的最新值
+async fn latest_values(
+    State(sources): State<Arc<IngestSources>>,
+    State(cache): State<Arc<DataCache>>,
+    Path(source): Path<String>,
+) -> Result<Json<ApiResponse<BTreeMap<String, IngestedValue>>>, StatusCode> {
+    let source = source.to_lowercase();
+    if !sources.sources.contains_key(&source) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let latest = cache.get_dataset(&ingest_key(&source)).await.unwrap_or_default();
+    Ok(Json(ApiResponse::success(latest)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::SecretString;
+
+    #[test]
+    fn test_parse_and_authorize() {
+        let sources = IngestSources::from_config(&[IngestSourceConfig {
+            name: "TradingView".to_string(),
+            token: Some(SecretString::new("s3cret")),
+            signature: None,
+        }], false).unwrap();
+        let headers = HeaderMap::new();
+        let source = sources.authorize("tradingview", Some("s3cret"), &headers, b"").unwrap();
+        assert_eq!(source.name, "tradingview");
+        assert_eq!(sources.authorize("tradingview", Some("wrong"), &headers, b""), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(sources.authorize("other", Some("s3cret"), &headers, b"").unwrap_err(), StatusCode::NOT_FOUND);
+
+        let single = IngestPayload::parse(br#"{"name": "tv_close", "value": "97250.5", "labels": {"ticker": "BTCUSD"}}"#).unwrap();
+        let points = build_metrics(source, single, Utc::now()).unwrap();
+        assert_eq!(points[0].value.as_f64(), Some(97250.5));
+        assert_eq!(points[0].source, "tradingview");
+        assert_eq!(points[0].labels["ticker"], "btcusd");
+
+        let batch = IngestPayload::parse(br#"{"metrics": [{"name": "a", "value": 1}, {"name": "b", "value": "n/a"}]}"#).unwrap();
+        assert_eq!(batch.len(), 2);
+        assert!(build_metrics(source, batch, Utc::now()).is_err());
+        assert!(IngestPayload::parse(b"close=1").is_err());
+    }
+}
@@ -0,0 +1,404 @@
+use anyhow::{Context, Result};
+use axum::Router;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::{conn::auto::Builder, graceful::GracefulShutdown},
+    service::TowerToHyperService,
+};
+use std::future::Future;
+use std::io;
+use std::time::Duration;
+use serde::Serialize;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+#[cfg(unix)]
+use tokio::net::UnixListener;
+use tracing::{debug, error, info, warn};
+
+use crate::config::ServerConfig;
+
+/// systemd 传入的第一个文件描述符
+#[cfg(unix)]
+const SD_LISTEN_FDS_START: std::os::unix::io::RawFd = 3;
+
+/// 已接受的连接
+pub trait Connection: AsyncRead + AsyncWrite + Unpin + Send + 'static {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send + 'static> Connection for T {}
+
+/// Web服务器监听器
+pub enum Listener {
+    /// TCP端口
+    Tcp(TcpListener),
+    /// Unix套接字
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl Listener {
+    /// 按配置创建监听器
+    ///
+    /// 优先使用 systemd 套接字激活传入的套接字（`LISTEN_FDS`），其次为 `server.unix_socket`，
+    /// 否则监听 `host:port`
+    ///
+    /// # 参数
+    /// * `config` - Web服务器配置
+    pub async fn bind(config: &ServerConfig) -> Result<Self> {
+        #[cfg(unix)]
+        if let Some(listener) = Self::from_systemd()? {
+            return Ok(listener);
+        }
+        Self::bind_target(&BindTarget::from_config(config), config.unix_socket_mode).await
+    }
+
+    /// 监听指定地址
+    ///
+    /// # 参数
+    /// * `target` - 监听地址
+    /// * `unix_socket_mode` - Unix套接字文件的权限
+    pub async fn bind_target(target: &BindTarget, unix_socket_mode: u32) -> Result<Self> {
+        match target.unix_socket.as_deref() {
+            #[cfg(unix)]
+            Some(path) => Self::bind_unix(path, unix_socket_mode),
+            #[cfg(not(unix))]
+            Some(_) => {
+                let _ = unix_socket_mode;
+                anyhow::bail!("当前平台不支持Unix套接字")
+            }
+            None => {
+                let addr = format!("{}:{}", target.host, target.port);
+                let listener = TcpListener::bind(&addr).await
+                    .with_context(|| format!("监听 {} 失败", addr))?;
+                Ok(Self::Tcp(listener))
+            }
+        }
+    }
+
+    /// 取得 systemd 套接字激活传入的套接字
+    ///
+    /// 只在 `LISTEN_PID` 为当前进程时使用；传入多个套接字时只使用第一个
+    #[cfg(unix)]
+    fn from_systemd() -> Result<Option<Self>> {
+        use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+        let env_number = |name| std::env::var(name).ok().and_then(|v| v.parse::<u32>().ok());
+        let fds = env_number("LISTEN_FDS").unwrap_or(0);
+        if env_number("LISTEN_PID") != Some(std::process::id()) || fds == 0 {
+            return Ok(None);
+        }
+        if fds > 1 {
+            warn!("⚠️ systemd 传入了 {} 个套接字，只使用第一个", fds);
+        }
+
+        // SAFETY: LISTEN_PID 为当前进程时，systemd 保证从3开始的描述符是已打开的监听套接字，且只在此处取得所有权
+        let unix = unsafe { std::os::unix::net::UnixListener::from_raw_fd(SD_LISTEN_FDS_START) };
+        if unix.local_addr().is_ok() {
+            unix.set_nonblocking(true)?;
+            info!("🧦 使用 systemd 传入的Unix套接字");
+            return Ok(Some(Self::Unix(UnixListener::from_std(unix)?)));
+        }
+
+        // 不是Unix套接字时按TCP套接字使用
+        // SAFETY: 描述符的所有权刚从 `unix` 中取回
+        let tcp = unsafe { std::net::TcpListener::from_raw_fd(unix.into_raw_fd()) };
+        tcp.local_addr().context("systemd 传入的描述符不是TCP或Unix监听套接字")?;
+        tcp.set_nonblocking(true)?;
+        info!("🧦 使用 systemd 传入的TCP套接字");
+        Ok(Some(Self::Tcp(TcpListener::from_std(tcp)?)))
+    }
+
+    /// 监听Unix套接字
+    ///
+    /// 删除上次运行遗留的套接字文件，仍有进程在该套接字上监听时拒绝启动
+    #[cfg(unix)]
+    fn bind_unix(path: &str, mode: u32) -> Result<Self> {
+        use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if !metadata.file_type().is_socket() {
+                anyhow::bail!("{} 已存在且不是套接字", path);
+            }
+            if std::os::unix::net::UnixStream::connect(path).is_ok() {
+                anyhow::bail!("Unix套接字 {} 正在被其他进程使用", path);
+            }
+            std::fs::remove_file(path).with_context(|| format!("删除遗留的套接字 {} 失败", path))?;
+        }
+
+        let listener = UnixListener::bind(path).with_context(|| format!("监听Unix套接字 {} 失败", path))?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+            .with_context(|| format!("设置套接字 {} 的权限失败", path))?;
+        Ok(Self::Unix(listener))
+    }
+
+    /// 监听地址（用于日志）
+    ///
+    /// # 参数
+    /// * `scheme` - TCP监听时显示的协议（`http` 或 `https`）
+    pub fn describe(&self, scheme: &str) -> String {
+        match self {
+            Self::Tcp(listener) => match listener.local_addr() {
+                Ok(addr) => format!("{}://{}", scheme, addr),
+                Err(_) => format!("{}://<未知地址>", scheme),
+            },
+            #[cfg(unix)]
+            Self::Unix(listener) => {
+                let path = listener.local_addr().ok()
+                    .and_then(|addr| addr.as_pathname().map(|p| p.display().to_string()));
+                format!("unix:{}", path.unwrap_or_else(|| "<匿名>".to_string()))
+            }
+        }
+    }
+
+    /// 接受一个连接，返回连接和对端描述
+    async fn accept(&self) -> io::Result<(Box<dyn Connection>, String)> {
+        match self {
+            Self::Tcp(listener) => {
+                let (stream, peer) = listener.accept().await?;
+                Ok((Box::new(stream), peer.to_string()))
+            }
+            #[cfg(unix)]
+            Self::Unix(listener) => {
+                let (stream, _) = listener.accept().await?;
+                Ok((Box::new(stream), "unix".to_string()))
+            }
+        }
+    }
+}
+
+/// 配置的监听地址
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BindTarget {
+    /// 监听主机
+    pub host: String,
+    /// 监听端口
+    pub port: u16,
+    /// Unix套接字路径（设置后不监听 `host:port`）
+    pub unix_socket: Option<String>,
+}
+
+impl BindTarget {
+    /// 取得配置中的监听地址
+    pub fn from_config(config: &ServerConfig) -> Self {
+        Self {
+            host: config.host.clone(),
+            port: config.port,
+            unix_socket: config.unix_socket.clone().filter(|path| !path.trim().is_empty()),
+        }
+    }
+}
+
+/// 监听器切换
+///
+/// 在运行中更换监听地址：先绑定新地址（失败时保持原监听器不变），再交给服务循环替换，
+/// 原监听器上已建立的连接处理完当前请求后关闭，调度器和采集任务不受影响
+pub struct Rebinder {
+    /// TCP监听时显示的协议
+    scheme: &'static str,
+    /// Unix套接字文件的权限
+    unix_socket_mode: u32,
+    /// 当前配置的监听地址（同时保证切换串行执行）
+    current: tokio::sync::Mutex<BindTarget>,
+    /// 新监听器发往服务循环
+    sender: mpsc::Sender<Listener>,
+}
+
+impl Rebinder {
+    /// 创建监听器切换，返回值中的接收端交给 [`serve`] 或 [`serve_with`]
+    ///
+    /// # 参数
+    /// * `config` - 启动时的Web服务器配置
+    /// * `scheme` - TCP监听时显示的协议（`http` 或 `https`）
+    pub fn new(config: &ServerConfig, scheme: &'static str) -> (Self, mpsc::Receiver<Listener>) {
+        let (sender, receiver) = mpsc::channel(1);
+        let rebinder = Self {
+            scheme,
+            unix_socket_mode: config.unix_socket_mode,
+            current: tokio::sync::Mutex::new(BindTarget::from_config(config)),
+            sender,
+        };
+        (rebinder, receiver)
+    }
+
+    /// 当前配置的监听地址
+    pub async fn current(&self) -> BindTarget {
+        self.current.lock().await.clone()
+    }
+
+    /// 切换到新的监听地址
+    ///
+    /// # 参数
+    /// * `target` - 新的监听地址
+    ///
+    /// # 返回
+    /// * `Result<String>` - 新监听器的地址描述；地址未变化或绑定失败时返回错误
+    pub async fn rebind(&self, target: BindTarget) -> Result<String> {
+        let mut current = self.current.lock().await;
+        if *current == target {
+            anyhow::bail!("监听地址未变化");
+        }
+        let listener = Listener::bind_target(&target, self.unix_socket_mode).await?;
+        let address = listener.describe(self.scheme);
+        self.sender.send(listener).await.map_err(|_| anyhow::anyhow!("Web服务器已停止"))?;
+        *current = target;
+        Ok(address)
+    }
+}
+
+/// 提供HTTP服务
+///
+/// # 参数
+/// * `listener` - 监听器
+/// * `rebinds` - 替换监听器（见 [`Rebinder`]）
+/// * `app` - 应用路由
+/// * `shutdown` - 关闭信号
+pub async fn serve(
+    listener: Listener,
+    rebinds: mpsc::Receiver<Listener>,
+    app: Router,
+    shutdown: impl Future<Output = ()>,
+) -> Result<()> {
+    serve_with(listener, rebinds, app, shutdown, |connection| async move { Ok(connection) }).await
+}
+
+/// 提供服务，每个连接先经过 `handshake`（如TLS握手）再交给路由处理
+///
+/// 握手失败只影响该连接，支持WebSocket升级；`shutdown` 完成后停止接受新连接，
+/// 并等待已建立的连接处理完当前请求。从 `rebinds` 收到新监听器时改为在新监听器上接受连接，
+/// 原监听器上的连接在后台处理完当前请求后关闭
+///
+/// # 参数
+/// * `listener` - 监听器
+/// * `rebinds` - 替换监听器（见 [`Rebinder`]）
+/// * `app` - 应用路由
+/// * `shutdown` - 关闭信号
+/// * `handshake` - 连接建立后的握手
+pub async fn serve_with<H, F, C>(
+    mut listener: Listener,
+    mut rebinds: mpsc::Receiver<Listener>,
+    app: Router,
+    shutdown: impl Future<Output = ()>,
+    handshake: H,
+) -> Result<()>
+where
+    H: Fn(Box<dyn Connection>) -> F,
+    F: Future<Output = io::Result<C>> + Send + 'static,
+    C: Connection,
+{
+    let mut graceful = GracefulShutdown::new();
+    tokio::pin!(shutdown);
+
+    loop {
+        let (connection, peer) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    // 文件描述符耗尽等错误时稍后重试，避免空转
+                    error!("❌ 接受连接失败: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            },
+            Some(next) = rebinds.recv() => {
+                // 原监听器在此关闭，之后的新连接只会到达新监听器
+                drop(std::mem::replace(&mut listener, next));
+                let draining = std::mem::replace(&mut graceful, GracefulShutdown::new());
+                info!("🔁 已切换监听器，原监听器上的 {} 个连接处理完当前请求后关闭", draining.count());
+                tokio::spawn(async move {
+                    draining.shutdown().await;
+                    debug!("原监听器上的连接已全部结束");
+                });
+                continue;
+            }
+            _ = &mut shutdown => break,
+        };
+
+        let handshake = handshake(connection);
+        let service = TowerToHyperService::new(app.clone());
+        let watcher = graceful.watcher();
+        tokio::spawn(async move {
+            let connection = match handshake.await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    debug!("连接 {} 握手失败: {}", peer, e);
+                    return;
+                }
+            };
+            let builder = Builder::new(TokioExecutor::new());
+            let connection = builder.serve_connection_with_upgrades(TokioIo::new(connection), service).into_owned();
+            if let Err(e) = watcher.watch(connection).await {
+                debug!("连接 {} 异常结束: {}", peer, e);
+            }
+        });
+    }
+
+    info!("🛑 停止接受新连接，等待 {} 个连接结束", graceful.count());
+    graceful.shutdown().await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// 发送一个HTTP请求并返回响应
+    async fn get_root(addr: &str) -> io::Result<String> {
+        let mut stream = tokio::net::TcpStream::connect(addr).await?;
+        stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok(response)
+    }
+
+    #[tokio::test]
+    async fn test_rebind_switches_listener() {
+        let config = ServerConfig {
+            host: "127.0.0.1".to_string(),
+            port: 0,
+            ..crate::config::AppConfig::default().server
+        };
+        let listener = Listener::bind(&config).await.unwrap();
+        let old_addr = listener.describe("http").trim_start_matches("http://").to_string();
+        let (rebinder, rebinds) = Rebinder::new(&config, "http");
+        let app = Router::new().route("/", get(|| async { "ok" }));
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve(listener, rebinds, app, async { stopped.await.ok(); }));
+        assert!(get_root(&old_addr).await.unwrap().ends_with("ok"));
+
+        // 地址未变化时拒绝切换
+        assert!(rebinder.rebind(BindTarget::from_config(&config)).await.is_err());
+
+        // 绑定失败时保持原监听器
+        let target = BindTarget { host: "127.0.0.1".to_string(), port: 0, unix_socket: Some("/nonexistent/everscan.sock".to_string()) };
+        assert!(rebinder.rebind(target).await.is_err());
+        assert!(get_root(&old_addr).await.unwrap().ends_with("ok"));
+
+        let new_addr = rebinder.rebind(BindTarget { host: "0.0.0.0".to_string(), port: 0, unix_socket: None }).await.unwrap();
+        let new_addr = new_addr.trim_start_matches("http://").replace("0.0.0.0", "127.0.0.1");
+        assert!(get_root(&new_addr).await.unwrap().ends_with("ok"));
+        assert!(get_root(&old_addr).await.is_err());
+
+        stop.send(()).unwrap();
+        server.await.unwrap().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_bind_unix_replaces_stale_socket() {
+        let path = std::env::temp_dir().join(format!("everscan-{}.sock", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+
+        // 遗留的套接字文件（监听器已关闭）会被删除后重新监听
+        drop(std::os::unix::net::UnixListener::bind(path).unwrap());
+        let listener = Listener::bind_unix(path, 0o600).unwrap();
+        assert_eq!(listener.describe("http"), format!("unix:{}", path));
+
+        // 仍在监听时拒绝启动
+        assert!(Listener::bind_unix(path, 0o600).is_err());
+
+        drop(listener);
+        std::fs::remove_file(path).unwrap();
+    }
+}
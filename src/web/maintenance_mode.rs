@@ -0,0 +1,163 @@
+use axum::{
+    extract::{Request, State},
+    http::{header::RETRY_AFTER, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use super::api::ApiResponse;
+
+/// 未指定时建议客户端重试的间隔（秒）
+pub const DEFAULT_RETRY_AFTER_SECONDS: u64 = 300;
+
+/// 维护模式下仍然提供的接口（相对 `/api` 的路径前缀）
+///
+/// 健康和就绪检查供负载均衡探测，版本信息供确认部署的构建，登录和管理接口供管理员结束维护
+const EXEMPT_PREFIXES: &[&str] = &["/health", "/ready", "/version", "/auth/login", "/admin/"];
+
+/// 维护模式状态
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceStatus {
+    /// 是否处于维护模式
+    pub enabled: bool,
+    /// 返回给客户端的说明
+    pub message: Option<String>,
+    /// 建议客户端重试的间隔（秒）
+    pub retry_after_seconds: u64,
+    /// 进入维护模式的时间
+    pub since: Option<DateTime<Utc>>,
+    /// 开启维护模式的管理员
+    pub enabled_by: Option<String>,
+}
+
+impl Default for MaintenanceStatus {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            message: None,
+            retry_after_seconds: DEFAULT_RETRY_AFTER_SECONDS,
+            since: None,
+            enabled_by: None,
+        }
+    }
+}
+
+/// 维护模式开关
+///
+/// 开启后数据接口返回503（附 `Retry-After`），健康检查、登录和管理接口照常提供；
+/// 只影响API，调度器和采集任务继续运行。状态只保存在内存中，重启后恢复为关闭
+#[derive(Debug, Default)]
+pub struct MaintenanceMode {
+    status: RwLock<MaintenanceStatus>,
+}
+
+impl MaintenanceMode {
+    /// 创建维护模式开关（初始为关闭）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 当前状态
+    pub async fn status(&self) -> MaintenanceStatus {
+        self.status.read().await.clone()
+    }
+
+    /// 是否处于维护模式
+    pub async fn is_enabled(&self) -> bool {
+        self.status.read().await.enabled
+    }
+
+    /// 开启维护模式
+    ///
+    /// # 参数
+    /// * `message` - 返回给客户端的说明
+    /// * `retry_after_seconds` - 建议客户端重试的间隔（秒）
+    /// * `operator` - 操作的管理员
+    pub async fn enable(&self, message: Option<String>, retry_after_seconds: u64, operator: &str) -> MaintenanceStatus {
+        let mut status = self.status.write().await;
+        *status = MaintenanceStatus {
+            enabled: true,
+            message,
+            retry_after_seconds: retry_after_seconds.max(1),
+            // 已在维护中时保留开始时间
+            since: status.since.or_else(|| Some(Utc::now())),
+            enabled_by: Some(operator.to_string()),
+        };
+        status.clone()
+    }
+
+    /// 关闭维护模式
+    pub async fn disable(&self) -> MaintenanceStatus {
+        let mut status = self.status.write().await;
+        *status = MaintenanceStatus::default();
+        status.clone()
+    }
+}
+
+/// 维护期间是否仍然提供该接口
+fn is_exempt(path: &str) -> bool {
+    EXEMPT_PREFIXES.iter().any(|prefix| path.starts_with(prefix))
+}
+
+/// 维护模式中间件
+///
+/// 维护期间除健康检查、就绪检查、版本信息、登录和管理接口外的请求返回503
+pub async fn maintenance_guard(
+    State(maintenance): State<Arc<MaintenanceMode>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if is_exempt(request.uri().path()) {
+        return next.run(request).await;
+    }
+    let status = maintenance.status.read().await.clone();
+    if !status.enabled {
+        return next.run(request).await;
+    }
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        [(RETRY_AFTER, status.retry_after_seconds.to_string())],
+        Json(ApiResponse::<()>::error(
+            status.message.unwrap_or_else(|| "服务维护中，请稍后重试".to_string())
+        )),
+    ).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exempt_paths() {
+        assert!(is_exempt("/health"));
+        assert!(is_exempt("/version"));
+        assert!(is_exempt("/auth/login"));
+        assert!(is_exempt("/admin/maintenance"));
+        assert!(!is_exempt("/market-data"));
+        assert!(!is_exempt("/auth/register"));
+        assert!(!is_exempt("/administrator"));
+    }
+
+    #[tokio::test]
+    async fn test_enable_and_disable() {
+        let maintenance = MaintenanceMode::new();
+        assert!(!maintenance.is_enabled().await);
+
+        let first = maintenance.enable(Some("迁移中".to_string()), 0, "admin").await;
+        assert!(first.enabled);
+        assert_eq!(first.retry_after_seconds, 1);
+
+        // 再次开启时更新说明，保留开始时间
+        let second = maintenance.enable(None, 600, "ops").await;
+        assert_eq!(second.since, first.since);
+        assert_eq!(second.enabled_by.as_deref(), Some("ops"));
+
+        let status = maintenance.disable().await;
+        assert!(!status.enabled);
+        assert!(status.since.is_none());
+    }
+}
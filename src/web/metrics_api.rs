@@ -0,0 +1,561 @@
+use axum::{
+    Router,
+    body::Body,
+    routing::{get, post},
+    extract::{Path, Query, State},
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::{IntoResponse, Json, Response},
+};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use super::api::{query_limit_rejection, ApiResponse, LimitedResponse};
+use super::AppState;
+#[cfg(feature = "forecast")]
+use crate::analytics::{forecast, ForecastModel, ForecastParams};
+#[cfg(feature = "forecast")]
+use crate::feature_flags::{self, FeatureFlags};
+use crate::analytics::{compare_variants, FormulaComparison, TrialIndex, TrialVariant, LABEL_VARIANT};
+use crate::models::{describe_metric, AggregatedMetric, MetricStats, TimeRange};
+#[cfg(feature = "forecast")]
+use crate::storage::bucket_average;
+use crate::storage::{parse_bucket, MetricQuery, MetricStore};
+use crate::tasks::TaskManager;
+
+/// 单次批量查询最多包含的指标数
+const MAX_SELECTIONS: usize = 20;
+
+/// 预测结果附带的说明
+#[cfg(feature = "forecast")]
+const FORECAST_DISCLAIMER: &str = "基于历史数据的统计外推，不构成投资建议";
+
+/// 预测最多步数
+#[cfg(feature = "forecast")]
+const MAX_FORECAST_HORIZON: usize = 200;
+
+/// 季节长度上限（数据点数）
+#[cfg(feature = "forecast")]
+const MAX_SEASON_LENGTH: usize = 365;
+
+/// 批量查询中的单个指标选择
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetricSelection {
+    /// 数据源（可选）
+    pub source: Option<String>,
+    /// 指标名称
+    pub metric_name: String,
+    /// 时间范围（可选，默认全部保留数据）
+    pub time_range: Option<TimeRange>,
+    /// 时间桶（如 `5m`、`1h`、`1d`，不指定时返回原始数据点）
+    pub bucket: Option<String>,
+    /// 标签过滤（如 `{"chain": "arbitrum"}`，须全部匹配）
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+/// 批量指标查询请求
+#[derive(Debug, Deserialize)]
+pub struct MetricsQueryRequest {
+    /// 指标选择
+    pub selections: Vec<MetricSelection>,
+}
+
+/// 批量指标查询的URL参数
+#[derive(Debug, Default, Deserialize)]
+pub struct MetricsQueryParams {
+    /// 是否包含已作废的数据点
+    #[serde(default)]
+    pub include_invalid: bool,
+}
+
+/// 对齐后的单个指标序列
+#[derive(Debug, Serialize)]
+pub struct AlignedSeries {
+    /// 数据源
+    pub source: Option<String>,
+    /// 指标名称
+    pub metric_name: String,
+    /// 时间桶
+    pub bucket: Option<String>,
+    /// 标签过滤
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// 与 `timestamps` 一一对应的数值，该时间点没有数据时为null
+    pub values: Vec<Option<f64>>,
+}
+
+/// 批量指标查询结果
+#[derive(Debug, Serialize)]
+pub struct MetricsQueryResponse {
+    /// 所有序列共用的时间轴（升序）
+    pub timestamps: Vec<DateTime<Utc>>,
+    /// 各指标序列（顺序与请求一致）
+    pub series: Vec<AlignedSeries>,
+}
+
+/// 指标目录中的一项
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogEntry {
+    /// 指标名称
+    pub metric_name: String,
+    /// 数据源
+    pub source: String,
+    /// 说明（未登记的指标为None）
+    pub description: Option<String>,
+    /// 单位
+    pub unit: Option<String>,
+    /// 产出该指标的任务ID
+    pub task_id: Option<String>,
+    /// 采集间隔（秒），优先取任务配置，否则按已存数据估算
+    pub interval_seconds: Option<u64>,
+    /// 数据点数
+    pub count: usize,
+    /// 最早数据时间
+    pub earliest: DateTime<Utc>,
+    /// 最新数据时间
+    pub latest: DateTime<Utc>,
+}
+
+/// 指标目录
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricCatalog {
+    /// 汇总统计
+    pub stats: MetricStats,
+    /// 各指标（按名称排序）
+    pub metrics: Vec<CatalogEntry>,
+}
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// 每行一个JSON数据点
+    #[default]
+    Ndjson,
+    /// CSV（timestamp,source,metric_name,value,unit,labels）
+    Csv,
+}
+
+/// 导出查询参数
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// 导出格式（默认ndjson）
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// 起始时间（包含）
+    pub since: Option<DateTime<Utc>>,
+    /// 结束时间（包含）
+    pub until: Option<DateTime<Utc>>,
+    /// 按数据源过滤
+    pub source: Option<String>,
+    /// 是否包含已作废的数据点
+    #[serde(default)]
+    pub include_invalid: bool,
+}
+
+/// 公式比较查询参数
+#[derive(Debug, Deserialize)]
+pub struct FormulaComparisonQuery {
+    /// 起始时间（包含）
+    pub since: Option<DateTime<Utc>>,
+    /// 结束时间（包含）
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// 预测查询参数
+#[cfg(feature = "forecast")]
+#[derive(Debug, Deserialize)]
+pub struct ForecastQuery {
+    /// 预测模型：ewma / holt_winters（默认ewma）
+    pub model: Option<ForecastModel>,
+    /// 预测步数（默认12）
+    pub horizon: Option<usize>,
+    /// 时间桶（如 `1h`，不指定时按原始数据点的间隔中位数外推）
+    pub bucket: Option<String>,
+    /// 最多使用最近的数据点数（默认500）
+    pub lookback: Option<usize>,
+    /// 数据源（可选）
+    pub source: Option<String>,
+    /// 水平平滑系数
+    pub alpha: Option<f64>,
+    /// 趋势平滑系数
+    pub beta: Option<f64>,
+    /// 季节平滑系数
+    pub gamma: Option<f64>,
+    /// 季节长度（数据点数）
+    pub season_length: Option<usize>,
+}
+
+/// 带时间的预测点
+#[cfg(feature = "forecast")]
+#[derive(Debug, Clone, Serialize)]
+pub struct ForecastValue {
+    /// 预测时间
+    pub timestamp: DateTime<Utc>,
+    /// 预测值
+    pub value: f64,
+    /// 95%置信区间下界
+    pub lower: f64,
+    /// 95%置信区间上界
+    pub upper: f64,
+}
+
+/// 指标预测结果
+#[cfg(feature = "forecast")]
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricForecast {
+    /// 指标名称
+    pub metric_name: String,
+    /// 预测模型
+    pub model: ForecastModel,
+    /// 单位
+    pub unit: Option<String>,
+    /// 相邻预测点间隔（秒）
+    pub interval_seconds: i64,
+    /// 参与拟合的数据点数
+    pub history_points: usize,
+    /// 说明：统计外推，不构成投资建议
+    pub disclaimer: &'static str,
+    /// 预测点
+    pub points: Vec<ForecastValue>,
+}
+
+/// 创建指标查询路由
+///
+/// # 返回
+/// * `Router<AppState>` - 指标查询路由
+pub fn create_metrics_routes() -> Router<AppState> {
+    let router = Router::new()
+        // 批量查询多个指标并按时间轴对齐
+        .route("/metrics/query", post(query_metrics))
+        // 已存储指标的目录
+        .route("/metrics/catalog", get(metric_catalog))
+        // 流式导出单个指标的完整历史
+        .route("/metrics/:name/export", get(export_metric))
+        // 指数公式现行与候选版本的比较
+        .route("/formulas/:index/comparison", get(compare_formulas));
+    // 短期统计外推（需启用 forecast 特性）
+    #[cfg(feature = "forecast")]
+    let router = router.route("/metrics/:name/forecast", get(forecast_metric));
+    router
+}
+
+/// 比较指数公式的现行和候选版本
+///
+/// 读取公式并行比较任务写入的两个版本，按计算时间配对并统计差异和分类一致率；
+/// 超出查询限制时返回HTTP 400
+async fn compare_formulas(
+    State(metrics): State<Arc<MetricStore>>,
+    Path(index): Path<String>,
+    Query(query): Query<FormulaComparisonQuery>,
+) -> LimitedResponse<FormulaComparison> {
+    let Some(index) = TrialIndex::parse(&index) else {
+        return Ok(Json(ApiResponse::error(format!("未知的指数: {}（可选 altcoin_season、composite_sentiment）", index))));
+    };
+    let variant = |variant: TrialVariant| MetricQuery {
+        since: query.since,
+        until: query.until,
+        labels: BTreeMap::from([(LABEL_VARIANT.to_string(), variant.as_str().to_string())]),
+        ..MetricQuery::default()
+    };
+    let baseline = metrics.query_checked(index.metric_name(), &variant(TrialVariant::Baseline)).await
+        .map_err(query_limit_rejection)?;
+    let candidate = metrics.query_checked(index.metric_name(), &variant(TrialVariant::Candidate)).await
+        .map_err(query_limit_rejection)?;
+    Ok(Json(ApiResponse::success(compare_variants(index, &baseline, &candidate))))
+}
+
+/// 预测指标的短期走势
+///
+/// 对等间隔序列拟合EWMA或Holt-Winters并外推，附带95%置信区间；读取的数据超出查询限制时返回HTTP 400
+#[cfg(feature = "forecast")]
+async fn forecast_metric(
+    State(metrics): State<Arc<MetricStore>>,
+    State(flags): State<Arc<FeatureFlags>>,
+    Path(name): Path<String>,
+    Query(query): Query<ForecastQuery>,
+) -> LimitedResponse<MetricForecast> {
+    if !flags.is_enabled(feature_flags::FORECAST) {
+        return Ok(Json(ApiResponse::error(format!("功能 {} 未开启", feature_flags::FORECAST))));
+    }
+    let horizon = query.horizon.unwrap_or(12);
+    if horizon > MAX_FORECAST_HORIZON {
+        return Ok(Json(ApiResponse::error(format!("预测步数最多为 {}", MAX_FORECAST_HORIZON))));
+    }
+    if let Some(season) = query.season_length.filter(|season| !(2..=MAX_SEASON_LENGTH).contains(season)) {
+        return Ok(Json(ApiResponse::error(format!("季节长度须在2到{}之间: {}", MAX_SEASON_LENGTH, season))));
+    }
+    let bucket = match query.bucket.as_deref().map(|b| (b, parse_bucket(b))) {
+        Some((raw, None)) => return Ok(Json(ApiResponse::error(format!("无效的时间桶: {}", raw)))),
+        Some((_, bucket)) => bucket,
+        None => None,
+    };
+
+    // 只读取拟合用到的最近 lookback 个数据点（或时间桶）
+    let lookback = query.lookback.unwrap_or(500).max(2);
+    let metric_query = MetricQuery {
+        source: query.source.clone(),
+        since: bucket.and_then(|bucket| bucket.checked_mul(i32::try_from(lookback).ok()?))
+            .and_then(|window| Utc::now().checked_sub_signed(window)),
+        limit: bucket.is_none().then_some(lookback),
+        ..MetricQuery::default()
+    };
+    let points = metrics.query_checked(&name, &metric_query).await.map_err(query_limit_rejection)?;
+    let unit = points.last().and_then(|m| m.value.unit()).map(str::to_string);
+    let (series, interval) = match bucket {
+        Some(bucket) => (fill_buckets(&bucket_average(&points, bucket), bucket), bucket),
+        None => {
+            let series: Vec<(DateTime<Utc>, f64)> = points.iter()
+                .filter_map(|m| Some((m.timestamp, m.value.as_f64()?)))
+                .collect();
+            let mut gaps: Vec<i64> = series.windows(2).map(|w| (w[1].0 - w[0].0).num_seconds()).collect();
+            gaps.sort_unstable();
+            let interval = gaps.get(gaps.len() / 2).copied().unwrap_or_default();
+            (series, chrono::Duration::seconds(interval))
+        }
+    };
+    let series = &series[series.len().saturating_sub(lookback)..];
+    let Some(&(last, _)) = series.last() else {
+        return Ok(Json(ApiResponse::error(format!("暂无指标 {} 的数值数据", name))));
+    };
+    if interval <= chrono::Duration::zero() {
+        return Ok(Json(ApiResponse::error("无法确定数据间隔，请指定时间桶")));
+    }
+
+    let defaults = ForecastParams::default();
+    let params = ForecastParams {
+        alpha: query.alpha.unwrap_or(defaults.alpha),
+        beta: query.beta.unwrap_or(defaults.beta),
+        gamma: query.gamma.unwrap_or(defaults.gamma),
+        season_length: query.season_length,
+    };
+    let model = query.model.unwrap_or_default();
+    let values: Vec<f64> = series.iter().map(|(_, v)| *v).collect();
+    match forecast(model, &values, horizon, &params) {
+        Ok(projection) => Ok(Json(ApiResponse::success(MetricForecast {
+            metric_name: name,
+            model,
+            unit,
+            interval_seconds: interval.num_seconds(),
+            history_points: values.len(),
+            disclaimer: FORECAST_DISCLAIMER,
+            points: projection.into_iter()
+                .map(|p| ForecastValue {
+                    timestamp: last + interval * p.step as i32,
+                    value: p.value,
+                    lower: p.lower,
+                    upper: p.upper,
+                })
+                .collect(),
+        }))),
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 将时间桶均值补齐为等间隔序列，空桶沿用前一个值
+#[cfg(feature = "forecast")]
+fn fill_buckets(buckets: &BTreeMap<DateTime<Utc>, f64>, bucket: chrono::Duration) -> Vec<(DateTime<Utc>, f64)> {
+    let mut series = Vec::new();
+    let mut iter = buckets.iter();
+    let Some((&start, &first)) = iter.next() else {
+        return series;
+    };
+    series.push((start, first));
+    for (&time, &value) in iter {
+        let (mut previous_time, previous_value) = *series.last().expect("非空");
+        while previous_time + bucket < time {
+            previous_time += bucket;
+            series.push((previous_time, previous_value));
+        }
+        series.push((time, value));
+    }
+    series
+}
+
+/// 流式导出单个指标的历史数据
+///
+/// 按 `?format=ndjson|csv` 逐页编码并写出，不受API查询限制，
+/// 内存占用与导出行数无关；中途读取归档失败时响应被截断，并记录错误
+async fn export_metric(
+    State(metrics): State<Arc<MetricStore>>,
+    Path(name): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Response {
+    let metric_query = MetricQuery {
+        since: query.since,
+        until: query.until,
+        source: query.source,
+        include_invalid: query.include_invalid,
+        ..MetricQuery::default()
+    };
+    let format = query.format;
+    let metric_name = name.clone();
+    let rows = metrics.export(&name, &metric_query).map(move |page| {
+        let page = page.inspect_err(|e| tracing::error!("❌ 导出指标 {} 失败: {:#}", metric_name, e))?;
+        match format {
+            ExportFormat::Ndjson => encode_ndjson(&page),
+            ExportFormat::Csv => encode_csv(&page),
+        }
+    });
+    let (content_type, extension, header) = match format {
+        ExportFormat::Ndjson => ("application/x-ndjson", "ndjson", None),
+        ExportFormat::Csv => ("text/csv; charset=utf-8", "csv", Some(Bytes::from_static(CSV_HEADER))),
+    };
+    let body = Body::from_stream(futures_util::stream::iter(header.map(anyhow::Ok)).chain(rows));
+    (
+        [
+            (CONTENT_TYPE, content_type.to_string()),
+            (CONTENT_DISPOSITION, format!("attachment; filename=\"{}.{}\"", name, extension)),
+        ],
+        body,
+    ).into_response()
+}
+
+/// CSV导出的表头
+const CSV_HEADER: &[u8] = b"timestamp,source,metric_name,value,unit,labels\n";
+
+/// 把一页数据点编码为NDJSON
+fn encode_ndjson(points: &[AggregatedMetric]) -> anyhow::Result<Bytes> {
+    let mut buffer = Vec::new();
+    for point in points {
+        serde_json::to_writer(&mut buffer, point)?;
+        buffer.push(b'\n');
+    }
+    Ok(Bytes::from(buffer))
+}
+
+/// 把一页数据点编码为CSV行（不含表头）
+///
+/// 非标量值和标签以JSON字符串写入对应列
+fn encode_csv(points: &[AggregatedMetric]) -> anyhow::Result<Bytes> {
+    let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(Vec::new());
+    for point in points {
+        let value = match point.value.as_f64() {
+            Some(value) => value.to_string(),
+            None => serde_json::to_string(&point.value)?,
+        };
+        let labels = if point.labels.is_empty() { String::new() } else { serde_json::to_string(&point.labels)? };
+        writer.write_record([
+            point.timestamp.to_rfc3339().as_str(),
+            &point.source,
+            &point.metric_name,
+            &value,
+            point.value.unit().unwrap_or_default(),
+            &labels,
+        ])?;
+    }
+    Ok(Bytes::from(writer.into_inner().map_err(|e| anyhow::anyhow!("写入CSV失败: {}", e))?))
+}
+
+/// 获取指标目录
+///
+/// 列出存储中的每个指标及其数据源、说明、单位、采集间隔和数据时间范围
+async fn metric_catalog(
+    State(metrics): State<Arc<MetricStore>>,
+    State(tasks): State<TaskManager>,
+) -> Json<ApiResponse<MetricCatalog>> {
+    let intervals: BTreeMap<String, u64> = tasks.get_task_health().await
+        .into_iter()
+        .map(|health| (health.task_id, health.interval_seconds))
+        .collect();
+
+    let entries = metrics.summaries().await
+        .into_iter()
+        .map(|summary| {
+            let definition = describe_metric(&summary.metric_name);
+            let task_id = definition.map(|d| d.task_id.to_string());
+            let interval_seconds = task_id.as_ref()
+                .and_then(|id| intervals.get(id).copied())
+                .or_else(|| summary.median_interval_seconds.and_then(|s| u64::try_from(s).ok()));
+            CatalogEntry {
+                description: definition.map(|d| d.description.to_string()),
+                task_id,
+                interval_seconds,
+                metric_name: summary.metric_name,
+                source: summary.source,
+                unit: summary.unit,
+                count: summary.count,
+                earliest: summary.earliest,
+                latest: summary.latest,
+            }
+        })
+        .collect();
+
+    Json(ApiResponse::success(MetricCatalog {
+        stats: metrics.stats().await,
+        metrics: entries,
+    }))
+}
+
+/// 批量查询多个指标
+///
+/// 所有序列按时间戳并集对齐到同一时间轴，便于多面板看板一次请求取回全部数据
+async fn query_metrics(
+    State(metrics): State<Arc<MetricStore>>,
+    Query(params): Query<MetricsQueryParams>,
+    Json(request): Json<MetricsQueryRequest>,
+) -> LimitedResponse<MetricsQueryResponse> {
+    if request.selections.is_empty() {
+        return Ok(Json(ApiResponse::error("至少需要选择一个指标")));
+    }
+    if request.selections.len() > MAX_SELECTIONS {
+        return Ok(Json(ApiResponse::error(format!("单次最多查询 {} 个指标", MAX_SELECTIONS))));
+    }
+
+    let mut collected: Vec<(MetricSelection, BTreeMap<DateTime<Utc>, f64>)> = Vec::new();
+    for selection in request.selections {
+        if let Some(range) = &selection.time_range {
+            if range.start > range.end {
+                return Ok(Json(ApiResponse::error(format!("指标 {} 的起始时间晚于结束时间", selection.metric_name))));
+            }
+        }
+        let bucket = match selection.bucket.as_deref().map(|b| (b, parse_bucket(b))) {
+            Some((raw, None)) => return Ok(Json(ApiResponse::error(format!("无效的时间桶: {}", raw)))),
+            Some((_, bucket)) => bucket,
+            None => None,
+        };
+
+        let query = MetricQuery {
+            since: selection.time_range.as_ref().map(|r| r.start),
+            until: selection.time_range.as_ref().map(|r| r.end),
+            source: selection.source.clone(),
+            limit: None,
+            labels: selection.labels.iter()
+                .map(|(key, value)| (key.to_lowercase(), value.to_lowercase()))
+                .collect(),
+            include_invalid: params.include_invalid,
+        };
+        let values = match bucket {
+            Some(bucket) => (*metrics.query_bucketed_checked(&selection.metric_name, &query, bucket).await
+                .map_err(query_limit_rejection)?).clone(),
+            None => metrics.query_checked(&selection.metric_name, &query).await
+                .map_err(query_limit_rejection)?
+                .iter()
+                .filter_map(|m| Some((m.timestamp, m.value.as_f64()?)))
+                .collect(),
+        };
+        collected.push((selection, values));
+    }
+
+    let timestamps: BTreeSet<DateTime<Utc>> = collected.iter()
+        .flat_map(|(_, values)| values.keys().copied())
+        .collect();
+    let series = collected.into_iter()
+        .map(|(selection, values)| AlignedSeries {
+            values: timestamps.iter().map(|t| values.get(t).copied()).collect(),
+            source: selection.source,
+            metric_name: selection.metric_name,
+            bucket: selection.bucket,
+            labels: selection.labels,
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(MetricsQueryResponse {
+        timestamps: timestamps.into_iter().collect(),
+        series,
+    })))
+}
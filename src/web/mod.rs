@@ -1,28 +1,350 @@
+pub mod admin_api;
+pub mod alert_api;
 pub mod api;
+pub mod assets;
+pub mod auth;
 pub mod cache;
+pub mod exchange_api;
+pub mod feeds;
+pub mod fields;
+pub mod formatting;
+pub mod ingest_api;
+pub mod listener;
+pub mod maintenance_mode;
+pub mod metrics_api;
+pub mod pages;
+pub mod portfolio_api;
+pub mod prometheus;
+pub mod read_only;
+pub mod refresh_api;
+pub mod signature;
+pub mod simulate_api;
+pub mod tenant_api;
+#[cfg(feature = "tls")]
+pub mod tls;
+pub mod user_api;
+pub mod versioning;
+pub mod watch_api;
 pub mod websocket;
 
 use axum::{
     Router,
     routing::get,
-    http::StatusCode,
-    response::{Html, IntoResponse},
-    extract::Path,
+    extract::FromRef,
 };
 use tower_http::{
-    services::ServeDir,
     cors::CorsLayer,
     trace::TraceLayer,
 };
 use std::sync::Arc;
 
+use crate::alerts::{AlertManager, AlertRuleStore, AnomalyLog, WatchStore};
+use crate::audit::AuditLog;
+use crate::calendar::EconomicCalendar;
+use crate::config::SentimentConfig;
+use crate::feature_flags::FeatureFlags;
+use crate::history::{AltcoinSeasonHistory, ChangeHistory, EtfFlowHistory, FearGreedHistory, LiquidationHistory, RankHistory, TrendsHistory};
+use crate::portfolio::{ExchangeAccountStore, PortfolioStore};
+use crate::startup::Readiness;
+use crate::storage::MetricStore;
+use crate::tasks::{DerivedMetricEngine, JobQueue, TaskManager};
+use crate::tenants::{CoinUniverse, TenantRegistry};
+use crate::users::{AuthService, UserStore};
 use crate::config::AppConfig;
 use self::{
-    api::create_api_routes,
+    versioning::create_versioned_api_routes,
+    assets::create_asset_routes,
+    feeds::create_feed_routes,
+    pages::create_page_routes,
+    prometheus::create_prometheus_routes,
     cache::DataCache,
-    websocket::websocket_handler,
+    ingest_api::IngestSources,
+    refresh_api::RefreshLimiter,
+    listener::Rebinder,
+    maintenance_mode::MaintenanceMode,
+    read_only::ReadOnlyMode,
+    websocket::{websocket_handler, WebSocketGuard, WebSocketHub},
 };
 
+/// Web应用共享状态
+/// 
+/// 处理器通过 `State<Arc<...>>` 按需提取其中的组件
+#[derive(Clone)]
+pub struct AppState {
+    /// 数据缓存
+    pub cache: Arc<DataCache>,
+    /// 告警管理器
+    pub alert_manager: Arc<AlertManager>,
+    /// 告警规则存储
+    pub alert_rules: Arc<AlertRuleStore>,
+    /// 价格目标监控存储
+    pub watches: Arc<WatchStore>,
+    /// 用户存储
+    pub users: Arc<UserStore>,
+    /// 会话令牌服务
+    pub auth: Arc<AuthService>,
+    /// 投资组合存储
+    pub portfolio: Arc<PortfolioStore>,
+    /// 交易所账户存储
+    pub exchanges: Arc<ExchangeAccountStore>,
+    /// 任务管理器
+    pub tasks: TaskManager,
+    /// 任务队列
+    pub jobs: JobQueue,
+    /// 审计日志
+    pub audit: Arc<AuditLog>,
+    /// 指标异常事件
+    pub anomalies: Arc<AnomalyLog>,
+    /// 山寨币季节指数历史
+    pub altcoin_season: Arc<AltcoinSeasonHistory>,
+    /// 贪婪恐惧指数历史
+    pub fear_greed: Arc<FearGreedHistory>,
+    /// 现货ETF资金流向历史
+    pub etf_flows: Arc<EtfFlowHistory>,
+    /// 合约爆仓历史
+    pub liquidations: Arc<LiquidationHistory>,
+    /// 数据集变化历史
+    pub changes: Arc<ChangeHistory>,
+    /// 币种市值排名历史
+    pub ranks: Arc<RankHistory>,
+    /// 经济日历
+    pub calendar: Arc<EconomicCalendar>,
+    /// 综合情绪权重
+    pub sentiment: Arc<SentimentConfig>,
+    /// 派生指标引擎（情景模拟使用）
+    pub derived_metrics: Arc<DerivedMetricEngine>,
+    /// 搜索热度历史
+    pub trends: Arc<TrendsHistory>,
+    /// 指标时间序列存储
+    pub metrics: Arc<MetricStore>,
+    /// 按需刷新限流器
+    pub refresh: Arc<RefreshLimiter>,
+    /// 推送数据源
+    pub ingest: Arc<IngestSources>,
+    /// WebSocket访问控制
+    pub websocket: Arc<WebSocketGuard>,
+    /// WebSocket发送队列与推送统计
+    pub websocket_hub: Arc<WebSocketHub>,
+    /// 监听器切换
+    pub rebinder: Arc<Rebinder>,
+    /// 维护模式开关
+    pub maintenance: Arc<MaintenanceMode>,
+    /// 只读API实例标记
+    pub read_only: ReadOnlyMode,
+    /// 启动依赖的就绪状态
+    pub readiness: Arc<Readiness>,
+    /// 租户注册表
+    pub tenants: Arc<TenantRegistry>,
+    /// 采集币种范围
+    pub coins: CoinUniverse,
+    /// 功能开关
+    pub feature_flags: Arc<FeatureFlags>,
+}
+
+impl FromRef<AppState> for Arc<DataCache> {
+    fn from_ref(state: &AppState) -> Self {
+        state.cache.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<AlertManager> {
+    fn from_ref(state: &AppState) -> Self {
+        state.alert_manager.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<AlertRuleStore> {
+    fn from_ref(state: &AppState) -> Self {
+        state.alert_rules.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<WatchStore> {
+    fn from_ref(state: &AppState) -> Self {
+        state.watches.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<UserStore> {
+    fn from_ref(state: &AppState) -> Self {
+        state.users.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<AuthService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.auth.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<PortfolioStore> {
+    fn from_ref(state: &AppState) -> Self {
+        state.portfolio.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ExchangeAccountStore> {
+    fn from_ref(state: &AppState) -> Self {
+        state.exchanges.clone()
+    }
+}
+
+impl FromRef<AppState> for TaskManager {
+    fn from_ref(state: &AppState) -> Self {
+        state.tasks.clone()
+    }
+}
+
+impl FromRef<AppState> for JobQueue {
+    fn from_ref(state: &AppState) -> Self {
+        state.jobs.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<AuditLog> {
+    fn from_ref(state: &AppState) -> Self {
+        state.audit.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<AnomalyLog> {
+    fn from_ref(state: &AppState) -> Self {
+        state.anomalies.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<AltcoinSeasonHistory> {
+    fn from_ref(state: &AppState) -> Self {
+        state.altcoin_season.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<FearGreedHistory> {
+    fn from_ref(state: &AppState) -> Self {
+        state.fear_greed.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<EtfFlowHistory> {
+    fn from_ref(state: &AppState) -> Self {
+        state.etf_flows.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<LiquidationHistory> {
+    fn from_ref(state: &AppState) -> Self {
+        state.liquidations.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ChangeHistory> {
+    fn from_ref(state: &AppState) -> Self {
+        state.changes.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<RankHistory> {
+    fn from_ref(state: &AppState) -> Self {
+        state.ranks.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<EconomicCalendar> {
+    fn from_ref(state: &AppState) -> Self {
+        state.calendar.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<SentimentConfig> {
+    fn from_ref(state: &AppState) -> Self {
+        state.sentiment.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<DerivedMetricEngine> {
+    fn from_ref(state: &AppState) -> Self {
+        state.derived_metrics.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<TrendsHistory> {
+    fn from_ref(state: &AppState) -> Self {
+        state.trends.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<MetricStore> {
+    fn from_ref(state: &AppState) -> Self {
+        state.metrics.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<RefreshLimiter> {
+    fn from_ref(state: &AppState) -> Self {
+        state.refresh.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<IngestSources> {
+    fn from_ref(state: &AppState) -> Self {
+        state.ingest.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<WebSocketGuard> {
+    fn from_ref(state: &AppState) -> Self {
+        state.websocket.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<WebSocketHub> {
+    fn from_ref(state: &AppState) -> Self {
+        state.websocket_hub.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Rebinder> {
+    fn from_ref(state: &AppState) -> Self {
+        state.rebinder.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<MaintenanceMode> {
+    fn from_ref(state: &AppState) -> Self {
+        state.maintenance.clone()
+    }
+}
+
+impl FromRef<AppState> for ReadOnlyMode {
+    fn from_ref(state: &AppState) -> Self {
+        state.read_only
+    }
+}
+
+impl FromRef<AppState> for Arc<Readiness> {
+    fn from_ref(state: &AppState) -> Self {
+        state.readiness.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<TenantRegistry> {
+    fn from_ref(state: &AppState) -> Self {
+        state.tenants.clone()
+    }
+}
+
+impl FromRef<AppState> for CoinUniverse {
+    fn from_ref(state: &AppState) -> Self {
+        state.coins.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<FeatureFlags> {
+    fn from_ref(state: &AppState) -> Self {
+        state.feature_flags.clone()
+    }
+}
+
 /// Web服务器结构
 /// 
 /// 负责提供RESTful API和静态文件服务
@@ -31,8 +353,8 @@ use self::{
 pub struct WebServer {
     /// 应用配置
     config: AppConfig,
-    /// 数据缓存
-    cache: Arc<DataCache>,
+    /// 应用共享状态
+    state: AppState,
 }
 
 impl WebServer {
@@ -40,17 +362,17 @@ impl WebServer {
     /// 
     /// # 参数
     /// * `config` - 应用配置
-    /// * `cache` - 数据缓存
+    /// * `state` - 应用共享状态
     /// 
     /// # 返回
     /// * `Self` - Web服务器实例
     pub fn new(
         config: AppConfig,
-        cache: Arc<DataCache>,
+        state: AppState,
     ) -> Self {
         Self {
             config,
-            cache,
+            state,
         }
     }
     
@@ -64,9 +386,10 @@ impl WebServer {
     pub async fn start(&self, port: u16) -> anyhow::Result<()> {
         let app = self.create_app();
         
-        let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
+        let addr = format!("{}:{}", self.config.server.host, port);
+        let listener = tokio::net::TcpListener::bind(&addr).await?;
         
-        tracing::info!("🌐 Web服务器启动在 http://0.0.0.0:{}", port);
+        tracing::info!("🌐 Web服务器启动在 http://{}", addr);
         
         axum::serve(listener, app).await?;
         
@@ -75,41 +398,26 @@ impl WebServer {
     
     /// 创建应用路由
     fn create_app(&self) -> Router {
-        // 创建API路由
-        let api_routes = create_api_routes(self.cache.clone());
+        // 创建API路由（/api/v1 等带版本的路由，以及指向旧版本的 /api）
+        let api_routes = create_versioned_api_routes(self.state.clone());
         
         Router::new()
-            // 主页
-            .route("/", get(dashboard_page))
             // WebSocket端点
             .route("/ws", get(websocket_handler))
             // API路由
-            .nest("/api", api_routes)
-            // 静态文件服务
-            .nest_service("/static", ServeDir::new("static"))
+            .merge(api_routes)
+            // 服务端渲染页面
+            .merge(create_page_routes())
+            // 日历订阅
+            .merge(create_feed_routes())
+            // Prometheus指标
+            .merge(create_prometheus_routes())
+            // 前端静态资源
+            .merge(create_asset_routes(self.config.server.static_dir.as_deref()))
             // 中间件
             .layer(CorsLayer::permissive())
             .layer(TraceLayer::new_for_http())
-            .with_state(self.cache.clone())
+            .with_state(self.state.clone())
     }
 }
-
-/// 仪表板页面处理器
-async fn dashboard_page() -> impl IntoResponse {
-    // 读取静态HTML文件
-    match tokio::fs::read_to_string("static/dashboard.html").await {
-        Ok(content) => Html(content),
-        Err(_) => {
-            // 如果文件不存在，返回简单的HTML页面
-            Html(include_str!("../../static/dashboard.html").to_string())
-        }
-    }
-}
-
-/// 404处理
-async fn not_found(Path(path): Path<String>) -> impl IntoResponse {
-    (
-        StatusCode::NOT_FOUND,
-        format!("页面未找到: /{}", path)
-    )
-} 
\ No newline at end of file
+ 
\ No newline at end of file
@@ -0,0 +1,400 @@
+use askama::Template;
+use axum::{
+    Router,
+    routing::get,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use tracing::error;
+
+use super::cache::{CachedMarketData, DataCache, RSISignal, LIQUIDITY_KEY};
+use super::formatting::{self, format_count, format_price, format_time, format_usd, FormatQuery};
+use super::AppState;
+use crate::alerts::{Alert, AlertManager, AlertRuleStore, WatchStore};
+use crate::analytics::LiquiditySnapshot;
+use crate::indicators::indicator_metric_name;
+use crate::storage::{MetricQuery, MetricStore};
+
+/// 币种详情页显示的历史数据点数
+const COIN_HISTORY_POINTS: usize = 12;
+
+/// 告警页显示的最近告警数
+const RECENT_ALERTS: usize = 50;
+
+/// 创建服务端渲染页面路由
+///
+/// 页面不依赖JavaScript，便于在终端浏览器或低配设备上快速查看
+///
+/// # 返回
+/// * `Router<AppState>` - 页面路由
+pub fn create_page_routes() -> Router<AppState> {
+    Router::new()
+        // 总览
+        .route("/ui", get(index_page))
+        // 币种详情
+        .route("/ui/coins/:coin_id", get(coin_page))
+        // 告警列表
+        .route("/ui/alerts", get(alerts_page))
+}
+
+/// 渲染模板，失败时返回500
+fn render(template: impl Template) -> Response {
+    match template.render() {
+        Ok(html) => Html(html).into_response(),
+        Err(e) => {
+            error!("❌ 页面渲染失败: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// 参数无效时的400页面（如 `?tz=` 为未知时区）
+fn bad_request(message: String) -> Response {
+    (StatusCode::BAD_REQUEST, render(NotFoundTemplate { message })).into_response()
+}
+
+/// 格式化大额数值（市值、成交量）
+fn format_amount(amount: Option<f64>) -> String {
+    amount.map_or_else(|| "-".to_string(), format_usd)
+}
+
+/// 格式化供应量
+fn format_supply(supply: Option<f64>) -> String {
+    supply.map_or_else(|| "-".to_string(), format_count)
+}
+
+/// 格式化涨跌幅，返回文本和样式类
+fn format_change(change: Option<f64>) -> (String, &'static str) {
+    match change {
+        Some(v) => (formatting::format_change(v), if v >= 0.0 { "up" } else { "down" }),
+        None => ("-".to_string(), ""),
+    }
+}
+
+/// RSI信号的中文描述
+fn rsi_signal_text(signal: &RSISignal) -> &'static str {
+    match signal {
+        RSISignal::Normal => "正常",
+        RSISignal::Overbought => "超买",
+        RSISignal::Oversold => "超卖",
+    }
+}
+
+/// 总览页中的一行币种数据
+struct CoinRow {
+    coin_id: String,
+    name: String,
+    symbol: String,
+    price: String,
+    change: String,
+    change_class: &'static str,
+    market_cap: String,
+    rsi: String,
+    updated_at: String,
+}
+
+impl CoinRow {
+    fn from_data(data: &CachedMarketData, tz: Tz) -> Self {
+        let (change, change_class) = format_change(data.price_change_24h);
+        Self {
+            coin_id: data.coin_id.clone(),
+            name: data.name.clone(),
+            symbol: data.symbol.to_uppercase(),
+            price: format_price(data.current_price),
+            change,
+            change_class,
+            market_cap: format_amount(data.market_cap),
+            rsi: format!("{:.1}", data.technical_indicators.rsi.value),
+            updated_at: format_time(data.updated_at, tz),
+        }
+    }
+}
+
+/// 带分类的指数
+struct IndexGauge {
+    value: String,
+    classification: String,
+}
+
+/// 数据集状态行
+struct DatasetRow {
+    key: String,
+    last_success: String,
+    stale: bool,
+    error: String,
+}
+
+/// 总览页
+#[derive(Template)]
+#[template(path = "index.html")]
+struct IndexTemplate {
+    generated_at: String,
+    fear_greed: Option<IndexGauge>,
+    altcoin_season: Option<IndexGauge>,
+    coins: Vec<CoinRow>,
+    datasets: Vec<DatasetRow>,
+}
+
+/// 总览：行情、情绪指数和数据集状态
+async fn index_page(
+    State(cache): State<Arc<DataCache>>,
+    Query(query): Query<FormatQuery>,
+) -> Response {
+    let tz = match query.timezone() {
+        Ok(tz) => tz,
+        Err(message) => return bad_request(message),
+    };
+    let fear_greed = cache.get_fear_greed_index().await.map(|data| IndexGauge {
+        value: data.value.to_string(),
+        classification: data.value_classification_zh,
+    });
+    let altcoin_season = cache.get_altcoin_season_index().await.map(|data| IndexGauge {
+        value: data.value.to_string(),
+        classification: data.classification_zh,
+    });
+
+    let mut coins: Vec<CoinRow> = cache.get_all_market_data().await.iter().map(|data| CoinRow::from_data(data, tz)).collect();
+    coins.sort_by(|a, b| a.coin_id.cmp(&b.coin_id));
+
+    let statuses: BTreeMap<String, _> = cache.get_all_entry_status().await.into_iter().collect();
+    let datasets = statuses.into_iter()
+        .map(|(key, status)| DatasetRow {
+            key,
+            last_success: status.last_success.map(|t| format_time(t, tz)).unwrap_or_else(|| "-".to_string()),
+            stale: status.stale,
+            error: status.last_error.unwrap_or_default(),
+        })
+        .collect();
+
+    render(IndexTemplate {
+        generated_at: format_time(Utc::now(), tz),
+        fear_greed,
+        altcoin_season,
+        coins,
+        datasets,
+    })
+}
+
+/// 币种详情页的历史数据行
+struct HistoryRow {
+    time: String,
+    price: String,
+    rsi: String,
+}
+
+/// 币种详情页
+#[derive(Template)]
+#[template(path = "coin.html")]
+struct CoinTemplate {
+    generated_at: String,
+    coin: CoinRow,
+    volume: String,
+    rsi_signal: &'static str,
+    bollinger_upper: String,
+    bollinger_middle: String,
+    bollinger_lower: String,
+    circulating_supply: String,
+    max_supply: String,
+    fully_diluted_market_cap: String,
+    dilution_ratio: String,
+    emission_30d: String,
+    spread: String,
+    depth: String,
+    history: Vec<HistoryRow>,
+}
+
+/// 未找到页面
+#[derive(Template)]
+#[template(path = "not_found.html")]
+struct NotFoundTemplate {
+    message: String,
+}
+
+/// 币种详情：行情、技术指标和最近的指标历史
+async fn coin_page(
+    State(cache): State<Arc<DataCache>>,
+    State(metrics): State<Arc<MetricStore>>,
+    Path(coin_id): Path<String>,
+    Query(query): Query<FormatQuery>,
+) -> Response {
+    let tz = match query.timezone() {
+        Ok(tz) => tz,
+        Err(message) => return bad_request(message),
+    };
+    let coin_id = coin_id.to_lowercase();
+    let Some(data) = cache.get_market_data(&coin_id).await else {
+        let page = render(NotFoundTemplate { message: format!("币种 {} 不在监控列表中", coin_id) });
+        return (StatusCode::NOT_FOUND, page).into_response();
+    };
+
+    // 价格和RSI按时间戳合并，最新的在前
+    let query = MetricQuery { limit: Some(COIN_HISTORY_POINTS), ..MetricQuery::default() };
+    let mut rows: BTreeMap<DateTime<Utc>, (Option<f64>, Option<f64>)> = BTreeMap::new();
+    for point in metrics.query(&indicator_metric_name(&coin_id, "price"), &query).await {
+        rows.entry(point.timestamp).or_default().0 = point.value.as_f64();
+    }
+    for point in metrics.query(&indicator_metric_name(&coin_id, "rsi"), &query).await {
+        rows.entry(point.timestamp).or_default().1 = point.value.as_f64();
+    }
+    let history = rows.into_iter()
+        .rev()
+        .take(COIN_HISTORY_POINTS)
+        .map(|(time, (price, rsi))| HistoryRow {
+            time: format_time(time, tz),
+            price: price.map(format_price).unwrap_or_else(|| "-".to_string()),
+            rsi: rsi.map(|v| format!("{:.1}", v)).unwrap_or_else(|| "-".to_string()),
+        })
+        .collect();
+
+    // 流动性快照按 `<符号>USDT` 交易对匹配
+    let pair = format!("{}USDT", data.symbol.to_uppercase());
+    let liquidity = cache.get_dataset::<Vec<LiquiditySnapshot>>(LIQUIDITY_KEY).await
+        .unwrap_or_default()
+        .into_iter()
+        .find(|s| s.symbol == pair);
+
+    let bands = &data.technical_indicators.bollinger_bands;
+    let supply = data.supply.as_ref();
+    render(CoinTemplate {
+        generated_at: format_time(Utc::now(), tz),
+        volume: format_amount(data.volume_24h),
+        rsi_signal: rsi_signal_text(&data.technical_indicators.rsi.signal),
+        bollinger_upper: format_price(bands.upper),
+        bollinger_middle: format_price(bands.middle),
+        bollinger_lower: format_price(bands.lower),
+        circulating_supply: format_supply(supply.map(|s| s.circulating_supply)),
+        max_supply: format_supply(supply.and_then(|s| s.max_supply.or(s.total_supply))),
+        fully_diluted_market_cap: format_amount(supply.and_then(|s| s.fully_diluted_market_cap)),
+        dilution_ratio: supply.and_then(|s| s.dilution_ratio)
+            .map(|r| format!("{:.1}%", r * 100.0))
+            .unwrap_or_else(|| "-".to_string()),
+        emission_30d: match supply.and_then(|s| s.emission_30d.zip(s.emission_30d_pct)) {
+            Some((emission, pct)) => format!("{}（+{:.2}%）", format_supply(Some(emission)), pct),
+            None => "-".to_string(),
+        },
+        spread: liquidity.as_ref()
+            .map(|l| format!("{:.2} bps（{}）", l.spread_bps, l.symbol))
+            .unwrap_or_else(|| "-".to_string()),
+        depth: liquidity.as_ref()
+            .map(|l| format!("±{}%：买 {} / 卖 {}", l.depth_pct, format_amount(Some(l.bid_depth)), format_amount(Some(l.ask_depth))))
+            .unwrap_or_else(|| "-".to_string()),
+        history,
+        coin: CoinRow::from_data(&data, tz),
+    })
+}
+
+/// 告警规则行
+struct RuleRow {
+    name: String,
+    condition: String,
+    severity: String,
+    enabled: bool,
+}
+
+/// 最近告警行
+struct AlertRow {
+    time: String,
+    severity: String,
+    severity_class: String,
+    title: String,
+    message: String,
+}
+
+/// 告警页
+#[derive(Template)]
+#[template(path = "alerts.html")]
+struct AlertsTemplate {
+    generated_at: String,
+    rules: Vec<RuleRow>,
+    alerts: Vec<AlertRow>,
+}
+
+/// 告警是否可以在公开页面展示
+///
+/// 规则和价格监控触发的告警只展示全局规则和全局监控的，用户私有（或已删除）的不展示；其他系统告警照常展示
+///
+/// # 参数
+/// * `alert` - 告警
+/// * `global` - 全局规则和全局监控的告警标识（`rule:<id>`、`watch:<id>`）
+fn is_public_alert(alert: &Alert, global: &HashSet<String>) -> bool {
+    match alert.rule.split_once(':') {
+        Some(("rule" | "watch", _)) => global.contains(&alert.rule),
+        _ => true,
+    }
+}
+
+/// 告警列表：全局规则和最近触发的告警
+///
+/// 页面不需要登录，因此只显示全局规则和它们触发的告警，不显示用户私有规则和私有监控的告警
+async fn alerts_page(
+    State(rules): State<Arc<AlertRuleStore>>,
+    State(watches): State<Arc<WatchStore>>,
+    State(alert_manager): State<Arc<AlertManager>>,
+    Query(query): Query<FormatQuery>,
+) -> Response {
+    let tz = match query.timezone() {
+        Ok(tz) => tz,
+        Err(message) => return bad_request(message),
+    };
+    let rules: Vec<_> = rules.list().await
+        .into_iter()
+        .filter(|r| r.owner.is_none())
+        .collect();
+    let global: HashSet<String> = rules.iter()
+        .map(|r| format!("rule:{}", r.id))
+        .chain(watches.list().await.into_iter().filter(|w| w.owner.is_none()).map(|w| format!("watch:{}", w.id)))
+        .collect();
+    let rules = rules.into_iter()
+        .map(|r| RuleRow {
+            condition: r.describe_condition(),
+            severity: r.severity.to_string(),
+            enabled: r.enabled,
+            name: r.name,
+        })
+        .collect();
+    let alerts = alert_manager.recent_alerts(usize::MAX).await
+        .into_iter()
+        .filter(|a| is_public_alert(a, &global))
+        .take(RECENT_ALERTS)
+        .map(|a| AlertRow {
+            time: format_time(a.created_at, tz),
+            severity: a.severity.to_string(),
+            severity_class: format!("{:?}", a.severity).to_lowercase(),
+            title: a.title,
+            message: a.message,
+        })
+        .collect();
+
+    render(AlertsTemplate {
+        generated_at: format_time(Utc::now(), tz),
+        rules,
+        alerts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_found_escapes_message() {
+        let html = NotFoundTemplate { message: "<script>".to_string() }.render().unwrap();
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn test_private_alerts_hidden() {
+        let global = HashSet::from(["rule:global".to_string()]);
+        let alert = |rule: &str| Alert::new(rule, crate::alerts::AlertSeverity::Warning, "t", "m");
+        assert!(is_public_alert(&alert("rule:global"), &global));
+        assert!(!is_public_alert(&alert("rule:private"), &global));
+        assert!(!is_public_alert(&alert("watch:private"), &global));
+        assert!(is_public_alert(&alert("staleness:fear_greed"), &global));
+    }
+}
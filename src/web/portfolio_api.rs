@@ -0,0 +1,102 @@
+use axum::{
+    Router,
+    routing::{get, post, put},
+    extract::{Path, Query, State},
+    response::Json,
+    http::StatusCode,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::alert_api::HistoryQuery;
+use super::api::ApiResponse;
+use super::auth::{audit_entry, AuthUser};
+use super::cache::DataCache;
+use super::AppState;
+use crate::audit::AuditLog;
+use crate::portfolio::{PortfolioSnapshot, PortfolioStore, PortfolioValuation, Position, PositionInput};
+
+/// 创建投资组合路由
+/// 
+/// 所有接口都需要登录
+/// 
+/// # 返回
+/// * `Router<AppState>` - 持仓管理与估值路由
+pub fn create_portfolio_routes() -> Router<AppState> {
+    Router::new()
+        // 当前估值
+        .route("/portfolio", get(get_valuation))
+        // 估值快照历史
+        .route("/portfolio/history", get(get_history))
+        // 新增持仓
+        .route("/portfolio/positions", post(add_position))
+        // 编辑 / 删除持仓
+        .route("/portfolio/positions/:id", put(update_position).delete(delete_position))
+}
+
+/// 获取当前组合估值
+async fn get_valuation(
+    user: AuthUser,
+    State(portfolio): State<Arc<PortfolioStore>>,
+    State(cache): State<Arc<DataCache>>,
+) -> Json<ApiResponse<PortfolioValuation>> {
+    Json(ApiResponse::success(portfolio.valuate(user.id, &cache).await))
+}
+
+/// 获取估值快照历史
+async fn get_history(
+    user: AuthUser,
+    State(portfolio): State<Arc<PortfolioStore>>,
+    Query(query): Query<HistoryQuery>,
+) -> Json<ApiResponse<Vec<PortfolioSnapshot>>> {
+    Json(ApiResponse::success(portfolio.snapshots(user.id, query.limit.unwrap_or(500)).await))
+}
+
+/// 新增持仓
+async fn add_position(
+    user: AuthUser,
+    State(audit): State<Arc<AuditLog>>,
+    State(portfolio): State<Arc<PortfolioStore>>,
+    Json(input): Json<PositionInput>,
+) -> Result<Json<ApiResponse<Position>>, StatusCode> {
+    match portfolio.add(user.id, input).await {
+        Ok(position) => {
+            audit.record(audit_entry(&Some(user), "portfolio.position.add").target(position.id).payload(&position)).await;
+            Ok(Json(ApiResponse::success(position)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 编辑持仓
+async fn update_position(
+    user: AuthUser,
+    State(audit): State<Arc<AuditLog>>,
+    State(portfolio): State<Arc<PortfolioStore>>,
+    Path(id): Path<Uuid>,
+    Json(input): Json<PositionInput>,
+) -> Result<Json<ApiResponse<Position>>, StatusCode> {
+    match portfolio.update(user.id, id, input).await {
+        Ok(position) => {
+            audit.record(audit_entry(&Some(user), "portfolio.position.update").target(position.id).payload(&position)).await;
+            Ok(Json(ApiResponse::success(position)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 删除持仓
+async fn delete_position(
+    user: AuthUser,
+    State(audit): State<Arc<AuditLog>>,
+    State(portfolio): State<Arc<PortfolioStore>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Position>>, StatusCode> {
+    match portfolio.delete(user.id, id).await {
+        Ok(position) => {
+            audit.record(audit_entry(&Some(user), "portfolio.position.delete").target(position.id).payload(&position)).await;
+            Ok(Json(ApiResponse::success(position)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
@@ -0,0 +1,59 @@
+use axum::{
+    Router,
+    routing::get,
+    extract::State,
+    http::header,
+    response::IntoResponse,
+};
+use std::fmt::Write;
+use std::sync::Arc;
+
+use super::AppState;
+use super::cache::{CacheStats, DataCache};
+
+/// Prometheus文本格式的Content-Type
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// 创建Prometheus指标路由
+///
+/// # 返回
+/// * `Router<AppState>` - Prometheus指标路由
+pub fn create_prometheus_routes() -> Router<AppState> {
+    Router::new()
+        // Prometheus抓取端点
+        .route("/metrics", get(prometheus_metrics))
+}
+
+/// 以Prometheus文本格式输出运行指标
+async fn prometheus_metrics(
+    State(cache): State<Arc<DataCache>>,
+) -> impl IntoResponse {
+    let mut body = String::new();
+    render_cache_metrics(&mut body, &cache.get_stats().await);
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], body)
+}
+
+/// 写入一个不带标签的指标
+fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, value: impl std::fmt::Display) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+/// 写入缓存统计指标
+///
+/// # 参数
+/// * `out` - 输出缓冲
+/// * `stats` - 缓存统计信息
+pub fn render_cache_metrics(out: &mut String, stats: &CacheStats) {
+    write_metric(out, "everscan_cache_items", "gauge", "缓存的币种行情数", stats.total_items);
+    write_metric(out, "everscan_cache_hits_total", "counter", "缓存命中次数", stats.hits);
+    write_metric(out, "everscan_cache_misses_total", "counter", "缓存未命中次数", stats.misses);
+    write_metric(out, "everscan_cache_memory_entries", "gauge", "计入容量的缓存条目数", stats.memory.entries);
+    write_metric(out, "everscan_cache_memory_bytes", "gauge", "缓存条目序列化后的估算字节数", stats.memory.approx_bytes);
+    write_metric(out, "everscan_cache_max_bytes", "gauge", "缓存估算内存上限（字节，0表示不限制）", stats.memory.max_bytes);
+    write_metric(out, "everscan_cache_max_entries", "gauge", "缓存条目数上限（0表示不限制）", stats.memory.max_entries);
+    write_metric(out, "everscan_cache_evictions_total", "counter", "因超出容量上限被淘汰的缓存条目数", stats.memory.evictions);
+    write_metric(out, "everscan_cache_serialized_hits_total", "counter", "复用预序列化市场数据的次数", stats.serialized_hits);
+    write_metric(out, "everscan_cache_serialized_misses_total", "counter", "重新序列化市场数据的次数", stats.serialized_misses);
+}
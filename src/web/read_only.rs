@@ -0,0 +1,73 @@
+use axum::{
+    extract::{Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+
+use super::api::ApiResponse;
+
+/// 只读实例上仍然接受的非GET接口（相对 `/api` 的路径前缀）
+///
+/// 指标查询、情景模拟和登录不写入数据；维护模式和监听器切换只作用于当前实例
+const WRITABLE_PREFIXES: &[&str] = &["/metrics/query", "/simulate", "/auth/login", "/admin/maintenance", "/admin/server/rebind"];
+
+/// 只读API实例标记（`--api-only`）
+///
+/// 只读实例只从共享数据目录加载数据，用户、告警规则、价格监控和投资组合等存储不会同步到其他实例，
+/// 因此拒绝写入请求，写操作应发送到采集实例
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadOnlyMode {
+    /// 是否为只读实例
+    pub enabled: bool,
+}
+
+impl ReadOnlyMode {
+    /// 创建只读实例标记
+    ///
+    /// # 参数
+    /// * `enabled` - 是否为只读实例
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+}
+
+/// 只读实例是否接受该请求
+fn is_allowed(method: &Method, path: &str) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        || WRITABLE_PREFIXES.iter().any(|prefix| path.starts_with(prefix))
+}
+
+/// 只读实例中间件
+///
+/// 只读实例上除查询类接口外的非GET请求返回503
+pub async fn read_only_guard(
+    State(mode): State<ReadOnlyMode>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !mode.enabled || is_allowed(request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(ApiResponse::<()>::error("只读API实例不接受写入请求，请发送到采集实例")),
+    ).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_requests() {
+        assert!(is_allowed(&Method::GET, "/alerts"));
+        assert!(is_allowed(&Method::POST, "/metrics/query"));
+        assert!(is_allowed(&Method::POST, "/auth/login"));
+        assert!(is_allowed(&Method::PUT, "/admin/maintenance"));
+        assert!(!is_allowed(&Method::POST, "/auth/register"));
+        assert!(!is_allowed(&Method::POST, "/alerts"));
+        assert!(!is_allowed(&Method::DELETE, "/watches/1"));
+        assert!(!is_allowed(&Method::PUT, "/portfolio/positions/1"));
+    }
+}
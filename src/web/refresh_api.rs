@@ -0,0 +1,203 @@
+use axum::{
+    Router,
+    routing::post,
+    extract::{Path, State},
+    response::Json,
+    http::{HeaderMap, StatusCode},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::info;
+
+use super::api::ApiResponse;
+use super::auth::audit_entry;
+use super::cache::DataCache;
+use super::ingest_api::token_matches;
+use super::AppState;
+use crate::audit::AuditLog;
+use crate::config::RefreshConfig;
+use crate::tasks::{JobQueue, JobSource};
+
+/// 刷新令牌请求头
+pub const REFRESH_TOKEN_HEADER: &str = "x-refresh-token";
+
+/// 可刷新的数据集及对应的任务ID
+const REFRESHABLE_DATASETS: &[(&str, &str)] = &[
+    ("market-data", "crypto_market_task"),
+    ("fear-greed", "fear_greed"),
+    ("altcoin-season", "altcoin_season"),
+];
+
+/// 按需刷新限流器
+///
+/// 同一任务在最小间隔内只接受一次刷新请求，避免外部系统频繁触发导致上游API限流。
+/// 单个币种的刷新执行的是整个行情任务，因此与 `market-data` 共用同一个限额
+pub struct RefreshLimiter {
+    /// 调用令牌（可选）
+    token: Option<String>,
+    /// 最小刷新间隔
+    min_interval: Duration,
+    /// 任务ID -> 最后一次接受刷新的时间
+    last_accepted: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl RefreshLimiter {
+    /// 根据配置创建限流器
+    pub fn from_config(config: &RefreshConfig) -> Self {
+        Self {
+            token: config.token.as_ref()
+                .filter(|t| !t.is_empty())
+                .map(|t| t.expose().to_string()),
+            min_interval: Duration::seconds(config.min_interval_seconds as i64),
+            last_accepted: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 校验调用令牌，未配置令牌时总是通过
+    pub fn authorize(&self, provided: Option<&str>) -> bool {
+        match &self.token {
+            Some(token) => provided.is_some_and(|p| token_matches(p, token)),
+            None => true,
+        }
+    }
+
+    /// 尝试为任务占用一次刷新
+    ///
+    /// # 参数
+    /// * `task_id` - 刷新执行的任务ID
+    /// * `now` - 当前时间
+    ///
+    /// # 返回
+    /// * `Result<(), i64>` - 被限流时返回需要等待的秒数
+    pub async fn try_acquire(&self, task_id: &str, now: DateTime<Utc>) -> Result<(), i64> {
+        let mut last_accepted = self.last_accepted.lock().await;
+        if let Some(last) = last_accepted.get(task_id) {
+            let next_allowed = *last + self.min_interval;
+            if now < next_allowed {
+                return Err((next_allowed - now).num_seconds().max(1));
+            }
+        }
+        last_accepted.insert(task_id.to_string(), now);
+        Ok(())
+    }
+}
+
+/// 已接受的刷新请求
+#[derive(Debug, Serialize)]
+pub struct RefreshAccepted {
+    /// 数据集
+    pub dataset: String,
+    /// 执行的任务ID
+    pub task_id: String,
+    /// 接受时间
+    pub queued_at: DateTime<Utc>,
+}
+
+/// 创建按需刷新路由
+///
+/// # 返回
+/// * `Router<AppState>` - 刷新接口路由
+pub fn create_refresh_routes() -> Router<AppState> {
+    Router::new()
+        // 刷新整个数据集
+        .route("/refresh/:dataset", post(refresh_dataset))
+        // 刷新单个币种
+        .route("/refresh/coin/:coin_id", post(refresh_coin))
+}
+
+/// 刷新数据集（market-data、fear-greed、altcoin-season）
+async fn refresh_dataset(
+    State(limiter): State<Arc<RefreshLimiter>>,
+    State(jobs): State<JobQueue>,
+    State(audit): State<Arc<AuditLog>>,
+    Path(dataset): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<RefreshAccepted>>, StatusCode> {
+    let Some((_, task_id)) = REFRESHABLE_DATASETS.iter().find(|(name, _)| *name == dataset) else {
+        let names: Vec<&str> = REFRESHABLE_DATASETS.iter().map(|(name, _)| *name).collect();
+        return Ok(Json(ApiResponse::error(format!(
+            "未知数据集: {}（支持: {}、coin/:id）", dataset, names.join("、")
+        ))));
+    };
+    enqueue_refresh(&limiter, &jobs, &audit, &headers, dataset, task_id).await
+}
+
+/// 刷新单个币种的行情
+///
+/// 执行整个行情任务，与 `market-data` 共用刷新限额
+async fn refresh_coin(
+    State(limiter): State<Arc<RefreshLimiter>>,
+    State(jobs): State<JobQueue>,
+    State(cache): State<Arc<DataCache>>,
+    State(audit): State<Arc<AuditLog>>,
+    Path(coin_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<RefreshAccepted>>, StatusCode> {
+    let coin_id = coin_id.to_lowercase();
+    if cache.get_market_data(&coin_id).await.is_none() {
+        return Ok(Json(ApiResponse::error(format!("币种 {} 不在监控列表中", coin_id))));
+    }
+    enqueue_refresh(&limiter, &jobs, &audit, &headers, format!("coin/{}", coin_id), "crypto_market_task").await
+}
+
+/// 校验令牌和限流后把任务放入队列
+async fn enqueue_refresh(
+    limiter: &RefreshLimiter,
+    jobs: &JobQueue,
+    audit: &AuditLog,
+    headers: &HeaderMap,
+    dataset: String,
+    task_id: &str,
+) -> Result<Json<ApiResponse<RefreshAccepted>>, StatusCode> {
+    let provided = headers.get(REFRESH_TOKEN_HEADER).and_then(|v| v.to_str().ok());
+    if !limiter.authorize(provided) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let now = Utc::now();
+    if let Err(retry_after) = limiter.try_acquire(task_id, now).await {
+        return Ok(Json(ApiResponse::error(format!(
+            "数据集 {} 刷新过于频繁，请 {} 秒后重试", dataset, retry_after
+        ))));
+    }
+
+    if let Err(e) = jobs.enqueue(task_id, JobSource::Refresh).await {
+        return Ok(Json(ApiResponse::error(e.to_string())));
+    }
+    audit.record(
+        audit_entry(&None, "dataset.refresh").target(&dataset).payload(serde_json::json!({ "task_id": task_id }))
+    ).await;
+    info!("🔄 收到数据集 {} 的刷新请求，任务 {} 已加入队列", dataset, task_id);
+
+    Ok(Json(ApiResponse::success(RefreshAccepted {
+        dataset,
+        task_id: task_id.to_string(),
+        queued_at: now,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::SecretString;
+
+    #[tokio::test]
+    async fn test_limiter_per_dataset() {
+        let limiter = RefreshLimiter::from_config(&RefreshConfig {
+            token: Some(SecretString::new("s3cret")),
+            min_interval_seconds: 60,
+        });
+        assert!(limiter.authorize(Some("s3cret")));
+        assert!(!limiter.authorize(None));
+        assert!(!limiter.authorize(Some("s3cre")));
+
+        let now = Utc::now();
+        assert!(limiter.try_acquire("fear_greed", now).await.is_ok());
+        assert_eq!(limiter.try_acquire("fear_greed", now + Duration::seconds(20)).await, Err(40));
+        assert!(limiter.try_acquire("crypto_market_task", now).await.is_ok());
+        assert!(limiter.try_acquire("fear_greed", now + Duration::seconds(60)).await.is_ok());
+    }
+}
@@ -0,0 +1,164 @@
+use anyhow::{anyhow, bail, Context, Result};
+use axum::http::HeaderMap;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, VerifyingKey};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::config::{SignatureConfig, SignatureScheme};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 校验密钥
+enum Key {
+    /// HMAC共享密钥
+    Hmac(Vec<u8>),
+    /// Ed25519公钥
+    Ed25519(VerifyingKey),
+}
+
+/// 入站请求签名校验器
+///
+/// 按数据源配置校验推送请求（以及以后的交易所回调）的签名，拒绝伪造的请求。
+/// 配置了时间戳请求头时签名内容为 `{时间戳}.{请求体}`（与出站Webhook签名一致），并拒绝时间偏差过大的请求以防重放
+pub struct SignatureVerifier {
+    /// 校验密钥
+    key: Key,
+    /// 签名请求头
+    header: String,
+    /// 时间戳请求头
+    timestamp_header: Option<String>,
+    /// 时间戳允许的最大偏差（秒）
+    max_age_seconds: i64,
+}
+
+impl SignatureVerifier {
+    /// 根据配置创建校验器
+    ///
+    /// # 参数
+    /// * `config` - 签名校验配置
+    ///
+    /// # 返回
+    /// * `Result<Self>` - 密钥为空或公钥格式无效时返回错误
+    pub fn from_config(config: &SignatureConfig) -> Result<Self> {
+        if config.key.is_empty() {
+            bail!("签名校验未配置密钥");
+        }
+        let key = match config.scheme {
+            SignatureScheme::HmacSha256 => Key::Hmac(config.key.expose().as_bytes().to_vec()),
+            SignatureScheme::Ed25519 => {
+                let bytes: [u8; 32] = decode(config.key.expose())
+                    .and_then(|b| b.try_into().ok())
+                    .ok_or_else(|| anyhow!("Ed25519公钥应为32字节的hex或base64"))?;
+                Key::Ed25519(VerifyingKey::from_bytes(&bytes).context("Ed25519公钥无效")?)
+            }
+        };
+        Ok(Self {
+            key,
+            header: config.header.to_lowercase(),
+            timestamp_header: config.timestamp_header.as_ref().map(|h| h.to_lowercase()),
+            max_age_seconds: i64::try_from(config.max_age_seconds).context("时间戳允许的最大偏差过大")?,
+        })
+    }
+
+    /// 校验请求签名
+    ///
+    /// # 参数
+    /// * `headers` - 请求头
+    /// * `body` - 原始请求体
+    /// * `now` - 当前时间
+    ///
+    /// # 返回
+    /// * `Result<()>` - 缺少签名、时间戳过期或签名不匹配时返回错误
+    pub fn verify(&self, headers: &HeaderMap, body: &[u8], now: DateTime<Utc>) -> Result<()> {
+        let signature = header_value(headers, &self.header)
+            .ok_or_else(|| anyhow!("缺少签名请求头 {}", self.header))?;
+        let signature = decode(signature).ok_or_else(|| anyhow!("签名格式无效"))?;
+
+        let mut message = Vec::with_capacity(body.len() + 16);
+        if let Some(timestamp_header) = &self.timestamp_header {
+            let timestamp = header_value(headers, timestamp_header)
+                .ok_or_else(|| anyhow!("缺少时间戳请求头 {}", timestamp_header))?;
+            let seconds: i64 = timestamp.parse().map_err(|_| anyhow!("时间戳格式无效"))?;
+            let skew = now.timestamp().checked_sub(seconds).map(i64::unsigned_abs);
+            if skew.is_none_or(|skew| skew > self.max_age_seconds.unsigned_abs()) {
+                bail!("时间戳超出允许范围");
+            }
+            message.extend_from_slice(timestamp.as_bytes());
+            message.push(b'.');
+        }
+        message.extend_from_slice(body);
+
+        match &self.key {
+            Key::Hmac(secret) => {
+                let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC可以接受任意长度的密钥");
+                mac.update(&message);
+                mac.verify_slice(&signature).map_err(|_| anyhow!("签名不匹配"))
+            }
+            Key::Ed25519(key) => {
+                let signature = Signature::from_slice(&signature).map_err(|_| anyhow!("签名格式无效"))?;
+                key.verify_strict(&message, &signature).map_err(|_| anyhow!("签名不匹配"))
+            }
+        }
+    }
+}
+
+/// 读取请求头的文本值
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok()).map(str::trim)
+}
+
+/// 解码hex或base64编码的签名/公钥（忽略 `sha256=`、`ed25519=` 等前缀）
+fn decode(value: &str) -> Option<Vec<u8>> {
+    let value = value.trim();
+    let value = ["sha256=", "ed25519="].iter()
+        .find_map(|prefix| value.strip_prefix(prefix))
+        .unwrap_or(value);
+    hex::decode(value).ok().or_else(|| STANDARD.decode(value).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::sign_payload;
+    use crate::secrets::SecretString;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    #[test]
+    fn test_verify_hmac_and_ed25519() {
+        let now = Utc::now();
+        let body = br#"{"name": "tv_close", "value": 1}"#;
+
+        let hmac = SignatureVerifier::from_config(&SignatureConfig {
+            scheme: SignatureScheme::HmacSha256,
+            key: SecretString::new("s3cret"),
+            header: "X-Signature".to_string(),
+            timestamp_header: Some("X-Timestamp".to_string()),
+            max_age_seconds: 300,
+        }).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-timestamp", now.timestamp().to_string().parse().unwrap());
+        headers.insert("x-signature", sign_payload("s3cret", now.timestamp(), body).parse().unwrap());
+        assert!(hmac.verify(&headers, body, now).is_ok());
+        assert!(hmac.verify(&headers, b"{}", now).is_err());
+        assert!(hmac.verify(&headers, body, now + chrono::Duration::minutes(10)).is_err());
+        // 极端时间戳不会溢出
+        headers.insert("x-timestamp", i64::MIN.to_string().parse().unwrap());
+        assert!(hmac.verify(&headers, body, now).is_err());
+
+        let signing = SigningKey::from_bytes(&[7u8; 32]);
+        let ed25519 = SignatureVerifier::from_config(&SignatureConfig {
+            scheme: SignatureScheme::Ed25519,
+            key: SecretString::new(hex::encode(signing.verifying_key().to_bytes())),
+            header: "x-signature".to_string(),
+            timestamp_header: None,
+            max_age_seconds: 300,
+        }).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-signature", STANDARD.encode(signing.sign(body).to_bytes()).parse().unwrap());
+        assert!(ed25519.verify(&headers, body, now).is_ok());
+        assert!(ed25519.verify(&headers, b"{}", now).is_err());
+        assert!(ed25519.verify(&HeaderMap::new(), body, now).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use axum::{
+    Router,
+    routing::post,
+    extract::State,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::api::ApiResponse;
+use super::auth::{user_id, AuthUser};
+use super::cache::DataCache;
+use super::AppState;
+use crate::alerts::{check_rule, AlertRuleStore, AlertSeverity, ScriptContext};
+use crate::analytics::{current_sentiment, AppliedOverride, CompositeSentiment, Scenario, ScenarioOverride};
+use crate::config::SentimentConfig;
+use crate::storage::MetricStore;
+use crate::tasks::DerivedMetricEngine;
+
+/// 创建情景模拟路由
+///
+/// # 返回
+/// * `Router<AppState>` - 情景模拟路由
+pub fn create_simulate_routes() -> Router<AppState> {
+    Router::new()
+        // 假设指标变化后的派生指标、综合情绪和告警
+        .route("/simulate", post(simulate))
+}
+
+/// 情景模拟请求
+#[derive(Debug, Deserialize)]
+pub struct SimulateRequest {
+    /// 假设变化（如 `{"metric": "price:btc", "change_pct": -20}`）
+    pub overrides: Vec<ScenarioOverride>,
+}
+
+/// 派生指标的模拟结果
+#[derive(Debug, Clone, Serialize)]
+pub struct DerivedMetricComparison {
+    /// 指标名称
+    pub name: String,
+    /// 单位（可选）
+    pub unit: Option<String>,
+    /// 当前数据下的值
+    pub baseline: Option<f64>,
+    /// 模拟情景下的值
+    pub simulated: Option<f64>,
+}
+
+/// 综合情绪的模拟结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SentimentComparison {
+    /// 当前数据下的综合情绪
+    pub baseline: Option<CompositeSentiment>,
+    /// 模拟情景下的综合情绪
+    pub simulated: Option<CompositeSentiment>,
+}
+
+/// 模拟情景下会触发的告警规则
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedRuleMatch {
+    /// 规则ID
+    pub rule_id: Uuid,
+    /// 规则名称
+    pub name: String,
+    /// 告警级别
+    pub severity: AlertSeverity,
+    /// 条件描述
+    pub condition: String,
+    /// 模拟情景下的触发值（脚本条件为1）
+    pub value: f64,
+    /// 当前数据下是否已满足条件
+    pub firing_now: bool,
+}
+
+/// 情景模拟结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationResult {
+    /// 已应用的假设变化
+    pub overrides: Vec<AppliedOverride>,
+    /// 派生指标
+    pub derived_metrics: Vec<DerivedMetricComparison>,
+    /// 综合情绪
+    pub sentiment: SentimentComparison,
+    /// 会触发的告警规则（当前用户可见的已启用规则）
+    pub alerts: Vec<SimulatedRuleMatch>,
+}
+
+/// 情景模拟
+///
+/// 在当前缓存的副本上应用假设变化，重新计算派生指标、综合情绪并检查告警规则；
+/// 不写入指标存储、不发送告警，用于安全地验证规则配置
+async fn simulate(
+    user: Option<AuthUser>,
+    State(cache): State<Arc<DataCache>>,
+    State(metrics): State<Arc<MetricStore>>,
+    State(derived): State<Arc<DerivedMetricEngine>>,
+    State(weights): State<Arc<SentimentConfig>>,
+    State(rules): State<Arc<AlertRuleStore>>,
+    Json(request): Json<SimulateRequest>,
+) -> Json<ApiResponse<SimulationResult>> {
+    let scenario = match Scenario::build(&cache, &metrics, &request.overrides).await {
+        Ok(scenario) => scenario,
+        Err(e) => return Json(ApiResponse::error(e.to_string())),
+    };
+
+    let baseline_derived = derived.simulate(&cache, &BTreeMap::new()).await;
+    let simulated_derived = derived.simulate(&scenario.cache, &scenario.stored).await;
+    let derived_metrics = baseline_derived.into_iter()
+        .zip(simulated_derived)
+        .map(|(baseline, simulated)| DerivedMetricComparison {
+            name: baseline.name,
+            unit: baseline.unit,
+            baseline: baseline.value,
+            simulated: simulated.value,
+        })
+        .collect();
+
+    let sentiment = SentimentComparison {
+        baseline: current_sentiment(&cache, &weights).await,
+        simulated: current_sentiment(&scenario.cache, &weights).await,
+    };
+
+    let owner = user_id(&user);
+    let baseline_context = ScriptContext::from_cache(&cache).await;
+    let simulated_context = ScriptContext::from_cache(&scenario.cache).await;
+    let mut alerts = Vec::new();
+    for rule in rules.list().await.into_iter().filter(|r| r.enabled && r.is_visible_to(owner)) {
+        let Some(value) = check_rule(&rule, &scenario.cache, &simulated_context).await else {
+            continue;
+        };
+        alerts.push(SimulatedRuleMatch {
+            rule_id: rule.id,
+            condition: rule.describe_condition(),
+            firing_now: check_rule(&rule, &cache, &baseline_context).await.is_some(),
+            name: rule.name,
+            severity: rule.severity,
+            value,
+        });
+    }
+
+    Json(ApiResponse::success(SimulationResult {
+        overrides: scenario.applied,
+        derived_metrics,
+        sentiment,
+        alerts,
+    }))
+}
@@ -0,0 +1,168 @@
+use axum::{
+    Router,
+    routing::{get, put},
+    extract::{MatchedPath, Query, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+    http::{header::RETRY_AFTER, StatusCode},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use super::api::ApiResponse;
+use super::auth::{audit_entry, AdminUser, AuthUser, API_KEY_HEADER};
+use super::cache::{CachedMarketData, DataCache};
+use super::fields::{FieldsQuery, Sparse};
+use super::formatting::{FormatQuery, Humanized};
+use super::AppState;
+use crate::audit::AuditLog;
+use crate::tenants::{CoinUniverse, ConsumerUsage, TenantInfo, TenantRegistry};
+
+/// 创建租户路由
+///
+/// # 返回
+/// * `Router<AppState>` - 租户接口路由
+pub fn create_tenant_routes() -> Router<AppState> {
+    Router::new()
+        // 当前租户信息
+        .route("/tenant", get(get_tenant))
+        // 修改关注列表
+        .route("/tenant/watchlist", put(update_watchlist))
+        // 关注币种的行情
+        .route("/tenant/market-data", get(get_tenant_market_data))
+        // 全部租户（管理员）
+        .route("/admin/tenants", get(list_tenants))
+        // 各租户最近一小时的API用量（管理员）
+        .route("/admin/usage", get(get_usage))
+}
+
+/// 修改关注列表请求
+#[derive(Debug, Deserialize)]
+pub struct WatchlistRequest {
+    /// 关注币种（CoinGecko ID）
+    pub coins: Vec<String>,
+}
+
+/// 租户API密钥校验、限流和用量统计中间件
+///
+/// 携带 `X-Api-Key` 的请求：密钥无效返回401，超出租户每分钟或每小时请求数返回429（附 `Retry-After`），
+/// 放行的请求按路由记入租户的用量统计；未携带密钥的请求不受影响
+pub async fn tenant_guard(
+    State(tenants): State<Arc<TenantRegistry>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(api_key) = request.headers().get(API_KEY_HEADER).and_then(|v| v.to_str().ok()) else {
+        return next.run(request).await;
+    };
+    let Some(tenant) = tenants.authenticate(api_key).await else {
+        return (StatusCode::UNAUTHORIZED, Json(ApiResponse::<()>::error("无效的API密钥"))).into_response();
+    };
+    let now = Utc::now();
+    if let Err(retry_after) = tenants.try_acquire(&tenant.id, now).await {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(RETRY_AFTER, retry_after.to_string())],
+            Json(ApiResponse::<()>::error(format!(
+                "租户 {} 超出请求次数限制，请 {} 秒后重试",
+                tenant.id, retry_after
+            ))),
+        ).into_response();
+    }
+    // 按路由模板统计，避免路径参数产生大量不同的接口
+    let endpoint = request.extensions().get::<MatchedPath>()
+        .map_or_else(|| request.uri().path().to_string(), |path| path.as_str().to_string());
+    let response = next.run(request).await;
+    tenants.record_usage(&tenant.id, &endpoint, response.status().as_u16(), now);
+    response
+}
+
+/// 获取当前租户信息
+async fn get_tenant(
+    user: AuthUser,
+    State(tenants): State<Arc<TenantRegistry>>,
+) -> Json<ApiResponse<TenantInfo>> {
+    match tenant_of(&user, &tenants).await {
+        Ok(tenant) => Json(ApiResponse::success(tenant)),
+        Err(message) => Json(ApiResponse::error(message)),
+    }
+}
+
+/// 修改当前租户的关注列表
+///
+/// 新增的币种从下一轮行情采集开始出现在缓存中
+async fn update_watchlist(
+    user: AuthUser,
+    State(tenants): State<Arc<TenantRegistry>>,
+    State(audit): State<Arc<AuditLog>>,
+    Json(request): Json<WatchlistRequest>,
+) -> Json<ApiResponse<TenantInfo>> {
+    let tenant = match tenant_of(&user, &tenants).await {
+        Ok(tenant) => tenant,
+        Err(message) => return Json(ApiResponse::error(message)),
+    };
+    match tenants.set_watchlist(&tenant.id, &request.coins).await {
+        Ok(tenant) => {
+            audit.record(
+                audit_entry(&Some(user), "tenant.watchlist")
+                    .target(&tenant.id)
+                    .payload(serde_json::json!({ "coins": tenant.watchlist }))
+            ).await;
+            Json(ApiResponse::success(tenant))
+        }
+        Err(e) => Json(ApiResponse::error(e.to_string())),
+    }
+}
+
+/// 获取当前租户关注币种的行情（尚未采集到的币种不返回）
+///
+/// 支持 `?fields=` 只返回指定字段，`?format=human`（可加 `&tz=`）附加可读字符串
+async fn get_tenant_market_data(
+    user: AuthUser,
+    State(tenants): State<Arc<TenantRegistry>>,
+    State(coins): State<CoinUniverse>,
+    State(cache): State<Arc<DataCache>>,
+    Query(query): Query<FieldsQuery>,
+    Query(format): Query<FormatQuery>,
+) -> Json<ApiResponse<Vec<Humanized<Sparse<CachedMarketData>>>>> {
+    let tenant = match tenant_of(&user, &tenants).await {
+        Ok(tenant) => tenant,
+        Err(message) => return Json(ApiResponse::error(message)),
+    };
+    let fields = match query.market_data() {
+        Ok(fields) => fields,
+        Err(message) => return Json(ApiResponse::error(message)),
+    };
+    let tz = match format.resolve() {
+        Ok(tz) => tz,
+        Err(message) => return Json(ApiResponse::error(message)),
+    };
+    let mut data = Vec::new();
+    for coingecko_id in &tenant.watchlist {
+        data.extend(cache.get_market_data(coins.coin_id(coingecko_id)).await.map(|d| Humanized::new(Sparse::new(d, fields.clone()), tz)));
+    }
+    Json(ApiResponse::success(data))
+}
+
+/// 获取全部租户
+async fn list_tenants(
+    _admin: AdminUser,
+    State(tenants): State<Arc<TenantRegistry>>,
+) -> Json<ApiResponse<Vec<TenantInfo>>> {
+    Json(ApiResponse::success(tenants.list().await))
+}
+
+/// 获取各租户最近一小时的请求数、访问的接口和错误率（管理员）
+async fn get_usage(
+    _admin: AdminUser,
+    State(tenants): State<Arc<TenantRegistry>>,
+) -> Json<ApiResponse<Vec<ConsumerUsage>>> {
+    Json(ApiResponse::success(tenants.usage(Utc::now())))
+}
+
+/// 当前请求所属的租户
+async fn tenant_of(user: &AuthUser, tenants: &TenantRegistry) -> Result<TenantInfo, String> {
+    let id = user.tenant.as_deref().ok_or("需要使用租户API密钥（X-Api-Key）访问")?;
+    tenants.get(id).await.ok_or_else(|| format!("租户 {} 不存在", id))
+}
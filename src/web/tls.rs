@@ -0,0 +1,133 @@
+use anyhow::{anyhow, bail, Context, Result};
+use axum::{
+    Router,
+    http::{header::HOST, HeaderMap, StatusCode, Uri},
+    response::{IntoResponse, Redirect},
+};
+use rustls_pemfile::Item;
+use std::fs::File;
+use std::future::Future;
+use std::io::{self, BufReader};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_rustls::{rustls, TlsAcceptor};
+
+use super::listener::{serve_with, Listener};
+use crate::config::TlsConfig;
+
+/// TLS握手超时
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 加载证书链和私钥
+///
+/// # 参数
+/// * `config` - HTTPS配置
+///
+/// # 返回
+/// * `Result<Arc<rustls::ServerConfig>>` - 同时支持HTTP/2和HTTP/1.1的TLS配置
+pub fn load_server_config(config: &TlsConfig) -> Result<Arc<rustls::ServerConfig>> {
+    let file = File::open(&config.cert_path)
+        .with_context(|| format!("打开证书文件 {} 失败", config.cert_path))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .with_context(|| format!("解析证书文件 {} 失败", config.cert_path))?;
+    if certs.is_empty() {
+        bail!("证书文件 {} 中没有PEM格式的证书", config.cert_path);
+    }
+    let key = read_private_key(&config.key_path)?;
+
+    let mut server_config = rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs.into_iter().map(rustls::Certificate).collect(), key)
+        .context("证书与私钥不匹配或格式无效")?;
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Arc::new(server_config))
+}
+
+/// 读取第一个PEM格式的私钥（PKCS#8、PKCS#1 或 SEC1）
+fn read_private_key(path: &str) -> Result<rustls::PrivateKey> {
+    let file = File::open(path).with_context(|| format!("打开私钥文件 {} 失败", path))?;
+    let items = rustls_pemfile::read_all(&mut BufReader::new(file))
+        .with_context(|| format!("解析私钥文件 {} 失败", path))?;
+    items.into_iter()
+        .find_map(|item| match item {
+            Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key) => Some(rustls::PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow!("私钥文件 {} 中没有PEM格式的私钥", path))
+}
+
+/// 以HTTPS提供服务
+///
+/// # 参数
+/// * `listener` - 监听器
+/// * `rebinds` - 替换监听器
+/// * `app` - 应用路由
+/// * `tls` - TLS配置
+/// * `shutdown` - 关闭信号
+pub async fn serve_tls(
+    listener: Listener,
+    rebinds: mpsc::Receiver<Listener>,
+    app: Router,
+    tls: Arc<rustls::ServerConfig>,
+    shutdown: impl Future<Output = ()>,
+) -> Result<()> {
+    let acceptor = TlsAcceptor::from(tls);
+    serve_with(listener, rebinds, app, shutdown, move |connection| {
+        let acceptor = acceptor.clone();
+        async move {
+            tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(connection)).await
+                .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "TLS握手超时"))?
+        }
+    })
+    .await
+}
+
+/// 创建HTTP到HTTPS的重定向路由（308，保留路径和查询参数）
+///
+/// # 参数
+/// * `https_port` - HTTPS端口
+pub fn redirect_router(https_port: u16) -> Router {
+    Router::new().fallback(move |uri: Uri, headers: HeaderMap| async move {
+        match headers.get(HOST).and_then(|v| v.to_str().ok()) {
+            Some(host) => Redirect::permanent(&https_url(host, https_port, &uri)).into_response(),
+            None => (StatusCode::BAD_REQUEST, "缺少Host请求头").into_response(),
+        }
+    })
+}
+
+/// 把请求地址改写为HTTPS地址
+///
+/// 去掉 `Host` 中的HTTP端口，HTTPS端口不是443时带上该端口
+///
+/// # 参数
+/// * `host` - `Host` 请求头
+/// * `https_port` - HTTPS端口
+/// * `uri` - 请求地址
+pub fn https_url(host: &str, https_port: u16, uri: &Uri) -> String {
+    // IPv6地址（如 `[::1]:80`）中的冒号不是端口分隔符
+    let hostname = match host.rsplit_once(':') {
+        Some((name, port)) if !port.contains(']') => name,
+        _ => host,
+    };
+    let path = uri.path_and_query().map_or("/", |p| p.as_str());
+    match https_port {
+        443 => format!("https://{}{}", hostname, path),
+        port => format!("https://{}:{}{}", hostname, port, path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_https_url() {
+        let uri: Uri = "/api/v1/health?verbose=1".parse().unwrap();
+        assert_eq!(https_url("scan.example.com", 443, &uri), "https://scan.example.com/api/v1/health?verbose=1");
+        assert_eq!(https_url("scan.example.com:8080", 8443, &uri), "https://scan.example.com:8443/api/v1/health?verbose=1");
+        assert_eq!(https_url("[::1]:80", 443, &Uri::from_static("/")), "https://[::1]/");
+        assert_eq!(https_url("[::1]", 3000, &Uri::from_static("/")), "https://[::1]:3000/");
+    }
+}
@@ -0,0 +1,138 @@
+use axum::{
+    Router,
+    routing::{get, post},
+    extract::State,
+    response::Json,
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::api::ApiResponse;
+use super::auth::AuthUser;
+use super::AppState;
+use crate::users::{AuthService, UserProfile, UserStore};
+
+/// 注册/登录请求
+#[derive(Debug, Deserialize)]
+pub struct Credentials {
+    /// 用户名
+    pub username: String,
+    /// 密码
+    pub password: String,
+}
+
+/// 登录结果
+#[derive(Debug, Serialize)]
+pub struct Session {
+    /// JWT令牌（放入 `Authorization: Bearer` 请求头）
+    pub token: String,
+    /// 用户资料
+    pub user: UserProfile,
+}
+
+/// 创建用户路由
+/// 
+/// # 返回
+/// * `Router<AppState>` - 注册、登录及个人数据路由
+pub fn create_user_routes() -> Router<AppState> {
+    Router::new()
+        .route("/auth/register", post(register))
+        .route("/auth/login", post(login))
+        .route("/me", get(me))
+        .route("/me/watchlist", get(get_watchlist).put(set_watchlist))
+        .route("/me/preferences", get(get_preferences).put(set_preferences))
+}
+
+/// 注册新用户
+async fn register(
+    State(users): State<Arc<UserStore>>,
+    State(auth): State<Arc<AuthService>>,
+    Json(credentials): Json<Credentials>,
+) -> Result<Json<ApiResponse<Session>>, StatusCode> {
+    if !auth.allow_registration() {
+        return Ok(Json(ApiResponse::error("注册已关闭".to_string())));
+    }
+    
+    let result = match users.register(&credentials.username, &credentials.password).await {
+        Ok(user) => auth.issue(&user).map(|token| Session { token, user: UserProfile::from(&user) }),
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(session) => Ok(Json(ApiResponse::success(session))),
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 用户登录
+async fn login(
+    State(users): State<Arc<UserStore>>,
+    State(auth): State<Arc<AuthService>>,
+    Json(credentials): Json<Credentials>,
+) -> Result<Json<ApiResponse<Session>>, StatusCode> {
+    let result = match users.authenticate(&credentials.username, &credentials.password).await {
+        Ok(user) => auth.issue(&user).map(|token| Session { token, user: UserProfile::from(&user) }),
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(session) => Ok(Json(ApiResponse::success(session))),
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 获取当前用户资料
+async fn me(
+    user: AuthUser,
+    State(users): State<Arc<UserStore>>,
+) -> Result<Json<ApiResponse<UserProfile>>, StatusCode> {
+    let profile = users.get(user.id).await
+        .map(|u| UserProfile::from(&u))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    Ok(Json(ApiResponse::success(profile)))
+}
+
+/// 获取当前用户的关注列表
+async fn get_watchlist(
+    user: AuthUser,
+    State(users): State<Arc<UserStore>>,
+) -> Result<Json<ApiResponse<Vec<String>>>, StatusCode> {
+    let watchlist = users.get(user.id).await
+        .map(|u| u.watchlist)
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    Ok(Json(ApiResponse::success(watchlist)))
+}
+
+/// 替换当前用户的关注列表
+async fn set_watchlist(
+    user: AuthUser,
+    State(users): State<Arc<UserStore>>,
+    Json(coins): Json<Vec<String>>,
+) -> Result<Json<ApiResponse<Vec<String>>>, StatusCode> {
+    match users.set_watchlist(user.id, coins).await {
+        Ok(user) => Ok(Json(ApiResponse::success(user.watchlist))),
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 获取当前用户的仪表板偏好
+async fn get_preferences(
+    user: AuthUser,
+    State(users): State<Arc<UserStore>>,
+) -> Result<Json<ApiResponse<serde_json::Map<String, serde_json::Value>>>, StatusCode> {
+    let preferences = users.get(user.id).await
+        .map(|u| u.preferences)
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    Ok(Json(ApiResponse::success(preferences)))
+}
+
+/// 替换当前用户的仪表板偏好
+async fn set_preferences(
+    user: AuthUser,
+    State(users): State<Arc<UserStore>>,
+    Json(preferences): Json<serde_json::Map<String, serde_json::Value>>,
+) -> Result<Json<ApiResponse<serde_json::Map<String, serde_json::Value>>>, StatusCode> {
+    match users.set_preferences(user.id, preferences).await {
+        Ok(user) => Ok(Json(ApiResponse::success(user.preferences))),
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
@@ -0,0 +1,124 @@
+use axum::{
+    Router,
+    routing::get,
+    extract::Request,
+    middleware::{self, Next},
+    response::{Json, Response},
+    http::HeaderValue,
+};
+use serde::Serialize;
+
+use super::api::{create_api_routes, ApiResponse};
+use super::AppState;
+
+/// 响应中标明API版本的头
+pub const API_VERSION_HEADER: &str = "x-api-version";
+
+/// REST API 版本
+///
+/// 兼容性约定：同一版本内只做向后兼容的修改（新增接口、新增字段、新增可选参数）；
+/// 删除或重命名字段、修改字段类型、调整 `ApiResponse` 外层结构等不兼容修改只能出现在新版本中。
+/// 新版本发布后旧版本至少继续提供一个发布周期，停用前通过 `Deprecation` 和 `Sunset` 响应头提前告知
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiVersion {
+    /// 第一版
+    V1,
+}
+
+/// 版本信息
+#[derive(Debug, Serialize)]
+pub struct ApiVersionInfo {
+    /// 版本
+    pub version: ApiVersion,
+    /// 路径前缀
+    pub prefix: String,
+    /// 是否为未带版本号的 `/api` 指向的版本
+    pub legacy_alias: bool,
+    /// 是否已计划停用
+    pub deprecated: bool,
+    /// 停用日期（HTTP日期格式，未计划停用时为None）
+    pub sunset: Option<&'static str>,
+}
+
+impl ApiVersion {
+    /// 同时提供的全部版本
+    pub const ALL: &'static [ApiVersion] = &[ApiVersion::V1];
+
+    /// 未带版本号的 `/api` 固定指向的版本（保持现有看板可用，不随新版本发布而变化）
+    pub const LEGACY: ApiVersion = ApiVersion::V1;
+
+    /// 版本标识（如 `v1`）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
+        }
+    }
+
+    /// 计划停用日期（HTTP日期格式）
+    pub fn sunset(&self) -> Option<&'static str> {
+        match self {
+            ApiVersion::V1 => None,
+        }
+    }
+
+    /// 该版本的路由（新增版本时在此返回其路由，可复用旧版本的处理器）
+    fn routes(&self, state: AppState) -> Router<AppState> {
+        match self {
+            ApiVersion::V1 => create_api_routes(state),
+        }
+    }
+
+    /// 版本信息
+    pub fn info(&self) -> ApiVersionInfo {
+        ApiVersionInfo {
+            version: *self,
+            prefix: format!("/api/{}", self.as_str()),
+            legacy_alias: *self == Self::LEGACY,
+            deprecated: self.sunset().is_some(),
+            sunset: self.sunset(),
+        }
+    }
+}
+
+/// 为响应加上版本头，已计划停用的版本同时加上 `Deprecation` 和 `Sunset`
+async fn version_headers(version: ApiVersion, request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(API_VERSION_HEADER, HeaderValue::from_static(version.as_str()));
+    if let Some(sunset) = version.sunset() {
+        headers.insert("deprecation", HeaderValue::from_static("true"));
+        headers.insert("sunset", HeaderValue::from_static(sunset));
+    }
+    response
+}
+
+/// 创建带版本的API路由
+///
+/// 每个版本挂载在 `/api/<版本>` 下并行提供，未带版本号的 `/api` 为 `ApiVersion::LEGACY` 的别名；
+/// `GET /api/versions` 列出全部版本
+///
+/// # 参数
+/// * `state` - 应用共享状态
+///
+/// # 返回
+/// * `Router<AppState>` - 包含完整路径的API路由
+pub fn create_versioned_api_routes(state: AppState) -> Router<AppState> {
+    let versioned = |version: ApiVersion| {
+        version.routes(state.clone())
+            .layer(middleware::from_fn(move |request: Request, next: Next| version_headers(version, request, next)))
+    };
+
+    let mut router = Router::new()
+        // 版本列表
+        .route("/api/versions", get(list_versions));
+    for version in ApiVersion::ALL {
+        router = router.nest(&format!("/api/{}", version.as_str()), versioned(*version));
+    }
+    router.nest("/api", versioned(ApiVersion::LEGACY))
+}
+
+/// 列出全部API版本
+async fn list_versions() -> Json<ApiResponse<Vec<ApiVersionInfo>>> {
+    Json(ApiResponse::success(ApiVersion::ALL.iter().map(ApiVersion::info).collect()))
+}
@@ -0,0 +1,95 @@
+use axum::{
+    Router,
+    routing::get,
+    extract::{Path, State},
+    response::Json,
+    http::StatusCode,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::api::ApiResponse;
+use super::auth::{audit_entry, user_id, AuthUser, MaybeAuthUser};
+use super::AppState;
+use crate::alerts::{PriceWatch, PriceWatchInput, WatchStore};
+use crate::audit::AuditLog;
+use crate::users::AuthService;
+
+/// 创建价格目标监控路由
+/// 
+/// 与告警规则相同，登录用户只能看到全局监控和自己的监控；创建和删除监控需要登录，全局监控只有管理员可以删除
+/// 
+/// # 返回
+/// * `Router<AppState>` - 价格目标监控路由
+pub fn create_watch_routes() -> Router<AppState> {
+    Router::new()
+        // 监控列表 / 创建监控
+        .route("/watches", get(list_watches).post(create_watch))
+        // 查看 / 删除监控
+        .route("/watches/:id", get(get_watch).delete(delete_watch))
+}
+
+/// 获取所有价格目标监控
+async fn list_watches(
+    MaybeAuthUser(user): MaybeAuthUser,
+    State(watches): State<Arc<WatchStore>>,
+) -> Json<ApiResponse<Vec<PriceWatch>>> {
+    let owner = user_id(&user);
+    let visible = watches.list().await
+        .into_iter()
+        .filter(|w| w.is_visible_to(owner))
+        .collect();
+    Json(ApiResponse::success(visible))
+}
+
+/// 获取单个价格目标监控
+async fn get_watch(
+    MaybeAuthUser(user): MaybeAuthUser,
+    State(watches): State<Arc<WatchStore>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<PriceWatch>>, StatusCode> {
+    match watches.get(id).await {
+        Some(watch) if watch.is_visible_to(user_id(&user)) => Ok(Json(ApiResponse::success(watch))),
+        _ => Ok(Json(ApiResponse::error(format!("监控 {} 不存在", id)))),
+    }
+}
+
+/// 创建价格目标监控
+async fn create_watch(
+    user: AuthUser,
+    State(watches): State<Arc<WatchStore>>,
+    State(auth): State<Arc<AuthService>>,
+    State(audit): State<Arc<AuditLog>>,
+    Json(input): Json<PriceWatchInput>,
+) -> Result<Json<ApiResponse<PriceWatch>>, StatusCode> {
+    let owner = (!user.is_admin(&auth)).then_some(user.id);
+    match watches.create(owner, input).await {
+        Ok(watch) => {
+            audit.record(audit_entry(&Some(user), "watch.create").target(watch.id).payload(&watch)).await;
+            Ok(Json(ApiResponse::success(watch)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// 删除价格目标监控
+async fn delete_watch(
+    user: AuthUser,
+    State(watches): State<Arc<WatchStore>>,
+    State(auth): State<Arc<AuthService>>,
+    State(audit): State<Arc<AuditLog>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<PriceWatch>>, StatusCode> {
+    match watches.get(id).await {
+        Some(watch) if user.can_modify(watch.owner, &auth) => {}
+        Some(watch) if watch.is_visible_to(Some(user.id)) => return Err(StatusCode::FORBIDDEN),
+        _ => return Ok(Json(ApiResponse::error(format!("监控 {} 不存在", id)))),
+    }
+    match watches.delete(id).await {
+        Ok(watch) => {
+            audit.record(audit_entry(&Some(user), "watch.delete").target(watch.id).payload(&watch)).await;
+            Ok(Json(ApiResponse::success(watch)))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
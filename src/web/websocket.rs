@@ -1,76 +1,351 @@
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        Query, State,
     },
-    response::Response,
+    http::{header::{AUTHORIZATION, HOST, ORIGIN}, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
 };
 use futures_util::{SinkExt, StreamExt}; // 添加必要的trait导入
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
 use tokio::time::{interval, Duration};
-use tracing::{info, warn, error};
+use tracing::{debug, info, warn, error};
 use serde_json;
 
+use super::auth::API_KEY_HEADER;
 use super::cache::DataCache;
+use super::ingest_api::token_matches;
+use crate::config::{OverflowPolicy, WebSocketConfig};
+use crate::tenants::TenantRegistry;
+use crate::users::AuthService;
+
+/// WebSocket访问控制
+///
+/// 校验来源页面（`Origin` 请求头）和访问令牌，未配置时允许所有连接
+pub struct WebSocketGuard {
+    /// 是否要求认证
+    require_auth: bool,
+    /// 专用访问令牌
+    tokens: Vec<String>,
+    /// 允许的来源页面（已规范化）
+    allowed_origins: Vec<String>,
+}
+
+impl WebSocketGuard {
+    /// 根据配置创建访问控制
+    pub fn from_config(config: &WebSocketConfig) -> Self {
+        Self {
+            require_auth: config.require_auth,
+            tokens: config.tokens.iter()
+                .filter(|t| !t.is_empty())
+                .map(|t| t.expose().to_string())
+                .collect(),
+            allowed_origins: config.allowed_origins.iter()
+                .map(|o| normalize_origin(o))
+                .filter(|o| !o.is_empty())
+                .collect(),
+        }
+    }
+
+    /// 校验来源页面
+    ///
+    /// 未配置允许列表、请求不带 `Origin`（非浏览器客户端）或与服务同源时通过
+    ///
+    /// # 参数
+    /// * `origin` - `Origin` 请求头
+    /// * `host` - `Host` 请求头
+    pub fn origin_allowed(&self, origin: Option<&str>, host: Option<&str>) -> bool {
+        let Some(origin) = origin.map(normalize_origin) else {
+            return true;
+        };
+        let same_origin = host.is_some_and(|host| origin.split_once("://").is_some_and(|(_, authority)| authority == host.to_lowercase()));
+        self.allowed_origins.is_empty() || same_origin || self.allowed_origins.contains(&origin)
+    }
+
+    /// 校验访问令牌：专用令牌、登录会话令牌或租户API密钥之一有效即可，不要求认证时总是通过
+    ///
+    /// # 参数
+    /// * `token` - 客户端提供的令牌
+    /// * `auth` - 会话令牌服务
+    /// * `tenants` - 租户注册表
+    pub async fn authorize(&self, token: Option<&str>, auth: &AuthService, tenants: &TenantRegistry) -> bool {
+        if !self.require_auth {
+            return true;
+        }
+        let Some(token) = token.map(str::trim).filter(|t| !t.is_empty()) else {
+            return false;
+        };
+        // 逐个比较全部令牌，耗时与匹配的位置无关
+        self.tokens.iter().fold(false, |matched, t| token_matches(token, t) | matched)
+            || auth.verify(token).is_ok()
+            || tenants.authenticate(token).await.is_some()
+    }
+}
+
+/// WebSocket推送统计
+#[derive(Debug, Clone, Serialize)]
+pub struct WebSocketStats {
+    /// 当前连接数
+    pub active_connections: u64,
+    /// 已发送的消息数
+    pub messages_sent: u64,
+    /// 因客户端接收过慢而丢弃的消息数
+    pub messages_dropped: u64,
+    /// 因发送队列溢出而断开的连接数
+    pub overflow_disconnects: u64,
+    /// 每个连接的发送队列长度
+    pub send_queue_capacity: usize,
+    /// 发送队列溢出策略
+    pub overflow_policy: OverflowPolicy,
+}
+
+/// WebSocket连接的发送队列配置与推送统计
+///
+/// 每个连接有独立的有界发送队列，推送循环只向队列追加消息，不会被接收过慢的客户端阻塞
+pub struct WebSocketHub {
+    /// 每个连接的发送队列长度
+    capacity: usize,
+    /// 队列溢出策略
+    policy: OverflowPolicy,
+    /// 当前连接数
+    active: AtomicU64,
+    /// 已发送的消息数
+    sent: AtomicU64,
+    /// 丢弃的消息数
+    dropped: AtomicU64,
+    /// 因溢出断开的连接数
+    disconnects: AtomicU64,
+}
+
+impl WebSocketHub {
+    /// 根据配置创建
+    pub fn from_config(config: &WebSocketConfig) -> Self {
+        Self {
+            capacity: config.send_queue_capacity.max(1),
+            policy: config.overflow_policy,
+            active: AtomicU64::new(0),
+            sent: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            disconnects: AtomicU64::new(0),
+        }
+    }
+
+    /// 推送统计
+    pub fn stats(&self) -> WebSocketStats {
+        WebSocketStats {
+            active_connections: self.active.load(Ordering::Relaxed),
+            messages_sent: self.sent.load(Ordering::Relaxed),
+            messages_dropped: self.dropped.load(Ordering::Relaxed),
+            overflow_disconnects: self.disconnects.load(Ordering::Relaxed),
+            send_queue_capacity: self.capacity,
+            overflow_policy: self.policy,
+        }
+    }
+}
+
+/// 消息入队结果
+#[derive(Debug, PartialEq, Eq)]
+enum Enqueued {
+    /// 已入队
+    Queued,
+    /// 队列已满，丢弃了最早的消息后入队
+    DroppedOldest,
+    /// 队列已满且策略为断开连接，队列已关闭
+    Overflow,
+}
+
+/// 单个连接的有界发送队列
+struct SendQueue {
+    /// 待发送消息和是否已关闭
+    state: Mutex<(VecDeque<Message>, bool)>,
+    /// 有新消息或队列关闭时唤醒发送任务
+    notify: Notify,
+    /// 队列长度
+    capacity: usize,
+    /// 溢出策略
+    policy: OverflowPolicy,
+}
+
+impl SendQueue {
+    fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self {
+            state: Mutex::new((VecDeque::with_capacity(capacity), false)),
+            notify: Notify::new(),
+            capacity,
+            policy,
+        }
+    }
+
+    /// 追加消息，不等待发送
+    fn push(&self, message: Message) -> Enqueued {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (queue, closed) = &mut *state;
+        let result = if queue.len() < self.capacity {
+            Enqueued::Queued
+        } else if self.policy == OverflowPolicy::DropOldest {
+            queue.pop_front();
+            Enqueued::DroppedOldest
+        } else {
+            queue.clear();
+            *closed = true;
+            self.notify.notify_one();
+            return Enqueued::Overflow;
+        };
+        queue.push_back(message);
+        self.notify.notify_one();
+        result
+    }
+
+    /// 取出下一条消息，队列关闭后返回None
+    async fn pop(&self) -> Option<Message> {
+        loop {
+            {
+                let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(message) = state.0.pop_front() {
+                    return Some(message);
+                }
+                if state.1 {
+                    return None;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+}
+
+/// 规范化来源页面（小写并去掉末尾的 `/`）
+fn normalize_origin(origin: &str) -> String {
+    origin.trim().trim_end_matches('/').to_lowercase()
+}
+
+/// WebSocket连接查询参数
+#[derive(Debug, Deserialize)]
+pub struct WebSocketQuery {
+    /// 访问令牌（浏览器无法为WebSocket设置请求头，通过查询参数传入）
+    pub token: Option<String>,
+}
 
 /// WebSocket连接处理器
 /// 
+/// 连接前校验来源页面（不允许时返回403）和访问令牌（缺失或无效时返回401），
+/// 令牌可通过 `?token=`、`Authorization: Bearer` 或 `X-Api-Key` 传入
+/// 
 /// # 参数
 /// * `ws` - WebSocket升级请求
 /// * `cache` - 数据缓存
+/// * `guard` - WebSocket访问控制
+/// * `auth` - 会话令牌服务
+/// * `tenants` - 租户注册表
+/// * `hub` - 发送队列配置与推送统计
+/// * `query` - 查询参数
+/// * `headers` - 请求头
 /// 
 /// # 返回
 /// * `Response` - WebSocket响应
+#[allow(clippy::too_many_arguments)]
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
     State(cache): State<Arc<DataCache>>,
+    State(guard): State<Arc<WebSocketGuard>>,
+    State(auth): State<Arc<AuthService>>,
+    State(tenants): State<Arc<TenantRegistry>>,
+    State(hub): State<Arc<WebSocketHub>>,
+    Query(query): Query<WebSocketQuery>,
+    headers: HeaderMap,
 ) -> Response {
     info!("🔌 新的WebSocket连接请求");
-    ws.on_upgrade(move |socket| handle_socket(socket, cache))
+
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let origin = header(ORIGIN.as_str());
+    if !guard.origin_allowed(origin, header(HOST.as_str())) {
+        warn!("🚫 拒绝来自 {} 的WebSocket连接", origin.unwrap_or_default());
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let token = query.token.as_deref()
+        .or_else(|| header(AUTHORIZATION.as_str()).and_then(|v| v.strip_prefix("Bearer ")))
+        .or_else(|| header(API_KEY_HEADER));
+    if !guard.authorize(token, &auth, &tenants).await {
+        warn!("🚫 拒绝未认证的WebSocket连接");
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    ws.on_upgrade(move |socket| handle_socket(socket, cache, hub))
 }
 
 /// 处理WebSocket连接
 /// 
+/// 推送任务把消息放入连接的发送队列，由单独的发送任务写入连接；
+/// 客户端接收过慢时按溢出策略丢弃最早的消息或断开连接
+/// 
 /// # 参数
 /// * `socket` - WebSocket连接
 /// * `cache` - 数据缓存
-async fn handle_socket(socket: WebSocket, cache: Arc<DataCache>) {
+/// * `hub` - 发送队列配置与推送统计
+async fn handle_socket(socket: WebSocket, cache: Arc<DataCache>, hub: Arc<WebSocketHub>) {
     info!("✅ WebSocket连接已建立");
+    hub.active.fetch_add(1, Ordering::Relaxed);
     
     let (mut sender, mut receiver) = socket.split();
+    let queue = Arc::new(SendQueue::new(hub.capacity, hub.policy));
+    
+    // 发送任务：按顺序写入队列中的消息
+    let send_queue = queue.clone();
+    let send_hub = hub.clone();
+    let mut send_task = tokio::spawn(async move {
+        while let Some(message) = send_queue.pop().await {
+            if let Err(e) = sender.send(message).await {
+                error!("❌ 发送WebSocket消息失败: {}", e);
+                return;
+            }
+            send_hub.sent.fetch_add(1, Ordering::Relaxed);
+        }
+        // 队列因溢出关闭，尽力通知客户端后断开
+        let _ = tokio::time::timeout(Duration::from_secs(1), sender.send(Message::Close(None))).await;
+    });
     
     // 启动数据推送任务
     let cache_clone = cache.clone();
-    let push_task = tokio::spawn(async move {
+    let push_hub = hub.clone();
+    let mut push_task = tokio::spawn(async move {
         let mut interval = interval(Duration::from_secs(30)); // 每30秒推送一次数据
         
         loop {
             interval.tick().await;
             
-            // 获取所有市场数据
-            let market_data = cache_clone.get_all_market_data();
-            
-            if !market_data.is_empty() {
-                // 序列化数据
-                match serde_json::to_string(&market_data) {
-                    Ok(json_data) => {
-                        // 发送数据
-                        if let Err(e) = sender.send(Message::Text(json_data)).await {
-                            error!("❌ 发送WebSocket消息失败: {}", e);
+            // 获取所有市场数据（所有连接共享同一份预序列化的JSON）
+            match cache_clone.get_all_market_data_json().await {
+                Ok(json_data) if json_data.as_ref() == b"[]" => {}
+                Ok(json_data) => {
+                    // WebSocket文本帧需要独立的字符串，只复制已序列化的字节
+                    let text = String::from_utf8_lossy(&json_data).into_owned();
+                    match queue.push(Message::Text(text)) {
+                        Enqueued::Queued => debug!("📤 已加入 {} 字节的市场数据", json_data.len()),
+                        Enqueued::DroppedOldest => {
+                            push_hub.dropped.fetch_add(1, Ordering::Relaxed);
+                            debug!("⚠️ WebSocket客户端接收过慢，丢弃最早的待发送消息");
+                        }
+                        Enqueued::Overflow => {
+                            push_hub.dropped.fetch_add(1, Ordering::Relaxed);
+                            push_hub.disconnects.fetch_add(1, Ordering::Relaxed);
+                            warn!("⚠️ WebSocket客户端接收过慢，发送队列已满，断开连接");
                             break;
                         }
-                        info!("📤 已推送 {} 个币种的市场数据", market_data.len());
-                    }
-                    Err(e) => {
-                        error!("❌ 序列化市场数据失败: {}", e);
                     }
                 }
+                Err(e) => {
+                    error!("❌ 序列化市场数据失败: {}", e);
+                }
             }
         }
     });
     
     // 处理客户端消息
-    let message_task = tokio::spawn(async move {
+    let mut message_task = tokio::spawn(async move {
         while let Some(msg) = receiver.next().await {
             match msg {
                 Ok(Message::Text(text)) => {
@@ -85,8 +360,8 @@ async fn handle_socket(socket: WebSocket, cache: Arc<DataCache>) {
                         }
                         "get_stats" => {
                             // 发送缓存统计信息
-                            let stats = cache.get_stats();
-                            if let Ok(stats_json) = serde_json::to_string(&stats) {
+                            let stats = cache.get_stats().await;
+                            if let Ok(_stats_json) = serde_json::to_string(&stats) {
                                 info!("📊 发送缓存统计信息");
                             }
                         }
@@ -113,13 +388,69 @@ async fn handle_socket(socket: WebSocket, cache: Arc<DataCache>) {
     
     // 等待任何一个任务完成
     tokio::select! {
-        _ = push_task => {
+        _ = &mut send_task => {
+            info!("📤 发送任务结束");
+        }
+        _ = &mut push_task => {
             info!("📤 数据推送任务结束");
+            // 队列溢出断开时等待发送任务发出关闭帧
+            let _ = (&mut send_task).await;
         }
-        _ = message_task => {
+        _ = &mut message_task => {
             info!("📨 消息处理任务结束");
         }
     }
+    send_task.abort();
+    push_task.abort();
+    message_task.abort();
     
+    hub.active.fetch_sub(1, Ordering::Relaxed);
     info!("🔌 WebSocket连接已断开");
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(queue: &SendQueue) -> Option<String> {
+        let mut state = queue.state.lock().unwrap();
+        match state.0.pop_front() {
+            Some(Message::Text(text)) => Some(text),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_send_queue_drop_oldest() {
+        let queue = SendQueue::new(2, OverflowPolicy::DropOldest);
+        assert_eq!(queue.push(Message::Text("1".to_string())), Enqueued::Queued);
+        assert_eq!(queue.push(Message::Text("2".to_string())), Enqueued::Queued);
+        assert_eq!(queue.push(Message::Text("3".to_string())), Enqueued::DroppedOldest);
+        assert_eq!(text(&queue).as_deref(), Some("2"));
+        assert_eq!(text(&queue).as_deref(), Some("3"));
+    }
+
+    #[tokio::test]
+    async fn test_send_queue_disconnect_on_overflow() {
+        let queue = SendQueue::new(1, OverflowPolicy::Disconnect);
+        assert_eq!(queue.push(Message::Text("1".to_string())), Enqueued::Queued);
+        assert_eq!(queue.push(Message::Text("2".to_string())), Enqueued::Overflow);
+        // 溢出后丢弃待发送消息，发送任务随之结束
+        assert!(queue.pop().await.is_none());
+    }
+
+    #[test]
+    fn test_origin_allowed() {
+        let open = WebSocketGuard::from_config(&WebSocketConfig::default());
+        assert!(open.origin_allowed(Some("https://evil.example"), Some("scan.example.com")));
+
+        let guard = WebSocketGuard::from_config(&WebSocketConfig {
+            allowed_origins: vec!["https://Dash.example.com/".to_string()],
+            ..WebSocketConfig::default()
+        });
+        assert!(guard.origin_allowed(Some("https://dash.example.com"), Some("scan.example.com")));
+        assert!(guard.origin_allowed(Some("http://scan.example.com:8080"), Some("scan.example.com:8080")));
+        assert!(guard.origin_allowed(None, Some("scan.example.com")));
+        assert!(!guard.origin_allowed(Some("https://evil.example"), Some("scan.example.com")));
+    }
+}